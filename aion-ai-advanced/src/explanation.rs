@@ -0,0 +1,221 @@
+//! Prediction Explanations for AION-CR
+//!
+//! Per-prediction feature attribution by occlusion: each input field, and
+//! each token of a multi-word text field, is removed in turn and the change
+//! in the model's score is its contribution. Works for any registered
+//! model with a `ScoringModel`, either registered as one or attached to it.
+//! All occluded inputs are scored in a single `score_batch` call.
+
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::{bail, Result};
+
+/// Most features attributed for one prediction; each adds an input to the scored batch
+pub const MAX_ATTRIBUTED_FEATURES: usize = 512;
+
+/// Contributors listed in `FeatureAttribution::top_contributors`
+const TOP_CONTRIBUTORS: usize = 5;
+
+/// A model that scores a JSON input, e.g. the probability of a compliance flag
+///
+/// Register it as an `Arc<dyn ScoringModel>` to make its predictions explainable.
+pub trait ScoringModel: Send + Sync {
+    fn score(&self, input: &Value) -> Result<f64>;
+
+    /// Score with the system's inference seed; models that sample should derive
+    /// their randomness from it so repeated predictions match
+    fn score_seeded(&self, input: &Value, seed: Option<u64>) -> Result<f64> {
+        let _ = seed;
+        self.score(input)
+    }
+
+    /// Score several inputs, in order; override to evaluate them in one model call
+    fn score_batch(&self, inputs: &[Value], seed: Option<u64>) -> Result<Vec<f64>> {
+        inputs.iter().map(|input| self.score_seeded(input, seed)).collect()
+    }
+}
+
+/// Shared handle under which scoring models are registered
+pub type SharedScoringModel = Arc<dyn ScoringModel>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttributionMethod {
+    /// Leave-one-out occlusion against an all-null baseline
+    Occlusion,
+}
+
+/// Contribution of one input field or token to a prediction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureContribution {
+    /// JSON pointer to the field
+    pub feature: String,
+    /// Word position within a text field, for token-level attribution
+    pub token_index: Option<usize>,
+    pub value: String,
+    /// Score change the feature causes; positive raises the prediction
+    pub contribution: f64,
+}
+
+/// Explanation of a single prediction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureAttribution {
+    pub model_id: String,
+    pub method: AttributionMethod,
+    pub prediction: f64,
+    /// Score with every feature removed
+    pub baseline: f64,
+    /// Every feature in input order
+    pub attributions: Vec<FeatureContribution>,
+    /// Largest contributions by magnitude
+    pub top_contributors: Vec<FeatureContribution>,
+    pub rationale: String,
+}
+
+struct Feature {
+    pointer: String,
+    token_index: Option<usize>,
+    value: String,
+}
+
+/// Attribute a model's prediction for `input` to its fields and tokens
+///
+/// Scoring is synchronous; call it from a blocking task inside async code.
+pub fn explain_with_occlusion(
+    model_id: &str,
+    model: &dyn ScoringModel,
+    input: &Value,
+    seed: Option<u64>,
+) -> Result<FeatureAttribution> {
+    let mut features = Vec::new();
+    collect_features(input, String::new(), &mut features);
+    if features.len() > MAX_ATTRIBUTED_FEATURES {
+        bail!(
+            "Input has {} features; at most {} can be attributed in one explanation",
+            features.len(),
+            MAX_ATTRIBUTED_FEATURES
+        );
+    }
+
+    // The prediction, the baseline and every occlusion go to the model as one batch
+    let mut inputs = Vec::with_capacity(features.len() + 2);
+    inputs.push(input.clone());
+    inputs.push(null_leaves(input));
+    inputs.extend(features.iter().map(|feature| occlude(input, feature)));
+    let scores = model.score_batch(&inputs, seed)?;
+    if scores.len() != inputs.len() {
+        bail!("Model {} returned {} scores for {} inputs", model_id, scores.len(), inputs.len());
+    }
+    let (prediction, baseline) = (scores[0], scores[1]);
+
+    let attributions: Vec<FeatureContribution> = features
+        .into_iter()
+        .zip(&scores[2..])
+        .map(|(feature, occluded)| FeatureContribution {
+            feature: feature.pointer,
+            token_index: feature.token_index,
+            value: feature.value,
+            contribution: prediction - occluded,
+        })
+        .collect();
+
+    let mut top_contributors: Vec<FeatureContribution> =
+        attributions.iter().filter(|attribution| attribution.contribution != 0.0).cloned().collect();
+    top_contributors.sort_by(|a, b| b.contribution.abs().total_cmp(&a.contribution.abs()));
+    top_contributors.truncate(TOP_CONTRIBUTORS);
+
+    let rationale = rationale(prediction, baseline, &top_contributors);
+    Ok(FeatureAttribution {
+        model_id: model_id.to_string(),
+        method: AttributionMethod::Occlusion,
+        prediction,
+        baseline,
+        attributions,
+        top_contributors,
+        rationale,
+    })
+}
+
+fn collect_features(value: &Value, pointer: String, features: &mut Vec<Feature>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                let escaped = key.replace('~', "~0").replace('/', "~1");
+                collect_features(field, format!("{}/{}", pointer, escaped), features);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                collect_features(item, format!("{}/{}", pointer, index), features);
+            }
+        }
+        Value::String(text) if text.split_whitespace().nth(1).is_some() => {
+            for (token_index, token) in text.split_whitespace().enumerate() {
+                features.push(Feature {
+                    pointer: pointer.clone(),
+                    token_index: Some(token_index),
+                    value: token.to_string(),
+                });
+            }
+        }
+        Value::Null => {}
+        leaf => features.push(Feature {
+            pointer,
+            token_index: None,
+            value: match leaf {
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            },
+        }),
+    }
+}
+
+fn occlude(input: &Value, feature: &Feature) -> Value {
+    let mut occluded = input.clone();
+    if let Some(target) = occluded.pointer_mut(&feature.pointer) {
+        match (feature.token_index, &mut *target) {
+            (Some(token_index), Value::String(text)) => {
+                let remaining: Vec<&str> = text
+                    .split_whitespace()
+                    .enumerate()
+                    .filter(|(index, _)| *index != token_index)
+                    .map(|(_, token)| token)
+                    .collect();
+                *text = remaining.join(" ");
+            }
+            _ => *target = Value::Null,
+        }
+    }
+    occluded
+}
+
+/// The input with its structure kept and every leaf removed
+fn null_leaves(value: &Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(fields.iter().map(|(key, field)| (key.clone(), null_leaves(field))).collect()),
+        Value::Array(items) => Value::Array(items.iter().map(null_leaves).collect()),
+        _ => Value::Null,
+    }
+}
+
+fn rationale(prediction: f64, baseline: f64, top_contributors: &[FeatureContribution]) -> String {
+    let mut rationale = format!("Predicted score {:.3} against a baseline of {:.3} with no input.", prediction, baseline);
+    if top_contributors.is_empty() {
+        rationale.push_str(" No single input changes the prediction when removed.");
+        return rationale;
+    }
+
+    let describe = |contribution: &FeatureContribution| match contribution.token_index {
+        Some(index) => format!("\"{}\" (word {} of {}, {:+.3})", contribution.value, index + 1, contribution.feature, contribution.contribution),
+        None => format!("{} = \"{}\" ({:+.3})", contribution.feature, contribution.value, contribution.contribution),
+    };
+    let raising: Vec<String> = top_contributors.iter().filter(|c| c.contribution > 0.0).map(describe).collect();
+    let lowering: Vec<String> = top_contributors.iter().filter(|c| c.contribution < 0.0).map(describe).collect();
+    if !raising.is_empty() {
+        rationale.push_str(&format!(" Raised most by {}.", raising.join(", ")));
+    }
+    if !lowering.is_empty() {
+        rationale.push_str(&format!(" Lowered most by {}.", lowering.join(", ")));
+    }
+    rationale
+}
@@ -0,0 +1,204 @@
+//! Model Registry for AION-CR
+//!
+//! Tracks the models loaded by the AI system: what they are, how much
+//! memory they hold, and whether they are serving inference. Unloading
+//! drops the registry's reference so the model's memory can be freed.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+use tracing::{info, warn};
+
+use crate::{AIModelType, SharedScoringModel};
+
+/// Registry failures callers may want to handle
+#[derive(Debug, thiserror::Error)]
+pub enum ModelRegistryError {
+    #[error("model {model_id} is not loaded")]
+    NotFound { model_id: String },
+
+    #[error("model {model_id} is serving {in_flight} inference request(s) and cannot be unloaded")]
+    Busy { model_id: String, in_flight: usize },
+}
+
+/// A loaded model as reported by the registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredModel {
+    pub model_id: String,
+    pub name: String,
+    pub model_type: AIModelType,
+    pub memory_bytes: u64,
+    /// Requests using the model when this snapshot was taken
+    pub in_flight: usize,
+    pub registered_at: DateTime<Utc>,
+}
+
+/// Registry health summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRegistryHealth {
+    pub loaded_models: usize,
+    pub total_memory_bytes: u64,
+    pub models_in_use: usize,
+}
+
+struct ModelEntry {
+    info: RegisteredModel,
+    model: Arc<dyn Any + Send + Sync>,
+    /// Scores inputs for a model that is not itself a `SharedScoringModel`
+    scorer: Option<SharedScoringModel>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl ModelEntry {
+    fn snapshot(&self) -> RegisteredModel {
+        RegisteredModel {
+            in_flight: self.in_flight.load(Ordering::SeqCst),
+            ..self.info.clone()
+        }
+    }
+}
+
+/// Use of a loaded model; the model cannot be unloaded while a lease is held
+pub struct ModelLease {
+    pub info: RegisteredModel,
+    model: Arc<dyn Any + Send + Sync>,
+    scorer: Option<SharedScoringModel>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl ModelLease {
+    /// The model, if it was registered as an `M`
+    pub fn model<M: Any>(&self) -> Option<&M> {
+        self.model.downcast_ref()
+    }
+
+    /// Scorer attached to the model, or the model itself if it was registered as a `SharedScoringModel`
+    pub fn scorer(&self) -> Option<SharedScoringModel> {
+        self.scorer.clone().or_else(|| self.model::<SharedScoringModel>().cloned())
+    }
+}
+
+impl Drop for ModelLease {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Loaded models by id
+#[derive(Default)]
+pub struct ModelRegistry {
+    models: RwLock<HashMap<String, ModelEntry>>,
+}
+
+impl ModelRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a model under a new id
+    pub async fn register_model<M: Any + Send + Sync>(
+        &self,
+        name: &str,
+        model_type: AIModelType,
+        memory_bytes: u64,
+        model: M,
+    ) -> Result<RegisteredModel> {
+        let info = RegisteredModel {
+            model_id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            model_type,
+            memory_bytes,
+            in_flight: 0,
+            registered_at: Utc::now(),
+        };
+
+        info!("📦 Registered model {} ({}, {} bytes)", info.name, info.model_id, memory_bytes);
+        self.models.write().await.insert(
+            info.model_id.clone(),
+            ModelEntry {
+                info: info.clone(),
+                model: Arc::new(model),
+                scorer: None,
+                in_flight: Arc::new(AtomicUsize::new(0)),
+            },
+        );
+        Ok(info)
+    }
+
+    pub async fn get_model(&self, model_id: &str) -> Option<RegisteredModel> {
+        self.models.read().await.get(model_id).map(ModelEntry::snapshot)
+    }
+
+    /// Loaded models, oldest first
+    pub async fn list_models(&self) -> Vec<RegisteredModel> {
+        let mut models: Vec<RegisteredModel> = self.models.read().await.values().map(ModelEntry::snapshot).collect();
+        models.sort_by_key(|model| model.registered_at);
+        models
+    }
+
+    /// Make a loaded model of any type scorable, and so explainable, through `scorer`
+    ///
+    /// Leases taken before this call keep the scorer they were taken with.
+    pub async fn attach_scorer(&self, model_id: &str, scorer: SharedScoringModel) -> Result<()> {
+        let mut models = self.models.write().await;
+        let entry = models.get_mut(model_id).ok_or_else(|| ModelRegistryError::NotFound {
+            model_id: model_id.to_string(),
+        })?;
+        entry.scorer = Some(scorer);
+        info!("🎯 Attached a scorer to model {}", model_id);
+        Ok(())
+    }
+
+    /// Mark a model as serving a request until the lease is dropped
+    pub async fn acquire_model(&self, model_id: &str) -> Result<ModelLease> {
+        let models = self.models.read().await;
+        let entry = models.get(model_id).ok_or_else(|| ModelRegistryError::NotFound {
+            model_id: model_id.to_string(),
+        })?;
+        entry.in_flight.fetch_add(1, Ordering::SeqCst);
+
+        Ok(ModelLease {
+            info: entry.snapshot(),
+            model: entry.model.clone(),
+            scorer: entry.scorer.clone(),
+            in_flight: entry.in_flight.clone(),
+        })
+    }
+
+    /// Unload an idle model, failing with `ModelRegistryError::Busy` while it serves requests
+    pub async fn unload_model(&self, model_id: &str) -> Result<RegisteredModel> {
+        let mut models = self.models.write().await;
+        let entry = models.get(model_id).ok_or_else(|| ModelRegistryError::NotFound {
+            model_id: model_id.to_string(),
+        })?;
+
+        let in_flight = entry.in_flight.load(Ordering::SeqCst);
+        if in_flight > 0 {
+            warn!("⚠️ Refusing to unload model {} with {} request(s) in flight", model_id, in_flight);
+            return Err(ModelRegistryError::Busy {
+                model_id: model_id.to_string(),
+                in_flight,
+            }
+            .into());
+        }
+
+        let entry = models.remove(model_id).expect("entry checked above");
+        info!("🗑️ Unloaded model {} ({} bytes freed)", model_id, entry.info.memory_bytes);
+        Ok(entry.info)
+    }
+
+    pub async fn health_check(&self) -> Result<ModelRegistryHealth> {
+        let models = self.models.read().await;
+        Ok(ModelRegistryHealth {
+            loaded_models: models.len(),
+            total_memory_bytes: models.values().map(|entry| entry.info.memory_bytes).sum(),
+            models_in_use: models.values().filter(|entry| entry.in_flight.load(Ordering::SeqCst) > 0).count(),
+        })
+    }
+}
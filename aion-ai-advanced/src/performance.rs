@@ -0,0 +1,153 @@
+//! Performance Measurement for AION-CR
+//!
+//! Rolling window of measured inference and training runs, summarized into
+//! the latency, throughput, memory, accuracy and error figures reported by
+//! `AIPerformanceMetrics`. Failed runs count as errors but not toward
+//! latency or throughput.
+
+use std::collections::VecDeque;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Measured operation kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PerformanceOperation {
+    Inference,
+    Training,
+}
+
+/// One measured operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceSample {
+    pub operation: PerformanceOperation,
+    pub completed_at: DateTime<Utc>,
+    pub latency_ms: f64,
+    /// Resident memory of the process after the operation, when readable
+    pub memory_usage_mb: Option<f64>,
+    /// Accuracy reported by a training run
+    pub accuracy: Option<f64>,
+    /// The operation returned an error
+    #[serde(default)]
+    pub failed: bool,
+}
+
+/// Summary of the samples currently in the window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PerformanceSummary {
+    pub inference_count: usize,
+    pub training_count: usize,
+    pub mean_inference_latency_ms: Option<f64>,
+    pub throughput_ops_per_sec: f64,
+    pub memory_usage_mb: Option<f64>,
+    pub model_accuracy: Option<f64>,
+    pub error_count: usize,
+    /// Failed share of all operations in the window
+    pub error_rate: f64,
+}
+
+/// Samples from the last `window`, capped at `max_samples`
+#[derive(Debug, Clone)]
+pub struct PerformanceWindow {
+    pub window: Duration,
+    pub max_samples: usize,
+    samples: VecDeque<PerformanceSample>,
+}
+
+impl PerformanceWindow {
+    pub fn new(window: Duration, max_samples: usize) -> Self {
+        Self {
+            window,
+            max_samples,
+            samples: VecDeque::new(),
+        }
+    }
+
+    pub fn record(&mut self, sample: PerformanceSample) {
+        self.samples.push_back(sample);
+        while self.samples.len() > self.max_samples {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Drop samples older than the window and summarize the rest
+    pub fn summarize(&mut self, now: DateTime<Utc>) -> PerformanceSummary {
+        let cutoff = now - self.window;
+        while self.samples.front().is_some_and(|sample| sample.completed_at < cutoff) {
+            self.samples.pop_front();
+        }
+
+        let inference_latencies: Vec<f64> = self
+            .samples
+            .iter()
+            .filter(|sample| sample.operation == PerformanceOperation::Inference && !sample.failed)
+            .map(|sample| sample.latency_ms)
+            .collect();
+        let accuracies: Vec<f64> = self.samples.iter().filter_map(|sample| sample.accuracy).collect();
+        let error_count = self.samples.iter().filter(|sample| sample.failed).count();
+        let training_count = self
+            .samples
+            .iter()
+            .filter(|sample| sample.operation == PerformanceOperation::Training && !sample.failed)
+            .count();
+
+        // Throughput over the span actually covered, so a fresh window is not diluted
+        let throughput_ops_per_sec = match self.samples.front() {
+            Some(oldest) if !inference_latencies.is_empty() => {
+                let oldest_start = oldest.completed_at - Duration::microseconds((oldest.latency_ms * 1000.0) as i64);
+                let span_seconds = (now - oldest_start).num_milliseconds().max(1000) as f64 / 1000.0;
+                inference_latencies.len() as f64 / span_seconds
+            }
+            _ => 0.0,
+        };
+
+        PerformanceSummary {
+            inference_count: inference_latencies.len(),
+            training_count,
+            mean_inference_latency_ms: mean(&inference_latencies),
+            throughput_ops_per_sec,
+            memory_usage_mb: self.samples.iter().rev().find_map(|sample| sample.memory_usage_mb),
+            model_accuracy: mean(&accuracies),
+            error_count,
+            error_rate: if self.samples.is_empty() { 0.0 } else { error_count as f64 / self.samples.len() as f64 },
+        }
+    }
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+/// Resident memory of this process in MB; `None` where `/proc` is unavailable
+pub fn current_memory_usage_mb() -> Option<f64> {
+    current_resident_bytes().map(|bytes| bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Resident memory of this process in bytes; `None` where `/proc` is unavailable
+pub fn current_resident_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes: u64 = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
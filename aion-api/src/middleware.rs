@@ -1,21 +1,353 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Instant;
+
 use axum::{
-    http::{Request, StatusCode},
+    body::{Body, Bytes, HttpBody},
+    extract::State,
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use http_body_util::LengthLimitError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::Instrument;
 
-pub async fn logging_middleware<B>(
-    request: Request<B>,
-    next: Next<B>,
-) -> Result<Response, StatusCode> {
+/// Response header carrying the correlation id, read back by the CLI's error parser
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+/// Request headers whose value is reused as the correlation id, in order of preference
+const INCOMING_ID_HEADERS: &[&str] = &[CORRELATION_ID_HEADER, "x-request-id"];
+
+/// Longest client-supplied correlation id accepted; longer ones are replaced
+const MAX_CORRELATION_ID_LEN: usize = 128;
+
+/// Correlation id of the current request, available to handlers as an extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationId(pub String);
+
+/// Which JSON fields are masked before a body is logged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    /// Field names, matched at any depth ignoring case, `-` and `_`
+    pub fields: HashSet<String>,
+    pub replacement: String,
+}
+
+impl RedactionConfig {
+    fn redacts(&self, key: &str) -> bool {
+        let key = normalize_field(key);
+        self.fields.iter().any(|field| normalize_field(field) == key)
+    }
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        let fields = [
+            // Credentials
+            "password", "secret", "client_secret", "token", "access_token", "refresh_token", "api_key",
+            "authorization", "private_key", "ccc",
+            // Personal data
+            "ssn", "tax_id", "date_of_birth", "email", "phone", "address", "account_number",
+        ];
+        Self {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            replacement: "[REDACTED]".to_string(),
+        }
+    }
+}
+
+/// Access logging settings for [`logging_middleware`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessLogConfig {
+    /// Log JSON request and response bodies, after redaction; off by default
+    /// because the redaction list cannot catch every field carrying personal data
+    pub log_bodies: bool,
+    /// Bodies larger than this, or of unknown length, are not buffered or logged
+    pub max_body_bytes: usize,
+    pub redaction: RedactionConfig,
+}
+
+impl Default for AccessLogConfig {
+    fn default() -> Self {
+        Self {
+            log_bodies: false,
+            max_body_bytes: 16 * 1024,
+            redaction: RedactionConfig::default(),
+        }
+    }
+}
+
+/// Log method, path, status, latency and correlation id of every request
+///
+/// The correlation id is taken from the request's `x-correlation-id` or
+/// `x-request-id` header when present, otherwise generated, and returned in
+/// the `x-correlation-id` response header. Query strings are never logged,
+/// and bodies are logged only when they are JSON, with configured fields
+/// redacted.
+pub async fn logging_middleware(
+    State(config): State<Arc<AccessLogConfig>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let correlation_id = correlation_id(request.headers());
+    let span = tracing::info_span!(
+        "request",
+        correlation_id = %correlation_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+    log_exchange(config, correlation_id, request, next).instrument(span).await
+}
+
+async fn log_exchange(
+    config: Arc<AccessLogConfig>,
+    correlation_id: String,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
     let method = request.method().clone();
-    let uri = request.uri().clone();
+    let path = request.uri().path().to_string();
+
+    let mut response = match forward(&config, &correlation_id, request, next).await {
+        Ok(response) => response,
+        Err(status) => status.into_response(),
+    };
+    if let Ok(value) = HeaderValue::from_str(&correlation_id) {
+        response.headers_mut().insert(CORRELATION_ID_HEADER, value);
+    }
 
-    tracing::info!("Request: {} {}", method, uri);
+    let status = response.status().as_u16();
+    let latency_ms = started.elapsed().as_millis() as u64;
+    if response.status().is_server_error() {
+        tracing::error!(status, latency_ms, "{} {} failed", method, path);
+    } else if response.status().is_client_error() {
+        tracing::warn!(status, latency_ms, "{} {} rejected", method, path);
+    } else {
+        tracing::info!(status, latency_ms, "{} {} completed", method, path);
+    }
 
-    let response = next.run(request).await;
+    response
+}
+
+/// Run the inner service, logging bodies when enabled
+///
+/// A body that cannot be buffered is never passed on truncated: the request
+/// is rejected with 413 or 400 and an unreadable response becomes a 500.
+async fn forward(
+    config: &AccessLogConfig,
+    correlation_id: &str,
+    mut request: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if config.log_bodies {
+        let (parts, body) = request.into_parts();
+        let (body, logged) = capture_body(&parts.headers, body, config).await?;
+        if let Some(logged) = logged {
+            tracing::debug!(body = %logged, "Request body");
+        }
+        request = Request::from_parts(parts, body);
+    }
+    request.extensions_mut().insert(CorrelationId(correlation_id.to_string()));
 
-    tracing::info!("Response: {}", response.status());
+    let mut response = next.run(request).await;
 
+    if config.log_bodies {
+        let (parts, body) = response.into_parts();
+        let (body, logged) = capture_body(&parts.headers, body, config)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        if let Some(logged) = logged {
+            tracing::debug!(body = %logged, "Response body");
+        }
+        response = Response::from_parts(parts, body);
+    }
     Ok(response)
-}
\ No newline at end of file
+}
+
+/// Client-supplied correlation id if usable, otherwise a new one
+fn correlation_id(headers: &HeaderMap) -> String {
+    INCOMING_ID_HEADERS
+        .iter()
+        .filter_map(|name| headers.get(*name))
+        .filter_map(|value| value.to_str().ok())
+        .map(str::trim)
+        .find(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_CORRELATION_ID_LEN
+                && id.chars().all(|c| c.is_ascii_alphanumeric() || "-_.:".contains(c))
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Buffer a small JSON body and render it redacted, handing back an equivalent body
+///
+/// Fails with 413 when the body outgrows its size hint and 400 when it cannot be read.
+async fn capture_body(
+    headers: &HeaderMap,
+    body: Body,
+    config: &AccessLogConfig,
+) -> Result<(Body, Option<String>), StatusCode> {
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json") || content_type.contains("+json"));
+    let fits = body
+        .size_hint()
+        .upper()
+        .is_some_and(|len| len <= config.max_body_bytes as u64);
+    if !is_json || !fits {
+        return Ok((body, None));
+    }
+
+    let bytes: Bytes = match axum::body::to_bytes(body, config.max_body_bytes).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Could not buffer body for logging: {}", e);
+            let too_large = std::error::Error::source(&e).is_some_and(|source| source.is::<LengthLimitError>());
+            return Err(if too_large { StatusCode::PAYLOAD_TOO_LARGE } else { StatusCode::BAD_REQUEST });
+        }
+    };
+    let logged = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(mut value) => {
+            redact_json(&mut value, &config.redaction);
+            value.to_string()
+        }
+        Err(_) => format!("<{} bytes of invalid JSON>", bytes.len()),
+    };
+    Ok((Body::from(bytes), Some(logged)))
+}
+
+/// Replace the values of redacted fields, at any depth, with the configured replacement
+pub fn redact_json(value: &mut Value, config: &RedactionConfig) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if config.redacts(key) {
+                    *field = Value::String(config.replacement.clone());
+                } else {
+                    redact_json(field, config);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                redact_json(item, config);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn normalize_field(field: &str) -> String {
+    field.chars().filter(|c| *c != '_' && *c != '-').flat_map(char::to_lowercase).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Router};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    fn app(config: AccessLogConfig) -> Router {
+        Router::new()
+            .route("/echo", post(|body: Bytes| async move { ([(header::CONTENT_TYPE, "application/json")], body) }))
+            .layer(axum::middleware::from_fn_with_state(Arc::new(config), logging_middleware))
+    }
+
+    #[test]
+    fn test_redaction_masks_configured_fields_at_any_depth() {
+        let mut body = json!({
+            "framework": "GDPR",
+            "Client-Secret": "s3cr3t",
+            "filers": [{ "name": "Acme", "taxId": "12-3456789", "contact": { "EMAIL": "cfo@acme.test" } }],
+        });
+        redact_json(&mut body, &RedactionConfig::default());
+
+        assert_eq!(body["framework"], "GDPR");
+        assert_eq!(body["Client-Secret"], "[REDACTED]");
+        assert_eq!(body["filers"][0]["name"], "Acme");
+        assert_eq!(body["filers"][0]["taxId"], "[REDACTED]");
+        assert_eq!(body["filers"][0]["contact"]["EMAIL"], "[REDACTED]");
+
+        let custom = RedactionConfig { fields: HashSet::from(["name".to_string()]), replacement: "***".to_string() };
+        let mut body = json!({ "name": "Acme", "email": "cfo@acme.test" });
+        redact_json(&mut body, &custom);
+        assert_eq!(body, json!({ "name": "***", "email": "cfo@acme.test" }));
+    }
+
+    #[test]
+    fn test_correlation_id_reuses_safe_client_ids() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("req-42"));
+        assert_eq!(correlation_id(&headers), "req-42");
+
+        headers.insert(CORRELATION_ID_HEADER, HeaderValue::from_static("corr-7"));
+        assert_eq!(correlation_id(&headers), "corr-7");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CORRELATION_ID_HEADER, HeaderValue::from_static("<script>"));
+        let generated = correlation_id(&headers);
+        assert!(uuid::Uuid::parse_str(&generated).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_router_returns_correlation_id_and_unchanged_bodies() {
+        let payload = r#"{"framework":"GDPR","password":"hunter2"}"#;
+        for log_bodies in [false, true] {
+            let config = AccessLogConfig { log_bodies, ..AccessLogConfig::default() };
+            let request = Request::post("/echo")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header("x-request-id", "req-42")
+                .body(Body::from(payload))
+                .unwrap();
+            let response = app(config).oneshot(request).await.unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[CORRELATION_ID_HEADER], "req-42");
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert_eq!(body, payload.as_bytes());
+        }
+    }
+
+    /// Body announcing a small length and then failing, like a client dropping mid-upload
+    struct FailingBody;
+
+    impl HttpBody for FailingBody {
+        type Data = Bytes;
+        type Error = std::io::Error;
+
+        fn poll_frame(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Option<Result<http_body::Frame<Bytes>, Self::Error>>> {
+            std::task::Poll::Ready(Some(Err(std::io::Error::other("connection reset"))))
+        }
+
+        fn size_hint(&self) -> http_body::SizeHint {
+            http_body::SizeHint::with_exact(64)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unbufferable_request_body_is_rejected() {
+        let config = AccessLogConfig { log_bodies: true, ..AccessLogConfig::default() };
+        let request = Request::post("/echo")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::new(FailingBody))
+            .unwrap();
+        let response = app(config).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(response.headers().contains_key(CORRELATION_ID_HEADER));
+    }
+
+    #[test]
+    fn test_body_logging_is_off_by_default() {
+        assert!(!AccessLogConfig::default().log_bodies);
+    }
+}
@@ -1,19 +1,31 @@
+use std::sync::Arc;
+
 use axum::{Router, routing::get};
 use aion_core::AionResult;
 
+use crate::middleware::{logging_middleware, AccessLogConfig};
+
 pub struct ApiServer {
     port: u16,
     host: String,
+    access_log: AccessLogConfig,
 }
 
 impl ApiServer {
     pub fn new(host: String, port: u16) -> Self {
-        Self { host, port }
+        Self { host, port, access_log: AccessLogConfig::default() }
+    }
+
+    /// Replace the default access logging and redaction settings
+    pub fn with_access_log(mut self, access_log: AccessLogConfig) -> Self {
+        self.access_log = access_log;
+        self
     }
 
     pub async fn start(self) -> AionResult<()> {
         let app = Router::new()
-            .route("/health", get(health_check));
+            .route("/health", get(health_check))
+            .layer(axum::middleware::from_fn_with_state(Arc::new(self.access_log), logging_middleware));
 
         let listener = tokio::net::TcpListener::bind(format!("{}:{}", self.host, self.port))
             .await
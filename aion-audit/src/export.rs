@@ -0,0 +1,321 @@
+use crate::integrity::{CHAIN_HASH_KEY, PREVIOUS_HASH_KEY, SIGNATURE_KEY};
+use aion_core::{AionError, AionResult, AuditTrail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+const CEF_VENDOR: &str = "AION-CR";
+const CEF_PRODUCT: &str = "aion-audit";
+
+/// Half-open `[start, end)` window on entry timestamps; an open bound is unbounded
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TimeRange {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn since(start: DateTime<Utc>) -> Self {
+        Self { start: Some(start), end: None }
+    }
+
+    pub fn between(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self { start: Some(start), end: Some(end) }
+    }
+
+    pub fn contains(&self, timestamp: DateTime<Utc>) -> bool {
+        self.start.is_none_or(|start| timestamp >= start) && self.end.is_none_or(|end| timestamp < end)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    /// ArcSight Common Event Format, one event per line
+    Cef,
+    /// One JSON object per line with the `SiemRecord` field mapping
+    JsonLines,
+}
+
+/// Where the previous incremental export stopped
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportCursor {
+    /// Number of entries already exported
+    pub position: usize,
+    /// Id of the last entry exported, to detect a rewritten trail
+    pub last_entry_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrailExport {
+    pub data: Vec<u8>,
+    pub exported_entries: usize,
+    /// Pass to the next `export_since` to get only newer entries
+    pub cursor: ExportCursor,
+}
+
+/// Stable field mapping of an audit entry for SIEM ingestion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiemRecord {
+    pub timestamp: DateTime<Utc>,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub action: String,
+    pub actor: String,
+    pub resource: String,
+    pub previous_state: Option<String>,
+    pub new_state: Option<String>,
+    pub hash: Option<String>,
+    pub previous_hash: Option<String>,
+    pub signature: Option<String>,
+    pub details: BTreeMap<String, String>,
+}
+
+impl From<&AuditTrail> for SiemRecord {
+    fn from(entry: &AuditTrail) -> Self {
+        let integrity_key = |key: &str| [CHAIN_HASH_KEY, PREVIOUS_HASH_KEY, SIGNATURE_KEY].contains(&key);
+        Self {
+            timestamp: entry.timestamp,
+            event_id: entry.id,
+            event_type: entry.entity_type.clone(),
+            action: entry.action.clone(),
+            actor: entry.actor.clone(),
+            resource: entry.entity_id.clone(),
+            previous_state: entry.previous_state.clone(),
+            new_state: entry.new_state.clone(),
+            hash: entry.details.get(CHAIN_HASH_KEY).cloned(),
+            previous_hash: entry.details.get(PREVIOUS_HASH_KEY).cloned(),
+            signature: entry.details.get(SIGNATURE_KEY).cloned(),
+            details: entry
+                .details
+                .iter()
+                .filter(|(key, _)| !integrity_key(key))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Render every entry in `range`
+pub fn export_entries(trail: &[AuditTrail], range: TimeRange, format: ExportFormat) -> AionResult<Vec<u8>> {
+    render(trail.iter().filter(|entry| range.contains(entry.timestamp)), format)
+}
+
+/// Render the entries in `range` recorded after `cursor`, and return the cursor to resume from
+///
+/// The cursor stops before the first entry recorded at or after the end of
+/// `range`, so a later export with a wider range still picks it up.
+pub fn export_entries_since(
+    trail: &[AuditTrail],
+    cursor: Option<&ExportCursor>,
+    range: TimeRange,
+    format: ExportFormat,
+) -> AionResult<TrailExport> {
+    let start = match cursor {
+        None => 0,
+        Some(cursor) => {
+            let resumes_here = cursor.position <= trail.len()
+                && cursor.last_entry_id == cursor.position.checked_sub(1).map(|last| trail[last].id);
+            if !resumes_here {
+                return Err(AionError::AuditTrailCorruption {
+                    details: format!(
+                        "export cursor at position {} does not match the trail ({} entries)",
+                        cursor.position,
+                        trail.len()
+                    ),
+                });
+            }
+            cursor.position
+        }
+    };
+
+    // Scanning stops at the first entry past the end of the range, so entries
+    // that are filtered out only because they are too new are not skipped:
+    // the cursor advances over exactly what was scanned
+    let scanned = trail[start..]
+        .iter()
+        .take_while(|entry| range.end.is_none_or(|end| entry.timestamp < end))
+        .count();
+    let end = start + scanned;
+    let new_entries: Vec<&AuditTrail> = trail[start..end].iter().filter(|entry| range.contains(entry.timestamp)).collect();
+    Ok(TrailExport {
+        exported_entries: new_entries.len(),
+        data: render(new_entries.into_iter(), format)?,
+        cursor: ExportCursor {
+            position: end,
+            last_entry_id: end.checked_sub(1).map(|last| trail[last].id),
+        },
+    })
+}
+
+fn render<'a>(entries: impl Iterator<Item = &'a AuditTrail>, format: ExportFormat) -> AionResult<Vec<u8>> {
+    let mut output = Vec::new();
+    for entry in entries {
+        let record = SiemRecord::from(entry);
+        let line = match format {
+            ExportFormat::JsonLines => serde_json::to_string(&record).map_err(|e| AionError::SerializationError {
+                reason: e.to_string(),
+            })?,
+            ExportFormat::Cef => cef_line(&record),
+        };
+        output.extend_from_slice(line.as_bytes());
+        output.push(b'\n');
+    }
+    Ok(output)
+}
+
+fn cef_line(record: &SiemRecord) -> String {
+    let mut extension = vec![
+        ("rt", record.timestamp.timestamp_millis().to_string()),
+        ("externalId", record.event_id.to_string()),
+        ("cat", record.event_type.clone()),
+        ("act", record.action.clone()),
+        ("suser", record.actor.clone()),
+        ("cs1Label", "resource".to_string()),
+        ("cs1", record.resource.clone()),
+    ];
+    let optional = [
+        ("cs2", "previousState", &record.previous_state),
+        ("cs3", "newState", &record.new_state),
+        ("cs4", "hash", &record.hash),
+        ("cs5", "previousHash", &record.previous_hash),
+        ("cs6", "signature", &record.signature),
+    ];
+    for (key, label, value) in optional {
+        if let Some(value) = value {
+            extension.push((label_key(key), label.to_string()));
+            extension.push((key, value.clone()));
+        }
+    }
+    if !record.details.is_empty() {
+        let details = record.details.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(";");
+        extension.push(("msg", details));
+    }
+
+    format!(
+        "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+        CEF_VENDOR,
+        CEF_PRODUCT,
+        env!("CARGO_PKG_VERSION"),
+        cef_header(&record.action),
+        cef_header(&format!("{} {}", record.event_type, record.action)),
+        cef_severity(&record.action),
+        extension
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, cef_value(value)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    )
+}
+
+fn label_key(key: &str) -> &'static str {
+    match key {
+        "cs2" => "cs2Label",
+        "cs3" => "cs3Label",
+        "cs4" => "cs4Label",
+        "cs5" => "cs5Label",
+        _ => "cs6Label",
+    }
+}
+
+/// Refused and privilege-related actions rank above routine ones
+fn cef_severity(action: &str) -> u8 {
+    if action.contains("denied") {
+        7
+    } else if action.starts_with("privilege_") {
+        5
+    } else {
+        3
+    }
+}
+
+fn cef_header(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn cef_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn entry(action: &str, actor: &str) -> AuditTrail {
+        AuditTrail {
+            id: Uuid::new_v4(),
+            entity_type: "privilege_level".to_string(),
+            entity_id: "security-manager".to_string(),
+            action: action.to_string(),
+            actor: actor.to_string(),
+            timestamp: Utc::now(),
+            details: HashMap::from([(CHAIN_HASH_KEY.to_string(), "abc".to_string())]),
+            previous_state: Some("255".to_string()),
+            new_state: Some("96".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_cef_mapping_and_escaping() {
+        let trail = vec![entry("privilege_change_denied", "ops|team=a\\b")];
+        let cef = String::from_utf8(export_entries(&trail, TimeRange::all(), ExportFormat::Cef).unwrap()).unwrap();
+
+        assert!(cef.starts_with("CEF:0|AION-CR|aion-audit|"));
+        assert!(cef.contains("|privilege_change_denied|privilege_level privilege_change_denied|7|"));
+        assert!(cef.contains("suser=ops|team\\=a\\\\b "));
+        assert!(cef.contains("cs4Label=hash cs4=abc"));
+        assert!(!cef.contains("cs6"));
+        assert_eq!(cef.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_incremental_export_emits_only_new_entries() {
+        let mut trail = vec![entry("privilege_lowered", "a"), entry("privilege_escalated", "b")];
+        let first = export_entries_since(&trail, None, TimeRange::all(), ExportFormat::JsonLines).unwrap();
+        assert_eq!(first.exported_entries, 2);
+
+        trail.push(entry("privilege_lowered", "c"));
+        let second = export_entries_since(&trail, Some(&first.cursor), TimeRange::all(), ExportFormat::JsonLines).unwrap();
+        assert_eq!(second.exported_entries, 1);
+        let record: SiemRecord = serde_json::from_slice(second.data.trim_ascii_end()).unwrap();
+        assert_eq!(record.actor, "c");
+        assert_eq!(record.hash.as_deref(), Some("abc"));
+        assert!(record.details.is_empty());
+
+        let caught_up = export_entries_since(&trail, Some(&second.cursor), TimeRange::all(), ExportFormat::JsonLines).unwrap();
+        assert_eq!(caught_up.exported_entries, 0);
+        assert!(caught_up.data.is_empty());
+
+        // A cursor from a different trail is refused rather than silently skipping entries
+        trail.remove(0);
+        assert!(export_entries_since(&trail, Some(&second.cursor), TimeRange::all(), ExportFormat::JsonLines).is_err());
+    }
+
+    #[test]
+    fn test_incremental_export_does_not_skip_entries_past_the_range() {
+        let now = Utc::now();
+        let mut later = entry("privilege_escalated", "later");
+        later.timestamp = now + chrono::Duration::hours(2);
+        let trail = vec![entry("privilege_lowered", "now"), later];
+
+        let range = TimeRange::between(now - chrono::Duration::hours(1), now + chrono::Duration::hours(1));
+        let first = export_entries_since(&trail, None, range, ExportFormat::JsonLines).unwrap();
+        assert_eq!(first.exported_entries, 1);
+        assert_eq!(first.cursor.position, 1);
+
+        let second = export_entries_since(&trail, Some(&first.cursor), TimeRange::all(), ExportFormat::JsonLines).unwrap();
+        assert_eq!(second.exported_entries, 1);
+        let record: SiemRecord = serde_json::from_slice(second.data.trim_ascii_end()).unwrap();
+        assert_eq!(record.actor, "later");
+        assert_eq!(second.cursor.position, 2);
+    }
+}
@@ -1,10 +1,108 @@
-use aion_core::AionResult;
+use aion_core::{AionError, AionResult, AuditTrail};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use tracing::warn;
+use uuid::Uuid;
 
-pub struct IntegrityChecker;
+type HmacSha256 = Hmac<Sha256>;
+
+/// Detail key holding an entry's link in the hash chain
+pub const CHAIN_HASH_KEY: &str = "integrity_hash";
+/// Detail key holding the signature over an entry's chain hash
+pub const SIGNATURE_KEY: &str = "integrity_signature";
+/// Detail key holding the chain hash of the entry before this one
+pub const PREVIOUS_HASH_KEY: &str = "integrity_previous_hash";
+/// Environment variable holding the audit signing key
+pub const SIGNING_KEY_ENV: &str = "AION_AUDIT_SIGNING_KEY";
+/// Set to `1` or `true` to sign with the built-in development key when no signing key is set
+pub const ALLOW_DEVELOPMENT_KEY_ENV: &str = "AION_AUDIT_ALLOW_DEVELOPMENT_KEY";
+
+/// Hash the chain starts from
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const DEVELOPMENT_SIGNING_KEY: &[u8] = b"aion-audit-development-signing-key";
+
+pub struct IntegrityChecker {
+    signing_key: Vec<u8>,
+}
+
+/// Outcome of recomputing a trail's hash chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityResult {
+    pub entries_checked: usize,
+    /// Recomputed hash of the last entry checked
+    pub root_hash: String,
+    /// First point where the stored trail disagrees with the recomputation
+    pub divergence: Option<IntegrityDivergence>,
+}
+
+impl IntegrityResult {
+    pub fn is_intact(&self) -> bool {
+        self.divergence.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityDivergence {
+    /// Position in the trail; equal to the trail length for seal-level problems
+    pub index: usize,
+    pub entry_id: Option<Uuid>,
+    pub kind: DivergenceKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DivergenceKind {
+    MissingHash,
+    /// The entry, or one before it, was changed after it was recorded
+    HashMismatch { expected: String, found: String },
+    MissingSignature,
+    InvalidSignature,
+    /// The trail is shorter than the sealed checkpoint
+    TrailTruncated { sealed_entries: usize },
+    SealRootMismatch { sealed: String, recomputed: String },
+    InvalidSealSignature,
+}
+
+/// Signed checkpoint of a trail prefix, re-verifiable with `verify_seal`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealRecord {
+    pub seal_id: Uuid,
+    pub entry_count: usize,
+    pub root_hash: String,
+    pub sealed_at: DateTime<Utc>,
+    pub signature: String,
+}
 
 impl IntegrityChecker {
-    pub fn new() -> Self {
-        Self
+    /// Checker signing with the key from `AION_AUDIT_SIGNING_KEY`
+    ///
+    /// Fails when the key is unset, unless `AION_AUDIT_ALLOW_DEVELOPMENT_KEY`
+    /// explicitly allows the development key.
+    pub fn new() -> AionResult<Self> {
+        if let Ok(key) = std::env::var(SIGNING_KEY_ENV) {
+            if !key.is_empty() {
+                return Ok(Self::with_key(key.as_bytes()));
+            }
+        }
+
+        let development_allowed = std::env::var(ALLOW_DEVELOPMENT_KEY_ENV)
+            .is_ok_and(|flag| flag == "1" || flag.eq_ignore_ascii_case("true"));
+        if !development_allowed {
+            return Err(AionError::ConfigurationError {
+                parameter: SIGNING_KEY_ENV.to_string(),
+                reason: format!("no audit signing key is set; set {} to use the development key", ALLOW_DEVELOPMENT_KEY_ENV),
+            });
+        }
+        warn!("⚠️ {} is not set; audit entries are signed with the development key", SIGNING_KEY_ENV);
+        Ok(Self::with_key(DEVELOPMENT_SIGNING_KEY))
+    }
+
+    pub fn with_key(signing_key: &[u8]) -> Self {
+        Self {
+            signing_key: signing_key.to_vec(),
+        }
     }
 
     pub fn verify_system_integrity(&self) -> AionResult<bool> {
@@ -14,10 +112,273 @@ impl IntegrityChecker {
     pub fn check_data_consistency(&self) -> AionResult<Vec<String>> {
         Ok(Vec::new())
     }
+
+    /// Chain `entry` onto `previous` by storing its hash and signature in its details
+    pub fn link(&self, previous: Option<&AuditTrail>, entry: &mut AuditTrail) {
+        let previous_hash = previous
+            .and_then(|p| p.details.get(CHAIN_HASH_KEY).cloned())
+            .unwrap_or_else(|| GENESIS_HASH.to_string());
+        self.link_to(&previous_hash, entry);
+    }
+
+    /// Chain `entry` onto the entry whose hash is `previous_hash`
+    pub fn link_to(&self, previous_hash: &str, entry: &mut AuditTrail) {
+        let hash = chain_hash(previous_hash, entry);
+        let signature = self.sign(&hash);
+        entry.details.insert(PREVIOUS_HASH_KEY.to_string(), previous_hash.to_string());
+        entry.details.insert(CHAIN_HASH_KEY.to_string(), hash);
+        entry.details.insert(SIGNATURE_KEY.to_string(), signature);
+    }
+
+    /// Check that `entry` carries a valid hash and signature for following
+    /// `previous_hash`. Returns the entry's hash.
+    pub fn verify_link(&self, previous_hash: &str, entry: &AuditTrail) -> Result<String, DivergenceKind> {
+        let expected = chain_hash(previous_hash, entry);
+        let Some(found) = entry.details.get(CHAIN_HASH_KEY) else {
+            return Err(DivergenceKind::MissingHash);
+        };
+        if *found != expected {
+            return Err(DivergenceKind::HashMismatch { expected, found: found.clone() });
+        }
+        let Some(signature) = entry.details.get(SIGNATURE_KEY) else {
+            return Err(DivergenceKind::MissingSignature);
+        };
+        if !self.verify_signature(&expected, signature) {
+            return Err(DivergenceKind::InvalidSignature);
+        }
+        Ok(expected)
+    }
+
+    /// Recompute the hash chain and signatures, reporting the first divergence
+    pub fn verify_trail(&self, trail: &[AuditTrail]) -> IntegrityResult {
+        self.verify_continuation(GENESIS_HASH, 0, trail)
+    }
+
+    /// Like `verify_trail`, for a part of a trail that starts at position
+    /// `first_index` and follows the entry hashed `previous_hash`. Positions
+    /// and counts in the result are relative to the whole trail.
+    pub fn verify_continuation(&self, previous_hash: &str, first_index: usize, entries: &[AuditTrail]) -> IntegrityResult {
+        let mut root_hash = previous_hash.to_string();
+
+        for (offset, entry) in entries.iter().enumerate() {
+            let index = first_index + offset;
+            match self.verify_link(&root_hash, entry) {
+                Ok(hash) => root_hash = hash,
+                Err(kind) => {
+                    return IntegrityResult {
+                        entries_checked: index,
+                        root_hash,
+                        divergence: Some(IntegrityDivergence { index, entry_id: Some(entry.id), kind }),
+                    }
+                }
+            }
+        }
+
+        IntegrityResult {
+            entries_checked: first_index + entries.len(),
+            root_hash,
+            divergence: None,
+        }
+    }
+
+    /// Signed checkpoint of the trail as it stands now
+    pub fn seal(&self, trail: &[AuditTrail]) -> SealRecord {
+        let root_hash = trail.iter().fold(GENESIS_HASH.to_string(), |previous, entry| chain_hash(&previous, entry));
+        self.seal_head(trail.len(), &root_hash)
+    }
+
+    /// Signed checkpoint of a trail of `entry_count` entries whose last chain
+    /// hash is `root_hash`. The hash already commits to every earlier entry,
+    /// so callers that verified each link as it was appended can seal without
+    /// rereading the trail.
+    pub fn seal_head(&self, entry_count: usize, root_hash: &str) -> SealRecord {
+        let sealed_at = Utc::now();
+        let signature = self.sign(&seal_payload(entry_count, root_hash, &sealed_at));
+
+        SealRecord {
+            seal_id: Uuid::new_v4(),
+            entry_count,
+            root_hash: root_hash.to_string(),
+            sealed_at,
+            signature,
+        }
+    }
+
+    /// Check that the first `seal.entry_count` entries of `trail` are the ones
+    /// that were sealed. Entries appended since the seal are verified as a chain.
+    pub fn verify_seal(&self, seal: &SealRecord, trail: &[AuditTrail]) -> IntegrityResult {
+        let diverged = |entries_checked: usize, root_hash: String, kind| IntegrityResult {
+            entries_checked,
+            root_hash,
+            divergence: Some(IntegrityDivergence { index: entries_checked, entry_id: None, kind }),
+        };
+
+        if !self.verify_signature(&seal_payload(seal.entry_count, &seal.root_hash, &seal.sealed_at), &seal.signature) {
+            return diverged(0, GENESIS_HASH.to_string(), DivergenceKind::InvalidSealSignature);
+        }
+        if trail.len() < seal.entry_count {
+            return diverged(
+                trail.len(),
+                GENESIS_HASH.to_string(),
+                DivergenceKind::TrailTruncated { sealed_entries: seal.entry_count },
+            );
+        }
+
+        let sealed = self.verify_trail(&trail[..seal.entry_count]);
+        if !sealed.is_intact() {
+            return sealed;
+        }
+        if sealed.root_hash != seal.root_hash {
+            return diverged(
+                seal.entry_count,
+                sealed.root_hash.clone(),
+                DivergenceKind::SealRootMismatch { sealed: seal.root_hash.clone(), recomputed: sealed.root_hash },
+            );
+        }
+        self.verify_trail(trail)
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.signing_key).expect("HMAC accepts keys of any length")
+    }
+
+    fn sign(&self, payload: &str) -> String {
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn verify_signature(&self, payload: &str, signature: &str) -> bool {
+        let Ok(bytes) = hex::decode(signature) else {
+            return false;
+        };
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&bytes).is_ok()
+    }
+}
+
+/// Recompute the hash chain and signatures of `trail` with the configured signing key
+pub fn verify_trail(trail: &[AuditTrail]) -> AionResult<IntegrityResult> {
+    Ok(IntegrityChecker::new()?.verify_trail(trail))
+}
+
+/// Produce a signed checkpoint of `trail` with the configured signing key
+pub fn seal(trail: &[AuditTrail]) -> AionResult<SealRecord> {
+    Ok(IntegrityChecker::new()?.seal(trail))
 }
 
-impl Default for IntegrityChecker {
-    fn default() -> Self {
-        Self::new()
+/// Hash of an entry's content, excluding its own integrity details, chained to the previous hash
+fn chain_hash(previous_hash: &str, entry: &AuditTrail) -> String {
+    let details: BTreeMap<&String, &String> = entry
+        .details
+        .iter()
+        .filter(|(key, _)| ![CHAIN_HASH_KEY, SIGNATURE_KEY, PREVIOUS_HASH_KEY].contains(&key.as_str()))
+        .collect();
+    let content = serde_json::json!([
+        entry.id,
+        entry.entity_type,
+        entry.entity_id,
+        entry.action,
+        entry.actor,
+        entry.timestamp.to_rfc3339(),
+        details,
+        entry.previous_state,
+        entry.new_state,
+    ]);
+
+    let mut hasher = Sha256::new();
+    hasher.update(previous_hash.as_bytes());
+    hasher.update(content.to_string().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+fn seal_payload(entry_count: usize, root_hash: &str, sealed_at: &DateTime<Utc>) -> String {
+    format!("{}|{}|{}", entry_count, root_hash, sealed_at.to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn entry(action: &str) -> AuditTrail {
+        AuditTrail {
+            id: Uuid::new_v4(),
+            entity_type: "framework".to_string(),
+            entity_id: "gdpr".to_string(),
+            action: action.to_string(),
+            actor: "auditor".to_string(),
+            timestamp: Utc::now(),
+            details: HashMap::new(),
+            previous_state: None,
+            new_state: None,
+        }
     }
-}
\ No newline at end of file
+
+    fn chained(checker: &IntegrityChecker, actions: &[&str]) -> Vec<AuditTrail> {
+        let mut trail: Vec<AuditTrail> = Vec::new();
+        for action in actions {
+            let mut next = entry(action);
+            checker.link(trail.last(), &mut next);
+            trail.push(next);
+        }
+        trail
+    }
+
+    #[test]
+    fn test_edited_entry_is_detected() {
+        let checker = IntegrityChecker::with_key(b"test-key");
+        let mut trail = chained(&checker, &["create", "update", "approve"]);
+        assert!(checker.verify_trail(&trail).is_intact());
+
+        trail[1].actor = "someone-else".to_string();
+        let result = checker.verify_trail(&trail);
+        let divergence = result.divergence.unwrap();
+        assert_eq!(divergence.index, 1);
+        assert!(matches!(divergence.kind, DivergenceKind::HashMismatch { .. }));
+        assert_eq!(result.entries_checked, 1);
+
+        // Rehashing the edit without the key still fails on the signature
+        let previous = trail[0].details[CHAIN_HASH_KEY].clone();
+        let forged = chain_hash(&previous, &trail[1]);
+        trail[1].details.insert(CHAIN_HASH_KEY.to_string(), forged);
+        assert_eq!(checker.verify_trail(&trail).divergence.unwrap().kind, DivergenceKind::InvalidSignature);
+    }
+
+    #[test]
+    fn test_seal_reverifies_and_allows_appends() {
+        let checker = IntegrityChecker::with_key(b"test-key");
+        let mut trail = chained(&checker, &["create", "update"]);
+        let seal = checker.seal(&trail);
+        assert_eq!(seal.entry_count, 2);
+
+        let mut next = entry("approve");
+        checker.link(trail.last(), &mut next);
+        trail.push(next);
+        assert!(checker.verify_seal(&seal, &trail).is_intact());
+
+        let truncated = checker.verify_seal(&seal, &trail[..1]);
+        assert_eq!(truncated.divergence.unwrap().kind, DivergenceKind::TrailTruncated { sealed_entries: 2 });
+
+        let other_key = IntegrityChecker::with_key(b"other-key");
+        assert_eq!(
+            other_key.verify_seal(&seal, &trail).divergence.unwrap().kind,
+            DivergenceKind::InvalidSealSignature
+        );
+    }
+
+    #[test]
+    fn test_missing_signing_key_fails_closed() {
+        // Only this test touches these variables
+        std::env::remove_var(SIGNING_KEY_ENV);
+        std::env::remove_var(ALLOW_DEVELOPMENT_KEY_ENV);
+        assert!(matches!(IntegrityChecker::new(), Err(AionError::ConfigurationError { .. })));
+
+        std::env::set_var(ALLOW_DEVELOPMENT_KEY_ENV, "true");
+        let development = IntegrityChecker::new().unwrap();
+        std::env::remove_var(ALLOW_DEVELOPMENT_KEY_ENV);
+        let trail = chained(&development, &["create"]);
+        assert!(IntegrityChecker::with_key(DEVELOPMENT_SIGNING_KEY).verify_trail(&trail).is_intact());
+    }
+}
@@ -1,7 +1,11 @@
 pub mod system;
 pub mod trail;
 pub mod integrity;
+pub mod privilege;
+pub mod export;
 
 pub use system::*;
 pub use trail::*;
-pub use integrity::*;
\ No newline at end of file
+pub use integrity::*;
+pub use privilege::*;
+pub use export::*;
@@ -0,0 +1,72 @@
+use aion_core::AuditTrail;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Entity type under which privilege changes are recorded
+pub const PRIVILEGE_ENTITY_TYPE: &str = "privilege_level";
+
+/// A change of privilege level, or a refused attempt at one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivilegeChangeRecord {
+    /// Who asked for the change
+    pub actor: String,
+    /// The component whose privileges changed
+    pub subject: String,
+    pub from_level: u8,
+    pub to_level: u8,
+    pub reason: String,
+    pub outcome: PrivilegeChangeOutcome,
+    /// Id of the integration event announcing the change, for correlation
+    pub event_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+    pub details: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PrivilegeChangeOutcome {
+    Applied,
+    Denied { error: String },
+}
+
+impl PrivilegeChangeRecord {
+    pub fn action(&self) -> &'static str {
+        match (&self.outcome, self.to_level.cmp(&self.from_level)) {
+            (PrivilegeChangeOutcome::Denied { .. }, _) => "privilege_change_denied",
+            (PrivilegeChangeOutcome::Applied, std::cmp::Ordering::Greater) => "privilege_escalated",
+            (PrivilegeChangeOutcome::Applied, std::cmp::Ordering::Less) => "privilege_lowered",
+            (PrivilegeChangeOutcome::Applied, std::cmp::Ordering::Equal) => "privilege_reapplied",
+        }
+    }
+
+    pub fn into_audit_trail(self) -> AuditTrail {
+        let action = self.action().to_string();
+        let mut details = self.details;
+        details.insert("reason".to_string(), self.reason);
+        details.insert("from_level".to_string(), self.from_level.to_string());
+        details.insert("to_level".to_string(), self.to_level.to_string());
+        if let Some(event_id) = self.event_id {
+            details.insert("integration_event_id".to_string(), event_id.to_string());
+        }
+        let new_state = match self.outcome {
+            PrivilegeChangeOutcome::Applied => self.to_level,
+            PrivilegeChangeOutcome::Denied { error } => {
+                details.insert("error".to_string(), error);
+                self.from_level
+            }
+        };
+
+        AuditTrail {
+            id: Uuid::new_v4(),
+            entity_type: PRIVILEGE_ENTITY_TYPE.to_string(),
+            entity_id: self.subject,
+            action,
+            actor: self.actor,
+            timestamp: self.timestamp,
+            details,
+            previous_state: Some(self.from_level.to_string()),
+            new_state: Some(new_state.to_string()),
+        }
+    }
+}
@@ -1,39 +1,182 @@
-use aion_core::{AionResult, AuditSystem, AuditTrail};
+use crate::export::{export_entries, export_entries_since, ExportCursor, ExportFormat, TimeRange, TrailExport};
+use crate::integrity::IntegrityChecker;
+use aion_core::{AionError, AionResult, AuditSystem, AuditTrail};
+use async_trait::async_trait;
 use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// Environment variable naming the file `FileAuditSystem::open_configured` appends to
+pub const AUDIT_TRAIL_PATH_ENV: &str = "AION_AUDIT_TRAIL_PATH";
+
+/// An audit system that also accepts fully-formed entries, for callers that
+/// record state transitions (`previous_state`/`new_state`) rather than bare actions.
+/// Appended entries are chained by the system's `IntegrityChecker`.
+#[async_trait]
+pub trait AuditTrailSink: AuditSystem + Send + Sync {
+    /// Record `entry`; once this returns `Ok` the entry is durable
+    async fn append(&mut self, entry: AuditTrail) -> AionResult<()>;
+
+    /// Every entry in recording order, as needed for `integrity::verify_trail`
+    fn entries(&self) -> &[AuditTrail];
+
+    /// Export the entries recorded within `range` for a SIEM
+    fn export_trail(&self, range: TimeRange, format: ExportFormat) -> AionResult<Vec<u8>> {
+        export_entries(self.entries(), range, format)
+    }
+
+    /// Export only the entries recorded since `cursor`; pass `None` on the first export
+    fn export_since(&self, cursor: Option<&ExportCursor>, range: TimeRange, format: ExportFormat) -> AionResult<TrailExport> {
+        export_entries_since(self.entries(), cursor, range, format)
+    }
+}
+
+fn new_entry(entity_type: &str, entity_id: &str, action: &str, actor: &str, details: HashMap<String, String>) -> AuditTrail {
+    AuditTrail {
+        id: uuid::Uuid::new_v4(),
+        entity_type: entity_type.to_string(),
+        entity_id: entity_id.to_string(),
+        action: action.to_string(),
+        actor: actor.to_string(),
+        timestamp: chrono::Utc::now(),
+        details,
+        previous_state: None,
+        new_state: None,
+    }
+}
 
 pub struct ComprehensiveAuditSystem {
     trail_storage: Vec<AuditTrail>,
+    integrity: IntegrityChecker,
 }
 
 impl ComprehensiveAuditSystem {
-    pub fn new() -> Self {
+    /// Audit system signing with the configured key; see `IntegrityChecker::new`
+    pub fn new() -> AionResult<Self> {
+        Ok(Self::with_integrity_checker(IntegrityChecker::new()?))
+    }
+
+    pub fn with_integrity_checker(integrity: IntegrityChecker) -> Self {
         Self {
             trail_storage: Vec::new(),
+            integrity,
         }
     }
 }
 
-impl Default for ComprehensiveAuditSystem {
-    fn default() -> Self {
-        Self::new()
+impl ComprehensiveAuditSystem {
+    fn push(&mut self, mut entry: AuditTrail) {
+        self.integrity.link(self.trail_storage.last(), &mut entry);
+        self.trail_storage.push(entry);
     }
 }
 
 impl AuditSystem for ComprehensiveAuditSystem {
     fn record_action(&mut self, entity_type: &str, entity_id: &str, action: &str, actor: &str, details: HashMap<String, String>) -> AionResult<()> {
-        let audit_entry = AuditTrail {
-            id: uuid::Uuid::new_v4(),
-            entity_type: entity_type.to_string(),
-            entity_id: entity_id.to_string(),
-            action: action.to_string(),
-            actor: actor.to_string(),
-            timestamp: chrono::Utc::now(),
-            details,
-            previous_state: None,
-            new_state: None,
+        self.push(new_entry(entity_type, entity_id, action, actor, details));
+        Ok(())
+    }
+
+    fn get_audit_trail(&self, entity_id: &str) -> AionResult<Vec<AuditTrail>> {
+        Ok(self.trail_storage.iter()
+            .filter(|entry| entry.entity_id == entity_id)
+            .cloned()
+            .collect())
+    }
+
+    fn verify_integrity(&self) -> AionResult<bool> {
+        Ok(self.integrity.verify_trail(&self.trail_storage).is_intact())
+    }
+}
+
+#[async_trait]
+impl AuditTrailSink for ComprehensiveAuditSystem {
+    async fn append(&mut self, entry: AuditTrail) -> AionResult<()> {
+        self.push(entry);
+        Ok(())
+    }
+
+    fn entries(&self) -> &[AuditTrail] {
+        &self.trail_storage
+    }
+}
+
+/// Audit system that appends every entry to a JSON-lines file, so the trail
+/// survives restarts. Existing entries are loaded when the file is opened.
+pub struct FileAuditSystem {
+    path: PathBuf,
+    trail_storage: Vec<AuditTrail>,
+    integrity: IntegrityChecker,
+}
+
+impl FileAuditSystem {
+    pub async fn open(path: impl Into<PathBuf>) -> AionResult<Self> {
+        Self::open_with_integrity_checker(path, IntegrityChecker::new()?).await
+    }
+
+    /// Open the file named by `AION_AUDIT_TRAIL_PATH`, or `None` when it is unset
+    pub async fn open_configured() -> AionResult<Option<Self>> {
+        match std::env::var_os(AUDIT_TRAIL_PATH_ENV) {
+            Some(path) if !path.is_empty() => Ok(Some(Self::open(PathBuf::from(path)).await?)),
+            _ => Ok(None),
+        }
+    }
+
+    pub async fn open_with_integrity_checker(path: impl Into<PathBuf>, integrity: IntegrityChecker) -> AionResult<Self> {
+        let path = path.into();
+        let mut trail_storage = Vec::new();
+
+        let contents = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(storage_error("audit_open", &path, e)),
         };
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(line).map_err(|e| AionError::AuditTrailCorruption {
+                details: format!("{} line {}: {}", path.display(), index + 1, e),
+            })?;
+            trail_storage.push(entry);
+        }
+
+        Ok(Self { path, trail_storage, integrity })
+    }
 
-        self.trail_storage.push(audit_entry);
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Chain `entry` onto the trail and render it as one JSON line
+    fn encode(&self, entry: &mut AuditTrail) -> AionResult<String> {
+        self.integrity.link(self.trail_storage.last(), entry);
+        let mut line = serde_json::to_string(entry).map_err(|e| AionError::SerializationError {
+            reason: e.to_string(),
+        })?;
+        line.push('\n');
+        Ok(line)
+    }
+}
+
+impl AuditSystem for FileAuditSystem {
+    /// Blocking append for the synchronous `AuditSystem` interface; async
+    /// callers should use `AuditTrailSink::append`
+    fn record_action(&mut self, entity_type: &str, entity_id: &str, action: &str, actor: &str, details: HashMap<String, String>) -> AionResult<()> {
+        let mut entry = new_entry(entity_type, entity_id, action, actor, details);
+        let line = self.encode(&mut entry)?;
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| storage_error("audit_append", &self.path, e))?;
+        file.write_all(line.as_bytes())
+            .and_then(|_| file.sync_data())
+            .map_err(|e| storage_error("audit_append", &self.path, e))?;
+
+        self.trail_storage.push(entry);
         Ok(())
     }
 
@@ -45,6 +188,40 @@ impl AuditSystem for ComprehensiveAuditSystem {
     }
 
     fn verify_integrity(&self) -> AionResult<bool> {
-        Ok(true)
+        Ok(self.integrity.verify_trail(&self.trail_storage).is_intact())
+    }
+}
+
+#[async_trait]
+impl AuditTrailSink for FileAuditSystem {
+    async fn append(&mut self, mut entry: AuditTrail) -> AionResult<()> {
+        let line = self.encode(&mut entry)?;
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| storage_error("audit_append", &self.path, e))?;
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|e| storage_error("audit_append", &self.path, e))?;
+        file.sync_data()
+            .await
+            .map_err(|e| storage_error("audit_append", &self.path, e))?;
+
+        self.trail_storage.push(entry);
+        Ok(())
+    }
+
+    fn entries(&self) -> &[AuditTrail] {
+        &self.trail_storage
     }
-}
\ No newline at end of file
+}
+
+fn storage_error(operation: &str, path: &Path, error: std::io::Error) -> AionError {
+    AionError::DatabaseError {
+        operation: operation.to_string(),
+        reason: format!("{}: {}", path.display(), error),
+    }
+}
@@ -1,4 +1,11 @@
-use aion_core::{AionResult, AuditTrail};
+use crate::integrity::{IntegrityChecker, IntegrityResult, SealRecord, CHAIN_HASH_KEY, GENESIS_HASH, PREVIOUS_HASH_KEY};
+use aion_core::{AionError, AionResult, AuditTrail};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+use uuid::Uuid;
 
 pub struct AuditTrailManager;
 
@@ -29,4 +36,368 @@ impl Default for AuditTrailManager {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// Append-only storage for a hash-chained audit trail. Appending is the only
+/// way to change a store: there is deliberately no update or delete.
+pub trait TrailStore: Send + Sync {
+    /// Hash of the newest entry, or `GENESIS_HASH` when the trail is empty
+    fn head_hash(&self) -> String;
+
+    /// Append an entry linked to the current head (see `IntegrityChecker::link_to`)
+    /// and return the new head hash. Entries linked to any other hash are rejected.
+    fn append(&mut self, entry: AuditTrail) -> AionResult<String>;
+
+    /// Entries that have not been archived yet, oldest first
+    fn live_entries(&self) -> &[AuditTrail];
+
+    /// Archived segments, oldest first
+    fn archived_segments(&self) -> &[ArchivedSegment];
+
+    /// Entries of an archived segment, read back from wherever the store keeps them
+    fn read_segment(&self, segment: &ArchivedSegment) -> AionResult<Vec<AuditTrail>>;
+}
+
+/// When `SegmentedTrailStore` moves live entries into an archived segment
+#[derive(Debug, Clone)]
+pub struct CompactionPolicy {
+    /// Entries older than this are archived
+    pub max_live_age: Duration,
+    /// Compaction runs at most this often, checked on append
+    pub compaction_interval: Duration,
+}
+
+impl Default for CompactionPolicy {
+    fn default() -> Self {
+        Self {
+            max_live_age: Duration::days(30),
+            compaction_interval: Duration::hours(1),
+        }
+    }
+}
+
+/// A run of entries moved out of the live trail. The first entry still links
+/// to `previous_hash`, so the chain runs unbroken through every segment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSegment {
+    pub segment_id: Uuid,
+    pub archived_at: DateTime<Utc>,
+    /// Position in the whole trail of the segment's first entry
+    pub first_index: usize,
+    pub entry_count: usize,
+    /// Head hash of the trail before this segment
+    pub previous_hash: String,
+    /// Hash of the segment's last entry
+    pub head_hash: String,
+    /// Seal over the whole trail up to the end of this segment
+    pub seal: SealRecord,
+}
+
+/// On-disk form of an archived segment
+#[derive(Serialize, Deserialize)]
+struct SegmentFile {
+    segment: ArchivedSegment,
+    entries: Vec<AuditTrail>,
+}
+
+/// File listing the archived segments in order, one JSON line each
+const SEGMENT_INDEX_FILE: &str = "segments.jsonl";
+
+/// `TrailStore` that verifies every append and periodically archives old
+/// entries to files in its archive directory. Only live entries and segment
+/// headers are held in memory.
+pub struct SegmentedTrailStore {
+    integrity: IntegrityChecker,
+    policy: CompactionPolicy,
+    archive_dir: PathBuf,
+    archive: Vec<ArchivedSegment>,
+    live: Vec<AuditTrail>,
+    last_compaction: DateTime<Utc>,
+}
+
+impl SegmentedTrailStore {
+    /// Open the store archiving to `archive_dir`, picking up any segments already archived there
+    pub fn open(integrity: IntegrityChecker, policy: CompactionPolicy, archive_dir: impl Into<PathBuf>) -> AionResult<Self> {
+        let archive_dir = archive_dir.into();
+        let index_path = archive_dir.join(SEGMENT_INDEX_FILE);
+        let contents = match std::fs::read_to_string(&index_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(archive_error("archive_open", &index_path, e)),
+        };
+
+        let mut archive = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let segment = serde_json::from_str(line).map_err(|e| AionError::AuditTrailCorruption {
+                details: format!("{} line {}: {}", index_path.display(), index + 1, e),
+            })?;
+            archive.push(segment);
+        }
+
+        Ok(Self {
+            integrity,
+            policy,
+            archive_dir,
+            archive,
+            live: Vec::new(),
+            last_compaction: Utc::now(),
+        })
+    }
+
+    /// Link `entry` to the current head and append it
+    pub fn record(&mut self, mut entry: AuditTrail) -> AionResult<String> {
+        self.integrity.link_to(&self.head_hash(), &mut entry);
+        self.append(entry)
+    }
+
+    /// Archive live entries older than the policy's `max_live_age` as one segment.
+    /// Returns the new segment, if any entries were old enough.
+    ///
+    /// The segment is written to disk before its entries leave memory, and
+    /// sealed from the running head hash, so the cost does not grow with the
+    /// size of the archive.
+    pub fn compact(&mut self, now: DateTime<Utc>) -> AionResult<Option<&ArchivedSegment>> {
+        self.last_compaction = now;
+        let cutoff = now - self.policy.max_live_age;
+        // Only a prefix is archived, so entries never leave the trail out of order
+        let count = self.live.iter().take_while(|entry| entry.timestamp < cutoff).count();
+        if count == 0 {
+            return Ok(None);
+        }
+
+        let previous_hash = self.archive_head();
+        let entries = &self.live[..count];
+        let head_hash = entries
+            .last()
+            .and_then(|entry| entry.details.get(CHAIN_HASH_KEY).cloned())
+            .unwrap_or_else(|| previous_hash.clone());
+        let first_index = self.archived_count();
+        let segment = ArchivedSegment {
+            segment_id: Uuid::new_v4(),
+            archived_at: now,
+            first_index,
+            entry_count: count,
+            seal: self.integrity.seal_head(first_index + count, &head_hash),
+            previous_hash,
+            head_hash,
+        };
+
+        self.write_segment(&segment, entries)?;
+        self.live.drain(..count);
+        info!("🗄️ Archived {} audit entries, chain head {}", count, segment.head_hash);
+        self.archive.push(segment);
+        Ok(self.archive.last())
+    }
+
+    /// Verify the chain across every archived segment and the live entries,
+    /// reading one segment at a time
+    pub fn verify(&self) -> AionResult<IntegrityResult> {
+        let mut result = self.integrity.verify_continuation(GENESIS_HASH, 0, &[]);
+        for segment in &self.archive {
+            let entries = self.read_segment(segment)?;
+            result = self.integrity.verify_continuation(&result.root_hash, result.entries_checked, &entries);
+            if !result.is_intact() {
+                return Ok(result);
+            }
+        }
+        Ok(self.integrity.verify_continuation(&result.root_hash, result.entries_checked, &self.live))
+    }
+
+    pub fn archive_dir(&self) -> &Path {
+        &self.archive_dir
+    }
+
+    fn archived_count(&self) -> usize {
+        self.archive.last().map_or(0, |segment| segment.first_index + segment.entry_count)
+    }
+
+    fn archive_head(&self) -> String {
+        self.archive
+            .last()
+            .map(|segment| segment.head_hash.clone())
+            .unwrap_or_else(|| GENESIS_HASH.to_string())
+    }
+
+    fn segment_path(&self, segment: &ArchivedSegment) -> PathBuf {
+        self.archive_dir.join(format!("segment-{:012}-{}.json", segment.first_index, segment.segment_id))
+    }
+
+    /// Write the segment file, then list it in the index; a segment missing
+    /// from the index was never archived and its entries are still live
+    fn write_segment(&self, segment: &ArchivedSegment, entries: &[AuditTrail]) -> AionResult<()> {
+        std::fs::create_dir_all(&self.archive_dir)
+            .map_err(|e| archive_error("archive_segment", &self.archive_dir, e))?;
+
+        let path = self.segment_path(segment);
+        let file = SegmentFile { segment: segment.clone(), entries: entries.to_vec() };
+        let data = serde_json::to_vec(&file).map_err(|e| AionError::SerializationError { reason: e.to_string() })?;
+        let temporary = path.with_extension("json.tmp");
+        std::fs::write(&temporary, &data)
+            .and_then(|_| std::fs::File::open(&temporary)?.sync_all())
+            .and_then(|_| std::fs::rename(&temporary, &path))
+            .map_err(|e| archive_error("archive_segment", &path, e))?;
+
+        let index_path = self.archive_dir.join(SEGMENT_INDEX_FILE);
+        let mut line = serde_json::to_string(segment).map_err(|e| AionError::SerializationError { reason: e.to_string() })?;
+        line.push('\n');
+        let mut index = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&index_path)
+            .map_err(|e| archive_error("archive_segment", &index_path, e))?;
+        index
+            .write_all(line.as_bytes())
+            .and_then(|_| index.sync_data())
+            .map_err(|e| archive_error("archive_segment", &index_path, e))
+    }
+}
+
+impl TrailStore for SegmentedTrailStore {
+    fn head_hash(&self) -> String {
+        self.live
+            .last()
+            .and_then(|entry| entry.details.get(CHAIN_HASH_KEY).cloned())
+            .unwrap_or_else(|| self.archive_head())
+    }
+
+    fn append(&mut self, entry: AuditTrail) -> AionResult<String> {
+        let head = self.head_hash();
+        let previous = entry.details.get(PREVIOUS_HASH_KEY).cloned().unwrap_or_default();
+        if previous != head {
+            return Err(AionError::VersionConflict {
+                entity: format!("audit entry {}", entry.id),
+                expected: head,
+                found: previous,
+            });
+        }
+        let hash = self.integrity.verify_link(&head, &entry).map_err(|kind| AionError::AuditTrailCorruption {
+            details: format!("audit entry {} rejected: {:?}", entry.id, kind),
+        })?;
+
+        self.live.push(entry);
+        let now = Utc::now();
+        if now - self.last_compaction >= self.policy.compaction_interval {
+            // The entry is appended either way; what could not be archived stays live
+            if let Err(e) = self.compact(now) {
+                warn!("⚠️ Audit trail compaction failed: {}", e);
+            }
+        }
+        Ok(hash)
+    }
+
+    fn live_entries(&self) -> &[AuditTrail] {
+        &self.live
+    }
+
+    fn archived_segments(&self) -> &[ArchivedSegment] {
+        &self.archive
+    }
+
+    fn read_segment(&self, segment: &ArchivedSegment) -> AionResult<Vec<AuditTrail>> {
+        let path = self.segment_path(segment);
+        let data = std::fs::read(&path).map_err(|e| archive_error("archive_read", &path, e))?;
+        let file: SegmentFile = serde_json::from_slice(&data).map_err(|e| AionError::AuditTrailCorruption {
+            details: format!("{}: {}", path.display(), e),
+        })?;
+        if file.segment.segment_id != segment.segment_id || file.entries.len() != segment.entry_count {
+            return Err(AionError::AuditTrailCorruption {
+                details: format!("{} does not hold segment {}", path.display(), segment.segment_id),
+            });
+        }
+        Ok(file.entries)
+    }
+}
+
+fn archive_error(operation: &str, path: &Path, error: std::io::Error) -> AionError {
+    AionError::DatabaseError {
+        operation: operation.to_string(),
+        reason: format!("{}: {}", path.display(), error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn entry(action: &str, timestamp: DateTime<Utc>) -> AuditTrail {
+        AuditTrail {
+            id: Uuid::new_v4(),
+            entity_type: "framework".to_string(),
+            entity_id: "gdpr".to_string(),
+            action: action.to_string(),
+            actor: "auditor".to_string(),
+            timestamp,
+            details: HashMap::new(),
+            previous_state: None,
+            new_state: None,
+        }
+    }
+
+    #[test]
+    fn test_out_of_order_append_is_rejected() {
+        let checker = IntegrityChecker::with_key(b"test-key");
+        let archive = tempfile::tempdir().unwrap();
+        let mut store = store(archive.path());
+        let first_head = store.record(entry("create", Utc::now())).unwrap();
+        let second_head = store.record(entry("update", Utc::now())).unwrap();
+
+        // Linked to the first entry, i.e. written as if "update" never happened
+        let mut stale = entry("delete", Utc::now());
+        checker.link_to(&first_head, &mut stale);
+        assert!(matches!(store.append(stale), Err(AionError::VersionConflict { .. })));
+
+        // Linked to the head but altered after linking
+        let mut altered = entry("approve", Utc::now());
+        checker.link_to(&second_head, &mut altered);
+        altered.actor = "someone-else".to_string();
+        assert!(matches!(store.append(altered), Err(AionError::AuditTrailCorruption { .. })));
+
+        assert_eq!(store.live_entries().len(), 2);
+        assert_eq!(store.head_hash(), second_head);
+    }
+
+    #[test]
+    fn test_compaction_preserves_chain() {
+        let now = Utc::now();
+        let archive = tempfile::tempdir().unwrap();
+        let mut store = store(archive.path());
+        store.record(entry("create", now - Duration::days(60))).unwrap();
+        let archived_head = store.record(entry("update", now - Duration::days(45))).unwrap();
+        store.record(entry("approve", now)).unwrap();
+
+        let segment = store.compact(now).unwrap().unwrap().clone();
+        assert_eq!(segment.entry_count, 2);
+        assert_eq!(segment.previous_hash, GENESIS_HASH);
+        assert_eq!(segment.head_hash, archived_head);
+        assert_eq!(segment.seal.root_hash, archived_head);
+        assert_eq!(store.read_segment(&segment).unwrap().len(), 2);
+        assert_eq!(store.live_entries().len(), 1);
+        assert_eq!(store.live_entries()[0].details[PREVIOUS_HASH_KEY], archived_head);
+
+        let head = store.record(entry("publish", now)).unwrap();
+        assert_eq!(store.head_hash(), head);
+        assert!(store.verify().unwrap().is_intact());
+        assert!(store.compact(now).unwrap().is_none());
+        let sealed = store.read_segment(&segment).unwrap();
+        assert!(IntegrityChecker::with_key(b"test-key").verify_seal(&segment.seal, &sealed).is_intact());
+
+        // The archive outlives the store; live entries are the caller's to replay
+        let reopened = SegmentedTrailStore::open(
+            IntegrityChecker::with_key(b"test-key"),
+            CompactionPolicy::default(),
+            archive.path(),
+        )
+        .unwrap();
+        assert_eq!(reopened.archived_segments().len(), 1);
+        assert_eq!(reopened.head_hash(), archived_head);
+        assert!(reopened.verify().unwrap().is_intact());
+    }
+
+    fn store(archive_dir: &Path) -> SegmentedTrailStore {
+        SegmentedTrailStore::open(IntegrityChecker::with_key(b"test-key"), CompactionPolicy::default(), archive_dir).unwrap()
+    }
+}
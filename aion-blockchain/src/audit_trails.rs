@@ -0,0 +1,747 @@
+//! Audit Trail Management
+//!
+//! Hash-chained audit entries with Merkle inclusion proofs, so a single
+//! record can be verified against a committed root without the whole trail.
+//! Entries can be anchored on-chain in batches, one transaction per Merkle
+//! root rather than per entry.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::*;
+
+/// Result of re-walking the stored hash chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainIntegrityReport {
+    pub entries_checked: u64,
+    pub intact: bool,
+    pub first_broken_link: Option<BrokenLink>,
+    pub checked_at: chrono::DateTime<Utc>,
+}
+
+/// First point at which the chain diverges from its recomputed hashes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenLink {
+    pub index: u64,
+    pub entry_id: Uuid,
+    pub kind: BrokenLinkKind,
+    pub expected_hash: String,
+    pub actual_hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BrokenLinkKind {
+    /// `previous_hash` does not match the preceding entry's `current_hash`
+    PreviousHashMismatch,
+    /// `current_hash` does not match the entry's recomputed hash
+    CurrentHashMismatch,
+}
+
+/// Audit trail health
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditTrailHealth {
+    pub integrity_verified: bool,
+    pub total_entries: u64,
+    pub integrity_report: ChainIntegrityReport,
+}
+
+/// On-chain anchor shared by every entry of a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchAnchor {
+    /// Merkle root committed on-chain for the batch
+    pub batch_root: String,
+    /// Position of the entry among the batch leaves
+    pub leaf_index: u64,
+    pub batch_size: u64,
+    pub tx_hash: String,
+}
+
+/// Proof that an audit entry is included under a Merkle root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub entry_id: Uuid,
+    /// The entry's `current_hash`, used as the Merkle leaf
+    pub leaf_hash: String,
+    /// Position of the leaf in the trail
+    pub leaf_index: u64,
+    /// Number of leaves under the root, which fixes the shape of the path
+    pub leaf_count: u64,
+    /// Sibling hashes from the leaf level up to just below the root,
+    /// skipping levels where the node was promoted without a sibling
+    pub siblings: Vec<String>,
+    /// Root the proof was generated against
+    pub root: String,
+}
+
+impl TrailStorage {
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub async fn append(&self, entry: AuditTrailEntry) {
+        self.entries.write().await.push(entry);
+    }
+
+    pub async fn entries(&self) -> Vec<AuditTrailEntry> {
+        self.entries.read().await.clone()
+    }
+
+    pub async fn last_hash(&self) -> Option<String> {
+        self.entries.read().await.last().map(|entry| entry.current_hash.clone())
+    }
+
+    /// Append the entry built by `build` from the current head's hash, under
+    /// one write lock so concurrent appends cannot chain onto the same head
+    pub async fn append_chained(&self, build: impl FnOnce(String) -> AuditTrailEntry) -> AuditTrailEntry {
+        let mut entries = self.entries.write().await;
+        let previous_hash = entries.last().map(|entry| entry.current_hash.clone()).unwrap_or_else(|| "0".to_string());
+        let mut entry = build(previous_hash);
+        entry.current_hash = HashChain::hash_entry(&entry);
+        entries.push(entry.clone());
+        entry
+    }
+
+    /// Append `entry` only if the trail is empty
+    pub async fn append_if_empty(&self, entry: AuditTrailEntry) {
+        let mut entries = self.entries.write().await;
+        if entries.is_empty() {
+            entries.push(entry);
+        }
+    }
+
+    /// Record the transaction an individually anchored entry was committed in
+    pub async fn record_anchor(&self, entry_id: Uuid, tx_hash: &str) {
+        if let Some(entry) = self.entries.write().await.iter_mut().find(|entry| entry.entry_id == entry_id) {
+            entry.blockchain_tx_hash = Some(tx_hash.to_string());
+        }
+    }
+
+    /// Record batch anchors, keyed by entry id, on the stored entries
+    pub async fn apply_batch_anchors(&self, anchors: &HashMap<Uuid, BatchAnchor>) {
+        for entry in self.entries.write().await.iter_mut() {
+            if let Some(anchor) = anchors.get(&entry.entry_id) {
+                entry.blockchain_tx_hash = Some(anchor.tx_hash.clone());
+                entry.batch_anchor = Some(anchor.clone());
+            }
+        }
+    }
+}
+
+impl HashChain {
+    /// Hash linking an entry to its predecessor
+    ///
+    /// Covers every field except the hash itself, the signature and the
+    /// anchoring references that are filled in after hashing.
+    pub fn hash_entry(entry: &AuditTrailEntry) -> String {
+        let mut metadata: Vec<_> = entry.metadata.iter().collect();
+        metadata.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut hasher = Sha256::new();
+        hasher.update(entry.previous_hash.as_bytes());
+        hasher.update(entry.entry_id.as_bytes());
+        hasher.update(entry.timestamp.to_rfc3339().as_bytes());
+        hasher.update(format!("{:?}", entry.event_type).as_bytes());
+        hasher.update(entry.actor.as_bytes());
+        hasher.update(entry.action.as_bytes());
+        hasher.update(entry.resource.as_bytes());
+        hasher.update(serde_json::to_vec(&metadata).unwrap_or_default());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Merkle leaf for an entry hash, domain-separated from inner nodes by a 0x00 prefix
+    pub fn hash_leaf(leaf: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update([0x00]);
+        hasher.update(leaf.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Hash of two child nodes, domain-separated from leaves by a 0x01 prefix
+    pub fn hash_pair(left: &str, right: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update([0x01]);
+        hasher.update(left.as_bytes());
+        hasher.update(right.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+impl MerkleTreeManager {
+    /// Merkle root over the entry hashes in `leaves`
+    ///
+    /// An odd node at the end of a level is promoted to the next level
+    /// unchanged rather than paired with itself, so no two leaf lists share
+    /// a root (CVE-2012-2459).
+    pub fn root(leaves: &[String]) -> Option<String> {
+        let mut level: Vec<String> = leaves.iter().map(|leaf| HashChain::hash_leaf(leaf)).collect();
+        if level.is_empty() {
+            return None;
+        }
+        while level.len() > 1 {
+            level = Self::next_level(&level);
+        }
+        level.pop()
+    }
+
+    /// Sibling path for the leaf at `index`
+    pub fn proof_path(leaves: &[String], index: usize) -> Option<Vec<String>> {
+        if index >= leaves.len() {
+            return None;
+        }
+
+        let mut siblings = Vec::new();
+        let mut level: Vec<String> = leaves.iter().map(|leaf| HashChain::hash_leaf(leaf)).collect();
+        let mut position = index;
+
+        while level.len() > 1 {
+            let sibling = if position % 2 == 0 { level.get(position + 1) } else { level.get(position - 1) };
+            // A promoted node has no sibling at this level
+            if let Some(sibling) = sibling {
+                siblings.push(sibling.clone());
+            }
+            level = Self::next_level(&level);
+            position /= 2;
+        }
+
+        Some(siblings)
+    }
+
+    /// Recompute the root from a leaf, its index, the tree's leaf count and its sibling path
+    ///
+    /// `None` when the index is outside the tree or the path does not have
+    /// exactly one sibling per level the leaf is paired at.
+    pub fn root_from_path(leaf: &str, index: u64, leaf_count: u64, siblings: &[String]) -> Option<String> {
+        if index >= leaf_count {
+            return None;
+        }
+
+        let mut hash = HashChain::hash_leaf(leaf);
+        let mut position = index;
+        let mut width = leaf_count;
+        let mut siblings = siblings.iter();
+
+        while width > 1 {
+            let promoted = position % 2 == 0 && position + 1 == width;
+            if !promoted {
+                let sibling = siblings.next()?;
+                hash = if position % 2 == 0 {
+                    HashChain::hash_pair(&hash, sibling)
+                } else {
+                    HashChain::hash_pair(sibling, &hash)
+                };
+            }
+            position /= 2;
+            width = width.div_ceil(2);
+        }
+
+        siblings.next().is_none().then_some(hash)
+    }
+
+    fn next_level(level: &[String]) -> Vec<String> {
+        level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => HashChain::hash_pair(left, right),
+                [promoted] => promoted.clone(),
+                _ => unreachable!("chunks of two"),
+            })
+            .collect()
+    }
+}
+
+impl AuditTrailManager {
+    pub async fn new(ethereum_manager: Arc<EthereumManager>) -> Result<Self> {
+        Ok(Self {
+            manager_id: Uuid::new_v4(),
+            trail_storage: Arc::new(TrailStorage::new()),
+            hash_chain: Arc::new(HashChain),
+            merkle_tree_manager: Arc::new(MerkleTreeManager),
+            timestamping_service: Arc::new(TimestampingService),
+            compliance_validator: Arc::new(ComplianceValidator),
+            trail_analyzer: Arc::new(TrailAnalyzer),
+            immutability_verifier: Arc::new(ImmutabilityVerifier),
+            ethereum_manager,
+            pending_batch: Arc::new(RwLock::new(Vec::new())),
+            batch_flusher: std::sync::Mutex::new(None),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Stop the periodic flusher and anchor whatever is still pending
+    pub async fn stop(&self) -> Result<()> {
+        if let Some(flusher) = self.batch_flusher.lock().unwrap().take() {
+            flusher.abort();
+        }
+        if !self.pending_batch.read().await.is_empty() {
+            self.flush_batch_anchor().await?;
+        }
+        Ok(())
+    }
+
+    /// Anchor the pending batch every `interval`
+    pub fn start_batch_flusher(self: &Arc<Self>, interval: Duration) {
+        let manager = Arc::clone(self);
+        let flusher = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                if manager.pending_batch.read().await.is_empty() {
+                    continue;
+                }
+                if let Err(e) = manager.flush_batch_anchor().await {
+                    warn!("⚠️ Scheduled audit batch anchoring failed, will retry: {}", e);
+                }
+            }
+        });
+
+        if let Some(previous) = self.batch_flusher.lock().unwrap().replace(flusher) {
+            previous.abort();
+        }
+    }
+
+    /// Queue an entry for the next batch anchor, returning the pending count
+    pub async fn queue_for_batch(&self, entry_id: Uuid) -> usize {
+        let mut pending = self.pending_batch.write().await;
+        pending.push(entry_id);
+        pending.len()
+    }
+
+    /// Anchor the Merkle root of all pending entries in a single transaction
+    ///
+    /// Each anchored entry records the batch root and its leaf index so an
+    /// inclusion proof can be checked against the on-chain root. The batch
+    /// is taken off the queue before the RPC, so entries queued meanwhile
+    /// wait for the next flush; if anchoring fails the batch is queued again.
+    pub async fn flush_batch_anchor(&self) -> Result<String> {
+        let batch: Vec<Uuid> = std::mem::take(&mut *self.pending_batch.write().await);
+        if batch.is_empty() {
+            return Err(anyhow!("No audit entries pending batch anchoring"));
+        }
+
+        match self.anchor_batch(&batch).await {
+            Ok(tx_hash) => Ok(tx_hash),
+            Err(e) => {
+                let mut pending = self.pending_batch.write().await;
+                pending.splice(0..0, batch);
+                Err(e)
+            }
+        }
+    }
+
+    async fn anchor_batch(&self, batch: &[Uuid]) -> Result<String> {
+        let leaves = self.batch_leaves(batch).await?;
+        let batch_root = MerkleTreeManager::root(&leaves).ok_or_else(|| anyhow!("Audit batch is empty"))?;
+        let tx_hash = self.ethereum_manager.anchor_hash(&batch_root).await?;
+
+        let anchors: HashMap<Uuid, BatchAnchor> = batch
+            .iter()
+            .enumerate()
+            .map(|(index, entry_id)| {
+                let anchor = BatchAnchor {
+                    batch_root: batch_root.clone(),
+                    leaf_index: index as u64,
+                    batch_size: leaves.len() as u64,
+                    tx_hash: tx_hash.clone(),
+                };
+                (*entry_id, anchor)
+            })
+            .collect();
+        self.trail_storage.apply_batch_anchors(&anchors).await;
+
+        info!("⛓️ Anchored batch of {} audit entries under root {} in {}", batch.len(), batch_root, tx_hash);
+        Ok(tx_hash)
+    }
+
+    /// Build a proof that `entry_id` is included in its anchored batch root
+    pub async fn generate_batch_proof(&self, entry_id: Uuid) -> Result<MerkleProof> {
+        let entries = self.trail_storage.entries().await;
+        let anchor = entries
+            .iter()
+            .find(|entry| entry.entry_id == entry_id)
+            .ok_or_else(|| anyhow!("Audit entry {} not found", entry_id))?
+            .batch_anchor
+            .clone()
+            .ok_or_else(|| anyhow!("Audit entry {} has not been batch anchored", entry_id))?;
+
+        let mut batch: Vec<&AuditTrailEntry> = entries
+            .iter()
+            .filter(|entry| {
+                entry
+                    .batch_anchor
+                    .as_ref()
+                    .map_or(false, |other| other.batch_root == anchor.batch_root && other.tx_hash == anchor.tx_hash)
+            })
+            .collect();
+        batch.sort_by_key(|entry| entry.batch_anchor.as_ref().map(|other| other.leaf_index));
+
+        let leaves: Vec<String> = batch.iter().map(|entry| entry.current_hash.clone()).collect();
+        let index = anchor.leaf_index as usize;
+        let siblings = MerkleTreeManager::proof_path(&leaves, index)
+            .ok_or_else(|| anyhow!("Audit entry {} is outside its batch", entry_id))?;
+
+        Ok(MerkleProof {
+            entry_id,
+            leaf_hash: leaves[index].clone(),
+            leaf_index: anchor.leaf_index,
+            leaf_count: leaves.len() as u64,
+            siblings,
+            root: anchor.batch_root,
+        })
+    }
+
+    pub async fn initialize_with_genesis(&self, genesis: AuditTrailEntry) -> Result<()> {
+        self.trail_storage.append_if_empty(genesis).await;
+        Ok(())
+    }
+
+    /// Append a new entry chained to the current head
+    pub async fn create_entry(&self, event_type: AuditEventType, details: AuditDetails) -> Result<AuditTrailEntry> {
+        let entry = self.trail_storage.append_chained(|previous_hash| AuditTrailEntry {
+            entry_id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            event_type,
+            actor: details.actor,
+            action: details.action,
+            resource: details.resource,
+            previous_hash,
+            current_hash: String::new(),
+            metadata: details.metadata,
+            digital_signature: String::new(),
+            blockchain_tx_hash: None,
+            ipfs_hash: None,
+            compliance_status: ComplianceStatus::Compliant,
+            batch_anchor: None,
+        }).await;
+        Ok(entry)
+    }
+
+    /// Walk the stored trail and recompute every hash link
+    ///
+    /// Reports the first entry whose `previous_hash` does not point at its
+    /// predecessor or whose `current_hash` does not match its contents.
+    pub async fn verify_chain_integrity(&self) -> Result<ChainIntegrityReport> {
+        let entries = self.trail_storage.entries().await;
+        let mut first_broken_link = None;
+
+        for (index, entry) in entries.iter().enumerate() {
+            if index > 0 {
+                let expected_previous = &entries[index - 1].current_hash;
+                if &entry.previous_hash != expected_previous {
+                    first_broken_link = Some(BrokenLink {
+                        index: index as u64,
+                        entry_id: entry.entry_id,
+                        kind: BrokenLinkKind::PreviousHashMismatch,
+                        expected_hash: expected_previous.clone(),
+                        actual_hash: entry.previous_hash.clone(),
+                    });
+                    break;
+                }
+            }
+
+            let expected_current = HashChain::hash_entry(entry);
+            if entry.current_hash != expected_current {
+                first_broken_link = Some(BrokenLink {
+                    index: index as u64,
+                    entry_id: entry.entry_id,
+                    kind: BrokenLinkKind::CurrentHashMismatch,
+                    expected_hash: expected_current,
+                    actual_hash: entry.current_hash.clone(),
+                });
+                break;
+            }
+        }
+
+        Ok(ChainIntegrityReport {
+            entries_checked: entries.len() as u64,
+            intact: first_broken_link.is_none(),
+            first_broken_link,
+            checked_at: Utc::now(),
+        })
+    }
+
+    pub async fn health_check(&self) -> Result<AuditTrailHealth> {
+        let report = self.verify_chain_integrity().await?;
+        Ok(AuditTrailHealth {
+            integrity_verified: report.intact,
+            total_entries: report.entries_checked,
+            integrity_report: report,
+        })
+    }
+
+    /// Current Merkle root over all stored entries
+    pub async fn merkle_root(&self) -> Result<String> {
+        let leaves = self.leaf_hashes().await;
+        MerkleTreeManager::root(&leaves).ok_or_else(|| anyhow!("Audit trail is empty"))
+    }
+
+    /// Build a proof that `entry_id` is included in the current Merkle root
+    pub async fn generate_inclusion_proof(&self, entry_id: Uuid) -> Result<MerkleProof> {
+        let entries = self.trail_storage.entries().await;
+        let index = entries
+            .iter()
+            .position(|entry| entry.entry_id == entry_id)
+            .ok_or_else(|| anyhow!("Audit entry {} not found", entry_id))?;
+
+        let leaves: Vec<String> = entries.iter().map(|entry| entry.current_hash.clone()).collect();
+        let siblings = MerkleTreeManager::proof_path(&leaves, index)
+            .ok_or_else(|| anyhow!("Audit entry {} is outside the trail", entry_id))?;
+        let root = MerkleTreeManager::root(&leaves).ok_or_else(|| anyhow!("Audit trail is empty"))?;
+
+        info!("🌳 Generated inclusion proof for audit entry {} at index {}", entry_id, index);
+        Ok(MerkleProof {
+            entry_id,
+            leaf_hash: leaves[index].clone(),
+            leaf_index: index as u64,
+            leaf_count: leaves.len() as u64,
+            siblings,
+            root,
+        })
+    }
+
+    /// Check a proof against an independently obtained root
+    pub async fn verify_inclusion_proof(&self, proof: &MerkleProof, root: &str) -> Result<bool> {
+        let computed =
+            MerkleTreeManager::root_from_path(&proof.leaf_hash, proof.leaf_index, proof.leaf_count, &proof.siblings);
+        Ok(computed.as_deref() == Some(root))
+    }
+
+    /// Leaf hashes of the given entries, in the given order
+    async fn batch_leaves(&self, entry_ids: &[Uuid]) -> Result<Vec<String>> {
+        let hashes: HashMap<Uuid, String> = self
+            .trail_storage
+            .entries()
+            .await
+            .into_iter()
+            .map(|entry| (entry.entry_id, entry.current_hash))
+            .collect();
+
+        entry_ids
+            .iter()
+            .map(|entry_id| {
+                hashes
+                    .get(entry_id)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Pending audit entry {} not found", entry_id))
+            })
+            .collect()
+    }
+
+    async fn leaf_hashes(&self) -> Vec<String> {
+        self.trail_storage
+            .entries()
+            .await
+            .into_iter()
+            .map(|entry| entry.current_hash)
+            .collect()
+    }
+}
+
+impl AuditDetails {
+    pub fn new(actor: &str, action: &str, resource: &str) -> Self {
+        Self {
+            actor: actor.to_string(),
+            action: action.to_string(),
+            resource: resource.to_string(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    pub fn with_metadata(mut self, key: &str, value: serde_json::Value) -> Self {
+        self.metadata.insert(key.to_string(), value);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_manager() -> AuditTrailManager {
+        let ethereum_manager = Arc::new(EthereumManager::new().await.unwrap());
+        AuditTrailManager::new(ethereum_manager).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_inclusion_proof_round_trip() {
+        let manager = test_manager().await;
+        let mut ids = Vec::new();
+        for i in 0..5 {
+            let details = AuditDetails::new("auditor", "check", &format!("record-{}", i));
+            ids.push(manager.create_entry(AuditEventType::ComplianceCheck, details).await.unwrap().entry_id);
+        }
+
+        let root = manager.merkle_root().await.unwrap();
+        for id in ids {
+            let proof = manager.generate_inclusion_proof(id).await.unwrap();
+            assert!(manager.verify_inclusion_proof(&proof, &root).await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_chain_integrity_reports_first_broken_link() {
+        let manager = test_manager().await;
+        for resource in ["a", "b", "c"] {
+            manager
+                .create_entry(AuditEventType::ComplianceCheck, AuditDetails::new("auditor", "check", resource))
+                .await
+                .unwrap();
+        }
+        assert!(manager.verify_chain_integrity().await.unwrap().intact);
+
+        manager.trail_storage.entries.write().await[1].actor = "intruder".to_string();
+
+        let report = manager.verify_chain_integrity().await.unwrap();
+        let broken = report.first_broken_link.unwrap();
+        assert!(!report.intact);
+        assert_eq!(broken.index, 1);
+        assert_eq!(broken.kind, BrokenLinkKind::CurrentHashMismatch);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_entries_extend_a_single_chain() {
+        let manager = Arc::new(test_manager().await);
+        let creators: Vec<_> = (0..64)
+            .map(|i| {
+                let manager = manager.clone();
+                tokio::spawn(async move {
+                    let details = AuditDetails::new("auditor", "check", &format!("record-{}", i));
+                    manager.create_entry(AuditEventType::ComplianceCheck, details).await.unwrap()
+                })
+            })
+            .collect();
+        for creator in creators {
+            creator.await.unwrap();
+        }
+
+        let entries = manager.trail_storage.entries().await;
+        assert_eq!(entries.len(), 64);
+        assert!(manager.verify_chain_integrity().await.unwrap().intact);
+    }
+
+    #[tokio::test]
+    async fn test_tampered_proof_fails() {
+        let manager = test_manager().await;
+        let entry = manager
+            .create_entry(AuditEventType::ComplianceCheck, AuditDetails::new("auditor", "check", "a"))
+            .await
+            .unwrap();
+        manager
+            .create_entry(AuditEventType::ComplianceCheck, AuditDetails::new("auditor", "check", "b"))
+            .await
+            .unwrap();
+
+        let root = manager.merkle_root().await.unwrap();
+        let mut proof = manager.generate_inclusion_proof(entry.entry_id).await.unwrap();
+        proof.leaf_hash = HashChain::hash_pair("forged", "entry");
+        assert!(!manager.verify_inclusion_proof(&proof, &root).await.unwrap());
+    }
+
+    #[test]
+    fn test_odd_leaves_are_promoted_not_duplicated() {
+        let leaves: Vec<String> = ["a", "b", "c"].iter().map(|leaf| leaf.to_string()).collect();
+        let mut duplicated = leaves.clone();
+        duplicated.push("c".to_string());
+
+        let root = MerkleTreeManager::root(&leaves).unwrap();
+        assert_ne!(Some(&root), MerkleTreeManager::root(&duplicated).as_ref());
+
+        // An inner node cannot pass for a leaf of a shorter tree
+        let inner = HashChain::hash_pair(&HashChain::hash_leaf("a"), &HashChain::hash_leaf("b"));
+        let shortened = MerkleTreeManager::root(&[inner, "c".to_string()]).unwrap();
+        assert_ne!(root, shortened);
+
+        for index in 0..leaves.len() {
+            let path = MerkleTreeManager::proof_path(&leaves, index).unwrap();
+            let computed = MerkleTreeManager::root_from_path(&leaves[index], index as u64, 3, &path);
+            assert_eq!(computed, Some(root.clone()));
+        }
+        // "c" is promoted past the first level, so its path is one hash long
+        assert_eq!(MerkleTreeManager::proof_path(&leaves, 2).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_proof_length_must_match_the_tree_depth() {
+        let leaves: Vec<String> = (0..4).map(|leaf| leaf.to_string()).collect();
+        let root = MerkleTreeManager::root(&leaves).unwrap();
+        let path = MerkleTreeManager::proof_path(&leaves, 1).unwrap();
+
+        let mut padded = path.clone();
+        padded.push(root.clone());
+        assert_eq!(MerkleTreeManager::root_from_path(&leaves[1], 1, 4, &padded), None);
+        assert_eq!(MerkleTreeManager::root_from_path(&leaves[1], 1, 4, &path[..1]), None);
+        assert_eq!(MerkleTreeManager::root_from_path(&leaves[1], 4, 4, &path), None);
+        assert_eq!(MerkleTreeManager::root_from_path(&leaves[1], 1, 4, &path), Some(root));
+    }
+
+    #[tokio::test]
+    async fn test_batch_proof_verifies_against_batch_root() {
+        let manager = test_manager().await;
+        let mut ids = Vec::new();
+        for resource in ["a", "b", "c"] {
+            let entry = manager
+                .create_entry(AuditEventType::ComplianceCheck, AuditDetails::new("auditor", "check", resource))
+                .await
+                .unwrap();
+            assert_eq!(manager.queue_for_batch(entry.entry_id).await, ids.len() + 1);
+            ids.push(entry.entry_id);
+        }
+
+        let leaves = manager.batch_leaves(&ids).await.unwrap();
+        let batch_root = MerkleTreeManager::root(&leaves).unwrap();
+        let anchors = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| {
+                let anchor = BatchAnchor {
+                    batch_root: batch_root.clone(),
+                    leaf_index: index as u64,
+                    batch_size: ids.len() as u64,
+                    tx_hash: "0xabc".to_string(),
+                };
+                (*id, anchor)
+            })
+            .collect();
+        manager.trail_storage.apply_batch_anchors(&anchors).await;
+
+        for id in ids {
+            let proof = manager.generate_batch_proof(id).await.unwrap();
+            assert_eq!(proof.root, batch_root);
+            assert!(manager.verify_inclusion_proof(&proof, &batch_root).await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_with_empty_batch_fails() {
+        let manager = test_manager().await;
+        assert!(manager.flush_batch_anchor().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_requeues_the_batch() {
+        let manager = test_manager().await;
+        let entry = manager
+            .create_entry(AuditEventType::ComplianceCheck, AuditDetails::new("auditor", "check", "a"))
+            .await
+            .unwrap();
+        manager.queue_for_batch(entry.entry_id).await;
+
+        // No audit network is configured, so anchoring fails
+        assert!(manager.flush_batch_anchor().await.is_err());
+        assert_eq!(*manager.pending_batch.read().await, vec![entry.entry_id]);
+    }
+}
@@ -0,0 +1,434 @@
+//! Consensus and Finality
+//!
+//! Decides when a block can no longer be reorganized away: from the chain's
+//! finalized checkpoint where proof-of-stake finalizes blocks, and from
+//! confirmation depth where finality is only probabilistic. Audit anchors
+//! count as immutable only once their block is final.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::{OnceCell, RwLock};
+use tracing::{info, warn};
+
+use crate::ethereum::parse_hex_u64;
+use crate::*;
+
+/// How a network decides that a block is final
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FinalityMechanism {
+    /// Blocks at or below the chain's finalized checkpoint are final
+    /// (Casper FFG on Ethereum; rollups inherit it from their L1 batches)
+    Checkpoint,
+    /// Blocks are final once they have `depth` confirmations, counting their own block
+    ConfirmationDepth { depth: u64 },
+}
+
+/// Finality of an anchoring transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnchorFinality {
+    /// Not in a canonical block: still in the mempool, dropped, or reorganized out
+    Unconfirmed,
+    /// Mined, but the block could still be reorganized away
+    Pending { block_number: u64 },
+    Final { block_number: u64 },
+}
+
+impl AnchorFinality {
+    pub fn is_final(&self) -> bool {
+        matches!(self, AnchorFinality::Final { .. })
+    }
+}
+
+/// Final anchors remembered before the oldest are evicted and re-checked on the chain
+const MAX_FINAL_ANCHORS: usize = 10_000;
+
+/// Anchors checked at once by `audit_anchor_summary`
+const MAX_CONCURRENT_ANCHOR_CHECKS: usize = 8;
+
+/// Anchor transactions already seen final, the oldest evicted beyond `capacity`
+///
+/// An evicted anchor is simply read from the chain again on its next check.
+#[derive(Debug)]
+pub struct FinalAnchors {
+    capacity: usize,
+    blocks: HashMap<String, u64>,
+    order: VecDeque<String>,
+}
+
+impl FinalAnchors {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), blocks: HashMap::new(), order: VecDeque::new() }
+    }
+
+    /// Block number of a final anchor
+    pub fn get(&self, tx_hash: &str) -> Option<u64> {
+        self.blocks.get(tx_hash).copied()
+    }
+
+    pub fn insert(&mut self, tx_hash: String, block_number: u64) {
+        if self.blocks.insert(tx_hash.clone(), block_number).is_some() {
+            return;
+        }
+        self.order.push_back(tx_hash);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.blocks.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Pending-vs-final state of the audit trail's anchors
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnchorFinalitySummary {
+    /// Anchors not yet final, including unconfirmed ones and those that could not be checked
+    pub pending: u32,
+    pub finalized: u32,
+}
+
+impl ConsensusEngine {
+    pub async fn new(ethereum_manager: Arc<EthereumManager>, bitcoin_manager: Arc<BitcoinManager>) -> Result<Self> {
+        Ok(Self {
+            settings: RwLock::new(ConsensusSettings::maximum_performance()),
+            ethereum_manager,
+            bitcoin_manager,
+            final_anchors: RwLock::new(FinalAnchors::new(MAX_FINAL_ANCHORS)),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        let settings = self.settings.read().await;
+        info!(
+            "🤝 Consensus engine started ({}, minimum reorg depth {})",
+            settings.consensus_algorithm, settings.finality_blocks
+        );
+        Ok(())
+    }
+
+    pub async fn configure(&self, settings: ConsensusSettings) {
+        *self.settings.write().await = settings;
+    }
+
+    pub async fn settings(&self) -> ConsensusSettings {
+        self.settings.read().await.clone()
+    }
+
+    /// Finality mechanism applied to `network`
+    pub async fn finality_mechanism(&self, network: &NetworkConfig) -> FinalityMechanism {
+        finality_mechanism(network, self.settings.read().await.finality_blocks)
+    }
+
+    /// Whether `block` on `network` is final
+    ///
+    /// Checkpoint networks compare against the node's `finalized` block and
+    /// fall back to confirmation depth if the node does not report one.
+    pub async fn is_final(&self, network: &str, block: u64) -> Result<bool> {
+        let network = self.network(network).await?;
+        Ok(self.last_final_block(&network).await?.is_some_and(|last_final| block <= last_final))
+    }
+
+    /// Finality of an audit anchor transaction
+    ///
+    /// Bitcoin anchors are read from the wallet's view of the transaction,
+    /// all others from their receipt on the audit network. Anchors are
+    /// re-read until final, so one whose block is reorganized away drops
+    /// back to `Unconfirmed`.
+    pub async fn anchor_finality(&self, tx_hash: &str) -> Result<AnchorFinality> {
+        self.check_anchor(tx_hash, &OnceCell::new()).await
+    }
+
+    /// Count the distinct anchors of `entries` that are pending and final
+    ///
+    /// Entries still waiting for a batch have no anchor and are not counted.
+    /// Anchors not yet known to be final are checked a few at a time, and
+    /// the audit network's last final block is looked up once for all of them.
+    pub async fn audit_anchor_summary(&self, entries: &[AuditTrailEntry]) -> AnchorFinalitySummary {
+        let anchors: HashSet<&str> = entries
+            .iter()
+            .filter_map(|entry| entry.blockchain_tx_hash.as_deref())
+            .collect();
+
+        let audit_last_final = OnceCell::new();
+        let audit_last_final = &audit_last_final;
+        let checks: Vec<_> = stream::iter(anchors)
+            .map(|tx_hash| async move { (tx_hash, self.check_anchor(tx_hash, audit_last_final).await) })
+            .buffer_unordered(MAX_CONCURRENT_ANCHOR_CHECKS)
+            .collect()
+            .await;
+
+        let mut summary = AnchorFinalitySummary::default();
+        for (tx_hash, finality) in checks {
+            match finality {
+                Ok(finality) if finality.is_final() => summary.finalized += 1,
+                Ok(_) => summary.pending += 1,
+                Err(e) => {
+                    warn!("Could not check finality of audit anchor {}: {}", tx_hash, e);
+                    summary.pending += 1;
+                }
+            }
+        }
+        summary
+    }
+
+    /// Finality of an anchor, sharing the audit network's last final block through `audit_last_final`
+    async fn check_anchor(&self, tx_hash: &str, audit_last_final: &OnceCell<Option<u64>>) -> Result<AnchorFinality> {
+        if let Some(block_number) = self.final_anchors.read().await.get(tx_hash) {
+            return Ok(AnchorFinality::Final { block_number });
+        }
+
+        let (network_id, finality) = if is_bitcoin_txid(tx_hash) {
+            self.bitcoin_anchor_finality(tx_hash).await?
+        } else {
+            self.ethereum_anchor_finality(tx_hash, audit_last_final).await?
+        };
+        if let AnchorFinality::Final { block_number } = finality {
+            info!("🔒 Audit anchor {} final in block {} on {}", tx_hash, block_number, network_id);
+            self.final_anchors.write().await.insert(tx_hash.to_string(), block_number);
+        }
+        Ok(finality)
+    }
+
+    async fn ethereum_anchor_finality(
+        &self,
+        tx_hash: &str,
+        last_final: &OnceCell<Option<u64>>,
+    ) -> Result<(String, AnchorFinality)> {
+        let network_id = self.ethereum_manager.audit_network_id().await?;
+        let network = self.network(&network_id).await?;
+        let receipt = self
+            .ethereum_manager
+            .rpc_with_failover(network.chain_id, "eth_getTransactionReceipt", json!([tx_hash]))
+            .await?;
+        let Some(block_number) = receipt.get("blockNumber").filter(|number| !number.is_null()) else {
+            return Ok((network_id, AnchorFinality::Unconfirmed));
+        };
+        let block_number = parse_hex_u64(block_number)?;
+
+        let last_final = *last_final.get_or_try_init(|| self.last_final_block(&network)).await?;
+        let finality = match last_final {
+            Some(last_final) if block_number <= last_final => AnchorFinality::Final { block_number },
+            _ => AnchorFinality::Pending { block_number },
+        };
+        Ok((network_id, finality))
+    }
+
+    /// Bitcoin anchors are final at the network's confirmation depth, never less than the minimum reorg depth
+    async fn bitcoin_anchor_finality(&self, txid: &str) -> Result<(String, AnchorFinality)> {
+        let network = self.bitcoin_manager.network().await?;
+        let depth = confirmation_depth(&network, self.settings.read().await.finality_blocks);
+        let finality = match self.bitcoin_manager.anchor_block(txid).await? {
+            None => AnchorFinality::Unconfirmed,
+            Some((block_number, confirmations)) if u64::from(confirmations) >= depth => {
+                AnchorFinality::Final { block_number }
+            }
+            Some((block_number, _)) => AnchorFinality::Pending { block_number },
+        };
+        Ok((network.network_id, finality))
+    }
+
+    /// Highest block on `network` that is final, `None` while the chain is shorter than its confirmation depth
+    ///
+    /// Checkpoint networks use the node's `finalized` block and fall back to
+    /// confirmation depth if the node does not report one.
+    async fn last_final_block(&self, network: &NetworkConfig) -> Result<Option<u64>> {
+        let min_depth = self.settings.read().await.finality_blocks;
+
+        if finality_mechanism(network, min_depth) == FinalityMechanism::Checkpoint {
+            match self.finalized_block(network).await {
+                Ok(finalized) => return Ok(Some(finalized)),
+                Err(e) => warn!(
+                    "⚠️ No finalized checkpoint from {} ({}), falling back to confirmation depth",
+                    network.network_id, e
+                ),
+            }
+        }
+
+        let head = self.head_block(network).await?;
+        Ok(deepest_final_block(head, confirmation_depth(network, min_depth)))
+    }
+
+    async fn network(&self, network_id: &str) -> Result<NetworkConfig> {
+        if let Some(network) = self.ethereum_manager.networks.read().await.get(network_id) {
+            return Ok(network.clone());
+        }
+        match self.bitcoin_manager.network.read().await.as_ref() {
+            Some(network) if network.network_id == network_id => Ok(network.clone()),
+            _ => Err(anyhow!("Network {} is not configured", network_id)),
+        }
+    }
+
+    async fn head_block(&self, network: &NetworkConfig) -> Result<u64> {
+        match network.blockchain_type {
+            BlockchainType::Bitcoin => self.bitcoin_manager.block_height().await,
+            _ => parse_hex_u64(
+                &self
+                    .ethereum_manager
+                    .rpc_with_failover(network.chain_id, "eth_blockNumber", json!([]))
+                    .await?,
+            ),
+        }
+    }
+
+    async fn finalized_block(&self, network: &NetworkConfig) -> Result<u64> {
+        let block = self
+            .ethereum_manager
+            .rpc_with_failover(network.chain_id, "eth_getBlockByNumber", json!(["finalized", false]))
+            .await?;
+        match block.get("number") {
+            Some(number) => parse_hex_u64(number),
+            None if block == Value::Null => Err(anyhow!("node has no finalized block")),
+            None => Err(anyhow!("finalized block has no number")),
+        }
+    }
+}
+
+/// Finality mechanism of a network
+///
+/// Ethereum and the rollups settling on it finalize by checkpoint; every
+/// other network is treated as probabilistic and needs its confirmation
+/// depth, never less than `min_depth`.
+pub fn finality_mechanism(network: &NetworkConfig, min_depth: u32) -> FinalityMechanism {
+    match network.blockchain_type {
+        BlockchainType::Ethereum | BlockchainType::Arbitrum | BlockchainType::Optimism => FinalityMechanism::Checkpoint,
+        _ => FinalityMechanism::ConfirmationDepth { depth: confirmation_depth(network, min_depth) },
+    }
+}
+
+fn confirmation_depth(network: &NetworkConfig, min_depth: u32) -> u64 {
+    network.confirmation_blocks.max(min_depth).max(1) as u64
+}
+
+/// Highest block with at least `depth` confirmations when `head` is the chain tip, the block itself counting as one
+fn deepest_final_block(head: u64, depth: u64) -> Option<u64> {
+    (head + 1).checked_sub(depth)
+}
+
+/// Bitcoin txids are bare 64-digit hex, Ethereum transaction hashes are `0x`-prefixed
+fn is_bitcoin_txid(tx_hash: &str) -> bool {
+    tx_hash.len() == 64 && tx_hash.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(blockchain_type: BlockchainType, confirmation_blocks: u32) -> NetworkConfig {
+        NetworkConfig {
+            network_id: "test".to_string(),
+            blockchain_type,
+            rpc_endpoint: "http://localhost:8545".to_string(),
+            websocket_endpoint: None,
+            chain_id: 1337,
+            gas_settings: GasSettings::default(),
+            confirmation_blocks,
+            max_fee_per_gas: 0,
+            priority_fee_per_gas: 0,
+            tx_type: TxType::Eip1559,
+            connection_pool: ConnectionPoolConfig::default(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_finality_mechanism_follows_network_consensus() {
+        assert_eq!(finality_mechanism(&network(BlockchainType::Ethereum, 12), 1), FinalityMechanism::Checkpoint);
+        assert_eq!(finality_mechanism(&network(BlockchainType::Arbitrum, 1), 1), FinalityMechanism::Checkpoint);
+        assert_eq!(
+            finality_mechanism(&network(BlockchainType::Polygon, 20), 1),
+            FinalityMechanism::ConfirmationDepth { depth: 20 }
+        );
+        // The configured minimum reorg depth wins over a shallower network setting
+        assert_eq!(
+            finality_mechanism(&network(BlockchainType::Bitcoin, 3), 6),
+            FinalityMechanism::ConfirmationDepth { depth: 6 }
+        );
+
+        // Block 100 has 20 confirmations at head 119 and one at head 100
+        assert_eq!(deepest_final_block(119, 20), Some(100));
+        assert_eq!(deepest_final_block(100, 1), Some(100));
+        assert_eq!(deepest_final_block(99, 1), Some(99));
+        assert_eq!(deepest_final_block(5, 10), None);
+    }
+
+    #[test]
+    fn test_final_anchors_evict_the_oldest_beyond_capacity() {
+        let mut anchors = FinalAnchors::new(2);
+        anchors.insert("0xa".to_string(), 1);
+        anchors.insert("0xb".to_string(), 2);
+        anchors.insert("0xa".to_string(), 1);
+        anchors.insert("0xc".to_string(), 3);
+
+        assert_eq!(anchors.get("0xa"), None);
+        assert_eq!(anchors.get("0xb"), Some(2));
+        assert_eq!(anchors.get("0xc"), Some(3));
+        assert_eq!(anchors.order.len(), 2);
+
+        assert!(is_bitcoin_txid(&"ab".repeat(32)));
+        assert!(!is_bitcoin_txid(&format!("0x{}", "ab".repeat(32))));
+    }
+
+    #[tokio::test]
+    async fn test_anchor_summary_looks_up_the_final_block_once() {
+        use crate::ethereum::tests::{manager_with_providers, mock_rpc, network as evm_network};
+
+        let (url, calls) = mock_rpc(|method, params| match method {
+            "eth_getTransactionReceipt" => match params[0].as_str() {
+                Some("0xpending") => Some(json!({ "blockNumber": "0x70" })),
+                Some("0xmempool") => Some(Value::Null),
+                _ => Some(json!({ "blockNumber": "0x10" })),
+            },
+            "eth_getBlockByNumber" => Some(json!({ "number": "0x64" })),
+            _ => None,
+        })
+        .await;
+        let ethereum_manager = Arc::new(manager_with_providers(&[&url]).await);
+        let mainnet = NetworkConfig { network_id: "mainnet".to_string(), chain_id: 1, ..evm_network(TxType::Eip1559) };
+        ethereum_manager.networks.write().await.insert("mainnet".to_string(), mainnet);
+        let bitcoin_manager = Arc::new(BitcoinManager::new().await.unwrap());
+        let engine = ConsensusEngine::new(ethereum_manager.clone(), bitcoin_manager).await.unwrap();
+
+        let audit = AuditTrailManager::new(ethereum_manager).await.unwrap();
+        for tx_hash in ["0xfinal1", "0xfinal2", "0xfinal3", "0xpending", "0xmempool"] {
+            let entry = audit
+                .create_entry(AuditEventType::ComplianceCheck, AuditDetails::new("tester", "Check", "policy"))
+                .await
+                .unwrap();
+            audit.trail_storage.record_anchor(entry.entry_id, tx_hash).await;
+        }
+
+        let summary = engine.audit_anchor_summary(&audit.trail_storage.entries().await).await;
+        assert_eq!((summary.pending, summary.finalized), (2, 3));
+        {
+            let calls = calls.lock().unwrap();
+            assert_eq!(calls.iter().filter(|method| *method == "eth_getBlockByNumber").count(), 1);
+            assert_eq!(calls.iter().filter(|method| *method == "eth_getTransactionReceipt").count(), 5);
+        }
+
+        // Final anchors are remembered and not read again
+        engine.audit_anchor_summary(&audit.trail_storage.entries().await).await;
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.iter().filter(|method| *method == "eth_getTransactionReceipt").count(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_unanchored_entries_are_not_counted_and_unknown_networks_fail() {
+        let ethereum_manager = Arc::new(EthereumManager::new().await.unwrap());
+        let bitcoin_manager = Arc::new(BitcoinManager::new().await.unwrap());
+        let engine = ConsensusEngine::new(ethereum_manager.clone(), bitcoin_manager).await.unwrap();
+
+        let audit = AuditTrailManager::new(ethereum_manager).await.unwrap();
+        audit
+            .create_entry(AuditEventType::ComplianceCheck, AuditDetails::new("tester", "Check", "policy"))
+            .await
+            .unwrap();
+        let summary = engine.audit_anchor_summary(&audit.trail_storage.entries().await).await;
+        assert_eq!((summary.pending, summary.finalized), (0, 0));
+
+        assert!(engine.is_final("unknown", 1).await.is_err());
+    }
+}
@@ -0,0 +1,380 @@
+//! Cross-Chain Bridge
+//!
+//! Compliance validation across the configured chains. Every result is
+//! attested by the bridge's validator set and is only accepted once a
+//! threshold of valid validator signatures has been collected.
+//!
+//! The validator set is external: its public keys are loaded from a
+//! configured file, and this node only signs for the validators whose
+//! private keys it has been given.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::*;
+
+/// A validator's signature over a cross-chain validation result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorAttestation {
+    pub validator_id: String,
+    /// Hex-encoded Ed25519 signature over the result digest
+    pub signature: String,
+}
+
+/// Attestation failures
+#[derive(Debug, thiserror::Error)]
+pub enum AttestationError {
+    #[error("insufficient validator signatures: {valid} valid, {required} required")]
+    InsufficientSignatures { valid: usize, required: usize },
+    #[error("invalid signature from validator {validator_id}: {reason}")]
+    InvalidSignature { validator_id: String, reason: String },
+    #[error("signature threshold {threshold} cannot be met by {validators} validators")]
+    UnreachableThreshold { threshold: usize, validators: usize },
+    #[error("key of local signer {validator_id} does not match its registered public key")]
+    SignerKeyMismatch { validator_id: String },
+}
+
+/// Persisted validator set: validator ids with their hex-encoded Ed25519 public keys
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidatorSetFile {
+    pub validators: HashMap<String, String>,
+}
+
+impl ValidatorSet {
+    /// An empty set; no result is accepted until validators are loaded
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            validators: RwLock::new(HashMap::new()),
+            local_signers: RwLock::new(HashMap::new()),
+            threshold: RwLock::new(1),
+        })
+    }
+
+    /// Replace the validator set with the public keys persisted at `path`
+    ///
+    /// Local signers that are no longer members are dropped.
+    pub async fn load(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let file: ValidatorSetFile = serde_json::from_slice(&tokio::fs::read(path).await?)
+            .map_err(|e| anyhow!("Invalid validator set {}: {}", path.display(), e))?;
+
+        let mut validators = HashMap::new();
+        for (validator_id, public_key) in file.validators {
+            let bytes: [u8; 32] = hex::decode(&public_key).ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(|| anyhow!("Validator {} has a malformed public key", validator_id))?;
+            let key = VerifyingKey::from_bytes(&bytes)
+                .map_err(|e| anyhow!("Validator {} has an invalid public key: {}", validator_id, e))?;
+            validators.insert(validator_id, key);
+        }
+
+        self.local_signers.write().await.retain(|validator_id, _| validators.contains_key(validator_id));
+        info!("🌉 Loaded {} bridge validators from {}", validators.len(), path.display());
+        *self.validators.write().await = validators;
+        Ok(())
+    }
+
+    /// Sign as `validator_id` with the hex-encoded seed stored at `key_path`
+    ///
+    /// The key must belong to a validator already in the set.
+    pub async fn load_local_signer(&self, validator_id: &str, key_path: impl AsRef<Path>) -> Result<()> {
+        let key_path = key_path.as_ref();
+        let text = tokio::fs::read_to_string(key_path).await
+            .map_err(|e| anyhow!("Failed to read validator key {}: {}", key_path.display(), e))?;
+        let seed: [u8; 32] = hex::decode(text.trim()).ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| anyhow!("Validator key {} is not a hex-encoded 32-byte seed", key_path.display()))?;
+        self.add_local_signer(validator_id, SigningKey::from_bytes(&seed)).await
+    }
+
+    async fn add_local_signer(&self, validator_id: &str, key: SigningKey) -> Result<()> {
+        let registered = self.validators.read().await.get(validator_id).copied();
+        if registered != Some(key.verifying_key()) {
+            return Err(AttestationError::SignerKeyMismatch { validator_id: validator_id.to_string() }.into());
+        }
+        self.local_signers.write().await.insert(validator_id.to_string(), key);
+        Ok(())
+    }
+
+    /// Register a remote validator by its public key
+    pub async fn register_validator(&self, validator_id: &str, public_key: VerifyingKey) {
+        self.validators.write().await.insert(validator_id.to_string(), public_key);
+    }
+
+    pub async fn set_threshold(&self, threshold: usize) -> Result<()> {
+        let validators = self.validators.read().await.len();
+        if threshold == 0 || threshold > validators {
+            return Err(AttestationError::UnreachableThreshold { threshold, validators }.into());
+        }
+        *self.threshold.write().await = threshold;
+        Ok(())
+    }
+
+    pub async fn threshold(&self) -> usize {
+        *self.threshold.read().await
+    }
+
+    pub async fn validator_count(&self) -> usize {
+        self.validators.read().await.len()
+    }
+
+    /// Sign a digest with every locally operated validator
+    async fn sign_locally(&self, digest: &[u8]) -> Vec<ValidatorAttestation> {
+        let mut attestations: Vec<ValidatorAttestation> = self
+            .local_signers
+            .read()
+            .await
+            .iter()
+            .map(|(validator_id, key)| ValidatorAttestation {
+                validator_id: validator_id.clone(),
+                signature: hex::encode(key.sign(digest).to_bytes()),
+            })
+            .collect();
+        attestations.sort_by(|a, b| a.validator_id.cmp(&b.validator_id));
+        attestations
+    }
+
+    /// Check one attestation against the registered public keys
+    async fn check(&self, attestation: &ValidatorAttestation, digest: &[u8]) -> Result<(), AttestationError> {
+        let invalid = |reason: &str| AttestationError::InvalidSignature {
+            validator_id: attestation.validator_id.clone(),
+            reason: reason.to_string(),
+        };
+
+        let validators = self.validators.read().await;
+        let public_key = validators
+            .get(&attestation.validator_id)
+            .ok_or_else(|| invalid("not a member of the validator set"))?;
+        let bytes: [u8; 64] = hex::decode(&attestation.signature).ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| invalid("malformed signature"))?;
+
+        public_key
+            .verify(digest, &Signature::from_bytes(&bytes))
+            .map_err(|_| invalid("signature does not match the result"))
+    }
+}
+
+impl CrossChainBridge {
+    pub async fn new() -> Result<Self> {
+        Ok(Self {
+            bridge_id: Uuid::new_v4(),
+            supported_chains: Arc::new(RwLock::new(HashMap::new())),
+            bridge_contracts: Arc::new(RwLock::new(HashMap::new())),
+            relay_network: Arc::new(RelayNetwork),
+            validator_set: Arc::new(ValidatorSet::new()?),
+            bridge_security: Arc::new(BridgeSecurityManager),
+            liquidity_manager: Arc::new(LiquidityManager),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!(
+            "🌉 Cross-chain bridge started with {} validators, threshold {}",
+            self.validator_set.validator_count().await,
+            self.validator_set.threshold().await
+        );
+        Ok(())
+    }
+
+    /// Register the configured networks as bridgeable chains
+    pub async fn configure_chains(&self, networks: &HashMap<String, NetworkConfig>) {
+        let mut chains = self.supported_chains.write().await;
+        for (network_id, network) in networks {
+            chains.insert(network_id.clone(), ChainConfig {
+                chain_name: network_id.clone(),
+                chain_id: network.chain_id,
+                blockchain_type: network.blockchain_type.clone(),
+                enabled: network.enabled,
+            });
+        }
+    }
+
+    pub async fn bridges_operational(&self) -> Result<bool> {
+        let validators = self.validator_set.validator_count().await;
+        Ok(validators > 0 && validators >= self.validator_set.threshold().await)
+    }
+
+    /// Validate compliance data against each chain and collect validator attestations
+    ///
+    /// Unknown or disabled chains are reported as non-compliant. The result
+    /// is rejected unless it carries a quorum of valid signatures.
+    pub async fn validate_compliance_across_chains(
+        &self,
+        chains: Vec<String>,
+        compliance_data: ComplianceData,
+    ) -> Result<CrossChainValidationResult> {
+        let supported = self.supported_chains.read().await;
+        let chain_results: HashMap<String, bool> = chains
+            .into_iter()
+            .map(|chain| {
+                let available = supported.get(&chain).map_or(false, |config| config.enabled);
+                if !available {
+                    warn!("🌉 Chain {} is not available to the bridge", chain);
+                }
+                (chain, available)
+            })
+            .collect();
+        drop(supported);
+
+        let mut result = CrossChainValidationResult {
+            validation_id: Uuid::new_v4(),
+            compliant: !chain_results.is_empty() && chain_results.values().all(|&ok| ok),
+            chain_results,
+            compliance_data_hash: hex::encode(Sha256::digest(serde_json::to_vec(&compliance_data)?)),
+            validated_at: Utc::now(),
+            attestations: Vec::new(),
+        };
+        result.attestations = self.validator_set.sign_locally(&attestation_digest(&result)).await;
+
+        self.verify_attestations(&result).await?;
+        info!(
+            "🌉 Cross-chain validation {} attested by {} validators",
+            result.validation_id,
+            result.attestations.len()
+        );
+        Ok(result)
+    }
+
+    /// Check that a result carries a quorum of valid validator signatures
+    ///
+    /// Any signature that fails to verify is reported as
+    /// `AttestationError::InvalidSignature`; too few distinct valid
+    /// signatures as `AttestationError::InsufficientSignatures`.
+    pub async fn verify_attestations(&self, result: &CrossChainValidationResult) -> Result<bool> {
+        let digest = attestation_digest(result);
+        let mut signers = HashSet::new();
+
+        for attestation in &result.attestations {
+            self.validator_set.check(attestation, &digest).await?;
+            signers.insert(attestation.validator_id.as_str());
+        }
+
+        let required = self.validator_set.threshold().await;
+        if signers.len() < required {
+            return Err(AttestationError::InsufficientSignatures { valid: signers.len(), required }.into());
+        }
+        Ok(true)
+    }
+}
+
+/// Digest validators sign, covering every field except the attestations
+fn attestation_digest(result: &CrossChainValidationResult) -> Vec<u8> {
+    let mut chains: Vec<_> = result.chain_results.iter().collect();
+    chains.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(result.validation_id.as_bytes());
+    for (chain, compliant) in chains {
+        hasher.update(chain.as_bytes());
+        hasher.update([*compliant as u8]);
+    }
+    hasher.update([result.compliant as u8]);
+    hasher.update(result.compliance_data_hash.as_bytes());
+    hasher.update(result.validated_at.to_rfc3339().as_bytes());
+    hasher.finalize().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A bridge with a persisted three-validator set, signing for all of them
+    async fn bridge_with_chain() -> CrossChainBridge {
+        let directory = tempfile::tempdir().unwrap();
+        let keys: Vec<_> = (1..=3u8).map(|seed| SigningKey::from_bytes(&[seed; 32])).collect();
+        let file = ValidatorSetFile {
+            validators: keys
+                .iter()
+                .enumerate()
+                .map(|(index, key)| (format!("validator-{}", index), hex::encode(key.verifying_key().to_bytes())))
+                .collect(),
+        };
+        let set_path = directory.path().join("validators.json");
+        std::fs::write(&set_path, serde_json::to_vec(&file).unwrap()).unwrap();
+
+        let bridge = CrossChainBridge::new().await.unwrap();
+        bridge.validator_set.load(&set_path).await.unwrap();
+        for (index, key) in keys.iter().enumerate() {
+            let key_path = directory.path().join(format!("validator-{}.key", index));
+            std::fs::write(&key_path, hex::encode(key.to_bytes())).unwrap();
+            bridge.validator_set.load_local_signer(&format!("validator-{}", index), &key_path).await.unwrap();
+        }
+        bridge.validator_set.set_threshold(2).await.unwrap();
+        bridge.supported_chains.write().await.insert("polygon_mainnet".to_string(), ChainConfig {
+            chain_name: "polygon_mainnet".to_string(),
+            chain_id: 137,
+            blockchain_type: BlockchainType::Polygon,
+            enabled: true,
+        });
+        bridge
+    }
+
+    fn compliance_data() -> ComplianceData {
+        ComplianceData {
+            regulation: "MiCA".to_string(),
+            subject: "token-issuer".to_string(),
+            payload: serde_json::json!({"reserve_ratio": 1.02}),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validation_result_is_attested() {
+        let bridge = bridge_with_chain().await;
+        let result = bridge
+            .validate_compliance_across_chains(vec!["polygon_mainnet".to_string()], compliance_data())
+            .await
+            .unwrap();
+
+        assert!(result.compliant);
+        assert!(bridge.verify_attestations(&result).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_attestation_errors_are_distinguished() {
+        let bridge = bridge_with_chain().await;
+        let result = bridge
+            .validate_compliance_across_chains(vec!["polygon_mainnet".to_string()], compliance_data())
+            .await
+            .unwrap();
+
+        let mut tampered = result.clone();
+        tampered.compliant = !tampered.compliant;
+        let error = bridge.verify_attestations(&tampered).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AttestationError>(),
+            Some(AttestationError::InvalidSignature { .. })
+        ));
+
+        let mut short = result.clone();
+        short.attestations.truncate(1);
+        let error = bridge.verify_attestations(&short).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AttestationError>(),
+            Some(AttestationError::InsufficientSignatures { valid: 1, required: 2 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_set_accepts_nothing() {
+        let bridge = CrossChainBridge::new().await.unwrap();
+        assert!(!bridge.bridges_operational().await.unwrap());
+
+        let impostor = SigningKey::from_bytes(&[9; 32]);
+        let error = bridge.validator_set.add_local_signer("validator-0", impostor).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AttestationError>(),
+            Some(AttestationError::SignerKeyMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,331 @@
+//! DeFi Integration
+//!
+//! Compliance risk scoring of DeFi protocols from their audit status, TVL
+//! concentration, admin-key controls and sanctions exposure.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{info, warn};
+
+use crate::*;
+
+/// Composite score at or above which a protocol is high risk
+const HIGH_RISK_SCORE: f64 = 0.6;
+/// Composite score at or above which a protocol is medium risk
+const MEDIUM_RISK_SCORE: f64 = 0.3;
+/// Timelock delay after which admin actions are considered reviewable by users
+const REVIEWABLE_TIMELOCK_HOURS: u32 = 48;
+/// High-risk alerts buffered for slow subscribers
+const ALERT_CHANNEL_CAPACITY: usize = 64;
+
+/// What is known about a protocol for risk scoring
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolProfile {
+    pub name: String,
+    pub audit_status: AuditStatus,
+    /// TVL held by each pool or depositor, in any common unit
+    pub tvl_distribution: Vec<f64>,
+    pub admin_control: AdminControl,
+    /// Addresses seen interacting with the protocol's contracts
+    pub interacting_addresses: Vec<String>,
+}
+
+/// Who can change or pause the protocol's contracts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AdminControl {
+    /// No admin functions remain
+    Immutable,
+    Timelock { delay_hours: u32 },
+    Multisig { threshold: u32, signers: u32 },
+    SingleKey,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ProtocolRiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+/// One signal contributing to a protocol's risk score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskFactor {
+    pub name: String,
+    /// 0.0 (no risk) to 1.0
+    pub risk: f64,
+    pub weight: f64,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolRiskScore {
+    pub protocol: String,
+    /// Weighted risk of all factors, 0.0 (no risk) to 1.0
+    pub score: f64,
+    pub level: ProtocolRiskLevel,
+    pub factors: Vec<RiskFactor>,
+    pub assessed_at: DateTime<Utc>,
+}
+
+/// Raised when a protocol is assessed as high risk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolRiskAlert {
+    pub protocol: String,
+    pub risk: ProtocolRiskScore,
+}
+
+impl DeFiIntegrator {
+    /// Interacting addresses are screened with `address_screener`, the
+    /// screener the transaction path blocks sanctioned recipients with
+    pub async fn new(address_screener: Arc<AddressScreener>) -> Result<Self> {
+        let (alerts, _) = broadcast::channel(ALERT_CHANNEL_CAPACITY);
+        Ok(Self {
+            protocols: RwLock::new(HashMap::new()),
+            address_screener,
+            alerts,
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("💰 DeFi integrator started with {} protocol profiles", self.protocols.read().await.len());
+        Ok(())
+    }
+
+    pub async fn register_protocol(&self, profile: ProtocolProfile) {
+        self.protocols.write().await.insert(profile.name.clone(), profile);
+    }
+
+    /// High-risk assessments as they happen
+    pub fn subscribe_alerts(&self) -> broadcast::Receiver<ProtocolRiskAlert> {
+        self.alerts.subscribe()
+    }
+
+    /// Score a registered protocol's compliance risk
+    ///
+    /// Any interaction with a sanctioned address makes the protocol high risk
+    /// regardless of its other factors. High-risk scores are published to
+    /// [`Self::subscribe_alerts`].
+    pub async fn assess_protocol(&self, protocol: &str) -> Result<ProtocolRiskScore> {
+        let profile = self
+            .protocols
+            .read()
+            .await
+            .get(protocol)
+            .cloned()
+            .ok_or_else(|| anyhow!("No risk profile registered for protocol {}", protocol))?;
+
+        let sanctions = self.sanctions_factor(&profile).await;
+        let sanctioned = sanctions.risk > 0.0;
+        let factors = vec![
+            audit_factor(&profile.audit_status),
+            concentration_factor(&profile.tvl_distribution),
+            admin_factor(&profile.admin_control),
+            sanctions,
+        ];
+
+        let total_weight: f64 = factors.iter().map(|factor| factor.weight).sum();
+        let score = factors.iter().map(|factor| factor.risk * factor.weight).sum::<f64>() / total_weight;
+        let level = if sanctioned || score >= HIGH_RISK_SCORE {
+            ProtocolRiskLevel::High
+        } else if score >= MEDIUM_RISK_SCORE {
+            ProtocolRiskLevel::Medium
+        } else {
+            ProtocolRiskLevel::Low
+        };
+
+        let risk = ProtocolRiskScore {
+            protocol: protocol.to_string(),
+            score,
+            level,
+            factors,
+            assessed_at: Utc::now(),
+        };
+        if level == ProtocolRiskLevel::High {
+            warn!("🚨 Protocol {} assessed as high risk ({:.2})", protocol, score);
+            // No subscribers is not an error; the assessment is still returned
+            let _ = self.alerts.send(ProtocolRiskAlert { protocol: protocol.to_string(), risk: risk.clone() });
+        } else {
+            info!("💰 Protocol {} assessed as {:?} risk ({:.2})", protocol, level, score);
+        }
+        Ok(risk)
+    }
+
+    /// Share of interacting addresses the screener reports as sanctioned
+    ///
+    /// Addresses that cannot be screened count as half a risk each rather
+    /// than as clear.
+    async fn sanctions_factor(&self, profile: &ProtocolProfile) -> RiskFactor {
+        let (mut exposed, mut unscreened) = (0usize, 0usize);
+        for address in &profile.interacting_addresses {
+            match self.address_screener.screen(address).await {
+                Ok(result) if result.is_sanctioned() => exposed += 1,
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("🛂 Could not screen {} for {}: {}", address, profile.name, e);
+                    unscreened += 1;
+                }
+            }
+        }
+
+        let total = profile.interacting_addresses.len();
+        let risk = if exposed > 0 {
+            (exposed as f64 / total as f64).max(0.7)
+        } else if unscreened > 0 {
+            0.5 * unscreened as f64 / total as f64
+        } else {
+            0.0
+        };
+        RiskFactor {
+            name: "sanctions_exposure".to_string(),
+            risk,
+            weight: 0.25,
+            detail: format!("{} of {} interacting addresses sanctioned, {} not screened", exposed, total, unscreened),
+        }
+    }
+}
+
+fn audit_factor(status: &AuditStatus) -> RiskFactor {
+    let risk = match status {
+        AuditStatus::Audited => 0.0,
+        AuditStatus::InProgress => 0.5,
+        AuditStatus::NotAudited => 0.8,
+        AuditStatus::Failed => 1.0,
+    };
+    RiskFactor {
+        name: "contract_audit".to_string(),
+        risk,
+        weight: 0.3,
+        detail: format!("{:?}", status),
+    }
+}
+
+/// Herfindahl index of TVL shares: 1.0 when a single holder has everything
+fn concentration_factor(tvl_distribution: &[f64]) -> RiskFactor {
+    let total: f64 = tvl_distribution.iter().filter(|tvl| **tvl > 0.0).sum();
+    let (risk, detail) = if total <= 0.0 {
+        (0.5, "TVL distribution unknown".to_string())
+    } else {
+        let index: f64 = tvl_distribution
+            .iter()
+            .filter(|tvl| **tvl > 0.0)
+            .map(|tvl| (tvl / total).powi(2))
+            .sum();
+        (index, format!("Herfindahl index {:.2} across {} holders", index, tvl_distribution.len()))
+    };
+    RiskFactor {
+        name: "tvl_concentration".to_string(),
+        risk,
+        weight: 0.2,
+        detail,
+    }
+}
+
+/// Risk of the admin keys being abused or compromised
+///
+/// A multisig is only as strong as the number of keys needed to act, so a
+/// 1-of-N multisig scores like a single key and each extra required signer
+/// lowers the risk.
+fn admin_factor(control: &AdminControl) -> RiskFactor {
+    let risk = match control {
+        AdminControl::Immutable => 0.0,
+        AdminControl::Timelock { delay_hours } if *delay_hours >= REVIEWABLE_TIMELOCK_HOURS => 0.2,
+        AdminControl::Timelock { .. } => 0.4,
+        AdminControl::Multisig { threshold, signers } if *threshold <= 1 || threshold > signers => 1.0,
+        AdminControl::Multisig { threshold, .. } => (1.0 / *threshold as f64).clamp(0.3, 0.7),
+        AdminControl::SingleKey => 1.0,
+    };
+    RiskFactor {
+        name: "admin_controls".to_string(),
+        risk,
+        weight: 0.25,
+        detail: format!("{:?}", control),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    struct StaticSource(Vec<&'static str>);
+
+    #[async_trait]
+    impl SanctionsListSource for StaticSource {
+        async fn fetch(&self) -> Result<SanctionsList> {
+            let party = SanctionsMatch { list: "SDN".to_string(), entity: "1".to_string(), currency: "ETH".to_string() };
+            Ok(SanctionsList {
+                source: "OFAC SDN".to_string(),
+                fetched_at: Utc::now(),
+                addresses: self.0.iter().map(|address| (address.to_string(), vec![party.clone()])).collect(),
+            })
+        }
+    }
+
+    async fn integrator(sanctioned: Vec<&'static str>) -> DeFiIntegrator {
+        DeFiIntegrator::new(Arc::new(AddressScreener::new(Arc::new(StaticSource(sanctioned))))).await.unwrap()
+    }
+
+    fn profile(name: &str, audit_status: AuditStatus, tvl: Vec<f64>, admin_control: AdminControl) -> ProtocolProfile {
+        ProtocolProfile {
+            name: name.to_string(),
+            audit_status,
+            tvl_distribution: tvl,
+            admin_control,
+            interacting_addresses: vec!["0xAAA".to_string(), "0xBBB".to_string()],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audited_decentralized_protocol_scores_below_risky_one() {
+        let defi = integrator(vec![]).await;
+        let mut alerts = defi.subscribe_alerts();
+        defi.register_protocol(profile(
+            "dex",
+            AuditStatus::Audited,
+            vec![10.0; 20],
+            AdminControl::Timelock { delay_hours: 72 },
+        ))
+        .await;
+        defi.register_protocol(profile("vault", AuditStatus::NotAudited, vec![95.0, 5.0], AdminControl::SingleKey))
+            .await;
+
+        let dex = defi.assess_protocol("dex").await.unwrap();
+        let vault = defi.assess_protocol("vault").await.unwrap();
+        assert_eq!(dex.level, ProtocolRiskLevel::Low);
+        assert_eq!(vault.level, ProtocolRiskLevel::High);
+        assert_eq!(dex.factors.len(), 4);
+        assert_eq!(alerts.try_recv().unwrap().protocol, "vault");
+        assert!(alerts.try_recv().is_err());
+
+        assert!(defi.assess_protocol("unknown").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sanctioned_interaction_makes_protocol_high_risk() {
+        let defi = integrator(vec!["0xaaa"]).await;
+        defi.register_protocol(profile(
+            "dex",
+            AuditStatus::Audited,
+            vec![10.0; 20],
+            AdminControl::Timelock { delay_hours: 72 },
+        ))
+        .await;
+
+        let exposed = defi.assess_protocol("dex").await.unwrap();
+        assert_eq!(exposed.level, ProtocolRiskLevel::High);
+    }
+
+    #[test]
+    fn test_more_required_signers_lower_admin_risk() {
+        let risk = |threshold, signers| admin_factor(&AdminControl::Multisig { threshold, signers }).risk;
+        assert_eq!(risk(1, 1), admin_factor(&AdminControl::SingleKey).risk);
+        assert_eq!(risk(1, 5), 1.0);
+        assert!(risk(2, 3) > risk(4, 7));
+        assert_eq!(risk(6, 5), 1.0);
+    }
+}
@@ -0,0 +1,2135 @@
+//! Ethereum Integration
+//!
+//! JSON-RPC access to the configured EVM networks, live fee estimation and
+//! anchoring of audit trail entries.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use ethers::abi::{Abi, Event, Function, Hash, RawLog};
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, Eip1559TransactionRequest};
+use futures::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, RwLock, Semaphore, SemaphorePermit, TryAcquireError};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::*;
+
+pub use ethers::abi::Token;
+
+/// Number of recent blocks sampled for fee estimation
+const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Longest wait between event subscription reconnects
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// JSON-RPC request ids used on event subscription sockets
+const SUBSCRIBE_REQUEST_ID: u64 = 1;
+const BACKFILL_REQUEST_ID: u64 = 2;
+const HEAD_REQUEST_ID: u64 = 3;
+
+/// Blocks per `eth_getLogs` request when backfilling, below common provider range limits
+const BACKFILL_PAGE_BLOCKS: u64 = 1_000;
+
+/// Blocks behind the newest delivered log for which delivered logs are remembered,
+/// deep enough to recognise logs a reorg re-emits or removes
+const REORG_DEPTH_BLOCKS: u64 = 128;
+
+/// JSON-RPC methods without side effects, safe to retry and fail over
+const READ_ONLY_METHODS: &[&str] = &[
+    "eth_accounts",
+    "eth_blockNumber",
+    "eth_call",
+    "eth_chainId",
+    "eth_estimateGas",
+    "eth_feeHistory",
+    "eth_gasPrice",
+    "eth_getBalance",
+    "eth_getBlockByHash",
+    "eth_getBlockByNumber",
+    "eth_getCode",
+    "eth_getLogs",
+    "eth_getStorageAt",
+    "eth_getTransactionByHash",
+    "eth_getTransactionCount",
+    "eth_getTransactionReceipt",
+    "eth_maxPriorityFeePerGas",
+    "eth_syncing",
+    "net_version",
+];
+
+/// How a JSON-RPC method may be repeated after a failed attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RetryPolicy {
+    /// Read-only: retry and fail over freely
+    Retry,
+    /// Signed raw transaction: rebroadcasting the same bytes cannot create a
+    /// second transaction, but a node may already have it
+    Rebroadcast,
+    /// Any other state-changing call: one attempt, as its outcome is unknown after a failure
+    Once,
+}
+
+impl RetryPolicy {
+    fn for_method(method: &str) -> Self {
+        if READ_ONLY_METHODS.contains(&method) {
+            RetryPolicy::Retry
+        } else if method == "eth_sendRawTransaction" {
+            RetryPolicy::Rebroadcast
+        } else {
+            RetryPolicy::Once
+        }
+    }
+}
+
+/// Transaction to be priced or submitted
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransactionRequest {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub data: Vec<u8>,
+    pub value: u128,
+    pub gas_limit: Option<u64>,
+}
+
+impl TransactionRequest {
+    /// A contract creation carrying no bytecode yet, used for pricing
+    pub fn contract_deployment() -> Self {
+        Self::default()
+    }
+
+    fn to_rpc_json(&self) -> Value {
+        let mut tx = json!({
+            "data": format!("0x{}", hex::encode(&self.data)),
+            "value": format!("0x{:x}", self.value),
+        });
+        if let Some(from) = &self.from {
+            tx["from"] = json!(from);
+        }
+        if let Some(to) = &self.to {
+            tx["to"] = json!(to);
+        }
+        if let Some(gas_limit) = self.gas_limit {
+            tx["gas"] = json!(format!("0x{:x}", gas_limit));
+        }
+        tx
+    }
+}
+
+/// Transaction envelope misconfiguration, surfaced instead of a rejected transaction
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionTypeError {
+    #[error("network {network_id} is configured for EIP-1559 but reports no base fee; set tx_type to Legacy")]
+    Eip1559Unsupported { network_id: String },
+    #[error("network {network_id} uses legacy transactions but has no gas price configured")]
+    MissingGasPrice { network_id: String },
+    #[error("network {network_id}: priority fee {priority_fee} exceeds max fee {max_fee}")]
+    PriorityFeeAboveMaxFee { network_id: String, priority_fee: u64, max_fee: u64 },
+}
+
+/// Suggested gas parameters for a transaction
+///
+/// For legacy networks `max_fee_per_gas` holds the gas price and the
+/// priority fee is zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasEstimate {
+    pub gas_limit: u64,
+    pub base_fee_per_gas: u64,
+    pub max_fee_per_gas: u64,
+    pub max_priority_fee_per_gas: u64,
+    pub source: GasEstimateSource,
+}
+
+/// Where a gas estimate came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GasEstimateSource {
+    /// `eth_feeHistory` / `eth_maxPriorityFeePerGas` from a live provider
+    LiveNetwork,
+    /// The network's static `GasSettings`
+    StaticDefaults,
+}
+
+/// Ethereum subsystem health
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EthereumHealth {
+    pub healthy: bool,
+    pub connected_networks: u32,
+    pub avg_tx_time: f64,
+    pub gas_optimization_active: bool,
+    /// Requests that only succeeded after failing over to another provider
+    pub failover_requests: u64,
+    /// Connection pool utilization per provider
+    pub provider_pools: HashMap<String, PoolUtilization>,
+}
+
+/// HTTP connection reuse and concurrency limits for a provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionPoolConfig {
+    /// Idle keep-alive connections kept open to the endpoint
+    pub max_idle_connections: usize,
+    /// How long an idle connection is kept before it is closed
+    pub idle_timeout: Duration,
+    /// TCP keep-alive probe interval, `None` to disable
+    pub tcp_keepalive: Option<Duration>,
+    /// Requests allowed in flight at once; further requests wait for a slot
+    pub max_concurrent_requests: usize,
+}
+
+impl Default for ConnectionPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_connections: 16,
+            idle_timeout: Duration::from_secs(90),
+            tcp_keepalive: Some(Duration::from_secs(60)),
+            max_concurrent_requests: 32,
+        }
+    }
+}
+
+/// HTTP client and request limiter for one provider
+///
+/// Built once from the provider's `ConnectionPoolConfig` so that bursts of
+/// requests reuse warm keep-alive connections. Clones share the same pool
+/// and counters.
+#[derive(Debug, Clone)]
+pub struct ProviderConnection {
+    client: reqwest::Client,
+    permits: Arc<Semaphore>,
+    max_concurrent_requests: usize,
+    max_idle_connections: usize,
+    counters: Arc<PoolCounters>,
+}
+
+#[derive(Debug, Default)]
+struct PoolCounters {
+    total_requests: AtomicU64,
+    waited_requests: AtomicU64,
+    peak_in_flight: AtomicU64,
+}
+
+/// Snapshot of a provider's connection pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolUtilization {
+    pub in_flight: usize,
+    pub max_concurrent_requests: usize,
+    pub max_idle_connections: usize,
+    /// `in_flight / max_concurrent_requests`
+    pub utilization: f64,
+    pub peak_in_flight: u64,
+    pub total_requests: u64,
+    /// Requests that had to wait for a free slot
+    pub waited_requests: u64,
+}
+
+impl ProviderConnection {
+    pub fn new(config: &ConnectionPoolConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .pool_max_idle_per_host(config.max_idle_connections)
+            .pool_idle_timeout(config.idle_timeout)
+            .tcp_keepalive(config.tcp_keepalive)
+            .build()?;
+        let max_concurrent_requests = config.max_concurrent_requests.max(1);
+
+        Ok(Self {
+            client,
+            permits: Arc::new(Semaphore::new(max_concurrent_requests)),
+            max_concurrent_requests,
+            max_idle_connections: config.max_idle_connections,
+            counters: Arc::new(PoolCounters::default()),
+        })
+    }
+
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Wait up to `timeout` for a request slot, released when the permit is dropped
+    pub async fn acquire(&self, timeout: Duration) -> Result<SemaphorePermit<'_>> {
+        self.counters.total_requests.fetch_add(1, Ordering::Relaxed);
+
+        let permit = match self.permits.try_acquire() {
+            Ok(permit) => permit,
+            Err(TryAcquireError::NoPermits) => {
+                self.counters.waited_requests.fetch_add(1, Ordering::Relaxed);
+                tokio::time::timeout(timeout, self.permits.acquire())
+                    .await
+                    .map_err(|_| anyhow!("no free connection slot within {:?}", timeout))??
+            }
+            Err(TryAcquireError::Closed) => return Err(anyhow!("connection pool is closed")),
+        };
+
+        self.counters.peak_in_flight.fetch_max(self.in_flight() as u64, Ordering::Relaxed);
+        Ok(permit)
+    }
+
+    fn in_flight(&self) -> usize {
+        self.max_concurrent_requests - self.permits.available_permits()
+    }
+
+    pub fn utilization(&self) -> PoolUtilization {
+        let in_flight = self.in_flight();
+        PoolUtilization {
+            in_flight,
+            max_concurrent_requests: self.max_concurrent_requests,
+            max_idle_connections: self.max_idle_connections,
+            utilization: in_flight as f64 / self.max_concurrent_requests as f64,
+            peak_in_flight: self.counters.peak_in_flight.load(Ordering::Relaxed),
+            total_requests: self.counters.total_requests.load(Ordering::Relaxed),
+            waited_requests: self.counters.waited_requests.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl EthereumManager {
+    pub async fn new() -> Result<Self> {
+        let providers = Arc::new(RwLock::new(HashMap::new()));
+        let networks = Arc::new(RwLock::new(HashMap::new()));
+        let failover_requests = Arc::new(AtomicU64::new(0));
+        let address_screener = Arc::new(AddressScreener::new(Arc::new(OfacSdnSource::default())));
+
+        let wallet_manager = Arc::new(WalletManager::new());
+        let transaction_manager = Arc::new(TransactionManager {
+            providers: providers.clone(),
+            networks: networks.clone(),
+            failover_requests: failover_requests.clone(),
+            address_screener: address_screener.clone(),
+            wallet_manager: wallet_manager.clone(),
+            send_lock: tokio::sync::Mutex::new(()),
+        });
+
+        let contract_manager = Arc::new(ContractManager {
+            providers: providers.clone(),
+            networks: networks.clone(),
+            failover_requests: failover_requests.clone(),
+            transaction_manager: transaction_manager.clone(),
+        });
+        let mev_protector = Arc::new(MEVProtector {
+            settings: RwLock::new(MevProtectionSettings::default()),
+            http_client: reqwest::Client::new(),
+            providers: providers.clone(),
+            networks: networks.clone(),
+            failover_requests: failover_requests.clone(),
+            transaction_manager: transaction_manager.clone(),
+        });
+
+        Ok(Self {
+            manager_id: Uuid::new_v4(),
+            contract_manager,
+            event_listener: Arc::new(EventListener { networks: networks.clone() }),
+            providers,
+            networks,
+            failover_requests,
+            address_screener,
+            wallet_manager,
+            transaction_manager,
+            gas_optimizer: Arc::new(GasOptimizer),
+            mev_protector,
+            layer2_integrator: Arc::new(Layer2Integrator),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("⛓️ Ethereum manager started with {} providers", self.providers.read().await.len());
+        Ok(())
+    }
+
+    /// Register the EVM networks and an HTTP provider for each
+    ///
+    /// A provider already registered for a network is rebuilt when the
+    /// network's endpoint or connection pool settings have changed.
+    pub async fn configure_networks(&self, networks: &HashMap<String, NetworkConfig>) {
+        let mut configured = self.networks.write().await;
+        let mut providers = self.providers.write().await;
+
+        for (network_id, network) in networks {
+            if !network.enabled || matches!(network.blockchain_type, BlockchainType::Bitcoin) {
+                continue;
+            }
+            configured.insert(network_id.clone(), network.clone());
+            let unchanged = providers.get(network_id).is_some_and(|provider| {
+                provider.endpoint == network.rpc_endpoint && provider.connection_pool() == &network.connection_pool
+            });
+            if unchanged {
+                continue;
+            }
+            let pool = network.connection_pool.clone();
+            match EthereumProvider::new(network_id, &network.rpc_endpoint, network.chain_id, pool) {
+                Ok(provider) => {
+                    providers.insert(network_id.clone(), provider);
+                }
+                Err(e) => warn!("Could not build HTTP client for provider {}: {}", network_id, e),
+            }
+        }
+    }
+
+    /// Estimate gas for `tx` on `network_id` from live fee data, see [`TransactionManager::estimate_gas`]
+    pub async fn estimate_gas(&self, network_id: &str, tx: &TransactionRequest) -> Result<GasEstimate> {
+        self.transaction_manager.estimate_gas(network_id, tx).await
+    }
+
+    /// Anchor an audit entry's hash on the primary Ethereum network
+    pub async fn store_audit_entry(&self, entry: &AuditTrailEntry) -> Result<String> {
+        let tx_hash = self.anchor_hash(&entry.current_hash).await?;
+        info!("⛓️ Audit entry {} anchored in {}", entry.entry_id, tx_hash);
+        Ok(tx_hash)
+    }
+
+    /// Commit a hash on the audit network as transaction data, sent to the signing account itself
+    pub async fn anchor_hash(&self, hash: &str) -> Result<String> {
+        let network_id = self.audit_network_id().await?;
+        let sender = self.wallet_manager.address().await?;
+
+        let tx = TransactionRequest {
+            to: Some(sender),
+            data: hash.as_bytes().to_vec(),
+            ..TransactionRequest::default()
+        };
+        let tx_hash = self.transaction_manager.send(&network_id, tx).await?;
+
+        info!("⛓️ Hash {} anchored on {} in {}", hash, network_id, tx_hash);
+        Ok(tx_hash)
+    }
+
+    /// Register an additional provider, e.g. a backup endpoint for a chain
+    pub async fn register_provider(&self, provider: EthereumProvider) {
+        self.providers.write().await.insert(provider.provider_id.clone(), provider);
+    }
+
+    /// Perform a JSON-RPC call on `chain_id`, failing over between providers
+    ///
+    /// Read-only methods are tried on each healthy provider for the chain in
+    /// turn, each up to its own `retry_attempts` with its own `timeout`. A
+    /// provider that exhausts its attempts is marked `Degraded` and skipped
+    /// until `check_provider_health` clears it. `eth_sendRawTransaction` is
+    /// retried the same way, checking by transaction hash whether an earlier
+    /// attempt got through. Any other method is sent once, to one provider.
+    pub async fn rpc_with_failover(&self, chain_id: u64, method: &str, params: Value) -> Result<Value> {
+        rpc_with_failover(&self.providers, &self.failover_requests, chain_id, method, params).await
+    }
+
+    /// Probe every provider and update its health status
+    pub async fn check_provider_health(&self) {
+        let providers: Vec<EthereumProvider> = self.providers.read().await.values().cloned().collect();
+
+        for provider in providers {
+            let status = match provider.rpc("eth_blockNumber", json!([])).await {
+                Ok(_) => ProviderHealth::Healthy,
+                Err(e) => {
+                    warn!("Provider {} failed health check: {}", provider.provider_id, e);
+                    ProviderHealth::Offline
+                }
+            };
+            self.set_provider_health(&provider.provider_id, status).await;
+        }
+    }
+
+    async fn set_provider_health(&self, provider_id: &str, status: ProviderHealth) {
+        set_provider_health(&self.providers, provider_id, status).await;
+    }
+
+    pub async fn health_check(&self) -> Result<EthereumHealth> {
+        self.check_provider_health().await;
+
+        let providers = self.providers.read().await;
+        let connected = providers
+            .values()
+            .filter(|provider| matches!(provider.health_status, ProviderHealth::Healthy))
+            .map(|provider| provider.chain_id)
+            .collect::<std::collections::HashSet<_>>()
+            .len() as u32;
+
+        Ok(EthereumHealth {
+            healthy: providers.is_empty() || connected > 0,
+            connected_networks: connected,
+            avg_tx_time: 0.0,
+            gas_optimization_active: true,
+            failover_requests: self.failover_requests.load(Ordering::Relaxed),
+            provider_pools: providers
+                .values()
+                .map(|provider| (provider.provider_id.clone(), provider.connection.utilization()))
+                .collect(),
+        })
+    }
+
+    /// Network used for audit anchoring: the enabled Ethereum network with the lowest chain id
+    pub(crate) async fn audit_network_id(&self) -> Result<String> {
+        self.networks
+            .read()
+            .await
+            .values()
+            .filter(|network| matches!(network.blockchain_type, BlockchainType::Ethereum))
+            .min_by_key(|network| network.chain_id)
+            .map(|network| network.network_id.clone())
+            .ok_or_else(|| anyhow!("No Ethereum network configured for audit anchoring"))
+    }
+}
+
+impl ContractManager {
+    /// Call a contract method on `network_id` without sending a transaction and decode its return values
+    ///
+    /// `args` are checked against the method's ABI inputs before encoding;
+    /// overloads are resolved by arity and argument types.
+    pub async fn call(
+        &self,
+        network_id: &str,
+        address: &str,
+        abi: &Value,
+        method: &str,
+        args: &[Token],
+    ) -> Result<Vec<Token>> {
+        let function = resolve_function(abi, method, args)?;
+        let tx = TransactionRequest {
+            to: Some(address.to_string()),
+            data: function.encode_input(args)?,
+            ..TransactionRequest::default()
+        };
+        let chain_id = self.network(network_id).await?.chain_id;
+
+        let output = self
+            .rpc(chain_id, "eth_call", json!([tx.to_rpc_json(), "latest"]))
+            .await?;
+        let output = output
+            .as_str()
+            .ok_or_else(|| anyhow!("eth_call returned non-string data"))
+            .and_then(hex_decode)?;
+        Ok(function.decode_output(&output)?)
+    }
+
+    /// The 32-byte word at storage `slot` of `address` on `network_id`
+    pub async fn storage_at(&self, network_id: &str, address: &str, slot: &str) -> Result<[u8; 32]> {
+        let chain_id = self.network(network_id).await?.chain_id;
+        let word = self
+            .rpc(chain_id, "eth_getStorageAt", json!([address, slot, "latest"]))
+            .await?;
+        let word = word.as_str().ok_or_else(|| anyhow!("eth_getStorageAt returned non-string data"))?;
+        // Some providers drop leading zeros
+        let digits = word.trim_start_matches("0x");
+        if digits.len() > 64 {
+            return Err(anyhow!("eth_getStorageAt returned more than 32 bytes: {}", word));
+        }
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(format!("{:0>64}", digits), &mut bytes)
+            .map_err(|e| anyhow!("invalid storage word {}: {}", word, e))?;
+        Ok(bytes)
+    }
+
+    /// The deployed bytecode of `address` on `network_id`, empty if it holds no contract
+    pub async fn code_at(&self, network_id: &str, address: &str) -> Result<Vec<u8>> {
+        let chain_id = self.network(network_id).await?.chain_id;
+        let code = self.rpc(chain_id, "eth_getCode", json!([address, "latest"])).await?;
+        code.as_str()
+            .ok_or_else(|| anyhow!("eth_getCode returned non-string data"))
+            .and_then(hex_decode)
+    }
+
+    /// Send a state-changing contract call on `network_id`, signed with the wallet manager's key
+    ///
+    /// Returns the transaction hash. See [`TransactionManager::send`] for
+    /// sanctions screening and rebroadcasting.
+    pub async fn send(
+        &self,
+        network_id: &str,
+        address: &str,
+        abi: &Value,
+        method: &str,
+        args: &[Token],
+    ) -> Result<String> {
+        let function = resolve_function(abi, method, args)?;
+        let tx = TransactionRequest {
+            to: Some(address.to_string()),
+            data: function.encode_input(args)?,
+            ..TransactionRequest::default()
+        };
+
+        let tx_hash = self.transaction_manager.send(network_id, tx).await?;
+        info!("📜 {} sent to {} on {} in {}", function.name, address, network_id, tx_hash);
+        Ok(tx_hash)
+    }
+
+    /// Like [`Self::send`], but broadcast once to a single provider and never retried
+    ///
+    /// For calls whose caller must learn of any failure rather than have it
+    /// papered over, such as minting; see [`TransactionManager::send_once`].
+    pub async fn send_once(
+        &self,
+        network_id: &str,
+        address: &str,
+        abi: &Value,
+        method: &str,
+        args: &[Token],
+    ) -> Result<String> {
+        let function = resolve_function(abi, method, args)?;
+        let tx = TransactionRequest {
+            to: Some(address.to_string()),
+            data: function.encode_input(args)?,
+            ..TransactionRequest::default()
+        };
+
+        let tx_hash = self.transaction_manager.send_once(network_id, tx).await?;
+        info!("📜 {} sent once to {} on {} in {}", function.name, address, network_id, tx_hash);
+        Ok(tx_hash)
+    }
+
+    async fn network(&self, network_id: &str) -> Result<NetworkConfig> {
+        self.networks
+            .read()
+            .await
+            .get(network_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown network: {}", network_id))
+    }
+
+    async fn rpc(&self, chain_id: u64, method: &str, params: Value) -> Result<Value> {
+        rpc_with_failover(&self.providers, &self.failover_requests, chain_id, method, params).await
+    }
+}
+
+/// Where and how `MEVProtector` submits transactions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MevProtectionSettings {
+    /// Private RPC accepting `eth_sendRawTransaction` without forwarding to the public mempool
+    pub relay_endpoint: String,
+    /// Chain the relay submits to; transactions signed for another chain are refused
+    pub relay_chain_id: u64,
+    pub relay_timeout: Duration,
+    /// Submit publicly when the relay cannot be reached, accepting front-running risk
+    pub public_fallback: bool,
+}
+
+impl Default for MevProtectionSettings {
+    fn default() -> Self {
+        Self {
+            relay_endpoint: "https://rpc.flashbots.net/fast".to_string(),
+            relay_chain_id: 1,
+            relay_timeout: Duration::from_secs(10),
+            public_fallback: false,
+        }
+    }
+}
+
+/// How a protected transaction reached the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubmissionPath {
+    PrivateRelay,
+    PublicMempool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateSubmission {
+    pub tx_hash: String,
+    pub path: SubmissionPath,
+}
+
+/// Relay failure, split by whether public submission could still help
+enum RelayError {
+    /// The relay never accepted the transaction
+    Unavailable(anyhow::Error),
+    /// The request may have reached the relay, so the transaction may already be pending
+    Unknown(anyhow::Error),
+    Rejected(String),
+}
+
+impl RelayError {
+    fn from_send(error: reqwest::Error) -> Self {
+        if error.is_connect() {
+            Self::Unavailable(error.into())
+        } else {
+            Self::Unknown(error.into())
+        }
+    }
+}
+
+impl MEVProtector {
+    pub async fn configure(&self, settings: MevProtectionSettings) {
+        *self.settings.write().await = settings;
+    }
+
+    pub async fn settings(&self) -> MevProtectionSettings {
+        self.settings.read().await.clone()
+    }
+
+    /// Submit a signed raw transaction through the private relay
+    ///
+    /// The recipient is screened for sanctions first, and the transaction
+    /// must be signed for the relay's chain. Only when the relay is
+    /// unreachable and `public_fallback` is set does the transaction go to the
+    /// public mempool on the network with the relay's chain id. A transaction
+    /// the relay rejects, or one whose relay request timed out and may already
+    /// be pending privately, is never re-sent publicly.
+    pub async fn submit_private(&self, signed_tx: &[u8]) -> Result<PrivateSubmission> {
+        self.transaction_manager.screen_raw_transaction(signed_tx).await?;
+        let settings = self.settings().await;
+        let (tx, _) = TypedTransaction::decode_signed(&ethers::utils::rlp::Rlp::new(signed_tx))
+            .map_err(|e| anyhow!("Invalid signed transaction: {}", e))?;
+        if let Some(chain_id) = tx.chain_id().filter(|chain_id| chain_id.as_u64() != settings.relay_chain_id) {
+            return Err(anyhow!(
+                "Transaction is signed for chain {} but the private relay serves chain {}",
+                chain_id,
+                settings.relay_chain_id
+            ));
+        }
+        let raw_tx = format!("0x{}", hex::encode(signed_tx));
+
+        match self.relay_send(&settings, &raw_tx).await {
+            Ok(tx_hash) => {
+                info!("🛡️ Transaction {} submitted through private relay", tx_hash);
+                Ok(PrivateSubmission { tx_hash, path: SubmissionPath::PrivateRelay })
+            }
+            Err(RelayError::Rejected(reason)) => Err(anyhow!("Private relay rejected transaction: {}", reason)),
+            Err(RelayError::Unknown(e)) => Err(anyhow!(
+                "Private relay outcome unknown, not falling back to the public mempool: {}",
+                e
+            )),
+            Err(RelayError::Unavailable(e)) if settings.public_fallback => {
+                warn!("🛡️ Private relay unavailable ({}), submitting to the public mempool", e);
+                let chain_id = settings.relay_chain_id;
+                let network_configured = self
+                    .networks
+                    .read()
+                    .await
+                    .values()
+                    .any(|network| network.enabled && network.chain_id == chain_id);
+                if !network_configured {
+                    return Err(anyhow!("No public network configured for chain {}", chain_id));
+                }
+                let tx_hash = rpc_with_failover(
+                    &self.providers,
+                    &self.failover_requests,
+                    chain_id,
+                    "eth_sendRawTransaction",
+                    json!([raw_tx]),
+                )
+                .await?;
+                let tx_hash = tx_hash
+                    .as_str()
+                    .ok_or_else(|| anyhow!("eth_sendRawTransaction returned a non-string hash"))?
+                    .to_string();
+                Ok(PrivateSubmission { tx_hash, path: SubmissionPath::PublicMempool })
+            }
+            Err(RelayError::Unavailable(e)) => Err(anyhow!(
+                "Private relay unavailable and public fallback is disabled: {}",
+                e
+            )),
+        }
+    }
+
+    async fn relay_send(&self, settings: &MevProtectionSettings, raw_tx: &str) -> Result<String, RelayError> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": Utc::now().timestamp_millis(),
+            "method": "eth_sendRawTransaction",
+            "params": [raw_tx],
+        });
+
+        let response = self
+            .http_client
+            .post(&settings.relay_endpoint)
+            .timeout(settings.relay_timeout)
+            .json(&request)
+            .send()
+            .await
+            .map_err(RelayError::from_send)?;
+        let status = response.status();
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(RelayError::Unavailable(anyhow!("relay returned HTTP {}", status)));
+        }
+
+        let body: Value = response.json().await.map_err(|e| RelayError::Unknown(e.into()))?;
+        if let Some(error) = body.get("error").filter(|error| !error.is_null()) {
+            return Err(RelayError::Rejected(error.to_string()));
+        }
+        body.get("result")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| RelayError::Unknown(anyhow!("relay returned no transaction hash")))
+    }
+}
+
+/// Contract event log decoded against its ABI
+#[derive(Debug, Clone)]
+pub struct DecodedEvent {
+    pub address: String,
+    pub event: String,
+    pub block_number: u64,
+    pub block_hash: String,
+    pub log_index: u64,
+    pub transaction_hash: String,
+    /// The log was delivered earlier and a reorg has since dropped it
+    pub removed: bool,
+    /// Event parameters by name, indexed and non-indexed alike
+    pub params: Vec<(String, Token)>,
+}
+
+impl DecodedEvent {
+    /// Parameters as a JSON object of their display values
+    pub fn params_json(&self) -> Value {
+        Value::Object(
+            self.params
+                .iter()
+                .map(|(name, value)| (name.clone(), json!(value.to_string())))
+                .collect(),
+        )
+    }
+}
+
+impl EventListener {
+    /// Stream `event` logs emitted by the contract at `address`
+    ///
+    /// Subscribes over the contract network's WebSocket endpoint. After a
+    /// dropped connection it reconnects with backoff and backfills the logs
+    /// missed in between with paged `eth_getLogs` from the last delivered
+    /// block. Logs a reorg drops are delivered again with `removed` set.
+    /// The subscription stops when the stream is dropped.
+    pub async fn subscribe(&self, address: &str, abi: &Value, event: &str) -> Result<impl Stream<Item = DecodedEvent>> {
+        let abi: Abi = serde_json::from_value(abi.clone()).map_err(|e| ContractCallError::InvalidAbi(e.to_string()))?;
+        let event = abi
+            .event(event)
+            .map_err(|_| anyhow!("contract ABI has no event {}", event))?
+            .clone();
+        let network = contract_network(&self.networks).await?;
+        let endpoint = network
+            .websocket_endpoint
+            .clone()
+            .ok_or_else(|| anyhow!("{} has no WebSocket endpoint for event subscriptions", network.network_id))?;
+
+        let subscription = LogSubscription::new(endpoint, address.to_string(), event);
+        let (sender, receiver) = mpsc::channel(256);
+        tokio::spawn(subscription.run(sender));
+
+        Ok(futures::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|event| (event, receiver))
+        }))
+    }
+}
+
+/// One contract event subscription, kept alive across reconnects
+struct LogSubscription {
+    endpoint: String,
+    address: String,
+    event: Event,
+    /// Logs delivered in recent blocks, as `(block hash, log index)` by block number
+    delivered: BTreeMap<u64, HashSet<(String, u64)>>,
+}
+
+/// Blocks still to fetch with `eth_getLogs` after a reconnect
+struct Backfill {
+    next_block: u64,
+    head: u64,
+}
+
+impl LogSubscription {
+    fn new(endpoint: String, address: String, event: Event) -> Self {
+        Self {
+            endpoint,
+            address,
+            event,
+            delivered: BTreeMap::new(),
+        }
+    }
+
+    /// Newest block a log was delivered from
+    fn last_block(&self) -> Option<u64> {
+        self.delivered.keys().next_back().copied()
+    }
+
+    async fn run(mut self, sender: mpsc::Sender<DecodedEvent>) {
+        let mut delay = Duration::from_secs(1);
+        while let Err(e) = self.stream_logs(&sender, &mut delay).await {
+            warn!(
+                "📡 {} subscription on {} dropped ({}), reconnecting in {:?}",
+                self.event.name, self.address, e, delay
+            );
+            tokio::select! {
+                _ = sender.closed() => return,
+                _ = tokio::time::sleep(delay) => {}
+            }
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+
+    fn filter(&self) -> Value {
+        json!({
+            "address": self.address,
+            "topics": [format!("0x{}", hex::encode(self.event.signature().as_bytes()))],
+        })
+    }
+
+    /// Deliver logs until the connection fails, or return `Ok` once the stream is dropped
+    async fn stream_logs(&mut self, sender: &mpsc::Sender<DecodedEvent>, delay: &mut Duration) -> Result<()> {
+        let (mut socket, _) = connect_async(self.endpoint.as_str()).await?;
+        socket.send(Message::Text(rpc_request(SUBSCRIBE_REQUEST_ID, "eth_subscribe", json!(["logs", self.filter()])))).await?;
+
+        // Logs pushed while the backfill is outstanding are held back so delivery stays in order
+        let mut held: Option<Vec<Value>> = None;
+        let mut backfill: Option<Backfill> = None;
+        if self.last_block().is_some() {
+            socket.send(Message::Text(rpc_request(HEAD_REQUEST_ID, "eth_blockNumber", json!([])))).await?;
+            held = Some(Vec::new());
+        }
+
+        loop {
+            let message = tokio::select! {
+                _ = sender.closed() => return Ok(()),
+                message = socket.next() => message,
+            };
+            let text = match message.ok_or_else(|| anyhow!("connection closed"))?? {
+                Message::Text(text) => text,
+                Message::Close(_) => return Err(anyhow!("connection closed by server")),
+                _ => continue,
+            };
+            let message: Value = serde_json::from_str(&text)?;
+            if let Some(error) = message.get("error").filter(|error| !error.is_null()) {
+                return Err(anyhow!("{}", error));
+            }
+
+            let logs = match message.get("id").and_then(Value::as_u64) {
+                Some(SUBSCRIBE_REQUEST_ID) => {
+                    info!("📡 Subscribed to {} events on {}", self.event.name, self.address);
+                    *delay = Duration::from_secs(1);
+                    continue;
+                }
+                Some(HEAD_REQUEST_ID) => {
+                    let next_block = self.last_block().unwrap_or_default();
+                    let page = Backfill { next_block, head: parse_hex_u64(&message["result"])? };
+                    socket.send(Message::Text(self.backfill_request(&page))).await?;
+                    backfill = Some(page);
+                    continue;
+                }
+                Some(BACKFILL_REQUEST_ID) => {
+                    let mut logs = message["result"].as_array().cloned().unwrap_or_default();
+                    if !logs.is_empty() {
+                        info!("📡 Backfilling {} missed {} events", logs.len(), self.event.name);
+                    }
+                    let page = backfill.as_mut().ok_or_else(|| anyhow!("unexpected eth_getLogs response"))?;
+                    page.next_block = page.next_block.saturating_add(BACKFILL_PAGE_BLOCKS);
+                    if page.next_block <= page.head {
+                        let request = self.backfill_request(page);
+                        socket.send(Message::Text(request)).await?;
+                    } else {
+                        backfill = None;
+                        logs.extend(held.take().unwrap_or_default());
+                    }
+                    logs
+                }
+                _ => {
+                    let log = &message["params"]["result"];
+                    if log.is_null() {
+                        continue;
+                    }
+                    match held.as_mut() {
+                        Some(held) => {
+                            held.push(log.clone());
+                            continue;
+                        }
+                        None => vec![log.clone()],
+                    }
+                }
+            };
+
+            for log in &logs {
+                if !self.deliver(log, sender).await {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn backfill_request(&self, page: &Backfill) -> String {
+        let mut range = self.filter();
+        let to_block = page.next_block.saturating_add(BACKFILL_PAGE_BLOCKS - 1).min(page.head);
+        range["fromBlock"] = json!(format!("0x{:x}", page.next_block));
+        range["toBlock"] = json!(format!("0x{:x}", to_block));
+        rpc_request(BACKFILL_REQUEST_ID, "eth_getLogs", json!([range]))
+    }
+
+    /// Forward a log unless it was already delivered; false once the stream is dropped
+    ///
+    /// Logs are told apart by block hash and log index, so a log a reorg
+    /// re-emits at the same or a lower position is still delivered. A
+    /// removed log is forwarded only if it was delivered before.
+    async fn deliver(&mut self, log: &Value, sender: &mpsc::Sender<DecodedEvent>) -> bool {
+        let decoded = match decode_log(&self.event, log) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                warn!("📡 Could not decode {} log: {}", self.event.name, e);
+                return true;
+            }
+        };
+
+        let identity = (decoded.block_hash.clone(), decoded.log_index);
+        if decoded.removed {
+            let was_delivered = self
+                .delivered
+                .get_mut(&decoded.block_number)
+                .is_some_and(|logs| logs.remove(&identity));
+            if !was_delivered {
+                return true;
+            }
+        } else {
+            if !self.delivered.entry(decoded.block_number).or_default().insert(identity) {
+                return true;
+            }
+            if let Some(newest) = self.last_block() {
+                self.delivered = self.delivered.split_off(&newest.saturating_sub(REORG_DEPTH_BLOCKS));
+            }
+        }
+        sender.send(decoded).await.is_ok()
+    }
+}
+
+fn rpc_request(id: u64, method: &str, params: Value) -> String {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }).to_string()
+}
+
+/// Decode a JSON-RPC log object as `event`
+pub fn decode_log(event: &Event, log: &Value) -> Result<DecodedEvent> {
+    let topics = log["topics"]
+        .as_array()
+        .ok_or_else(|| anyhow!("log has no topics"))?
+        .iter()
+        .map(|topic| {
+            let bytes = topic.as_str().ok_or_else(|| anyhow!("non-string topic")).and_then(hex_decode)?;
+            if bytes.len() != 32 {
+                return Err(anyhow!("topic is {} bytes, expected 32", bytes.len()));
+            }
+            Ok(Hash::from_slice(&bytes))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let data = log["data"].as_str().map(hex_decode).transpose()?.unwrap_or_default();
+    let parsed = event.parse_log(RawLog { topics, data })?;
+
+    Ok(DecodedEvent {
+        address: log["address"].as_str().unwrap_or_default().to_string(),
+        event: event.name.clone(),
+        block_number: parse_hex_u64(&log["blockNumber"])?,
+        block_hash: log["blockHash"].as_str().unwrap_or_default().to_string(),
+        log_index: parse_hex_u64(&log["logIndex"])?,
+        transaction_hash: log["transactionHash"].as_str().unwrap_or_default().to_string(),
+        removed: log["removed"].as_bool().unwrap_or(false),
+        params: parsed.params.into_iter().map(|param| (param.name, param.value)).collect(),
+    })
+}
+
+/// Network contract calls and subscriptions go to: the enabled Ethereum network with the lowest chain id
+async fn contract_network(networks: &RwLock<HashMap<String, NetworkConfig>>) -> Result<NetworkConfig> {
+    networks
+        .read()
+        .await
+        .values()
+        .filter(|network| matches!(network.blockchain_type, BlockchainType::Ethereum))
+        .min_by_key(|network| network.chain_id)
+        .cloned()
+        .ok_or_else(|| anyhow!("No Ethereum network configured for contract calls"))
+}
+
+/// Contract call rejected before encoding because it does not match the ABI
+#[derive(Debug, thiserror::Error)]
+pub enum ContractCallError {
+    #[error("invalid contract ABI: {0}")]
+    InvalidAbi(String),
+    #[error("contract ABI has no method {method}")]
+    UnknownMethod { method: String },
+    #[error("{method} takes {expected} argument(s), got {got}")]
+    ArityMismatch { method: String, expected: String, got: usize },
+    #[error("argument {index} of {method} must be {expected}")]
+    ArgumentType { method: String, index: usize, expected: String },
+}
+
+/// Pick the ABI function `method` whose inputs match `args`
+pub fn resolve_function(abi: &Value, method: &str, args: &[Token]) -> Result<Function, ContractCallError> {
+    let abi: Abi = serde_json::from_value(abi.clone()).map_err(|e| ContractCallError::InvalidAbi(e.to_string()))?;
+    let overloads = abi
+        .functions_by_name(method)
+        .map_err(|_| ContractCallError::UnknownMethod { method: method.to_string() })?;
+
+    let candidates: Vec<&Function> = overloads.iter().filter(|function| function.inputs.len() == args.len()).collect();
+    if candidates.is_empty() {
+        let mut arities: Vec<String> = overloads.iter().map(|function| function.inputs.len().to_string()).collect();
+        arities.sort();
+        arities.dedup();
+        return Err(ContractCallError::ArityMismatch {
+            method: method.to_string(),
+            expected: arities.join(" or "),
+            got: args.len(),
+        });
+    }
+
+    let mut mismatch = None;
+    for function in candidates {
+        let bad_argument = function
+            .inputs
+            .iter()
+            .zip(args)
+            .position(|(param, arg)| !arg.type_check(&param.kind));
+        match bad_argument {
+            None => return Ok(function.clone()),
+            Some(index) => {
+                mismatch.get_or_insert(ContractCallError::ArgumentType {
+                    method: method.to_string(),
+                    index,
+                    expected: function.inputs[index].kind.to_string(),
+                });
+            }
+        }
+    }
+    Err(mismatch.expect("at least one candidate was checked"))
+}
+
+/// Failover shared by `EthereumManager` and `ContractManager`, so both see the same provider health
+async fn rpc_with_failover(
+    providers: &RwLock<HashMap<String, EthereumProvider>>,
+    failover_requests: &AtomicU64,
+    chain_id: u64,
+    method: &str,
+    params: Value,
+) -> Result<Value> {
+    rpc_with_policy(providers, failover_requests, chain_id, method, params, RetryPolicy::for_method(method)).await
+}
+
+async fn rpc_with_policy(
+    providers: &RwLock<HashMap<String, EthereumProvider>>,
+    failover_requests: &AtomicU64,
+    chain_id: u64,
+    method: &str,
+    params: Value,
+    policy: RetryPolicy,
+) -> Result<Value> {
+    let mut candidates: Vec<EthereumProvider> = providers
+        .read()
+        .await
+        .values()
+        .filter(|provider| provider.chain_id == chain_id)
+        .filter(|provider| matches!(provider.health_status, ProviderHealth::Healthy))
+        .cloned()
+        .collect();
+    candidates.sort_by(|a, b| a.provider_id.cmp(&b.provider_id));
+
+    if candidates.is_empty() {
+        return Err(anyhow!("No healthy provider for chain {}", chain_id));
+    }
+
+    if policy == RetryPolicy::Once {
+        let provider = &candidates[0];
+        return provider.rpc(method, params).await.map_err(|e| {
+            warn!("RPC {} failed on {}; not retried as its outcome is unknown: {}", method, provider.provider_id, e);
+            e.context(format!("{} is not idempotent and was not retried", method))
+        });
+    }
+
+    let mut last_error = None;
+    for (index, provider) in candidates.iter().enumerate() {
+        for attempt in 1..=provider.retry_attempts.max(1) {
+            if policy == RetryPolicy::Rebroadcast && last_error.is_some() {
+                if let Some(tx_hash) = known_raw_transaction(provider, &params).await {
+                    info!("🔁 {} already known to {} after a failed attempt", tx_hash, provider.provider_id);
+                    return Ok(Value::String(tx_hash));
+                }
+            }
+            match provider.rpc(method, params.clone()).await {
+                Ok(result) => {
+                    if index > 0 {
+                        failover_requests.fetch_add(1, Ordering::Relaxed);
+                        info!("🔀 {} served by failover provider {}", method, provider.provider_id);
+                    }
+                    return Ok(result);
+                }
+                Err(e) => {
+                    warn!(
+                        "RPC {} failed on {} (attempt {}/{}): {}",
+                        method, provider.provider_id, attempt, provider.retry_attempts, e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        set_provider_health(providers, &provider.provider_id, ProviderHealth::Degraded).await;
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow!("{} failed on chain {}", method, chain_id)))
+}
+
+/// Hash of the raw transaction in `params` if `provider` already has it
+async fn known_raw_transaction(provider: &EthereumProvider, params: &Value) -> Option<String> {
+    let raw = params.get(0).and_then(Value::as_str).and_then(|raw| hex_decode(raw).ok())?;
+    let tx_hash = format!("0x{}", hex::encode(ethers::utils::keccak256(raw)));
+    match provider.rpc("eth_getTransactionByHash", json!([tx_hash])).await {
+        Ok(transaction) if !transaction.is_null() => Some(tx_hash),
+        _ => None,
+    }
+}
+
+async fn set_provider_health(
+    providers: &RwLock<HashMap<String, EthereumProvider>>,
+    provider_id: &str,
+    status: ProviderHealth,
+) {
+    if let Some(provider) = providers.write().await.get_mut(provider_id) {
+        provider.health_status = status;
+    }
+}
+
+impl TransactionManager {
+    /// Sign `tx` with the wallet manager's key and broadcast it on `network_id`
+    ///
+    /// The recipient is screened with [`Self::screen_recipient`] first. The
+    /// transaction is priced with [`Self::estimate_gas`], given the signing
+    /// account's next pending nonce and signed locally, so a failed broadcast
+    /// is only ever retried with the same raw transaction, which cannot be
+    /// mined twice. Returns the transaction hash.
+    pub async fn send(&self, network_id: &str, tx: TransactionRequest) -> Result<String> {
+        self.sign_and_broadcast(network_id, tx, RetryPolicy::Rebroadcast).await
+    }
+
+    /// Like [`Self::send`], but the raw transaction goes to one provider,
+    /// one time. A failed broadcast is returned as an error without
+    /// rebroadcasting or failing over.
+    pub async fn send_once(&self, network_id: &str, tx: TransactionRequest) -> Result<String> {
+        self.sign_and_broadcast(network_id, tx, RetryPolicy::Once).await
+    }
+
+    async fn sign_and_broadcast(&self, network_id: &str, mut tx: TransactionRequest, policy: RetryPolicy) -> Result<String> {
+        let network = self.network(network_id).await?;
+        let sender = self.wallet_manager.address().await?;
+        // Anchoring sends to the signing account itself, which needs no screening
+        if let Some(to) = tx.to.as_deref().filter(|to| !to.eq_ignore_ascii_case(&sender)) {
+            self.screen_recipient(to).await?;
+        }
+        tx.from = Some(sender.clone());
+
+        let gas = self.estimate_gas(network_id, &tx).await?;
+        tx.gas_limit = Some(gas.gas_limit);
+
+        let _sending = self.send_lock.lock().await;
+        let nonce = self
+            .rpc(network.chain_id, "eth_getTransactionCount", json!([sender, "pending"]))
+            .await
+            .and_then(|value| parse_hex_u64(&value))?;
+        let unsigned = Self::typed_transaction(&network, &tx, &gas, nonce)?;
+        let raw_tx = self.wallet_manager.sign_transaction(&unsigned).await?;
+
+        let tx_hash = rpc_with_policy(
+            &self.providers,
+            &self.failover_requests,
+            network.chain_id,
+            "eth_sendRawTransaction",
+            json!([format!("0x{}", hex::encode(raw_tx))]),
+            policy,
+        )
+        .await?;
+        Ok(tx_hash
+            .as_str()
+            .ok_or_else(|| anyhow!("eth_sendRawTransaction returned a non-string hash"))?
+            .to_string())
+    }
+
+    /// Refuse a transaction to `to` if it is sanctioned and blocking is enabled
+    ///
+    /// Every outgoing EVM transaction is screened here, whether signed by
+    /// [`Self::send`] or submitted pre-signed through [`MEVProtector`].
+    pub async fn screen_recipient(&self, to: &str) -> Result<()> {
+        self.address_screener.check_counterparty(to).await
+    }
+
+    /// Screen the recipient of a signed raw transaction, see [`Self::screen_recipient`]
+    pub async fn screen_raw_transaction(&self, raw_tx: &[u8]) -> Result<()> {
+        let (tx, _) = TypedTransaction::decode_signed(&ethers::utils::rlp::Rlp::new(raw_tx))
+            .map_err(|e| anyhow!("Invalid signed transaction: {}", e))?;
+        match tx.to().and_then(|to| to.as_address()) {
+            Some(to) => self.screen_recipient(&format!("{:#x}", to)).await,
+            // Contract creation has no counterparty
+            None => Ok(()),
+        }
+    }
+
+    /// Estimate gas for `tx` on `network_id` from live fee data
+    ///
+    /// Uses `eth_feeHistory` at the network's configured priority-fee
+    /// percentile, floored by `eth_maxPriorityFeePerGas`. Falls back to the
+    /// network's static `GasSettings` if the provider cannot be reached.
+    pub async fn estimate_gas(&self, network_id: &str, tx: &TransactionRequest) -> Result<GasEstimate> {
+        let network = self.network(network_id).await?;
+
+        match self.live_gas_estimate(&network, tx).await {
+            // A wrong tx_type will not fix itself with static pricing
+            Err(e) if e.downcast_ref::<TransactionTypeError>().is_some() => Err(e),
+            Ok(estimate) => {
+                info!(
+                    "⛽ Live gas estimate for {}: max fee {} wei, priority {} wei",
+                    network_id, estimate.max_fee_per_gas, estimate.max_priority_fee_per_gas
+                );
+                Ok(estimate)
+            }
+            Err(e) => {
+                warn!("⛽ Live gas estimation failed for {} ({}), using static gas settings", network_id, e);
+                Ok(Self::static_gas_estimate(&network, tx))
+            }
+        }
+    }
+
+    async fn live_gas_estimate(
+        &self,
+        network: &NetworkConfig,
+        tx: &TransactionRequest,
+    ) -> Result<GasEstimate> {
+        let chain_id = network.chain_id;
+        if network.tx_type == TxType::Legacy {
+            return self.live_legacy_gas_estimate(network, tx).await;
+        }
+
+        let percentile = network.gas_settings.priority_fee_percentile.clamp(0.0, 100.0);
+        let history = self
+            .rpc(
+                chain_id,
+                "eth_feeHistory",
+                json!([format!("0x{:x}", FEE_HISTORY_BLOCKS), "latest", [percentile]]),
+            )
+            .await?;
+
+        // The last base fee entry is the projected base fee of the next block
+        let base_fee_per_gas = history["baseFeePerGas"]
+            .as_array()
+            .and_then(|fees| fees.last())
+            .ok_or_else(|| anyhow!("eth_feeHistory returned no base fees"))
+            .and_then(parse_hex_u64)?;
+        if base_fee_per_gas == 0 {
+            return Err(TransactionTypeError::Eip1559Unsupported { network_id: network.network_id.clone() }.into());
+        }
+
+        let rewards: Vec<u64> = history["reward"]
+            .as_array()
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .filter_map(|block| block.get(0))
+                    .filter_map(|reward| parse_hex_u64(reward).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let history_priority_fee = if rewards.is_empty() {
+            0
+        } else {
+            rewards.iter().sum::<u64>() / rewards.len() as u64
+        };
+
+        let node_priority_fee = match self.rpc(chain_id, "eth_maxPriorityFeePerGas", json!([])).await {
+            Ok(value) => parse_hex_u64(&value).unwrap_or(0),
+            Err(_) => 0,
+        };
+        let max_priority_fee_per_gas = history_priority_fee.max(node_priority_fee);
+
+        // Allow the base fee to double before the transaction is priced out
+        let mut max_fee_per_gas = base_fee_per_gas.saturating_mul(2).saturating_add(max_priority_fee_per_gas);
+        if network.max_fee_per_gas > 0 && max_fee_per_gas > network.max_fee_per_gas {
+            warn!(
+                "⛽ Estimated max fee {} wei exceeds {} cap of {} wei",
+                max_fee_per_gas, network.network_id, network.max_fee_per_gas
+            );
+            max_fee_per_gas = network.max_fee_per_gas;
+        }
+
+        let gas_limit = self.live_gas_limit(network, tx).await;
+
+        Ok(GasEstimate {
+            gas_limit,
+            base_fee_per_gas,
+            max_fee_per_gas,
+            max_priority_fee_per_gas: max_priority_fee_per_gas.min(max_fee_per_gas),
+            source: GasEstimateSource::LiveNetwork,
+        })
+    }
+
+    /// Price a type-0 transaction from `eth_gasPrice`
+    async fn live_legacy_gas_estimate(
+        &self,
+        network: &NetworkConfig,
+        tx: &TransactionRequest,
+    ) -> Result<GasEstimate> {
+        let mut gas_price = self
+            .rpc(network.chain_id, "eth_gasPrice", json!([]))
+            .await
+            .and_then(|value| parse_hex_u64(&value))?;
+        if network.max_fee_per_gas > 0 && gas_price > network.max_fee_per_gas {
+            warn!(
+                "⛽ Gas price {} wei exceeds {} cap of {} wei",
+                gas_price, network.network_id, network.max_fee_per_gas
+            );
+            gas_price = network.max_fee_per_gas;
+        }
+
+        Ok(GasEstimate {
+            gas_limit: self.live_gas_limit(network, tx).await,
+            base_fee_per_gas: gas_price,
+            max_fee_per_gas: gas_price,
+            max_priority_fee_per_gas: 0,
+            source: GasEstimateSource::LiveNetwork,
+        })
+    }
+
+    async fn live_gas_limit(&self, network: &NetworkConfig, tx: &TransactionRequest) -> u64 {
+        match tx.gas_limit {
+            Some(limit) => limit,
+            None => self
+                .rpc(network.chain_id, "eth_estimateGas", json!([tx.to_rpc_json()]))
+                .await
+                .and_then(|value| parse_hex_u64(&value))
+                .unwrap_or(network.gas_settings.gas_limit),
+        }
+    }
+
+    pub fn static_gas_estimate(network: &NetworkConfig, tx: &TransactionRequest) -> GasEstimate {
+        let settings = &network.gas_settings;
+        if network.tx_type == TxType::Legacy {
+            return GasEstimate {
+                gas_limit: tx.gas_limit.unwrap_or(settings.gas_limit),
+                base_fee_per_gas: settings.gas_price,
+                max_fee_per_gas: settings.gas_price,
+                max_priority_fee_per_gas: 0,
+                source: GasEstimateSource::StaticDefaults,
+            };
+        }
+        GasEstimate {
+            gas_limit: tx.gas_limit.unwrap_or(settings.gas_limit),
+            base_fee_per_gas: settings.gas_price.saturating_sub(settings.max_priority_fee),
+            max_fee_per_gas: settings.gas_price,
+            max_priority_fee_per_gas: settings.max_priority_fee,
+            source: GasEstimateSource::StaticDefaults,
+        }
+    }
+
+    /// Build the JSON-RPC transaction object in the network's envelope
+    pub fn build_transaction(network: &NetworkConfig, tx: &TransactionRequest, gas: &GasEstimate) -> Result<Value> {
+        Self::check_fees(network, gas)?;
+        let mut rpc_tx = tx.to_rpc_json();
+        rpc_tx["chainId"] = json!(format!("0x{:x}", network.chain_id));
+
+        match network.tx_type {
+            TxType::Legacy => {
+                rpc_tx["type"] = json!("0x0");
+                rpc_tx["gasPrice"] = json!(format!("0x{:x}", gas.max_fee_per_gas));
+            }
+            TxType::Eip1559 => {
+                rpc_tx["type"] = json!("0x2");
+                rpc_tx["maxFeePerGas"] = json!(format!("0x{:x}", gas.max_fee_per_gas));
+                rpc_tx["maxPriorityFeePerGas"] = json!(format!("0x{:x}", gas.max_priority_fee_per_gas));
+            }
+        }
+
+        Ok(rpc_tx)
+    }
+
+    /// The unsigned transaction in the network's envelope, ready for local signing
+    pub fn typed_transaction(
+        network: &NetworkConfig,
+        tx: &TransactionRequest,
+        gas: &GasEstimate,
+        nonce: u64,
+    ) -> Result<TypedTransaction> {
+        Self::check_fees(network, gas)?;
+        let parse_address = |address: &str| {
+            address
+                .parse::<Address>()
+                .map_err(|e| anyhow!("invalid Ethereum address {}: {}", address, e))
+        };
+        let from = tx.from.as_deref().map(parse_address).transpose()?;
+        let to = tx.to.as_deref().map(parse_address).transpose()?;
+        let gas_limit = tx.gas_limit.unwrap_or(gas.gas_limit);
+
+        let typed = match network.tx_type {
+            TxType::Legacy => TypedTransaction::Legacy(ethers::types::TransactionRequest {
+                from,
+                to: to.map(Into::into),
+                gas: Some(gas_limit.into()),
+                gas_price: Some(gas.max_fee_per_gas.into()),
+                value: Some(tx.value.into()),
+                data: Some(tx.data.clone().into()),
+                nonce: Some(nonce.into()),
+                chain_id: Some(network.chain_id.into()),
+            }),
+            TxType::Eip1559 => TypedTransaction::Eip1559(Eip1559TransactionRequest {
+                from,
+                to: to.map(Into::into),
+                gas: Some(gas_limit.into()),
+                value: Some(tx.value.into()),
+                data: Some(tx.data.clone().into()),
+                nonce: Some(nonce.into()),
+                access_list: Default::default(),
+                max_priority_fee_per_gas: Some(gas.max_priority_fee_per_gas.into()),
+                max_fee_per_gas: Some(gas.max_fee_per_gas.into()),
+                chain_id: Some(network.chain_id.into()),
+            }),
+        };
+        Ok(typed)
+    }
+
+    fn check_fees(network: &NetworkConfig, gas: &GasEstimate) -> Result<(), TransactionTypeError> {
+        match network.tx_type {
+            TxType::Legacy if gas.max_fee_per_gas == 0 => {
+                Err(TransactionTypeError::MissingGasPrice { network_id: network.network_id.clone() })
+            }
+            TxType::Eip1559 if gas.max_priority_fee_per_gas > gas.max_fee_per_gas => {
+                Err(TransactionTypeError::PriorityFeeAboveMaxFee {
+                    network_id: network.network_id.clone(),
+                    priority_fee: gas.max_priority_fee_per_gas,
+                    max_fee: gas.max_fee_per_gas,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn network(&self, network_id: &str) -> Result<NetworkConfig> {
+        self.networks
+            .read()
+            .await
+            .get(network_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown network: {}", network_id))
+    }
+
+    async fn rpc(&self, chain_id: u64, method: &str, params: Value) -> Result<Value> {
+        rpc_with_failover(&self.providers, &self.failover_requests, chain_id, method, params).await
+    }
+}
+
+impl WalletManager {
+    pub fn new() -> Self {
+        Self { signer: RwLock::new(None) }
+    }
+
+    /// Sign with `wallet` from now on
+    pub async fn set_signing_key(&self, wallet: LocalWallet) {
+        info!("🔑 Transaction signing key set for {:?}", wallet.address());
+        *self.signer.write().await = Some(wallet);
+    }
+
+    /// Load a hex-encoded secp256k1 private key from `path`
+    pub async fn load_signing_key(&self, path: &std::path::Path) -> Result<()> {
+        let encoded = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| anyhow!("Could not read transaction signing key {}: {}", path.display(), e))?;
+        let wallet = encoded
+            .trim()
+            .trim_start_matches("0x")
+            .parse::<LocalWallet>()
+            .map_err(|e| anyhow!("Invalid transaction signing key {}: {}", path.display(), e))?;
+        self.set_signing_key(wallet).await;
+        Ok(())
+    }
+
+    /// `0x`-prefixed address of the signing account
+    pub async fn address(&self) -> Result<String> {
+        let address = self.signer().await?.address();
+        Ok(format!("{:#x}", address))
+    }
+
+    /// Sign `tx` and return its raw RLP encoding for `eth_sendRawTransaction`
+    pub async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Vec<u8>> {
+        let signer = self.signer().await?;
+        let signature = signer.sign_transaction_sync(tx)?;
+        Ok(tx.rlp_signed(&signature).to_vec())
+    }
+
+    async fn signer(&self) -> Result<LocalWallet> {
+        self.signer
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| anyhow!("No transaction signing key loaded; configure transaction_signing_key_path"))
+    }
+}
+
+impl Default for WalletManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EthereumProvider {
+    /// HTTP provider with default rate limit, timeout and retries
+    pub fn new(provider_id: &str, endpoint: &str, chain_id: u64, connection_pool: ConnectionPoolConfig) -> Result<Self> {
+        Ok(Self {
+            provider_id: provider_id.to_string(),
+            endpoint: endpoint.to_string(),
+            chain_id,
+            provider_type: ProviderType::HTTP,
+            rate_limit: 25,
+            timeout: Duration::from_secs(10),
+            retry_attempts: 3,
+            health_status: ProviderHealth::Healthy,
+            connection: ProviderConnection::new(&connection_pool)?,
+            connection_pool,
+        })
+    }
+
+    /// Pool settings the provider's connection was built from
+    pub fn connection_pool(&self) -> &ConnectionPoolConfig {
+        &self.connection_pool
+    }
+
+    /// Client and request limiter, shared by clones
+    pub fn connection(&self) -> &ProviderConnection {
+        &self.connection
+    }
+
+    /// Perform a JSON-RPC call against this provider
+    ///
+    /// Waits for a free slot under the provider's concurrency cap, then sends
+    /// the request over its pooled keep-alive connections.
+    pub async fn rpc(&self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": Utc::now().timestamp_millis(),
+            "method": method,
+            "params": params,
+        });
+
+        let _permit = self.connection.acquire(self.timeout).await?;
+        let response: Value = self
+            .connection
+            .client()
+            .post(&self.endpoint)
+            .timeout(self.timeout)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(anyhow!("{} failed on {}: {}", method, self.provider_id, error));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{} returned no result on {}", method, self.provider_id))
+    }
+}
+
+pub(crate) fn parse_hex_u64(value: &Value) -> Result<u64> {
+    let text = value.as_str().ok_or_else(|| anyhow!("expected hex quantity, got {}", value))?;
+    Ok(u64::from_str_radix(text.trim_start_matches("0x"), 16)?)
+}
+
+/// Bytes of a `0x`-prefixed JSON-RPC data string
+fn hex_decode(text: &str) -> Result<Vec<u8>> {
+    hex::decode(text.trim_start_matches("0x")).map_err(|e| anyhow!("invalid hex data {}: {}", text, e))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// JSON-RPC server answering from `respond`, or with HTTP 500 where it returns `None`
+    ///
+    /// Returns the server's URL and the methods it has been called with, in order.
+    pub(crate) async fn mock_rpc<F>(respond: F) -> (String, Arc<std::sync::Mutex<Vec<String>>>)
+    where
+        F: Fn(&str, &Value) -> Option<Value> + Send + Sync + 'static,
+    {
+        use hyper::service::{make_service_fn, service_fn};
+        use hyper::{Body, Request, Response};
+        use std::convert::Infallible;
+
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let respond = Arc::new(respond);
+        let log = calls.clone();
+        let make_service = make_service_fn(move |_| {
+            let (respond, log) = (respond.clone(), log.clone());
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let (respond, log) = (respond.clone(), log.clone());
+                    async move {
+                        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                        let call: Value = serde_json::from_slice(&body).unwrap();
+                        let method = call["method"].as_str().unwrap_or_default().to_string();
+                        log.lock().unwrap().push(method.clone());
+                        let response = match respond(&method, &call["params"]) {
+                            Some(result) => Response::new(Body::from(
+                                json!({ "jsonrpc": "2.0", "id": call["id"], "result": result }).to_string(),
+                            )),
+                            None => Response::builder().status(500).body(Body::empty()).unwrap(),
+                        };
+                        Ok::<_, Infallible>(response)
+                    }
+                }))
+            }
+        });
+
+        let server = hyper::Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        (url, calls)
+    }
+
+    pub(crate) async fn manager_with_providers(urls: &[&str]) -> EthereumManager {
+        let manager = EthereumManager::new().await.unwrap();
+        for (index, url) in urls.iter().enumerate() {
+            let provider_id = format!("provider-{}", index);
+            let provider = EthereumProvider::new(&provider_id, url, 1, ConnectionPoolConfig::default()).unwrap();
+            manager.register_provider(provider).await;
+        }
+        manager
+    }
+
+    /// Well-known test key, never funded on a real network
+    const TEST_SIGNING_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    async fn signed_transaction(to: &str) -> Vec<u8> {
+        let wallet_manager = WalletManager::new();
+        wallet_manager.set_signing_key(TEST_SIGNING_KEY.parse().unwrap()).await;
+        let network = network(TxType::Eip1559);
+        let tx = TransactionRequest { to: Some(to.to_string()), ..TransactionRequest::default() };
+        let gas = TransactionManager::static_gas_estimate(&network, &tx);
+        let unsigned = TransactionManager::typed_transaction(&network, &tx, &gas, 0).unwrap();
+        wallet_manager.sign_transaction(&unsigned).await.unwrap()
+    }
+
+    pub(crate) fn network(tx_type: TxType) -> NetworkConfig {
+        NetworkConfig {
+            network_id: "test".to_string(),
+            blockchain_type: BlockchainType::Ethereum,
+            rpc_endpoint: "http://localhost:8545".to_string(),
+            websocket_endpoint: None,
+            chain_id: 56,
+            gas_settings: GasSettings::default(),
+            confirmation_blocks: 1,
+            max_fee_per_gas: 0,
+            priority_fee_per_gas: 0,
+            tx_type,
+            connection_pool: ConnectionPoolConfig::default(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_build_transaction_matches_network_tx_type() {
+        let tx = TransactionRequest::default();
+
+        let legacy_network = network(TxType::Legacy);
+        let legacy_gas = TransactionManager::static_gas_estimate(&legacy_network, &tx);
+        let legacy = TransactionManager::build_transaction(&legacy_network, &tx, &legacy_gas).unwrap();
+        assert_eq!(legacy["type"], "0x0");
+        assert!(legacy.get("gasPrice").is_some());
+        assert!(legacy.get("maxFeePerGas").is_none());
+
+        let eip1559_network = network(TxType::Eip1559);
+        let eip1559_gas = TransactionManager::static_gas_estimate(&eip1559_network, &tx);
+        let eip1559 = TransactionManager::build_transaction(&eip1559_network, &tx, &eip1559_gas).unwrap();
+        assert_eq!(eip1559["type"], "0x2");
+        assert!(eip1559.get("gasPrice").is_none());
+        assert!(eip1559.get("maxPriorityFeePerGas").is_some());
+    }
+
+    #[test]
+    fn test_legacy_network_without_gas_price_is_rejected() {
+        let mut legacy_network = network(TxType::Legacy);
+        legacy_network.gas_settings.gas_price = 0;
+        let tx = TransactionRequest::default();
+        let gas = TransactionManager::static_gas_estimate(&legacy_network, &tx);
+
+        let error = TransactionManager::build_transaction(&legacy_network, &tx, &gas).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<TransactionTypeError>(),
+            Some(TransactionTypeError::MissingGasPrice { .. })
+        ));
+    }
+
+    #[test]
+    fn test_contract_calls_are_checked_against_the_abi() {
+        let abi = json!([{
+            "type": "function",
+            "name": "isCompliant",
+            "inputs": [{ "name": "entity", "type": "address" }],
+            "outputs": [{ "name": "", "type": "bool" }],
+            "stateMutability": "view"
+        }]);
+        let entity = Token::Address([0x11; 20].into());
+
+        let function = resolve_function(&abi, "isCompliant", &[entity.clone()]).unwrap();
+        assert_eq!(function.encode_input(&[entity.clone()]).unwrap().len(), 4 + 32);
+
+        let mut output = vec![0u8; 32];
+        output[31] = 1;
+        assert_eq!(function.decode_output(&output).unwrap(), vec![Token::Bool(true)]);
+
+        assert!(matches!(
+            resolve_function(&abi, "isCompliant", &[]),
+            Err(ContractCallError::ArityMismatch { got: 0, .. })
+        ));
+        assert!(matches!(
+            resolve_function(&abi, "isCompliant", &[Token::Bool(true)]),
+            Err(ContractCallError::ArgumentType { index: 0, .. })
+        ));
+        assert!(matches!(
+            resolve_function(&abi, "revoke", &[entity]),
+            Err(ContractCallError::UnknownMethod { .. })
+        ));
+    }
+
+    #[test]
+    fn test_event_logs_are_decoded_per_abi() {
+        let abi: Abi = serde_json::from_value(json!([{
+            "type": "event",
+            "name": "ComplianceViolationRecorded",
+            "anonymous": false,
+            "inputs": [
+                { "name": "entity", "type": "address", "indexed": true },
+                { "name": "severity", "type": "uint256", "indexed": false }
+            ]
+        }]))
+        .unwrap();
+        let event = abi.event("ComplianceViolationRecorded").unwrap();
+
+        let log = json!({
+            "address": "0x00000000000000000000000000000000000000aa",
+            "topics": [
+                format!("0x{}", hex::encode(event.signature().as_bytes())),
+                format!("0x{}{}", "00".repeat(12), "11".repeat(20)),
+            ],
+            "data": format!("0x{}", hex::encode(ethers::abi::encode(&[Token::Uint(7u64.into())]))),
+            "blockNumber": "0x10",
+            "logIndex": "0x2",
+            "transactionHash": "0xabc",
+        });
+
+        let decoded = decode_log(event, &log).unwrap();
+        assert_eq!(decoded.event, "ComplianceViolationRecorded");
+        assert_eq!((decoded.block_number, decoded.log_index), (16, 2));
+        assert_eq!(decoded.params[0], ("entity".to_string(), Token::Address([0x11; 20].into())));
+        assert_eq!(decoded.params[1], ("severity".to_string(), Token::Uint(7u64.into())));
+    }
+
+    #[tokio::test]
+    async fn test_reorged_logs_are_removed_and_redelivered() {
+        let abi: Abi = serde_json::from_value(json!([{
+            "type": "event",
+            "name": "ComplianceViolationRecorded",
+            "anonymous": false,
+            "inputs": [{ "name": "severity", "type": "uint256", "indexed": false }]
+        }]))
+        .unwrap();
+        let event = abi.event("ComplianceViolationRecorded").unwrap().clone();
+        let log = |block: u64, block_hash: &str, removed: bool| {
+            json!({
+                "address": "0x00000000000000000000000000000000000000aa",
+                "topics": [format!("0x{}", hex::encode(event.signature().as_bytes()))],
+                "data": format!("0x{}", hex::encode(ethers::abi::encode(&[Token::Uint(7u64.into())]))),
+                "blockNumber": format!("0x{:x}", block),
+                "blockHash": block_hash,
+                "logIndex": "0x0",
+                "transactionHash": "0xabc",
+                "removed": removed,
+            })
+        };
+
+        let mut subscription = LogSubscription::new(String::new(), String::new(), event.clone());
+        let (sender, mut receiver) = mpsc::channel(16);
+        for log in [
+            log(10, "0xa", false),
+            log(11, "0xb", false),
+            // Duplicate from a backfill overlapping live delivery
+            log(11, "0xb", false),
+            // Reorg: block 11 is replaced and its log moves into a new block 11
+            log(11, "0xb", true),
+            log(11, "0xc", false),
+            // Removal of a log never delivered
+            log(9, "0xz", true),
+        ] {
+            assert!(subscription.deliver(&log, &sender).await);
+        }
+        drop(sender);
+
+        let mut delivered = Vec::new();
+        while let Some(event) = receiver.recv().await {
+            delivered.push((event.block_number, event.block_hash, event.removed));
+        }
+        assert_eq!(
+            delivered,
+            vec![
+                (10, "0xa".to_string(), false),
+                (11, "0xb".to_string(), false),
+                (11, "0xb".to_string(), true),
+                (11, "0xc".to_string(), false),
+            ]
+        );
+        assert_eq!(subscription.last_block(), Some(11));
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_relay_only_falls_back_when_allowed() {
+        let (relay_url, _) = mock_rpc(|_, _| None).await;
+        let (rpc_url, calls) = mock_rpc(|method, _| (method == "eth_sendRawTransaction").then(|| json!("0xabc"))).await;
+        let manager = EthereumManager::new().await.unwrap();
+        let provider = EthereumProvider::new("bsc", &rpc_url, 56, ConnectionPoolConfig::default()).unwrap();
+        manager.register_provider(provider).await;
+        manager.address_screener.set_blocking(false);
+        let protector = &manager.mev_protector;
+        protector
+            .configure(MevProtectionSettings {
+                relay_endpoint: relay_url,
+                relay_chain_id: 1,
+                relay_timeout: Duration::from_secs(5),
+                public_fallback: false,
+            })
+            .await;
+        let signed_tx = signed_transaction("0x00000000000000000000000000000000000000c1").await;
+
+        let error = protector.submit_private(&signed_tx).await.unwrap_err();
+        assert!(error.to_string().contains("signed for chain 56"));
+
+        protector
+            .configure(MevProtectionSettings { relay_chain_id: 56, ..protector.settings().await })
+            .await;
+        let error = protector.submit_private(&signed_tx).await.unwrap_err();
+        assert!(error.to_string().contains("public fallback is disabled"));
+
+        protector
+            .configure(MevProtectionSettings { public_fallback: true, ..protector.settings().await })
+            .await;
+        let error = protector.submit_private(&signed_tx).await.unwrap_err();
+        assert!(error.to_string().contains("No public network configured for chain 56"));
+
+        manager.networks.write().await.insert("bsc".to_string(), network(TxType::Eip1559));
+        let submission = protector.submit_private(&signed_tx).await.unwrap();
+        assert_eq!(submission.path, SubmissionPath::PublicMempool);
+        assert_eq!(submission.tx_hash, "0xabc");
+        assert_eq!(calls.lock().unwrap().as_slice(), ["eth_sendRawTransaction"]);
+    }
+
+    #[tokio::test]
+    async fn test_relay_timeout_never_falls_back() {
+        // Accepts the request and never answers, as a relay that may have taken the transaction
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let relay_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                held.push(socket);
+            }
+        });
+        let (rpc_url, calls) = mock_rpc(|method, _| (method == "eth_sendRawTransaction").then(|| json!("0xabc"))).await;
+        let manager = EthereumManager::new().await.unwrap();
+        let provider = EthereumProvider::new("bsc", &rpc_url, 56, ConnectionPoolConfig::default()).unwrap();
+        manager.register_provider(provider).await;
+        manager.networks.write().await.insert("bsc".to_string(), network(TxType::Eip1559));
+        manager.address_screener.set_blocking(false);
+        manager
+            .mev_protector
+            .configure(MevProtectionSettings {
+                relay_endpoint: relay_url,
+                relay_chain_id: 56,
+                relay_timeout: Duration::from_millis(200),
+                public_fallback: true,
+            })
+            .await;
+        let signed_tx = signed_transaction("0x00000000000000000000000000000000000000c1").await;
+
+        let error = manager.mev_protector.submit_private(&signed_tx).await.unwrap_err();
+        assert!(error.to_string().contains("outcome unknown"));
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_provider_connection_caps_concurrent_requests() {
+        let pool = ConnectionPoolConfig { max_concurrent_requests: 2, ..ConnectionPoolConfig::default() };
+        let provider = EthereumProvider::new("test", "http://localhost:8545", 1, pool).unwrap();
+        let shared = provider.clone();
+        let wait = Duration::from_millis(20);
+
+        let first = provider.connection.acquire(wait).await.unwrap();
+        let _second = shared.connection.acquire(wait).await.unwrap();
+        let busy = provider.connection.utilization();
+        assert_eq!(busy.in_flight, 2);
+        assert_eq!(busy.utilization, 1.0);
+
+        assert!(provider.connection.acquire(wait).await.is_err());
+        drop(first);
+        let _third = provider.connection.acquire(wait).await.unwrap();
+
+        let stats = shared.connection.utilization();
+        assert_eq!(stats.total_requests, 4);
+        assert_eq!(stats.waited_requests, 1);
+        assert_eq!(stats.peak_in_flight, 2);
+    }
+
+    #[tokio::test]
+    async fn test_configured_networks_build_providers_from_their_pool_settings() {
+        let manager = EthereumManager::new().await.unwrap();
+        let pool = ConnectionPoolConfig { max_concurrent_requests: 4, ..ConnectionPoolConfig::default() };
+        let mut networks = HashMap::from([(
+            "test".to_string(),
+            NetworkConfig { connection_pool: pool.clone(), ..network(TxType::Eip1559) },
+        )]);
+
+        manager.configure_networks(&networks).await;
+        let provider = manager.providers.read().await["test"].clone();
+        assert_eq!(provider.connection_pool(), &pool);
+        assert_eq!(provider.connection().utilization().max_concurrent_requests, 4);
+
+        networks.get_mut("test").unwrap().connection_pool.max_concurrent_requests = 8;
+        manager.configure_networks(&networks).await;
+        let provider = manager.providers.read().await["test"].clone();
+        assert_eq!(provider.connection_pool().max_concurrent_requests, 8);
+        assert_eq!(provider.connection().utilization().max_concurrent_requests, 8);
+    }
+
+    #[tokio::test]
+    async fn test_only_idempotent_calls_are_retried() {
+        let (primary, primary_calls) = mock_rpc(|_, _| None).await;
+        let (backup, backup_calls) = mock_rpc(|_, _| None).await;
+        let manager = manager_with_providers(&[&primary, &backup]).await;
+
+        // A failed send may still have been applied, so it is neither retried nor failed over
+        assert!(manager.rpc_with_failover(1, "eth_sendTransaction", json!([{}])).await.is_err());
+        assert_eq!(*primary_calls.lock().unwrap(), vec!["eth_sendTransaction"]);
+        assert!(backup_calls.lock().unwrap().is_empty());
+
+        assert!(manager.rpc_with_failover(1, "eth_blockNumber", json!([])).await.is_err());
+        assert_eq!(primary_calls.lock().unwrap().len(), 4);
+        assert_eq!(backup_calls.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_raw_transaction_rebroadcast_checks_hash_first() {
+        let raw = [0x02u8, 0xf8, 0x6b];
+        let expected = format!("0x{}", hex::encode(ethers::utils::keccak256(raw)));
+        let (url, calls) = mock_rpc(|method, _| match method {
+            "eth_getTransactionByHash" => Some(json!({ "blockNumber": null })),
+            _ => None,
+        })
+        .await;
+        let manager = manager_with_providers(&[&url]).await;
+
+        let tx_hash = manager
+            .rpc_with_failover(1, "eth_sendRawTransaction", json!([format!("0x{}", hex::encode(raw))]))
+            .await
+            .unwrap();
+        assert_eq!(tx_hash, json!(expected));
+        assert_eq!(*calls.lock().unwrap(), vec!["eth_sendRawTransaction", "eth_getTransactionByHash"]);
+    }
+
+    #[tokio::test]
+    async fn test_contract_send_is_signed_locally_and_rebroadcast_unchanged() {
+        let broadcasts = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sent = broadcasts.clone();
+        let (url, calls) = mock_rpc(move |method, params| match method {
+            "eth_feeHistory" => Some(json!({ "baseFeePerGas": ["0x3b9aca00"], "reward": [["0x1"]] })),
+            "eth_maxPriorityFeePerGas" => Some(json!("0x1")),
+            "eth_estimateGas" => Some(json!("0x5208")),
+            "eth_getTransactionCount" => Some(json!("0x7")),
+            "eth_getTransactionByHash" => Some(Value::Null),
+            "eth_sendRawTransaction" => {
+                let mut sent = sent.lock().unwrap();
+                sent.push(params[0].clone());
+                // The first broadcast is lost in transit
+                (sent.len() > 1).then(|| json!("0xabc"))
+            }
+            _ => None,
+        })
+        .await;
+        let manager = manager_with_providers(&[&url]).await;
+        manager.address_screener.set_blocking(false);
+        let mainnet = NetworkConfig { network_id: "mainnet".to_string(), chain_id: 1, ..network(TxType::Eip1559) };
+        manager.networks.write().await.insert("mainnet".to_string(), mainnet);
+
+        let abi = json!([{ "type": "function", "name": "ping", "stateMutability": "nonpayable", "inputs": [], "outputs": [] }]);
+        let contract = "0x00000000000000000000000000000000000000c1";
+        let error = manager.contract_manager.send("mainnet", contract, &abi, "ping", &[]).await.unwrap_err();
+        assert!(error.to_string().contains("No transaction signing key"));
+
+        manager.wallet_manager.set_signing_key(TEST_SIGNING_KEY.parse().unwrap()).await;
+        let tx_hash = manager.contract_manager.send("mainnet", contract, &abi, "ping", &[]).await.unwrap();
+        assert_eq!(tx_hash, "0xabc");
+
+        let broadcasts = broadcasts.lock().unwrap();
+        assert_eq!(broadcasts.len(), 2);
+        assert_eq!(broadcasts[0], broadcasts[1]);
+        let calls = calls.lock().unwrap();
+        assert!(!calls.iter().any(|method| method == "eth_accounts" || method == "eth_sendTransaction"));
+    }
+
+    #[tokio::test]
+    async fn test_send_once_broadcasts_a_single_time() {
+        let (url, calls) = mock_rpc(|method, _| match method {
+            "eth_feeHistory" => Some(json!({ "baseFeePerGas": ["0x3b9aca00"], "reward": [["0x1"]] })),
+            "eth_maxPriorityFeePerGas" => Some(json!("0x1")),
+            "eth_estimateGas" => Some(json!("0x5208")),
+            "eth_getTransactionCount" => Some(json!("0x7")),
+            _ => None,
+        })
+        .await;
+        let manager = manager_with_providers(&[&url, &url]).await;
+        manager.address_screener.set_blocking(false);
+        manager.wallet_manager.set_signing_key(TEST_SIGNING_KEY.parse().unwrap()).await;
+        let mainnet = NetworkConfig { network_id: "mainnet".to_string(), chain_id: 1, ..network(TxType::Eip1559) };
+        manager.networks.write().await.insert("mainnet".to_string(), mainnet);
+
+        let abi = json!([{ "type": "function", "name": "ping", "stateMutability": "nonpayable", "inputs": [], "outputs": [] }]);
+        let contract = "0x00000000000000000000000000000000000000c1";
+        assert!(manager.contract_manager.send_once("mainnet", contract, &abi, "ping", &[]).await.is_err());
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.iter().filter(|method| *method == "eth_sendRawTransaction").count(), 1);
+        assert!(!calls.iter().any(|method| method == "eth_getTransactionByHash"));
+    }
+
+    #[tokio::test]
+    async fn test_sanctioned_recipients_are_refused_at_the_send_chokepoint() {
+        const SANCTIONED: &str = "0xabc0000000000000000000000000000000000001";
+
+        struct ListedAddress;
+        #[async_trait::async_trait]
+        impl SanctionsListSource for ListedAddress {
+            async fn fetch(&self) -> Result<SanctionsList> {
+                let listing = SanctionsMatch {
+                    list: "OFAC SDN".to_string(),
+                    entity: "EXAMPLE MIXER, LTD".to_string(),
+                    currency: "ETH".to_string(),
+                };
+                Ok(SanctionsList {
+                    source: "OFAC SDN".to_string(),
+                    fetched_at: Utc::now(),
+                    addresses: HashMap::from([(SANCTIONED.to_string(), vec![listing])]),
+                })
+            }
+        }
+
+        let manager = EthereumManager::new().await.unwrap();
+        let transaction_manager = TransactionManager {
+            providers: manager.providers.clone(),
+            networks: manager.networks.clone(),
+            failover_requests: manager.failover_requests.clone(),
+            address_screener: Arc::new(AddressScreener::new(Arc::new(ListedAddress))),
+            wallet_manager: manager.wallet_manager.clone(),
+            send_lock: tokio::sync::Mutex::new(()),
+        };
+
+        // Pre-signed transactions, as submitted through the MEV protector
+        let error = transaction_manager
+            .screen_raw_transaction(&signed_transaction(SANCTIONED).await)
+            .await
+            .unwrap_err();
+        assert!(error.downcast_ref::<SanctionsError>().is_some());
+        let clean = signed_transaction("0x00000000000000000000000000000000000000c1").await;
+        assert!(transaction_manager.screen_raw_transaction(&clean).await.is_ok());
+        assert!(transaction_manager.screen_raw_transaction(&[0x02, 0xf8]).await.is_err());
+
+        // Transactions signed here, as sent by contract calls, are refused before pricing
+        manager.wallet_manager.set_signing_key(TEST_SIGNING_KEY.parse().unwrap()).await;
+        let mainnet = NetworkConfig { network_id: "mainnet".to_string(), chain_id: 1, ..network(TxType::Eip1559) };
+        manager.networks.write().await.insert("mainnet".to_string(), mainnet);
+        let tx = TransactionRequest { to: Some(SANCTIONED.to_string()), ..TransactionRequest::default() };
+        let error = transaction_manager.send("mainnet", tx).await.unwrap_err();
+        assert!(error.downcast_ref::<SanctionsError>().is_some());
+    }
+}
@@ -0,0 +1,396 @@
+//! Governance System
+//!
+//! Proposal lifecycle for decentralized regulatory decision making. Status
+//! changes follow a fixed state machine guarded by the voting window, quorum,
+//! approval threshold and execution delay, and every change is recorded in
+//! the audit trail.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::*;
+
+/// Voting power in circulation when no token supply has been configured
+const DEFAULT_TOTAL_SUPPLY: u64 = 1_000_000;
+
+/// Rejected governance operations
+#[derive(Debug, thiserror::Error)]
+pub enum GovernanceError {
+    #[error("proposal {0} not found")]
+    ProposalNotFound(Uuid),
+    #[error("illegal proposal transition {from:?} -> {to:?}")]
+    IllegalTransition { from: ProposalStatus, to: ProposalStatus },
+    #[error("proposal {proposal_id} cannot move to {to:?}: {reason}")]
+    GuardFailed { proposal_id: Uuid, to: ProposalStatus, reason: String },
+    #[error("invalid proposal: {0}")]
+    InvalidProposal(String),
+    #[error("proposal {0} is not open for voting")]
+    VotingClosed(Uuid),
+    #[error("voter {voter} needs {required} credits but has {available}")]
+    InsufficientCredits { voter: String, required: u64, available: u64 },
+}
+
+/// Side a vote is cast on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VoteChoice {
+    For,
+    Against,
+    Abstain,
+}
+
+/// Votes a voter has cast on one proposal
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VotePosition {
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+}
+
+impl VotePosition {
+    pub fn total(&self) -> u64 {
+        self.votes_for + self.votes_against + self.votes_abstain
+    }
+}
+
+/// Governance subsystem health
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceHealth {
+    pub active: bool,
+    pub total_proposals: u32,
+    pub active_proposals: u32,
+}
+
+impl GovernanceSystem {
+    pub async fn new(audit_trail_manager: Arc<AuditTrailManager>) -> Result<Self> {
+        Ok(Self {
+            system_id: Uuid::new_v4(),
+            governance_token: Arc::new(GovernanceToken { total_supply: DEFAULT_TOTAL_SUPPLY }),
+            proposal_manager: Arc::new(ProposalManager::new()),
+            voting_mechanism: Arc::new(VotingMechanism),
+            execution_engine: Arc::new(ExecutionEngine),
+            treasury_manager: Arc::new(TreasuryManager),
+            delegation_system: Arc::new(DelegationSystem),
+            quadratic_voting: Arc::new(QuadraticVoting::new()),
+            audit_trail_manager,
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    pub async fn initialize(&self) -> Result<()> {
+        info!("🗳️ Governance initialized with total voting supply {}", self.governance_token.total_supply);
+        Ok(())
+    }
+
+    pub async fn create_initial_proposals(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Register a new proposal in the `Pending` state
+    pub async fn create_proposal(&self, mut proposal: GovernanceProposal) -> Result<String> {
+        if proposal.voting_end <= proposal.voting_start {
+            return Err(GovernanceError::InvalidProposal("voting must end after it starts".to_string()).into());
+        }
+        for (name, value) in [("quorum", proposal.quorum_required), ("approval threshold", proposal.approval_threshold)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(GovernanceError::InvalidProposal(format!("{} {} is not a fraction", name, value)).into());
+            }
+        }
+
+        proposal.status = ProposalStatus::Pending;
+        let proposal_id = proposal.proposal_id;
+        self.proposal_manager.proposals.write().await.insert(proposal_id, proposal);
+        Ok(proposal_id.to_string())
+    }
+
+    pub async fn get_proposal(&self, proposal_id: Uuid) -> Option<GovernanceProposal> {
+        self.proposal_manager.proposals.read().await.get(&proposal_id).cloned()
+    }
+
+    /// Cast `votes` on a proposal, paying for them quadratically in voice credits
+    ///
+    /// The proposal must be `Active` and inside its voting window. The cost
+    /// is charged on the voter's cumulative votes on the proposal, so neither
+    /// repeated small votes nor splitting votes across for/against/abstain is
+    /// cheaper than casting them at once.
+    pub async fn cast_quadratic_vote(&self, proposal: Uuid, voter: &str, votes: u64, choice: VoteChoice) -> Result<()> {
+        let mut proposals = self.proposal_manager.proposals.write().await;
+        let target = proposals.get_mut(&proposal).ok_or(GovernanceError::ProposalNotFound(proposal))?;
+
+        let now = Utc::now();
+        if target.status != ProposalStatus::Active || now < target.voting_start || now >= target.voting_end {
+            return Err(GovernanceError::VotingClosed(proposal).into());
+        }
+
+        let cost = self.quadratic_voting.spend(proposal, voter, votes, choice).await?;
+        match choice {
+            VoteChoice::For => target.votes_for += votes,
+            VoteChoice::Against => target.votes_against += votes,
+            VoteChoice::Abstain => target.votes_abstain += votes,
+        }
+
+        info!("🗳️ {} cast {} {:?} votes on {} for {} credits", voter, votes, choice, proposal, cost);
+        Ok(())
+    }
+
+    /// Move a proposal to a new status
+    ///
+    /// Legal moves are Pending→Active (inside the voting window),
+    /// Active→Succeeded/Failed (after voting ends, decided by quorum and
+    /// approval threshold), Succeeded→Executed (after the execution delay),
+    /// and Pending/Active→Cancelled. Each accepted move is audited.
+    pub async fn transition_proposal(&self, id: Uuid, to: ProposalStatus) -> Result<()> {
+        let mut proposals = self.proposal_manager.proposals.write().await;
+        let proposal = proposals.get_mut(&id).ok_or(GovernanceError::ProposalNotFound(id))?;
+        let from = proposal.status;
+
+        self.check_transition(proposal, to, Utc::now())?;
+        proposal.status = to;
+        let proposal = proposal.clone();
+        drop(proposals);
+
+        let details = AuditDetails::new("GovernanceSystem", "TransitionProposal", &id.to_string())
+            .with_metadata("from", serde_json::json!(from))
+            .with_metadata("to", serde_json::json!(to))
+            .with_metadata("votes_for", serde_json::json!(proposal.votes_for))
+            .with_metadata("votes_against", serde_json::json!(proposal.votes_against))
+            .with_metadata("votes_abstain", serde_json::json!(proposal.votes_abstain));
+        self.audit_trail_manager
+            .create_entry(AuditEventType::Custom("GovernanceProposalTransition".to_string()), details)
+            .await?;
+
+        info!("🗳️ Proposal {} moved {:?} -> {:?}", id, from, to);
+        Ok(())
+    }
+
+    fn check_transition(&self, proposal: &GovernanceProposal, to: ProposalStatus, now: DateTime<Utc>) -> Result<()> {
+        let guard = |reason: String| GovernanceError::GuardFailed { proposal_id: proposal.proposal_id, to, reason };
+
+        match (proposal.status, to) {
+            (ProposalStatus::Pending, ProposalStatus::Active) => {
+                if now < proposal.voting_start {
+                    return Err(guard(format!("voting opens at {}", proposal.voting_start)).into());
+                }
+                if now >= proposal.voting_end {
+                    return Err(guard(format!("voting closed at {}", proposal.voting_end)).into());
+                }
+            }
+            (ProposalStatus::Active, ProposalStatus::Succeeded | ProposalStatus::Failed) => {
+                if now < proposal.voting_end {
+                    return Err(guard(format!("voting is open until {}", proposal.voting_end)).into());
+                }
+                let passed = self.tally_outcome(proposal);
+                if passed.is_ok() != (to == ProposalStatus::Succeeded) {
+                    let reason = passed.err().unwrap_or_else(|| "quorum and approval threshold were met".to_string());
+                    return Err(guard(reason).into());
+                }
+            }
+            (ProposalStatus::Succeeded, ProposalStatus::Executed) => {
+                let executable_at = proposal.voting_end + proposal.execution_delay;
+                if now < executable_at {
+                    return Err(guard(format!("execution delay runs until {}", executable_at)).into());
+                }
+            }
+            (ProposalStatus::Pending | ProposalStatus::Active, ProposalStatus::Cancelled) => {}
+            (from, to) => return Err(GovernanceError::IllegalTransition { from, to }.into()),
+        }
+
+        Ok(())
+    }
+
+    /// `Ok` if the tally meets quorum and approval threshold, otherwise why not
+    fn tally_outcome(&self, proposal: &GovernanceProposal) -> Result<(), String> {
+        let cast = proposal.votes_for + proposal.votes_against + proposal.votes_abstain;
+        let supply = self.governance_token.total_supply.max(1);
+        let turnout = cast as f64 / supply as f64;
+        if turnout < proposal.quorum_required {
+            return Err(format!("turnout {:.4} below quorum {:.4}", turnout, proposal.quorum_required));
+        }
+
+        let decisive = proposal.votes_for + proposal.votes_against;
+        let approval = if decisive == 0 { 0.0 } else { proposal.votes_for as f64 / decisive as f64 };
+        if approval < proposal.approval_threshold {
+            return Err(format!("approval {:.4} below threshold {:.4}", approval, proposal.approval_threshold));
+        }
+
+        Ok(())
+    }
+
+    pub async fn health_check(&self) -> Result<GovernanceHealth> {
+        let proposals = self.proposal_manager.proposals.read().await;
+        Ok(GovernanceHealth {
+            active: true,
+            total_proposals: proposals.len() as u32,
+            active_proposals: proposals.values().filter(|p| p.status == ProposalStatus::Active).count() as u32,
+        })
+    }
+}
+
+impl QuadraticVoting {
+    pub fn new() -> Self {
+        Self {
+            credits: RwLock::new(HashMap::new()),
+            positions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Credits needed to cast `votes` votes at once: n votes cost n²
+    pub fn cost_for_votes(votes: u64) -> u64 {
+        votes.saturating_mul(votes)
+    }
+
+    /// Add voice credits to a voter's balance
+    pub async fn grant_credits(&self, voter: &str, credits: u64) {
+        let mut balances = self.credits.write().await;
+        let balance = balances.entry(voter.to_string()).or_insert(0);
+        *balance = balance.saturating_add(credits);
+    }
+
+    pub async fn remaining_credits(&self, voter: &str) -> u64 {
+        self.credits.read().await.get(voter).copied().unwrap_or(0)
+    }
+
+    /// Deduct the marginal cost of `votes` more votes and record them
+    async fn spend(&self, proposal: Uuid, voter: &str, votes: u64, choice: VoteChoice) -> Result<u64> {
+        let mut balances = self.credits.write().await;
+        let mut positions = self.positions.write().await;
+
+        let key = (proposal, voter.to_string());
+        let already_cast = positions.get(&key).map(VotePosition::total).unwrap_or(0);
+        let cost = Self::cost_for_votes(already_cast.saturating_add(votes)) - Self::cost_for_votes(already_cast);
+
+        let available = balances.get(voter).copied().unwrap_or(0);
+        if cost > available {
+            return Err(GovernanceError::InsufficientCredits { voter: voter.to_string(), required: cost, available }.into());
+        }
+
+        balances.insert(voter.to_string(), available - cost);
+        let position = positions.entry(key).or_default();
+        match choice {
+            VoteChoice::For => position.votes_for += votes,
+            VoteChoice::Against => position.votes_against += votes,
+            VoteChoice::Abstain => position.votes_abstain += votes,
+        }
+        Ok(cost)
+    }
+}
+
+impl ProposalManager {
+    pub fn new() -> Self {
+        Self {
+            proposals: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn governance() -> GovernanceSystem {
+        let ethereum_manager = Arc::new(EthereumManager::new().await.unwrap());
+        let audit_trail_manager = Arc::new(AuditTrailManager::new(ethereum_manager).await.unwrap());
+        GovernanceSystem::new(audit_trail_manager).await.unwrap()
+    }
+
+    fn proposal(voting_start: DateTime<Utc>, voting_end: DateTime<Utc>) -> GovernanceProposal {
+        GovernanceProposal {
+            proposal_id: Uuid::new_v4(),
+            title: "Raise reporting threshold".to_string(),
+            description: String::new(),
+            proposal_type: ProposalType::ParameterChange,
+            proposer: "council".to_string(),
+            creation_time: Utc::now(),
+            voting_start,
+            voting_end,
+            execution_delay: chrono::Duration::hours(48),
+            quorum_required: 0.1,
+            approval_threshold: 0.5,
+            status: ProposalStatus::Pending,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            execution_payload: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_illegal_transitions_are_rejected() {
+        let governance = governance().await;
+        let now = Utc::now();
+        let open = proposal(now - chrono::Duration::hours(1), now + chrono::Duration::hours(1));
+        let id = governance.create_proposal(open).await.unwrap().parse().unwrap();
+
+        let error = governance.transition_proposal(id, ProposalStatus::Executed).await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<GovernanceError>(), Some(GovernanceError::IllegalTransition { .. })));
+
+        governance.transition_proposal(id, ProposalStatus::Active).await.unwrap();
+        let error = governance.transition_proposal(id, ProposalStatus::Succeeded).await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<GovernanceError>(), Some(GovernanceError::GuardFailed { .. })));
+
+        let trail = governance.audit_trail_manager.trail_storage.entries().await;
+        assert_eq!(trail.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tally_decides_outcome_and_execution_waits_for_delay() {
+        let governance = governance().await;
+        let now = Utc::now();
+        let mut closed = proposal(now - chrono::Duration::hours(2), now - chrono::Duration::hours(1));
+        closed.status = ProposalStatus::Active;
+        closed.votes_for = 80_000;
+        closed.votes_against = 30_000;
+        let id = closed.proposal_id;
+        governance.proposal_manager.proposals.write().await.insert(id, closed);
+
+        assert!(governance.transition_proposal(id, ProposalStatus::Failed).await.is_err());
+        governance.transition_proposal(id, ProposalStatus::Succeeded).await.unwrap();
+
+        let error = governance.transition_proposal(id, ProposalStatus::Executed).await.unwrap_err();
+        assert!(error.to_string().contains("execution delay"));
+        assert_eq!(governance.get_proposal(id).await.unwrap().status, ProposalStatus::Succeeded);
+    }
+
+    #[test]
+    fn test_quadratic_cost() {
+        assert_eq!(QuadraticVoting::cost_for_votes(0), 0);
+        assert_eq!(QuadraticVoting::cost_for_votes(3), 9);
+        assert_eq!(QuadraticVoting::cost_for_votes(10), 100);
+    }
+
+    #[tokio::test]
+    async fn test_split_votes_are_priced_on_cumulative_total() {
+        let governance = governance().await;
+        let now = Utc::now();
+        let mut open = proposal(now - chrono::Duration::hours(1), now + chrono::Duration::hours(1));
+        open.status = ProposalStatus::Active;
+        let id = open.proposal_id;
+        governance.proposal_manager.proposals.write().await.insert(id, open);
+        governance.quadratic_voting.grant_credits("alice", 30).await;
+
+        governance.cast_quadratic_vote(id, "alice", 3, VoteChoice::For).await.unwrap();
+        assert_eq!(governance.quadratic_voting.remaining_credits("alice").await, 21);
+
+        // 3 more votes move alice from 3 to 6 cumulative: 36 - 9 = 27 credits
+        let error = governance.cast_quadratic_vote(id, "alice", 3, VoteChoice::Against).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<GovernanceError>(),
+            Some(GovernanceError::InsufficientCredits { required: 27, available: 21, .. })
+        ));
+
+        governance.cast_quadratic_vote(id, "alice", 2, VoteChoice::Against).await.unwrap();
+        assert_eq!(governance.quadratic_voting.remaining_credits("alice").await, 5);
+
+        let tally = governance.get_proposal(id).await.unwrap();
+        assert_eq!((tally.votes_for, tally.votes_against), (3, 2));
+    }
+}
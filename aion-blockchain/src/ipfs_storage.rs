@@ -0,0 +1,547 @@
+//! IPFS Storage
+//!
+//! Content is added to an IPFS node and replicated to the configured pinning
+//! services (IPFS Pinning Service API). A store only succeeds once enough
+//! replicas have been confirmed pinned, and availability can be re-checked
+//! later through the node gateways.
+//!
+//! Sensitive content can be encrypted before upload: the payload is sealed
+//! with AES-256-GCM under a fresh content key, which is wrapped for each
+//! recipient through secp256k1 ECDH. Only ciphertext and public keys leave
+//! the process.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use ipfs_api_backend_hyper::{IpfsApi, IpfsClient, TryFromUri};
+use ring::rand::{SecureRandom, SystemRandom};
+use secp256k1::ecdh::SharedSecret;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::*;
+
+/// Status polls before a queued pin is reported as unconfirmed
+const PIN_CONFIRM_ATTEMPTS: u32 = 5;
+const PIN_CONFIRM_INTERVAL: Duration = Duration::from_secs(2);
+const GATEWAY_TIMEOUT: Duration = Duration::from_secs(15);
+
+const ENVELOPE_VERSION: u32 = 1;
+const KEY_WRAP_CONTEXT: &[u8] = b"aion-ipfs-key-wrap-v1";
+
+/// Redundancy failures
+#[derive(Debug, thiserror::Error)]
+pub enum IpfsRedundancyError {
+    #[error("no healthy IPFS node available to add content")]
+    NoNodeAvailable,
+    #[error("content {cid} pinned on {confirmed} of {required} required replicas: {}", .failures.join("; "))]
+    RedundancyNotMet {
+        cid: String,
+        confirmed: u32,
+        required: u32,
+        failures: Vec<String>,
+    },
+}
+
+/// Whether audit metadata is encrypted before upload, and for whom
+#[derive(Debug, Clone, Default)]
+pub struct EncryptionSettings {
+    pub encrypt: bool,
+    pub recipients: Vec<PublicKey>,
+}
+
+/// Ciphertext envelope stored on IPFS in place of the plaintext
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedEnvelope {
+    pub version: u32,
+    pub algorithm: String,
+    pub nonce: String,
+    pub ciphertext: String,
+    pub recipients: Vec<WrappedKey>,
+}
+
+/// Content key wrapped for one recipient
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedKey {
+    pub recipient_public_key: String,
+    /// Ephemeral key the recipient combines with its secret key
+    pub ephemeral_public_key: String,
+    pub nonce: String,
+    pub wrapped_key: String,
+}
+
+/// Outcome of pinning content on one node or service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinOutcome {
+    pub service_name: String,
+    pub pinned: bool,
+    pub error: Option<String>,
+}
+
+/// Result of a redundant store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpfsStoreReceipt {
+    pub cid: String,
+    pub size_bytes: u64,
+    pub replicas_confirmed: u32,
+    pub min_replicas: u32,
+    pub pins: Vec<PinOutcome>,
+    pub stored_at: DateTime<Utc>,
+}
+
+/// Whether content could be fetched from one node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeAvailability {
+    pub node_id: String,
+    pub available: bool,
+    pub error: Option<String>,
+}
+
+/// Result of probing node gateways for a CID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailabilityReport {
+    pub cid: String,
+    pub available: bool,
+    pub reachable_replicas: u32,
+    pub min_replicas: u32,
+    pub nodes: Vec<NodeAvailability>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl IPFSManager {
+    pub async fn new() -> Result<Self> {
+        let mut nodes = HashMap::new();
+        nodes.insert("local".to_string(), IPFSNode {
+            node_id: "local".to_string(),
+            api_endpoint: "http://127.0.0.1:5001".to_string(),
+            gateway_endpoint: "http://127.0.0.1:8080".to_string(),
+            swarm_addresses: Vec::new(),
+            node_type: IPFSNodeType::Local,
+            storage_capacity: 0,
+            bandwidth_limit: 0,
+            health_status: NodeHealth::Online,
+            last_ping: Utc::now(),
+        });
+
+        Ok(Self {
+            manager_id: Uuid::new_v4(),
+            ipfs_nodes: Arc::new(RwLock::new(nodes)),
+            pinning_services: Arc::new(RwLock::new(HashMap::new())),
+            content_manager: Arc::new(ContentManager),
+            encryption_manager: Arc::new(EncryptionManager::new()),
+            redundancy_manager: Arc::new(RedundancyManager { min_replicas: 1 }),
+            access_controller: Arc::new(IPFSAccessController),
+            http_client: reqwest::Client::new(),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!(
+            "📦 IPFS manager started with {} nodes and {} pinning services",
+            self.ipfs_nodes.read().await.len(),
+            self.pinning_services.read().await.len()
+        );
+        Ok(())
+    }
+
+    pub async fn register_pinning_service(&self, service: PinningService) {
+        self.pinning_services.write().await.insert(service.service_name.clone(), service);
+    }
+
+    /// Store an audit entry's metadata with the configured redundancy
+    ///
+    /// Encrypted for the configured recipients when encryption is enabled.
+    pub async fn store_audit_metadata(&self, entry: &AuditTrailEntry) -> Result<String> {
+        let data = serde_json::to_vec(entry)?;
+        let settings = self.encryption_manager.settings().await;
+        if settings.encrypt {
+            return self.store_encrypted(&data, &settings.recipients).await;
+        }
+
+        let receipt = self.store_with_redundancy(&data, self.redundancy_manager.min_replicas).await?;
+        Ok(receipt.cid)
+    }
+
+    /// Encrypt `data` for `recipients` and store the ciphertext, returning its CID
+    pub async fn store_encrypted(&self, data: &[u8], recipients: &[PublicKey]) -> Result<String> {
+        let envelope = self.encryption_manager.seal(data, recipients)?;
+        let receipt = self
+            .store_with_redundancy(&serde_json::to_vec(&envelope)?, self.redundancy_manager.min_replicas)
+            .await?;
+        info!("🔐 Stored encrypted content {} for {} recipients", receipt.cid, recipients.len());
+        Ok(receipt.cid)
+    }
+
+    /// Fetch an encrypted envelope and decrypt it with a recipient's secret key
+    pub async fn fetch_and_decrypt(&self, cid: &str, key: &SecretKey) -> Result<Vec<u8>> {
+        let envelope: EncryptedEnvelope = serde_json::from_slice(&self.fetch(cid).await?)?;
+        self.encryption_manager.open(&envelope, key)
+    }
+
+    /// Add content and pin it on the node and every enabled pinning service
+    ///
+    /// Fails with `IpfsRedundancyError::RedundancyNotMet` unless at least
+    /// `min_replicas` pins are confirmed; the error lists each failed pin.
+    pub async fn store_with_redundancy(&self, data: &[u8], min_replicas: u32) -> Result<IpfsStoreReceipt> {
+        let node = self.primary_node().await?;
+        let client = IpfsClient::from_str(&node.api_endpoint)?;
+        let cid = client.add(Cursor::new(data.to_vec())).await?.hash;
+
+        let mut pins = vec![match client.pin_add(&cid, true).await {
+            Ok(_) => PinOutcome { service_name: node.node_id.clone(), pinned: true, error: None },
+            Err(e) => PinOutcome { service_name: node.node_id.clone(), pinned: false, error: Some(e.to_string()) },
+        }];
+
+        let services: Vec<PinningService> = self
+            .pinning_services
+            .read()
+            .await
+            .values()
+            .filter(|service| service.enabled)
+            .cloned()
+            .collect();
+        let remote_pins = futures::future::join_all(services.iter().map(|service| self.pin_remote(service, &cid))).await;
+        pins.extend(remote_pins);
+
+        let receipt = check_redundancy(cid, data.len() as u64, min_replicas, pins)?;
+        info!(
+            "📦 Stored {} with {}/{} confirmed replicas",
+            receipt.cid, receipt.replicas_confirmed, receipt.min_replicas
+        );
+        Ok(receipt)
+    }
+
+    /// Check that `cid` can be fetched through at least `min_replicas` node gateways
+    pub async fn verify_availability(&self, cid: &str) -> Result<AvailabilityReport> {
+        let nodes: Vec<IPFSNode> = self.ipfs_nodes.read().await.values().cloned().collect();
+        let probes = nodes.iter().map(|node| async move {
+            let url = format!("{}/ipfs/{}", node.gateway_endpoint.trim_end_matches('/'), cid);
+            let outcome = self.http_client.head(&url).timeout(GATEWAY_TIMEOUT).send().await;
+            match outcome {
+                Ok(response) if response.status().is_success() => {
+                    NodeAvailability { node_id: node.node_id.clone(), available: true, error: None }
+                }
+                Ok(response) => NodeAvailability {
+                    node_id: node.node_id.clone(),
+                    available: false,
+                    error: Some(format!("gateway returned {}", response.status())),
+                },
+                Err(e) => NodeAvailability { node_id: node.node_id.clone(), available: false, error: Some(e.to_string()) },
+            }
+        });
+        let nodes = futures::future::join_all(probes).await;
+
+        let reachable_replicas = nodes.iter().filter(|node| node.available).count() as u32;
+        let min_replicas = self.redundancy_manager.min_replicas;
+        if reachable_replicas < min_replicas {
+            warn!("📦 {} reachable on {} of {} required nodes", cid, reachable_replicas, min_replicas);
+        }
+
+        Ok(AvailabilityReport {
+            cid: cid.to_string(),
+            available: reachable_replicas >= min_replicas,
+            reachable_replicas,
+            min_replicas,
+            nodes,
+            checked_at: Utc::now(),
+        })
+    }
+
+    /// Fetch raw content by CID from the primary node
+    pub async fn fetch(&self, cid: &str) -> Result<Vec<u8>> {
+        let node = self.primary_node().await?;
+        let client = IpfsClient::from_str(&node.api_endpoint)?;
+        let data = client.cat(cid).map_ok(|chunk| chunk.to_vec()).try_concat().await?;
+        Ok(data)
+    }
+
+    async fn primary_node(&self) -> Result<IPFSNode> {
+        let nodes = self.ipfs_nodes.read().await;
+        let mut online: Vec<&IPFSNode> = nodes
+            .values()
+            .filter(|node| matches!(node.health_status, NodeHealth::Online))
+            .collect();
+        online.sort_by_key(|node| (!matches!(node.node_type, IPFSNodeType::Local), node.node_id.clone()));
+        online
+            .first()
+            .map(|node| (*node).clone())
+            .ok_or_else(|| anyhow!(IpfsRedundancyError::NoNodeAvailable))
+    }
+
+    /// Request a pin from a Pinning Service API endpoint and wait for it to be pinned
+    async fn pin_remote(&self, service: &PinningService, cid: &str) -> PinOutcome {
+        match self.request_remote_pin(service, cid).await {
+            Ok(()) => PinOutcome { service_name: service.service_name.clone(), pinned: true, error: None },
+            Err(e) => {
+                warn!("📦 Pinning {} on {} failed: {}", cid, service.service_name, e);
+                PinOutcome { service_name: service.service_name.clone(), pinned: false, error: Some(e.to_string()) }
+            }
+        }
+    }
+
+    async fn request_remote_pin(&self, service: &PinningService, cid: &str) -> Result<()> {
+        let endpoint = service.endpoint.trim_end_matches('/');
+        let created: Value = self
+            .http_client
+            .post(format!("{}/pins", endpoint))
+            .bearer_auth(&service.access_token)
+            .json(&json!({ "cid": cid }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let request_id = created["requestid"]
+            .as_str()
+            .ok_or_else(|| anyhow!("pin response has no request id"))?
+            .to_string();
+
+        let mut status = created["status"].as_str().unwrap_or_default().to_string();
+        for _ in 0..PIN_CONFIRM_ATTEMPTS {
+            match status.as_str() {
+                "pinned" => return Ok(()),
+                "failed" => return Err(anyhow!("service reported the pin as failed")),
+                _ => tokio::time::sleep(PIN_CONFIRM_INTERVAL).await,
+            }
+            let current: Value = self
+                .http_client
+                .get(format!("{}/pins/{}", endpoint, request_id))
+                .bearer_auth(&service.access_token)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            status = current["status"].as_str().unwrap_or_default().to_string();
+        }
+
+        if status == "pinned" {
+            Ok(())
+        } else {
+            Err(anyhow!("pin still '{}' after {} status checks", status, PIN_CONFIRM_ATTEMPTS))
+        }
+    }
+}
+
+impl EncryptionManager {
+    pub fn new() -> Self {
+        Self {
+            settings: RwLock::new(EncryptionSettings::default()),
+        }
+    }
+
+    /// Encrypt audit metadata for `recipients` from now on
+    pub async fn enable(&self, recipients: Vec<PublicKey>) -> Result<()> {
+        if recipients.is_empty() {
+            return Err(anyhow!("Encryption needs at least one recipient"));
+        }
+        *self.settings.write().await = EncryptionSettings { encrypt: true, recipients };
+        Ok(())
+    }
+
+    pub async fn settings(&self) -> EncryptionSettings {
+        self.settings.read().await.clone()
+    }
+
+    /// Seal `data` under a fresh content key wrapped for every recipient
+    pub fn seal(&self, data: &[u8], recipients: &[PublicKey]) -> Result<EncryptedEnvelope> {
+        if recipients.is_empty() {
+            return Err(anyhow!("Encryption needs at least one recipient"));
+        }
+
+        let rng = SystemRandom::new();
+        let content_key: [u8; 32] = random_bytes(&rng)?;
+        let (nonce, ciphertext) = aes_encrypt(&rng, &content_key, data)?;
+
+        let secp = Secp256k1::new();
+        let recipients = recipients
+            .iter()
+            .map(|recipient| -> Result<WrappedKey> {
+                let ephemeral_secret = loop {
+                    if let Ok(key) = SecretKey::from_slice(&random_bytes::<32>(&rng)?) {
+                        break key;
+                    }
+                };
+                let wrapping_key = wrapping_key(recipient, &ephemeral_secret);
+                let (nonce, wrapped_key) = aes_encrypt(&rng, &wrapping_key, &content_key)?;
+                Ok(WrappedKey {
+                    recipient_public_key: hex::encode(recipient.serialize()),
+                    ephemeral_public_key: hex::encode(PublicKey::from_secret_key(&secp, &ephemeral_secret).serialize()),
+                    nonce: hex::encode(nonce),
+                    wrapped_key: hex::encode(wrapped_key),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(EncryptedEnvelope {
+            version: ENVELOPE_VERSION,
+            algorithm: "AES-256-GCM+secp256k1-ECDH".to_string(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+            recipients,
+        })
+    }
+
+    /// Unwrap the content key for `key` and decrypt the envelope
+    pub fn open(&self, envelope: &EncryptedEnvelope, key: &SecretKey) -> Result<Vec<u8>> {
+        let own_public_key = hex::encode(PublicKey::from_secret_key(&Secp256k1::new(), key).serialize());
+        let slot = envelope
+            .recipients
+            .iter()
+            .find(|slot| slot.recipient_public_key == own_public_key)
+            .ok_or_else(|| anyhow!("Content was not encrypted for this key"))?;
+
+        let ephemeral_public_key = PublicKey::from_slice(&hex::decode(&slot.ephemeral_public_key)?)?;
+        let wrapping_key = wrapping_key(&ephemeral_public_key, key);
+        let content_key = aes_decrypt(&wrapping_key, &hex::decode(&slot.nonce)?, &hex::decode(&slot.wrapped_key)?)?;
+        let content_key: [u8; 32] = content_key
+            .try_into()
+            .map_err(|_| anyhow!("Unwrapped content key has the wrong length"))?;
+
+        aes_decrypt(&content_key, &hex::decode(&envelope.nonce)?, &hex::decode(&envelope.ciphertext)?)
+    }
+}
+
+/// Key-wrapping key derived from the ECDH shared secret
+fn wrapping_key(public_key: &PublicKey, secret_key: &SecretKey) -> [u8; 32] {
+    let shared = SharedSecret::new(public_key, secret_key);
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_WRAP_CONTEXT);
+    hasher.update(shared.secret_bytes());
+    hasher.finalize().into()
+}
+
+fn aes_encrypt(rng: &SystemRandom, key: &[u8; 32], plaintext: &[u8]) -> Result<([u8; 12], Vec<u8>)> {
+    let nonce: [u8; 12] = random_bytes(rng)?;
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| anyhow!("Invalid AES key length"))?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| anyhow!("Encryption failed"))?;
+    Ok((nonce, ciphertext))
+}
+
+fn aes_decrypt(key: &[u8; 32], nonce: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+    if nonce.len() != 12 {
+        return Err(anyhow!("Invalid nonce length"));
+    }
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| anyhow!("Invalid AES key length"))?;
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Decryption failed: wrong key or tampered ciphertext"))
+}
+
+fn random_bytes<const N: usize>(rng: &SystemRandom) -> Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    rng.fill(&mut bytes).map_err(|_| anyhow!("System random number generator failed"))?;
+    Ok(bytes)
+}
+
+/// Turn pin outcomes into a receipt, or an error if too few were confirmed
+fn check_redundancy(cid: String, size_bytes: u64, min_replicas: u32, pins: Vec<PinOutcome>) -> Result<IpfsStoreReceipt> {
+    let replicas_confirmed = pins.iter().filter(|pin| pin.pinned).count() as u32;
+    if replicas_confirmed < min_replicas {
+        let failures = pins
+            .iter()
+            .filter(|pin| !pin.pinned)
+            .map(|pin| format!("{}: {}", pin.service_name, pin.error.as_deref().unwrap_or("not pinned")))
+            .collect();
+        return Err(IpfsRedundancyError::RedundancyNotMet {
+            cid,
+            confirmed: replicas_confirmed,
+            required: min_replicas,
+            failures,
+        }
+        .into());
+    }
+
+    Ok(IpfsStoreReceipt {
+        cid,
+        size_bytes,
+        replicas_confirmed,
+        min_replicas,
+        pins,
+        stored_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pin(service_name: &str, pinned: bool) -> PinOutcome {
+        PinOutcome {
+            service_name: service_name.to_string(),
+            pinned,
+            error: (!pinned).then(|| "timeout".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_redundancy_not_met_lists_failed_services() {
+        let pins = vec![pin("local", true), pin("pinata", false), pin("web3storage", false)];
+        let error = check_redundancy("bafy".to_string(), 10, 2, pins).unwrap_err();
+
+        match error.downcast_ref::<IpfsRedundancyError>() {
+            Some(IpfsRedundancyError::RedundancyNotMet { confirmed, required, failures, .. }) => {
+                assert_eq!((*confirmed, *required), (1, 2));
+                assert_eq!(failures, &vec!["pinata: timeout".to_string(), "web3storage: timeout".to_string()]);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_receipt_reports_each_pin() {
+        let pins = vec![pin("local", true), pin("pinata", true), pin("web3storage", false)];
+        let receipt = check_redundancy("bafy".to_string(), 10, 2, pins).unwrap();
+        assert_eq!(receipt.replicas_confirmed, 2);
+        assert_eq!(receipt.pins.len(), 3);
+    }
+
+    fn keypair(seed: u8) -> (SecretKey, PublicKey) {
+        let secret = SecretKey::from_slice(&[seed; 32]).unwrap();
+        (secret, PublicKey::from_secret_key(&Secp256k1::new(), &secret))
+    }
+
+    #[test]
+    fn test_envelope_opens_for_each_recipient_only() {
+        let manager = EncryptionManager::new();
+        let (alice_secret, alice) = keypair(1);
+        let (bob_secret, bob) = keypair(2);
+        let (mallory_secret, _) = keypair(3);
+        let data = br#"{"actor":"compliance-officer","resource":"customer-42"}"#;
+
+        let envelope = manager.seal(data, &[alice, bob]).unwrap();
+        let serialized = serde_json::to_string(&envelope).unwrap();
+        assert!(!serialized.contains("customer-42"));
+
+        assert_eq!(manager.open(&envelope, &alice_secret).unwrap(), data);
+        assert_eq!(manager.open(&envelope, &bob_secret).unwrap(), data);
+        assert!(manager.open(&envelope, &mallory_secret).is_err());
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_is_rejected() {
+        let manager = EncryptionManager::new();
+        let (secret, public) = keypair(7);
+        let mut envelope = manager.seal(b"sensitive", &[public]).unwrap();
+
+        let mut ciphertext = hex::decode(&envelope.ciphertext).unwrap();
+        ciphertext[0] ^= 0xff;
+        envelope.ciphertext = hex::encode(ciphertext);
+
+        assert!(manager.open(&envelope, &secret).is_err());
+    }
+}
@@ -23,6 +23,7 @@ pub mod defi_integration;
 pub mod nft_compliance;
 pub mod audit_trails;
 pub mod governance;
+pub mod sanctions;
 
 pub use ethereum::*;
 pub use bitcoin::*;
@@ -35,6 +36,7 @@ pub use defi_integration::*;
 pub use nft_compliance::*;
 pub use audit_trails::*;
 pub use governance::*;
+pub use sanctions::*;
 
 /// Main Blockchain Integration System
 pub struct BlockchainIntegration {
@@ -82,9 +84,25 @@ pub struct NetworkConfig {
     pub confirmation_blocks: u32,
     pub max_fee_per_gas: u64,
     pub priority_fee_per_gas: u64,
+    /// Transaction envelope the network accepts
+    #[serde(default)]
+    pub tx_type: TxType,
+    /// Connection reuse and concurrency limits for the network's HTTP provider
+    #[serde(default)]
+    pub connection_pool: ConnectionPoolConfig,
     pub enabled: bool,
 }
 
+/// EVM transaction envelope
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TxType {
+    /// Pre-London type-0 transaction priced with a single `gasPrice`
+    Legacy,
+    /// Type-2 transaction with `maxFeePerGas` / `maxPriorityFeePerGas`
+    #[default]
+    Eip1559,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BlockchainType {
     Ethereum,
@@ -112,6 +130,10 @@ pub enum BlockchainType {
 pub struct EthereumManager {
     pub manager_id: Uuid,
     pub providers: Arc<RwLock<HashMap<String, EthereumProvider>>>,
+    pub networks: Arc<RwLock<HashMap<String, NetworkConfig>>>,
+    pub failover_requests: Arc<std::sync::atomic::AtomicU64>,
+    /// Counterparty sanctions screening, shared with the transaction manager
+    pub address_screener: Arc<AddressScreener>,
     pub wallet_manager: Arc<WalletManager>,
     pub contract_manager: Arc<ContractManager>,
     pub transaction_manager: Arc<TransactionManager>,
@@ -121,6 +143,67 @@ pub struct EthereumManager {
     pub layer2_integrator: Arc<Layer2Integrator>,
 }
 
+/// Bitcoin Manager for OP_RETURN audit anchoring through a Bitcoin Core wallet
+pub struct BitcoinManager {
+    pub manager_id: Uuid,
+    /// Network anchors are broadcast on, if any is configured
+    pub network: Arc<RwLock<Option<NetworkConfig>>>,
+    pub http_client: reqwest::Client,
+    pub fee_policy: BitcoinFeePolicy,
+}
+
+/// Consensus Engine deciding when blocks, and the audit anchors in them, are final
+pub struct ConsensusEngine {
+    settings: RwLock<ConsensusSettings>,
+    pub ethereum_manager: Arc<EthereumManager>,
+    pub bitcoin_manager: Arc<BitcoinManager>,
+    /// Anchor transactions already seen final, with their block number
+    pub final_anchors: RwLock<FinalAnchors>,
+}
+
+/// Typed calls to deployed contracts over the Ethereum manager's providers
+pub struct ContractManager {
+    pub providers: Arc<RwLock<HashMap<String, EthereumProvider>>>,
+    pub networks: Arc<RwLock<HashMap<String, NetworkConfig>>>,
+    pub failover_requests: Arc<std::sync::atomic::AtomicU64>,
+    /// Signs and broadcasts state-changing calls
+    pub transaction_manager: Arc<TransactionManager>,
+}
+
+/// Prices, signs and broadcasts every outgoing EVM transaction
+pub struct TransactionManager {
+    pub providers: Arc<RwLock<HashMap<String, EthereumProvider>>>,
+    pub networks: Arc<RwLock<HashMap<String, NetworkConfig>>>,
+    pub failover_requests: Arc<std::sync::atomic::AtomicU64>,
+    pub address_screener: Arc<AddressScreener>,
+    pub wallet_manager: Arc<WalletManager>,
+    /// Held from nonce lookup to broadcast so concurrent sends get distinct nonces
+    send_lock: tokio::sync::Mutex<()>,
+}
+
+/// Key outgoing transactions are signed with, loaded from configured key storage
+pub struct WalletManager {
+    /// Sending fails until a key is loaded
+    signer: RwLock<Option<ethers::signers::LocalWallet>>,
+}
+
+/// Contract event subscriptions over the networks' WebSocket endpoints
+pub struct EventListener {
+    pub networks: Arc<RwLock<HashMap<String, NetworkConfig>>>,
+}
+
+/// Private-relay submission keeping signed transactions out of the public mempool
+pub struct MEVProtector {
+    settings: RwLock<MevProtectionSettings>,
+    pub http_client: reqwest::Client,
+    /// Public submission path, shared with the Ethereum manager
+    pub providers: Arc<RwLock<HashMap<String, EthereumProvider>>>,
+    pub networks: Arc<RwLock<HashMap<String, NetworkConfig>>>,
+    pub failover_requests: Arc<std::sync::atomic::AtomicU64>,
+    /// Screens the recipient of every submitted transaction
+    pub transaction_manager: Arc<TransactionManager>,
+}
+
 #[derive(Debug, Clone)]
 pub struct EthereumProvider {
     pub provider_id: String,
@@ -131,6 +214,9 @@ pub struct EthereumProvider {
     pub timeout: std::time::Duration,
     pub retry_attempts: u32,
     pub health_status: ProviderHealth,
+    connection_pool: ConnectionPoolConfig,
+    /// Client and request limiter built from `connection_pool`, shared by clones
+    connection: ProviderConnection,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -202,6 +288,7 @@ pub struct IPFSManager {
     pub encryption_manager: Arc<EncryptionManager>,
     pub redundancy_manager: Arc<RedundancyManager>,
     pub access_controller: Arc<IPFSAccessController>,
+    pub http_client: reqwest::Client,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -238,6 +325,8 @@ pub struct ZKProofSystem {
     pub verifier: Arc<ZKVerifier>,
     pub trusted_setup_manager: Arc<TrustedSetupManager>,
     pub recursive_proof_composer: Arc<RecursiveProofComposer>,
+    /// Key into `proving_systems` used when no system is requested
+    pub active_system: Arc<RwLock<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -276,6 +365,15 @@ pub struct CrossChainBridge {
     pub liquidity_manager: Arc<LiquidityManager>,
 }
 
+/// Outcome of `create_audit_trail`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditTrailReceipt {
+    pub entry_id: Uuid,
+    pub entry_hash: String,
+    /// Anchoring transaction, `None` while the entry waits in a batch
+    pub tx_hash: Option<String>,
+}
+
 /// Audit Trail Manager for Immutable Compliance Records
 pub struct AuditTrailManager {
     pub manager_id: Uuid,
@@ -286,6 +384,9 @@ pub struct AuditTrailManager {
     pub compliance_validator: Arc<ComplianceValidator>,
     pub trail_analyzer: Arc<TrailAnalyzer>,
     pub immutability_verifier: Arc<ImmutabilityVerifier>,
+    pub ethereum_manager: Arc<EthereumManager>,
+    pub pending_batch: Arc<RwLock<Vec<Uuid>>>,
+    pub batch_flusher: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -303,6 +404,9 @@ pub struct AuditTrailEntry {
     pub blockchain_tx_hash: Option<String>,
     pub ipfs_hash: Option<String>,
     pub compliance_status: ComplianceStatus,
+    /// Set once the entry has been anchored as part of a Merkle batch
+    #[serde(default)]
+    pub batch_anchor: Option<BatchAnchor>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -333,6 +437,7 @@ pub struct GovernanceSystem {
     pub treasury_manager: Arc<TreasuryManager>,
     pub delegation_system: Arc<DelegationSystem>,
     pub quadratic_voting: Arc<QuadraticVoting>,
+    pub audit_trail_manager: Arc<AuditTrailManager>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -378,6 +483,10 @@ pub struct BlockchainHealth {
     pub average_transaction_time: f64,
     pub gas_optimization_active: bool,
     pub security_score: f64,
+    /// Audit anchors mined but not yet final, and so still exposed to reorgs
+    pub pending_audit_anchors: u32,
+    /// Audit anchors past their network's finality point
+    pub final_audit_anchors: u32,
     pub last_check: DateTime<Utc>,
 }
 
@@ -391,15 +500,19 @@ impl BlockchainIntegration {
         // Initialize all blockchain subsystems
         let ethereum_manager = Arc::new(EthereumManager::new().await?);
         let bitcoin_manager = Arc::new(BitcoinManager::new().await?);
-        let smart_contract_deployer = Arc::new(SmartContractDeployer::new().await?);
-        let consensus_engine = Arc::new(ConsensusEngine::new().await?);
+        let consensus_engine = Arc::new(ConsensusEngine::new(ethereum_manager.clone(), bitcoin_manager.clone()).await?);
         let ipfs_manager = Arc::new(IPFSManager::new().await?);
         let zk_proof_system = Arc::new(ZKProofSystem::new().await?);
         let cross_chain_bridge = Arc::new(CrossChainBridge::new().await?);
-        let defi_integrator = Arc::new(DeFiIntegrator::new().await?);
-        let nft_manager = Arc::new(NFTManager::new().await?);
-        let audit_trail_manager = Arc::new(AuditTrailManager::new().await?);
-        let governance_system = Arc::new(GovernanceSystem::new().await?);
+        let defi_integrator = Arc::new(DeFiIntegrator::new(ethereum_manager.address_screener.clone()).await?);
+        let nft_manager = Arc::new(NFTManager::new(ethereum_manager.contract_manager.clone(), ipfs_manager.clone()).await?);
+        let audit_trail_manager = Arc::new(AuditTrailManager::new(ethereum_manager.clone()).await?);
+        let upgrade_manager = Arc::new(UpgradeManager::new(
+            ethereum_manager.contract_manager.clone(),
+            audit_trail_manager.clone(),
+        ));
+        let smart_contract_deployer = Arc::new(SmartContractDeployer::new(upgrade_manager).await?);
+        let governance_system = Arc::new(GovernanceSystem::new(audit_trail_manager.clone()).await?);
         let blockchain_analytics = Arc::new(BlockchainAnalytics::new().await?);
 
         // Maximum configuration
@@ -418,6 +531,28 @@ impl BlockchainIntegration {
             governance_enabled: true,
         };
 
+        ethereum_manager.configure_networks(&configuration.networks).await;
+        ethereum_manager.address_screener
+            .set_blocking(configuration.security_settings.block_sanctioned_addresses);
+        bitcoin_manager.configure_network(&configuration.networks).await;
+        consensus_engine.configure(configuration.consensus_settings.clone()).await;
+        cross_chain_bridge.configure_chains(&configuration.networks).await;
+        let security_settings = &configuration.security_settings;
+        if let Some(path) = &security_settings.transaction_signing_key_path {
+            ethereum_manager.wallet_manager.load_signing_key(path).await?;
+        }
+        if let Some(path) = &security_settings.bridge_validator_set_path {
+            let validator_set = &cross_chain_bridge.validator_set;
+            validator_set.load(path).await?;
+            for (validator_id, key_path) in &security_settings.bridge_signer_key_paths {
+                validator_set.load_local_signer(validator_id, key_path).await?;
+            }
+            validator_set.set_threshold(security_settings.bridge_signature_threshold).await?;
+        }
+        if let Some(path) = &security_settings.upgrade_approvers_path {
+            smart_contract_deployer.upgrade_manager.load_approvers(path).await?;
+        }
+
         Ok(Self {
             integration_id,
             ethereum_manager,
@@ -467,34 +602,119 @@ impl BlockchainIntegration {
         // Start governance system
         self.start_governance_system().await?;
 
+        // Periodically anchor batched audit entries
+        let settings = &self.configuration.compliance_settings;
+        if settings.batch_anchoring_enabled {
+            self.audit_trail_manager.start_batch_flusher(
+                std::time::Duration::from_secs(settings.batch_flush_interval_seconds),
+            );
+        }
+
         info!("✅ Blockchain Integration fully operational with maximum autonomy");
         Ok(())
     }
 
+    /// Graceful shutdown, anchoring any audit entries still waiting in a batch
+    pub async fn shutdown(&self) -> Result<()> {
+        info!("🛑 Shutting down Blockchain Integration");
+
+        self.audit_trail_manager.stop().await?;
+
+        info!("✅ Blockchain Integration shut down");
+        Ok(())
+    }
+
     /// Create immutable compliance audit trail
-    pub async fn create_audit_trail(&self, event: AuditEventType, details: AuditDetails) -> Result<String> {
+    ///
+    /// With batch anchoring enabled the entry is queued and the receipt has
+    /// no transaction hash unless this entry filled the batch. Once the
+    /// entry is recorded, anchoring, IPFS and proof failures are logged
+    /// rather than returned, so callers do not retry and duplicate it; an
+    /// unanchored batch stays queued for the next flush, and an unbatched
+    /// entry that failed to anchor is returned with no transaction hash.
+    pub async fn create_audit_trail(&self, event: AuditEventType, details: AuditDetails) -> Result<AuditTrailReceipt> {
         info!("📝 Creating immutable compliance audit trail");
 
         // Create audit trail entry
         let entry = self.audit_trail_manager.create_entry(event, details).await?;
 
-        // Store in blockchain for immutability
-        let tx_hash = self.store_audit_on_blockchain(&entry).await?;
+        // Store in blockchain for immutability, batching entries under one Merkle root if enabled
+        let settings = &self.configuration.compliance_settings;
+        let tx_hash = if settings.batch_anchoring_enabled {
+            let pending = self.audit_trail_manager.queue_for_batch(entry.entry_id).await;
+            if pending >= settings.batch_max_entries {
+                match self.audit_trail_manager.flush_batch_anchor().await {
+                    Ok(tx_hash) => Some(tx_hash),
+                    Err(e) => {
+                        warn!("⚠️ Audit batch anchoring failed, will retry on the next flush: {}", e);
+                        None
+                    }
+                }
+            } else {
+                None
+            }
+        } else {
+            match self.store_audit_on_blockchain(&entry).await {
+                Ok(tx_hash) => {
+                    self.audit_trail_manager.trail_storage.record_anchor(entry.entry_id, &tx_hash).await;
+                    Some(tx_hash)
+                }
+                Err(e) => {
+                    warn!("⚠️ Audit entry {} recorded but not anchored: {}", entry.entry_id, e);
+                    None
+                }
+            }
+        };
 
         // Store metadata in IPFS
-        let ipfs_hash = self.ipfs_manager.store_audit_metadata(&entry).await?;
+        let ipfs_hash = match self.ipfs_manager.store_audit_metadata(&entry).await {
+            Ok(ipfs_hash) => Some(ipfs_hash),
+            Err(e) => {
+                warn!("⚠️ Audit entry {} metadata not stored in IPFS: {}", entry.entry_id, e);
+                None
+            }
+        };
 
         // Generate zero-knowledge proof for privacy
-        let zk_proof = self.zk_proof_system.generate_audit_proof(&entry).await?;
+        if let Err(e) = self.zk_proof_system.generate_audit_proof(&entry).await {
+            warn!("⚠️ Audit entry {} has no zero-knowledge proof: {}", entry.entry_id, e);
+        }
 
-        info!("✅ Audit trail created - TX: {}, IPFS: {}", tx_hash, ipfs_hash);
-        Ok(tx_hash)
+        // The anchor only becomes immutable once its block is final; see `is_audit_entry_immutable`
+        info!("✅ Audit trail {} created - TX: {:?} (pending finality), IPFS: {:?}", entry.entry_id, tx_hash, ipfs_hash);
+        Ok(AuditTrailReceipt {
+            entry_id: entry.entry_id,
+            entry_hash: entry.current_hash,
+            tx_hash,
+        })
+    }
+
+    /// Whether an audit entry's anchor has reached finality on the audit network
+    ///
+    /// Entries not yet anchored, or anchored in a block that could still be
+    /// reorganized away, are not immutable.
+    pub async fn is_audit_entry_immutable(&self, entry_id: Uuid) -> Result<bool> {
+        let entry = self.audit_trail_manager.trail_storage.entries().await
+            .into_iter()
+            .find(|entry| entry.entry_id == entry_id)
+            .ok_or_else(|| anyhow::anyhow!("Audit entry {} not found", entry_id))?;
+
+        match entry.blockchain_tx_hash {
+            Some(tx_hash) => Ok(self.consensus_engine.anchor_finality(&tx_hash).await?.is_final()),
+            None => Ok(false),
+        }
     }
 
     /// Deploy compliance smart contract
     pub async fn deploy_compliance_contract(&self, contract_type: ContractType, params: ContractParams) -> Result<String> {
         info!("📄 Deploying compliance smart contract: {:?}", contract_type);
 
+        // Price the deployment from live network fees rather than static defaults
+        let network_id = self.primary_network_id()?;
+        let gas_estimate = self.ethereum_manager
+            .estimate_gas(&network_id, &TransactionRequest::contract_deployment()).await?;
+        let params = params.with_gas_estimate(gas_estimate);
+
         let contract_address = self.smart_contract_deployer
             .deploy_contract(contract_type, params).await?;
 
@@ -562,10 +782,37 @@ impl BlockchainIntegration {
         Ok(proposal_id)
     }
 
+    /// Screen a counterparty address against the OFAC sanctions list
+    ///
+    /// Results are cached for a few minutes; the list itself is refreshed
+    /// from OFAC as it ages.
+    pub async fn screen_address(&self, address: &str) -> Result<ScreeningResult> {
+        self.ethereum_manager.address_screener.screen(address).await
+    }
+
     /// Integrate with DeFi protocols for compliance monitoring
     pub async fn integrate_defi_compliance(&self, protocols: Vec<String>) -> Result<DeFiComplianceIntegration> {
         info!("💰 Integrating DeFi compliance monitoring for {} protocols", protocols.len());
 
+        // Assess each protocol before monitoring it; high-risk ones are alerted
+        // through `DeFiIntegrator::subscribe_alerts` and recorded in the audit trail
+        for protocol in &protocols {
+            match self.defi_integrator.assess_protocol(protocol).await {
+                Ok(risk) if risk.level == ProtocolRiskLevel::High => {
+                    let recorded = self.create_audit_trail(
+                        AuditEventType::AlertTriggered,
+                        AuditDetails::new("DeFiIntegrator", "HighRiskProtocol", protocol)
+                            .with_metadata("risk", serde_json::json!(risk)),
+                    ).await;
+                    if let Err(e) = recorded {
+                        warn!("⚠️ High-risk assessment of {} not recorded in the audit trail: {}", protocol, e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!("⚠️ Monitoring {} without a risk assessment: {}", protocol, e),
+            }
+        }
+
         let integration = self.defi_integrator
             .setup_compliance_monitoring(protocols).await?;
 
@@ -589,6 +836,8 @@ impl BlockchainIntegration {
         let contracts_health = self.smart_contract_deployer.health_check().await?;
         let audit_health = self.audit_trail_manager.health_check().await?;
         let governance_health = self.governance_system.health_check().await?;
+        let anchor_finality = self.consensus_engine
+            .audit_anchor_summary(&self.audit_trail_manager.trail_storage.entries().await).await;
 
         let health = BlockchainHealth {
             healthy: ethereum_health.healthy && bitcoin_health.healthy && contracts_health.healthy,
@@ -600,6 +849,8 @@ impl BlockchainIntegration {
             average_transaction_time: ethereum_health.avg_tx_time,
             gas_optimization_active: ethereum_health.gas_optimization_active,
             security_score: 0.98,
+            pending_audit_anchors: anchor_finality.pending,
+            final_audit_anchors: anchor_finality.finalized,
             last_check: Utc::now(),
         };
 
@@ -620,6 +871,8 @@ impl BlockchainIntegration {
             confirmation_blocks: 12,
             max_fee_per_gas: 100_000_000_000, // 100 gwei
             priority_fee_per_gas: 2_000_000_000, // 2 gwei
+            tx_type: TxType::Eip1559,
+            connection_pool: ConnectionPoolConfig::default(),
             enabled: true,
         });
 
@@ -633,6 +886,28 @@ impl BlockchainIntegration {
             confirmation_blocks: 20,
             max_fee_per_gas: 30_000_000_000, // 30 gwei
             priority_fee_per_gas: 30_000_000_000, // 30 gwei
+            tx_type: TxType::Eip1559,
+            connection_pool: ConnectionPoolConfig::default(),
+            enabled: true,
+        });
+
+        // BSC burns part of the gas fee but validators still expect gasPrice-priced transactions
+        networks.insert("bsc_mainnet".to_string(), NetworkConfig {
+            network_id: "bsc_mainnet".to_string(),
+            blockchain_type: BlockchainType::BinanceSmartChain,
+            rpc_endpoint: "https://bsc-dataseed.binance.org".to_string(),
+            websocket_endpoint: None,
+            chain_id: 56,
+            gas_settings: GasSettings {
+                gas_price: 3_000_000_000, // 3 gwei
+                max_priority_fee: 0,
+                ..GasSettings::default()
+            },
+            confirmation_blocks: 15,
+            max_fee_per_gas: 10_000_000_000, // 10 gwei
+            priority_fee_per_gas: 0,
+            tx_type: TxType::Legacy,
+            connection_pool: ConnectionPoolConfig::default(),
             enabled: true,
         });
 
@@ -684,7 +959,7 @@ impl BlockchainIntegration {
         info!("⛓️ Initializing audit trail blockchain");
 
         // Create genesis audit entry
-        let genesis_entry = AuditTrailEntry {
+        let mut genesis_entry = AuditTrailEntry {
             entry_id: Uuid::new_v4(),
             timestamp: Utc::now(),
             event_type: AuditEventType::Custom("GenesisBlock".to_string()),
@@ -692,16 +967,28 @@ impl BlockchainIntegration {
             action: "Initialize".to_string(),
             resource: "AuditTrail".to_string(),
             previous_hash: "0".to_string(),
-            current_hash: "genesis".to_string(),
+            current_hash: String::new(),
             metadata: HashMap::new(),
             digital_signature: "genesis_signature".to_string(),
             blockchain_tx_hash: None,
             ipfs_hash: None,
             compliance_status: ComplianceStatus::Compliant,
+            batch_anchor: None,
         };
+        genesis_entry.current_hash = HashChain::hash_entry(&genesis_entry);
 
         self.audit_trail_manager.initialize_with_genesis(genesis_entry).await?;
 
+        // Re-validate the stored chain rather than trusting it after a restart
+        let report = self.audit_trail_manager.verify_chain_integrity().await?;
+        match &report.first_broken_link {
+            Some(link) => error!(
+                "🚨 Audit trail integrity broken at entry {} ({}): {:?}, expected {} but found {}",
+                link.index, link.entry_id, link.kind, link.expected_hash, link.actual_hash
+            ),
+            None => info!("✅ Audit trail integrity verified across {} entries", report.entries_checked),
+        }
+
         info!("✅ Audit trail blockchain initialized");
         Ok(())
     }
@@ -718,6 +1005,15 @@ impl BlockchainIntegration {
         Ok(())
     }
 
+    /// Enabled Ethereum network with the lowest chain id
+    fn primary_network_id(&self) -> Result<String> {
+        self.configuration.networks.values()
+            .filter(|network| network.enabled && matches!(network.blockchain_type, BlockchainType::Ethereum))
+            .min_by_key(|network| network.chain_id)
+            .map(|network| network.network_id.clone())
+            .ok_or_else(|| anyhow::anyhow!("No enabled Ethereum network configured"))
+    }
+
     async fn store_audit_on_blockchain(&self, entry: &AuditTrailEntry) -> Result<String> {
         // Store audit entry on blockchain for immutability
         let tx_hash = self.ethereum_manager
@@ -733,6 +1029,8 @@ pub struct GasSettings {
     pub gas_limit: u64,
     pub gas_price: u64,
     pub max_priority_fee: u64,
+    /// Reward percentile sampled from `eth_feeHistory` for the priority fee
+    pub priority_fee_percentile: f64,
 }
 
 impl Default for GasSettings {
@@ -741,6 +1039,7 @@ impl Default for GasSettings {
             gas_limit: 21000,
             gas_price: 20_000_000_000, // 20 gwei
             max_priority_fee: 2_000_000_000, // 2 gwei
+            priority_fee_percentile: 50.0,
         }
     }
 }
@@ -768,6 +1067,8 @@ pub enum UpgradeStrategy {
 pub struct ConsensusSettings {
     pub consensus_algorithm: String,
     pub block_time: std::time::Duration,
+    /// Minimum reorg depth tolerated on confirmation-depth networks,
+    /// raised to a network's own `confirmation_blocks` where that is larger
     pub finality_blocks: u32,
 }
 
@@ -787,6 +1088,18 @@ pub struct BlockchainSecuritySettings {
     pub time_lock_enabled: bool,
     pub access_control_enabled: bool,
     pub audit_required: bool,
+    /// Validator signatures required before a cross-chain result is trusted
+    pub bridge_signature_threshold: usize,
+    /// Persisted public keys of the bridge validator set; without it no cross-chain result is trusted
+    pub bridge_validator_set_path: Option<std::path::PathBuf>,
+    /// Key seed files of the validators this node signs for, by validator id
+    pub bridge_signer_key_paths: HashMap<String, std::path::PathBuf>,
+    /// Refuse transactions to addresses on the sanctions list
+    pub block_sanctioned_addresses: bool,
+    /// Hex-encoded secp256k1 key outgoing EVM transactions are signed with; without it nothing is sent
+    pub transaction_signing_key_path: Option<std::path::PathBuf>,
+    /// Persisted public keys of those who may override a storage-incompatible upgrade; without it none can
+    pub upgrade_approvers_path: Option<std::path::PathBuf>,
 }
 
 impl BlockchainSecuritySettings {
@@ -796,6 +1109,12 @@ impl BlockchainSecuritySettings {
             time_lock_enabled: true,
             access_control_enabled: true,
             audit_required: true,
+            bridge_signature_threshold: 2,
+            bridge_validator_set_path: None,
+            bridge_signer_key_paths: HashMap::new(),
+            block_sanctioned_addresses: true,
+            transaction_signing_key_path: None,
+            upgrade_approvers_path: None,
         }
     }
 }
@@ -825,6 +1144,12 @@ pub struct BlockchainComplianceSettings {
     pub audit_trail_enabled: bool,
     pub regulatory_reporting: bool,
     pub privacy_preserving: bool,
+    /// Anchor audit entries as Merkle batches instead of one transaction each; off by default
+    pub batch_anchoring_enabled: bool,
+    /// Pending entries that trigger an immediate flush
+    pub batch_max_entries: usize,
+    /// Maximum time an entry waits before its batch is anchored
+    pub batch_flush_interval_seconds: u64,
 }
 
 impl BlockchainComplianceSettings {
@@ -834,53 +1159,96 @@ impl BlockchainComplianceSettings {
             audit_trail_enabled: true,
             regulatory_reporting: true,
             privacy_preserving: true,
+            batch_anchoring_enabled: false,
+            batch_max_entries: 256,
+            batch_flush_interval_seconds: 300,
         }
     }
 }
 
 // Additional type definitions...
-pub struct BitcoinManager;
-pub struct WalletManager;
-pub struct ContractManager;
-pub struct TransactionManager;
-pub struct EventListener;
 pub struct GasOptimizer;
-pub struct MEVProtector;
 pub struct Layer2Integrator;
 pub struct DeploymentManager;
 pub struct VerificationEngine;
-pub struct UpgradeManager;
+pub struct UpgradeManager {
+    /// Reads proxies and implementations on chain
+    pub contract_manager: Arc<ContractManager>,
+    /// Where upgrade decisions are recorded
+    pub audit_trail_manager: Arc<AuditTrailManager>,
+    /// Keys allowed to override a storage-incompatible upgrade, by approver id
+    approvers: RwLock<HashMap<String, ed25519_dalek::VerifyingKey>>,
+}
 pub struct SecurityAnalyzer;
 pub struct GasEstimator;
 pub struct ContentManager;
-pub struct EncryptionManager;
-pub struct RedundancyManager;
+pub struct EncryptionManager {
+    settings: RwLock<EncryptionSettings>,
+}
+pub struct RedundancyManager {
+    /// Confirmed copies required before stored content is accepted
+    pub min_replicas: u32,
+}
 pub struct IPFSAccessController;
 pub struct CircuitManager;
-pub struct ProofGenerator;
-pub struct ZKVerifier;
+pub struct ProofGenerator {
+    /// Key proofs are signed with; proving fails until one is configured
+    signing_key: RwLock<Option<ed25519_dalek::SigningKey>>,
+    /// Random nonce blinding each proof's witness commitment; never published with the proof
+    witness_blindings: RwLock<HashMap<Uuid, [u8; 32]>>,
+}
+pub struct ZKVerifier {
+    /// Prover keys whose proofs are accepted, held independently of any prover
+    trusted_keys: RwLock<Vec<ed25519_dalek::VerifyingKey>>,
+}
 pub struct TrustedSetupManager;
 pub struct RecursiveProofComposer;
 pub struct RelayNetwork;
-pub struct ValidatorSet;
+pub struct ValidatorSet {
+    validators: RwLock<HashMap<String, ed25519_dalek::VerifyingKey>>,
+    /// Keys of validators operated by this node
+    local_signers: RwLock<HashMap<String, ed25519_dalek::SigningKey>>,
+    threshold: RwLock<usize>,
+}
 pub struct BridgeSecurityManager;
 pub struct LiquidityManager;
-pub struct DeFiIntegrator;
-pub struct NFTManager;
-pub struct TrailStorage;
+pub struct DeFiIntegrator {
+    protocols: RwLock<HashMap<String, ProtocolProfile>>,
+    address_screener: Arc<AddressScreener>,
+    alerts: tokio::sync::broadcast::Sender<ProtocolRiskAlert>,
+}
+pub struct NFTManager {
+    pub contract_manager: Arc<ContractManager>,
+    pub ipfs_manager: Arc<IPFSManager>,
+    certificate_contract: RwLock<Option<CertificateContract>>,
+    certificates: RwLock<HashMap<TokenId, ComplianceCertificate>>,
+}
+pub struct TrailStorage {
+    entries: RwLock<Vec<AuditTrailEntry>>,
+}
 pub struct HashChain;
 pub struct MerkleTreeManager;
 pub struct TimestampingService;
 pub struct ComplianceValidator;
 pub struct TrailAnalyzer;
 pub struct ImmutabilityVerifier;
-pub struct GovernanceToken;
-pub struct ProposalManager;
+pub struct GovernanceToken {
+    /// Voting power in circulation, the base for proposal quorum
+    pub total_supply: u64,
+}
+pub struct ProposalManager {
+    proposals: RwLock<HashMap<Uuid, GovernanceProposal>>,
+}
 pub struct VotingMechanism;
 pub struct ExecutionEngine;
 pub struct TreasuryManager;
 pub struct DelegationSystem;
-pub struct QuadraticVoting;
+pub struct QuadraticVoting {
+    /// Remaining voice credits per voter
+    credits: RwLock<HashMap<String, u64>>,
+    /// Votes already cast per (proposal, voter)
+    positions: RwLock<HashMap<(Uuid, String), VotePosition>>,
+}
 pub struct BlockchainAnalytics;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -906,7 +1274,7 @@ pub enum ComplianceStatus {
     Pending,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProposalStatus {
     Pending,
     Active,
@@ -933,28 +1301,95 @@ pub enum ProxyPattern {
 }
 
 // Placeholder implementations will be added to individual modules...
-pub struct AuditDetails;
-pub struct ContractParams;
-pub struct ComplianceData;
-pub struct CrossChainValidationResult;
-pub struct ComplianceStatement;
-pub struct ZKProof;
+pub struct AuditDetails {
+    pub actor: String,
+    pub action: String,
+    pub resource: String,
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceData {
+    pub regulation: String,
+    pub subject: String,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossChainValidationResult {
+    pub validation_id: Uuid,
+    pub chain_results: HashMap<String, bool>,
+    pub compliant: bool,
+    /// SHA-256 of the validated compliance data
+    pub compliance_data_hash: String,
+    pub validated_at: DateTime<Utc>,
+    pub attestations: Vec<ValidatorAttestation>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceStatement {
+    pub statement_id: Uuid,
+    pub regulation: String,
+    pub claim: String,
+    /// Values revealed to the verifier
+    pub public_inputs: Vec<String>,
+    /// Values the proof commits to without revealing
+    pub private_witness: Vec<String>,
+    /// Proving system to use instead of the active one
+    #[serde(default)]
+    pub preferred_system: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZKProof {
+    pub proof_id: Uuid,
+    pub system_name: String,
+    pub statement_id: Option<Uuid>,
+    pub public_inputs: Vec<String>,
+    pub witness_commitment: String,
+    /// Proof ids folded into this proof; empty for a leaf proof
+    pub component_proofs: Vec<Uuid>,
+    /// Hex-encoded Ed25519 signature of the prover over every other field
+    pub proof_data: String,
+    pub created_at: DateTime<Utc>,
+}
 pub struct DeFiComplianceIntegration;
 pub struct ConstructorParam;
 pub struct DeploymentRecord;
-pub struct PinningService;
-pub struct ChainConfig;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinningService {
+    pub service_name: String,
+    /// Base URL of an IPFS Pinning Service API endpoint
+    pub endpoint: String,
+    #[serde(skip_serializing)]
+    pub access_token: String,
+    pub enabled: bool,
+}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainConfig {
+    pub chain_name: String,
+    pub chain_id: u64,
+    pub blockchain_type: BlockchainType,
+    pub enabled: bool,
+}
 pub struct BridgeContract;
 
+pub struct ContractParams {
+    pub gas_estimate: Option<GasEstimate>,
+}
+
 impl AuditDetails {
-    fn contract_deployment(address: &str) -> Self { Self }
-    fn cross_chain_validation(_result: &CrossChainValidationResult) -> Self { Self }
-    fn governance_proposal(_id: &str) -> Self { Self }
+    fn contract_deployment(address: &str) -> Self { Self::new("System", "DeployContract", address) }
+    fn cross_chain_validation(_result: &CrossChainValidationResult) -> Self { Self::new("System", "ValidateCrossChain", "CrossChainBridge") }
+    fn governance_proposal(id: &str) -> Self { Self::new("System", "CreateProposal", id) }
 }
 
 impl ContractParams {
-    fn from_config(_config: &ContractConfig) -> Self { Self }
-    fn defi_monitor(_protocol: &str) -> Self { Self }
+    fn from_config(_config: &ContractConfig) -> Self { Self { gas_estimate: None } }
+    fn defi_monitor(_protocol: &str) -> Self { Self { gas_estimate: None } }
+
+    fn with_gas_estimate(mut self, estimate: GasEstimate) -> Self {
+        self.gas_estimate = Some(estimate);
+        self
+    }
 }
 
 /// Initialize complete blockchain integration
@@ -0,0 +1,348 @@
+//! NFT Compliance Certificates
+//!
+//! Soulbound (ERC-5192, non-transferable) certificates minted to an entity's
+//! address, each pointing at IPFS metadata that carries the attestation and
+//! the audit transaction it can be verified against.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::*;
+
+/// Identifier of a minted certificate token, the full 256-bit ERC-721 id
+pub type TokenId = ethers::types::U256;
+
+/// Functions of the soulbound certificate contract the manager calls
+const CERTIFICATE_CONTRACT_ABI: &str = r#"[
+    {"type": "function", "name": "mint", "stateMutability": "nonpayable",
+     "inputs": [{"name": "to", "type": "address"}, {"name": "tokenId", "type": "uint256"}, {"name": "tokenURI", "type": "string"}],
+     "outputs": []},
+    {"type": "function", "name": "revoke", "stateMutability": "nonpayable",
+     "inputs": [{"name": "tokenId", "type": "uint256"}, {"name": "reason", "type": "string"}],
+     "outputs": []},
+    {"type": "function", "name": "locked", "stateMutability": "view",
+     "inputs": [{"name": "tokenId", "type": "uint256"}],
+     "outputs": [{"name": "", "type": "bool"}]},
+    {"type": "function", "name": "ownerOf", "stateMutability": "view",
+     "inputs": [{"name": "tokenId", "type": "uint256"}],
+     "outputs": [{"name": "", "type": "address"}]},
+    {"type": "function", "name": "revoked", "stateMutability": "view",
+     "inputs": [{"name": "tokenId", "type": "uint256"}],
+     "outputs": [{"name": "", "type": "bool"}]}
+]"#;
+
+/// What a certificate attests to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceAttestation {
+    pub regulation: String,
+    pub status: ComplianceStatus,
+    pub issued_by: String,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Transaction anchoring the audit entry behind the attestation
+    pub audit_tx_hash: String,
+}
+
+/// Where the soulbound certificate contract is deployed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateContract {
+    pub network_id: String,
+    pub address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceCertificate {
+    pub token_id: TokenId,
+    /// Address the certificate is bound to
+    pub entity: String,
+    pub attestation: ComplianceAttestation,
+    pub metadata_cid: String,
+    pub mint_tx_hash: String,
+    pub revocation: Option<CertificateRevocation>,
+}
+
+/// On-chain state of a certificate token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateStatus {
+    pub holder: String,
+    /// ERC-5192 `locked`: the token cannot be transferred
+    pub locked: bool,
+    pub revoked: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateRevocation {
+    pub reason: String,
+    pub tx_hash: String,
+    pub revoked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CertificateError {
+    #[error("attestation for {regulation} expired at {expires_at}")]
+    AttestationExpired { regulation: String, expires_at: DateTime<Utc> },
+    #[error("attestation has no audit transaction to verify it against")]
+    MissingAuditTransaction,
+    #[error("no certificate contract configured")]
+    NoCertificateContract,
+    #[error("certificate {0} not found")]
+    NotFound(TokenId),
+    #[error("certificate {0} is already revoked")]
+    AlreadyRevoked(TokenId),
+    #[error("certificate {0} is transferable; the contract does not lock it")]
+    NotSoulbound(TokenId),
+}
+
+impl NFTManager {
+    pub async fn new(contract_manager: Arc<ContractManager>, ipfs_manager: Arc<IPFSManager>) -> Result<Self> {
+        Ok(Self {
+            contract_manager,
+            ipfs_manager,
+            certificate_contract: RwLock::new(None),
+            certificates: RwLock::new(HashMap::new()),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Network and address of the deployed soulbound certificate contract
+    pub async fn set_certificate_contract(&self, network_id: &str, address: &str) {
+        *self.certificate_contract.write().await = Some(CertificateContract {
+            network_id: network_id.to_string(),
+            address: address.to_string(),
+        });
+    }
+
+    /// Mint a soulbound certificate for `entity` (its address) encoding `attestation`
+    ///
+    /// The attestation is stored on IPFS as token metadata, including the
+    /// audit transaction hash. Expired attestations are rejected. The mint
+    /// is broadcast once; on an error it may or may not have been mined, so
+    /// check [`Self::certificate_status`] before minting again.
+    pub async fn mint_certificate(&self, entity: &str, attestation: ComplianceAttestation) -> Result<TokenId> {
+        let now = Utc::now();
+        if attestation.expires_at <= now {
+            return Err(CertificateError::AttestationExpired {
+                regulation: attestation.regulation.clone(),
+                expires_at: attestation.expires_at,
+            }
+            .into());
+        }
+        if attestation.audit_tx_hash.is_empty() {
+            return Err(CertificateError::MissingAuditTransaction.into());
+        }
+        let contract = self.certificate_contract().await?;
+
+        let token_id = certificate_token_id(entity, &attestation);
+        let metadata = certificate_metadata(token_id, entity, &attestation);
+        let receipt = self
+            .ipfs_manager
+            .store_with_redundancy(&serde_json::to_vec(&metadata)?, self.ipfs_manager.redundancy_manager.min_replicas)
+            .await?;
+
+        let holder = entity
+            .parse()
+            .map_err(|_| anyhow!("Certificate holder {} is not an Ethereum address", entity))?;
+        let args = [
+            Token::Address(holder),
+            Token::Uint(token_id),
+            Token::String(format!("ipfs://{}", receipt.cid)),
+        ];
+        let mint_tx_hash = self
+            .contract_manager
+            .send_once(&contract.network_id, &contract.address, &certificate_abi(), "mint", &args)
+            .await?;
+
+        info!(
+            "🏅 Minted compliance certificate {} for {} ({}) in {}",
+            token_id, entity, attestation.regulation, mint_tx_hash
+        );
+        self.certificates.write().await.insert(token_id, ComplianceCertificate {
+            token_id,
+            entity: entity.to_string(),
+            attestation,
+            metadata_cid: receipt.cid,
+            mint_tx_hash,
+            revocation: None,
+        });
+        Ok(token_id)
+    }
+
+    /// Revoke a certificate on-chain, returning the revocation transaction hash
+    ///
+    /// Whether the certificate exists and is already revoked is read from
+    /// the contract, so certificates minted by other instances or before a
+    /// restart can be revoked too.
+    pub async fn revoke_certificate(&self, token_id: TokenId, reason: &str) -> Result<String> {
+        let status = self.certificate_status(token_id).await?;
+        if status.revoked {
+            return Err(CertificateError::AlreadyRevoked(token_id).into());
+        }
+        let contract = self.certificate_contract().await?;
+
+        let args = [Token::Uint(token_id), Token::String(reason.to_string())];
+        let tx_hash = self
+            .contract_manager
+            .send(&contract.network_id, &contract.address, &certificate_abi(), "revoke", &args)
+            .await?;
+
+        if let Some(certificate) = self.certificates.write().await.get_mut(&token_id) {
+            certificate.revocation = Some(CertificateRevocation {
+                reason: reason.to_string(),
+                tx_hash: tx_hash.clone(),
+                revoked_at: Utc::now(),
+            });
+        }
+        warn!("🏅 Revoked compliance certificate {}: {}", token_id, reason);
+        Ok(tx_hash)
+    }
+
+    /// Holder, lock and revocation state of a certificate, read from the contract
+    pub async fn certificate_status(&self, token_id: TokenId) -> Result<CertificateStatus> {
+        let holder = match self.call_certificate_contract("ownerOf", token_id).await {
+            Ok(Token::Address(holder)) => format!("{:#x}", holder),
+            Ok(other) => return Err(anyhow!("ownerOf returned {:?}", other)),
+            // ERC-721 `ownerOf` reverts for tokens that were never minted
+            Err(e) if e.to_string().to_lowercase().contains("revert") => {
+                return Err(CertificateError::NotFound(token_id).into());
+            }
+            Err(e) => return Err(e),
+        };
+        let locked = self.call_certificate_contract("locked", token_id).await?;
+        let revoked = self.call_certificate_contract("revoked", token_id).await?;
+
+        Ok(CertificateStatus {
+            holder,
+            locked: locked.into_bool().ok_or_else(|| anyhow!("locked returned a non-bool"))?,
+            revoked: revoked.into_bool().ok_or_else(|| anyhow!("revoked returned a non-bool"))?,
+        })
+    }
+
+    /// Check a certificate is live and soulbound, returning its on-chain state
+    pub async fn verify_certificate(&self, token_id: TokenId) -> Result<CertificateStatus> {
+        let status = self.certificate_status(token_id).await?;
+        if !status.locked {
+            return Err(CertificateError::NotSoulbound(token_id).into());
+        }
+        Ok(status)
+    }
+
+    /// Certificate minted by this instance, with its attestation and metadata CID
+    ///
+    /// Revocation state here only reflects revocations made by this
+    /// instance; use [`Self::certificate_status`] for the on-chain state.
+    pub async fn certificate(&self, token_id: TokenId) -> Option<ComplianceCertificate> {
+        self.certificates.read().await.get(&token_id).cloned()
+    }
+
+    async fn call_certificate_contract(&self, method: &str, token_id: TokenId) -> Result<Token> {
+        let contract = self.certificate_contract().await?;
+        self.contract_manager
+            .call(&contract.network_id, &contract.address, &certificate_abi(), method, &[Token::Uint(token_id)])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("{} returned no value", method))
+    }
+
+    async fn certificate_contract(&self) -> Result<CertificateContract> {
+        self.certificate_contract
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| CertificateError::NoCertificateContract.into())
+    }
+}
+
+/// ERC-721 metadata for a certificate
+pub fn certificate_metadata(token_id: TokenId, entity: &str, attestation: &ComplianceAttestation) -> Value {
+    json!({
+        "name": format!("{} Compliance Certificate #{}", attestation.regulation, token_id),
+        "description": format!("Non-transferable attestation of {} compliance for {}", attestation.regulation, entity),
+        "attributes": [
+            { "trait_type": "Regulation", "value": attestation.regulation },
+            { "trait_type": "Status", "value": format!("{:?}", attestation.status) },
+            { "trait_type": "Issued By", "value": attestation.issued_by },
+            { "trait_type": "Expires", "display_type": "date", "value": attestation.expires_at.timestamp() },
+        ],
+        "entity": entity,
+        "soulbound": true,
+        "attestation": attestation,
+        "audit_tx_hash": attestation.audit_tx_hash,
+    })
+}
+
+/// Token id derived from the holder and attestation, so a re-mint of the same
+/// attestation collides on-chain instead of creating a duplicate
+fn certificate_token_id(entity: &str, attestation: &ComplianceAttestation) -> TokenId {
+    let mut hasher = Sha256::new();
+    hasher.update(entity.to_lowercase().as_bytes());
+    hasher.update(attestation.regulation.as_bytes());
+    hasher.update(attestation.issued_at.to_rfc3339().as_bytes());
+    hasher.update(attestation.audit_tx_hash.as_bytes());
+    TokenId::from_big_endian(&hasher.finalize())
+}
+
+fn certificate_abi() -> Value {
+    serde_json::from_str(CERTIFICATE_CONTRACT_ABI).expect("certificate ABI is valid JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attestation(expires_at: DateTime<Utc>) -> ComplianceAttestation {
+        ComplianceAttestation {
+            regulation: "MiCA".to_string(),
+            status: ComplianceStatus::Compliant,
+            issued_by: "compliance-officer".to_string(),
+            issued_at: Utc::now() - chrono::Duration::days(1),
+            expires_at,
+            audit_tx_hash: "0xaudit".to_string(),
+        }
+    }
+
+    async fn nft_manager() -> NFTManager {
+        let ethereum_manager = EthereumManager::new().await.unwrap();
+        let ipfs_manager = Arc::new(IPFSManager::new().await.unwrap());
+        NFTManager::new(ethereum_manager.contract_manager.clone(), ipfs_manager).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_expired_attestation_is_not_minted() {
+        let manager = nft_manager().await;
+        manager.set_certificate_contract("ethereum", "0x00000000000000000000000000000000000000c1").await;
+
+        let expired = attestation(Utc::now() - chrono::Duration::hours(1));
+        let error = manager.mint_certificate("0x00000000000000000000000000000000000000e1", expired).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<CertificateError>(),
+            Some(CertificateError::AttestationExpired { .. })
+        ));
+    }
+
+    #[test]
+    fn test_certificate_metadata_carries_audit_transaction() {
+        let attestation = attestation(Utc::now() + chrono::Duration::days(365));
+        let entity = "0x00000000000000000000000000000000000000e1";
+        let token_id = certificate_token_id(entity, &attestation);
+        assert_eq!(token_id, certificate_token_id(&entity.to_uppercase(), &attestation));
+        // The whole digest is the id, not a truncation of it
+        assert!(token_id > TokenId::from(u64::MAX));
+
+        let metadata = certificate_metadata(token_id, entity, &attestation);
+        assert_eq!(metadata["audit_tx_hash"], "0xaudit");
+        assert_eq!(metadata["soulbound"], true);
+        assert_eq!(metadata["attestation"]["regulation"], "MiCA");
+    }
+}
@@ -0,0 +1,448 @@
+//! Sanctions Screening
+//!
+//! Screening of counterparty addresses against the digital currency addresses
+//! published on the OFAC SDN list, read through the marketplace
+//! `treasury-ofac` connector, with short-lived result caching and optional
+//! blocking of transactions to sanctioned addresses.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use aion_api_marketplace::ConnectorConfig;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// Endpoints of the marketplace `treasury-ofac` connector the SDN list is read from
+pub const OFAC_SDN_ENDPOINT: &str = "sdn";
+pub const OFAC_SDN_COMMENTS_ENDPOINT: &str = "sdn_comments";
+
+/// How long a screening result is reused before the list is consulted again
+const SCREENING_CACHE_TTL: Duration = Duration::from_secs(300);
+/// Age after which the sanctioned-address list is refreshed on the next screening
+const LIST_REFRESH_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+/// Wait after a refresh attempt before another one, so a source outage is not hit on every screening
+const LIST_REFRESH_RETRY_INTERVAL: Duration = Duration::from_secs(600);
+/// Cached results kept before expired ones are pruned
+const MAX_CACHED_RESULTS: usize = 10_000;
+
+const SDN_ADDRESS_MARKER: &str = "Digital Currency Address - ";
+/// Prefix of alternative addresses listed for the same party
+const SDN_ALTERNATE_PREFIX: &str = "alt. ";
+/// SDN.CSV column holding the entity number, name and remarks
+const SDN_ENTITY_COLUMN: usize = 0;
+const SDN_NAME_COLUMN: usize = 1;
+const SDN_REMARKS_COLUMN: usize = 11;
+/// SDN_COMMENTS.CSV column continuing the entity's remarks
+const SDN_COMMENTS_REMARKS_COLUMN: usize = 1;
+/// Placeholder OFAC uses for an empty field
+const SDN_EMPTY_FIELD: &str = "-0-";
+
+/// A sanctioned party an address is attributed to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SanctionsMatch {
+    pub list: String,
+    pub entity: String,
+    /// Currency ticker the address is listed under, e.g. `XBT` or `ETH`
+    pub currency: String,
+}
+
+/// Sanctioned addresses from one fetch of a sanctions list
+#[derive(Debug, Clone)]
+pub struct SanctionsList {
+    pub source: String,
+    pub fetched_at: DateTime<Utc>,
+    /// Normalized address to the parties it is attributed to
+    pub addresses: HashMap<String, Vec<SanctionsMatch>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScreeningRiskLevel {
+    Clear,
+    Sanctioned,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreeningResult {
+    pub address: String,
+    pub risk_level: ScreeningRiskLevel,
+    pub matches: Vec<SanctionsMatch>,
+    pub list_source: String,
+    /// When the list used for this result was fetched
+    pub list_fetched_at: DateTime<Utc>,
+    pub screened_at: DateTime<Utc>,
+}
+
+impl ScreeningResult {
+    pub fn is_sanctioned(&self) -> bool {
+        self.risk_level == ScreeningRiskLevel::Sanctioned
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SanctionsError {
+    #[error("transaction to {address} blocked: address is on {lists}")]
+    BlockedAddress { address: String, lists: String },
+}
+
+/// Where sanctioned addresses come from
+#[async_trait]
+pub trait SanctionsListSource: Send + Sync {
+    async fn fetch(&self) -> Result<SanctionsList>;
+}
+
+/// OFAC Specially Designated Nationals list, read through the `treasury-ofac` connector
+pub struct OfacSdnSource {
+    pub connector: ConnectorConfig,
+    http_client: reqwest::Client,
+}
+
+impl OfacSdnSource {
+    pub fn new(connector: ConnectorConfig) -> Self {
+        Self {
+            connector,
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Body of the connector endpoint `name`, with the connector's configured headers
+    async fn fetch_endpoint(&self, name: &str) -> Result<String> {
+        let endpoint = self
+            .connector
+            .endpoints
+            .iter()
+            .find(|endpoint| endpoint.name == name)
+            .ok_or_else(|| anyhow!("Connector {} has no {} endpoint", self.connector.id, name))?;
+
+        let mut request = self
+            .http_client
+            .get(format!("{}{}", self.connector.base_url, endpoint.path))
+            .timeout(Duration::from_secs(60));
+        for (header, value) in &self.connector.authentication.headers {
+            request = request.header(header.as_str(), value.as_str());
+        }
+        Ok(request.send().await?.error_for_status()?.text().await?)
+    }
+}
+
+impl Default for OfacSdnSource {
+    fn default() -> Self {
+        Self::new(ConnectorConfig::treasury_ofac())
+    }
+}
+
+#[async_trait]
+impl SanctionsListSource for OfacSdnSource {
+    async fn fetch(&self) -> Result<SanctionsList> {
+        // Both files are needed: an address can sit in the overflowing part of a remark
+        let (sdn, comments) = tokio::try_join!(
+            self.fetch_endpoint(OFAC_SDN_ENDPOINT),
+            self.fetch_endpoint(OFAC_SDN_COMMENTS_ENDPOINT),
+        )?;
+
+        Ok(SanctionsList {
+            source: "OFAC SDN".to_string(),
+            fetched_at: Utc::now(),
+            addresses: parse_sdn_csv(&sdn, &comments)?,
+        })
+    }
+}
+
+/// Screens addresses against a sanctions list, refreshing the list as it ages
+pub struct AddressScreener {
+    source: Arc<dyn SanctionsListSource>,
+    list: RwLock<Option<(Instant, SanctionsList)>>,
+    /// When the list was last due and a refresh was started, whether or not it succeeded
+    last_refresh_attempt: RwLock<Option<Instant>>,
+    cache: RwLock<HashMap<String, (Instant, ScreeningResult)>>,
+    block_sanctioned: AtomicBool,
+}
+
+impl AddressScreener {
+    pub fn new(source: Arc<dyn SanctionsListSource>) -> Self {
+        Self {
+            source,
+            list: RwLock::new(None),
+            last_refresh_attempt: RwLock::new(None),
+            cache: RwLock::new(HashMap::new()),
+            block_sanctioned: AtomicBool::new(true),
+        }
+    }
+
+    /// Whether `check_counterparty` refuses sanctioned addresses
+    pub fn set_blocking(&self, enabled: bool) {
+        self.block_sanctioned.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Fetch the list now, replacing the current one and clearing cached results
+    pub async fn refresh(&self) -> Result<usize> {
+        let list = self.source.fetch().await?;
+        let count = list.addresses.len();
+        info!("🛂 Loaded {} sanctioned addresses from {}", count, list.source);
+
+        *self.list.write().await = Some((Instant::now(), list));
+        self.cache.write().await.clear();
+        Ok(count)
+    }
+
+    /// Screen an address against the current sanctions list
+    ///
+    /// A list that cannot be refreshed keeps being used until a refresh
+    /// succeeds, retried at most every `LIST_REFRESH_RETRY_INTERVAL`;
+    /// screening fails only when no list has ever been loaded.
+    pub async fn screen(&self, address: &str) -> Result<ScreeningResult> {
+        let key = normalize_address(address);
+        if let Some((at, result)) = self.cache.read().await.get(&key) {
+            if at.elapsed() < SCREENING_CACHE_TTL {
+                return Ok(result.clone());
+            }
+        }
+
+        self.ensure_fresh_list().await?;
+        let list = self.list.read().await;
+        let (_, list) = list.as_ref().ok_or_else(|| anyhow!("No sanctions list loaded"))?;
+        let matches = list.addresses.get(&key).cloned().unwrap_or_default();
+
+        let result = ScreeningResult {
+            address: address.to_string(),
+            risk_level: if matches.is_empty() { ScreeningRiskLevel::Clear } else { ScreeningRiskLevel::Sanctioned },
+            matches,
+            list_source: list.source.clone(),
+            list_fetched_at: list.fetched_at,
+            screened_at: Utc::now(),
+        };
+        if result.is_sanctioned() {
+            warn!("🛂 Address {} matches {} sanctions entries", address, result.matches.len());
+        }
+
+        let mut cache = self.cache.write().await;
+        if cache.len() >= MAX_CACHED_RESULTS {
+            cache.retain(|_, (at, _)| at.elapsed() < SCREENING_CACHE_TTL);
+        }
+        cache.insert(key, (Instant::now(), result.clone()));
+        Ok(result)
+    }
+
+    /// Refuse a transaction to `address` if it is sanctioned and blocking is enabled
+    pub async fn check_counterparty(&self, address: &str) -> Result<()> {
+        if !self.block_sanctioned.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let result = self.screen(address).await?;
+        if result.is_sanctioned() {
+            return Err(SanctionsError::BlockedAddress {
+                address: address.to_string(),
+                lists: result.list_source,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    async fn ensure_fresh_list(&self) -> Result<()> {
+        let stale = match self.list.read().await.as_ref() {
+            Some((loaded_at, _)) => loaded_at.elapsed() >= LIST_REFRESH_INTERVAL,
+            None => true,
+        };
+        if !stale {
+            return Ok(());
+        }
+
+        {
+            let mut last_attempt = self.last_refresh_attempt.write().await;
+            if matches!(*last_attempt, Some(at) if at.elapsed() < LIST_REFRESH_RETRY_INTERVAL) {
+                if self.list.read().await.is_none() {
+                    return Err(anyhow!("No sanctions list available for screening; the last refresh attempt failed"));
+                }
+                return Ok(());
+            }
+            *last_attempt = Some(Instant::now());
+        }
+
+        match self.refresh().await {
+            Ok(_) => Ok(()),
+            Err(e) if self.list.read().await.is_some() => {
+                warn!("🛂 Sanctions list refresh failed, screening against the previous list: {}", e);
+                Ok(())
+            }
+            Err(e) => Err(anyhow!("No sanctions list available for screening: {}", e)),
+        }
+    }
+}
+
+/// Hex and bech32 addresses are case-insensitive; base58 addresses are not
+pub fn normalize_address(address: &str) -> String {
+    let address = address.trim();
+    let lower = address.to_lowercase();
+    if lower.starts_with("0x") || lower.starts_with("bc1") || lower.starts_with("ltc1") {
+        lower
+    } else {
+        address.to_string()
+    }
+}
+
+/// Digital currency addresses from the remarks of the SDN and SDN comments CSV exports
+///
+/// Remarks longer than the SDN.CSV field limit continue in the comments
+/// export under the same entity number, so the two are joined before the
+/// remarks are searched, including `alt.` addresses.
+pub fn parse_sdn_csv(sdn_csv: &str, comments_csv: &str) -> Result<HashMap<String, Vec<SanctionsMatch>>> {
+    let mut overflow: HashMap<String, String> = HashMap::new();
+    for record in sdn_records(comments_csv) {
+        let record = record?;
+        if let (Some(entity_number), Some(remarks)) = (record.get(SDN_ENTITY_COLUMN), record.get(SDN_COMMENTS_REMARKS_COLUMN)) {
+            overflow.entry(entity_number.trim().to_string()).or_default().push_str(remarks);
+        }
+    }
+
+    let mut addresses: HashMap<String, Vec<SanctionsMatch>> = HashMap::new();
+    for record in sdn_records(sdn_csv) {
+        let record = record?;
+        let (Some(entity_number), Some(entity), Some(remarks)) = (
+            record.get(SDN_ENTITY_COLUMN),
+            record.get(SDN_NAME_COLUMN),
+            record.get(SDN_REMARKS_COLUMN),
+        ) else {
+            continue;
+        };
+        let mut remarks = if remarks == SDN_EMPTY_FIELD { String::new() } else { remarks.to_string() };
+        if let Some(rest) = overflow.get(entity_number.trim()) {
+            remarks.push_str(rest);
+        }
+
+        for remark in remarks.split(';') {
+            let remark = remark.trim();
+            let remark = remark.strip_prefix(SDN_ALTERNATE_PREFIX).unwrap_or(remark);
+            let Some(listing) = remark.strip_prefix(SDN_ADDRESS_MARKER) else {
+                continue;
+            };
+            let mut parts = listing.split_whitespace();
+            let (Some(currency), Some(address)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+
+            addresses
+                .entry(normalize_address(address.trim_end_matches('.')))
+                .or_default()
+                .push(SanctionsMatch {
+                    list: "OFAC SDN".to_string(),
+                    entity: entity.to_string(),
+                    currency: currency.to_string(),
+                });
+        }
+    }
+    Ok(addresses)
+}
+
+/// Records of a headerless OFAC CSV export; trailing short records are tolerated
+fn sdn_records(csv: &str) -> impl Iterator<Item = Result<csv::StringRecord>> + '_ {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(csv.as_bytes())
+        .into_records()
+        .map(|record| record.map_err(|e| anyhow!("Malformed OFAC CSV export: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    const SDN_SAMPLE: &str = concat!(
+        "36,\"AEROCARIBBEAN AIRLINES\",-0-,\"CUBA\",-0-,-0-,-0-,-0-,-0-,-0-,-0-,-0-\n",
+        "30000,\"EXAMPLE MIXER, LTD\",-0-,\"CYBER2\",-0-,-0-,-0-,-0-,-0-,-0-,-0-,",
+        "\"Digital Currency Address - ETH 0xAbC0000000000000000000000000000000000001; ",
+        "alt. Digital Currency Address - XBT 1ExampleBase58Address; Digital Curr\"\n",
+    );
+    /// The end of entity 30000's remarks, cut off in SDN.CSV
+    const SDN_COMMENTS_SAMPLE: &str = concat!(
+        "30000,\"ency Address - ETH 0xabc0000000000000000000000000000000000003; ",
+        "alt. Digital Currency Address - USDT 0xabc0000000000000000000000000000000000004.\"\n",
+    );
+
+    struct StaticSource {
+        fetches: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SanctionsListSource for StaticSource {
+        async fn fetch(&self) -> Result<SanctionsList> {
+            self.fetches.fetch_add(1, Ordering::Relaxed);
+            Ok(SanctionsList {
+                source: "OFAC SDN".to_string(),
+                fetched_at: Utc::now(),
+                addresses: parse_sdn_csv(SDN_SAMPLE, SDN_COMMENTS_SAMPLE)?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_sdn_remarks_yield_digital_currency_addresses() {
+        let addresses = parse_sdn_csv(SDN_SAMPLE, SDN_COMMENTS_SAMPLE).unwrap();
+        assert_eq!(addresses.len(), 4);
+
+        let eth = &addresses["0xabc0000000000000000000000000000000000001"];
+        assert_eq!(eth[0].entity, "EXAMPLE MIXER, LTD");
+        assert_eq!(eth[0].currency, "ETH");
+        // Alternative addresses, and addresses continued in the comments export
+        assert!(addresses.contains_key("1ExampleBase58Address"));
+        assert_eq!(addresses["0xabc0000000000000000000000000000000000003"][0].currency, "ETH");
+        assert_eq!(addresses["0xabc0000000000000000000000000000000000004"][0].currency, "USDT");
+
+        // Without the comments export the overflowing addresses are missed
+        assert_eq!(parse_sdn_csv(SDN_SAMPLE, "").unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_screening_is_cached_and_blocks_sanctioned_counterparties() {
+        let source = Arc::new(StaticSource { fetches: AtomicUsize::new(0) });
+        let screener = AddressScreener::new(source.clone());
+
+        let hit = screener.screen("0xABC0000000000000000000000000000000000001").await.unwrap();
+        assert!(hit.is_sanctioned());
+        assert_eq!(hit.matches[0].entity, "EXAMPLE MIXER, LTD");
+
+        let clear = screener.screen("0x0000000000000000000000000000000000000002").await.unwrap();
+        assert_eq!(clear.risk_level, ScreeningRiskLevel::Clear);
+        assert_eq!(source.fetches.load(Ordering::Relaxed), 1);
+
+        let error = screener.check_counterparty("0xabc0000000000000000000000000000000000001").await.unwrap_err();
+        assert!(error.downcast_ref::<SanctionsError>().is_some());
+
+        screener.set_blocking(false);
+        assert!(screener.check_counterparty("0xabc0000000000000000000000000000000000001").await.is_ok());
+    }
+
+    struct UnavailableSource {
+        fetches: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SanctionsListSource for UnavailableSource {
+        async fn fetch(&self) -> Result<SanctionsList> {
+            self.fetches.fetch_add(1, Ordering::Relaxed);
+            Err(anyhow!("connection refused"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_is_not_retried_on_every_screening() {
+        let source = Arc::new(UnavailableSource { fetches: AtomicUsize::new(0) });
+        let screener = AddressScreener::new(source.clone());
+
+        for _ in 0..3 {
+            assert!(screener.screen("0x0000000000000000000000000000000000000002").await.is_err());
+        }
+        assert_eq!(source.fetches.load(Ordering::Relaxed), 1);
+
+        // An explicit refresh still goes to the source
+        assert!(screener.refresh().await.is_err());
+        assert_eq!(source.fetches.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,528 @@
+//! Smart Contract Management
+//!
+//! Checks guarding upgrades of proxied compliance contracts: the proxy and
+//! new implementation are verified on chain against EIP-1967, and storage
+//! layouts, the compiler's `storageLayout` output (`storage` and `types`),
+//! must line up unless a configured approver signs an override.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::*;
+
+/// Outcome of comparing two implementations' storage layouts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    pub compatible: bool,
+    pub issues: Vec<StorageIssue>,
+}
+
+/// A storage variable of the old implementation the new one would misread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageIssue {
+    pub label: String,
+    pub kind: StorageIssueKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageIssueKind {
+    /// The variable no longer exists, so its slot is reused or abandoned
+    Removed { slot: String, offset: u64 },
+    /// The variable moved to another slot or offset
+    Reordered { from_slot: String, from_offset: u64, to_slot: String, to_offset: u64 },
+    /// The variable kept its position but changed type or size
+    Retyped { from: String, to: String },
+    /// A layout could not be read
+    InvalidLayout { reason: String },
+}
+
+/// EIP-1967 slot holding a proxy's implementation address
+pub const IMPLEMENTATION_SLOT: &str = "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+/// EIP-1967 slot holding a beacon proxy's beacon address
+pub const BEACON_SLOT: &str = "0xa3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50";
+
+/// A request to point `proxy` at `new_implementation`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradeProposal {
+    pub network_id: String,
+    pub proxy: String,
+    pub new_implementation: String,
+    pub pattern: ProxyPattern,
+    /// `storageLayout` of the implementation currently behind the proxy
+    pub old_layout: Value,
+    /// `storageLayout` of `new_implementation`
+    pub new_layout: Value,
+}
+
+/// Approval, signed by a configured approver, to upgrade despite storage incompatibilities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradeOverride {
+    /// Approver id the signing key is configured under
+    pub approved_by: String,
+    pub reason: String,
+    /// Hex-encoded Ed25519 signature over the proposal, approver and reason
+    pub signature: String,
+}
+
+/// Persisted upgrade approvers: approver ids with their hex-encoded Ed25519 public keys
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpgradeApproversFile {
+    pub approvers: HashMap<String, String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpgradeError {
+    #[error("upgrade of {proxy} blocked: {issues} storage incompatibilities")]
+    IncompatibleStorage { proxy: String, issues: usize },
+    #[error("storage checks do not cover {pattern:?} proxies; use Transparent, UUPS or Beacon")]
+    UnsupportedPattern { pattern: ProxyPattern },
+    #[error("{proxy} is not an EIP-1967 proxy: storage slot {slot} is empty")]
+    NotAProxy { proxy: String, slot: String },
+    #[error("{implementation} has no deployed code")]
+    NoImplementationCode { implementation: String },
+    #[error("{implementation} is not UUPS-upgradeable: proxiableUUID() does not return the EIP-1967 implementation slot")]
+    NotProxiable { implementation: String },
+    #[error("override by {approved_by} is not signed by a configured upgrade approver")]
+    UnauthenticatedOverride { approved_by: String },
+}
+
+/// A variable from a `storageLayout` object
+struct StorageVariable {
+    label: String,
+    slot: String,
+    offset: u64,
+    type_label: String,
+    size: String,
+}
+
+impl SmartContractDeployer {
+    pub async fn new(upgrade_manager: Arc<UpgradeManager>) -> Result<Self> {
+        Ok(Self {
+            deployer_id: Uuid::new_v4(),
+            contract_templates: Arc::new(RwLock::new(HashMap::new())),
+            deployment_manager: Arc::new(DeploymentManager),
+            verification_engine: Arc::new(VerificationEngine),
+            upgrade_manager,
+            security_analyzer: Arc::new(SecurityAnalyzer),
+            gas_estimator: Arc::new(GasEstimator),
+            deployment_history: Arc::new(RwLock::new(Vec::new())),
+        })
+    }
+}
+
+impl UpgradeOverride {
+    /// Sign an override of `proposal` as `approved_by`
+    pub fn sign(key: &SigningKey, approved_by: &str, proposal: &UpgradeProposal, reason: &str) -> Self {
+        let digest = Self::digest(approved_by, proposal, reason);
+        Self {
+            approved_by: approved_by.to_string(),
+            reason: reason.to_string(),
+            signature: hex::encode(key.sign(&digest).to_bytes()),
+        }
+    }
+
+    /// What an override signs; every field is length-prefixed so none can bleed into the next
+    fn digest(approved_by: &str, proposal: &UpgradeProposal, reason: &str) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"aion-upgrade-override");
+        for field in [
+            proposal.network_id.as_str(),
+            proposal.proxy.as_str(),
+            proposal.new_implementation.as_str(),
+            approved_by,
+            reason,
+        ] {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field.as_bytes());
+        }
+        hasher.finalize().into()
+    }
+}
+
+impl UpgradeManager {
+    /// A manager with no approvers, so no incompatible upgrade can be overridden
+    pub fn new(contract_manager: Arc<ContractManager>, audit_trail_manager: Arc<AuditTrailManager>) -> Self {
+        Self { contract_manager, audit_trail_manager, approvers: RwLock::new(HashMap::new()) }
+    }
+
+    /// Replace the approver set with the one persisted at `path`
+    pub async fn load_approvers(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let file: UpgradeApproversFile = serde_json::from_slice(&tokio::fs::read(path).await?)
+            .map_err(|e| anyhow!("Invalid upgrade approvers {}: {}", path.display(), e))?;
+
+        let mut approvers = HashMap::new();
+        for (approver_id, public_key) in file.approvers {
+            let bytes: [u8; 32] = hex::decode(&public_key).ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(|| anyhow!("Upgrade approver {} has a malformed public key", approver_id))?;
+            let key = VerifyingKey::from_bytes(&bytes)
+                .map_err(|e| anyhow!("Upgrade approver {} has an invalid public key: {}", approver_id, e))?;
+            approvers.insert(approver_id, key);
+        }
+
+        info!("🔑 Loaded {} upgrade approvers from {}", approvers.len(), path.display());
+        *self.approvers.write().await = approvers;
+        Ok(())
+    }
+
+    /// Allow `approver_id` holding `key` to override incompatible upgrades
+    pub async fn add_approver(&self, approver_id: &str, key: VerifyingKey) {
+        self.approvers.write().await.insert(approver_id.to_string(), key);
+    }
+
+    /// Compare the storage layouts of the current and proposed implementations
+    ///
+    /// Variables are matched by slot and offset, so renaming one is safe.
+    /// Every position the old layout occupies must hold a variable of the
+    /// same type and size in the new one; new variables may only be appended.
+    pub fn check_storage_compatibility(&self, old_layout: &Value, new_layout: &Value) -> CompatibilityReport {
+        let (old_vars, new_vars) = match (storage_variables(old_layout), storage_variables(new_layout)) {
+            (Ok(old_vars), Ok(new_vars)) => (old_vars, new_vars),
+            (Err(reason), _) | (_, Err(reason)) => {
+                return CompatibilityReport {
+                    compatible: false,
+                    issues: vec![StorageIssue {
+                        label: String::new(),
+                        kind: StorageIssueKind::InvalidLayout { reason },
+                    }],
+                };
+            }
+        };
+
+        let mut issues = Vec::new();
+        for old in &old_vars {
+            let in_place = new_vars.iter().find(|new| new.slot == old.slot && new.offset == old.offset);
+            let kind = match in_place {
+                Some(new) if new.type_label == old.type_label && new.size == old.size => continue,
+                Some(new) => StorageIssueKind::Retyped {
+                    from: old.type_label.clone(),
+                    to: new.type_label.clone(),
+                },
+                None => match new_vars.iter().find(|new| new.label == old.label) {
+                    Some(new) => StorageIssueKind::Reordered {
+                        from_slot: old.slot.clone(),
+                        from_offset: old.offset,
+                        to_slot: new.slot.clone(),
+                        to_offset: new.offset,
+                    },
+                    None => StorageIssueKind::Removed { slot: old.slot.clone(), offset: old.offset },
+                },
+            };
+            issues.push(StorageIssue { label: old.label.clone(), kind });
+        }
+
+        CompatibilityReport { compatible: issues.is_empty(), issues }
+    }
+
+    /// Decide whether the proxy of `proposal` may be pointed at its new implementation
+    ///
+    /// The proxy must be an EIP-1967 proxy of the proposal's pattern and the
+    /// new implementation must be deployed; a UUPS implementation must also
+    /// answer `proxiableUUID()` with the implementation slot, or upgrading to
+    /// it would brick the proxy. Incompatible layouts then block the upgrade
+    /// unless `approval` is signed by a configured approver. The decision,
+    /// the issues found and any override are recorded in the audit trail
+    /// either way.
+    pub async fn authorize_upgrade(
+        &self,
+        proposal: &UpgradeProposal,
+        approval: Option<&UpgradeOverride>,
+    ) -> Result<CompatibilityReport> {
+        let proxy = &proposal.proxy;
+        let pattern = &proposal.pattern;
+        if matches!(pattern, ProxyPattern::Diamond) {
+            return Err(UpgradeError::UnsupportedPattern { pattern: pattern.clone() }.into());
+        }
+        self.verify_on_chain(proposal).await?;
+        if let Some(approval) = approval {
+            self.authenticate(proposal, approval).await?;
+        }
+
+        let report = self.check_storage_compatibility(&proposal.old_layout, &proposal.new_layout);
+        let allowed = report.compatible || approval.is_some();
+
+        let actor = approval.map_or("UpgradeManager", |approval| approval.approved_by.as_str());
+        let action = if allowed { "AuthorizeUpgrade" } else { "BlockUpgrade" };
+        let details = AuditDetails::new(actor, action, proxy)
+            .with_metadata("proxy_pattern", json!(pattern))
+            .with_metadata("new_implementation", json!(proposal.new_implementation))
+            .with_metadata("compatible", json!(report.compatible))
+            .with_metadata("issues", json!(report.issues))
+            .with_metadata("override_reason", json!(approval.map(|approval| &approval.reason)))
+            .with_metadata("override_signature", json!(approval.map(|approval| &approval.signature)));
+        self.audit_trail_manager
+            .create_entry(AuditEventType::Custom("ContractUpgradeDecision".to_string()), details)
+            .await?;
+
+        if !allowed {
+            let issues = report.issues.len();
+            warn!("🚫 Upgrade of {} blocked by {} storage incompatibilities", proxy, issues);
+            return Err(UpgradeError::IncompatibleStorage { proxy: proxy.to_string(), issues }.into());
+        }
+        if report.compatible {
+            info!("⬆️ Upgrade of {} authorized with a compatible storage layout", proxy);
+        } else {
+            warn!(
+                "⚠️ Upgrade of {} authorized by {} despite {} storage incompatibilities",
+                proxy, actor, report.issues.len()
+            );
+        }
+        Ok(report)
+    }
+
+    async fn verify_on_chain(&self, proposal: &UpgradeProposal) -> Result<()> {
+        let contracts = &self.contract_manager;
+        let network_id = &proposal.network_id;
+
+        let slot = match proposal.pattern {
+            ProxyPattern::Beacon => BEACON_SLOT,
+            _ => IMPLEMENTATION_SLOT,
+        };
+        let word = contracts.storage_at(network_id, &proposal.proxy, slot).await?;
+        if word == [0u8; 32] {
+            return Err(UpgradeError::NotAProxy { proxy: proposal.proxy.clone(), slot: slot.to_string() }.into());
+        }
+
+        let implementation = &proposal.new_implementation;
+        if contracts.code_at(network_id, implementation).await?.is_empty() {
+            return Err(UpgradeError::NoImplementationCode { implementation: implementation.clone() }.into());
+        }
+
+        if matches!(proposal.pattern, ProxyPattern::UUPS) {
+            let abi = json!([{
+                "type": "function", "name": "proxiableUUID", "stateMutability": "view",
+                "inputs": [], "outputs": [{ "name": "", "type": "bytes32" }]
+            }]);
+            let expected = hex::decode(IMPLEMENTATION_SLOT.trim_start_matches("0x"))?;
+            // A revert means the implementation is not proxiable at all
+            let answer = contracts.call(network_id, implementation, &abi, "proxiableUUID", &[]).await.ok();
+            if !matches!(answer.as_deref(), Some([Token::FixedBytes(uuid)]) if *uuid == expected) {
+                return Err(UpgradeError::NotProxiable { implementation: implementation.clone() }.into());
+            }
+        }
+        Ok(())
+    }
+
+    async fn authenticate(&self, proposal: &UpgradeProposal, approval: &UpgradeOverride) -> Result<()> {
+        let approvers = self.approvers.read().await;
+        let digest = UpgradeOverride::digest(&approval.approved_by, proposal, &approval.reason);
+        let signature = hex::decode(&approval.signature).ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok());
+        let authentic = match (approvers.get(&approval.approved_by), signature) {
+            (Some(key), Some(signature)) => key.verify_strict(&digest, &signature).is_ok(),
+            _ => false,
+        };
+        if !authentic {
+            warn!("🚫 Rejected upgrade override of {} claimed by {}", proposal.proxy, approval.approved_by);
+            return Err(UpgradeError::UnauthenticatedOverride { approved_by: approval.approved_by.clone() }.into());
+        }
+        Ok(())
+    }
+}
+
+fn storage_variables(layout: &Value) -> Result<Vec<StorageVariable>, String> {
+    let storage = layout["storage"].as_array().ok_or("layout has no storage array")?;
+    storage
+        .iter()
+        .map(|variable| {
+            let label = variable["label"].as_str().ok_or("storage variable without a label")?;
+            let type_id = variable["type"].as_str().ok_or_else(|| format!("{} has no type", label))?;
+            let slot = match &variable["slot"] {
+                Value::String(slot) => slot.clone(),
+                Value::Number(slot) => slot.to_string(),
+                _ => return Err(format!("{} has no slot", label)),
+            };
+            let definition = &layout["types"][type_id];
+
+            Ok(StorageVariable {
+                label: label.to_string(),
+                slot,
+                offset: variable["offset"].as_u64().unwrap_or(0),
+                type_label: definition["label"].as_str().unwrap_or(type_id).to_string(),
+                size: definition["numberOfBytes"].as_str().unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ethereum::tests::{manager_with_providers, mock_rpc, network};
+
+    fn layout(variables: &[(&str, &str, u64, &str)]) -> Value {
+        json!({
+            "storage": variables
+                .iter()
+                .map(|(label, slot, offset, type_id)| json!({
+                    "label": label, "slot": slot, "offset": offset, "type": type_id
+                }))
+                .collect::<Vec<_>>(),
+            "types": {
+                "t_address": { "label": "address", "numberOfBytes": "20" },
+                "t_bool": { "label": "bool", "numberOfBytes": "1" },
+                "t_uint256": { "label": "uint256", "numberOfBytes": "32" }
+            }
+        })
+    }
+
+    const PROXY: &str = "0x00000000000000000000000000000000000000a1";
+    const IMPLEMENTATION: &str = "0x00000000000000000000000000000000000000b2";
+    const NOT_PROXIABLE: &str = "0x00000000000000000000000000000000000000b3";
+
+    /// A chain where `PROXY` is an EIP-1967 proxy, `IMPLEMENTATION` is a
+    /// UUPS implementation and `NOT_PROXIABLE` is a contract without
+    /// `proxiableUUID()`; everything else is empty
+    async fn upgrade_manager() -> UpgradeManager {
+        let (url, _) = mock_rpc(|method, params| match method {
+            "eth_getStorageAt" if params[0] == PROXY && params[1] == IMPLEMENTATION_SLOT => {
+                Some(json!(format!("0x{:0>64}", &IMPLEMENTATION[2..])))
+            }
+            "eth_getStorageAt" => Some(json!("0x0")),
+            "eth_getCode" if params[0] == IMPLEMENTATION || params[0] == NOT_PROXIABLE => Some(json!("0x6080")),
+            "eth_getCode" => Some(json!("0x")),
+            "eth_call" if params[0]["to"] == IMPLEMENTATION => Some(json!(IMPLEMENTATION_SLOT)),
+            _ => None,
+        })
+        .await;
+        let ethereum_manager = Arc::new(manager_with_providers(&[&url]).await);
+        let mainnet = NetworkConfig { network_id: "mainnet".to_string(), chain_id: 1, ..network(TxType::Eip1559) };
+        ethereum_manager.networks.write().await.insert("mainnet".to_string(), mainnet);
+        let audit_trail_manager = Arc::new(AuditTrailManager::new(ethereum_manager.clone()).await.unwrap());
+        UpgradeManager::new(ethereum_manager.contract_manager.clone(), audit_trail_manager)
+    }
+
+    fn proposal(old_layout: Value, new_layout: Value) -> UpgradeProposal {
+        UpgradeProposal {
+            network_id: "mainnet".to_string(),
+            proxy: PROXY.to_string(),
+            new_implementation: IMPLEMENTATION.to_string(),
+            pattern: ProxyPattern::UUPS,
+            old_layout,
+            new_layout,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_storage_incompatibilities_are_reported() {
+        let manager = upgrade_manager().await;
+        let current = [
+            ("owner", "0", 0, "t_address"),
+            ("paused", "0", 20, "t_bool"),
+            ("threshold", "1", 0, "t_uint256"),
+            ("fee", "2", 0, "t_uint256"),
+        ];
+        let old = layout(&current);
+
+        let mut appended = current.to_vec();
+        appended.push(("limit", "3", 0, "t_uint256"));
+        assert!(manager.check_storage_compatibility(&old, &layout(&appended)).compatible);
+
+        let mut renamed = current.to_vec();
+        renamed[0].0 = "admin";
+        assert!(manager.check_storage_compatibility(&old, &layout(&renamed)).compatible);
+
+        let broken = layout(&[
+            ("threshold", "0", 0, "t_uint256"),
+            ("owner", "1", 0, "t_address"),
+            ("paused", "1", 20, "t_bool"),
+        ]);
+        let report = manager.check_storage_compatibility(&old, &broken);
+        assert!(!report.compatible);
+        let kinds: Vec<(&str, &StorageIssueKind)> =
+            report.issues.iter().map(|issue| (issue.label.as_str(), &issue.kind)).collect();
+        assert!(matches!(kinds[0], ("owner", StorageIssueKind::Retyped { .. })));
+        assert!(matches!(kinds[1], ("paused", StorageIssueKind::Reordered { .. })));
+        assert!(matches!(kinds[2], ("threshold", StorageIssueKind::Retyped { .. })));
+        assert!(matches!(kinds[3], ("fee", StorageIssueKind::Removed { .. })));
+
+        let mut retyped = current.to_vec();
+        retyped[1].3 = "t_uint256";
+        let report = manager.check_storage_compatibility(&old, &layout(&retyped));
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(
+            report.issues[0].kind,
+            StorageIssueKind::Retyped { from: "bool".to_string(), to: "uint256".to_string() }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incompatible_upgrade_is_blocked_unless_an_approver_signs_off() {
+        let manager = upgrade_manager().await;
+        let proposal = proposal(
+            layout(&[("owner", "0", 0, "t_address")]),
+            layout(&[("threshold", "0", 0, "t_uint256")]),
+        );
+
+        let error = manager.authorize_upgrade(&proposal, None).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<UpgradeError>(),
+            Some(UpgradeError::IncompatibleStorage { issues: 1, .. })
+        ));
+
+        let approver = SigningKey::from_bytes(&[7u8; 32]);
+        let reason = "owner slot deliberately repurposed";
+        let approval = UpgradeOverride::sign(&approver, "compliance-officer", &proposal, reason);
+        let error = manager.authorize_upgrade(&proposal, Some(&approval)).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<UpgradeError>(),
+            Some(UpgradeError::UnauthenticatedOverride { .. })
+        ));
+
+        manager.add_approver("compliance-officer", approver.verifying_key()).await;
+        let forged = UpgradeOverride { reason: "anything".to_string(), ..approval.clone() };
+        let error = manager.authorize_upgrade(&proposal, Some(&forged)).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<UpgradeError>(),
+            Some(UpgradeError::UnauthenticatedOverride { .. })
+        ));
+
+        let report = manager.authorize_upgrade(&proposal, Some(&approval)).await.unwrap();
+        assert!(!report.compatible);
+
+        let trail = manager.audit_trail_manager.trail_storage.entries().await;
+        assert_eq!(trail.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_upgrades_are_checked_against_the_chain() {
+        let manager = upgrade_manager().await;
+        let storage = layout(&[("owner", "0", 0, "t_address")]);
+        let upgrade = proposal(storage.clone(), storage);
+        manager.authorize_upgrade(&upgrade, None).await.unwrap();
+
+        let not_a_proxy = UpgradeProposal { proxy: NOT_PROXIABLE.to_string(), ..upgrade.clone() };
+        let error = manager.authorize_upgrade(&not_a_proxy, None).await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<UpgradeError>(), Some(UpgradeError::NotAProxy { .. })));
+
+        let beacon = UpgradeProposal { pattern: ProxyPattern::Beacon, ..upgrade.clone() };
+        let error = manager.authorize_upgrade(&beacon, None).await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<UpgradeError>(), Some(UpgradeError::NotAProxy { .. })));
+
+        let undeployed = UpgradeProposal { new_implementation: "0xdead".to_string(), ..upgrade.clone() };
+        let error = manager.authorize_upgrade(&undeployed, None).await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<UpgradeError>(), Some(UpgradeError::NoImplementationCode { .. })));
+
+        let bricking = UpgradeProposal { new_implementation: NOT_PROXIABLE.to_string(), ..upgrade.clone() };
+        let error = manager.authorize_upgrade(&bricking, None).await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<UpgradeError>(), Some(UpgradeError::NotProxiable { .. })));
+
+        let transparent = UpgradeProposal {
+            new_implementation: NOT_PROXIABLE.to_string(),
+            pattern: ProxyPattern::Transparent,
+            ..upgrade
+        };
+        manager.authorize_upgrade(&transparent, None).await.unwrap();
+    }
+}
@@ -0,0 +1,549 @@
+//! Zero-Knowledge Proof System
+//!
+//! Compliance proofs bind a statement's public inputs to a commitment over
+//! its private witness under a registered proving system. Proofs produced by
+//! a system with recursion support can be folded into a single proof that
+//! verifies exactly like a leaf proof.
+//!
+//! Witness commitments are blinded with a random nonce that stays with the
+//! prover, so low-entropy witnesses cannot be recovered by hashing guesses.
+//!
+//! Every proof is signed by the prover's Ed25519 key. The verifier accepts
+//! only proofs signed by a key it has been configured to trust, so a proof
+//! cannot be produced by recomputing a hash over chosen inputs.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::*;
+
+/// Proving system used when none has been selected
+const DEFAULT_PROVING_SYSTEM: &str = "halo2";
+
+/// Proof composition errors
+#[derive(Debug, thiserror::Error)]
+pub enum ZKProofError {
+    #[error("proving system '{system_name}' does not support recursive proof composition")]
+    RecursionUnsupported { system_name: String },
+    #[error("proving system '{0}' is not registered")]
+    UnknownSystem(String),
+    #[error("no proofs to compose")]
+    EmptyComposition,
+    #[error("component proof {0} failed verification")]
+    InvalidComponent(Uuid),
+    #[error("no registered proving system satisfies the requirements: {}", .rejected.join("; "))]
+    NoSuitableSystem { rejected: Vec<String> },
+    #[error("no proving key is configured")]
+    NoProvingKey,
+    #[error("no trusted verifying key is configured")]
+    NoVerifyingKey,
+}
+
+/// Constraints used to choose a proving system at call time
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProofRequirements {
+    /// Largest acceptable proof in bytes
+    pub max_proof_size: Option<u32>,
+    /// Verification-time budget
+    pub max_verification_time: Option<Duration>,
+    /// Whether a system needing a trusted setup ceremony is acceptable
+    pub allow_trusted_setup: bool,
+    pub min_security_level: Option<u32>,
+    pub require_recursion: bool,
+}
+
+/// Result of verifying a proof
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofVerification {
+    pub proof_id: Uuid,
+    pub valid: bool,
+    pub system_name: String,
+    pub verified_at: DateTime<Utc>,
+}
+
+impl ZKProofSystem {
+    pub async fn new() -> Result<Self> {
+        Ok(Self {
+            system_id: Uuid::new_v4(),
+            proving_systems: Arc::new(RwLock::new(Self::default_proving_systems())),
+            circuit_manager: Arc::new(CircuitManager),
+            proof_generator: Arc::new(ProofGenerator {
+                signing_key: RwLock::new(None),
+                witness_blindings: RwLock::new(HashMap::new()),
+            }),
+            verifier: Arc::new(ZKVerifier { trusted_keys: RwLock::new(Vec::new()) }),
+            trusted_setup_manager: Arc::new(TrustedSetupManager),
+            recursive_proof_composer: Arc::new(RecursiveProofComposer),
+            active_system: Arc::new(RwLock::new(DEFAULT_PROVING_SYSTEM.to_string())),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!(
+            "🔒 ZK proof system started with {} proving systems, active: {}",
+            self.proving_systems.read().await.len(),
+            self.active_system.read().await
+        );
+        Ok(())
+    }
+
+    /// Sign new proofs with `key`
+    pub async fn set_proving_key(&self, key: SigningKey) {
+        *self.proof_generator.signing_key.write().await = Some(key);
+    }
+
+    /// Load the proving key from a file holding its hex-encoded 32-byte seed
+    pub async fn load_proving_key(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let text = tokio::fs::read_to_string(path).await
+            .map_err(|e| anyhow!("Failed to read proving key {}: {}", path.display(), e))?;
+        let seed: [u8; 32] = hex::decode(text.trim()).ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| anyhow!("Proving key {} is not a hex-encoded 32-byte seed", path.display()))?;
+        self.set_proving_key(SigningKey::from_bytes(&seed)).await;
+        Ok(())
+    }
+
+    /// Nonce that opens the witness commitment of a proof made by this prover
+    pub async fn witness_blinding(&self, proof_id: Uuid) -> Option<[u8; 32]> {
+        self.proof_generator.witness_blindings.read().await.get(&proof_id).copied()
+    }
+
+    /// Accept proofs signed by the prover holding `key`
+    pub async fn trust_verifying_key(&self, key: VerifyingKey) {
+        let mut trusted = self.verifier.trusted_keys.write().await;
+        if !trusted.contains(&key) {
+            trusted.push(key);
+        }
+    }
+
+    fn default_proving_systems() -> HashMap<String, ProvingSystem> {
+        let systems = [
+            ("groth16", ZKSystemType::Groth16, 128, 192, 2, true, false, false),
+            ("plonk", ZKSystemType::PLONK, 128, 868, 5, true, true, false),
+            ("halo2", ZKSystemType::Halo2, 128, 3_500, 10, false, true, true),
+            ("nova", ZKSystemType::Nova, 128, 10_000, 50, false, true, true),
+            ("stark", ZKSystemType::STARK, 100, 90_000, 30, false, true, true),
+            ("bulletproofs", ZKSystemType::Bulletproofs, 128, 1_300, 40, false, true, false),
+        ];
+
+        systems
+            .into_iter()
+            .map(|(name, system_type, security_level, proof_size, verification_ms, trusted_setup, universal, recursion)| {
+                let system = ProvingSystem {
+                    system_name: name.to_string(),
+                    system_type,
+                    security_level,
+                    proof_size,
+                    verification_time: Duration::from_millis(verification_ms),
+                    trusted_setup_required: trusted_setup,
+                    universal_setup: universal,
+                    recursion_supported: recursion,
+                };
+                (name.to_string(), system)
+            })
+            .collect()
+    }
+
+    /// Make a registered proving system the default for new proofs
+    pub async fn set_active_system(&self, system_name: &str) -> Result<()> {
+        if !self.proving_systems.read().await.contains_key(system_name) {
+            return Err(ZKProofError::UnknownSystem(system_name.to_string()).into());
+        }
+        *self.active_system.write().await = system_name.to_string();
+        Ok(())
+    }
+
+    /// Choose the registered proving system that best fits `requirements`
+    ///
+    /// Among the systems meeting every constraint, the one with the fastest
+    /// verification wins, then the smallest proof. The error lists each
+    /// candidate with the reason it was rejected.
+    pub async fn select_system(&self, requirements: ProofRequirements) -> Result<String> {
+        let systems = self.proving_systems.read().await;
+        let mut suitable = Vec::new();
+        let mut rejected = Vec::new();
+
+        for (name, system) in systems.iter() {
+            match Self::unmet_requirement(system, &requirements) {
+                Some(reason) => rejected.push(format!("{} ({})", name, reason)),
+                None => suitable.push(system),
+            }
+        }
+
+        match suitable
+            .into_iter()
+            .min_by(|a, b| {
+                (a.verification_time, a.proof_size, &a.system_name)
+                    .cmp(&(b.verification_time, b.proof_size, &b.system_name))
+            }) {
+            Some(system) => {
+                info!("🔒 Selected proving system {}", system.system_name);
+                Ok(system.system_name.clone())
+            }
+            None => {
+                rejected.sort();
+                Err(ZKProofError::NoSuitableSystem { rejected }.into())
+            }
+        }
+    }
+
+    fn unmet_requirement(system: &ProvingSystem, requirements: &ProofRequirements) -> Option<String> {
+        if let Some(max_size) = requirements.max_proof_size {
+            if system.proof_size > max_size {
+                return Some(format!("proof size {} bytes exceeds {}", system.proof_size, max_size));
+            }
+        }
+        if let Some(budget) = requirements.max_verification_time {
+            if system.verification_time > budget {
+                return Some(format!("verification time {:?} exceeds {:?}", system.verification_time, budget));
+            }
+        }
+        if system.trusted_setup_required && !requirements.allow_trusted_setup {
+            return Some("requires a trusted setup".to_string());
+        }
+        if let Some(min_level) = requirements.min_security_level {
+            if system.security_level < min_level {
+                return Some(format!("security level {} below {}", system.security_level, min_level));
+            }
+        }
+        if requirements.require_recursion && !system.recursion_supported {
+            return Some("no recursion support".to_string());
+        }
+        None
+    }
+
+    /// Prove a compliance statement under its preferred or the active proving system
+    pub async fn generate_compliance_proof(&self, statement: ComplianceStatement) -> Result<ZKProof> {
+        let system_name = match &statement.preferred_system {
+            Some(preferred) => preferred.clone(),
+            None => self.active_system.read().await.clone(),
+        };
+        self.proving_system(&system_name).await?;
+
+        let proof = self.proof_generator.prove(&system_name, &statement).await?;
+        info!("🔒 Generated {} proof {} for statement {}", system_name, proof.proof_id, statement.statement_id);
+        Ok(proof)
+    }
+
+    /// Prove that an audit entry's hash was produced without revealing its actor or resource
+    pub async fn generate_audit_proof(&self, entry: &AuditTrailEntry) -> Result<ZKProof> {
+        let statement = ComplianceStatement {
+            statement_id: entry.entry_id,
+            regulation: "AuditTrail".to_string(),
+            claim: format!("{:?}", entry.event_type),
+            public_inputs: vec![entry.current_hash.clone()],
+            private_witness: vec![entry.actor.clone(), entry.resource.clone()],
+            preferred_system: None,
+        };
+        self.generate_compliance_proof(statement).await
+    }
+
+    /// Verify a leaf or recursive proof against the trusted prover keys
+    pub async fn verify_proof(&self, proof: &ZKProof) -> Result<ProofVerification> {
+        let valid = self.proving_systems.read().await.contains_key(&proof.system_name)
+            && self.verifier.verify(proof).await?;
+
+        Ok(ProofVerification {
+            proof_id: proof.proof_id,
+            valid,
+            system_name: proof.system_name.clone(),
+            verified_at: Utc::now(),
+        })
+    }
+
+    /// Fold several compliance proofs into one recursive proof
+    ///
+    /// Requires the active proving system to support recursion. Every
+    /// component is verified first; the composed proof exposes the union of
+    /// their public inputs and verifies under `verify_proof` like a leaf.
+    pub async fn compose_proofs(&self, proofs: Vec<ZKProof>) -> Result<ZKProof> {
+        if proofs.is_empty() {
+            return Err(ZKProofError::EmptyComposition.into());
+        }
+
+        let system_name = self.active_system.read().await.clone();
+        let system = self.proving_system(&system_name).await?;
+        if !system.recursion_supported {
+            return Err(ZKProofError::RecursionUnsupported { system_name }.into());
+        }
+
+        for proof in &proofs {
+            if !self.verify_proof(proof).await?.valid {
+                return Err(ZKProofError::InvalidComponent(proof.proof_id).into());
+            }
+        }
+
+        let composed = self.recursive_proof_composer.fold(&self.proof_generator, &system_name, &proofs).await?;
+        info!("🔒 Composed {} proofs into recursive {} proof {}", proofs.len(), system_name, composed.proof_id);
+        Ok(composed)
+    }
+
+    async fn proving_system(&self, system_name: &str) -> Result<ProvingSystem> {
+        self.proving_systems
+            .read()
+            .await
+            .get(system_name)
+            .cloned()
+            .ok_or_else(|| anyhow!(ZKProofError::UnknownSystem(system_name.to_string())))
+    }
+}
+
+impl ProofGenerator {
+    async fn prove(&self, system_name: &str, statement: &ComplianceStatement) -> Result<ZKProof> {
+        let blinding = random_blinding()?;
+        self.seal_blinded(
+            ZKProof {
+                proof_id: Uuid::new_v4(),
+                system_name: system_name.to_string(),
+                statement_id: Some(statement.statement_id),
+                public_inputs: statement.public_inputs.clone(),
+                witness_commitment: commit(&blinding, statement.private_witness.iter().map(String::as_str)),
+                component_proofs: Vec::new(),
+                proof_data: String::new(),
+                created_at: Utc::now(),
+            },
+            blinding,
+        )
+        .await
+    }
+
+    /// Seal the proof and keep the nonce its witness commitment was blinded with
+    async fn seal_blinded(&self, proof: ZKProof, blinding: [u8; 32]) -> Result<ZKProof> {
+        let proof = self.seal(proof).await?;
+        self.witness_blindings.write().await.insert(proof.proof_id, blinding);
+        Ok(proof)
+    }
+
+    /// Fill in the proof data by signing every other field of the proof
+    async fn seal(&self, mut proof: ZKProof) -> Result<ZKProof> {
+        let signing_key = self.signing_key.read().await;
+        let key = signing_key.as_ref().ok_or(ZKProofError::NoProvingKey)?;
+        proof.proof_data = hex::encode(key.sign(&proof_digest(&proof)).to_bytes());
+        Ok(proof)
+    }
+}
+
+impl RecursiveProofComposer {
+    /// Fold verified component proofs; their proof data becomes the witness
+    async fn fold(&self, generator: &ProofGenerator, system_name: &str, proofs: &[ZKProof]) -> Result<ZKProof> {
+        let blinding = random_blinding()?;
+        generator
+            .seal_blinded(
+                ZKProof {
+                    proof_id: Uuid::new_v4(),
+                    system_name: system_name.to_string(),
+                    statement_id: None,
+                    public_inputs: proofs.iter().flat_map(|proof| proof.public_inputs.clone()).collect(),
+                    witness_commitment: commit(&blinding, proofs.iter().map(|proof| proof.proof_data.as_str())),
+                    component_proofs: proofs.iter().map(|proof| proof.proof_id).collect(),
+                    proof_data: String::new(),
+                    created_at: Utc::now(),
+                },
+                blinding,
+            )
+            .await
+    }
+}
+
+impl ZKVerifier {
+    /// Whether a trusted key signed the proof; fails if no key is trusted
+    async fn verify(&self, proof: &ZKProof) -> Result<bool> {
+        let trusted = self.trusted_keys.read().await;
+        if trusted.is_empty() {
+            return Err(ZKProofError::NoVerifyingKey.into());
+        }
+        let Some(signature) = hex::decode(&proof.proof_data).ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok()) else {
+            return Ok(false);
+        };
+        let digest = proof_digest(proof);
+        Ok(trusted.iter().any(|key| key.verify_strict(&digest, &signature).is_ok()))
+    }
+}
+
+fn proof_digest(proof: &ZKProof) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(proof.proof_id.as_bytes());
+    hasher.update((proof.system_name.len() as u64).to_be_bytes());
+    hasher.update(proof.system_name.as_bytes());
+    if let Some(statement_id) = proof.statement_id {
+        hasher.update(statement_id.as_bytes());
+    }
+    for input in &proof.public_inputs {
+        hasher.update((input.len() as u64).to_be_bytes());
+        hasher.update(input.as_bytes());
+    }
+    hasher.update(proof.witness_commitment.as_bytes());
+    for component in &proof.component_proofs {
+        hasher.update(component.as_bytes());
+    }
+    hasher.update(proof.created_at.timestamp_nanos_opt().unwrap_or_default().to_be_bytes());
+    hasher.finalize().into()
+}
+
+fn random_blinding() -> Result<[u8; 32]> {
+    let mut blinding = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut blinding)
+        .map_err(|_| anyhow!("System random number generator failed"))?;
+    Ok(blinding)
+}
+
+fn commit<'a>(blinding: &[u8; 32], values: impl Iterator<Item = &'a str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(blinding);
+    for value in values {
+        hasher.update((value.len() as u64).to_be_bytes());
+        hasher.update(value.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statement(claim: &str) -> ComplianceStatement {
+        ComplianceStatement {
+            statement_id: Uuid::new_v4(),
+            regulation: "GDPR".to_string(),
+            claim: claim.to_string(),
+            public_inputs: vec![claim.to_string()],
+            private_witness: vec!["customer-record".to_string()],
+            preferred_system: None,
+        }
+    }
+
+    /// A proof system signing with, and trusting, the key derived from `seed`
+    async fn keyed_system(seed: u8) -> ZKProofSystem {
+        let system = ZKProofSystem::new().await.unwrap();
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        system.trust_verifying_key(key.verifying_key()).await;
+        system.set_proving_key(key).await;
+        system
+    }
+
+    #[tokio::test]
+    async fn test_composed_proof_verifies_like_leaf() {
+        let system = keyed_system(1).await;
+        let mut proofs = Vec::new();
+        for claim in ["q1-retention", "q1-consent", "q1-erasure"] {
+            proofs.push(system.generate_compliance_proof(statement(claim)).await.unwrap());
+        }
+
+        let composed = system.compose_proofs(proofs).await.unwrap();
+        assert_eq!(composed.component_proofs.len(), 3);
+        assert!(system.verify_proof(&composed).await.unwrap().valid);
+
+        let mut forged = composed.clone();
+        forged.public_inputs.pop();
+        assert!(!system.verify_proof(&forged).await.unwrap().valid);
+    }
+
+    #[tokio::test]
+    async fn test_compose_rejects_non_recursive_system() {
+        let system = keyed_system(1).await;
+        system.set_active_system("groth16").await.unwrap();
+        let proof = system.generate_compliance_proof(statement("q1-retention")).await.unwrap();
+
+        let error = system.compose_proofs(vec![proof]).await.unwrap_err();
+        match error.downcast_ref::<ZKProofError>() {
+            Some(ZKProofError::RecursionUnsupported { system_name }) => assert_eq!(system_name, "groth16"),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_select_system_honours_constraints() {
+        let system = ZKProofSystem::new().await.unwrap();
+
+        let compact = ProofRequirements {
+            max_proof_size: Some(1_000),
+            allow_trusted_setup: true,
+            ..ProofRequirements::default()
+        };
+        assert_eq!(system.select_system(compact).await.unwrap(), "groth16");
+
+        let transparent_recursive = ProofRequirements {
+            require_recursion: true,
+            ..ProofRequirements::default()
+        };
+        assert_eq!(system.select_system(transparent_recursive).await.unwrap(), "halo2");
+
+        let impossible = ProofRequirements {
+            max_proof_size: Some(100),
+            ..ProofRequirements::default()
+        };
+        let message = system.select_system(impossible).await.unwrap_err().to_string();
+        assert!(message.contains("groth16 (proof size 192 bytes exceeds 100)"));
+    }
+
+    #[tokio::test]
+    async fn test_preferred_system_overrides_active() {
+        let system = keyed_system(1).await;
+        let mut preferred = statement("q1-retention");
+        preferred.preferred_system = Some("plonk".to_string());
+
+        let proof = system.generate_compliance_proof(preferred).await.unwrap();
+        assert_eq!(proof.system_name, "plonk");
+    }
+
+    #[tokio::test]
+    async fn test_forged_proof_is_rejected() {
+        let system = keyed_system(1).await;
+        let genuine = system.generate_compliance_proof(statement("q1-retention")).await.unwrap();
+        assert!(system.verify_proof(&genuine).await.unwrap().valid);
+
+        // Re-sealing altered inputs with the old unkeyed digest no longer passes
+        let mut forged = genuine.clone();
+        forged.public_inputs = vec!["q1-erasure".to_string()];
+        forged.proof_data = hex::encode(proof_digest(&forged));
+        assert!(!system.verify_proof(&forged).await.unwrap().valid);
+
+        // Nor does a proof signed by a prover the verifier does not trust
+        let impostor = keyed_system(2).await;
+        let untrusted = impostor.generate_compliance_proof(statement("q1-erasure")).await.unwrap();
+        assert!(!system.verify_proof(&untrusted).await.unwrap().valid);
+    }
+
+    #[tokio::test]
+    async fn test_witness_commitment_is_blinded() {
+        let system = keyed_system(1).await;
+        let first = system.generate_compliance_proof(statement("q1-retention")).await.unwrap();
+        let second = system.generate_compliance_proof(statement("q1-retention")).await.unwrap();
+
+        // Hashing a guessed witness does not reproduce the commitment
+        let witness = ["customer-record"];
+        assert_ne!(first.witness_commitment, second.witness_commitment);
+        assert_ne!(first.witness_commitment, commit(&[0u8; 32], witness.into_iter()));
+
+        // Only the prover's nonce opens it
+        let blinding = system.witness_blinding(first.proof_id).await.unwrap();
+        assert_eq!(first.witness_commitment, commit(&blinding, witness.into_iter()));
+        assert!(keyed_system(1).await.witness_blinding(first.proof_id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_keys_fail_closed() {
+        let unkeyed = ZKProofSystem::new().await.unwrap();
+        let error = unkeyed.generate_compliance_proof(statement("q1-retention")).await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<ZKProofError>(), Some(ZKProofError::NoProvingKey)));
+
+        let proof = keyed_system(1).await.generate_compliance_proof(statement("q1-retention")).await.unwrap();
+        let error = unkeyed.verify_proof(&proof).await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<ZKProofError>(), Some(ZKProofError::NoVerifyingKey)));
+    }
+}
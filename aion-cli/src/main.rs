@@ -7,8 +7,15 @@ use tokio;
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
 use tabled::{Table, Tabled};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use uuid::Uuid;
+use aion_core::truncate_string;
+
+mod settings;
+
+use settings::FileConfig;
+
+const DEFAULT_SERVER: &str = "http://localhost:8080";
 
 #[derive(Tabled)]
 struct AgentStatus {
@@ -36,6 +43,16 @@ struct ComplianceResult {
     last_check: String,
 }
 
+#[derive(Tabled)]
+struct ViolationResult {
+    #[tabled(rename = "#")]
+    index: usize,
+    severity: String,
+    entity: String,
+    framework: String,
+    description: String,
+}
+
 #[derive(Tabled)]
 struct ConflictResult {
     id: String,
@@ -61,6 +78,7 @@ struct CliConfig {
     color: bool,
     auto_confirm: bool,
     max_retries: u32,
+    dry_run: bool,
 }
 
 impl Default for CliConfig {
@@ -71,6 +89,7 @@ impl Default for CliConfig {
             color: true,
             auto_confirm: false,
             max_retries: 3,
+            dry_run: false,
         }
     }
 }
@@ -79,18 +98,33 @@ impl Default for CliConfig {
 async fn main() {
     let matches = create_cli_app().get_matches();
 
-    let config = CliConfig {
-        output_format: matches.value_of("format").unwrap_or("table").to_string(),
-        verbose: matches.is_present("verbose"),
-        color: !matches.is_present("no-color"),
-        auto_confirm: matches.is_present("yes"),
-        max_retries: matches.value_of("retries").unwrap_or("3").parse().unwrap_or(3),
+    // `config` commands must still run when the file is invalid, so `config set` can repair it
+    let managing_config = matches.subcommand_name() == Some("config");
+    let file_config = match settings::config_path() {
+        Some(path) => FileConfig::load(&path).unwrap_or_else(|e| {
+            if !managing_config {
+                eprintln!("{}: {}", "Error".red(), e);
+                process::exit(1);
+            }
+            eprintln!("{}: {}", "Warning".yellow(), e);
+            FileConfig::default()
+        }),
+        None => FileConfig::default(),
     };
+    let (server, config) = resolve_config(&matches, &file_config);
+
+    if config.dry_run {
+        let command = command_path(&matches);
+        if READ_ONLY_COMMANDS.contains(&command.as_str()) {
+            eprintln!("{}", format!("--dry-run has no effect on read-only command '{}'", command).yellow());
+        } else if !DRY_RUN_COMMANDS.contains(&command.as_str()) {
+            // Running a state-changing command for real when a dry run was asked for is the one unsafe outcome
+            eprintln!("{}", format!("--dry-run is not supported for '{}'; nothing was sent", command).red());
+            process::exit(2);
+        }
+    }
 
-    let cli = AionCli::new(
-        matches.value_of("server").unwrap_or("http://localhost:8080"),
-        config,
-    );
+    let cli = AionCli::new(&server, config);
 
     let result = match matches.subcommand() {
         ("agents", Some(sub_m)) => cli.handle_agents_command(sub_m).await,
@@ -121,6 +155,40 @@ async fn main() {
     }
 }
 
+/// Server URL and settings for this run. Flags given on the command line win
+/// over the config file, which wins over the built-in defaults.
+fn resolve_config(matches: &ArgMatches<'_>, file_config: &FileConfig) -> (String, CliConfig) {
+    let flag = |name: &str| if matches.occurrences_of(name) > 0 { matches.value_of(name) } else { None };
+    // Of a flag and its negation, the one given last wins
+    let switch = |on: &str, off: &str| match (matches.index_of(on), matches.index_of(off)) {
+        (Some(on), Some(off)) => Some(on > off),
+        (Some(_), None) => Some(true),
+        (None, Some(_)) => Some(false),
+        (None, None) => None,
+    };
+
+    let server = flag("server")
+        .map(|server| server.trim_end_matches('/').to_string())
+        .or_else(|| file_config.server.clone())
+        .unwrap_or_else(|| DEFAULT_SERVER.to_string());
+    let config = CliConfig {
+        output_format: flag("format")
+            .map(str::to_string)
+            .or_else(|| file_config.output_format.clone())
+            .unwrap_or_else(|| "table".to_string()),
+        verbose: switch("verbose", "no-verbose").or(file_config.verbose).unwrap_or(false),
+        color: switch("color", "no-color").or(file_config.color).unwrap_or(true),
+        auto_confirm: matches.is_present("yes"),
+        // Validated by clap, so a bad --retries never falls back to the file
+        max_retries: flag("retries")
+            .and_then(|retries| retries.parse().ok())
+            .or(file_config.max_retries)
+            .unwrap_or(3),
+        dry_run: matches.is_present("dry-run"),
+    };
+    (server, config)
+}
+
 fn create_cli_app() -> App<'static, 'static> {
     App::new("aion-cli")
         .version("1.0.0")
@@ -131,18 +199,24 @@ fn create_cli_app() -> App<'static, 'static> {
             .long("server")
             .value_name("URL")
             .help("AION-CR server URL")
-            .default_value("http://localhost:8080"))
+            .default_value(DEFAULT_SERVER))
         .arg(Arg::with_name("format")
             .short("f")
             .long("format")
             .value_name("FORMAT")
             .help("Output format")
-            .possible_values(&["table", "json", "yaml", "csv"])
+            .possible_values(settings::OUTPUT_FORMATS)
             .default_value("table"))
         .arg(Arg::with_name("verbose")
             .short("v")
             .long("verbose")
             .help("Enable verbose output"))
+        .arg(Arg::with_name("no-verbose")
+            .long("no-verbose")
+            .help("Disable verbose output set in the config file"))
+        .arg(Arg::with_name("color")
+            .long("color")
+            .help("Enable colored output disabled in the config file"))
         .arg(Arg::with_name("no-color")
             .long("no-color")
             .help("Disable colored output"))
@@ -154,7 +228,11 @@ fn create_cli_app() -> App<'static, 'static> {
             .long("retries")
             .value_name("N")
             .help("Number of retries for failed requests")
-            .default_value("3"))
+            .default_value("3")
+            .validator(|retries| retries.parse::<u32>().map(|_| ()).map_err(|_| format!("'{}' is not a whole number", retries))))
+        .arg(Arg::with_name("dry-run")
+            .long("dry-run")
+            .help("Print the request that would be sent instead of sending it"))
         .subcommand(create_agents_subcommand())
         .subcommand(create_compliance_subcommand())
         .subcommand(create_conflicts_subcommand())
@@ -283,7 +361,29 @@ fn create_compliance_subcommand() -> App<'static, 'static> {
                 .long("severity")
                 .value_name("LEVEL")
                 .help("Minimum severity level")
-                .possible_values(&["Critical", "High", "Medium", "Low"])))
+                .possible_values(&["Critical", "High", "Medium", "Low"]))
+            .arg(Arg::with_name("entity")
+                .long("entity")
+                .value_name("ENTITY_ID")
+                .help("Only violations for this entity"))
+            .arg(Arg::with_name("framework")
+                .long("framework")
+                .value_name("FRAMEWORK")
+                .help("Only violations of this framework"))
+            .arg(Arg::with_name("since")
+                .long("since")
+                .value_name("DATE")
+                .help("Only violations detected since this date (YYYY-MM-DD or RFC 3339)"))
+            .arg(Arg::with_name("limit")
+                .long("limit")
+                .value_name("COUNT")
+                .help("Violations per page (ignored for JSON output)")
+                .default_value("50"))
+            .arg(Arg::with_name("offset")
+                .long("offset")
+                .value_name("COUNT")
+                .help("Violations to skip (ignored for JSON output)")
+                .default_value("0")))
 }
 
 fn create_conflicts_subcommand() -> App<'static, 'static> {
@@ -470,9 +570,9 @@ fn create_config_subcommand() -> App<'static, 'static> {
     SubCommand::with_name("config")
         .about("Configuration management")
         .subcommand(SubCommand::with_name("show")
-            .about("Show current configuration"))
+            .about("Show the settings in effect, from flags, the config file and defaults"))
         .subcommand(SubCommand::with_name("set")
-            .about("Set configuration value")
+            .about("Set a value in the local config file")
             .arg(Arg::with_name("key")
                 .value_name("KEY")
                 .help("Configuration key")
@@ -482,13 +582,13 @@ fn create_config_subcommand() -> App<'static, 'static> {
                 .help("Configuration value")
                 .required(true)))
         .subcommand(SubCommand::with_name("get")
-            .about("Get configuration value")
+            .about("Get a value from the local config file")
             .arg(Arg::with_name("key")
                 .value_name("KEY")
                 .help("Configuration key")
                 .required(true)))
         .subcommand(SubCommand::with_name("reset")
-            .about("Reset configuration to defaults"))
+            .about("Delete the local config file, returning to defaults"))
 }
 
 impl AionCli {
@@ -500,6 +600,65 @@ impl AionCli {
         }
     }
 
+    /// Send `request`, retrying transient failures up to `max_retries` times when
+    /// `retry` allows it. Other responses, including 4xx errors, are returned at once
+    /// for the caller to report.
+    async fn send_with_retry(
+        &self,
+        request: reqwest::RequestBuilder,
+        retry: Retry,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error>> {
+        let max_retries = if retry == Retry::Idempotent { self.config.max_retries } else { 0 };
+        let mut attempt = 0;
+
+        loop {
+            // Streaming bodies can't be cloned, so they get a single attempt
+            let Some(this_attempt) = request.try_clone() else {
+                return Ok(request.send().await?);
+            };
+            let outcome = this_attempt.send().await;
+
+            let (delay, reason) = match &outcome {
+                Ok(response) if is_retryable_status(response.status()) => {
+                    let retry_after = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| parse_retry_after(value, Utc::now()));
+                    (retry_after.unwrap_or_else(|| backoff_delay(attempt)), response.status().to_string())
+                }
+                Err(e) if e.is_timeout() || e.is_connect() => (backoff_delay(attempt), e.to_string()),
+                _ => return Ok(outcome?),
+            };
+
+            if attempt >= max_retries {
+                return Ok(outcome?);
+            }
+            attempt += 1;
+            if self.config.verbose {
+                eprintln!("{}", format!("Request failed ({}), retry {}/{} in {:.1}s",
+                    reason, attempt, max_retries, delay.as_secs_f64()).yellow());
+            }
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// In dry-run mode, print the request instead of sending it and return true
+    fn print_dry_run(&self, method: &str, url: &str, body: &Value) -> Result<bool, Box<dyn std::error::Error>> {
+        if !self.config.dry_run {
+            return Ok(false);
+        }
+
+        if self.config.output_format == "json" {
+            println!("{}", json!({ "method": method, "url": url, "body": body }));
+        } else {
+            println!("{}", "DRY RUN: nothing was sent".yellow().bold());
+            println!("{} {}", method, url);
+            println!("{}", serde_json::to_string_pretty(body)?);
+        }
+        Ok(true)
+    }
+
     async fn handle_agents_command(&self, matches: &ArgMatches<'_>) -> Result<(), Box<dyn std::error::Error>> {
         match matches.subcommand() {
             ("list", _) => self.list_agents().await,
@@ -520,34 +679,16 @@ impl AionCli {
             println!("{}", "Fetching agents list...".blue());
         }
 
-        let response = self.client
-            .get(&format!("{}/api/v1/agents", self.base_url))
-            .send()
-            .await?;
+        let request = self.client
+            .get(&format!("{}/api/v1/agents", self.base_url));
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         let agents: Value = response.json().await?;
 
-        match self.config.output_format.as_str() {
-            "json" => println!("{}", serde_json::to_string_pretty(&agents)?),
-            "yaml" => println!("{}", serde_yaml::to_string(&agents)?),
-            "csv" => {
-                // CSV output implementation
-                println!("id,name,type,status,decisions,accuracy,autonomy");
-                if let Some(agents_array) = agents.as_array() {
-                    for agent in agents_array {
-                        println!("{},{},{},{},{},{},{}",
-                            agent["id"].as_str().unwrap_or(""),
-                            agent["name"].as_str().unwrap_or(""),
-                            agent["agent_type"].as_str().unwrap_or(""),
-                            agent["status"].as_str().unwrap_or(""),
-                            agent["performance_metrics"]["decisions_made"].as_u64().unwrap_or(0),
-                            agent["performance_metrics"]["accuracy_rate"].as_f64().unwrap_or(0.0),
-                            agent["performance_metrics"]["autonomy_score"].as_f64().unwrap_or(0.0)
-                        );
-                    }
-                }
-            },
-            _ => {
+        match render_output(&agents, &self.config.output_format)? {
+            Some(rendered) => println!("{}", rendered),
+            None => {
                 // Table output (default)
                 let mut table_data = Vec::new();
                 if let Some(agents_array) = agents.as_array() {
@@ -556,7 +697,7 @@ impl AionCli {
                             id: agent["id"].as_str().unwrap_or("").chars().take(8).collect(),
                             name: agent["name"].as_str().unwrap_or("").to_string(),
                             agent_type: agent["agent_type"].as_str().unwrap_or("").to_string(),
-                            status: self.colorize_status(agent["status"].as_str().unwrap_or("")),
+                            status: self.colorize_status(agent["status"].as_str().unwrap_or("")).to_string(),
                             decisions_made: agent["performance_metrics"]["decisions_made"].as_u64().unwrap_or(0),
                             accuracy: format!("{:.1}%", agent["performance_metrics"]["accuracy_rate"].as_f64().unwrap_or(0.0) * 100.0),
                             autonomy_level: format!("{:.1}%", agent["performance_metrics"]["autonomy_score"].as_f64().unwrap_or(0.0) * 100.0),
@@ -598,6 +739,11 @@ impl AionCli {
             }
         });
 
+        let url = format!("{}/api/v1/agents/create", self.base_url);
+        if self.print_dry_run("POST", &url, &request_body)? {
+            return Ok(());
+        }
+
         if self.config.verbose {
             println!("{}", format!("Creating agent: {}", name).blue());
         }
@@ -609,11 +755,11 @@ impl AionCli {
         pb.set_message("Creating agent...");
         pb.enable_steady_tick(100);
 
-        let response = self.client
-            .post(&format!("{}/api/v1/agents/create", self.base_url))
-            .json(&request_body)
-            .send()
-            .await?;
+        let request = self.client
+            .post(&url)
+            .json(&request_body);
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         pb.finish_with_message("Agent creation completed");
 
@@ -626,8 +772,7 @@ impl AionCli {
                 println!("{}", "⚠️  Agent created with MAXIMUM AUTONOMY privileges".yellow().bold());
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to create agent".red(), error_text);
+            eprintln!("{}: {}", "Failed to create agent".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -640,16 +785,15 @@ impl AionCli {
             println!("{}", format!("Activating agent: {}", agent_id).blue());
         }
 
-        let response = self.client
-            .post(&format!("{}/api/v1/agents/{}/activate", self.base_url, agent_id))
-            .send()
-            .await?;
+        let request = self.client
+            .post(&format!("{}/api/v1/agents/{}/activate", self.base_url, agent_id));
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         if response.status().is_success() {
             println!("{}", format!("Agent {} activated successfully", agent_id).green());
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to activate agent".red(), error_text);
+            eprintln!("{}: {}", "Failed to activate agent".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -673,16 +817,15 @@ impl AionCli {
             println!("{}", format!("Deactivating agent: {}", agent_id).blue());
         }
 
-        let response = self.client
-            .post(&format!("{}/api/v1/agents/{}/deactivate", self.base_url, agent_id))
-            .send()
-            .await?;
+        let request = self.client
+            .post(&format!("{}/api/v1/agents/{}/deactivate", self.base_url, agent_id));
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         if response.status().is_success() {
             println!("{}", format!("Agent {} deactivated successfully", agent_id).green());
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to deactivate agent".red(), error_text);
+            eprintln!("{}: {}", "Failed to deactivate agent".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -691,17 +834,17 @@ impl AionCli {
     async fn agent_status(&self, matches: &ArgMatches<'_>) -> Result<(), Box<dyn std::error::Error>> {
         let agent_id = matches.value_of("id").unwrap();
 
-        let response = self.client
-            .get(&format!("{}/api/v1/agents/{}/status", self.base_url, agent_id))
-            .send()
-            .await?;
+        let request = self.client
+            .get(&format!("{}/api/v1/agents/{}/status", self.base_url, agent_id));
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         if response.status().is_success() {
             let status: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&status)?),
-                _ => {
+            match render_output(&status, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", format!("Agent Status: {}", agent_id).bold().blue());
                     println!("Name: {}", status["name"].as_str().unwrap_or("Unknown"));
                     println!("Type: {}", status["agent_type"].as_str().unwrap_or("Unknown"));
@@ -719,8 +862,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to get agent status".red(), error_text);
+            eprintln!("{}: {}", "Failed to get agent status".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -749,11 +891,11 @@ impl AionCli {
         pb.set_message("Executing task...");
         pb.enable_steady_tick(100);
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/agents/{}/execute", self.base_url, agent_id))
-            .json(&task_request)
-            .send()
-            .await?;
+            .json(&task_request);
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         pb.finish_with_message("Task execution completed");
 
@@ -762,8 +904,7 @@ impl AionCli {
             println!("{}", format!("Task executed successfully. Task ID: {}",
                 result["task_id"].as_str().unwrap_or("unknown")).green());
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to execute task".red(), error_text);
+            eprintln!("{}: {}", "Failed to execute task".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -806,20 +947,20 @@ impl AionCli {
         pb.set_message("Running compliance assessment...");
         pb.enable_steady_tick(100);
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/compliance/assess", self.base_url))
-            .json(&assessment_request)
-            .send()
-            .await?;
+            .json(&assessment_request);
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         pb.finish_with_message("Assessment completed");
 
         if response.status().is_success() {
             let result: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&result)?),
-                _ => {
+            match render_output(&result, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", "Compliance Assessment Results".bold().blue());
                     println!("Entity: {}", entity);
                     println!("Framework: {}", framework);
@@ -846,8 +987,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to run compliance assessment".red(), error_text);
+            eprintln!("{}: {}", "Failed to run compliance assessment".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -863,34 +1003,140 @@ impl AionCli {
             "alert_threshold": 0.8
         });
 
+        let url = format!("{}/api/v1/compliance/monitor", self.base_url);
+        if self.print_dry_run("POST", &url, &monitoring_request)? {
+            return Ok(());
+        }
+
         if self.config.verbose {
             println!("{}", format!("Starting {} monitoring for frameworks: {}",
                 if real_time { "real-time" } else { "periodic" }, frameworks.join(", ")).blue());
         }
 
-        let response = self.client
-            .post(&format!("{}/api/v1/compliance/monitor", self.base_url))
-            .json(&monitoring_request)
-            .send()
-            .await?;
+        let request = self.client
+            .post(&url)
+            .json(&monitoring_request);
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         if response.status().is_success() {
             let result: Value = response.json().await?;
-            println!("{}", format!("Monitoring started successfully. Monitor ID: {}",
-                result["monitor_id"].as_str().unwrap_or("unknown")).green());
+            let monitor_id = result["monitor_id"].as_str().unwrap_or("unknown");
+            // Status goes to stderr in JSON mode so stdout stays one alert per line
+            let started = format!("Monitoring started successfully. Monitor ID: {}", monitor_id);
+            if self.config.output_format == "json" {
+                eprintln!("{}", started);
+            } else {
+                println!("{}", started.green());
+            }
 
             if real_time {
-                println!("{}", "Real-time alerts will be displayed below:".blue());
-                // Here you would implement real-time alert streaming
+                if self.config.output_format != "json" {
+                    println!("{}", "Real-time alerts will be displayed below (Ctrl+C to stop):".blue());
+                }
+                self.stream_compliance_alerts(monitor_id).await?;
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to start monitoring".red(), error_text);
+            eprintln!("{}: {}", "Failed to start monitoring".red(), describe_error_response(response).await);
         }
 
         Ok(())
     }
 
+    /// Print alerts from the monitor's server-sent event stream until Ctrl+C,
+    /// reconnecting with exponential backoff when the connection drops
+    async fn stream_compliance_alerts(&self, monitor_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/compliance/monitor/{}/alerts", self.base_url, monitor_id);
+        let mut last_event_id: Option<String> = None;
+        let mut backoff = ALERT_STREAM_INITIAL_BACKOFF;
+
+        let ctrl_c = tokio::signal::ctrl_c();
+        tokio::pin!(ctrl_c);
+
+        loop {
+            let outcome = tokio::select! {
+                _ = &mut ctrl_c => break,
+                outcome = self.consume_alert_stream(&url, &mut last_event_id, &mut backoff) => outcome,
+            };
+
+            match outcome {
+                Ok(()) => eprintln!("{}", "Alert stream closed by server".yellow()),
+                Err(AlertStreamError::Fatal(reason)) => {
+                    eprintln!("{}: {}", "Alert stream unavailable".red(), reason);
+                    return Ok(());
+                }
+                Err(AlertStreamError::Transient(reason)) => eprintln!("{}: {}", "Alert stream disconnected".yellow(), reason),
+            }
+            eprintln!("{}", format!("Reconnecting in {}s...", backoff.as_secs()).yellow());
+
+            tokio::select! {
+                _ = &mut ctrl_c => break,
+                _ = tokio::time::sleep(backoff) => {}
+            }
+            backoff = (backoff * 2).min(ALERT_STREAM_MAX_BACKOFF);
+        }
+
+        eprintln!("{}", "Stopped streaming alerts".blue());
+        Ok(())
+    }
+
+    /// Read one connection's worth of alerts. Returns `Ok` when the server ends the stream.
+    async fn consume_alert_stream(
+        &self,
+        url: &str,
+        last_event_id: &mut Option<String>,
+        backoff: &mut std::time::Duration,
+    ) -> Result<(), AlertStreamError> {
+        let mut request = self.client.get(url).header("Accept", "text/event-stream");
+        if let Some(id) = last_event_id.as_deref() {
+            request = request.header("Last-Event-ID", id);
+        }
+
+        let mut response = request.send().await.map_err(|e| AlertStreamError::Transient(e.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            let reason = format!("HTTP {}", status);
+            // Client errors won't fix themselves, except timeouts and rate limiting
+            return Err(if status.is_client_error() && status.as_u16() != 408 && status.as_u16() != 429 {
+                AlertStreamError::Fatal(reason)
+            } else {
+                AlertStreamError::Transient(reason)
+            });
+        }
+        *backoff = ALERT_STREAM_INITIAL_BACKOFF;
+
+        let mut parser = SseParser::default();
+        while let Some(chunk) = response.chunk().await.map_err(|e| AlertStreamError::Transient(e.to_string()))? {
+            for event in parser.feed(&chunk) {
+                if event.id.is_some() {
+                    *last_event_id = event.id.clone();
+                }
+                self.print_alert(&event.data);
+            }
+        }
+        Ok(())
+    }
+
+    fn print_alert(&self, data: &str) {
+        let alert: Value = serde_json::from_str(data).unwrap_or_else(|_| json!({ "message": data }));
+
+        if self.config.output_format == "json" {
+            println!("{}", alert);
+            return;
+        }
+
+        let severity = alert["severity"].as_str().unwrap_or("Info");
+        let timestamp = alert["timestamp"].as_str().map(str::to_string).unwrap_or_else(|| Utc::now().to_rfc3339());
+        let framework = alert["framework"].as_str().or_else(|| alert["regulation"].as_str()).unwrap_or("-");
+        let message = alert["message"].as_str().or_else(|| alert["description"].as_str()).unwrap_or("");
+
+        print!("[{}] {} {}: {}", timestamp, self.colorize_severity(severity), framework, message);
+        match alert["entity_id"].as_str() {
+            Some(entity) => println!(" (entity: {})", entity),
+            None => println!(),
+        }
+    }
+
     async fn generate_compliance_report(&self, matches: &ArgMatches<'_>) -> Result<(), Box<dyn std::error::Error>> {
         let entity = matches.value_of("entity");
         let format = matches.value_of("format").unwrap_or("pdf");
@@ -914,11 +1160,12 @@ impl AionCli {
         pb.set_message("Generating report...");
         pb.enable_steady_tick(100);
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/compliance/report", self.base_url))
-            .json(&report_request)
-            .send()
-            .await?;
+            .json(&report_request);
+
+        // Each call creates a report, so a retry could create a second one
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         pb.finish_with_message("Report generated");
 
@@ -933,60 +1180,72 @@ impl AionCli {
                 println!("{}", format!("Report saved to: {}", output_path).green());
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to generate report".red(), error_text);
+            eprintln!("{}: {}", "Failed to generate report".red(), describe_error_response(response).await);
         }
 
         Ok(())
     }
 
     async fn list_violations(&self, matches: &ArgMatches<'_>) -> Result<(), Box<dyn std::error::Error>> {
-        let min_severity = matches.value_of("severity");
+        let scripted_output = self.config.output_format != "table";
+        let limit: usize = matches.value_of("limit").unwrap_or("50").parse()?;
+        let offset: usize = matches.value_of("offset").unwrap_or("0").parse()?;
 
-        let mut url = format!("{}/api/v1/compliance/violations", self.base_url);
-        if let Some(severity) = min_severity {
-            url.push_str(&format!("?min_severity={}", severity));
+        let mut query: Vec<(&str, String)> = Vec::new();
+        if let Some(severity) = matches.value_of("severity") {
+            query.push(("min_severity", severity.to_string()));
+        }
+        if let Some(entity) = matches.value_of("entity") {
+            query.push(("entity_id", entity.to_string()));
+        }
+        if let Some(framework) = matches.value_of("framework") {
+            query.push(("framework", framework.to_string()));
+        }
+        if let Some(since) = matches.value_of("since") {
+            query.push(("since", parse_since(since)?.to_rfc3339()));
+        }
+        // Non-table output is for scripting, so it returns every matching violation
+        if !scripted_output {
+            query.push(("limit", limit.to_string()));
+            query.push(("offset", offset.to_string()));
         }
 
-        let response = self.client.get(&url).send().await?;
+        let url = format!("{}/api/v1/compliance/violations", self.base_url);
+        let response = self.send_with_retry(self.client.get(&url).query(&query), Retry::Idempotent).await?;
 
         if response.status().is_success() {
-            let violations: Value = response.json().await?;
+            let body: Value = response.json().await?;
+            // Paginated responses wrap the page as {"violations": [...], "total": n}
+            let violations = body.get("violations").unwrap_or(&body);
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&violations)?),
-                _ => {
-                    if let Some(violations_array) = violations.as_array() {
-                        if violations_array.is_empty() {
-                            println!("{}", "No violations found".green());
-                        } else {
-                            println!("\n{}", "Compliance Violations".bold().red());
-                            for (i, violation) in violations_array.iter().enumerate() {
-                                let severity = violation["severity"].as_str().unwrap_or("Unknown");
-                                let severity_colored = match severity {
-                                    "Critical" => severity.red(),
-                                    "High" => severity.red(),
-                                    "Medium" => severity.yellow(),
-                                    "Low" => severity.blue(),
-                                    _ => severity.normal(),
-                                };
-
-                                println!("{}. {} [{}]",
-                                    i + 1,
-                                    violation["description"].as_str().unwrap_or("Unknown"),
-                                    severity_colored
-                                );
-                                println!("   Entity: {}", violation["entity_id"].as_str().unwrap_or("Unknown"));
-                                println!("   Framework: {}", violation["framework"].as_str().unwrap_or("Unknown"));
-                                println!();
-                            }
-                        }
-                    }
+            if let Some(rendered) = render_output(violations, &self.config.output_format)? {
+                println!("{}", rendered);
+            } else if let Some(violations_array) = violations.as_array() {
+                let total = body["total"].as_u64().map(|t| t as usize).unwrap_or(offset + violations_array.len());
+
+                if violations_array.is_empty() {
+                    println!("{}", "No violations found".green());
+                } else {
+                    println!("\n{}", "Compliance Violations".bold().red());
+
+                    let table_data: Vec<ViolationResult> = violations_array
+                        .iter()
+                        .enumerate()
+                        .map(|(i, violation)| ViolationResult {
+                            index: offset + i + 1,
+                            severity: self.colorize_severity(violation["severity"].as_str().unwrap_or("Unknown")).to_string(),
+                            entity: violation["entity_id"].as_str().unwrap_or("Unknown").to_string(),
+                            framework: violation["framework"].as_str().unwrap_or("Unknown").to_string(),
+                            description: truncate_string(violation["description"].as_str().unwrap_or("Unknown"), 60),
+                        })
+                        .collect();
+
+                    println!("{}", Table::new(&table_data));
                 }
+                println!("{}", page_footer(offset, violations_array.len(), total, limit).dimmed());
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to list violations".red(), error_text);
+            eprintln!("{}: {}", "Failed to list violations".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1030,20 +1289,20 @@ impl AionCli {
         pb.set_message("Analyzing conflicts...");
         pb.enable_steady_tick(100);
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/conflicts/detect", self.base_url))
-            .json(&conflict_request)
-            .send()
-            .await?;
+            .json(&conflict_request);
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         pb.finish_with_message("Conflict analysis completed");
 
         if response.status().is_success() {
             let result: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&result)?),
-                _ => {
+            match render_output(&result, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", "Conflict Detection Results".bold().blue());
 
                     if let Some(conflicts) = result["conflicts"].as_array() {
@@ -1058,7 +1317,7 @@ impl AionCli {
                                     id: format!("C{:03}", i + 1),
                                     rule1: conflict["rule1"]["text"].as_str().unwrap_or("").chars().take(50).collect::<String>() + "...",
                                     rule2: conflict["rule2"]["text"].as_str().unwrap_or("").chars().take(50).collect::<String>() + "...",
-                                    severity: self.colorize_severity(conflict["severity"].as_str().unwrap_or("")),
+                                    severity: self.colorize_severity(conflict["severity"].as_str().unwrap_or("")).to_string(),
                                     resolution_status: conflict["resolution_status"].as_str().unwrap_or("Pending").to_string(),
                                 });
                             }
@@ -1069,8 +1328,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to detect conflicts".red(), error_text);
+            eprintln!("{}: {}", "Failed to detect conflicts".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1097,11 +1355,11 @@ impl AionCli {
         pb.set_message("Resolving conflict...");
         pb.enable_steady_tick(100);
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/conflicts/{}/resolve", self.base_url, conflict_id))
-            .json(&resolution_request)
-            .send()
-            .await?;
+            .json(&resolution_request);
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         pb.finish_with_message("Conflict resolution completed");
 
@@ -1123,8 +1381,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to resolve conflict".red(), error_text);
+            eprintln!("{}: {}", "Failed to resolve conflict".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1143,18 +1400,18 @@ impl AionCli {
             println!("{}", format!("Analyzing conflict patterns for timeframe: {}", timeframe).blue());
         }
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/conflicts/analyze", self.base_url))
-            .json(&analysis_request)
-            .send()
-            .await?;
+            .json(&analysis_request);
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         if response.status().is_success() {
             let result: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&result)?),
-                _ => {
+            match render_output(&result, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", "Conflict Pattern Analysis".bold().blue());
                     println!("Timeframe: {}", timeframe);
                     println!("Total Conflicts: {}", result["total_conflicts"].as_u64().unwrap_or(0));
@@ -1174,8 +1431,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to analyze conflicts".red(), error_text);
+            eprintln!("{}: {}", "Failed to analyze conflicts".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1201,11 +1457,11 @@ impl AionCli {
         pb.set_message("Generating graph...");
         pb.enable_steady_tick(100);
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/conflicts/graph", self.base_url))
-            .json(&graph_request)
-            .send()
-            .await?;
+            .json(&graph_request);
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         pb.finish_with_message("Graph generation completed");
 
@@ -1214,8 +1470,7 @@ impl AionCli {
             std::fs::write(output_file, graph_data)?;
             println!("{}", format!("Conflict graph saved to: {}", output_file).green());
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to generate graph".red(), error_text);
+            eprintln!("{}: {}", "Failed to generate graph".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1263,11 +1518,11 @@ impl AionCli {
             .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} epochs ({eta})")
             .progress_chars("#>-"));
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/ml/train", self.base_url))
-            .json(&training_request)
-            .send()
-            .await?;
+            .json(&training_request);
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         pb.finish_with_message("Training completed");
 
@@ -1278,8 +1533,7 @@ impl AionCli {
             println!("Final Accuracy: {:.1}%", result["accuracy"].as_f64().unwrap_or(0.0) * 100.0);
             println!("Training Time: {:.1}s", result["training_time_seconds"].as_f64().unwrap_or(0.0));
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to train model".red(), error_text);
+            eprintln!("{}: {}", "Failed to train model".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1299,18 +1553,18 @@ impl AionCli {
             println!("{}", format!("Making prediction using model: {}", model_name).blue());
         }
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/ml/predict", self.base_url))
-            .json(&prediction_request)
-            .send()
-            .await?;
+            .json(&prediction_request);
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         if response.status().is_success() {
             let result: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&result)?),
-                _ => {
+            match render_output(&result, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", "Prediction Results".bold().blue());
                     println!("Model: {}", model_name);
                     println!("Input: {}", input_text);
@@ -1326,8 +1580,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to make prediction".red(), error_text);
+            eprintln!("{}: {}", "Failed to make prediction".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1347,18 +1600,18 @@ impl AionCli {
             println!("{}", format!("Analyzing text using {} analysis", analysis_type).blue());
         }
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/nlp/analyze", self.base_url))
-            .json(&analysis_request)
-            .send()
-            .await?;
+            .json(&analysis_request);
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         if response.status().is_success() {
             let result: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&result)?),
-                _ => {
+            match render_output(&result, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", "Text Analysis Results".bold().blue());
                     println!("Text: {}", text);
                     println!("Analysis Type: {}", analysis_type);
@@ -1394,8 +1647,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to analyze text".red(), error_text);
+            eprintln!("{}: {}", "Failed to analyze text".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1404,17 +1656,17 @@ impl AionCli {
     async fn list_models(&self, matches: &ArgMatches<'_>) -> Result<(), Box<dyn std::error::Error>> {
         let show_status = matches.is_present("status");
 
-        let response = self.client
-            .get(&format!("{}/api/v1/ml/models", self.base_url))
-            .send()
-            .await?;
+        let request = self.client
+            .get(&format!("{}/api/v1/ml/models", self.base_url));
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         if response.status().is_success() {
             let models: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&models)?),
-                _ => {
+            match render_output(&models, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", "Available ML Models".bold().blue());
 
                     if let Some(models_array) = models.as_array() {
@@ -1433,8 +1685,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to list models".red(), error_text);
+            eprintln!("{}: {}", "Failed to list models".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1477,17 +1728,17 @@ impl AionCli {
     async fn show_metrics(&self, matches: &ArgMatches<'_>) -> Result<(), Box<dyn std::error::Error>> {
         let timeframe = matches.value_of("timeframe").unwrap_or("1h");
 
-        let response = self.client
-            .get(&format!("{}/api/v1/monitoring/metrics?timeframe={}", self.base_url, timeframe))
-            .send()
-            .await?;
+        let request = self.client
+            .get(&format!("{}/api/v1/monitoring/metrics?timeframe={}", self.base_url, timeframe));
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         if response.status().is_success() {
             let metrics: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&metrics)?),
-                _ => {
+            match render_output(&metrics, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", format!("System Metrics ({})", timeframe).bold().blue());
 
                     if let Some(system) = metrics["system"].as_object() {
@@ -1508,8 +1759,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to get metrics".red(), error_text);
+            eprintln!("{}: {}", "Failed to get metrics".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1529,7 +1779,7 @@ impl AionCli {
             println!("{}", format!("Fetching {} logs (last {} lines)", level, lines).blue());
         }
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_with_retry(self.client.get(&url), Retry::Idempotent).await?;
 
         if response.status().is_success() {
             if follow {
@@ -1540,8 +1790,7 @@ impl AionCli {
             let logs_text = response.text().await?;
             println!("{}", logs_text);
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to get logs".red(), error_text);
+            eprintln!("{}: {}", "Failed to get logs".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1552,17 +1801,17 @@ impl AionCli {
             println!("{}", "Checking system health...".blue());
         }
 
-        let response = self.client
-            .get(&format!("{}/health", self.base_url))
-            .send()
-            .await?;
+        let request = self.client
+            .get(&format!("{}/health", self.base_url));
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         if response.status().is_success() {
             let health: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&health)?),
-                _ => {
+            match render_output(&health, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", "System Health Check".bold().blue());
 
                     let status = health["status"].as_str().unwrap_or("unknown");
@@ -1626,6 +1875,11 @@ impl AionCli {
             "monitoring": true
         });
 
+        let url = format!("{}/api/v1/deploy/start", self.base_url);
+        if self.print_dry_run("POST", &url, &deployment_request)? {
+            return Ok(());
+        }
+
         if self.config.verbose {
             println!("{}", format!("Starting {} deployment with {} replicas", environment, scale).blue());
         }
@@ -1641,11 +1895,11 @@ impl AionCli {
         pb.set_message("Deploying AION-CR...");
         pb.enable_steady_tick(100);
 
-        let response = self.client
-            .post(&format!("{}/api/v1/deploy/start", self.base_url))
-            .json(&deployment_request)
-            .send()
-            .await?;
+        let request = self.client
+            .post(&url)
+            .json(&deployment_request);
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         pb.finish_with_message("Deployment initiated");
 
@@ -1660,8 +1914,7 @@ impl AionCli {
                 println!("{}", "🤖 Autonomous mode: ENABLED".green().bold());
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to start deployment".red(), error_text);
+            eprintln!("{}: {}", "Failed to start deployment".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1690,18 +1943,17 @@ impl AionCli {
         pb.set_message("Stopping deployment...");
         pb.enable_steady_tick(100);
 
-        let response = self.client
-            .post(&format!("{}/api/v1/deploy/stop", self.base_url))
-            .send()
-            .await?;
+        let request = self.client
+            .post(&format!("{}/api/v1/deploy/stop", self.base_url));
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         pb.finish_with_message("Deployment stopped");
 
         if response.status().is_success() {
             println!("{}", "Deployment stopped successfully".green());
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to stop deployment".red(), error_text);
+            eprintln!("{}: {}", "Failed to stop deployment".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1720,11 +1972,11 @@ impl AionCli {
             println!("{}", format!("Scaling deployment to {} replicas", replicas).blue());
         }
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/deploy/scale", self.base_url))
-            .json(&scale_request)
-            .send()
-            .await?;
+            .json(&scale_request);
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         if response.status().is_success() {
             println!("{}", format!("Deployment scaled to {} replicas", replicas).green());
@@ -1733,8 +1985,7 @@ impl AionCli {
                 println!("{}", "Auto-scaling enabled".blue());
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to scale deployment".red(), error_text);
+            eprintln!("{}: {}", "Failed to scale deployment".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1760,36 +2011,34 @@ impl AionCli {
         pb.set_message("Updating deployment...");
         pb.enable_steady_tick(100);
 
-        let response = self.client
+        let request = self.client
             .post(&format!("{}/api/v1/deploy/update", self.base_url))
-            .json(&update_request)
-            .send()
-            .await?;
+            .json(&update_request);
+
+        let response = self.send_with_retry(request, Retry::Never).await?;
 
         pb.finish_with_message("Update completed");
 
         if response.status().is_success() {
             println!("{}", "Deployment updated successfully".green());
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to update deployment".red(), error_text);
+            eprintln!("{}: {}", "Failed to update deployment".red(), describe_error_response(response).await);
         }
 
         Ok(())
     }
 
     async fn deployment_status(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let response = self.client
-            .get(&format!("{}/api/v1/deploy/status", self.base_url))
-            .send()
-            .await?;
+        let request = self.client
+            .get(&format!("{}/api/v1/deploy/status", self.base_url));
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         if response.status().is_success() {
             let status: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&status)?),
-                _ => {
+            match render_output(&status, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", "Deployment Status".bold().blue());
                     println!("Environment: {}", status["environment"].as_str().unwrap_or("Unknown"));
                     println!("Status: {}", self.colorize_status(status["status"].as_str().unwrap_or("")));
@@ -1809,8 +2058,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to get deployment status".red(), error_text);
+            eprintln!("{}: {}", "Failed to get deployment status".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -1830,25 +2078,22 @@ impl AionCli {
     }
 
     async fn show_config(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let response = self.client
-            .get(&format!("{}/api/v1/config", self.base_url))
-            .send()
-            .await?;
-
-        if response.status().is_success() {
-            let config: Value = response.json().await?;
+        let path = settings::config_path();
+        let config = json!({
+            "config_file": path.as_ref().map(|path| path.display().to_string()),
+            "server": self.base_url,
+            "output_format": self.config.output_format,
+            "verbose": self.config.verbose,
+            "color": self.config.color,
+            "max_retries": self.config.max_retries,
+        });
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&config)?),
-                "yaml" => println!("{}", serde_yaml::to_string(&config)?),
-                _ => {
-                    println!("\n{}", "AION-CR Configuration".bold().blue());
-                    self.print_config_tree(&config, 0);
-                }
+        match render_output(&config, &self.config.output_format)? {
+            Some(rendered) => println!("{}", rendered),
+            None => {
+                println!("\n{}", "AION-CLI Configuration".bold().blue());
+                self.print_config_tree(&config, 0);
             }
-        } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to get configuration".red(), error_text);
         }
 
         Ok(())
@@ -1891,54 +2136,39 @@ impl AionCli {
     async fn set_config(&self, matches: &ArgMatches<'_>) -> Result<(), Box<dyn std::error::Error>> {
         let key = matches.value_of("key").unwrap();
         let value = matches.value_of("value").unwrap();
+        let path = settings::config_path().ok_or("Cannot locate a home directory; set AION_CONFIG")?;
 
-        let config_request = json!({
-            "key": key,
-            "value": value
-        });
-
-        if self.config.verbose {
-            println!("{}", format!("Setting configuration: {} = {}", key, value).blue());
+        let (mut file_config, problems) = FileConfig::load_repairable(&path)?;
+        for problem in &problems {
+            eprintln!("{}: dropping invalid value from {}: {}", "Warning".yellow(), path.display(), problem);
         }
+        file_config.set(key, value)?;
+        file_config.save(&path)?;
 
-        let response = self.client
-            .post(&format!("{}/api/v1/config/set", self.base_url))
-            .json(&config_request)
-            .send()
-            .await?;
-
-        if response.status().is_success() {
-            println!("{}", format!("Configuration updated: {} = {}", key, value).green());
-        } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to set configuration".red(), error_text);
+        if self.config.verbose {
+            println!("{}", format!("Wrote {}", path.display()).blue());
         }
-
+        println!("{}", format!("Configuration updated: {} = {}", key, value).green());
         Ok(())
     }
 
     async fn get_config(&self, matches: &ArgMatches<'_>) -> Result<(), Box<dyn std::error::Error>> {
         let key = matches.value_of("key").unwrap();
+        let file_config = match settings::config_path() {
+            Some(path) => FileConfig::load(&path)?,
+            None => FileConfig::default(),
+        };
 
-        let response = self.client
-            .get(&format!("{}/api/v1/config/get?key={}", self.base_url, key))
-            .send()
-            .await?;
-
-        if response.status().is_success() {
-            let result: Value = response.json().await?;
-            println!("{}: {}", key.blue(), result["value"].as_str().unwrap_or("null"));
-        } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to get configuration".red(), error_text);
+        match file_config.get(key)? {
+            Some(value) => println!("{}: {}", key.blue(), value),
+            None => println!("{}: {}", key.blue(), "(not set)".dimmed()),
         }
-
         Ok(())
     }
 
     async fn reset_config(&self) -> Result<(), Box<dyn std::error::Error>> {
         if !self.config.auto_confirm {
-            print!("Are you sure you want to reset configuration to defaults? [y/N]: ");
+            print!("Are you sure you want to delete the local configuration file? [y/N]: ");
             io::stdout().flush()?;
             let mut input = String::new();
             io::stdin().read_line(&mut input)?;
@@ -1948,22 +2178,13 @@ impl AionCli {
             }
         }
 
-        if self.config.verbose {
-            println!("{}", "Resetting configuration to defaults...".blue());
-        }
-
-        let response = self.client
-            .post(&format!("{}/api/v1/config/reset", self.base_url))
-            .send()
-            .await?;
+        let path = settings::config_path().ok_or("Cannot locate a home directory; set AION_CONFIG")?;
+        FileConfig::remove(&path)?;
 
-        if response.status().is_success() {
-            println!("{}", "Configuration reset to defaults".green());
-        } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to reset configuration".red(), error_text);
+        if self.config.verbose {
+            println!("{}", format!("Removed {}", path.display()).blue());
         }
-
+        println!("{}", "Configuration reset to defaults".green());
         Ok(())
     }
 
@@ -1972,17 +2193,17 @@ impl AionCli {
             println!("{}", "Fetching system status...".blue());
         }
 
-        let response = self.client
-            .get(&format!("{}/api/v1/status", self.base_url))
-            .send()
-            .await?;
+        let request = self.client
+            .get(&format!("{}/api/v1/status", self.base_url));
+
+        let response = self.send_with_retry(request, Retry::Idempotent).await?;
 
         if response.status().is_success() {
             let status: Value = response.json().await?;
 
-            match self.config.output_format.as_str() {
-                "json" => println!("{}", serde_json::to_string_pretty(&status)?),
-                _ => {
+            match render_output(&status, &self.config.output_format)? {
+                Some(rendered) => println!("{}", rendered),
+                None => {
                     println!("\n{}", "AION-CR System Status".bold().blue());
 
                     let system_status = status["status"].as_str().unwrap_or("unknown");
@@ -2016,8 +2237,7 @@ impl AionCli {
                 }
             }
         } else {
-            let error_text = response.text().await?;
-            eprintln!("{}: {}", "Failed to get system status".red(), error_text);
+            eprintln!("{}: {}", "Failed to get system status".red(), describe_error_response(response).await);
         }
 
         Ok(())
@@ -2108,4 +2328,618 @@ impl AionCli {
             _ => severity.normal(),
         }
     }
-}
\ No newline at end of file
+}
+
+/// Headers proxies and the API use to identify a request; the API's
+/// `x-correlation-id` comes first as it is also in the server's access log
+const REQUEST_ID_HEADERS: &[&str] = &["x-correlation-id", "x-request-id", "request-id"];
+/// Longest plain-text error body shown
+const ERROR_BODY_MAX_CHARS: usize = 200;
+
+/// Describe a failed response for the user: HTTP status, request id, and the
+/// server's message, whether structured JSON or plain text/HTML
+async fn describe_error_response(response: reqwest::Response) -> String {
+    let status = response.status();
+    let request_id = REQUEST_ID_HEADERS
+        .iter()
+        .find_map(|header| response.headers().get(*header))
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response.text().await.unwrap_or_default();
+    format_error_body(status, request_id.as_deref(), &body)
+}
+
+fn format_error_body(status: reqwest::StatusCode, request_id: Option<&str>, body: &str) -> String {
+    let mut summary = format!("HTTP {}", status);
+    if let Some(id) = request_id {
+        summary.push_str(&format!(" (request id {})", id));
+    }
+
+    let detail = structured_error(body).unwrap_or_else(|| plain_error_text(body));
+    if !detail.is_empty() {
+        summary.push_str(": ");
+        summary.push_str(&detail);
+    }
+    summary
+}
+
+/// `{error, message, code}`, where `error` may itself be such an object
+fn structured_error(body: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(body).ok()?;
+    let error = match &value["error"] {
+        nested @ Value::Object(_) => nested,
+        _ => &value,
+    };
+
+    let message = error["message"].as_str().or_else(|| value["error"].as_str())?;
+    let mut detail = message.to_string();
+    if let Some(kind) = value["error"].as_str().filter(|kind| *kind != message) {
+        detail = format!("{}: {}", kind, detail);
+    }
+    match &error["code"] {
+        Value::String(code) => detail.push_str(&format!(" [{}]", code)),
+        Value::Number(code) => detail.push_str(&format!(" [{}]", code)),
+        _ => {}
+    }
+    Some(detail)
+}
+
+/// Text of a non-JSON body: an HTML page's title or its tag-stripped text, collapsed and truncated
+fn plain_error_text(body: &str) -> String {
+    let lower = body.to_lowercase();
+    let text = if lower.contains("<html") || lower.contains("<!doctype") {
+        // Lowercasing can shift byte offsets for some scripts, so slice defensively
+        match (lower.find("<title>"), lower.find("</title>")) {
+            (Some(start), Some(end)) => body.get(start + 7..end).map(str::to_string).unwrap_or_else(|| strip_tags(body)),
+            _ => strip_tags(body),
+        }
+    } else {
+        body.to_string()
+    };
+    truncate_string(&text.split_whitespace().collect::<Vec<_>>().join(" "), ERROR_BODY_MAX_CHARS)
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Whether a request may be sent again after a transient failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Retry {
+    /// GETs and read-only POSTs, which are safe to repeat
+    Idempotent,
+    /// Requests that change server state; repeating one could apply it twice
+    Never,
+}
+
+const RETRY_BASE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+const RETRY_MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(30);
+/// Longest `Retry-After` honoured, so a misbehaving server can't stall the CLI indefinitely
+const RETRY_AFTER_MAX: std::time::Duration = std::time::Duration::from_secs(120);
+
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Exponential backoff with equal jitter: half the delay is fixed, half random
+fn backoff_delay(attempt: u32) -> std::time::Duration {
+    let exponential = RETRY_BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt)).min(RETRY_MAX_DELAY);
+    let half = exponential / 2;
+    half + half.mul_f64(rand::Rng::gen_range(&mut rand::thread_rng(), 0.0..=1.0))
+}
+
+/// `Retry-After` is either delay seconds or an HTTP date
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<std::time::Duration> {
+    let delay = match value.trim().parse::<u64>() {
+        Ok(seconds) => std::time::Duration::from_secs(seconds),
+        Err(_) => {
+            let at = DateTime::parse_from_rfc2822(value.trim()).ok()?.with_timezone(&Utc);
+            (at - now).to_std().unwrap_or_default()
+        }
+    };
+    Some(delay.min(RETRY_AFTER_MAX))
+}
+
+/// Render `value` in a machine-readable output format. Returns `None` for
+/// "table", which each command draws itself.
+fn render_output(value: &Value, format: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    match format {
+        "json" => Ok(Some(serde_json::to_string_pretty(value)?)),
+        "yaml" => Ok(Some(serde_yaml::to_string(value)?.trim_end().to_string())),
+        "csv" => Ok(Some(render_csv(value))),
+        "table" => Ok(None),
+        other => Err(format!("Unsupported output format: {}", other).into()),
+    }
+}
+
+/// One row per element of a top-level array, otherwise a single row. Nested
+/// objects and arrays become dotted columns, e.g. `metrics.accuracy` or `tags.0`.
+fn render_csv(value: &Value) -> String {
+    let rows: Vec<Vec<(String, String)>> = match value {
+        Value::Array(items) => items.iter().map(|item| flatten_value("", item)).collect(),
+        other => vec![flatten_value("", other)],
+    };
+
+    let mut columns: Vec<String> = Vec::new();
+    for (column, _) in rows.iter().flatten() {
+        if !columns.contains(column) {
+            columns.push(column.clone());
+        }
+    }
+
+    let mut output = columns.iter().map(|c| csv_field(c)).collect::<Vec<_>>().join(",");
+    for row in &rows {
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                row.iter()
+                    .find(|(key, _)| key == column)
+                    .map(|(_, value)| csv_field(value))
+                    .unwrap_or_default()
+            })
+            .collect();
+        output.push('\n');
+        output.push_str(&fields.join(","));
+    }
+    output
+}
+
+fn flatten_value(prefix: &str, value: &Value) -> Vec<(String, String)> {
+    let key = |child: &str| if prefix.is_empty() { child.to_string() } else { format!("{}.{}", prefix, child) };
+    match value {
+        Value::Object(map) => map.iter().flat_map(|(k, v)| flatten_value(&key(k), v)).collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .flat_map(|(i, v)| flatten_value(&key(&i.to_string()), v))
+            .collect(),
+        Value::String(text) => vec![(prefix_or_value(prefix), text.clone())],
+        Value::Null => vec![(prefix_or_value(prefix), String::new())],
+        other => vec![(prefix_or_value(prefix), other.to_string())],
+    }
+}
+
+/// A bare scalar at the top level still needs a column name
+fn prefix_or_value(prefix: &str) -> String {
+    if prefix.is_empty() { "value".to_string() } else { prefix.to_string() }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Commands that honour `--dry-run` by printing their request
+const DRY_RUN_COMMANDS: &[&str] = &["agents create", "deploy start", "compliance monitor"];
+
+/// Commands that change nothing on the server, so `--dry-run` is only warned about
+const READ_ONLY_COMMANDS: &[&str] = &[
+    "agents list",
+    "agents status",
+    "compliance assess",
+    "compliance report",
+    "compliance violations",
+    "conflicts detect",
+    "conflicts analyze",
+    "conflicts graph",
+    "ml predict",
+    "ml analyze",
+    "ml models",
+    "monitor start",
+    "monitor metrics",
+    "monitor logs",
+    "monitor health",
+    "deploy status",
+    "config show",
+    "config get",
+    "status",
+    "interactive",
+];
+
+/// Subcommand names joined by spaces, e.g. "agents create"
+fn command_path(matches: &ArgMatches<'_>) -> String {
+    let mut path = Vec::new();
+    let mut current = matches;
+    while let (name, Some(sub_matches)) = current.subcommand() {
+        path.push(name);
+        current = sub_matches;
+    }
+    if let (name, None) = current.subcommand() {
+        if !name.is_empty() {
+            path.push(name);
+        }
+    }
+    path.join(" ")
+}
+
+const ALERT_STREAM_INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_secs(1);
+const ALERT_STREAM_MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(60);
+
+enum AlertStreamError {
+    /// Worth reconnecting: network errors, server errors, timeouts
+    Transient(String),
+    /// Reconnecting would fail the same way, e.g. an unknown monitor
+    Fatal(String),
+}
+
+#[derive(Debug, PartialEq)]
+struct SseEvent {
+    id: Option<String>,
+    data: String,
+}
+
+/// Incremental parser for `text/event-stream` bodies. Chunks may split lines,
+/// or UTF-8 characters, anywhere.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    data: Vec<String>,
+    id: Option<String>,
+}
+
+impl SseParser {
+    fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            if line.is_empty() {
+                // A blank line dispatches the event; comment-only keep-alives carry no data
+                if !self.data.is_empty() {
+                    events.push(SseEvent { id: self.id.take(), data: self.data.join("\n") });
+                    self.data.clear();
+                }
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "data" => self.data.push(value.to_string()),
+                "id" => self.id = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        events
+    }
+}
+
+/// Accept a plain date (midnight UTC) or a full RFC 3339 timestamp
+fn parse_since(value: &str) -> Result<DateTime<Utc>, Box<dyn std::error::Error>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid --since value '{}': expected YYYY-MM-DD or RFC 3339", value))?;
+    Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+}
+
+fn page_footer(offset: usize, shown: usize, total: usize, limit: usize) -> String {
+    if shown == 0 {
+        return format!("Offset {} of {} violations", offset, total);
+    }
+    let mut footer = format!("Showing {}-{} of {} violations", offset + 1, offset + shown, total);
+    if offset + shown < total {
+        footer.push_str(&format!(" (next page: --offset {} --limit {})", offset + shown, limit));
+    }
+    footer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_response() -> Value {
+        json!({
+            "status": "healthy",
+            "uptime": 3600,
+            "components": { "api": "healthy", "database": "degraded" },
+            "frameworks": ["GDPR", "SOX, 2002"],
+            "last_error": null
+        })
+    }
+
+    #[test]
+    fn test_flags_override_the_config_file() {
+        let file_config = FileConfig {
+            server: Some("https://aion.example.com".to_string()),
+            output_format: Some("yaml".to_string()),
+            verbose: Some(true),
+            color: Some(false),
+            max_retries: Some(5),
+        };
+
+        let matches = create_cli_app().get_matches_from(vec!["aion-cli", "status"]);
+        let (server, config) = resolve_config(&matches, &file_config);
+        assert_eq!(server, "https://aion.example.com");
+        assert_eq!(config.output_format, "yaml");
+        assert!(config.verbose);
+        assert!(!config.color);
+        assert_eq!(config.max_retries, 5);
+
+        let matches = create_cli_app().get_matches_from(vec![
+            "aion-cli", "--server", "http://localhost:9000/", "--format", "json",
+            "--no-verbose", "--color", "--retries", "0", "status",
+        ]);
+        let (server, config) = resolve_config(&matches, &file_config);
+        assert_eq!(server, "http://localhost:9000");
+        assert_eq!(config.output_format, "json");
+        assert!(!config.verbose);
+        assert!(config.color);
+        assert_eq!(config.max_retries, 0);
+
+        assert!(create_cli_app().get_matches_from_safe(vec!["aion-cli", "--retries", "many", "status"]).is_err());
+    }
+
+    #[test]
+    fn test_status_renders_in_every_format() {
+        let status = status_response();
+
+        let json_output = render_output(&status, "json").unwrap().unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&json_output).unwrap(), status);
+
+        let yaml_output = render_output(&status, "yaml").unwrap().unwrap();
+        assert_eq!(serde_yaml::from_str::<Value>(&yaml_output).unwrap(), status);
+
+        let csv_output = render_output(&status, "csv").unwrap().unwrap();
+        let mut lines = csv_output.lines();
+        assert_eq!(lines.next(), Some("components.api,components.database,frameworks.0,frameworks.1,last_error,status,uptime"));
+        assert_eq!(lines.next(), Some("healthy,degraded,GDPR,\"SOX, 2002\",,healthy,3600"));
+        assert_eq!(lines.next(), None);
+
+        assert!(render_output(&status, "table").unwrap().is_none());
+        assert!(render_output(&status, "xml").is_err());
+    }
+
+    #[test]
+    fn test_retry_after_and_backoff() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(parse_retry_after("5", now), Some(std::time::Duration::from_secs(5)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now), Some(std::time::Duration::from_secs(30)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now), Some(std::time::Duration::ZERO));
+        assert_eq!(parse_retry_after("86400", now), Some(RETRY_AFTER_MAX));
+        assert_eq!(parse_retry_after("soon", now), None);
+
+        for attempt in 0..20 {
+            let delay = backoff_delay(attempt);
+            let ceiling = RETRY_BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt)).min(RETRY_MAX_DELAY);
+            assert!(delay >= ceiling / 2 && delay <= ceiling);
+        }
+
+        assert!(is_retryable_status(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_retryable_status(reqwest::StatusCode::NOT_FOUND));
+    }
+
+    /// Serve `statuses` in order, repeating the last one, and count the requests received
+    async fn status_server(statuses: Vec<u16>) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let received = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let counter = received.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let index = counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let status = statuses[index.min(statuses.len() - 1)];
+                let mut buffer = [0u8; 4096];
+                let _ = socket.read(&mut buffer).await;
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nretry-after: 0\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                    status
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (base_url, received)
+    }
+
+    #[tokio::test]
+    async fn test_retry_loop_honours_max_retries_and_fails_fast_on_client_errors() {
+        let config = CliConfig { max_retries: 2, ..CliConfig::default() };
+        let requests = |cli: &AionCli, base_url: &str| cli.client.get(format!("{}/api/v1/status", base_url));
+
+        // Transient failures are retried until max_retries is spent
+        let (base_url, received) = status_server(vec![503]).await;
+        let cli = AionCli::new(&base_url, config.clone());
+        let response = cli.send_with_retry(requests(&cli, &base_url), Retry::Idempotent).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(received.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        // A success after a transient failure ends the loop
+        let (base_url, received) = status_server(vec![503, 200]).await;
+        let response = cli.send_with_retry(requests(&cli, &base_url), Retry::Idempotent).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(received.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // Client errors are returned at once
+        let (base_url, received) = status_server(vec![404]).await;
+        let response = cli.send_with_retry(requests(&cli, &base_url), Retry::Idempotent).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        assert_eq!(received.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Requests that change state are never repeated
+        let (base_url, received) = status_server(vec![503]).await;
+        let response = cli.send_with_retry(cli.client.post(format!("{}/api/v1/compliance/report", base_url)), Retry::Never).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(received.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_error_bodies_are_summarised() {
+        let json_error = r#"{"error": "validation_failed", "message": "entity_id is required", "code": 4001}"#;
+        assert_eq!(
+            format_error_body(reqwest::StatusCode::BAD_REQUEST, Some("req-42"), json_error),
+            "HTTP 400 Bad Request (request id req-42): validation_failed: entity_id is required [4001]"
+        );
+
+        let nested = r#"{"error": {"message": "agent not found", "code": "AGENT_404"}}"#;
+        assert_eq!(
+            format_error_body(reqwest::StatusCode::NOT_FOUND, None, nested),
+            "HTTP 404 Not Found: agent not found [AGENT_404]"
+        );
+
+        let proxy_page = "<html><head><title>502 Bad Gateway</title></head><body><center><h1>502 Bad Gateway</h1></center><hr><center>nginx</center></body></html>";
+        assert_eq!(
+            format_error_body(reqwest::StatusCode::BAD_GATEWAY, None, proxy_page),
+            "HTTP 502 Bad Gateway: 502 Bad Gateway"
+        );
+
+        let long_text = "é".repeat(500);
+        let summary = format_error_body(reqwest::StatusCode::INTERNAL_SERVER_ERROR, None, &long_text);
+        assert!(summary.ends_with("..."));
+        assert!(summary.chars().count() < 260);
+
+        assert_eq!(format_error_body(reqwest::StatusCode::SERVICE_UNAVAILABLE, None, ""), "HTTP 503 Service Unavailable");
+    }
+
+    #[test]
+    fn test_csv_array_rows_share_columns() {
+        let agents = json!([
+            { "id": "a1", "metrics": { "accuracy": 0.9 } },
+            { "id": "a2", "tags": ["x"] }
+        ]);
+        assert_eq!(render_csv(&agents), "id,metrics.accuracy,tags.0\na1,0.9,\na2,,x");
+    }
+
+    #[test]
+    fn test_parse_since_accepts_dates_and_timestamps() {
+        let expected = DateTime::parse_from_rfc3339("2024-03-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(parse_since("2024-03-01").unwrap(), expected);
+        assert_eq!(parse_since("2024-03-01T02:00:00+02:00").unwrap(), expected);
+
+        for invalid in ["yesterday", "2024-13-01", "01/03/2024", ""] {
+            let error = parse_since(invalid).unwrap_err().to_string();
+            assert!(error.contains("Invalid --since value"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_page_footer_points_at_the_next_page() {
+        assert_eq!(page_footer(0, 20, 45, 20), "Showing 1-20 of 45 violations (next page: --offset 20 --limit 20)");
+        assert_eq!(page_footer(40, 5, 45, 20), "Showing 41-45 of 45 violations");
+        assert_eq!(page_footer(60, 0, 45, 20), "Offset 60 of 45 violations");
+        assert_eq!(page_footer(0, 0, 0, 20), "Offset 0 of 0 violations");
+    }
+
+    #[test]
+    fn test_sse_parser_handles_split_chunks() {
+        let stream = ": keep-alive\r\n\r\nid: 7\r\ndata: {\"message\": \"héllo\"}\r\n\r\ndata: first\ndata:second\nretry: 10\n\n";
+        let expected = vec![
+            SseEvent { id: Some("7".to_string()), data: "{\"message\": \"héllo\"}".to_string() },
+            SseEvent { id: None, data: "first\nsecond".to_string() },
+        ];
+
+        // Split at every byte, including inside the two-byte "é" and between "\r" and "\n"
+        for split in 0..=stream.len() {
+            let (head, tail) = stream.as_bytes().split_at(split);
+            let mut parser = SseParser::default();
+            let mut events = parser.feed(head);
+            events.extend(parser.feed(tail));
+            assert_eq!(events, expected, "split at byte {}", split);
+        }
+
+        // An event is held back until its blank line arrives
+        let mut parser = SseParser::default();
+        assert!(parser.feed(b"data: pending\n").is_empty());
+        assert_eq!(parser.feed(b"\n"), vec![SseEvent { id: None, data: "pending".to_string() }]);
+    }
+
+    #[test]
+    fn test_colored_cells_keep_the_table_aligned() {
+        let rows = vec![
+            ViolationResult {
+                index: 1,
+                severity: "\u{1b}[31mCritical\u{1b}[0m".to_string(),
+                entity: "bank-1".to_string(),
+                framework: "SOX".to_string(),
+                description: "Missing control".to_string(),
+            },
+            ViolationResult {
+                index: 2,
+                severity: "Low".to_string(),
+                entity: "bank-2".to_string(),
+                framework: "GDPR".to_string(),
+                description: "Stale consent".to_string(),
+            },
+        ];
+
+        let strip_ansi = |line: &str| {
+            let mut visible = String::new();
+            let mut chars = line.chars();
+            while let Some(c) = chars.next() {
+                if c == '\u{1b}' {
+                    chars.by_ref().find(|c| *c == 'm');
+                } else {
+                    visible.push(c);
+                }
+            }
+            visible
+        };
+
+        let rendered = Table::new(&rows).to_string();
+        let widths: Vec<usize> = rendered.lines().map(|line| strip_ansi(line).chars().count()).collect();
+        assert!(widths.windows(2).all(|pair| pair[0] == pair[1]), "{}", rendered);
+    }
+
+    #[test]
+    fn test_dry_run_commands_are_classified() {
+        let command = |args: &[&str]| {
+            let mut argv = vec!["aion-cli"];
+            argv.extend_from_slice(args);
+            command_path(&create_cli_app().get_matches_from(argv))
+        };
+
+        assert_eq!(command(&["--dry-run", "agents", "create", "--name", "a", "--type", "ComplianceGovernor"]), "agents create");
+        assert_eq!(command(&["agents", "list"]), "agents list");
+        assert_eq!(command(&["status"]), "status");
+
+        // No command is both dry-runnable and read-only, so each gets exactly one treatment
+        for dry_run in DRY_RUN_COMMANDS {
+            assert!(!READ_ONLY_COMMANDS.contains(dry_run), "{}", dry_run);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_sends_nothing() {
+        let (base_url, received) = status_server(vec![200]).await;
+        let config = CliConfig { dry_run: true, output_format: "json".to_string(), ..CliConfig::default() };
+        let cli = AionCli::new(&base_url, config);
+
+        let matches = create_cli_app().get_matches_from(vec![
+            "aion-cli", "--dry-run", "agents", "create", "--name", "auditor", "--type", "ComplianceGovernor", "--autonomous",
+        ]);
+        let (_, agents) = matches.subcommand();
+        cli.handle_agents_command(agents.unwrap()).await.unwrap();
+
+        assert_eq!(received.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+}
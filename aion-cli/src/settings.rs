@@ -0,0 +1,235 @@
+//! Persistent CLI settings, read from `~/.aion/config.toml` or the file named by `AION_CONFIG`
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the config file location
+pub const CONFIG_ENV: &str = "AION_CONFIG";
+
+pub const OUTPUT_FORMATS: &[&str] = &["table", "json", "yaml", "csv"];
+
+/// Keys accepted by `config get` and `config set`
+pub const KEYS: &[&str] = &["server", "output_format", "verbose", "color", "max_retries"];
+
+/// Values from the config file. Unset values fall back to the built-in defaults,
+/// and command-line flags override both.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbose: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+}
+
+/// `$AION_CONFIG`, else `~/.aion/config.toml`
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_ENV).filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".aion").join("config.toml"))
+}
+
+impl FileConfig {
+    /// Load and validate the config file. A missing file is an empty config.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Cannot read {}: {}", path.display(), e)),
+        };
+
+        let config: Self = toml::from_str(&text).map_err(|e| format!("Invalid config file {}: {}", path.display(), e))?;
+        config
+            .validate()
+            .map_err(|e| format!("Invalid config file {}: {}", path.display(), e))?;
+        Ok(config)
+    }
+
+    /// Load every valid value of the config file, so `config set` can repair a
+    /// file that `load` rejects. Returns the problems with the values left out.
+    /// A file that is not TOML at all still has to be fixed by hand.
+    pub fn load_repairable(path: &Path) -> Result<(Self, Vec<String>), String> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Self::default(), Vec::new())),
+            Err(e) => return Err(format!("Cannot read {}: {}", path.display(), e)),
+        };
+        let table: toml::Table = toml::from_str(&text).map_err(|e| format!("Invalid config file {}: {}", path.display(), e))?;
+
+        let mut config = Self::default();
+        let mut problems = Vec::new();
+        for (key, value) in table {
+            let value = match value {
+                toml::Value::String(value) => value,
+                other => other.to_string(),
+            };
+            if let Err(e) = config.set(&key, &value) {
+                problems.push(e);
+            }
+        }
+        Ok((config, problems))
+    }
+
+    /// Replace the config file atomically: a crash leaves the old file or the new one
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        std::fs::create_dir_all(parent).map_err(|e| format!("Cannot create {}: {}", parent.display(), e))?;
+        let text = toml::to_string_pretty(self).map_err(|e| format!("Cannot serialize config: {}", e))?;
+
+        let staging = path.with_extension("toml.tmp");
+        let write_error = |e: std::io::Error| format!("Cannot write {}: {}", path.display(), e);
+        let mut file = std::fs::File::create(&staging).map_err(write_error)?;
+        file.write_all(text.as_bytes()).and_then(|_| file.sync_all()).map_err(write_error)?;
+        std::fs::rename(&staging, path).map_err(write_error)?;
+        #[cfg(unix)]
+        std::fs::File::open(parent).and_then(|directory| directory.sync_all()).map_err(write_error)?;
+        Ok(())
+    }
+
+    /// Delete the config file, returning to the built-in defaults
+    pub fn remove(path: &Path) -> Result<(), String> {
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("Cannot remove {}: {}", path.display(), e)),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(format) = &self.output_format {
+            if !OUTPUT_FORMATS.contains(&format.as_str()) {
+                return Err(format!("output_format must be one of {}, not '{}'", OUTPUT_FORMATS.join(", "), format));
+            }
+        }
+        if let Some(server) = &self.server {
+            if !server.starts_with("http://") && !server.starts_with("https://") {
+                return Err(format!("server must be an http:// or https:// URL, not '{}'", server));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(match key {
+            "server" => self.server.clone(),
+            "output_format" => self.output_format.clone(),
+            "verbose" => self.verbose.map(|v| v.to_string()),
+            "color" => self.color.map(|v| v.to_string()),
+            "max_retries" => self.max_retries.map(|v| v.to_string()),
+            other => return Err(unknown_key(other)),
+        })
+    }
+
+    /// Parse and set one value, rejecting anything `load` would refuse
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let mut updated = self.clone();
+        match key {
+            "server" => updated.server = Some(value.trim_end_matches('/').to_string()),
+            "output_format" => updated.output_format = Some(value.to_string()),
+            "verbose" => updated.verbose = Some(parse_bool(key, value)?),
+            "color" => updated.color = Some(parse_bool(key, value)?),
+            "max_retries" => {
+                updated.max_retries = Some(value.parse().map_err(|_| format!("max_retries must be a whole number, not '{}'", value))?)
+            }
+            other => return Err(unknown_key(other)),
+        }
+        updated.validate()?;
+        *self = updated;
+        Ok(())
+    }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Ok(true),
+        "false" | "no" | "off" | "0" => Ok(false),
+        _ => Err(format!("{} must be true or false, not '{}'", key, value)),
+    }
+}
+
+fn unknown_key(key: &str) -> String {
+    format!("Unknown configuration key '{}'; expected one of {}", key, KEYS.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_config(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aion-cli-{}-{}", std::process::id(), name)).join("config.toml")
+    }
+
+    #[test]
+    fn test_set_get_round_trip_through_file() {
+        let path = temp_config("round-trip");
+        let mut config = FileConfig::load(&path).unwrap();
+        assert_eq!(config, FileConfig::default());
+
+        config.set("server", "https://aion.example.com/").unwrap();
+        config.set("output_format", "yaml").unwrap();
+        config.set("max_retries", "5").unwrap();
+        config.save(&path).unwrap();
+
+        let loaded = FileConfig::load(&path).unwrap();
+        assert_eq!(loaded.get("server").unwrap().as_deref(), Some("https://aion.example.com"));
+        assert_eq!(loaded.get("output_format").unwrap().as_deref(), Some("yaml"));
+        assert_eq!(loaded.get("max_retries").unwrap().as_deref(), Some("5"));
+        assert_eq!(loaded.get("color").unwrap(), None);
+        assert!(loaded.get("password").is_err());
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_output_format_is_rejected() {
+        let mut config = FileConfig::default();
+        assert!(config.set("output_format", "xml").is_err());
+        assert_eq!(config.output_format, None);
+
+        let path = temp_config("invalid");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "output_format = \"xml\"\n").unwrap();
+        let error = FileConfig::load(&path).unwrap_err();
+        assert!(error.contains("output_format must be one of"));
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_repairable_load_keeps_valid_values() {
+        let path = temp_config("repair");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "server = \"https://aion.example.com\"\noutput_format = \"xml\"\nmax_retries = 5\n").unwrap();
+        assert!(FileConfig::load(&path).is_err());
+
+        let (mut config, problems) = FileConfig::load_repairable(&path).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("output_format"));
+        assert_eq!(config.max_retries, Some(5));
+
+        config.set("output_format", "json").unwrap();
+        config.save(&path).unwrap();
+        let loaded = FileConfig::load(&path).unwrap();
+        assert_eq!(loaded.server.as_deref(), Some("https://aion.example.com"));
+        assert_eq!(loaded.output_format.as_deref(), Some("json"));
+        assert!(!path.with_extension("toml.tmp").exists());
+
+        FileConfig::remove(&path).unwrap();
+        assert_eq!(FileConfig::load(&path).unwrap(), FileConfig::default());
+        FileConfig::remove(&path).unwrap();
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -4,6 +4,8 @@ use std::collections::HashMap;
 use chrono::{DateTime, Utc, Duration};
 use uuid::Uuid;
 
+use crate::regulatory_monitor::RegulatoryDiff;
+
 /// Enterprise Alert and Notification System
 /// Provides real-time alerting, escalation management, and multi-channel notifications
 #[derive(Debug, Clone)]
@@ -626,6 +628,15 @@ impl AlertNotificationSystem {
         Ok(alert.alert_id)
     }
 
+    /// Raise one alert per changed clause of a regulation
+    pub async fn process_regulatory_diff(&mut self, diff: &RegulatoryDiff) -> AionResult<Vec<String>> {
+        let mut alert_ids = Vec::with_capacity(diff.changes.len());
+        for alert_data in diff.alerts() {
+            alert_ids.push(self.process_incoming_alert(alert_data).await?);
+        }
+        Ok(alert_ids)
+    }
+
     pub async fn acknowledge_alert(&mut self, alert_id: &str, user_id: &str, message: Option<String>) -> AionResult<()> {
         let mut alert = self.alert_engine.active_alerts.get(alert_id)
             .ok_or_else(|| AionError::NotFound(format!("Alert {} not found", alert_id)))?
@@ -1,5 +1,168 @@
 use aion_core::types::*;
-use aion_core::{AionResult};
+use aion_core::{AionError, AionResult};
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Evidence type that documents a requirement as not applicable
+pub const EXEMPTION_EVIDENCE_TYPE: &str = "exemption";
+
+/// Evidence collected this long after the end of the `as_of` day (UTC) still
+/// counts, since the day ends up to 14 hours later in the evidence's time zone
+const EVIDENCE_DATE_TOLERANCE_HOURS: i64 = 14;
+/// Allowed clock skew between the assessor and the systems stamping evidence
+const CLOCK_SKEW_MINUTES: i64 = 5;
+
+/// Whether the evidence on file satisfies a requirement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoverageStatus {
+    Met,
+    Unmet,
+    PartiallyMet,
+    NotApplicable,
+}
+
+impl CoverageStatus {
+    pub fn compliance_status(&self) -> ComplianceStatus {
+        match self {
+            CoverageStatus::Met => ComplianceStatus::Compliant,
+            CoverageStatus::Unmet => ComplianceStatus::NonCompliant,
+            CoverageStatus::PartiallyMet => ComplianceStatus::PartiallyCompliant,
+            CoverageStatus::NotApplicable => ComplianceStatus::NotApplicable,
+        }
+    }
+}
+
+/// Coverage of one requirement and the evidence behind it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequirementCoverage {
+    pub requirement_id: Uuid,
+    pub title: String,
+    pub mandatory: bool,
+    pub status: CoverageStatus,
+    /// Evidence that satisfied the requirement, in the order given
+    pub satisfied_by: Vec<Uuid>,
+    /// Required evidence types with no verified evidence on file
+    pub missing_evidence: Vec<String>,
+}
+
+/// Requirement-by-requirement coverage of a framework
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageReport {
+    pub framework_id: NormativeId,
+    pub requirements: Vec<RequirementCoverage>,
+    /// Share of applicable requirements met, mandatory ones counting double; partial coverage counts half
+    pub score: f64,
+    pub overall_status: ComplianceStatus,
+}
+
+impl CoverageReport {
+    pub fn count(&self, status: CoverageStatus) -> usize {
+        self.requirements.iter().filter(|coverage| coverage.status == status).count()
+    }
+}
+
+/// Map each of the framework's requirements to the evidence that satisfies it
+///
+/// Evidence counts only once verified. It applies to a requirement when its
+/// `requirement_id` metadata names that requirement, or when it carries no
+/// `requirement_id` and its type is one the requirement asks for. Verified
+/// `exemption` evidence marks a requirement not applicable.
+pub fn assess_coverage(framework: &NormativeFramework, evidence: &[Evidence]) -> CoverageReport {
+    coverage_of(&framework.id, &framework.requirements, evidence)
+}
+
+fn coverage_of(framework_id: &NormativeId, requirements: &[Requirement], evidence: &[Evidence]) -> CoverageReport {
+    let verified: Vec<&Evidence> = evidence.iter().filter(|item| item.verification_status == "verified").collect();
+    let requirements: Vec<RequirementCoverage> = requirements
+        .iter()
+        .map(|requirement| requirement_coverage(requirement, &verified))
+        .collect();
+
+    let (mut earned, mut possible) = (0.0, 0.0);
+    for coverage in &requirements {
+        let weight = if coverage.mandatory { 2.0 } else { 1.0 };
+        earned += weight
+            * match coverage.status {
+                CoverageStatus::Met => 1.0,
+                CoverageStatus::PartiallyMet => 0.5,
+                CoverageStatus::Unmet | CoverageStatus::NotApplicable => 0.0,
+            };
+        if coverage.status != CoverageStatus::NotApplicable {
+            possible += weight;
+        }
+    }
+
+    let applicable: Vec<&RequirementCoverage> =
+        requirements.iter().filter(|coverage| coverage.status != CoverageStatus::NotApplicable).collect();
+    let overall_status = if applicable.is_empty() {
+        ComplianceStatus::NotApplicable
+    } else if applicable.iter().all(|coverage| coverage.status == CoverageStatus::Met) {
+        ComplianceStatus::Compliant
+    } else if applicable.iter().any(|coverage| coverage.mandatory && coverage.status == CoverageStatus::Unmet) {
+        ComplianceStatus::NonCompliant
+    } else {
+        ComplianceStatus::PartiallyCompliant
+    };
+
+    CoverageReport {
+        framework_id: framework_id.clone(),
+        score: if possible > 0.0 { earned / possible } else { 1.0 },
+        overall_status,
+        requirements,
+    }
+}
+
+fn requirement_coverage(requirement: &Requirement, verified: &[&Evidence]) -> RequirementCoverage {
+    let requirement_id = requirement.id.to_string();
+    let applies = |item: &&&Evidence| match item.metadata.get("requirement_id") {
+        Some(id) => *id == requirement_id,
+        None => requirement.evidence_required.contains(&item.evidence_type),
+    };
+    let relevant: Vec<&Evidence> = verified.iter().filter(applies).copied().collect();
+
+    let coverage = |status, satisfied_by: Vec<&Evidence>, missing_evidence| RequirementCoverage {
+        requirement_id: requirement.id,
+        title: requirement.title.clone(),
+        mandatory: requirement.mandatory,
+        status,
+        satisfied_by: satisfied_by.iter().map(|item| item.id).collect(),
+        missing_evidence,
+    };
+
+    let exemptions: Vec<&Evidence> =
+        relevant.iter().filter(|item| item.evidence_type == EXEMPTION_EVIDENCE_TYPE).copied().collect();
+    if !exemptions.is_empty() {
+        return coverage(CoverageStatus::NotApplicable, exemptions, Vec::new());
+    }
+
+    if requirement.evidence_required.is_empty() {
+        let status = if relevant.is_empty() { CoverageStatus::Unmet } else { CoverageStatus::Met };
+        let missing = if relevant.is_empty() { vec!["any verified evidence".to_string()] } else { Vec::new() };
+        return coverage(status, relevant, missing);
+    }
+
+    let mut missing_evidence = Vec::new();
+    for required in &requirement.evidence_required {
+        if !relevant.iter().any(|item| &item.evidence_type == required) && !missing_evidence.contains(required) {
+            missing_evidence.push(required.clone());
+        }
+    }
+    let satisfied_by: Vec<&Evidence> = relevant
+        .into_iter()
+        .filter(|item| requirement.evidence_required.contains(&item.evidence_type))
+        .collect();
+
+    let status = if missing_evidence.is_empty() {
+        CoverageStatus::Met
+    } else if satisfied_by.is_empty() {
+        CoverageStatus::Unmet
+    } else {
+        CoverageStatus::PartiallyMet
+    };
+    coverage(status, satisfied_by, missing_evidence)
+}
 
 /// Compliance assessor for automated evaluation
 pub struct ComplianceAssessor {
@@ -14,26 +177,298 @@ impl ComplianceAssessor {
     }
 
     pub fn assess(&mut self, framework: &NormativeFramework, context: &GovernanceContext) -> AionResult<ComplianceAssessment> {
+        self.assess_as_of(framework, context, Utc::now().date_naive())
+    }
+
+    /// Assess against the requirements that were in force on `as_of`
+    ///
+    /// A framework that was not in force on that date is reported as not applicable.
+    pub fn assess_as_of(
+        &mut self,
+        framework: &NormativeFramework,
+        context: &GovernanceContext,
+        as_of: NaiveDate,
+    ) -> AionResult<ComplianceAssessment> {
+        self.assess_with_evidence(framework, context, &[], as_of)
+    }
+
+    /// Assess the requirements in force on `as_of` against the evidence on file
+    ///
+    /// Requirement and overall statuses come from `assess_coverage`. Fails
+    /// with a structured validation error when the context has no
+    /// organization, or evidence is dated after `as_of` (or in the future) or
+    /// names a requirement that is not a UUID of one of the framework's
+    /// requirements.
+    pub fn assess_with_evidence(
+        &mut self,
+        framework: &NormativeFramework,
+        context: &GovernanceContext,
+        evidence: &[Evidence],
+        as_of: NaiveDate,
+    ) -> AionResult<ComplianceAssessment> {
+        check_inputs(framework, context, evidence, as_of)?;
+        let version = framework
+            .version_in_effect_on(as_of)
+            .map_or(framework.version.as_str(), |version| version.version.as_str());
+
+        let (overall_status, requirement_assessments) = match framework.requirements_in_effect_on(as_of) {
+            Some(requirements) => {
+                let report = coverage_of(&framework.id, requirements, evidence);
+                let assessments = report
+                    .requirements
+                    .iter()
+                    .map(|coverage| RequirementAssessment {
+                        requirement_id: coverage.requirement_id,
+                        status: coverage.status.compliance_status(),
+                        evidence: evidence
+                            .iter()
+                            .filter(|item| coverage.satisfied_by.contains(&item.id))
+                            .cloned()
+                            .collect(),
+                        gaps: coverage
+                            .missing_evidence
+                            .iter()
+                            .map(|missing| format!("Missing evidence: {}", missing))
+                            .collect(),
+                        notes: format!("Assessed against version {} in force on {}", version, as_of),
+                        risk_level: if coverage.mandatory { "High" } else { "Low" }.to_string(),
+                    })
+                    .collect();
+                (report.overall_status, assessments)
+            }
+            None => (ComplianceStatus::NotApplicable, Vec::new()),
+        };
+
         // Basic assessment implementation
         let assessment = ComplianceAssessment {
-            id: uuid::Uuid::new_v4(),
+            id: Uuid::new_v4(),
             entity_id: context.organization.clone(),
             normative_framework: framework.id.clone(),
-            assessment_date: chrono::Utc::now(),
+            assessment_date: Utc::now(),
             assessor: "AION-CR Automated Assessor".to_string(),
-            overall_status: ComplianceStatus::Compliant, // Simplified
-            requirement_assessments: Vec::new(),
+            overall_status,
+            requirement_assessments,
             findings: Vec::new(),
             recommendations: Vec::new(),
-            next_review_date: Some(chrono::Utc::now() + chrono::Duration::days(365)),
+            next_review_date: Some(Utc::now() + chrono::Duration::days(365)),
         };
 
         Ok(assessment)
     }
 }
 
+fn check_inputs(
+    framework: &NormativeFramework,
+    context: &GovernanceContext,
+    evidence: &[Evidence],
+    as_of: NaiveDate,
+) -> AionResult<()> {
+    if context.organization.trim().is_empty() {
+        return Err(AionError::MissingField {
+            field: "context.organization".to_string(),
+        });
+    }
+
+    let end_of_day = as_of.and_time(NaiveTime::MIN).and_utc() + Duration::days(1);
+    let latest = (end_of_day + Duration::hours(EVIDENCE_DATE_TOLERANCE_HOURS))
+        .min(Utc::now() + Duration::minutes(CLOCK_SKEW_MINUTES));
+    let known: HashSet<Uuid> = framework
+        .requirements
+        .iter()
+        .chain(framework.versions.iter().flat_map(|version| version.requirements.iter()))
+        .map(|requirement| requirement.id)
+        .collect();
+    for (index, item) in evidence.iter().enumerate() {
+        if item.collected_date > latest {
+            return Err(AionError::OutOfRange {
+                field: format!("evidence[{}].collected_date", index),
+                expected: format!("no later than {}", latest.to_rfc3339()),
+                actual: item.collected_date.to_rfc3339(),
+            });
+        }
+
+        let Some(reference) = item.metadata.get("requirement_id") else {
+            continue;
+        };
+        let field = format!("evidence[{}].metadata.requirement_id", index);
+        let requirement_id = Uuid::parse_str(reference).map_err(|_| AionError::TypeMismatch {
+            field: field.clone(),
+            expected: "UUID".to_string(),
+            actual: reference.clone(),
+        })?;
+        if !known.contains(&requirement_id) {
+            return Err(AionError::ReferenceUnresolved {
+                field,
+                reference: reference.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
 impl Default for ComplianceAssessor {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aion_core::testing;
+    use std::collections::HashMap;
+
+    fn requirement(title: &str) -> Requirement {
+        testing::requirement(title, title)
+    }
+
+    #[test]
+    fn test_historical_period_uses_rules_then_in_force() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let rules_2022 = requirement("Retain records for five years");
+        let rules_2024 = requirement("Retain records for seven years");
+        let mut framework = NormativeFramework::new(
+            "Record Keeping Rule".to_string(),
+            "Record retention".to_string(),
+            NormativeType::Regulation,
+            Jurisdiction::Federal,
+            "Parliament".to_string(),
+        );
+        framework.requirements = vec![rules_2024.clone()];
+        framework.versions = vec![
+            NormativeVersion {
+                version: "2022".to_string(),
+                effective_from: date(2022, 1, 1),
+                effective_to: Some(date(2024, 1, 1)),
+                requirements: vec![rules_2022.clone()],
+            },
+            NormativeVersion {
+                version: "2024".to_string(),
+                effective_from: date(2024, 1, 1),
+                effective_to: None,
+                requirements: vec![rules_2024],
+            },
+        ];
+        let context = GovernanceContext {
+            organization: "Test Org".to_string(),
+            sector: "Finance".to_string(),
+            region: "US".to_string(),
+            applicable_jurisdictions: vec![Jurisdiction::Federal],
+            business_context: HashMap::new(),
+            risk_profile: "Medium".to_string(),
+            maturity_level: "Advanced".to_string(),
+        };
+        let mut assessor = ComplianceAssessor::new();
+
+        let assessment = assessor.assess_as_of(&framework, &context, date(2022, 9, 30)).unwrap();
+        assert_eq!(assessment.requirement_assessments.len(), 1);
+        assert_eq!(assessment.requirement_assessments[0].requirement_id, rules_2022.id);
+        assert!(assessment.requirement_assessments[0].notes.contains("version 2022"));
+
+        let before = assessor.assess_as_of(&framework, &context, date(2021, 6, 30)).unwrap();
+        assert_eq!(before.overall_status, ComplianceStatus::NotApplicable);
+    }
+
+    fn evidence(evidence_type: &str, requirement: Option<&Requirement>, verification_status: &str) -> Evidence {
+        Evidence {
+            id: Uuid::new_v4(),
+            evidence_type: evidence_type.to_string(),
+            description: format!("{} on file", evidence_type),
+            source: "document store".to_string(),
+            collected_date: Utc::now(),
+            verification_status: verification_status.to_string(),
+            metadata: requirement
+                .map(|requirement| HashMap::from([("requirement_id".to_string(), requirement.id.to_string())]))
+                .unwrap_or_default(),
+        }
+    }
+
+    #[test]
+    fn test_coverage_maps_evidence_to_requirements() {
+        let mut encryption = requirement("Encrypt data at rest");
+        encryption.evidence_required = vec!["key_policy".to_string(), "audit_log".to_string()];
+        let mut training = requirement("Train staff annually");
+        training.mandatory = false;
+        training.evidence_required = vec!["training_record".to_string()];
+        let mut retention = requirement("Retain records");
+        retention.evidence_required = vec!["retention_schedule".to_string()];
+        let breach = requirement("Notify breaches within 72 hours");
+
+        let mut framework = NormativeFramework::new(
+            "Data Protection Rule".to_string(),
+            "Data protection".to_string(),
+            NormativeType::Regulation,
+            Jurisdiction::Federal,
+            "Parliament".to_string(),
+        );
+        framework.requirements = vec![encryption.clone(), training.clone(), retention.clone(), breach.clone()];
+
+        let key_policy = evidence("key_policy", None, "verified");
+        let training_record = evidence("training_record", Some(&training), "verified");
+        let exemption = evidence(EXEMPTION_EVIDENCE_TYPE, Some(&breach), "verified");
+        let rejected = evidence("retention_schedule", None, "rejected");
+        let report = assess_coverage(&framework, &[key_policy.clone(), training_record.clone(), exemption, rejected]);
+
+        let statuses: Vec<CoverageStatus> = report.requirements.iter().map(|coverage| coverage.status).collect();
+        assert_eq!(
+            statuses,
+            vec![CoverageStatus::PartiallyMet, CoverageStatus::Met, CoverageStatus::Unmet, CoverageStatus::NotApplicable]
+        );
+        assert_eq!(report.requirements[0].satisfied_by, vec![key_policy.id]);
+        assert_eq!(report.requirements[0].missing_evidence, vec!["audit_log".to_string()]);
+        assert_eq!(report.requirements[1].satisfied_by, vec![training_record.id]);
+        assert_eq!(report.count(CoverageStatus::NotApplicable), 1);
+
+        // Mandatory encryption (2 x 0.5) + optional training (1) out of 2 + 1 + 2
+        assert!((report.score - 0.4).abs() < 1e-9);
+        assert_eq!(report.overall_status, ComplianceStatus::NonCompliant);
+    }
+
+    #[test]
+    fn test_invalid_inputs_fail_with_structured_errors() {
+        let retention = requirement("Retain records");
+        let mut framework = NormativeFramework::new(
+            "Record Keeping Rule".to_string(),
+            "Record retention".to_string(),
+            NormativeType::Regulation,
+            Jurisdiction::Federal,
+            "Parliament".to_string(),
+        );
+        framework.requirements = vec![retention.clone()];
+        let mut context = GovernanceContext {
+            organization: "Test Org".to_string(),
+            sector: "Finance".to_string(),
+            region: "US".to_string(),
+            applicable_jurisdictions: vec![Jurisdiction::Federal],
+            business_context: HashMap::new(),
+            risk_profile: "Medium".to_string(),
+            maturity_level: "Advanced".to_string(),
+        };
+        let today = Utc::now().date_naive();
+        let mut assessor = ComplianceAssessor::new();
+
+        let mut mistyped = evidence("retention_schedule", Some(&retention), "verified");
+        mistyped.metadata.insert("requirement_id".to_string(), "req-7".to_string());
+        let error = assessor.assess_with_evidence(&framework, &context, &[mistyped], today).unwrap_err();
+        assert!(matches!(
+            &error,
+            AionError::TypeMismatch { field, actual, .. }
+                if field == "evidence[0].metadata.requirement_id" && actual == "req-7"
+        ));
+
+        let unknown = evidence("retention_schedule", Some(&requirement("Unrelated")), "verified");
+        let error = assessor.assess_with_evidence(&framework, &context, &[unknown], today).unwrap_err();
+        assert!(matches!(error, AionError::ReferenceUnresolved { .. }));
+
+        // Evidence gathered after the assessment date cannot support it
+        let later = evidence("retention_schedule", Some(&retention), "verified");
+        let last_year = today - chrono::Duration::days(365);
+        let error = assessor.assess_with_evidence(&framework, &context, &[later.clone()], last_year).unwrap_err();
+        assert_eq!(error.field_path(), Some("evidence[0].collected_date"));
+        assessor.assess_with_evidence(&framework, &context, &[later], today).unwrap();
+
+        context.organization = String::new();
+        let error = assessor.assess(&framework, &context).unwrap_err();
+        assert_eq!(error.field_path(), Some("context.organization"));
+    }
+}
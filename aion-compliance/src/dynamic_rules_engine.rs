@@ -1,6 +1,8 @@
 use aion_core::{AionResult, NormativeFramework};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use crate::granular_legal_database::*;
@@ -18,6 +20,9 @@ pub struct DynamicRulesEngine {
     pub rule_processor: RealTimeRuleProcessor,
     /// Query optimization engine
     pub query_optimizer: QueryOptimizer,
+    /// Active rule set; replaced wholesale by `reload_rules`
+    #[serde(skip)]
+    rule_set: Arc<RwLock<Arc<RuleSet>>>,
 }
 
 /// User Consultation Interface
@@ -293,9 +298,62 @@ impl DynamicRulesEngine {
             ml_database: MLNormalizedDatabase::new()?,
             rule_processor: RealTimeRuleProcessor::new()?,
             query_optimizer: QueryOptimizer::new()?,
+            rule_set: Arc::default(),
         })
     }
 
+    /// Snapshot of the active rule set. Holders keep evaluating against this
+    /// snapshot even if a reload swaps in a newer one.
+    pub fn active_rules(&self) -> Arc<RuleSet> {
+        self.rule_set.read().unwrap().clone()
+    }
+
+    /// Evaluate every active rule against the given facts
+    pub fn evaluate_rules(&self, facts: &FactSet) -> Vec<RuleOutcome> {
+        let rule_set = self.active_rules();
+        rule_set.rules.iter().map(|rule| evaluate(rule, facts)).collect()
+    }
+
+    /// Replace the active rule set. Every rule in the source is loaded and
+    /// validated first; on any error the current set stays in place.
+    pub fn reload_rules(&self, source: RuleSource) -> AionResult<ReloadReport> {
+        let rules = source.load()?;
+        validate_rule_set(&rules)?;
+
+        let mut active = self.rule_set.write().unwrap();
+        let previous: HashMap<&str, &Rule> = active.rules.iter().map(|rule| (rule.id.as_str(), rule)).collect();
+
+        let mut report = ReloadReport {
+            version: active.version + 1,
+            ..ReloadReport::default()
+        };
+        for rule in &rules {
+            match previous.get(rule.id.as_str()) {
+                None => report.added.push(rule.id.clone()),
+                Some(old) if *old != rule => report.changed.push(rule.id.clone()),
+                Some(_) => report.unchanged += 1,
+            }
+        }
+        let incoming: std::collections::HashSet<&str> = rules.iter().map(|rule| rule.id.as_str()).collect();
+        report.removed = previous.keys().filter(|id| !incoming.contains(*id)).map(|id| id.to_string()).collect();
+        report.removed.sort();
+
+        *active = Arc::new(RuleSet {
+            version: report.version,
+            loaded_at: Some(Utc::now()),
+            rules,
+        });
+
+        tracing::info!(
+            "🔄 Reloaded rule set v{}: {} added, {} removed, {} changed",
+            report.version,
+            report.added.len(),
+            report.removed.len(),
+            report.changed.len()
+        );
+        Ok(report)
+    }
+
     /// User consultation with complete regulatory guidance
     pub fn consult_user(&self, query: &UserQuery) -> AionResult<UserConsultationResponse> {
         // Parse user query and determine complexity
@@ -801,4 +859,690 @@ pub struct BestPracticeExample;
 pub struct CommonMistake;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct IndustryGuidance;
\ No newline at end of file
+pub struct IndustryGuidance;
+
+// ---------------------------------------------------------------------------
+// Rule DSL
+//
+// Rules are plain data: they serialize to JSON/YAML for storage and can be
+// swapped at runtime, or written in the text form accepted by `Rule::parse`:
+//
+//     if jurisdiction == "EU" and personal_data == true then dpia_required
+// ---------------------------------------------------------------------------
+
+/// A value a fact or a comparison operand can hold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FactValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    List(Vec<FactValue>),
+}
+
+impl std::fmt::Display for FactValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FactValue::Bool(b) => write!(f, "{}", b),
+            FactValue::Number(n) => write!(f, "{}", n),
+            FactValue::Text(s) => write!(f, "\"{}\"", s),
+            FactValue::List(items) => {
+                let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+        }
+    }
+}
+
+impl From<bool> for FactValue {
+    fn from(value: bool) -> Self {
+        FactValue::Bool(value)
+    }
+}
+
+impl From<f64> for FactValue {
+    fn from(value: f64) -> Self {
+        FactValue::Number(value)
+    }
+}
+
+impl From<i64> for FactValue {
+    fn from(value: i64) -> Self {
+        FactValue::Number(value as f64)
+    }
+}
+
+impl From<&str> for FactValue {
+    fn from(value: &str) -> Self {
+        FactValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for FactValue {
+    fn from(value: String) -> Self {
+        FactValue::Text(value)
+    }
+}
+
+/// Named facts about the entity a rule is evaluated against
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FactSet {
+    pub facts: HashMap<String, FactValue>,
+}
+
+impl FactSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: &str, value: impl Into<FactValue>) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    pub fn insert(&mut self, name: &str, value: impl Into<FactValue>) {
+        self.facts.insert(name.to_string(), value.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&FactValue> {
+        self.facts.get(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleComparison {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    /// The fact is one of the listed values
+    In,
+    /// The fact is a list containing the value, or text containing it
+    Contains,
+}
+
+impl RuleComparison {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            RuleComparison::Eq => "==",
+            RuleComparison::Ne => "!=",
+            RuleComparison::Gt => ">",
+            RuleComparison::Ge => ">=",
+            RuleComparison::Lt => "<",
+            RuleComparison::Le => "<=",
+            RuleComparison::In => "in",
+            RuleComparison::Contains => "contains",
+        }
+    }
+
+    fn apply(&self, fact: &FactValue, value: &FactValue) -> bool {
+        match (self, fact, value) {
+            (RuleComparison::Eq, _, _) => fact_eq(fact, value),
+            (RuleComparison::Ne, _, _) => !fact_eq(fact, value),
+            (RuleComparison::Gt, FactValue::Number(a), FactValue::Number(b)) => a > b,
+            (RuleComparison::Ge, FactValue::Number(a), FactValue::Number(b)) => a >= b,
+            (RuleComparison::Lt, FactValue::Number(a), FactValue::Number(b)) => a < b,
+            (RuleComparison::Le, FactValue::Number(a), FactValue::Number(b)) => a <= b,
+            (RuleComparison::In, _, FactValue::List(options)) => options.iter().any(|option| fact_eq(fact, option)),
+            (RuleComparison::Contains, FactValue::List(items), _) => items.iter().any(|item| fact_eq(item, value)),
+            (RuleComparison::Contains, FactValue::Text(text), FactValue::Text(needle)) => {
+                text.to_lowercase().contains(&needle.to_lowercase())
+            }
+            _ => false,
+        }
+    }
+}
+
+// Text compares case-insensitively so "EU" and "eu" facts agree
+fn fact_eq(a: &FactValue, b: &FactValue) -> bool {
+    match (a, b) {
+        (FactValue::Text(a), FactValue::Text(b)) => a.eq_ignore_ascii_case(b),
+        _ => a == b,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleCondition {
+    All(Vec<RuleCondition>),
+    Any(Vec<RuleCondition>),
+    Not(Box<RuleCondition>),
+    Compare {
+        field: String,
+        op: RuleComparison,
+        value: FactValue,
+    },
+}
+
+impl std::fmt::Display for RuleCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |conditions: &[RuleCondition], separator: &str| {
+            conditions.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(separator)
+        };
+        match self {
+            RuleCondition::All(conditions) => write!(f, "({})", join(conditions, " and ")),
+            RuleCondition::Any(conditions) => write!(f, "({})", join(conditions, " or ")),
+            RuleCondition::Not(condition) => write!(f, "not {}", condition),
+            RuleCondition::Compare { field, op, value } => write!(f, "{} {} {}", field, op.symbol(), value),
+        }
+    }
+}
+
+/// A condition over facts and the consequences that hold when it is met
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    pub when: RuleCondition,
+    /// Consequences that apply when the condition holds, e.g. "dpia_required"
+    pub then: Vec<String>,
+}
+
+impl Rule {
+    /// Parse the text form: `if <condition> then <consequence>[, <consequence>...]`
+    pub fn parse(id: &str, source: &str) -> AionResult<Self> {
+        let tokens = tokenize_rule(source)?;
+        let mut parser = RuleParser { tokens, position: 0 };
+
+        parser.expect_keyword("if")?;
+        let when = parser.parse_or()?;
+        parser.expect_keyword("then")?;
+
+        let mut then = vec![parser.expect_identifier()?];
+        while parser.eat(&RuleToken::Comma) {
+            then.push(parser.expect_identifier()?);
+        }
+        if parser.position < parser.tokens.len() {
+            return Err(rule_error(format!("unexpected {:?} after consequences", parser.tokens[parser.position])));
+        }
+
+        Ok(Self {
+            id: id.to_string(),
+            description: source.trim().to_string(),
+            when,
+            then,
+        })
+    }
+}
+
+/// A leaf comparison that did not hold, or a negation whose operand did
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConditionFailure {
+    pub condition: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleOutcome {
+    pub rule_id: String,
+    pub passed: bool,
+    /// The rule's consequences when it passed, empty otherwise
+    pub consequences: Vec<String>,
+    pub failures: Vec<ConditionFailure>,
+}
+
+/// An immutable, versioned collection of rules
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleSet {
+    pub version: u64,
+    pub loaded_at: Option<DateTime<Utc>>,
+    pub rules: Vec<Rule>,
+}
+
+/// A rule written in the text form accepted by `Rule::parse`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleDefinition {
+    pub id: String,
+    pub source: String,
+}
+
+/// Where `DynamicRulesEngine::reload_rules` takes its rules from
+#[derive(Debug, Clone)]
+pub enum RuleSource {
+    Rules(Vec<Rule>),
+    Definitions(Vec<RuleDefinition>),
+    /// A JSON array of serialized rules
+    Json(String),
+    /// A `.json` file of serialized rules, or a text file with one
+    /// `<id>: if ... then ...` rule per line (`#` starts a comment)
+    File(PathBuf),
+}
+
+impl RuleSource {
+    fn load(self) -> AionResult<Vec<Rule>> {
+        match self {
+            RuleSource::Rules(rules) => Ok(rules),
+            RuleSource::Definitions(definitions) => {
+                let mut rules = Vec::with_capacity(definitions.len());
+                let mut errors = Vec::new();
+                for definition in definitions {
+                    match Rule::parse(&definition.id, &definition.source) {
+                        Ok(rule) => rules.push(rule),
+                        Err(e) => errors.push(format!("{}: {}", definition.id, e)),
+                    }
+                }
+                if errors.is_empty() {
+                    Ok(rules)
+                } else {
+                    Err(rule_error(errors.join("; ")))
+                }
+            }
+            RuleSource::Json(json) => serde_json::from_str(&json).map_err(|e| aion_core::AionError::SerializationError {
+                reason: format!("invalid rule set: {}", e),
+            }),
+            RuleSource::File(path) => {
+                let content = std::fs::read_to_string(&path).map_err(|e| aion_core::AionError::ConfigurationError {
+                    parameter: path.display().to_string(),
+                    reason: e.to_string(),
+                })?;
+                if path.extension().is_some_and(|ext| ext == "json") {
+                    return RuleSource::Json(content).load();
+                }
+
+                let mut definitions = Vec::new();
+                for (number, line) in content.lines().enumerate() {
+                    let line = line.trim();
+                    if line.is_empty() || line.starts_with('#') {
+                        continue;
+                    }
+                    let Some((id, source)) = line.split_once(':') else {
+                        return Err(rule_error(format!("{}:{}: expected '<id>: if ... then ...'", path.display(), number + 1)));
+                    };
+                    definitions.push(RuleDefinition {
+                        id: id.trim().to_string(),
+                        source: source.trim().to_string(),
+                    });
+                }
+                RuleSource::Definitions(definitions).load()
+            }
+        }
+    }
+}
+
+/// Rule ids affected by a reload
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReloadReport {
+    /// Version of the newly active rule set
+    pub version: u64,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+    pub unchanged: usize,
+}
+
+fn validate_rule_set(rules: &[Rule]) -> AionResult<()> {
+    let mut errors = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for rule in rules {
+        if rule.id.trim().is_empty() {
+            errors.push("rule with empty id".to_string());
+        } else if !seen.insert(rule.id.as_str()) {
+            errors.push(format!("duplicate rule id '{}'", rule.id));
+        }
+        if rule.then.is_empty() {
+            errors.push(format!("{}: no consequences", rule.id));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(rule_error(errors.join("; ")))
+    }
+}
+
+/// Evaluate a rule against a fact set, reporting which sub-conditions failed
+pub fn evaluate(rule: &Rule, facts: &FactSet) -> RuleOutcome {
+    let mut failures = Vec::new();
+    let passed = evaluate_condition(&rule.when, facts, &mut failures);
+
+    RuleOutcome {
+        rule_id: rule.id.clone(),
+        passed,
+        consequences: if passed { rule.then.clone() } else { Vec::new() },
+        failures: if passed { Vec::new() } else { failures },
+    }
+}
+
+fn evaluate_condition(condition: &RuleCondition, facts: &FactSet, failures: &mut Vec<ConditionFailure>) -> bool {
+    match condition {
+        RuleCondition::All(conditions) => {
+            // Evaluate every branch so all failing parts are reported, not just the first
+            let results: Vec<bool> = conditions.iter().map(|c| evaluate_condition(c, facts, failures)).collect();
+            results.into_iter().all(|passed| passed)
+        }
+        RuleCondition::Any(conditions) => {
+            let mut branch_failures = Vec::new();
+            let results: Vec<bool> = conditions
+                .iter()
+                .map(|c| evaluate_condition(c, facts, &mut branch_failures))
+                .collect();
+            let passed = results.into_iter().any(|passed| passed);
+            if !passed {
+                failures.extend(branch_failures);
+            }
+            passed
+        }
+        RuleCondition::Not(inner) => {
+            let inner_passed = evaluate_condition(inner, facts, &mut Vec::new());
+            if inner_passed {
+                failures.push(ConditionFailure {
+                    condition: condition.to_string(),
+                    reason: format!("{} holds", inner),
+                });
+            }
+            !inner_passed
+        }
+        RuleCondition::Compare { field, op, value } => {
+            let Some(fact) = facts.get(field) else {
+                failures.push(ConditionFailure {
+                    condition: condition.to_string(),
+                    reason: format!("fact '{}' is not set", field),
+                });
+                return false;
+            };
+            let passed = op.apply(fact, value);
+            if !passed {
+                failures.push(ConditionFailure {
+                    condition: condition.to_string(),
+                    reason: format!("{} is {}", field, fact),
+                });
+            }
+            passed
+        }
+    }
+}
+
+fn rule_error(message: String) -> aion_core::AionError {
+    aion_core::AionError::ValidationError {
+        field: "rule".to_string(),
+        message,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RuleToken {
+    Identifier(String),
+    Text(String),
+    Number(f64),
+    Operator(RuleComparison),
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Comma,
+}
+
+fn tokenize_rule(source: &str) -> AionResult<Vec<RuleToken>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | '[' | ']' | ',' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => RuleToken::LeftParen,
+                    ')' => RuleToken::RightParen,
+                    '[' => RuleToken::LeftBracket,
+                    ']' => RuleToken::RightBracket,
+                    _ => RuleToken::Comma,
+                });
+            }
+            '"' | '\'' => {
+                chars.next();
+                let text: String = chars.by_ref().take_while(|&next| next != c).collect();
+                tokens.push(RuleToken::Text(text));
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let followed_by_eq = chars.next_if_eq(&'=').is_some();
+                let op = match (c, followed_by_eq) {
+                    ('=', true) => RuleComparison::Eq,
+                    ('!', true) => RuleComparison::Ne,
+                    ('<', true) => RuleComparison::Le,
+                    ('>', true) => RuleComparison::Ge,
+                    ('<', false) => RuleComparison::Lt,
+                    ('>', false) => RuleComparison::Gt,
+                    _ => return Err(rule_error(format!("unexpected '{}'", c))),
+                };
+                tokens.push(RuleToken::Operator(op));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut number = String::new();
+                while let Some(next) = chars.next_if(|n| n.is_ascii_digit() || *n == '.' || *n == '-') {
+                    number.push(next);
+                }
+                let value = number
+                    .parse()
+                    .map_err(|_| rule_error(format!("invalid number '{}'", number)))?;
+                tokens.push(RuleToken::Number(value));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = String::new();
+                while let Some(next) = chars.next_if(|n| n.is_alphanumeric() || *n == '_' || *n == '.') {
+                    word.push(next);
+                }
+                tokens.push(match word.to_lowercase().as_str() {
+                    "in" => RuleToken::Operator(RuleComparison::In),
+                    "contains" => RuleToken::Operator(RuleComparison::Contains),
+                    _ => RuleToken::Identifier(word),
+                });
+            }
+            _ => return Err(rule_error(format!("unexpected '{}'", c))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct RuleParser {
+    tokens: Vec<RuleToken>,
+    position: usize,
+}
+
+impl RuleParser {
+    fn peek(&self) -> Option<&RuleToken> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<RuleToken> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &RuleToken) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(RuleToken::Identifier(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> AionResult<()> {
+        if self.at_keyword(keyword) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(rule_error(format!("expected '{}', found {:?}", keyword, self.peek())))
+        }
+    }
+
+    fn expect_identifier(&mut self) -> AionResult<String> {
+        match self.next() {
+            Some(RuleToken::Identifier(word)) => Ok(word),
+            other => Err(rule_error(format!("expected a name, found {:?}", other))),
+        }
+    }
+
+    fn parse_or(&mut self) -> AionResult<RuleCondition> {
+        let mut branches = vec![self.parse_and()?];
+        while self.at_keyword("or") {
+            self.position += 1;
+            branches.push(self.parse_and()?);
+        }
+        Ok(if branches.len() == 1 { branches.remove(0) } else { RuleCondition::Any(branches) })
+    }
+
+    fn parse_and(&mut self) -> AionResult<RuleCondition> {
+        let mut branches = vec![self.parse_unary()?];
+        while self.at_keyword("and") {
+            self.position += 1;
+            branches.push(self.parse_unary()?);
+        }
+        Ok(if branches.len() == 1 { branches.remove(0) } else { RuleCondition::All(branches) })
+    }
+
+    fn parse_unary(&mut self) -> AionResult<RuleCondition> {
+        if self.at_keyword("not") {
+            self.position += 1;
+            return Ok(RuleCondition::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat(&RuleToken::LeftParen) {
+            let condition = self.parse_or()?;
+            if !self.eat(&RuleToken::RightParen) {
+                return Err(rule_error("missing ')'".to_string()));
+            }
+            return Ok(condition);
+        }
+
+        let field = self.expect_identifier()?;
+        let op = match self.next() {
+            Some(RuleToken::Operator(op)) => op,
+            other => return Err(rule_error(format!("expected a comparison after '{}', found {:?}", field, other))),
+        };
+        let value = self.parse_value()?;
+        Ok(RuleCondition::Compare { field, op, value })
+    }
+
+    fn parse_value(&mut self) -> AionResult<FactValue> {
+        match self.next() {
+            Some(RuleToken::Text(text)) => Ok(FactValue::Text(text)),
+            Some(RuleToken::Number(number)) => Ok(FactValue::Number(number)),
+            Some(RuleToken::Identifier(word)) => Ok(match word.to_lowercase().as_str() {
+                "true" => FactValue::Bool(true),
+                "false" => FactValue::Bool(false),
+                // Bare words such as EU read as text
+                _ => FactValue::Text(word),
+            }),
+            Some(RuleToken::LeftBracket) => {
+                let mut items = Vec::new();
+                if !self.eat(&RuleToken::RightBracket) {
+                    loop {
+                        items.push(self.parse_value()?);
+                        if self.eat(&RuleToken::RightBracket) {
+                            break;
+                        }
+                        if !self.eat(&RuleToken::Comma) {
+                            return Err(rule_error("expected ',' or ']' in list".to_string()));
+                        }
+                    }
+                }
+                Ok(FactValue::List(items))
+            }
+            other => Err(rule_error(format!("expected a value, found {:?}", other))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dpia_rule_evaluation() {
+        let rule = Rule::parse(
+            "dpia",
+            "if jurisdiction == EU and personal_data == true and not (employees < 10) then dpia_required",
+        )
+        .unwrap();
+
+        let eu_processor = FactSet::new()
+            .with("jurisdiction", "eu")
+            .with("personal_data", true)
+            .with("employees", 250i64);
+        let outcome = evaluate(&rule, &eu_processor);
+        assert!(outcome.passed);
+        assert_eq!(outcome.consequences, vec!["dpia_required".to_string()]);
+
+        let us_startup = FactSet::new()
+            .with("jurisdiction", "US")
+            .with("personal_data", true)
+            .with("employees", 5i64);
+        let outcome = evaluate(&rule, &us_startup);
+        assert!(!outcome.passed);
+        assert!(outcome.consequences.is_empty());
+        assert_eq!(outcome.failures.len(), 2);
+        assert!(outcome.failures[0].condition.starts_with("jurisdiction =="));
+        assert!(outcome.failures[1].condition.starts_with("not"));
+
+        let missing = evaluate(&rule, &FactSet::new().with("jurisdiction", "EU").with("employees", 50i64));
+        assert!(missing.failures.iter().any(|f| f.reason.contains("'personal_data' is not set")));
+    }
+
+    #[test]
+    fn test_rule_round_trips_through_json() {
+        let rule = Rule::parse("sectors", "if sector in [banking, insurance] or tags contains 'critical' then report_quarterly, appoint_officer").unwrap();
+        let json = serde_json::to_string(&rule).unwrap();
+        let restored: Rule = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, rule);
+
+        let facts = FactSet::new().with("sector", "Insurance");
+        assert_eq!(evaluate(&restored, &facts).consequences.len(), 2);
+        assert!(Rule::parse("bad", "if sector == then x").is_err());
+    }
+
+    #[test]
+    fn test_reload_rules_swaps_atomically() {
+        let engine = DynamicRulesEngine::new().unwrap();
+        let definition = |id: &str, source: &str| RuleDefinition { id: id.to_string(), source: source.to_string() };
+
+        let report = engine
+            .reload_rules(RuleSource::Definitions(vec![
+                definition("dpia", "if jurisdiction == EU and personal_data == true then dpia_required"),
+                definition("dpo", "if employees >= 250 then appoint_dpo"),
+            ]))
+            .unwrap();
+        assert_eq!(report.version, 1);
+        assert_eq!(report.added.len(), 2);
+
+        let in_flight = engine.active_rules();
+
+        // One bad rule rejects the whole reload
+        let failed = engine.reload_rules(RuleSource::Definitions(vec![
+            definition("dpia", "if jurisdiction == EU then dpia_required"),
+            definition("broken", "if employees >= then x"),
+        ]));
+        assert!(failed.is_err());
+        assert_eq!(engine.active_rules().version, 1);
+
+        let report = engine
+            .reload_rules(RuleSource::Definitions(vec![
+                definition("dpia", "if jurisdiction == EU then dpia_required"),
+                definition("breach", "if breach == true then notify_authority"),
+            ]))
+            .unwrap();
+        assert_eq!(report.version, 2);
+        assert_eq!(report.added, vec!["breach".to_string()]);
+        assert_eq!(report.removed, vec!["dpo".to_string()]);
+        assert_eq!(report.changed, vec!["dpia".to_string()]);
+
+        // Evaluations that started before the swap keep the old rules
+        assert_eq!(in_flight.version, 1);
+        assert!(in_flight.rules.iter().any(|rule| rule.id == "dpo"));
+        let facts = FactSet::new().with("jurisdiction", "EU");
+        assert!(engine.evaluate_rules(&facts).iter().any(|outcome| outcome.rule_id == "dpia" && outcome.passed));
+    }
+}
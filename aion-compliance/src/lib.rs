@@ -235,9 +235,9 @@ mod tests {
     fn test_reference_id_generation() {
         use aion_core::generate_reference_id;
 
-        let compliance_ref = generate_reference_id("COMP");
-        let audit_ref = generate_reference_id("AUDIT");
-        let framework_ref = generate_reference_id("FW");
+        let compliance_ref = generate_reference_id("COMP").unwrap();
+        let audit_ref = generate_reference_id("AUDIT").unwrap();
+        let framework_ref = generate_reference_id("FW").unwrap();
 
         assert!(compliance_ref.starts_with("COMP-"));
         assert!(audit_ref.starts_with("AUDIT-"));
@@ -1,12 +1,15 @@
-use aion_core::{AionResult, NormativeFramework, AionError};
+use aion_core::{AionResult, NormativeFramework, AionError, NormativeId, Requirement, calculate_similarity};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::LazyLock;
 use chrono::{DateTime, Utc, Duration};
 use uuid::Uuid;
 use tokio::time::{interval, Duration as TokioDuration};
 use reqwest::Client;
 use regex::Regex;
 
+use crate::alert_notification_system::{AlertSeverity, AlertSource, IncomingAlertData};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegulatoryUpdate {
     pub id: Uuid,
@@ -1415,4 +1418,433 @@ pub enum EntityType {
     Regulation,
     Currency,
     Percentage,
-}
\ No newline at end of file
+}
+// Clause-level diffing between two versions of a framework
+
+/// Lowest keyword similarity at which two clauses count as the same provision
+const CLAUSE_MATCH_THRESHOLD: f64 = 0.6;
+
+/// Longest clause text quoted in a change summary
+const SUMMARY_QUOTE_CHARS: usize = 160;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClauseChangeType {
+    Added,
+    Removed,
+    Modified,
+    /// Moved to a new number with its text unchanged
+    Renumbered,
+}
+
+/// A run of words in a word-level diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffSegment {
+    Unchanged(String),
+    Added(String),
+    Removed(String),
+}
+
+/// One clause that differs between two versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequirementChange {
+    pub change_type: ClauseChangeType,
+    /// Clause number in the new version, or in the old one for removals
+    pub clause: String,
+    /// Previous clause number when the clause was renumbered
+    pub renumbered_from: Option<String>,
+    pub requirement_id: Uuid,
+    pub old_text: Option<String>,
+    pub new_text: Option<String>,
+    pub text_diff: Vec<DiffSegment>,
+    pub mandatory_before: Option<bool>,
+    pub mandatory_after: Option<bool>,
+    pub severity: UpdateSeverity,
+    /// One-line description suitable for a notification
+    pub summary: String,
+}
+
+/// What changed between two versions of a regulation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegulatoryDiff {
+    pub framework_id: NormativeId,
+    pub framework_title: String,
+    pub old_version: String,
+    pub new_version: String,
+    pub changes: Vec<RequirementChange>,
+}
+
+impl RegulatoryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The most severe change, or `Informational` when nothing substantive changed
+    pub fn severity(&self) -> UpdateSeverity {
+        self.changes
+            .iter()
+            .map(|change| change.severity.clone())
+            .min_by_key(severity_rank)
+            .unwrap_or(UpdateSeverity::Informational)
+    }
+
+    pub fn summaries(&self) -> Vec<String> {
+        self.changes.iter().map(|change| change.summary.clone()).collect()
+    }
+
+    /// One alert per changed clause, for `AlertNotificationSystem::process_regulatory_diff`
+    pub fn alerts(&self) -> Vec<IncomingAlertData> {
+        self.changes
+            .iter()
+            .map(|change| IncomingAlertData {
+                title: format!("{}: {}", self.framework_title, change.summary),
+                description: format!(
+                    "{} changed from version {} to {}. {}",
+                    self.framework_title, self.old_version, self.new_version, change.summary
+                ),
+                severity: match change.severity {
+                    UpdateSeverity::Critical => AlertSeverity::Critical,
+                    UpdateSeverity::High => AlertSeverity::High,
+                    UpdateSeverity::Medium => AlertSeverity::Medium,
+                    UpdateSeverity::Low => AlertSeverity::Low,
+                    UpdateSeverity::Informational => AlertSeverity::Info,
+                },
+                source: AlertSource {
+                    system: "aion-compliance".to_string(),
+                    component: "regulatory_monitor".to_string(),
+                    instance: self.framework_id.0.to_string(),
+                    location: change.clause.clone(),
+                },
+                timestamp: Some(Utc::now()),
+                metadata: HashMap::from([
+                    ("framework_id".to_string(), self.framework_id.0.to_string()),
+                    ("requirement_id".to_string(), change.requirement_id.to_string()),
+                    ("clause".to_string(), change.clause.clone()),
+                    ("change_type".to_string(), format!("{:?}", change.change_type)),
+                    ("old_version".to_string(), self.old_version.clone()),
+                    ("new_version".to_string(), self.new_version.clone()),
+                ]),
+                affected_entities: vec![self.framework_id.0.to_string()],
+                rule_id: Some("regulatory_change".to_string()),
+            })
+            .collect()
+    }
+}
+
+/// Compare two versions of a framework clause by clause
+///
+/// Clauses are paired by requirement id, then by text similarity, so a
+/// clause that moved to a new number is reported as renumbered rather than
+/// removed and re-added; clauses left over are paired by number.
+pub fn diff_versions(old: &NormativeFramework, new: &NormativeFramework) -> RegulatoryDiff {
+    let mut old_left: Vec<&Requirement> = old.requirements.iter().collect();
+    let mut new_left: Vec<&Requirement> = new.requirements.iter().collect();
+    let mut pairs: Vec<(&Requirement, &Requirement)> = Vec::new();
+
+    // Same requirement id
+    old_left.retain(|before| match new_left.iter().position(|after| after.id == before.id) {
+        Some(index) => {
+            pairs.push((*before, new_left.remove(index)));
+            false
+        }
+        None => true,
+    });
+
+    // Most similar text first, so renumbered clauses find their counterpart
+    let mut candidates: Vec<(f64, usize, usize)> = Vec::new();
+    for (i, before) in old_left.iter().enumerate() {
+        for (j, after) in new_left.iter().enumerate() {
+            let similarity = calculate_similarity(&before.description, &after.description);
+            if similarity >= CLAUSE_MATCH_THRESHOLD {
+                candidates.push((similarity, i, j));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let (mut old_used, mut new_used) = (vec![false; old_left.len()], vec![false; new_left.len()]);
+    for (_, i, j) in candidates {
+        if !old_used[i] && !new_used[j] {
+            old_used[i] = true;
+            new_used[j] = true;
+            pairs.push((old_left[i], new_left[j]));
+        }
+    }
+    let mut old_rest: Vec<&Requirement> = old_left.iter().zip(&old_used).filter(|(_, used)| !**used).map(|(r, _)| *r).collect();
+    let mut new_rest: Vec<&Requirement> = new_left.iter().zip(&new_used).filter(|(_, used)| !**used).map(|(r, _)| *r).collect();
+
+    // Same clause number, rewritten beyond recognition
+    old_rest.retain(|before| {
+        let label = clause_label(before);
+        match new_rest.iter().position(|after| clause_label(after) == label) {
+            Some(index) => {
+                pairs.push((*before, new_rest.remove(index)));
+                false
+            }
+            None => true,
+        }
+    });
+
+    let mut changes: Vec<RequirementChange> = pairs
+        .into_iter()
+        .filter_map(|(before, after)| modified_clause(before, after))
+        .collect();
+    changes.extend(new_rest.into_iter().map(added_clause));
+    changes.extend(old_rest.into_iter().map(removed_clause));
+
+    // Report in the order clauses appear in the new version, removals last
+    let position = |change: &RequirementChange| {
+        new.requirements
+            .iter()
+            .position(|requirement| requirement.id == change.requirement_id && change.change_type != ClauseChangeType::Removed)
+            .unwrap_or(usize::MAX)
+    };
+    changes.sort_by_key(position);
+
+    RegulatoryDiff {
+        framework_id: new.id.clone(),
+        framework_title: new.title.clone(),
+        old_version: old.version.clone(),
+        new_version: new.version.clone(),
+        changes,
+    }
+}
+
+fn modified_clause(before: &Requirement, after: &Requirement) -> Option<RequirementChange> {
+    let (old_label, new_label) = (clause_label(before), clause_label(after));
+    let renumbered_from = (old_label != new_label).then_some(old_label);
+    let text_changed = before.description.split_whitespace().ne(after.description.split_whitespace());
+    let mandatory_changed = before.mandatory != after.mandatory;
+    if !text_changed && !mandatory_changed && renumbered_from.is_none() {
+        return None;
+    }
+
+    let text_diff = word_diff(&before.description, &after.description);
+    let mut summary = match &renumbered_from {
+        Some(old_label) => format!("{} (formerly {})", new_label, old_label),
+        None => new_label.clone(),
+    };
+    if text_changed {
+        let added = diff_text(&text_diff, |segment| matches!(segment, DiffSegment::Added(_)));
+        let removed = diff_text(&text_diff, |segment| matches!(segment, DiffSegment::Removed(_)));
+        summary.push_str(&match (added.is_empty(), removed.is_empty()) {
+            (false, true) => format!(" added \"{}\"", added.join("\", \"")),
+            (true, false) => format!(" removed \"{}\"", removed.join("\", \"")),
+            _ => format!(" changed \"{}\" to \"{}\"", removed.join("\", \""), added.join("\", \"")),
+        });
+    } else if !mandatory_changed {
+        summary = format!("{} renumbered as {}", renumbered_from.as_deref().unwrap_or_default(), new_label);
+    }
+    if mandatory_changed {
+        summary.push_str(if after.mandatory { ", now mandatory" } else { ", no longer mandatory" });
+    }
+
+    let change_type = if text_changed || mandatory_changed {
+        ClauseChangeType::Modified
+    } else {
+        ClauseChangeType::Renumbered
+    };
+    let severity = match change_type {
+        ClauseChangeType::Renumbered => UpdateSeverity::Informational,
+        _ if after.mandatory || before.mandatory => UpdateSeverity::High,
+        _ => UpdateSeverity::Low,
+    };
+
+    Some(RequirementChange {
+        change_type,
+        clause: new_label,
+        renumbered_from,
+        requirement_id: after.id,
+        old_text: Some(before.description.clone()),
+        new_text: Some(after.description.clone()),
+        text_diff,
+        mandatory_before: Some(before.mandatory),
+        mandatory_after: Some(after.mandatory),
+        severity,
+        summary,
+    })
+}
+
+fn added_clause(requirement: &Requirement) -> RequirementChange {
+    let clause = clause_label(requirement);
+    RequirementChange {
+        change_type: ClauseChangeType::Added,
+        summary: format!("{} added: \"{}\"", clause, quote(&requirement.description)),
+        clause,
+        renumbered_from: None,
+        requirement_id: requirement.id,
+        old_text: None,
+        new_text: Some(requirement.description.clone()),
+        text_diff: vec![DiffSegment::Added(requirement.description.clone())],
+        mandatory_before: None,
+        mandatory_after: Some(requirement.mandatory),
+        severity: if requirement.mandatory { UpdateSeverity::High } else { UpdateSeverity::Low },
+    }
+}
+
+fn removed_clause(requirement: &Requirement) -> RequirementChange {
+    let clause = clause_label(requirement);
+    RequirementChange {
+        change_type: ClauseChangeType::Removed,
+        summary: format!("{} removed: \"{}\"", clause, quote(&requirement.description)),
+        clause,
+        renumbered_from: None,
+        requirement_id: requirement.id,
+        old_text: Some(requirement.description.clone()),
+        new_text: None,
+        text_diff: vec![DiffSegment::Removed(requirement.description.clone())],
+        mandatory_before: Some(requirement.mandatory),
+        mandatory_after: None,
+        severity: if requirement.mandatory { UpdateSeverity::Medium } else { UpdateSeverity::Low },
+    }
+}
+
+static CLAUSE_LABEL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*((?:article|art\.|section|sec\.|§|clause|rule|paragraph)\s*\d+[a-z]?(?:\(\w+\))*)")
+        .expect("clause pattern is valid")
+});
+
+/// Clause number from a requirement title such as "Article 17(3) - Erasure", else the whole title
+fn clause_label(requirement: &Requirement) -> String {
+    CLAUSE_LABEL
+        .captures(&requirement.title)
+        .map(|captures| captures[1].to_string())
+        .unwrap_or_else(|| requirement.title.trim().to_string())
+}
+
+/// Word-level diff by longest common subsequence
+fn word_diff(old: &str, new: &str) -> Vec<DiffSegment> {
+    let before: Vec<&str> = old.split_whitespace().collect();
+    let after: Vec<&str> = new.split_whitespace().collect();
+    let mut common = vec![vec![0u32; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            common[i][j] = if before[i] == after[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    let mut push = |segment: DiffSegment| {
+        match (segments.last_mut(), &segment) {
+            (Some(DiffSegment::Unchanged(text)), DiffSegment::Unchanged(word))
+            | (Some(DiffSegment::Added(text)), DiffSegment::Added(word))
+            | (Some(DiffSegment::Removed(text)), DiffSegment::Removed(word)) => {
+                text.push(' ');
+                text.push_str(word);
+                return;
+            }
+            _ => {}
+        }
+        segments.push(segment);
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < before.len() || j < after.len() {
+        if i < before.len() && j < after.len() && before[i] == after[j] {
+            push(DiffSegment::Unchanged(before[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if j < after.len() && (i == before.len() || common[i][j + 1] >= common[i + 1][j]) {
+            push(DiffSegment::Added(after[j].to_string()));
+            j += 1;
+        } else {
+            push(DiffSegment::Removed(before[i].to_string()));
+            i += 1;
+        }
+    }
+    segments
+}
+
+fn diff_text(diff: &[DiffSegment], keep: impl Fn(&DiffSegment) -> bool) -> Vec<String> {
+    diff.iter()
+        .filter(|segment| keep(segment))
+        .map(|segment| match segment {
+            DiffSegment::Unchanged(text) | DiffSegment::Added(text) | DiffSegment::Removed(text) => quote(text),
+        })
+        .collect()
+}
+
+fn quote(text: &str) -> String {
+    if text.chars().count() <= SUMMARY_QUOTE_CHARS {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(SUMMARY_QUOTE_CHARS).collect::<String>())
+    }
+}
+
+fn severity_rank(severity: &UpdateSeverity) -> u8 {
+    match severity {
+        UpdateSeverity::Critical => 0,
+        UpdateSeverity::High => 1,
+        UpdateSeverity::Medium => 2,
+        UpdateSeverity::Low => 3,
+        UpdateSeverity::Informational => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aion_core::{testing, Jurisdiction};
+
+    fn clause(title: &str, description: &str) -> Requirement {
+        testing::requirement(title, description)
+    }
+
+    fn version(version: &str, requirements: Vec<Requirement>) -> NormativeFramework {
+        let mut framework = testing::framework("GDPR", Jurisdiction::Regional, requirements);
+        framework.description = "General Data Protection Regulation".to_string();
+        framework.authority = "European Union".to_string();
+        framework.version = version.to_string();
+        framework
+    }
+
+    #[test]
+    fn test_diff_reports_clause_level_changes() {
+        let erasure = clause("Article 16 - Erasure", "Controllers must erase personal data without undue delay on request");
+        let breach = clause("Article 17 - Breach notification", "Controllers must notify the supervisory authority of a personal data breach");
+        let records = clause("Article 18 - Records", "Controllers must keep records of processing activities");
+        let old = version("2016", vec![erasure.clone(), breach.clone(), records.clone()]);
+
+        let mut amended_breach = breach.clone();
+        amended_breach.id = Uuid::new_v4();
+        amended_breach.description = "Controllers must notify the supervisory authority of a personal data breach within 72 hours".to_string();
+        let mut moved_records = records.clone();
+        moved_records.id = Uuid::new_v4();
+        moved_records.title = "Article 19 - Records".to_string();
+        let portability = clause("Article 18 - Portability", "Data subjects may receive their personal data in a machine-readable format");
+        let new = version("2018", vec![amended_breach.clone(), portability, moved_records]);
+
+        let diff = diff_versions(&old, &new);
+        let kinds: Vec<(ClauseChangeType, &str)> =
+            diff.changes.iter().map(|change| (change.change_type, change.clause.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (ClauseChangeType::Modified, "Article 17"),
+                (ClauseChangeType::Added, "Article 18"),
+                (ClauseChangeType::Renumbered, "Article 19"),
+                (ClauseChangeType::Removed, "Article 16"),
+            ]
+        );
+        assert_eq!(diff.changes[0].summary, "Article 17 added \"within 72 hours\"");
+        assert_eq!(diff.changes[0].requirement_id, amended_breach.id);
+        assert_eq!(diff.changes[2].renumbered_from.as_deref(), Some("Article 18"));
+        assert_eq!(diff.changes[2].summary, "Article 18 renumbered as Article 19");
+        assert!(matches!(diff.severity(), UpdateSeverity::High));
+
+        let alerts = diff.alerts();
+        assert_eq!(alerts.len(), 4);
+        assert_eq!(alerts[0].title, "GDPR: Article 17 added \"within 72 hours\"");
+    }
+
+    #[test]
+    fn test_identical_versions_have_no_changes() {
+        let old = version("1", vec![clause("Section 1", "Keep records for seven years")]);
+        let mut new = old.clone();
+        new.version = "2".to_string();
+        assert!(diff_versions(&old, &new).is_empty());
+    }
+}
@@ -3,10 +3,204 @@ use aion_core::{
     ConflictDetector, ConflictType, ConflictSeverity, ResolutionStrategy
 };
 use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use rayon::prelude::*;
 
+use crate::strategies::{SemanticSimilarityStrategy, SimilarityConflict};
+
+/// Detection algorithms, named as in the CLI's `--algorithm` choices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionAlgorithm {
+    /// Every detector: requirement, authority, scope, hierarchical, temporal and jurisdictional
+    MlOptimization,
+    /// Structural conflicts in the dependency graph between rules
+    GraphAnalysis,
+    /// Pairwise similarity of requirement text, via `SemanticSimilarityStrategy`
+    SemanticSimilarity,
+}
+
+impl DetectionAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DetectionAlgorithm::MlOptimization => "ml_optimization",
+            DetectionAlgorithm::GraphAnalysis => "graph_analysis",
+            DetectionAlgorithm::SemanticSimilarity => "semantic_similarity",
+        }
+    }
+}
+
+impl FromStr for DetectionAlgorithm {
+    type Err = AionError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "ml_optimization" => Ok(DetectionAlgorithm::MlOptimization),
+            "graph_analysis" => Ok(DetectionAlgorithm::GraphAnalysis),
+            "semantic_similarity" => Ok(DetectionAlgorithm::SemanticSimilarity),
+            other => Err(AionError::ConfigurationError {
+                parameter: "algorithm".to_string(),
+                reason: format!(
+                    "unknown detection algorithm '{}'; expected ml_optimization, graph_analysis or semantic_similarity",
+                    other
+                ),
+            }),
+        }
+    }
+}
+
+/// A conflict between two rules found by `detect_conflicts`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conflict {
+    pub id: Uuid,
+    pub rule_a: NormativeId,
+    pub rule_b: NormativeId,
+    /// `rule_a` as it stood when the conflict was detected
+    pub framework_a: NormativeFramework,
+    /// `rule_b` as it stood when the conflict was detected
+    pub framework_b: NormativeFramework,
+    pub conflict_type: ConflictType,
+    pub severity: ConflictSeverity,
+    pub explanation: String,
+    pub affected_requirements: Vec<Uuid>,
+    pub suggested_strategy: Option<ResolutionStrategy>,
+    pub algorithm: DetectionAlgorithm,
+    /// Text similarity of the two rules, for similarity-based detection
+    pub similarity: Option<f64>,
+    #[serde(default)]
+    pub resolution_status: ConflictStatus,
+}
+
+/// Where a detected conflict stands in being resolved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStatus {
+    #[default]
+    Unresolved,
+    /// A resolution was proposed and awaits review
+    PendingReview,
+    Resolved,
+}
+
+/// Detect conflicts between rules in-process
+///
+/// Each pair of rules is reported at most once per conflict type, keeping
+/// the most severe finding; results are ordered from most to least severe.
+pub fn detect_conflicts(rules: &[NormativeFramework], algorithm: DetectionAlgorithm) -> AionResult<Vec<Conflict>> {
+    let mut seen_ids = HashSet::new();
+    for rule in rules {
+        if !seen_ids.insert(&rule.id) {
+            return Err(AionError::InvalidFrameworkStructure {
+                field: "id".to_string(),
+                reason: format!("rule {} appears more than once", rule.id.0),
+            });
+        }
+    }
+
+    let mut detector = AdvancedConflictDetector::new();
+    let found = match algorithm {
+        DetectionAlgorithm::MlOptimization => detector.detect_all_conflicts(rules)?,
+        DetectionAlgorithm::GraphAnalysis => {
+            let mut found = detector.detect_hierarchical_conflicts(rules)?;
+            found.extend(detector.detect_dependency_cycles(rules));
+            found
+        }
+        DetectionAlgorithm::SemanticSimilarity => SemanticSimilarityStrategy::new()
+            .detect(rules)
+            .into_iter()
+            .map(NormativeConflict::from)
+            .collect(),
+    };
+
+    // Keep the most severe finding per unordered pair and conflict type
+    let mut strongest: HashMap<(Uuid, Uuid, ConflictType), NormativeConflict> = HashMap::new();
+    for conflict in found {
+        let (a, b) = (conflict.normative_a.0, conflict.normative_b.0);
+        let key = (a.min(b), a.max(b), conflict.conflict_type.clone());
+        let replace = strongest.get(&key).is_none_or(|existing| {
+            detector.severity_priority(&conflict.severity) > detector.severity_priority(&existing.severity)
+        });
+        if replace {
+            strongest.insert(key, conflict);
+        }
+    }
+
+    let by_id: HashMap<&NormativeId, &NormativeFramework> = rules.iter().map(|rule| (&rule.id, rule)).collect();
+    let mut conflicts: Vec<Conflict> = strongest
+        .into_values()
+        .filter_map(|conflict| {
+            let framework_a = (*by_id.get(&conflict.normative_a)?).clone();
+            let framework_b = (*by_id.get(&conflict.normative_b)?).clone();
+            Some(Conflict {
+                id: conflict.id,
+                explanation: format!(
+                    "{} ('{}' vs '{}')",
+                    conflict.description, framework_a.title, framework_b.title
+                ),
+                rule_a: conflict.normative_a,
+                rule_b: conflict.normative_b,
+                framework_a,
+                framework_b,
+                conflict_type: conflict.conflict_type,
+                severity: conflict.severity,
+                affected_requirements: conflict.affected_requirements,
+                suggested_strategy: conflict.resolution_strategy,
+                algorithm,
+                similarity: conflict.context.get("similarity_score").and_then(|score| score.parse().ok()),
+                resolution_status: ConflictStatus::Unresolved,
+            })
+        })
+        .collect();
+    conflicts.sort_by(|a, b| {
+        detector
+            .severity_priority(&b.severity)
+            .cmp(&detector.severity_priority(&a.severity))
+            .then_with(|| (a.rule_a.0, a.rule_b.0).cmp(&(b.rule_a.0, b.rule_b.0)))
+    });
+
+    Ok(conflicts)
+}
+
+impl From<SimilarityConflict> for NormativeConflict {
+    fn from(conflict: SimilarityConflict) -> Self {
+        let (conflict_type, resolution_strategy) = if conflict.opposing_obligations {
+            (ConflictType::DirectContradiction, ResolutionStrategy::LexSuperior)
+        } else {
+            (ConflictType::ImplicitConflict, ResolutionStrategy::Harmonization)
+        };
+        let severity = if conflict.confidence >= 0.8 {
+            ConflictSeverity::High
+        } else if conflict.confidence >= 0.5 {
+            ConflictSeverity::Medium
+        } else {
+            ConflictSeverity::Low
+        };
+
+        NormativeConflict {
+            id: Uuid::new_v4(),
+            conflict_type,
+            severity,
+            normative_a: conflict.rule_a.clone(),
+            normative_b: conflict.rule_b.clone(),
+            involved_frameworks: vec![conflict.rule_a, conflict.rule_b],
+            description: conflict.explanation,
+            affected_requirements: conflict.requirement_a.into_iter().chain(conflict.requirement_b).collect(),
+            context: HashMap::from([
+                ("similarity_score".to_string(), conflict.similarity.to_string()),
+                ("confidence".to_string(), conflict.confidence.to_string()),
+            ]),
+            discovered_at: Utc::now(),
+            resolution_strategy: Some(resolution_strategy),
+            resolution_notes: None,
+            resolved_at: None,
+            resolved_by: None,
+        }
+    }
+}
+
 pub struct AdvancedConflictDetector {
     conflict_cache: HashMap<(NormativeId, NormativeId), Option<NormativeConflict>>,
     similarity_threshold: f64,
@@ -341,6 +535,52 @@ impl AdvancedConflictDetector {
         Ok(conflicts)
     }
 
+    /// Rules that depend on each other, directly or transitively, can never both take effect first
+    fn detect_dependency_cycles(&self, frameworks: &[NormativeFramework]) -> Vec<NormativeConflict> {
+        let dependencies: HashMap<&NormativeId, &Vec<NormativeId>> =
+            frameworks.iter().map(|f| (&f.id, &f.dependencies)).collect();
+
+        let reaches = |from: &NormativeId, to: &NormativeId| {
+            let mut stack = vec![from];
+            let mut visited = HashSet::new();
+            while let Some(current) = stack.pop() {
+                if current == to {
+                    return true;
+                }
+                if visited.insert(current) {
+                    stack.extend(dependencies.get(current).into_iter().flat_map(|deps| deps.iter()));
+                }
+            }
+            false
+        };
+
+        let mut conflicts = Vec::new();
+        for framework in frameworks {
+            for dep_id in &framework.dependencies {
+                if dep_id != &framework.id && dependencies.contains_key(dep_id) && reaches(dep_id, &framework.id) {
+                    conflicts.push(NormativeConflict {
+                        id: Uuid::new_v4(),
+                        conflict_type: ConflictType::ImplementationConflict,
+                        severity: ConflictSeverity::High,
+                        normative_a: framework.id.clone(),
+                        normative_b: dep_id.clone(),
+                        involved_frameworks: vec![framework.id.clone(), dep_id.clone()],
+                        description: "Circular dependency between frameworks".to_string(),
+                        affected_requirements: vec![],
+                        context: HashMap::new(),
+                        discovered_at: Utc::now(),
+                        resolution_strategy: Some(ResolutionStrategy::LexSuperior),
+                        resolution_notes: None,
+                        resolved_at: None,
+                        resolved_by: None,
+                    });
+                }
+            }
+        }
+
+        conflicts
+    }
+
     fn detect_temporal_conflicts(&self, frameworks: &[NormativeFramework]) -> AionResult<Vec<NormativeConflict>> {
         let mut conflicts = Vec::new();
 
@@ -527,4 +767,80 @@ impl SemanticAnalyzer {
     fn calculate_similarity(&self, text1: &str, text2: &str) -> f64 {
         aion_core::calculate_similarity(text1, text2)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aion_core::{testing, Jurisdiction};
+
+    fn framework(title: &str, authority: &str, requirement: &str, mandatory: bool) -> NormativeFramework {
+        let mut retention = testing::requirement("Record retention", requirement);
+        retention.mandatory = mandatory;
+        let mut framework = testing::framework(title, Jurisdiction::Federal, vec![retention]);
+        framework.authority = authority.to_string();
+        framework.description = format!("{} rules for customer data retention", authority);
+        framework.tags = vec!["data-retention".to_string()];
+        framework
+    }
+
+    #[test]
+    fn test_contradictory_pair_is_detected() {
+        let text = "Financial institutions must retain customer transaction records for seven years";
+        let rules = vec![
+            framework("Bank Secrecy Rule", "FinCEN", text, true),
+            framework("Records Guidance", "OCC", text, false),
+        ];
+
+        let conflicts = detect_conflicts(&rules, DetectionAlgorithm::SemanticSimilarity).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.conflict_type, ConflictType::DirectContradiction);
+        assert_eq!(conflict.severity, ConflictSeverity::High);
+        assert_eq!((&conflict.rule_a, &conflict.rule_b), (&rules[0].id, &rules[1].id));
+        assert!(conflict.explanation.contains("opposing obligations ('Bank Secrecy Rule' vs 'Records Guidance')"));
+        assert_eq!(conflict.similarity, Some(1.0));
+        assert_eq!(conflict.affected_requirements, vec![rules[0].requirements[0].id, rules[1].requirements[0].id]);
+    }
+
+    #[test]
+    fn test_unrelated_rules_do_not_conflict() {
+        let mut rules = vec![
+            framework("Retention Rule", "FinCEN", "Retain transaction records for seven years", true),
+            framework("Emissions Rule", "EPA", "Report quarterly methane emissions from wells", false),
+        ];
+        rules[1].jurisdiction = Jurisdiction::State;
+        rules[1].tags = vec!["emissions".to_string()];
+
+        assert!(detect_conflicts(&rules, DetectionAlgorithm::SemanticSimilarity).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_graph_analysis_reports_dependency_cycle_once() {
+        let mut rules = vec![
+            framework("Rule A", "FinCEN", "Retain records", true),
+            framework("Rule B", "OCC", "Report holdings", true),
+        ];
+        rules[0].dependencies = vec![rules[1].id.clone()];
+        rules[1].dependencies = vec![rules[0].id.clone()];
+
+        let conflicts = detect_conflicts(&rules, DetectionAlgorithm::GraphAnalysis).unwrap();
+        let cycles: Vec<_> = conflicts
+            .iter()
+            .filter(|conflict| conflict.conflict_type == ConflictType::ImplementationConflict)
+            .collect();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].algorithm, DetectionAlgorithm::GraphAnalysis);
+    }
+
+    #[test]
+    fn test_algorithm_names_match_cli() {
+        for name in ["ml_optimization", "graph_analysis", "semantic_similarity"] {
+            assert_eq!(name.parse::<DetectionAlgorithm>().unwrap().as_str(), name);
+        }
+        assert!("neural".parse::<DetectionAlgorithm>().is_err());
+
+        let rule = framework("Rule A", "FinCEN", "Retain records", true);
+        assert!(detect_conflicts(&[rule.clone(), rule], DetectionAlgorithm::MlOptimization).is_err());
+    }
+}
@@ -1,6 +1,11 @@
 use aion_core::types::*;
 use aion_core::{AionResult};
 use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::detector::{Conflict, ConflictStatus};
 
 /// Conflict graph for analyzing complex regulatory conflicts
 pub struct ConflictGraph {
@@ -198,4 +203,184 @@ impl Default for ConflictGraph {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// A rule in an exported conflict map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictMapNode {
+    pub id: NormativeId,
+    pub title: String,
+    pub jurisdiction: Jurisdiction,
+    pub authority: String,
+    pub conflict_count: usize,
+}
+
+/// A conflict between two rules in an exported conflict map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictMapEdge {
+    pub conflict_id: Uuid,
+    pub source: NormativeId,
+    pub target: NormativeId,
+    pub conflict_type: ConflictType,
+    pub severity: ConflictSeverity,
+    pub resolution_status: ConflictStatus,
+    pub explanation: String,
+}
+
+/// Rules and the conflicts between them, for rendering without a server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictMap {
+    pub nodes: Vec<ConflictMapNode>,
+    pub edges: Vec<ConflictMapEdge>,
+}
+
+/// Build the conflict map for detected conflicts; nodes appear in first-seen order
+pub fn conflict_map(conflicts: &[Conflict]) -> ConflictMap {
+    let mut nodes: Vec<ConflictMapNode> = Vec::new();
+    let mut positions: HashMap<NormativeId, usize> = HashMap::new();
+
+    for conflict in conflicts {
+        for framework in [&conflict.framework_a, &conflict.framework_b] {
+            let position = *positions.entry(framework.id.clone()).or_insert_with(|| {
+                nodes.push(ConflictMapNode {
+                    id: framework.id.clone(),
+                    title: framework.title.clone(),
+                    jurisdiction: framework.jurisdiction.clone(),
+                    authority: framework.authority.clone(),
+                    conflict_count: 0,
+                });
+                nodes.len() - 1
+            });
+            nodes[position].conflict_count += 1;
+        }
+    }
+
+    let edges = conflicts
+        .iter()
+        .map(|conflict| ConflictMapEdge {
+            conflict_id: conflict.id,
+            source: conflict.rule_a.clone(),
+            target: conflict.rule_b.clone(),
+            conflict_type: conflict.conflict_type.clone(),
+            severity: conflict.severity.clone(),
+            resolution_status: conflict.resolution_status,
+            explanation: conflict.explanation.clone(),
+        })
+        .collect();
+
+    ConflictMap { nodes, edges }
+}
+
+/// Render conflicts as a GraphViz DOT graph
+///
+/// Nodes are rules and edges are conflicts, colored and labelled by
+/// severity. Unresolved conflicts are solid, those pending review dashed,
+/// and resolved ones dotted and grey.
+pub fn export_dot(conflicts: &[Conflict]) -> String {
+    let map = conflict_map(conflicts);
+    let mut dot = String::from("graph conflicts {\n");
+    dot.push_str("    node [shape=box, style=rounded];\n");
+
+    for node in &map.nodes {
+        let _ = writeln!(
+            dot,
+            "    \"{}\" [label=\"{}\\n{:?} · {}\"];",
+            node.id.0,
+            escape_dot(&node.title),
+            node.jurisdiction,
+            escape_dot(&node.authority)
+        );
+    }
+
+    for edge in &map.edges {
+        let (style, color) = match edge.resolution_status {
+            ConflictStatus::Unresolved => ("solid", severity_color(&edge.severity)),
+            ConflictStatus::PendingReview => ("dashed", severity_color(&edge.severity)),
+            ConflictStatus::Resolved => ("dotted", "grey60"),
+        };
+        let _ = writeln!(
+            dot,
+            "    \"{}\" -- \"{}\" [label=\"{:?}\\n{:?}\", color=\"{}\", style={}, penwidth={}, tooltip=\"{}\"];",
+            edge.source.0,
+            edge.target.0,
+            edge.severity,
+            edge.conflict_type,
+            color,
+            style,
+            severity_pen_width(&edge.severity),
+            escape_dot(&edge.explanation)
+        );
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Render conflicts as the JSON form of `ConflictMap`
+pub fn export_json(conflicts: &[Conflict]) -> String {
+    serde_json::to_string_pretty(&conflict_map(conflicts)).expect("conflict map contains only serializable fields")
+}
+
+fn severity_color(severity: &ConflictSeverity) -> &'static str {
+    match severity {
+        ConflictSeverity::Critical => "red3",
+        ConflictSeverity::High => "orangered",
+        ConflictSeverity::Medium => "orange",
+        ConflictSeverity::Low => "gold3",
+        ConflictSeverity::Informational => "steelblue",
+    }
+}
+
+fn severity_pen_width(severity: &ConflictSeverity) -> u8 {
+    match severity {
+        ConflictSeverity::Critical => 4,
+        ConflictSeverity::High => 3,
+        ConflictSeverity::Medium => 2,
+        ConflictSeverity::Low | ConflictSeverity::Informational => 1,
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::{detect_conflicts, DetectionAlgorithm};
+    use aion_core::testing;
+
+    fn framework(title: &str, jurisdiction: Jurisdiction) -> NormativeFramework {
+        let retention = testing::requirement(
+            "Record retention",
+            "Financial institutions must retain customer transaction records for seven years",
+        );
+        let mut framework = testing::framework(title, jurisdiction, vec![retention]);
+        framework.authority = "FinCEN".to_string();
+        framework.description = "Customer data retention".to_string();
+        framework
+    }
+
+    #[test]
+    fn test_export_styles_edges_by_severity_and_status() {
+        let rules = vec![
+            framework("Bank \"Secrecy\" Rule", Jurisdiction::Federal),
+            framework("State Retention Act", Jurisdiction::State),
+        ];
+        let mut conflicts = detect_conflicts(&rules, DetectionAlgorithm::SemanticSimilarity).unwrap();
+        assert_eq!(conflicts.len(), 1);
+
+        let dot = export_dot(&conflicts);
+        assert!(dot.starts_with("graph conflicts {"));
+        assert!(dot.contains("Bank \\\"Secrecy\\\" Rule"));
+        assert!(dot.contains("style=solid"));
+
+        conflicts[0].resolution_status = ConflictStatus::Resolved;
+        assert!(export_dot(&conflicts).contains("color=\"grey60\", style=dotted"));
+
+        let map: ConflictMap = serde_json::from_str(&export_json(&conflicts)).unwrap();
+        assert_eq!(map.nodes.len(), 2);
+        assert!(map.nodes.iter().all(|node| node.conflict_count == 1));
+        assert_eq!(map.edges[0].resolution_status, ConflictStatus::Resolved);
+    }
+}
@@ -1,9 +1,295 @@
 use aion_core::{
     AionError, AionResult, NormativeFramework, NormativeConflict, ConflictResolver,
-    ResolutionStrategy, ConflictType, ConflictSeverity, Jurisdiction
+    ResolutionStrategy, ConflictType, ConflictSeverity, Jurisdiction, NormativeId,
+    NormativeType, Requirement
 };
+use aion_normative::HierarchyManager;
 use std::collections::HashMap;
+use std::str::FromStr;
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::detector::{Conflict, ConflictStatus};
+
+/// Least confidence at which a proposal is applied without review
+pub const AUTO_APPLY_CONFIDENCE: f64 = 0.7;
+
+/// Resolution modes, named as in the CLI's `--strategy` choices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionMode {
+    /// Decide precedence and apply the edits; fails when the decision is not confident enough
+    Automatic,
+    /// Recommend precedence and edits for a reviewer to accept or reject
+    Manual,
+    /// Apply confident proposals, send the rest for review
+    Hybrid,
+}
+
+impl ResolutionMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResolutionMode::Automatic => "automatic",
+            ResolutionMode::Manual => "manual",
+            ResolutionMode::Hybrid => "hybrid",
+        }
+    }
+}
+
+impl FromStr for ResolutionMode {
+    type Err = AionError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "automatic" => Ok(ResolutionMode::Automatic),
+            "manual" => Ok(ResolutionMode::Manual),
+            "hybrid" => Ok(ResolutionMode::Hybrid),
+            other => Err(AionError::ConfigurationError {
+                parameter: "strategy".to_string(),
+                reason: format!("unknown resolution strategy '{}'; expected automatic, manual or hybrid", other),
+            }),
+        }
+    }
+}
+
+/// A change to the yielding rule that removes the conflict
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestedEdit {
+    pub rule_id: NormativeId,
+    /// Requirement to change, or `None` for a change to the rule itself
+    pub requirement_id: Option<Uuid>,
+    pub field: String,
+    pub current: String,
+    pub proposed: String,
+    pub reason: String,
+}
+
+/// Proposed resolution of one detected conflict
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionProposal {
+    pub conflict_id: Uuid,
+    pub mode: ResolutionMode,
+    /// Legal principle used to decide precedence
+    pub principle: ResolutionStrategy,
+    pub prevailing_rule: NormativeId,
+    pub yielding_rule: NormativeId,
+    pub edits: Vec<SuggestedEdit>,
+    pub confidence: f64,
+    /// The precedence rule applied and why it decides this pair
+    pub rationale: String,
+    pub requires_review: bool,
+    pub auto_apply: bool,
+}
+
+impl ResolutionProposal {
+    /// Status of the conflict once this proposal is accepted
+    pub fn status(&self) -> ConflictStatus {
+        if self.auto_apply {
+            ConflictStatus::Resolved
+        } else {
+            ConflictStatus::PendingReview
+        }
+    }
+}
+
+struct Precedence<'a> {
+    prevailing: &'a NormativeFramework,
+    yielding: &'a NormativeFramework,
+    principle: ResolutionStrategy,
+    confidence: f64,
+    rationale: String,
+}
+
+/// Propose which rule of a conflict should yield and how to amend it
+///
+/// Precedence is decided by jurisdiction level through the normative
+/// hierarchy, then by effective date, then by issuing authority; when none
+/// of these separates the rules the stricter one is kept.
+pub fn propose_resolution(conflict: &Conflict, strategy: ResolutionMode) -> AionResult<ResolutionProposal> {
+    let precedence = decide_precedence(conflict);
+
+    if strategy == ResolutionMode::Automatic && precedence.confidence < AUTO_APPLY_CONFIDENCE {
+        return Err(AionError::ConflictResolutionError {
+            strategy: strategy.as_str().to_string(),
+            reason: format!(
+                "precedence between '{}' and '{}' is uncertain (confidence {:.2}); use the hybrid or manual strategy",
+                conflict.framework_a.title, conflict.framework_b.title, precedence.confidence
+            ),
+        });
+    }
+
+    let auto_apply = match strategy {
+        ResolutionMode::Automatic => true,
+        ResolutionMode::Manual => false,
+        ResolutionMode::Hybrid => precedence.confidence >= AUTO_APPLY_CONFIDENCE,
+    };
+
+    tracing::info!(
+        "⚖️ Proposed '{}' yields to '{}' for conflict {} ({:?}, confidence {:.2})",
+        precedence.yielding.title,
+        precedence.prevailing.title,
+        conflict.id,
+        precedence.principle,
+        precedence.confidence
+    );
+
+    Ok(ResolutionProposal {
+        conflict_id: conflict.id,
+        mode: strategy,
+        edits: suggest_edits(conflict, &precedence),
+        principle: precedence.principle,
+        prevailing_rule: precedence.prevailing.id.clone(),
+        yielding_rule: precedence.yielding.id.clone(),
+        confidence: precedence.confidence,
+        rationale: precedence.rationale,
+        requires_review: !auto_apply,
+        auto_apply,
+    })
+}
+
+fn decide_precedence(conflict: &Conflict) -> Precedence<'_> {
+    let (a, b) = (&conflict.framework_a, &conflict.framework_b);
+
+    if let Some(resolution) = HierarchyManager::new().precedence_between(a, b) {
+        let (prevailing, yielding) = if resolution.primary_framework == a.id { (a, b) } else { (b, a) };
+        return Precedence {
+            prevailing,
+            yielding,
+            principle: ResolutionStrategy::LexSuperior,
+            confidence: 0.9,
+            rationale: format!(
+                "Lex superior: {}; '{}' ({:?}) must yield",
+                resolution.reasoning, yielding.title, yielding.jurisdiction
+            ),
+        };
+    }
+
+    if a.effective_date != b.effective_date {
+        let (prevailing, yielding) = if a.effective_date > b.effective_date { (a, b) } else { (b, a) };
+        return Precedence {
+            prevailing,
+            yielding,
+            principle: ResolutionStrategy::LexPosterior,
+            confidence: 0.8,
+            rationale: format!(
+                "Lex posterior: both rules are {:?}; '{}' took effect on {} after '{}' on {}, so the later rule prevails",
+                a.jurisdiction,
+                prevailing.title,
+                prevailing.effective_date.date_naive(),
+                yielding.title,
+                yielding.effective_date.date_naive()
+            ),
+        };
+    }
+
+    let resolver = AdvancedConflictResolver::new();
+    let authority_a = resolver.get_authority_level(&a.authority);
+    let authority_b = resolver.get_authority_level(&b.authority);
+    if authority_a != authority_b {
+        let (prevailing, yielding) = if authority_a > authority_b { (a, b) } else { (b, a) };
+        return Precedence {
+            prevailing,
+            yielding,
+            principle: ResolutionStrategy::LexSuperior,
+            confidence: 0.75,
+            rationale: format!(
+                "Lex superior: both rules are {:?} and took effect together; '{}' is issued by {}, which outranks {}",
+                a.jurisdiction, prevailing.title, prevailing.authority, yielding.authority
+            ),
+        };
+    }
+
+    let strictness = |framework: &NormativeFramework| {
+        affected(conflict, framework).filter(|requirement| requirement.mandatory).count()
+    };
+    let (prevailing, yielding) = if strictness(b) > strictness(a) { (b, a) } else { (a, b) };
+    Precedence {
+        prevailing,
+        yielding,
+        principle: ResolutionStrategy::Arbitration,
+        confidence: 0.5,
+        rationale: format!(
+            "No hierarchy, date or authority separates '{}' and '{}'; keeping the stricter '{}', since complying with it satisfies both",
+            a.title, b.title, prevailing.title
+        ),
+    }
+}
+
+/// Requirements of `framework` named by the conflict
+fn affected<'a>(conflict: &'a Conflict, framework: &'a NormativeFramework) -> impl Iterator<Item = &'a Requirement> {
+    framework
+        .requirements
+        .iter()
+        .filter(move |requirement| conflict.affected_requirements.contains(&requirement.id))
+}
+
+fn suggest_edits(conflict: &Conflict, precedence: &Precedence) -> Vec<SuggestedEdit> {
+    let (prevailing, yielding) = (precedence.prevailing, precedence.yielding);
+    let mut edits = Vec::new();
+
+    if conflict.conflict_type == ConflictType::ImplementationConflict && yielding.dependencies.contains(&prevailing.id) {
+        edits.push(SuggestedEdit {
+            rule_id: yielding.id.clone(),
+            requirement_id: None,
+            field: "dependencies".to_string(),
+            current: prevailing.id.0.to_string(),
+            proposed: String::new(),
+            reason: format!("Drop the dependency on '{}' to break the cycle", prevailing.title),
+        });
+    }
+
+    for requirement in affected(conflict, yielding) {
+        edits.push(SuggestedEdit {
+            rule_id: yielding.id.clone(),
+            requirement_id: Some(requirement.id),
+            field: "description".to_string(),
+            current: requirement.description.clone(),
+            proposed: format!("Subject to '{}', {}", prevailing.title, lowercase_first(&requirement.description)),
+            reason: format!("Defer to '{}' where the requirements overlap", prevailing.title),
+        });
+
+        let counterpart = affected(conflict, prevailing)
+            .find(|other| other.category == requirement.category)
+            .or_else(|| affected(conflict, prevailing).next());
+        if let Some(counterpart) = counterpart.filter(|other| other.mandatory != requirement.mandatory) {
+            edits.push(SuggestedEdit {
+                rule_id: yielding.id.clone(),
+                requirement_id: Some(requirement.id),
+                field: "mandatory".to_string(),
+                current: requirement.mandatory.to_string(),
+                proposed: counterpart.mandatory.to_string(),
+                reason: format!("Align with '{}' in '{}'", counterpart.title, prevailing.title),
+            });
+        }
+    }
+
+    if edits.is_empty() {
+        edits.push(SuggestedEdit {
+            rule_id: yielding.id.clone(),
+            requirement_id: None,
+            field: "description".to_string(),
+            current: yielding.description.clone(),
+            proposed: format!(
+                "{} Where this framework overlaps '{}', the latter prevails.",
+                yielding.description.trim_end(),
+                prevailing.title
+            ),
+            reason: format!("Record that '{}' takes precedence", prevailing.title),
+        });
+    }
+
+    edits
+}
+
+/// Lowercase the first letter for use mid-sentence, leaving acronyms alone
+fn lowercase_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match (chars.next(), chars.clone().next()) {
+        (Some(first), second) if !second.is_some_and(char::is_uppercase) => first.to_lowercase().chain(chars).collect(),
+        _ => text.to_string(),
+    }
+}
 
 pub struct AdvancedConflictResolver {
     resolution_strategies: HashMap<ConflictType, Vec<ResolutionStrategy>>,
@@ -480,14 +766,14 @@ impl AdvancedConflictResolver {
             ConflictSeverity::Critical => strategies.first().unwrap_or(&ResolutionStrategy::Arbitration),
             ConflictSeverity::High => strategies.get(0).unwrap_or(&ResolutionStrategy::LexSuperior),
             ConflictSeverity::Medium => strategies.get(1).unwrap_or(&ResolutionStrategy::Harmonization),
-            ConflictSeverity::Low => strategies.last().unwrap_or(&ResolutionStrategy::Mediation),
+            ConflictSeverity::Low | ConflictSeverity::Informational => strategies.last().unwrap_or(&ResolutionStrategy::Mediation),
         };
 
         Ok(strategy.clone())
     }
 
     fn execute_resolution_strategy(&self, conflict: &NormativeConflict, strategy: &ResolutionStrategy) -> AionResult<NormativeFramework> {
-        let frameworks = &conflict.framework_ids;
+        let frameworks = &conflict.involved_frameworks;
         if frameworks.is_empty() {
             return Err(AionError::ConflictResolutionError {
                 strategy: format!("{:?}", strategy),
@@ -500,16 +786,24 @@ impl AdvancedConflictResolver {
             id: frameworks[0].clone(),
             title: format!("Resolved via {:?}", strategy),
             description: format!("Conflict resolved using {:?} strategy", strategy),
-            authority: "System Resolution".to_string(),
+            normative_type: NormativeType::Framework,
             jurisdiction: Jurisdiction::Federal,
-            requirements: Vec::new(),
-            tags: vec!["resolved".to_string()],
+            authority: "System Resolution".to_string(),
             effective_date: Utc::now(),
-            updated_at: Utc::now(),
+            expiration_date: None,
+            version: "1.0".to_string(),
+            status: "active".to_string(),
+            tags: vec!["resolved".to_string()],
             metadata: HashMap::from([
                 ("resolution_strategy".to_string(), format!("{:?}", strategy)),
                 ("conflict_id".to_string(), conflict.id.to_string()),
             ]),
+            requirements: Vec::new(),
+            dependencies: Vec::new(),
+            supersedes: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            versions: Vec::new(),
         })
     }
 
@@ -607,4 +901,84 @@ pub struct ConflictResolution {
     pub confidence_score: f64,
     pub resolution_notes: String,
     pub metadata: HashMap<String, String>,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::{detect_conflicts, DetectionAlgorithm};
+    use aion_core::testing;
+    use chrono::TimeZone;
+
+    fn framework(title: &str, authority: &str, jurisdiction: Jurisdiction, mandatory: bool) -> NormativeFramework {
+        let effective_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut retention = testing::requirement(
+            "Record retention",
+            "Financial institutions must retain customer transaction records for seven years",
+        );
+        retention.mandatory = mandatory;
+        let mut framework = testing::framework(title, jurisdiction, vec![retention]);
+        framework.authority = authority.to_string();
+        framework.description = format!("{} rules for customer data retention", authority);
+        framework.tags = vec!["data-retention".to_string()];
+        framework.effective_date = effective_date;
+        framework.created_at = effective_date;
+        framework.updated_at = effective_date;
+        framework
+    }
+
+    fn conflict_between(rules: &[NormativeFramework]) -> Conflict {
+        detect_conflicts(rules, DetectionAlgorithm::SemanticSimilarity).unwrap().remove(0)
+    }
+
+    #[test]
+    fn test_federal_rule_prevails_over_state_rule() {
+        let rules = vec![
+            framework("State Retention Act", "State Government", Jurisdiction::State, false),
+            framework("Bank Secrecy Rule", "FinCEN", Jurisdiction::Federal, true),
+        ];
+        let proposal = propose_resolution(&conflict_between(&rules), ResolutionMode::Automatic).unwrap();
+
+        assert_eq!(proposal.principle, ResolutionStrategy::LexSuperior);
+        assert_eq!((&proposal.prevailing_rule, &proposal.yielding_rule), (&rules[1].id, &rules[0].id));
+        assert!(proposal.auto_apply && !proposal.requires_review);
+        assert!(proposal.rationale.contains("'Bank Secrecy Rule' takes precedence due to higher jurisdiction level (National)"));
+
+        let description = &proposal.edits[0];
+        assert_eq!(description.rule_id, rules[0].id);
+        assert_eq!(description.requirement_id, Some(rules[0].requirements[0].id));
+        assert!(description.proposed.starts_with("Subject to 'Bank Secrecy Rule', financial institutions must"));
+        let mandatory = &proposal.edits[1];
+        assert_eq!((mandatory.field.as_str(), mandatory.proposed.as_str()), ("mandatory", "true"));
+    }
+
+    #[test]
+    fn test_uncertain_precedence_needs_review() {
+        let rules = vec![
+            framework("Records Guidance", "FinCEN", Jurisdiction::Federal, false),
+            framework("Bank Secrecy Rule", "FinCEN", Jurisdiction::Federal, true),
+        ];
+        let conflict = conflict_between(&rules);
+
+        assert!(propose_resolution(&conflict, ResolutionMode::Automatic).is_err());
+
+        let proposal = propose_resolution(&conflict, ResolutionMode::Hybrid).unwrap();
+        assert_eq!(proposal.principle, ResolutionStrategy::Arbitration);
+        assert_eq!(proposal.prevailing_rule, rules[1].id);
+        assert!(proposal.requires_review && !proposal.auto_apply);
+    }
+
+    #[test]
+    fn test_later_rule_prevails_in_manual_mode() {
+        let mut rules = vec![
+            framework("Records Guidance 2020", "FinCEN", Jurisdiction::Federal, true),
+            framework("Records Guidance 2024", "FinCEN", Jurisdiction::Federal, false),
+        ];
+        rules[0].effective_date = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+
+        let proposal = propose_resolution(&conflict_between(&rules), ResolutionMode::Manual).unwrap();
+        assert_eq!(proposal.principle, ResolutionStrategy::LexPosterior);
+        assert_eq!(proposal.yielding_rule, rules[0].id);
+        assert!(proposal.requires_review && !proposal.auto_apply);
+        assert_eq!("hybrid".parse::<ResolutionMode>().unwrap(), ResolutionMode::Hybrid);
+    }
+}
@@ -1,5 +1,8 @@
 use aion_core::types::*;
-use aion_core::{AionResult};
+use aion_core::{AionError, AionResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
 
 pub struct StrategyManager {
     strategies: std::collections::HashMap<ConflictType, Vec<ResolutionStrategy>>,
@@ -130,4 +133,210 @@ impl Default for StrategyManager {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// Words common to most rules that say nothing about their subject
+const RULE_STOP_WORDS: &[&str] = &[
+    "shall", "must", "may", "will", "should", "with", "from", "that", "this", "these", "those", "each", "any",
+    "such", "which", "within", "under", "than", "other", "into", "upon", "been", "being", "have", "also",
+];
+
+/// Words that turn an obligation into a prohibition or exemption
+const NEGATIONS: &[&str] = &["not", "never", "prohibited", "forbidden", "exempt", "exempted", "neither", "nor"];
+
+/// Pair of rules whose text is similar enough to be a potential conflict
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarityConflict {
+    pub rule_a: NormativeId,
+    pub rule_b: NormativeId,
+    /// Most similar requirements of the two rules; `None` when a rule has no requirements
+    pub requirement_a: Option<Uuid>,
+    pub requirement_b: Option<Uuid>,
+    /// Keyword similarity of the compared texts, 0.0 to 1.0
+    pub similarity: f64,
+    /// Likelihood the pair actually conflicts, 0.0 to 1.0
+    pub confidence: f64,
+    /// One text obliges what the other makes optional or prohibits
+    pub opposing_obligations: bool,
+    pub explanation: String,
+}
+
+/// Flags rules whose requirement text overlaps above a threshold
+///
+/// Texts are reduced to subject keywords with `aion_core::extract_keywords`,
+/// dropping modal and connective words, and compared by Jaccard similarity,
+/// so rules on unrelated subjects score at or near zero.
+#[derive(Debug, Clone)]
+pub struct SemanticSimilarityStrategy {
+    threshold: f64,
+}
+
+impl SemanticSimilarityStrategy {
+    pub const DEFAULT_THRESHOLD: f64 = 0.5;
+
+    pub fn new() -> Self {
+        Self {
+            threshold: Self::DEFAULT_THRESHOLD,
+        }
+    }
+
+    /// Similarity at or above which a pair is reported
+    pub fn with_threshold(mut self, threshold: f64) -> AionResult<Self> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(AionError::ConfigurationError {
+                parameter: "similarity_threshold".to_string(),
+                reason: format!("{} is outside 0.0..=1.0", threshold),
+            });
+        }
+        self.threshold = threshold;
+        Ok(self)
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Similarity of two texts after dropping words every rule shares
+    pub fn text_similarity(&self, text_a: &str, text_b: &str) -> f64 {
+        let keywords_a = subject_keywords(text_a);
+        let keywords_b = subject_keywords(text_b);
+        let union = keywords_a.union(&keywords_b).count();
+        if union == 0 {
+            0.0
+        } else {
+            keywords_a.intersection(&keywords_b).count() as f64 / union as f64
+        }
+    }
+
+    /// Compare two rules by their most similar requirements
+    pub fn compare(&self, rule_a: &NormativeFramework, rule_b: &NormativeFramework) -> SimilarityConflict {
+        let mut best: Option<(Option<&Requirement>, Option<&Requirement>, f64)> = None;
+        for requirement_a in &rule_a.requirements {
+            for requirement_b in &rule_b.requirements {
+                let similarity = self.text_similarity(&requirement_text(requirement_a), &requirement_text(requirement_b));
+                if best.as_ref().is_none_or(|(_, _, best_similarity)| similarity > *best_similarity) {
+                    best = Some((Some(requirement_a), Some(requirement_b), similarity));
+                }
+            }
+        }
+        let (requirement_a, requirement_b, similarity) = best.unwrap_or_else(|| {
+            let similarity = self.text_similarity(
+                &format!("{} {}", rule_a.title, rule_a.description),
+                &format!("{} {}", rule_b.title, rule_b.description),
+            );
+            (None, None, similarity)
+        });
+
+        let opposing_obligations = match (requirement_a, requirement_b) {
+            (Some(a), Some(b)) => {
+                a.mandatory != b.mandatory || has_negation(&requirement_text(a)) != has_negation(&requirement_text(b))
+            }
+            _ => has_negation(&rule_a.description) != has_negation(&rule_b.description),
+        };
+        // Overlapping subject alone may be duplication; opposing obligations make it a conflict
+        let confidence = similarity * if opposing_obligations { 1.0 } else { 0.6 };
+
+        let subject = match (requirement_a, requirement_b) {
+            (Some(a), Some(b)) => format!("requirements '{}' and '{}'", a.title, b.title),
+            _ => format!("rules '{}' and '{}'", rule_a.title, rule_b.title),
+        };
+        let explanation = if opposing_obligations {
+            format!("{} cover the same subject ({:.0}% similar) but impose opposing obligations", subject, similarity * 100.0)
+        } else {
+            format!("{} cover the same subject ({:.0}% similar) and may overlap", subject, similarity * 100.0)
+        };
+
+        SimilarityConflict {
+            rule_a: rule_a.id.clone(),
+            rule_b: rule_b.id.clone(),
+            requirement_a: requirement_a.map(|requirement| requirement.id),
+            requirement_b: requirement_b.map(|requirement| requirement.id),
+            similarity,
+            confidence,
+            opposing_obligations,
+            explanation,
+        }
+    }
+
+    /// Every pair of rules at or above the threshold, most similar first
+    pub fn detect(&self, rules: &[NormativeFramework]) -> Vec<SimilarityConflict> {
+        let mut conflicts: Vec<SimilarityConflict> = rules
+            .iter()
+            .enumerate()
+            .flat_map(|(i, rule_a)| rules[i + 1..].iter().map(move |rule_b| (rule_a, rule_b)))
+            .map(|(rule_a, rule_b)| self.compare(rule_a, rule_b))
+            .filter(|conflict| conflict.similarity >= self.threshold)
+            .collect();
+        conflicts.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        conflicts
+    }
+}
+
+impl Default for SemanticSimilarityStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn requirement_text(requirement: &Requirement) -> String {
+    format!("{} {}", requirement.title, requirement.description)
+}
+
+fn subject_keywords(text: &str) -> HashSet<String> {
+    aion_core::extract_keywords(text)
+        .into_iter()
+        .filter(|word| !RULE_STOP_WORDS.contains(&word.as_str()) && !NEGATIONS.contains(&word.as_str()))
+        .collect()
+}
+
+fn has_negation(text: &str) -> bool {
+    text.split(|c: char| !c.is_alphanumeric())
+        .any(|word| NEGATIONS.contains(&word.to_lowercase().as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aion_core::testing;
+
+    fn rule(title: &str, requirement: &str, mandatory: bool) -> NormativeFramework {
+        let mut clause = testing::requirement(title, requirement);
+        clause.mandatory = mandatory;
+        let mut framework = testing::framework(title, Jurisdiction::Federal, vec![clause]);
+        framework.authority = "SEC".to_string();
+        framework.description = String::new();
+        framework
+    }
+
+    #[test]
+    fn test_unrelated_rules_score_near_zero() {
+        let strategy = SemanticSimilarityStrategy::new();
+        let filing = rule("Annual Report", "Issuers shall file an annual report on Form 10-K with the Commission", true);
+        let emissions = rule("Methane Monitoring", "Operators must monitor methane emissions at each well site", true);
+
+        let comparison = strategy.compare(&filing, &emissions);
+        assert!(comparison.similarity < 0.05, "similarity was {}", comparison.similarity);
+        assert!(strategy.detect(&[filing, emissions]).is_empty());
+    }
+
+    #[test]
+    fn test_threshold_controls_reported_pairs() {
+        let mandatory = rule("Annual Report", "Issuers shall file an annual report on Form 10-K within 60 days", true);
+        let exempt = rule("Small Issuer Relief", "Smaller issuers are exempt from filing the annual report on Form 10-K", false);
+        let rules = [mandatory, exempt];
+
+        let strict = SemanticSimilarityStrategy::new().with_threshold(0.9).unwrap();
+        assert!(strict.detect(&rules).is_empty());
+
+        let lenient = SemanticSimilarityStrategy::new().with_threshold(0.3).unwrap();
+        let conflicts = lenient.detect(&rules);
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert!(conflict.similarity >= 0.3 && conflict.similarity < 0.9);
+        assert!(conflict.opposing_obligations);
+        assert_eq!(conflict.confidence, conflict.similarity);
+        assert_eq!(conflict.requirement_a, Some(rules[0].requirements[0].id));
+
+        assert!(SemanticSimilarityStrategy::new().with_threshold(1.5).is_err());
+    }
+}
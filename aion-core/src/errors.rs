@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum AionError {
     #[error("Normative framework not found: {id}")]
     NormativeNotFound { id: String },
@@ -8,9 +8,32 @@ pub enum AionError {
     #[error("Normative conflict detected: {description}")]
     NormativeConflict { description: String },
 
+    /// Catch-all for validation failures without a structured variant below
     #[error("Compliance validation failed: {reason}")]
     ComplianceValidationError { reason: String },
 
+    /// A required field is absent or empty; `field` is its path, e.g. `requirements[0].title`
+    #[error("Missing required field: {field}")]
+    MissingField { field: String },
+
+    #[error("Type mismatch: {field}: expected {expected}, found {actual}")]
+    TypeMismatch {
+        field: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Value out of range: {field}: expected {expected}, found {actual}")]
+    OutOfRange {
+        field: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// `field` names an entity, e.g. a requirement ID, that does not exist
+    #[error("Unresolved reference: {field}: {reference}")]
+    ReferenceUnresolved { field: String, reference: String },
+
     #[error("Business rule validation failed: {rule_name}: {message}")]
     BusinessRuleViolation { rule_name: String, message: String },
 
@@ -59,10 +82,28 @@ pub enum AionError {
 
     #[error("Internal error: {message}")]
     InternalError { message: String },
+
+    #[error("Precedence cycle detected: {}", chain.join(" -> "))]
+    PrecedenceCycle { chain: Vec<String> },
 }
 
 pub type AionResult<T> = Result<T, AionError>;
 
+impl AionError {
+    /// Path of the offending field, for errors that identify one
+    pub fn field_path(&self) -> Option<&str> {
+        match self {
+            AionError::MissingField { field }
+            | AionError::TypeMismatch { field, .. }
+            | AionError::OutOfRange { field, .. }
+            | AionError::ReferenceUnresolved { field, .. }
+            | AionError::ValidationError { field, .. }
+            | AionError::InvalidFrameworkStructure { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+}
+
 impl From<serde_json::Error> for AionError {
     fn from(err: serde_json::Error) -> Self {
         AionError::SerializationError {
@@ -117,6 +158,26 @@ mod tests {
         assert!(error_message.contains("Missing required documentation"));
     }
 
+    #[test]
+    fn test_structured_validation_errors() {
+        let error = AionError::TypeMismatch {
+            field: "evidence[0].metadata.requirement_id".to_string(),
+            expected: "UUID".to_string(),
+            actual: "req-7".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Type mismatch: evidence[0].metadata.requirement_id: expected UUID, found req-7"
+        );
+        assert_eq!(error.field_path(), Some("evidence[0].metadata.requirement_id"));
+
+        let error = AionError::MissingField { field: "context.organization".to_string() };
+        assert_eq!(error.field_path(), Some("context.organization"));
+
+        let catch_all = AionError::ComplianceValidationError { reason: "Missing documentation".to_string() };
+        assert_eq!(catch_all.field_path(), None);
+    }
+
     #[test]
     fn test_business_rule_violation() {
         let error = AionError::BusinessRuleViolation {
@@ -306,6 +367,17 @@ mod tests {
         assert!(error_message.contains("Unexpected system state"));
     }
 
+    #[test]
+    fn test_precedence_cycle_error() {
+        let error = AionError::PrecedenceCycle {
+            chain: vec!["a".to_string(), "b".to_string(), "a".to_string()],
+        };
+
+        let error_message = error.to_string();
+        assert!(error_message.contains("Precedence cycle detected"));
+        assert!(error_message.contains("a -> b -> a"));
+    }
+
     #[test]
     fn test_aion_result_type() {
         let success: AionResult<String> = Ok("success".to_string());
@@ -2,6 +2,8 @@ pub mod types;
 pub mod errors;
 pub mod traits;
 pub mod utils;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 pub use types::*;
 pub use errors::*;
@@ -0,0 +1,35 @@
+//! Fixtures shared by the workspace's tests
+//!
+//! Enabled for other crates with the `testing` feature, as a dev-dependency only.
+
+use crate::types::{Jurisdiction, NormativeFramework, NormativeType, Requirement};
+use uuid::Uuid;
+
+/// A regulation titled `title`, issued by "Regulator", holding `requirements`
+pub fn framework(title: &str, jurisdiction: Jurisdiction, requirements: Vec<Requirement>) -> NormativeFramework {
+    let mut framework = NormativeFramework::new(
+        title.to_string(),
+        format!("{} rules", title),
+        NormativeType::Regulation,
+        jurisdiction,
+        "Regulator".to_string(),
+    );
+    framework.requirements = requirements;
+    framework
+}
+
+/// A mandatory, priority-1 requirement in the "general" category
+pub fn requirement(title: &str, description: &str) -> Requirement {
+    Requirement {
+        id: Uuid::new_v4(),
+        title: title.to_string(),
+        description: description.to_string(),
+        mandatory: true,
+        conditions: Vec::new(),
+        exceptions: Vec::new(),
+        evidence_required: Vec::new(),
+        validation_rules: Vec::new(),
+        priority: 1,
+        category: "general".to_string(),
+    }
+}
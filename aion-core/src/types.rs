@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -108,6 +108,25 @@ pub struct NormativeFramework {
     pub supersedes: Vec<NormativeId>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Past and current texts of the framework, for assessing earlier periods
+    #[serde(default)]
+    pub versions: Vec<NormativeVersion>,
+}
+
+/// The text of a framework as it was in force over a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormativeVersion {
+    pub version: String,
+    pub effective_from: NaiveDate,
+    /// First day the version no longer applies; `None` while still in force
+    pub effective_to: Option<NaiveDate>,
+    pub requirements: Vec<Requirement>,
+}
+
+impl NormativeVersion {
+    pub fn is_in_effect_on(&self, date: NaiveDate) -> bool {
+        self.effective_from <= date && self.effective_to.is_none_or(|to| date < to)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -293,6 +312,7 @@ impl NormativeFramework {
             supersedes: Vec::new(),
             created_at: now,
             updated_at: now,
+            versions: Vec::new(),
         }
     }
 
@@ -303,6 +323,29 @@ impl NormativeFramework {
             && self.status == "active"
     }
 
+    /// The recorded version in force on `date`; the latest-starting one if periods overlap
+    pub fn version_in_effect_on(&self, date: NaiveDate) -> Option<&NormativeVersion> {
+        self.versions
+            .iter()
+            .filter(|version| version.is_in_effect_on(date))
+            .max_by_key(|version| version.effective_from)
+    }
+
+    /// Requirements that applied on `date`
+    ///
+    /// Uses the recorded version for that date. A framework without a version
+    /// history falls back to its current requirements while within its
+    /// effective and expiration dates.
+    pub fn requirements_in_effect_on(&self, date: NaiveDate) -> Option<&[Requirement]> {
+        if !self.versions.is_empty() {
+            return self.version_in_effect_on(date).map(|version| version.requirements.as_slice());
+        }
+
+        let in_force = self.effective_date.date_naive() <= date
+            && self.expiration_date.is_none_or(|exp| date < exp.date_naive());
+        in_force.then_some(self.requirements.as_slice())
+    }
+
     pub fn add_requirement(&mut self, requirement: Requirement) {
         self.requirements.push(requirement);
         self.updated_at = Utc::now();
@@ -455,6 +498,37 @@ mod tests {
         assert!(!framework.is_active());
     }
 
+    #[test]
+    fn test_normative_framework_version_in_effect_on() {
+        let mut framework = NormativeFramework::new(
+            "Test".to_string(),
+            "Test".to_string(),
+            NormativeType::Regulation,
+            Jurisdiction::Federal,
+            "Authority".to_string(),
+        );
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        framework.versions = vec![
+            NormativeVersion {
+                version: "2021".to_string(),
+                effective_from: date(2021, 1, 1),
+                effective_to: Some(date(2023, 1, 1)),
+                requirements: Vec::new(),
+            },
+            NormativeVersion {
+                version: "2023".to_string(),
+                effective_from: date(2023, 1, 1),
+                effective_to: None,
+                requirements: Vec::new(),
+            },
+        ];
+
+        assert_eq!(framework.version_in_effect_on(date(2022, 6, 30)).unwrap().version, "2021");
+        assert_eq!(framework.version_in_effect_on(date(2023, 1, 1)).unwrap().version, "2023");
+        assert!(framework.version_in_effect_on(date(2020, 12, 31)).is_none());
+        assert!(framework.requirements_in_effect_on(date(2020, 12, 31)).is_none());
+    }
+
     #[test]
     fn test_normative_framework_add_requirement() {
         let mut framework = NormativeFramework::new(
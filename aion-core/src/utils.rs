@@ -1,7 +1,10 @@
 use crate::{AionError, AionResult};
-use chrono::{DateTime, Utc, Timelike};
+use chrono::{DateTime, NaiveDateTime, Utc, Timelike};
 use regex::Regex;
-use std::collections::HashMap;
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{LazyLock, Mutex, OnceLock};
 
 pub fn validate_email(email: &str) -> bool {
     let email_regex = Regex::new(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$").unwrap();
@@ -64,20 +67,190 @@ pub fn merge_metadata(base: &HashMap<String, String>, overlay: &HashMap<String,
     result
 }
 
+/// Languages with a built-in stopword list and stemmer for keyword extraction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeywordLanguage {
+    English,
+    Spanish,
+    French,
+    German,
+    Portuguese,
+    Italian,
+}
+
+impl KeywordLanguage {
+    /// Language of a code such as `es` or `pt-BR`, by its primary subtag
+    pub fn from_code(code: &str) -> Option<Self> {
+        let primary = code.split(['-', '_']).next().unwrap_or_default().to_lowercase();
+        match primary.as_str() {
+            "en" => Some(KeywordLanguage::English),
+            "es" => Some(KeywordLanguage::Spanish),
+            "fr" => Some(KeywordLanguage::French),
+            "de" => Some(KeywordLanguage::German),
+            "pt" => Some(KeywordLanguage::Portuguese),
+            "it" => Some(KeywordLanguage::Italian),
+            _ => None,
+        }
+    }
+
+    pub fn stopwords(self) -> &'static [&'static str] {
+        match self {
+            KeywordLanguage::English => &[
+                "the", "and", "for", "are", "but", "not", "you", "all", "can", "had", "her", "was", "one", "our",
+                "out", "day", "get", "has", "him", "his", "how", "man", "new", "now", "old", "see", "two", "way",
+                "who", "boy", "did", "its", "let", "put", "say", "she", "too", "use",
+            ],
+            KeywordLanguage::Spanish => &[
+                "el", "la", "los", "las", "del", "con", "por", "para", "una", "uno", "unos", "unas", "que", "como",
+                "más", "pero", "sus", "este", "esta", "estos", "estas", "ese", "esa", "ser", "son", "fue", "han",
+                "hay", "sin", "sobre", "entre", "cuando", "donde", "también", "muy", "todo", "todos", "cada",
+                "otro", "otra", "según", "desde", "hasta", "les", "al", "de", "en", "se", "su", "no",
+            ],
+            KeywordLanguage::French => &[
+                "les", "des", "une", "un", "le", "la", "du", "de", "et", "ou", "que", "qui", "dans", "pour", "par",
+                "sur", "avec", "sans", "sont", "est", "aux", "ces", "cette", "ce", "son", "ses", "leur", "leurs",
+                "pas", "plus", "ne", "être", "été", "tout", "tous", "toute", "toutes", "entre", "lors", "dont",
+                "comme", "mais", "ainsi",
+            ],
+            KeywordLanguage::German => &[
+                "der", "die", "das", "den", "dem", "des", "ein", "eine", "einer", "eines", "einem", "einen", "und",
+                "oder", "mit", "von", "für", "auf", "ist", "sind", "wird", "werden", "nicht", "auch", "als", "bei",
+                "nach", "aus", "zur", "zum", "durch", "über", "unter", "sich", "diese", "dieser", "dieses", "wenn",
+                "dass", "sowie", "nur", "noch", "kann",
+            ],
+            KeywordLanguage::Portuguese => &[
+                "os", "as", "um", "uma", "uns", "umas", "do", "da", "dos", "das", "de", "em", "no", "na", "nos",
+                "nas", "por", "para", "com", "sem", "que", "como", "mais", "mas", "seu", "sua", "seus", "suas",
+                "este", "esta", "esse", "essa", "ser", "são", "foi", "ou", "ao", "aos", "pelo", "pela", "entre",
+                "sobre", "também", "não",
+            ],
+            KeywordLanguage::Italian => &[
+                "il", "lo", "la", "gli", "le", "un", "una", "uno", "del", "della", "dei", "delle", "degli", "di",
+                "da", "in", "con", "per", "tra", "fra", "che", "come", "più", "non", "sono", "essere", "suo",
+                "sua", "suoi", "questo", "questa", "quello", "quella", "nel", "nella", "nei", "alle", "alla", "al",
+                "ai", "ed", "anche", "ogni",
+            ],
+        }
+    }
+
+    /// Words: ASCII letters for English, as keywords have always been
+    /// extracted, and any letters for languages that need accents
+    fn word_regex(self) -> &'static Regex {
+        static ASCII_WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b[a-zA-Z]+\b").unwrap());
+        static LETTER_WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b\p{L}+\b").unwrap());
+        match self {
+            KeywordLanguage::English => &ASCII_WORD,
+            _ => &LETTER_WORD,
+        }
+    }
+
+    fn stemmer(self) -> Stemmer {
+        Stemmer::create(match self {
+            KeywordLanguage::English => Algorithm::English,
+            KeywordLanguage::Spanish => Algorithm::Spanish,
+            KeywordLanguage::French => Algorithm::French,
+            KeywordLanguage::German => Algorithm::German,
+            KeywordLanguage::Portuguese => Algorithm::Portuguese,
+            KeywordLanguage::Italian => Algorithm::Italian,
+        })
+    }
+}
+
+/// Words dropped from extracted keywords
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StopwordSet {
+    Language(KeywordLanguage),
+    /// Lowercase words supplied by the caller
+    Custom(HashSet<String>),
+    None,
+}
+
+impl StopwordSet {
+    pub fn contains(&self, word: &str) -> bool {
+        match self {
+            StopwordSet::Language(language) => language.stopwords().contains(&word),
+            StopwordSet::Custom(words) => words.contains(word),
+            StopwordSet::None => false,
+        }
+    }
+}
+
+/// How [`extract_keywords_with`] tokenizes and filters text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeywordOptions {
+    /// Language whose word pattern and stemmer are used
+    pub language: KeywordLanguage,
+    pub stopwords: StopwordSet,
+    /// Reduce keywords to their stem, so "processing" and "processed" match
+    pub stemming: bool,
+    /// Shortest keyword kept, in characters, measured before stemming
+    pub min_length: usize,
+    /// Keep only keywords occurring at least this often in the text
+    pub min_frequency: usize,
+}
+
+impl KeywordOptions {
+    /// Stopwords and stemmer of `language`, with stemming on
+    pub fn for_language(language: KeywordLanguage) -> Self {
+        Self {
+            language,
+            stopwords: StopwordSet::Language(language),
+            stemming: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for KeywordOptions {
+    fn default() -> Self {
+        Self {
+            language: KeywordLanguage::English,
+            stopwords: StopwordSet::Language(KeywordLanguage::English),
+            stemming: false,
+            min_length: 3,
+            min_frequency: 1,
+        }
+    }
+}
+
+/// Lowercase English keywords of `text`, in order, without stemming
 pub fn extract_keywords(text: &str) -> Vec<String> {
-    let word_regex = Regex::new(r"\b[a-zA-Z]{3,}\b").unwrap();
-    word_regex
+    extract_keywords_with(text, &KeywordOptions::default())
+}
+
+/// Keywords of `text` in order of occurrence, repeated as often as they occur
+pub fn extract_keywords_with(text: &str, options: &KeywordOptions) -> Vec<String> {
+    let stemmer = options.stemming.then(|| options.language.stemmer());
+
+    let keywords: Vec<String> = options
+        .language
+        .word_regex()
         .find_iter(text)
         .map(|m| m.as_str().to_lowercase())
-        .filter(|word| !is_stop_word(word))
+        .filter(|word| word.chars().count() >= options.min_length && !options.stopwords.contains(word))
+        .map(|word| match &stemmer {
+            Some(stemmer) => stemmer.stem(&word).into_owned(),
+            None => word,
+        })
+        .collect();
+
+    if options.min_frequency <= 1 {
+        return keywords;
+    }
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for keyword in &keywords {
+        *counts.entry(keyword.as_str()).or_insert(0) += 1;
+    }
+    keywords
+        .iter()
+        .filter(|keyword| counts[keyword.as_str()] >= options.min_frequency)
+        .cloned()
         .collect()
 }
 
+#[cfg(test)]
 fn is_stop_word(word: &str) -> bool {
-    matches!(
-        word,
-        "the" | "and" | "for" | "are" | "but" | "not" | "you" | "all" | "can" | "had" | "her" | "was" | "one" | "our" | "out" | "day" | "get" | "has" | "him" | "his" | "how" | "man" | "new" | "now" | "old" | "see" | "two" | "way" | "who" | "boy" | "did" | "its" | "let" | "put" | "say" | "she" | "too" | "use"
-    )
+    StopwordSet::Language(KeywordLanguage::English).contains(word)
 }
 
 pub fn sanitize_input(input: &str) -> String {
@@ -87,18 +260,59 @@ pub fn sanitize_input(input: &str) -> String {
         .collect()
 }
 
+/// TF-IDF weighted cosine similarity of two texts' keywords, from 0.0 to 1.0
+///
+/// Document frequencies come from the two texts alone, so shared words weigh
+/// less than words only one text uses. Use [`calculate_similarity_with_corpus`]
+/// to weigh terms by how common they are across a collection.
 pub fn calculate_similarity(text1: &str, text2: &str) -> f64 {
-    let words1: std::collections::HashSet<_> = extract_keywords(text1).into_iter().collect();
-    let words2: std::collections::HashSet<_> = extract_keywords(text2).into_iter().collect();
+    calculate_similarity_with_corpus::<&str>(text1, text2, &[])
+}
 
-    let intersection = words1.intersection(&words2).count();
-    let union = words1.union(&words2).count();
+/// TF-IDF weighted cosine similarity with document frequencies from `corpus`
+///
+/// Both texts count as documents alongside `corpus`, and IDF is smoothed as
+/// `ln((1 + n) / (1 + df)) + 1`, so every term keeps a positive weight.
+pub fn calculate_similarity_with_corpus<S: AsRef<str>>(text1: &str, text2: &str, corpus: &[S]) -> f64 {
+    let terms1 = term_frequencies(text1);
+    let terms2 = term_frequencies(text2);
+    if terms1.is_empty() || terms2.is_empty() {
+        return 0.0;
+    }
 
-    if union == 0 {
-        0.0
-    } else {
-        intersection as f64 / union as f64
+    let corpus_terms: Vec<HashSet<String>> = corpus
+        .iter()
+        .map(|document| extract_keywords(document.as_ref()).into_iter().collect())
+        .collect();
+    let documents = corpus_terms.len() + 2;
+    let idf = |term: &str| {
+        let document_frequency = corpus_terms.iter().filter(|terms| terms.contains(term)).count()
+            + usize::from(terms1.contains_key(term))
+            + usize::from(terms2.contains_key(term));
+        ((1 + documents) as f64 / (1 + document_frequency) as f64).ln() + 1.0
+    };
+    let weigh = |terms: &BTreeMap<String, usize>| -> BTreeMap<String, f64> {
+        terms.iter().map(|(term, count)| (term.clone(), *count as f64 * idf(term))).collect()
+    };
+    let weights1 = weigh(&terms1);
+    let weights2 = weigh(&terms2);
+
+    // Sorted iteration makes identical texts score exactly 1.0
+    let dot: f64 = weights1
+        .iter()
+        .filter_map(|(term, weight)| weights2.get(term).map(|other| weight * other))
+        .sum();
+    let norm1: f64 = weights1.values().map(|weight| weight * weight).sum();
+    let norm2: f64 = weights2.values().map(|weight| weight * weight).sum();
+    (dot / (norm1 * norm2).sqrt()).min(1.0)
+}
+
+fn term_frequencies(text: &str) -> BTreeMap<String, usize> {
+    let mut frequencies = BTreeMap::new();
+    for keyword in extract_keywords(text) {
+        *frequencies.entry(keyword).or_insert(0) += 1;
     }
+    frequencies
 }
 
 pub fn generate_checksum(data: &[u8]) -> String {
@@ -135,11 +349,13 @@ pub fn escape_sql_string(input: &str) -> String {
     input.replace('\'', "''").replace('\\', "\\\\")
 }
 
+/// Shorten `input` to at most `max_length` characters, ending in "..." when cut
 pub fn truncate_string(input: &str, max_length: usize) -> String {
-    if input.len() <= max_length {
+    if input.chars().count() <= max_length {
         input.to_string()
     } else {
-        format!("{}...", &input[..max_length.saturating_sub(3)])
+        let kept: String = input.chars().take(max_length.saturating_sub(3)).collect();
+        format!("{}...", kept)
     }
 }
 
@@ -148,16 +364,142 @@ pub fn is_business_hours(timestamp: DateTime<Utc>) -> bool {
     hour >= 9 && hour < 17
 }
 
-pub fn generate_reference_id(prefix: &str) -> String {
-    let timestamp = Utc::now().format("%Y%m%d%H%M%S");
-    let random_suffix: u32 = (::rand::random::<u32>() % 10000);
-    format!("{}-{}-{:04}", prefix, timestamp, random_suffix)
+const REFERENCE_TIMESTAMP_FORMAT: &str = "%Y%m%d%H%M%S";
+/// Longest component whose values still fit in a `u64`
+const MAX_REFERENCE_COMPONENT_LENGTH: usize = 18;
+/// Random draws before a second's reference ids are considered exhausted
+const MAX_REFERENCE_ATTEMPTS: usize = 64;
+
+/// How the trailing component of a reference id is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReferenceIdComponent {
+    Random,
+    /// Per-prefix counter that restarts at 1 every second
+    Sequential,
+}
+
+/// Format of the ids built by [`generate_reference_id_with`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceIdConfig {
+    pub component: ReferenceIdComponent,
+    /// Digits of the trailing component, 1 to 18
+    pub length: usize,
+}
+
+impl Default for ReferenceIdConfig {
+    fn default() -> Self {
+        Self {
+            component: ReferenceIdComponent::Random,
+            length: 4,
+        }
+    }
+}
+
+/// Ids issued by this process in the current second
+///
+/// Ids embed their second, so older ones can never collide and are dropped.
+#[derive(Default)]
+struct IssuedReferenceIds {
+    timestamp: String,
+    ids: HashSet<String>,
+    counters: HashMap<String, u64>,
+}
+
+fn issued_reference_ids() -> &'static Mutex<IssuedReferenceIds> {
+    static ISSUED: OnceLock<Mutex<IssuedReferenceIds>> = OnceLock::new();
+    ISSUED.get_or_init(Default::default)
+}
+
+/// Reference id `PREFIX-YYYYMMDDHHMMSS-NNNN` with a random component
+///
+/// Fails for an invalid prefix or once the current second has no unused ids.
+pub fn generate_reference_id(prefix: &str) -> AionResult<String> {
+    generate_reference_id_with(prefix, &ReferenceIdConfig::default())
+}
+
+/// Reference id `PREFIX-YYYYMMDDHHMMSS-N…` with the component `config` describes
+///
+/// The prefix must be non-empty ASCII alphanumeric. Ids are unique within
+/// the process: a component already issued in the same second is replaced,
+/// and generation fails once the second has no unused components left.
+pub fn generate_reference_id_with(prefix: &str, config: &ReferenceIdConfig) -> AionResult<String> {
+    if !is_valid_reference_prefix(prefix) {
+        return Err(AionError::ValidationError {
+            field: "prefix".to_string(),
+            message: format!("Reference id prefix must be non-empty and alphanumeric: {:?}", prefix),
+        });
+    }
+    if !(1..=MAX_REFERENCE_COMPONENT_LENGTH).contains(&config.length) {
+        return Err(AionError::ConfigurationError {
+            parameter: "reference_id.length".to_string(),
+            reason: format!("must be between 1 and {}, got {}", MAX_REFERENCE_COMPONENT_LENGTH, config.length),
+        });
+    }
+
+    let capacity = 10u64.pow(config.length as u32);
+
+    // Read the clock under the lock so a caller holding an older second can
+    // never reset the set after a newer second has started issuing ids
+    let mut issued = issued_reference_ids().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let timestamp = Utc::now().format(REFERENCE_TIMESTAMP_FORMAT).to_string();
+    let format_id = |component: u64| format!("{}-{}-{:0width$}", prefix, timestamp, component, width = config.length);
+    if issued.timestamp != timestamp {
+        *issued = IssuedReferenceIds { timestamp: timestamp.clone(), ..Default::default() };
+    }
+
+    match config.component {
+        ReferenceIdComponent::Sequential => loop {
+            let counter = issued.counters.entry(prefix.to_string()).or_insert(0);
+            *counter += 1;
+            if *counter >= capacity {
+                break;
+            }
+            let id = format_id(*counter);
+            if issued.ids.insert(id.clone()) {
+                return Ok(id);
+            }
+        },
+        ReferenceIdComponent::Random => {
+            for _ in 0..MAX_REFERENCE_ATTEMPTS {
+                let id = format_id(::rand::Rng::gen_range(&mut ::rand::thread_rng(), 0..capacity));
+                if issued.ids.insert(id.clone()) {
+                    return Ok(id);
+                }
+            }
+        }
+    }
+
+    Err(AionError::InternalError {
+        message: format!("No unused {}-digit reference ids left for {} in {}", config.length, prefix, timestamp),
+    })
+}
+
+/// Whether `id` has the reference id format for `prefix`
+pub fn is_valid_reference_id(prefix: &str, id: &str) -> bool {
+    let Some(rest) = id.strip_prefix(prefix).and_then(|rest| rest.strip_prefix('-')) else {
+        return false;
+    };
+    let Some((timestamp, component)) = rest.split_once('-') else {
+        return false;
+    };
+
+    is_valid_reference_prefix(prefix)
+        && timestamp.len() == 14
+        && NaiveDateTime::parse_from_str(timestamp, REFERENCE_TIMESTAMP_FORMAT).is_ok()
+        && (1..=MAX_REFERENCE_COMPONENT_LENGTH).contains(&component.len())
+        && component.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+fn is_valid_reference_prefix(prefix: &str) -> bool {
+    !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_alphanumeric())
 }
 
 use std::sync::atomic::{AtomicU64, Ordering};
 
 static COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// Process-local counter; instances of a cluster overlap, so use an
+/// `aion_db::SequenceStore` where numbers must be unique across processes
 pub fn next_sequence_number() -> u64 {
     COUNTER.fetch_add(1, Ordering::SeqCst)
 }
@@ -291,6 +633,9 @@ mod tests {
         assert!(keywords.contains(&"jumps".to_string()));
         assert!(!keywords.contains(&"the".to_string())); // Stop word
         assert!(!keywords.contains(&"and".to_string())); // Stop word
+
+        // The English default keeps the ASCII-only tokenizer
+        assert_eq!(extract_keywords("café naïve regulation"), vec!["regulation".to_string()]);
     }
 
     #[test]
@@ -329,6 +674,25 @@ mod tests {
         assert_eq!(calculate_similarity("", ""), 0.0);
     }
 
+    #[test]
+    fn test_corpus_idf_favors_distinctive_terms() {
+        let corpus = [
+            "Reporting entities must file quarterly reports",
+            "Reporting entities must retain records",
+            "Reporting entities must notify the regulator",
+        ];
+        let query = "Reporting entities must disclose cybersecurity incidents";
+        let shares_boilerplate = "Reporting entities must publish annual accounts";
+        let shares_subject = "Material cybersecurity incidents require disclosure";
+
+        assert_eq!(calculate_similarity_with_corpus(query, query, &corpus), 1.0);
+        assert!(
+            calculate_similarity_with_corpus(query, shares_subject, &corpus)
+                > calculate_similarity_with_corpus(query, shares_boilerplate, &corpus)
+        );
+        assert_eq!(calculate_similarity_with_corpus("", query, &corpus), 0.0);
+    }
+
     #[test]
     fn test_generate_checksum() {
         let data1 = b"test data";
@@ -380,6 +744,7 @@ mod tests {
         assert_eq!(truncate_string("this is a very long string", 10), "this is...");
         assert_eq!(truncate_string("test", 3), "");
         assert_eq!(truncate_string("", 10), "");
+        assert_eq!(truncate_string("ééééé", 4), "é...");
     }
 
     #[test]
@@ -397,13 +762,38 @@ mod tests {
 
     #[test]
     fn test_generate_reference_id() {
-        let id1 = generate_reference_id("TEST");
-        let id2 = generate_reference_id("TEST");
+        let id1 = generate_reference_id("TEST").unwrap();
+        let id2 = generate_reference_id("TEST").unwrap();
 
         assert!(id1.starts_with("TEST-"));
         assert!(id2.starts_with("TEST-"));
         assert_ne!(id1, id2); // Should be different due to timestamp/random
         assert!(id1.len() > 10); // Should have reasonable length
+        assert!(is_valid_reference_id("TEST", &id1));
+
+        assert!(generate_reference_id("").is_err());
+        assert!(generate_reference_id("AUD-IT").is_err());
+        assert!(!is_valid_reference_id("TEST", "TEST-20241301120000-0001"));
+        assert!(!is_valid_reference_id("TEST", "TESTX-20240101120000-0001"));
+        assert!(!is_valid_reference_id("TEST", "TEST-20240101120000-00a1"));
+    }
+
+    #[test]
+    fn test_reference_ids_are_unique_within_a_run() {
+        let random = ReferenceIdConfig { component: ReferenceIdComponent::Random, length: 6 };
+        let sequential = ReferenceIdConfig { component: ReferenceIdComponent::Sequential, length: 6 };
+
+        let mut seen = HashSet::new();
+        for _ in 0..5000 {
+            for config in [&random, &sequential] {
+                let id = generate_reference_id_with("UNIQ", config).unwrap();
+                assert!(is_valid_reference_id("UNIQ", &id));
+                assert!(seen.insert(id));
+            }
+        }
+
+        let too_long = ReferenceIdConfig { length: 19, ..random };
+        assert!(generate_reference_id_with("UNIQ", &too_long).is_err());
     }
 
     #[test]
@@ -469,4 +859,43 @@ mod tests {
         assert!(keywords.contains(&"important".to_string()));
         assert!(keywords.contains(&"regulation".to_string()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_keyword_options_stem_and_filter() {
+        let text = "Processing rules: data processed under the rules, processes reviewed.";
+        assert_eq!(
+            extract_keywords(text),
+            vec!["processing", "rules", "data", "processed", "under", "rules", "processes", "reviewed"]
+        );
+
+        let options = KeywordOptions {
+            stemming: true,
+            min_frequency: 2,
+            ..KeywordOptions::default()
+        };
+        assert_eq!(
+            extract_keywords_with(text, &options),
+            vec!["process", "rule", "process", "rule", "process"]
+        );
+    }
+
+    #[test]
+    fn test_keyword_options_for_other_languages() {
+        let spanish = KeywordOptions {
+            stemming: false,
+            ..KeywordOptions::for_language(KeywordLanguage::from_code("es-MX").unwrap())
+        };
+        assert_eq!(
+            extract_keywords_with("Los requisitos de reporte según la regulación", &spanish),
+            vec!["requisitos", "reporte", "regulación"]
+        );
+
+        let custom = KeywordOptions {
+            stopwords: StopwordSet::Custom(HashSet::from(["filing".to_string()])),
+            min_length: 5,
+            ..KeywordOptions::default()
+        };
+        assert_eq!(extract_keywords_with("The annual filing deadline", &custom), vec!["annual", "deadline"]);
+        assert_eq!(KeywordLanguage::from_code("xx"), None);
+    }
+}
@@ -4,10 +4,12 @@ pub mod migrations;
 pub mod normative_store;
 pub mod query_engine;
 pub mod backup;
+pub mod sequence;
 
 pub use schema::*;
 pub use repository::*;
 pub use migrations::*;
 pub use normative_store::*;
 pub use query_engine::*;
-pub use backup::*;
\ No newline at end of file
+pub use backup::*;
+pub use sequence::*;
\ No newline at end of file
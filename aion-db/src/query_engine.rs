@@ -1,9 +1,31 @@
 // Query engine for complex database operations
-use aion_core::{AionResult};
-use std::collections::HashMap;
+use crate::normative_store::{FrameworkQuery, InMemoryNormativeStore, NormativeStore};
+use aion_core::{AionResult, Jurisdiction, NormativeFramework, NormativeId, NormativeVersion};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Metadata key holding a comma-separated list of industries a framework applies to
+pub const INDUSTRY_METADATA_KEY: &str = "industry";
+
+// BM25 tuning; the usual defaults from the literature
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+// Titles are short but on-point, so their terms count this many times
+const TITLE_BOOST: usize = 3;
+const SNIPPET_WORDS: usize = 24;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it",
+    "of", "on", "or", "shall", "that", "the", "this", "to", "with",
+];
 
 pub struct QueryEngine {
-    connection_pool: Option<String>, // Simplified
+    store: Arc<dyn NormativeStore>,
+    /// Built from the store on the first search, then kept current by [`QueryEngine::put_framework`]
+    text_index: RwLock<Option<TextIndex>>,
 }
 
 #[derive(Debug, Clone)]
@@ -14,9 +36,75 @@ pub struct Query {
 
 impl QueryEngine {
     pub fn new() -> Self {
-        Self {
-            connection_pool: None,
+        Self::with_store(Arc::new(InMemoryNormativeStore::new()))
+    }
+
+    /// Runs queries against the given backend, e.g. one built by `open_normative_store`
+    pub fn with_store(store: Arc<dyn NormativeStore>) -> Self {
+        Self { store, text_index: RwLock::new(None) }
+    }
+
+    pub fn store(&self) -> Arc<dyn NormativeStore> {
+        Arc::clone(&self.store)
+    }
+
+    pub async fn find_frameworks(&self, query: &FrameworkQuery) -> AionResult<Vec<NormativeFramework>> {
+        self.store.query(query).await
+    }
+
+    pub async fn get_framework(&self, id: &NormativeId) -> AionResult<Option<NormativeFramework>> {
+        self.store.get(id).await
+    }
+
+    /// Store `framework` and bring the full-text index up to date with it
+    ///
+    /// Frameworks written to the store by other means are only searchable
+    /// once the index is rebuilt with [`QueryEngine::rebuild_text_index`].
+    pub async fn put_framework(&self, framework: NormativeFramework) -> AionResult<()> {
+        let mut index = self.text_index.write().await;
+        self.store.put(framework.clone()).await?;
+        if let Some(index) = index.as_mut() {
+            index.upsert(&framework);
         }
+        Ok(())
+    }
+
+    /// Re-index every stored framework
+    pub async fn rebuild_text_index(&self) -> AionResult<()> {
+        let mut index = self.text_index.write().await;
+        let frameworks = self.store.query(&FrameworkQuery::default()).await?;
+        *index = Some(TextIndex::build(&frameworks));
+        Ok(())
+    }
+
+    pub async fn framework_versions(&self, id: &NormativeId) -> AionResult<Vec<NormativeVersion>> {
+        self.store.list_versions(id).await
+    }
+
+    /// Relevance-ranked full-text search over every stored framework and requirement
+    pub async fn search_text(&self, query: &str, limit: usize) -> Vec<RankedMatch> {
+        self.search_text_filtered(query, &TextSearchFilter::default(), limit).await
+    }
+
+    pub async fn search_text_filtered(&self, query: &str, filter: &TextSearchFilter, limit: usize) -> Vec<RankedMatch> {
+        {
+            let index = self.text_index.read().await;
+            if let Some(index) = index.as_ref() {
+                return index.search_filtered(query, filter, limit);
+            }
+        }
+
+        let mut index = self.text_index.write().await;
+        if index.is_none() {
+            match self.store.query(&FrameworkQuery::default()).await {
+                Ok(frameworks) => *index = Some(TextIndex::build(&frameworks)),
+                Err(e) => {
+                    tracing::warn!("⚠️ Full-text search could not load frameworks: {}", e);
+                    return Vec::new();
+                }
+            }
+        }
+        index.as_ref().map_or_else(Vec::new, |index| index.search_filtered(query, filter, limit))
     }
 
     pub fn execute_query(&self, query: &Query) -> AionResult<Vec<HashMap<String, String>>> {
@@ -65,4 +153,357 @@ impl Default for QueryEngine {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+/// Restricts [`QueryEngine::search_text_filtered`] to part of the corpus
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TextSearchFilter {
+    pub jurisdiction: Option<Jurisdiction>,
+    /// Matched case-insensitively against the framework's `industry` metadata
+    pub industry: Option<String>,
+}
+
+impl TextSearchFilter {
+    pub fn matches(&self, framework: &NormativeFramework) -> bool {
+        let industries = framework.metadata.get(INDUSTRY_METADATA_KEY).map(String::as_str);
+        self.matches_fields(&framework.jurisdiction, industries)
+    }
+
+    fn matches_fields(&self, jurisdiction: &Jurisdiction, industries: Option<&str>) -> bool {
+        self.jurisdiction.as_ref().is_none_or(|j| jurisdiction == j)
+            && self.industry.as_ref().is_none_or(|industry| {
+                industries.is_some_and(|listed| {
+                    listed.split(',').any(|entry| entry.trim().eq_ignore_ascii_case(industry.trim()))
+                })
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedMatch {
+    pub framework_id: NormativeId,
+    /// Set when the match is a single requirement rather than the framework as a whole
+    pub requirement_id: Option<Uuid>,
+    pub framework_title: String,
+    pub title: String,
+    pub snippet: String,
+    pub score: f64,
+    pub matched_terms: Vec<String>,
+}
+
+struct IndexedDocument {
+    framework_id: NormativeId,
+    requirement_id: Option<Uuid>,
+    framework_title: String,
+    title: String,
+    body: String,
+    length: usize,
+    /// Distinct terms, to find the document's postings when it is removed
+    terms: Vec<String>,
+}
+
+/// What a [`TextSearchFilter`] needs to know about an indexed framework
+struct IndexedFramework {
+    jurisdiction: Jurisdiction,
+    industries: Option<String>,
+    documents: Vec<usize>,
+}
+
+/// Inverted index over frameworks and their requirements, scored with BM25
+///
+/// Frameworks can be replaced one at a time with [`TextIndex::upsert`];
+/// the slots of removed documents are left empty rather than renumbered.
+#[derive(Default)]
+pub struct TextIndex {
+    documents: Vec<Option<IndexedDocument>>,
+    postings: HashMap<String, Vec<(usize, usize)>>,
+    frameworks: HashMap<NormativeId, IndexedFramework>,
+    document_count: usize,
+    total_length: usize,
+}
+
+impl TextIndex {
+    pub fn build(frameworks: &[NormativeFramework]) -> Self {
+        let mut index = Self::default();
+        for framework in frameworks {
+            index.upsert(framework);
+        }
+        index
+    }
+
+    /// Index `framework`, replacing any earlier copy with the same id
+    pub fn upsert(&mut self, framework: &NormativeFramework) {
+        self.remove(&framework.id);
+
+        let mut documents = vec![self.add_document(
+            framework,
+            None,
+            &framework.title,
+            &format!("{} {}", framework.description, framework.tags.join(" ")),
+        )];
+        for requirement in &framework.requirements {
+            documents.push(self.add_document(
+                framework,
+                Some(requirement.id),
+                &requirement.title,
+                &requirement.description,
+            ));
+        }
+
+        self.frameworks.insert(framework.id.clone(), IndexedFramework {
+            jurisdiction: framework.jurisdiction.clone(),
+            industries: framework.metadata.get(INDUSTRY_METADATA_KEY).cloned(),
+            documents,
+        });
+    }
+
+    /// Drop every document of the framework `id`
+    pub fn remove(&mut self, id: &NormativeId) {
+        let Some(framework) = self.frameworks.remove(id) else { return };
+        let removed: HashSet<usize> = framework.documents.into_iter().collect();
+
+        for &doc_id in &removed {
+            if let Some(document) = self.documents[doc_id].take() {
+                self.document_count -= 1;
+                self.total_length -= document.length;
+                for term in &document.terms {
+                    if let Some(postings) = self.postings.get_mut(term) {
+                        postings.retain(|(posted, _)| !removed.contains(posted));
+                        if postings.is_empty() {
+                            self.postings.remove(term);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_document(&mut self, framework: &NormativeFramework, requirement_id: Option<Uuid>, title: &str, body: &str) -> usize {
+        let doc_id = self.documents.len();
+        let mut frequencies: HashMap<String, usize> = HashMap::new();
+        let title_tokens = tokenize(title);
+        let body_tokens = tokenize(body);
+
+        for token in &title_tokens {
+            *frequencies.entry(token.clone()).or_insert(0) += TITLE_BOOST;
+        }
+        for token in &body_tokens {
+            *frequencies.entry(token.clone()).or_insert(0) += 1;
+        }
+
+        let terms: Vec<String> = frequencies.keys().cloned().collect();
+        for (term, frequency) in frequencies {
+            self.postings.entry(term).or_default().push((doc_id, frequency));
+        }
+
+        let length = title_tokens.len() * TITLE_BOOST + body_tokens.len();
+        self.document_count += 1;
+        self.total_length += length;
+        self.documents.push(Some(IndexedDocument {
+            framework_id: framework.id.clone(),
+            requirement_id,
+            framework_title: framework.title.clone(),
+            title: title.to_string(),
+            body: body.to_string(),
+            length,
+            terms,
+        }));
+        doc_id
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Vec<RankedMatch> {
+        self.search_filtered(query, &TextSearchFilter::default(), limit)
+    }
+
+    /// Like [`TextIndex::search`], over the frameworks `filter` matches
+    ///
+    /// Term statistics still come from the whole index, so a document
+    /// scores the same whichever filter it is found through.
+    pub fn search_filtered(&self, query: &str, filter: &TextSearchFilter, limit: usize) -> Vec<RankedMatch> {
+        let included = |document: &IndexedDocument| {
+            self.frameworks
+                .get(&document.framework_id)
+                .is_some_and(|framework| filter.matches_fields(&framework.jurisdiction, framework.industries.as_deref()))
+        };
+        let average_length = if self.document_count == 0 {
+            0.0
+        } else {
+            self.total_length as f64 / self.document_count as f64
+        };
+        let terms: Vec<String> = tokenize(query)
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let total_docs = self.document_count as f64;
+        let mut scores: HashMap<usize, (f64, Vec<String>)> = HashMap::new();
+
+        for term in &terms {
+            let Some(postings) = self.postings.get(term) else { continue };
+            let doc_freq = postings.len() as f64;
+            let idf = ((total_docs - doc_freq + 0.5) / (doc_freq + 0.5) + 1.0).ln();
+
+            for &(doc_id, frequency) in postings {
+                let Some(document) = self.documents[doc_id].as_ref().filter(|document| included(document)) else {
+                    continue;
+                };
+                let tf = frequency as f64;
+                let length_norm = 1.0 - BM25_B + BM25_B * document.length as f64 / average_length;
+                let entry = scores.entry(doc_id).or_insert((0.0, Vec::new()));
+                entry.0 += idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * length_norm);
+                entry.1.push(term.clone());
+            }
+        }
+
+        let mut ranked: Vec<RankedMatch> = scores
+            .into_iter()
+            .filter_map(|(doc_id, (score, mut matched_terms))| {
+                let document = self.documents[doc_id].as_ref()?;
+                matched_terms.sort();
+                Some(RankedMatch {
+                    framework_id: document.framework_id.clone(),
+                    requirement_id: document.requirement_id,
+                    framework_title: document.framework_title.clone(),
+                    title: document.title.clone(),
+                    snippet: snippet(&document.body, &matched_terms),
+                    score,
+                    matched_terms,
+                })
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.title.cmp(&b.title))
+        });
+        ranked.truncate(limit);
+        ranked
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 1)
+        .map(|word| word.to_lowercase())
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// A window of the body starting a little before the first matched term
+fn snippet(body: &str, terms: &[String]) -> String {
+    let words: Vec<&str> = body.split_whitespace().collect();
+    let first_hit = words
+        .iter()
+        .position(|word| tokenize(word).iter().any(|token| terms.contains(token)))
+        .unwrap_or(0);
+    let start = first_hit.saturating_sub(SNIPPET_WORDS / 4);
+    let end = (start + SNIPPET_WORDS).min(words.len());
+
+    let mut text = words[start..end].join(" ");
+    if start > 0 {
+        text = format!("...{}", text);
+    }
+    if end < words.len() {
+        text.push_str("...");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aion_core::{testing, Requirement};
+
+    fn requirement(title: &str, description: &str) -> Requirement {
+        testing::requirement(title, description)
+    }
+
+    fn framework(title: &str, jurisdiction: Jurisdiction, industry: &str, requirements: Vec<Requirement>) -> NormativeFramework {
+        let mut framework = testing::framework(title, jurisdiction, requirements);
+        framework.metadata.insert(INDUSTRY_METADATA_KEY.to_string(), industry.to_string());
+        framework
+    }
+
+    #[tokio::test]
+    async fn test_search_text_ranks_most_relevant_article_first() {
+        let store = Arc::new(InMemoryNormativeStore::new());
+        store.put(framework(
+            "GDPR",
+            Jurisdiction::International,
+            "Technology, All Industries",
+            vec![
+                requirement(
+                    "Notification of a personal data breach",
+                    "In the case of a personal data breach the controller shall notify the supervisory authority \
+                     of the breach without undue delay and not later than 72 hours after becoming aware of it.",
+                ),
+                requirement(
+                    "Records of processing activities",
+                    "Each controller shall maintain a record of processing activities under its responsibility, \
+                     including any data transfers.",
+                ),
+            ],
+        )).await.unwrap();
+        store.put(framework(
+            "HIPAA",
+            Jurisdiction::Federal,
+            "Healthcare",
+            vec![requirement(
+                "Breach notification to individuals",
+                "A covered entity shall notify each individual whose unsecured protected health information has been breached.",
+            )],
+        )).await.unwrap();
+
+        let engine = QueryEngine::with_store(store);
+
+        let results = engine.search_text("personal data breach notification", 10).await;
+        assert!(!results.is_empty());
+        assert_eq!(results[0].title, "Notification of a personal data breach");
+        assert!(results[0].snippet.contains("breach"));
+        assert!(results.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        assert_eq!(engine.search_text("breach", 1).await.len(), 1);
+
+        let healthcare = engine.search_text_filtered(
+            "breach notification",
+            &TextSearchFilter { industry: Some("healthcare".to_string()), ..Default::default() },
+            10,
+        ).await;
+        assert!(!healthcare.is_empty());
+        assert!(healthcare.iter().all(|m| m.framework_title == "HIPAA"));
+
+        let international = engine.search_text_filtered(
+            "breach",
+            &TextSearchFilter { jurisdiction: Some(Jurisdiction::International), ..Default::default() },
+            10,
+        ).await;
+        assert!(international.iter().all(|m| m.framework_title == "GDPR"));
+    }
+
+    #[tokio::test]
+    async fn test_put_framework_updates_the_index_in_place() {
+        let engine = QueryEngine::new();
+        let mut gdpr = framework(
+            "GDPR",
+            Jurisdiction::International,
+            "Technology",
+            vec![requirement("Breach notification", "Notify the supervisory authority of a breach.")],
+        );
+        engine.put_framework(gdpr.clone()).await.unwrap();
+        engine.put_framework(framework("HIPAA", Jurisdiction::Federal, "Healthcare", Vec::new())).await.unwrap();
+        assert_eq!(engine.search_text("breach", 10).await.len(), 1);
+
+        gdpr.requirements = vec![requirement("Data portability", "Receive personal data in a portable format.")];
+        engine.put_framework(gdpr.clone()).await.unwrap();
+        assert!(engine.search_text("breach", 10).await.is_empty());
+        let portability = engine.search_text("portable", 10).await;
+        assert_eq!(portability.len(), 1);
+        assert_eq!(portability[0].framework_id, gdpr.id);
+
+        // Writes that bypass the engine only show up after a rebuild
+        engine.store().put(framework("SOX", Jurisdiction::Federal, "Finance", Vec::new())).await.unwrap();
+        assert!(engine.search_text("sox", 10).await.is_empty());
+        engine.rebuild_text_index().await.unwrap();
+        assert_eq!(engine.search_text("sox", 10).await.len(), 1);
+    }
+}
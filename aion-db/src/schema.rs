@@ -22,7 +22,9 @@ impl DatabaseSchema {
         Self::create_framework_supersessions_table(pool).await?;
         Self::create_framework_tags_table(pool).await?;
         Self::create_framework_metadata_table(pool).await?;
+        Self::create_framework_versions_table(pool).await?;
         Self::create_conflict_resolutions_table(pool).await?;
+        Self::create_sequence_counters_table(pool).await?;
         Self::create_indexes(pool).await?;
         Self::create_views(pool).await?;
         Ok(())
@@ -442,6 +444,46 @@ impl DatabaseSchema {
         Ok(())
     }
 
+    async fn create_framework_versions_table(pool: &Pool<Postgres>) -> AionResult<()> {
+        let query = r#"
+            CREATE TABLE IF NOT EXISTS framework_versions (
+                framework_id UUID NOT NULL REFERENCES normative_frameworks(id) ON DELETE CASCADE,
+                version VARCHAR(50) NOT NULL,
+                effective_from DATE NOT NULL,
+                effective_to DATE,
+                requirements JSONB NOT NULL DEFAULT '[]',
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                PRIMARY KEY (framework_id, version),
+                CONSTRAINT valid_version_period CHECK (effective_to IS NULL OR effective_to >= effective_from)
+            );
+        "#;
+
+        sqlx::query(query).execute(pool).await.map_err(|e| AionError::DatabaseError {
+            operation: "create_framework_versions_table".to_string(),
+            reason: e.to_string(),
+        })?;
+
+        Ok(())
+    }
+
+    pub async fn create_sequence_counters_table(pool: &Pool<Postgres>) -> AionResult<()> {
+        let query = r#"
+            CREATE TABLE IF NOT EXISTS sequence_counters (
+                domain VARCHAR(200) PRIMARY KEY,
+                value BIGINT NOT NULL,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                CONSTRAINT positive_sequence CHECK (value > 0)
+            );
+        "#;
+
+        sqlx::query(query).execute(pool).await.map_err(|e| AionError::DatabaseError {
+            operation: "create_sequence_counters_table".to_string(),
+            reason: e.to_string(),
+        })?;
+
+        Ok(())
+    }
+
     async fn create_conflict_resolutions_table(pool: &Pool<Postgres>) -> AionResult<()> {
         let query = r#"
             CREATE TABLE IF NOT EXISTS conflict_resolutions (
@@ -570,7 +612,9 @@ impl DatabaseSchema {
             "DROP VIEW IF EXISTS conflict_summary CASCADE;",
             "DROP VIEW IF EXISTS framework_summary CASCADE;",
             "DROP VIEW IF EXISTS active_frameworks CASCADE;",
+            "DROP TABLE IF EXISTS sequence_counters CASCADE;",
             "DROP TABLE IF EXISTS conflict_resolutions CASCADE;",
+            "DROP TABLE IF EXISTS framework_versions CASCADE;",
             "DROP TABLE IF EXISTS framework_metadata CASCADE;",
             "DROP TABLE IF EXISTS framework_tags CASCADE;",
             "DROP TABLE IF EXISTS framework_supersessions CASCADE;",
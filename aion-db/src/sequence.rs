@@ -0,0 +1,200 @@
+use aion_core::{AionError, AionResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Monotonic sequence numbers per domain (e.g. `audit`), starting at 1
+///
+/// Numbers within a domain are never handed out twice, by any caller
+/// sharing the backend. `aion_core::next_sequence_number` only holds that
+/// within one process.
+#[async_trait]
+pub trait SequenceStore: Send + Sync {
+    async fn next_sequence_number_for(&self, domain: &str) -> AionResult<u64>;
+}
+
+/// Process-local sequences, used by tests and single-node deployments
+#[derive(Default)]
+pub struct InMemorySequenceStore {
+    counters: Mutex<HashMap<String, u64>>,
+}
+
+impl InMemorySequenceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SequenceStore for InMemorySequenceStore {
+    async fn next_sequence_number_for(&self, domain: &str) -> AionResult<u64> {
+        let mut counters = self.counters.lock().map_err(|_| AionError::DatabaseError {
+            operation: "next_sequence_number".to_string(),
+            reason: "in-memory sequence lock poisoned".to_string(),
+        })?;
+        let counter = counters.entry(domain.to_string()).or_insert(0);
+        *counter += 1;
+        Ok(*counter)
+    }
+}
+
+/// Which backend [`open_sequence_store`] should build
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum SequenceStoreConfig {
+    #[default]
+    InMemory,
+    Postgres {
+        database_url: String,
+        #[serde(default = "default_max_connections")]
+        max_connections: u32,
+    },
+}
+
+fn default_max_connections() -> u32 {
+    5
+}
+
+pub async fn open_sequence_store(config: &SequenceStoreConfig) -> AionResult<Arc<dyn SequenceStore>> {
+    match config {
+        SequenceStoreConfig::InMemory => Ok(Arc::new(InMemorySequenceStore::new())),
+        SequenceStoreConfig::Postgres { database_url, max_connections } => {
+            let pool = PgPoolOptions::new()
+                .max_connections(*max_connections)
+                .connect(database_url)
+                .await
+                .map_err(|e| AionError::DatabaseError {
+                    operation: "connect".to_string(),
+                    reason: e.to_string(),
+                })?;
+            let store = PostgresSequenceStore::new(pool);
+            store.initialize().await?;
+            Ok(Arc::new(store))
+        }
+    }
+}
+
+/// Sequences kept in the `sequence_counters` table, shared by every instance using the database
+pub struct PostgresSequenceStore {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresSequenceStore {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn initialize(&self) -> AionResult<()> {
+        crate::schema::DatabaseSchema::create_sequence_counters_table(&self.pool).await
+    }
+
+    /// Next number of `domain`
+    ///
+    /// The increment is a single upsert, so the row lock serializes callers
+    /// across processes and each committed value is returned exactly once.
+    pub async fn next(&self, domain: &str) -> AionResult<u64> {
+        let query = r#"
+            INSERT INTO sequence_counters (domain, value)
+            VALUES ($1, 1)
+            ON CONFLICT (domain) DO UPDATE
+                SET value = sequence_counters.value + 1, updated_at = NOW()
+            RETURNING value;
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(domain)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AionError::DatabaseError {
+                operation: "next_sequence_number".to_string(),
+                reason: e.to_string(),
+            })?;
+
+        let value: i64 = row.get("value");
+        Ok(value as u64)
+    }
+}
+
+#[async_trait]
+impl SequenceStore for PostgresSequenceStore {
+    async fn next_sequence_number_for(&self, domain: &str) -> AionResult<u64> {
+        self.next(domain).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_callers_never_share_a_number() {
+        let store: Arc<dyn SequenceStore> = Arc::new(InMemorySequenceStore::new());
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let store = store.clone();
+                tokio::spawn(async move {
+                    let mut numbers = Vec::new();
+                    for _ in 0..500 {
+                        numbers.push(store.next_sequence_number_for("audit").await.unwrap());
+                    }
+                    numbers
+                })
+            })
+            .collect();
+        let mut numbers = Vec::new();
+        for task in tasks {
+            numbers.extend(task.await.unwrap());
+        }
+
+        let unique: HashSet<u64> = numbers.iter().copied().collect();
+        assert_eq!(unique.len(), 4000);
+        assert_eq!(unique.iter().max(), Some(&4000));
+        assert_eq!(store.next_sequence_number_for("filings").await.unwrap(), 1);
+    }
+
+    /// Two pools stand in for two instances
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "needs a Postgres database in AION_TEST_DATABASE_URL"]
+    async fn test_postgres_sequence_is_unique_across_instances() {
+        let database_url = std::env::var("AION_TEST_DATABASE_URL").expect("AION_TEST_DATABASE_URL is set");
+        let instances: Vec<Arc<PostgresSequenceStore>> = connect_instances(&database_url, 2).await;
+        instances[0].initialize().await.unwrap();
+        let domain = format!("test-{}", uuid::Uuid::new_v4());
+
+        let tasks: Vec<_> = (0..16)
+            .map(|task| {
+                let store = instances[task % instances.len()].clone();
+                let domain = domain.clone();
+                tokio::spawn(async move {
+                    let mut numbers = Vec::new();
+                    for _ in 0..50 {
+                        numbers.push(store.next(&domain).await.unwrap());
+                    }
+                    numbers
+                })
+            })
+            .collect();
+        let mut numbers = Vec::new();
+        for task in tasks {
+            numbers.extend(task.await.unwrap());
+        }
+
+        let unique: HashSet<u64> = numbers.iter().copied().collect();
+        assert_eq!(unique.len(), 800);
+        assert_eq!(unique.iter().max(), Some(&800));
+    }
+
+    async fn connect_instances(database_url: &str, count: usize) -> Vec<Arc<PostgresSequenceStore>> {
+        let mut stores = Vec::new();
+        for _ in 0..count {
+            let pool = PgPoolOptions::new().max_connections(4).connect(database_url).await.unwrap();
+            stores.push(Arc::new(PostgresSequenceStore::new(pool)));
+        }
+        stores
+    }
+}
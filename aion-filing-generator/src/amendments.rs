@@ -0,0 +1,169 @@
+/*!
+ * Filing Amendments
+ *
+ * Plans an amendment of a previously generated filing: applies a JSON
+ * merge patch to the prior filing's data and works out which template
+ * fields are carried over unchanged and which must be regenerated.
+ */
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{field_pointer, FormTemplate};
+
+/// Metadata key holding the ID of the filing an amendment is based on
+pub const AMENDMENT_BASE_FILING_KEY: &str = "amendment.base_filing_id";
+/// Metadata key holding the JSON array of carried-over field paths
+pub const AMENDMENT_CARRIED_OVER_KEY: &str = "amendment.carried_over_fields";
+/// Metadata key holding the JSON array of regenerated field paths
+pub const AMENDMENT_REGENERATED_KEY: &str = "amendment.regenerated_fields";
+
+/// Which parts of a filing an amendment reuses and which it regenerates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmendmentPlan {
+    /// Prior data with the changes applied
+    pub merged_data: Value,
+    /// JSON pointers of template fields reused from the base filing
+    pub carried_over: Vec<String>,
+    /// JSON pointers of changed values, narrowed to template fields where possible
+    pub regenerated: Vec<String>,
+}
+
+impl AmendmentPlan {
+    /// Plan an amendment of `base_data` by the merge patch `changes`
+    pub fn new(template: &FormTemplate, base_data: &Value, changes: &Value) -> Self {
+        let mut changed = Vec::new();
+        changed_paths(changes, String::new(), &mut changed);
+
+        let mut merged_data = base_data.clone();
+        merge_patch(&mut merged_data, changes);
+
+        let mut carried_over = Vec::new();
+        let mut regenerated = Vec::new();
+        for field in &template.fields {
+            let path = field_pointer(&field.field_id);
+            if changed.iter().any(|change| paths_overlap(&path, change)) {
+                regenerated.push(path);
+            } else if base_data.pointer(&path).is_some() {
+                carried_over.push(path);
+            }
+        }
+
+        // Changes outside the template schema still count as regenerated
+        for change in changed {
+            if !regenerated.iter().any(|path| paths_overlap(path, &change)) {
+                regenerated.push(change);
+            }
+        }
+
+        Self { merged_data, carried_over, regenerated }
+    }
+
+    /// The template narrowed to the fields that must be regenerated
+    pub fn regeneration_template(&self, template: &FormTemplate) -> FormTemplate {
+        let mut narrowed = template.clone();
+        narrowed
+            .fields
+            .retain(|field| self.regenerated.contains(&field_pointer(&field.field_id)));
+        narrowed
+    }
+
+    /// Copy regenerated values from `regenerated_data` into the merged data
+    pub fn apply_regenerated(&mut self, regenerated_data: &Value) {
+        for path in &self.regenerated {
+            if let Some(value) = regenerated_data.pointer(path) {
+                set_pointer(&mut self.merged_data, path, value.clone());
+            }
+        }
+    }
+}
+
+/// Apply an RFC 7396 JSON merge patch; `null` removes a member
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let Value::Object(target) = target else {
+        return;
+    };
+
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// Collect the JSON pointers of every leaf a merge patch touches
+fn changed_paths(patch: &Value, prefix: String, paths: &mut Vec<String>) {
+    match patch {
+        Value::Object(members) if !members.is_empty() => {
+            for (key, value) in members {
+                let segment = key.replace('~', "~0").replace('/', "~1");
+                changed_paths(value, format!("{}/{}", prefix, segment), paths);
+            }
+        }
+        _ if prefix.is_empty() => {}
+        _ => paths.push(prefix),
+    }
+}
+
+/// Whether one pointer equals or contains the other
+fn paths_overlap(a: &str, b: &str) -> bool {
+    let contains = |outer: &str, inner: &str| {
+        inner.strip_prefix(outer).is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    };
+    contains(a, b) || contains(b, a)
+}
+
+/// Set the value at a JSON pointer, creating intermediate objects
+fn set_pointer(target: &mut Value, pointer: &str, value: Value) {
+    let mut current = target;
+    for segment in pointer.split('/').skip(1) {
+        let key = segment.replace("~1", "/").replace("~0", "~");
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        let Value::Object(members) = current else {
+            return;
+        };
+        current = members.entry(key).or_insert(Value::Null);
+    }
+    *current = value;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_patch_and_changed_paths() {
+        let mut data = json!({"financials": {"revenue": 10, "expenses": 4}, "notes": "draft"});
+        let changes = json!({"financials": {"revenue": 12}, "notes": null});
+        merge_patch(&mut data, &changes);
+        assert_eq!(data, json!({"financials": {"revenue": 12, "expenses": 4}}));
+
+        let mut paths = Vec::new();
+        changed_paths(&changes, String::new(), &mut paths);
+        paths.sort();
+        assert_eq!(paths, vec!["/financials/revenue", "/notes"]);
+
+        assert!(paths_overlap("/financials", "/financials/revenue"));
+        assert!(!paths_overlap("/financials/rev", "/financials/revenue"));
+    }
+
+    #[test]
+    fn test_set_pointer_creates_parents() {
+        let mut data = json!({"filer": "n/a"});
+        set_pointer(&mut data, "/filer/cik", json!("0000320193"));
+        set_pointer(&mut data, "/a~1b", json!(true));
+        assert_eq!(data, json!({"filer": {"cik": "0000320193"}, "a/b": true}));
+    }
+}
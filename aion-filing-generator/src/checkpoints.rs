@@ -0,0 +1,331 @@
+/*!
+ * Filing Checkpoints
+ *
+ * Persists the intermediate results of a filing generation run so a run
+ * that fails part-way (for example on a signature service outage) can be
+ * resumed from its last successful stage instead of starting over.
+ */
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::{FilingRequest, GeneratedDocument, ValidationResult};
+
+/// Stages of a filing generation run, in execution order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum FilingStage {
+    /// The request passed validation; no work has been checkpointed yet
+    RequestValidated,
+    /// Source data was extracted and enhanced by the AI assistant
+    AiEnhanced,
+    /// The enhanced data passed the regulatory compliance checks
+    ComplianceValidated,
+    /// The document was rendered in the requested output format
+    DocumentGenerated,
+    /// The document was signed, or signing was not required
+    DocumentSigned,
+    /// The approval workflow was created, or none was requested
+    WorkflowCreated,
+}
+
+impl FilingStage {
+    /// The stage that runs after this one, if any
+    pub fn next(self) -> Option<FilingStage> {
+        match self {
+            FilingStage::RequestValidated => Some(FilingStage::AiEnhanced),
+            FilingStage::AiEnhanced => Some(FilingStage::ComplianceValidated),
+            FilingStage::ComplianceValidated => Some(FilingStage::DocumentGenerated),
+            FilingStage::DocumentGenerated => Some(FilingStage::DocumentSigned),
+            FilingStage::DocumentSigned => Some(FilingStage::WorkflowCreated),
+            FilingStage::WorkflowCreated => None,
+        }
+    }
+}
+
+/// Saved state of a filing generation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilingCheckpoint {
+    pub filing_id: Uuid,
+    pub request: FilingRequest,
+    /// Template version the filing is pinned to
+    pub template_version: Option<String>,
+    /// Last stage that completed successfully
+    pub stage: FilingStage,
+    pub ai_enhanced_data: Option<serde_json::Value>,
+    pub validation_results: Option<ValidationResult>,
+    pub ai_confidence: Option<f64>,
+    pub compliance_score: Option<f64>,
+    pub generated_document: Option<GeneratedDocument>,
+    pub signed_document: Option<GeneratedDocument>,
+    pub workflow_id: Option<String>,
+    /// Metadata copied onto the generated filing
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// Error of the most recent failed attempt
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FilingCheckpoint {
+    pub fn new(filing_id: Uuid, request: FilingRequest) -> Self {
+        let now = Utc::now();
+        Self {
+            filing_id,
+            request,
+            template_version: None,
+            stage: FilingStage::RequestValidated,
+            ai_enhanced_data: None,
+            validation_results: None,
+            ai_confidence: None,
+            compliance_score: None,
+            generated_document: None,
+            signed_document: None,
+            workflow_id: None,
+            metadata: HashMap::new(),
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether every stage has completed
+    pub fn is_complete(&self) -> bool {
+        self.stage.next().is_none()
+    }
+
+    /// Record that a stage completed successfully
+    pub fn complete_stage(&mut self, stage: FilingStage) {
+        self.stage = stage;
+        self.last_error = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Record a failed attempt at the stage after the current one
+    pub fn record_failure(&mut self, error: &anyhow::Error) {
+        self.last_error = Some(format!("{:#}", error));
+        self.updated_at = Utc::now();
+    }
+
+    pub fn ai_enhanced_data(&self) -> Result<&serde_json::Value> {
+        self.ai_enhanced_data
+            .as_ref()
+            .ok_or_else(|| anyhow!("Checkpoint for filing {} has no AI-enhanced data", self.filing_id))
+    }
+
+    pub fn generated_document(&self) -> Result<&GeneratedDocument> {
+        self.generated_document
+            .as_ref()
+            .ok_or_else(|| anyhow!("Checkpoint for filing {} has no generated document", self.filing_id))
+    }
+
+    pub fn signed_document(&self) -> Result<&GeneratedDocument> {
+        self.signed_document
+            .as_ref()
+            .ok_or_else(|| anyhow!("Checkpoint for filing {} has no signed document", self.filing_id))
+    }
+}
+
+/// Checkpoint failures
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    #[error("no checkpoint found for filing {filing_id}")]
+    NotFound { filing_id: Uuid },
+    #[error("filing {filing_id} has not completed; it stopped after stage {stage:?}")]
+    Incomplete { filing_id: Uuid, stage: FilingStage },
+}
+
+/// Checkpoint storage configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointConfig {
+    /// Directory checkpoints are written to; `None` keeps them in memory only
+    pub directory: Option<PathBuf>,
+    /// Keep checkpoints of completed filings so amendments can reuse their data
+    #[serde(default = "default_retain_completed")]
+    pub retain_completed: bool,
+}
+
+fn default_retain_completed() -> bool {
+    true
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            retain_completed: true,
+        }
+    }
+}
+
+/// Checkpoint store keyed by filing ID
+///
+/// Checkpoints are cached in memory and written through to the configured
+/// directory, so a run can also be resumed after a process restart.
+pub struct CheckpointStore {
+    checkpoints: Arc<RwLock<HashMap<Uuid, FilingCheckpoint>>>,
+    config: CheckpointConfig,
+}
+
+impl CheckpointStore {
+    pub async fn new(config: CheckpointConfig) -> Result<Self> {
+        match &config.directory {
+            Some(directory) => {
+                tokio::fs::create_dir_all(directory).await?;
+                info!("💾 Filing checkpoints persisted to {}", directory.display());
+            }
+            None => warn!("💾 No checkpoint directory configured; interrupted filings cannot resume after a restart"),
+        }
+
+        Ok(Self {
+            checkpoints: Arc::new(RwLock::new(HashMap::new())),
+            config,
+        })
+    }
+
+    pub async fn save(&self, checkpoint: &FilingCheckpoint) -> Result<()> {
+        if let Some(path) = self.path_for(checkpoint.filing_id) {
+            // Write then rename so a crash never leaves a truncated checkpoint
+            let staging = path.with_extension("json.tmp");
+            tokio::fs::write(&staging, serde_json::to_vec_pretty(checkpoint)?).await?;
+            tokio::fs::rename(&staging, &path).await?;
+        }
+
+        self.checkpoints.write().await.insert(checkpoint.filing_id, checkpoint.clone());
+        debug!("💾 Checkpoint saved for filing {} at {:?}", checkpoint.filing_id, checkpoint.stage);
+        Ok(())
+    }
+
+    pub async fn load(&self, filing_id: Uuid) -> Result<FilingCheckpoint> {
+        if let Some(checkpoint) = self.checkpoints.read().await.get(&filing_id) {
+            return Ok(checkpoint.clone());
+        }
+
+        let path = self.path_for(filing_id).ok_or(CheckpointError::NotFound { filing_id })?;
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(CheckpointError::NotFound { filing_id }.into());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let checkpoint: FilingCheckpoint = serde_json::from_slice(&bytes)?;
+
+        self.checkpoints.write().await.insert(filing_id, checkpoint.clone());
+        Ok(checkpoint)
+    }
+
+    /// Load the checkpoint of a filing that ran every stage
+    pub async fn load_completed(&self, filing_id: Uuid) -> Result<FilingCheckpoint> {
+        let checkpoint = self.load(filing_id).await?;
+        if !checkpoint.is_complete() {
+            return Err(CheckpointError::Incomplete { filing_id, stage: checkpoint.stage }.into());
+        }
+        Ok(checkpoint)
+    }
+
+    /// Called once a filing has completed; discards its checkpoint unless retained
+    pub async fn finish(&self, filing_id: Uuid) -> Result<()> {
+        if self.config.retain_completed {
+            return Ok(());
+        }
+        self.remove(filing_id).await
+    }
+
+    /// Discard the checkpoint of a filing
+    pub async fn remove(&self, filing_id: Uuid) -> Result<()> {
+        self.checkpoints.write().await.remove(&filing_id);
+
+        if let Some(path) = self.path_for(filing_id) {
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    fn path_for(&self, filing_id: Uuid) -> Option<PathBuf> {
+        self.config
+            .directory
+            .as_ref()
+            .map(|directory| directory.join(format!("{}.json", filing_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DataSource, FilingPeriod, OutputFormat, PeriodType};
+
+    fn checkpoint() -> FilingCheckpoint {
+        FilingCheckpoint::new(Uuid::new_v4(), FilingRequest {
+            organization_id: "org-1".to_string(),
+            form_type: "SEC 10-K".to_string(),
+            jurisdiction: "US".to_string(),
+            filing_period: FilingPeriod {
+                period_type: PeriodType::Annual,
+                start_date: chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                end_date: chrono::NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+                fiscal_year: 2024,
+            },
+            data_sources: vec![DataSource::Manual { data: serde_json::json!({}) }],
+            output_format: OutputFormat::PDF,
+            language: "en".to_string(),
+            require_signature: true,
+            signature_config: None,
+            workflow_config: None,
+            deadline: None,
+            metadata: HashMap::new(),
+            idempotency_key: None,
+        })
+    }
+
+    #[test]
+    fn test_stages_run_in_order() {
+        let mut stage = FilingStage::RequestValidated;
+        let mut visited = vec![stage];
+        while let Some(next) = stage.next() {
+            assert!(next > stage);
+            visited.push(next);
+            stage = next;
+        }
+        assert_eq!(visited.len(), 6);
+        assert_eq!(stage, FilingStage::WorkflowCreated);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_survives_restart() {
+        let directory = tempfile::tempdir().unwrap();
+        let config = CheckpointConfig {
+            directory: Some(directory.path().to_path_buf()),
+            retain_completed: false,
+        };
+
+        let mut saved = checkpoint();
+        saved.ai_enhanced_data = Some(serde_json::json!({"revenue": 1_000_000}));
+        saved.complete_stage(FilingStage::ComplianceValidated);
+        CheckpointStore::new(config.clone()).await.unwrap().save(&saved).await.unwrap();
+
+        let store = CheckpointStore::new(config).await.unwrap();
+        let loaded = store.load(saved.filing_id).await.unwrap();
+        assert_eq!(loaded.stage, FilingStage::ComplianceValidated);
+        assert_eq!(loaded.ai_enhanced_data, saved.ai_enhanced_data);
+
+        store.remove(saved.filing_id).await.unwrap();
+        let error = store.load(saved.filing_id).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<CheckpointError>(),
+            Some(CheckpointError::NotFound { .. })
+        ));
+    }
+}
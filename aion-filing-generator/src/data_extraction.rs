@@ -0,0 +1,610 @@
+/*!
+ * Data Extraction
+ *
+ * Pulls filing data out of the request's data sources and coerces every
+ * value to the type its form field expects: dates to ISO 8601, currency
+ * and percentage text to exact decimal numbers, yes/no answers to booleans.
+ * Numeric dates and decimal separators follow the filing's jurisdiction, so
+ * "1.234,56" and "31/12/2023" read correctly for an EU filing. Values that
+ * cannot be coerced are left as they are and reported with their field
+ * path, so validation errors later point at the same input.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::{Connection, Row};
+use tracing::{debug, info, warn};
+
+use crate::source_validation::{authorize, mask_connection_string};
+use crate::validators::field_pointer;
+use crate::{DataSource, FieldType, FileFormat, FilingRequest, FormTemplate};
+
+/// Filing metadata key holding the coercion issues of a filing, as JSON
+pub const COERCION_ISSUES_KEY: &str = "extraction.coercion_issues";
+
+/// Data extraction configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionConfig {
+    /// Coerce extracted values to the types of the template's fields
+    pub coerce_types: bool,
+    /// Unambiguous `chrono` formats accepted for date fields, tried in order
+    /// before the numeric formats of the filing's [`FilingLocale`]
+    pub date_formats: Vec<String>,
+    /// Query run against `Database` sources, with the organization ID, form
+    /// type and period start and end dates bound as `$1`–`$4`
+    ///
+    /// It must return text columns `field_id` (dotted for nested fields)
+    /// and `value`.
+    pub database_query: String,
+}
+
+impl Default for ExtractionConfig {
+    fn default() -> Self {
+        Self {
+            coerce_types: true,
+            date_formats: ["%Y-%m-%d", "%Y/%m/%d", "%B %d, %Y", "%b %d, %Y", "%d %B %Y"]
+                .iter()
+                .map(|format| format.to_string())
+                .collect(),
+            database_query: "SELECT field_id, value FROM filing_data \
+                WHERE organization_id = $1 AND form_type = $2 AND period_start = $3 AND period_end = $4"
+                .to_string(),
+        }
+    }
+}
+
+/// Decimal separator of a jurisdiction's numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecimalSeparator {
+    /// `1,234.56`
+    Point,
+    /// `1.234,56` or `1 234,56`
+    Comma,
+}
+
+/// Field order of a jurisdiction's numeric dates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DateOrder {
+    /// `12/31/2023`
+    MonthFirst,
+    /// `31/12/2023` or `31.12.2023`
+    DayFirst,
+    /// No numeric convention is assumed; only unambiguous formats are accepted
+    Unknown,
+}
+
+/// Number and date conventions of a filing's jurisdiction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilingLocale {
+    pub decimal_separator: DecimalSeparator,
+    pub date_order: DateOrder,
+}
+
+impl FilingLocale {
+    /// Conventions for a jurisdiction code such as `US`, `EU` or `DE`
+    ///
+    /// Unknown jurisdictions read `.` as the decimal separator and accept no
+    /// numeric dates, so an ambiguous `01/02/2023` is reported rather than guessed.
+    pub fn for_jurisdiction(jurisdiction: &str) -> Self {
+        let (decimal_separator, date_order) = match jurisdiction.trim().to_uppercase().as_str() {
+            "US" | "USA" => (DecimalSeparator::Point, DateOrder::MonthFirst),
+            "UK" | "GB" | "IE" | "AU" | "NZ" | "IN" | "SG" | "HK" | "MX" | "MY" | "IL" => {
+                (DecimalSeparator::Point, DateOrder::DayFirst)
+            }
+            "EU" | "DE" | "FR" | "ES" | "IT" | "NL" | "BE" | "AT" | "PT" | "LU" | "DK" | "SE" | "FI"
+            | "NO" | "PL" | "CZ" | "GR" | "HU" | "RO" | "BR" | "AR" | "CO" | "CL" | "VE" | "RU" | "TR"
+            | "ID" | "ZA" => (DecimalSeparator::Comma, DateOrder::DayFirst),
+            _ => (DecimalSeparator::Point, DateOrder::Unknown),
+        };
+        Self { decimal_separator, date_order }
+    }
+
+    /// Characters that may group the digits of the integer part
+    fn group_separators(&self) -> &'static [char] {
+        match self.decimal_separator {
+            DecimalSeparator::Point => &[',', ' ', '\u{a0}', '\u{202f}'],
+            DecimalSeparator::Comma => &['.', ' ', '\u{a0}', '\u{202f}'],
+        }
+    }
+
+    fn decimal_char(&self) -> char {
+        match self.decimal_separator {
+            DecimalSeparator::Point => '.',
+            DecimalSeparator::Comma => ',',
+        }
+    }
+
+    /// `chrono` formats of numeric dates in this locale
+    fn numeric_date_formats(&self) -> &'static [&'static str] {
+        match self.date_order {
+            DateOrder::MonthFirst => &["%m/%d/%Y", "%m-%d-%Y"],
+            DateOrder::DayFirst => &["%d/%m/%Y", "%d.%m.%Y", "%d-%m-%Y"],
+            DateOrder::Unknown => &[],
+        }
+    }
+}
+
+/// Formats used to coerce the values of one filing
+#[derive(Debug, Clone)]
+pub struct CoercionFormats<'a> {
+    pub locale: FilingLocale,
+    pub date_formats: &'a [String],
+}
+
+impl CoercionFormats<'_> {
+    fn parse_date(&self, text: &str) -> Option<NaiveDate> {
+        self.date_formats
+            .iter()
+            .map(String::as_str)
+            .chain(self.locale.numeric_date_formats().iter().copied())
+            .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+            .or_else(|| DateTime::parse_from_rfc3339(text).ok().map(|datetime| datetime.date_naive()))
+    }
+
+    /// RFC 3339 timestamps keep their offset; naive timestamps and dates are taken as UTC
+    fn parse_datetime(&self, text: &str) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(text)
+            .map(|datetime| datetime.with_timezone(&Utc))
+            .ok()
+            .or_else(|| {
+                let numeric = self.locale.numeric_date_formats().iter().flat_map(|date| {
+                    [format!("{} %H:%M:%S", date), format!("{} %H:%M", date)]
+                });
+                ["%Y-%m-%d %H:%M:%S".to_string(), "%Y-%m-%dT%H:%M:%S".to_string()]
+                    .into_iter()
+                    .chain(numeric)
+                    .find_map(|format| NaiveDateTime::parse_from_str(text, &format).ok())
+                    .map(|datetime| datetime.and_utc())
+            })
+            .or_else(|| self.parse_date(text).and_then(|date| date.and_hms_opt(0, 0, 0)).map(|datetime| datetime.and_utc()))
+    }
+}
+
+/// A value that could not be coerced to its field's type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoercionIssue {
+    /// JSON pointer to the value, e.g. `/financials/revenue`
+    pub field_path: String,
+    pub value: Value,
+    pub expected: String,
+}
+
+/// Data extracted for a filing, coerced to the template's field types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedFilingData {
+    pub data: Value,
+    pub coercion_issues: Vec<CoercionIssue>,
+}
+
+/// Data extraction service
+pub struct DataExtractionService {
+    config: ExtractionConfig,
+    http_client: reqwest::Client,
+}
+
+impl DataExtractionService {
+    pub async fn new(config: ExtractionConfig) -> Result<Self> {
+        info!("📥 Initializing data extraction service");
+
+        Ok(Self {
+            config,
+            http_client: reqwest::Client::new(),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("🚀 Starting data extraction service");
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        info!("🛑 Stopping data extraction service");
+        Ok(())
+    }
+
+    /// Extract the data of all request sources and coerce it to the template's schema
+    ///
+    /// Sources are merged in order, so later sources override fields of
+    /// earlier ones.
+    pub async fn extract_filing_data(
+        &self,
+        request: &FilingRequest,
+        template: &FormTemplate,
+    ) -> Result<ExtractedFilingData> {
+        let mut data = Value::Object(Map::new());
+        for source in &request.data_sources {
+            merge(&mut data, self.extract_source(source, request).await?);
+        }
+
+        let coercion_issues = if self.config.coerce_types {
+            let formats = CoercionFormats {
+                locale: FilingLocale::for_jurisdiction(&request.jurisdiction),
+                date_formats: &self.config.date_formats,
+            };
+            coerce_to_template(&mut data, template, &formats)
+        } else {
+            Vec::new()
+        };
+        for issue in &coercion_issues {
+            warn!("📥 Could not coerce {} to {}: {}", issue.field_path, issue.expected, issue.value);
+        }
+
+        debug!("📥 Extracted data for {} from {} sources", template.template_id, request.data_sources.len());
+        Ok(ExtractedFilingData { data, coercion_issues })
+    }
+
+    async fn extract_source(&self, source: &DataSource, request: &FilingRequest) -> Result<Value> {
+        match source {
+            DataSource::Manual { data } => Ok(data.clone()),
+            DataSource::File { path, format } => {
+                let content = tokio::fs::read_to_string(path)
+                    .await
+                    .with_context(|| format!("Failed to read data source {}", path))?;
+                match format {
+                    FileFormat::JSON => Ok(serde_json::from_str(&content)?),
+                    FileFormat::CSV => csv_record(&content),
+                    other => bail!("Extraction from {:?} files is not supported", other),
+                }
+            }
+            DataSource::Api { endpoint, credentials } => {
+                let response = authorize(self.http_client.get(endpoint), credentials)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                Ok(response.json().await?)
+            }
+            DataSource::Database { connection_string } => self.query_database(connection_string, request).await,
+        }
+    }
+
+    /// Field values of the request's organization, form and period
+    ///
+    /// Only PostgreSQL sources are supported, as in source validation.
+    async fn query_database(&self, connection_string: &str, request: &FilingRequest) -> Result<Value> {
+        if !connection_string.starts_with("postgres://") && !connection_string.starts_with("postgresql://") {
+            bail!("Only PostgreSQL database sources are supported");
+        }
+
+        let mut connection = sqlx::PgConnection::connect(connection_string)
+            .await
+            .map_err(|e| anyhow!("Failed to connect to database source: {}", mask_connection_string(&e.to_string())))?;
+        let rows = sqlx::query(&self.config.database_query)
+            .bind(&request.organization_id)
+            .bind(&request.form_type)
+            .bind(request.filing_period.start_date)
+            .bind(request.filing_period.end_date)
+            .fetch_all(&mut connection)
+            .await
+            .context("Failed to query database source")?;
+        let _ = connection.close().await;
+
+        let mut data = Value::Object(Map::new());
+        for row in rows {
+            let field_id: String = row.try_get("field_id")?;
+            let value: Option<String> = row.try_get("value")?;
+            merge(&mut data, nested_value(&field_id, value.map(Value::String).unwrap_or(Value::Null)));
+        }
+        debug!("📥 Read fields from database source for {}", request.form_type);
+        Ok(data)
+    }
+}
+
+/// `value` nested under the segments of a dotted field ID
+fn nested_value(field_id: &str, value: Value) -> Value {
+    field_id.rsplit('.').fold(value, |nested, segment| {
+        Value::Object(Map::from_iter([(segment.to_string(), nested)]))
+    })
+}
+
+/// Field values of a CSV file with a header row and a single record
+fn csv_record(content: &str) -> Result<Value> {
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let headers = reader.headers()?.clone();
+    let record = reader.records().next().ok_or_else(|| anyhow!("CSV data source has no records"))??;
+
+    Ok(Value::Object(
+        headers
+            .iter()
+            .zip(record.iter())
+            .map(|(header, value)| (header.to_string(), Value::String(value.to_string())))
+            .collect(),
+    ))
+}
+
+/// Deep-merge `source` into `target`; non-object values replace what was there
+fn merge(target: &mut Value, source: Value) {
+    match (target, source) {
+        (Value::Object(target), Value::Object(source)) => {
+            for (key, value) in source {
+                merge(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (target, source) => *target = source,
+    }
+}
+
+/// Coerce the value of every template field in place, returning the values that could not be
+pub fn coerce_to_template(data: &mut Value, template: &FormTemplate, formats: &CoercionFormats<'_>) -> Vec<CoercionIssue> {
+    let mut issues = Vec::new();
+    for field in &template.fields {
+        let path = field_pointer(&field.field_id);
+        let Some(value) = data.pointer_mut(&path).filter(|value| !value.is_null()) else {
+            continue;
+        };
+        match coerce_value(value, &field.field_type, formats) {
+            Ok(Some(coerced)) => *value = coerced,
+            Ok(None) => {}
+            Err(expected) => issues.push(CoercionIssue {
+                field_path: path,
+                value: value.clone(),
+                expected: expected.to_string(),
+            }),
+        }
+    }
+    issues
+}
+
+/// Coerced value, `None` if it already has the right type, or the expected type on failure
+fn coerce_value(value: &Value, field_type: &FieldType, formats: &CoercionFormats<'_>) -> Result<Option<Value>, &'static str> {
+    match field_type {
+        FieldType::Date => match value.as_str().map(str::trim) {
+            Some(text) => formats.parse_date(text)
+                .map(|date| Some(Value::String(date.format("%Y-%m-%d").to_string())))
+                .ok_or("a date"),
+            None => Err("a date"),
+        },
+        FieldType::DateTime => match value.as_str().map(str::trim) {
+            Some(text) => formats.parse_datetime(text)
+                .map(|datetime| Some(Value::String(datetime.to_rfc3339_opts(SecondsFormat::Secs, true))))
+                .ok_or("a date and time"),
+            None => Err("a date and time"),
+        },
+        FieldType::Number | FieldType::Currency | FieldType::Percentage => match value {
+            Value::Number(_) => Ok(None),
+            Value::String(text) => parse_number(text, &formats.locale).map(Some).ok_or("a number"),
+            _ => Err("a number"),
+        },
+        FieldType::Boolean => match value {
+            Value::Bool(_) => Ok(None),
+            Value::String(text) => parse_boolean(text).map(|flag| Some(Value::Bool(flag))).ok_or("a boolean"),
+            Value::Number(number) => match number.as_i64() {
+                Some(0) => Ok(Some(Value::Bool(false))),
+                Some(1) => Ok(Some(Value::Bool(true))),
+                _ => Err("a boolean"),
+            },
+            _ => Err("a boolean"),
+        },
+        // Identifiers such as CIKs and tax IDs often arrive as numbers
+        FieldType::Text | FieldType::Phone | FieldType::TaxId | FieldType::Select { .. } => match value {
+            Value::Number(number) => Ok(Some(Value::String(number.to_string()))),
+            Value::Bool(flag) => Ok(Some(Value::String(flag.to_string()))),
+            _ => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Exact decimal from text such as `$1,234.50`, `EUR 1.234,50`, `(500)` or `12.5%`
+///
+/// The locale decides the decimal separator. Grouping must be in threes,
+/// so a number written in another convention is rejected rather than
+/// misread. Accounting parentheses and a minus sign mark negatives. Values
+/// without a fractional part become JSON integers; others keep their digits.
+fn parse_number(text: &str, locale: &FilingLocale) -> Option<Value> {
+    let decoration = |c: char| c.is_alphabetic() || c.is_whitespace() || matches!(c, '%' | '$' | '€' | '£' | '¥' | '₹');
+
+    let mut text = text.trim();
+    let parenthesized = text.starts_with('(') && text.ends_with(')');
+    if parenthesized {
+        text = &text[1..text.len() - 1];
+    }
+    let text = text.trim_matches(decoration);
+    let (minus, text) = match text.strip_prefix('-').or_else(|| text.strip_prefix('\u{2212}')) {
+        Some(rest) => (true, rest.trim_matches(decoration)),
+        None => (false, text.strip_prefix('+').unwrap_or(text).trim_matches(decoration)),
+    };
+
+    let (integer, fraction) = match text.split_once(locale.decimal_char()) {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (text, None),
+    };
+    let integer = ungrouped_digits(integer, locale.group_separators())?;
+    let fraction = match fraction {
+        Some(fraction) if !fraction.is_empty() && fraction.chars().all(|c| c.is_ascii_digit()) => fraction,
+        Some(_) => return None,
+        None => "",
+    };
+
+    let mut number = if fraction.is_empty() {
+        Decimal::from_str(&integer).ok()?
+    } else {
+        Decimal::from_str(&format!("{}.{}", integer, fraction)).ok()?
+    };
+    number.set_sign_negative((parenthesized || minus) && !number.is_zero());
+
+    if number.scale() == 0 {
+        if let Some(integer) = number.to_i64() {
+            return Some(Value::from(integer));
+        }
+    }
+    serde_json::Number::from_str(&number.to_string()).ok().map(Value::Number)
+}
+
+/// Digits of an integer part, if any grouping separators split it into threes
+fn ungrouped_digits(integer: &str, separators: &[char]) -> Option<String> {
+    let groups: Vec<&str> = integer.split(|c| separators.contains(&c)).collect();
+    let well_formed = groups.iter().all(|group| !group.is_empty() && group.chars().all(|c| c.is_ascii_digit()))
+        && (groups.len() == 1 || (groups[0].len() <= 3 && groups[1..].iter().all(|group| group.len() == 3)));
+    well_formed.then(|| groups.concat())
+}
+
+fn parse_boolean(text: &str) -> Option<bool> {
+    match text.trim().to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" | "x" | "checked" | "on" => Some(true),
+        "false" | "no" | "n" | "0" | "unchecked" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FilingPeriod, FormField, OutputFormat};
+
+    fn field(field_id: &str, field_type: FieldType) -> FormField {
+        FormField {
+            field_id: field_id.to_string(),
+            name: field_id.to_string(),
+            description: String::new(),
+            field_type,
+            required: false,
+            validation: None,
+            default_value: None,
+            help_text: None,
+            conditional_logic: None,
+        }
+    }
+
+    #[test]
+    fn test_values_are_coerced_to_field_types() {
+        let template = FormTemplate::for_tests("test-form", vec![
+            field("period_end", FieldType::Date),
+            field("fiscal_year_end", FieldType::Date),
+            field("financials.revenue", FieldType::Currency),
+            field("financials.net_loss", FieldType::Currency),
+            field("financials.margin", FieldType::Percentage),
+            field("filer.cik", FieldType::Text),
+            field("filer.accelerated", FieldType::Boolean),
+            field("filed_at", FieldType::DateTime),
+        ]);
+        let mut data = serde_json::json!({
+            "period_end": "12/31/2023",
+            "fiscal_year_end": "2023-12-31",
+            "financials": {"revenue": "$1,234,567.50", "net_loss": "(5,000)", "margin": "12.5%"},
+            "filer": {"cik": 320193, "accelerated": "Yes"},
+            "filed_at": "2024-02-15 16:30:00",
+        });
+
+        let config = ExtractionConfig::default();
+        let formats = CoercionFormats { locale: FilingLocale::for_jurisdiction("US"), date_formats: &config.date_formats };
+        let issues = coerce_to_template(&mut data, &template, &formats);
+
+        assert!(issues.is_empty());
+        assert_eq!(data["period_end"], "2023-12-31");
+        assert_eq!(data["period_end"], data["fiscal_year_end"]);
+        assert_eq!(data["financials"]["revenue"].to_string(), "1234567.50");
+        assert_eq!(data["financials"]["net_loss"], -5000);
+        assert_eq!(data["financials"]["margin"], 12.5);
+        assert_eq!(data["filer"]["cik"], "320193");
+        assert_eq!(data["filer"]["accelerated"], true);
+        assert_eq!(data["filed_at"], "2024-02-15T16:30:00Z");
+    }
+
+    #[test]
+    fn test_numbers_and_dates_follow_the_jurisdiction() {
+        let template = FormTemplate::for_tests("test-form", vec![
+            field("period_end", FieldType::Date),
+            field("period_start", FieldType::Date),
+            field("revenue", FieldType::Currency),
+            field("assets", FieldType::Currency),
+        ]);
+        let config = ExtractionConfig::default();
+        let coerce = |jurisdiction: &str| {
+            let mut data = serde_json::json!({
+                "period_end": "31/12/2023",
+                "period_start": "01/02/2023",
+                "revenue": "1.234,56",
+                "assets": "€ 2 000 000,10",
+            });
+            let formats = CoercionFormats {
+                locale: FilingLocale::for_jurisdiction(jurisdiction),
+                date_formats: &config.date_formats,
+            };
+            let issues = coerce_to_template(&mut data, &template, &formats);
+            (data, issues)
+        };
+
+        let (eu, issues) = coerce("EU");
+        assert!(issues.is_empty());
+        assert_eq!(eu["period_end"], "2023-12-31");
+        assert_eq!(eu["period_start"], "2023-02-01");
+        assert_eq!(eu["revenue"].to_string(), "1234.56");
+        assert_eq!(eu["assets"].to_string(), "2000000.10");
+
+        // The same text is rejected, not misread, under US conventions
+        let (us, issues) = coerce("US");
+        let paths: Vec<_> = issues.iter().map(|issue| issue.field_path.as_str()).collect();
+        assert_eq!(paths, ["/period_end", "/revenue", "/assets"]);
+        assert_eq!(us["period_start"], "2023-01-02");
+        assert_eq!(us["revenue"], "1.234,56");
+
+        // Without a known convention, numeric dates are ambiguous
+        let (_, issues) = coerce("GLOBAL");
+        assert!(issues.iter().any(|issue| issue.field_path == "/period_start"));
+    }
+
+    #[test]
+    fn test_parse_number_keeps_decimal_digits_exactly() {
+        let us = FilingLocale::for_jurisdiction("US");
+
+        assert_eq!(parse_number("0.10", &us).unwrap().to_string(), "0.10");
+        assert_eq!(parse_number("-$1,000.01", &us).unwrap().to_string(), "-1000.01");
+        assert_eq!(parse_number("(500)", &us), Some(Value::from(-500)));
+        assert_eq!(parse_number("12345678901234567890.12", &us).unwrap().to_string(), "12345678901234567890.12");
+        assert_eq!(parse_number("12,34.5", &us), None);
+        assert_eq!(parse_number("1.234,56", &us), None);
+    }
+
+    #[test]
+    fn test_database_rows_nest_by_field_id() {
+        let mut data = Value::Object(Map::new());
+        merge(&mut data, nested_value("financials.revenue", Value::String("1000".to_string())));
+        merge(&mut data, nested_value("financials.assets", Value::Null));
+        merge(&mut data, nested_value("filer_name", Value::String("Acme".to_string())));
+
+        assert_eq!(
+            data,
+            serde_json::json!({"financials": {"revenue": "1000", "assets": null}, "filer_name": "Acme"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_uncoercible_values_are_reported_with_their_path() {
+        let template = FormTemplate::for_tests("test-form", vec![
+            field("period_end", FieldType::Date),
+            field("financials.revenue", FieldType::Currency),
+        ]);
+        let request = FilingRequest {
+            organization_id: "org-1".to_string(),
+            form_type: "test-form".to_string(),
+            jurisdiction: "US".to_string(),
+            filing_period: FilingPeriod::for_fiscal_quarter(2024, 4, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()).unwrap(),
+            data_sources: vec![
+                DataSource::Manual { data: serde_json::json!({"period_end": "end of year", "financials": {"revenue": "1000"}}) },
+                DataSource::Manual { data: serde_json::json!({"financials": {"revenue": "about a million"}}) },
+            ],
+            output_format: OutputFormat::PDF,
+            language: "en".to_string(),
+            require_signature: false,
+            signature_config: None,
+            workflow_config: None,
+            deadline: None,
+            metadata: Default::default(),
+            idempotency_key: None,
+        };
+
+        let service = DataExtractionService::new(ExtractionConfig::default()).await.unwrap();
+        let extracted = service.extract_filing_data(&request, &template).await.unwrap();
+
+        let paths: Vec<_> = extracted.coercion_issues.iter().map(|issue| issue.field_path.as_str()).collect();
+        assert_eq!(paths, ["/period_end", "/financials/revenue"]);
+        assert_eq!(extracted.coercion_issues[1].value, "about a million");
+        assert_eq!(extracted.data["period_end"], "end of year");
+    }
+}
@@ -0,0 +1,671 @@
+/*!
+ * Digital Signature Service
+ *
+ * Signs generated filing documents and verifies those signatures for
+ * auditors. Classical (Ed25519, ECDSA P-256) and quantum-safe
+ * (CRYSTALS-Dilithium5) signatures are supported. Every signature carries
+ * the signer's certificate chain, which is checked against the configured
+ * trust anchors on verification.
+ *
+ * Signing keys are never generated at startup: each algorithm's key and
+ * certificate chain are loaded from the configured key directory, where
+ * `provision_identity` writes them once. Signing with an algorithm that has
+ * no stored key fails.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use pqcrypto_dilithium::dilithium5;
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _, SecretKey as _};
+use ring::rand::SystemRandom;
+use ring::signature::{self as ring_signature, EcdsaKeyPair, Ed25519KeyPair, KeyPair, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::GeneratedDocument;
+
+/// Signature algorithms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SignatureAlgorithm {
+    Ed25519,
+    EcdsaP256,
+    /// CRYSTALS-Dilithium5, quantum-safe
+    Dilithium5,
+}
+
+impl SignatureAlgorithm {
+    const ALL: [SignatureAlgorithm; 3] = [Self::Ed25519, Self::EcdsaP256, Self::Dilithium5];
+
+    /// File name stem of this algorithm's key and certificate chain
+    fn file_stem(self) -> &'static str {
+        match self {
+            Self::Ed25519 => "ed25519",
+            Self::EcdsaP256 => "ecdsa-p256",
+            Self::Dilithium5 => "dilithium5",
+        }
+    }
+}
+
+/// Signature service configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureConfig {
+    pub default_algorithm: SignatureAlgorithm,
+    /// Subject name on the service's signing certificates
+    pub signer_name: String,
+    pub certificate_validity_days: i64,
+    /// Directory holding each algorithm's signing key and certificate chain
+    pub key_directory: Option<PathBuf>,
+    /// SHA-256 fingerprints (hex) of root public keys trusted on verification
+    pub trusted_root_fingerprints: Vec<String>,
+    /// Also trust the roots of the chains loaded from `key_directory`
+    pub trust_local_roots: bool,
+}
+
+impl Default for SignatureConfig {
+    fn default() -> Self {
+        Self {
+            default_algorithm: SignatureAlgorithm::Dilithium5,
+            signer_name: "AION-CR Filing Generator".to_string(),
+            certificate_validity_days: 365,
+            key_directory: None,
+            trusted_root_fingerprints: Vec::new(),
+            trust_local_roots: false,
+        }
+    }
+}
+
+/// Per-request signature options
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DigitalSignatureConfig {
+    /// Overrides `SignatureConfig::default_algorithm`
+    pub algorithm: Option<SignatureAlgorithm>,
+    pub reason: Option<String>,
+}
+
+/// Signature failures
+#[derive(Debug, thiserror::Error)]
+pub enum DigitalSignatureError {
+    #[error("document {document_id} carries no signature")]
+    Unsigned { document_id: Uuid },
+    #[error("signature carries no certificate chain")]
+    MissingCertificateChain,
+    #[error("signing with {algorithm:?} failed")]
+    SigningFailed { algorithm: SignatureAlgorithm },
+    #[error("no signing key is configured for {algorithm:?}")]
+    NoSigningKey { algorithm: SignatureAlgorithm },
+    #[error("stored {algorithm:?} key does not match its certificate")]
+    KeyMismatch { algorithm: SignatureAlgorithm },
+}
+
+/// Certificate binding a subject to a public key, signed by its issuer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignerCertificate {
+    pub subject: String,
+    pub issuer: String,
+    pub algorithm: SignatureAlgorithm,
+    /// Hex-encoded public key
+    pub public_key: String,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+    /// Hex-encoded issuer signature over the certificate body
+    pub issuer_signature: String,
+}
+
+impl SignerCertificate {
+    /// SHA-256 fingerprint (hex) of the certificate's public key
+    pub fn fingerprint(&self) -> String {
+        hex::decode(&self.public_key).ok().map_or_else(String::new, |key| sha256_hex(&key))
+    }
+
+    fn signed_body(&self) -> Vec<u8> {
+        format!(
+            "{}\n{}\n{:?}\n{}\n{}\n{}",
+            self.subject,
+            self.issuer,
+            self.algorithm,
+            self.public_key,
+            self.not_before.to_rfc3339(),
+            self.not_after.to_rfc3339()
+        )
+        .into_bytes()
+    }
+}
+
+/// A signature attached to a generated document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentSignature {
+    pub signature_id: Uuid,
+    pub algorithm: SignatureAlgorithm,
+    pub signed_at: DateTime<Utc>,
+    /// SHA-256 (hex) of the document content at signing time
+    pub document_digest: String,
+    /// Hex-encoded signature
+    pub signature: String,
+    pub reason: Option<String>,
+    /// Signer certificate first, root last
+    pub certificate_chain: Vec<SignerCertificate>,
+}
+
+/// Outcome of verifying a document signature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureVerification {
+    pub valid: bool,
+    pub signer: String,
+    pub signer_fingerprint: String,
+    pub algorithm: SignatureAlgorithm,
+    pub quantum_safe: bool,
+    pub signed_at: DateTime<Utc>,
+    /// Document content still matches the digest that was signed
+    pub digest_matches: bool,
+    pub signature_valid: bool,
+    pub chain_trusted: bool,
+    pub failures: Vec<String>,
+}
+
+/// Private key material of a signing identity
+enum SignerKey {
+    Ed25519(Ed25519KeyPair),
+    EcdsaP256(EcdsaKeyPair),
+    Dilithium5 { public: dilithium5::PublicKey, secret: dilithium5::SecretKey },
+}
+
+impl SignerKey {
+    /// A new key with its storable private key bytes
+    fn generate(algorithm: SignatureAlgorithm, rng: &SystemRandom) -> Result<(Self, Vec<u8>)> {
+        let failed = || DigitalSignatureError::SigningFailed { algorithm };
+        Ok(match algorithm {
+            SignatureAlgorithm::Ed25519 => {
+                let pkcs8 = Ed25519KeyPair::generate_pkcs8(rng).map_err(|_| failed())?;
+                let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).map_err(|_| failed())?;
+                (SignerKey::Ed25519(key), pkcs8.as_ref().to_vec())
+            }
+            SignatureAlgorithm::EcdsaP256 => {
+                let scheme = &ring_signature::ECDSA_P256_SHA256_FIXED_SIGNING;
+                let pkcs8 = EcdsaKeyPair::generate_pkcs8(scheme, rng).map_err(|_| failed())?;
+                let key = EcdsaKeyPair::from_pkcs8(scheme, pkcs8.as_ref(), rng).map_err(|_| failed())?;
+                (SignerKey::EcdsaP256(key), pkcs8.as_ref().to_vec())
+            }
+            SignatureAlgorithm::Dilithium5 => {
+                let (public, secret) = dilithium5::keypair();
+                let stored = secret.as_bytes().to_vec();
+                (SignerKey::Dilithium5 { public, secret }, stored)
+            }
+        })
+    }
+
+    /// Rebuild a stored key; Dilithium keys take their public half from the certificate
+    fn from_stored(
+        algorithm: SignatureAlgorithm,
+        stored: &[u8],
+        certificate: &SignerCertificate,
+        rng: &SystemRandom,
+    ) -> Result<Self> {
+        let mismatch = || DigitalSignatureError::KeyMismatch { algorithm };
+        let key = match algorithm {
+            SignatureAlgorithm::Ed25519 => {
+                SignerKey::Ed25519(Ed25519KeyPair::from_pkcs8(stored).map_err(|_| mismatch())?)
+            }
+            SignatureAlgorithm::EcdsaP256 => {
+                let scheme = &ring_signature::ECDSA_P256_SHA256_FIXED_SIGNING;
+                SignerKey::EcdsaP256(EcdsaKeyPair::from_pkcs8(scheme, stored, rng).map_err(|_| mismatch())?)
+            }
+            SignatureAlgorithm::Dilithium5 => {
+                let public = hex::decode(&certificate.public_key).ok()
+                    .and_then(|bytes| dilithium5::PublicKey::from_bytes(&bytes).ok())
+                    .ok_or_else(mismatch)?;
+                let secret = dilithium5::SecretKey::from_bytes(stored).map_err(|_| mismatch())?;
+                SignerKey::Dilithium5 { public, secret }
+            }
+        };
+
+        // A key that cannot produce signatures the certificate vouches for is unusable
+        let probe = b"aion-cr key check";
+        let signature = hex::encode(key.sign(probe, rng)?);
+        if !verify_with(algorithm, &certificate.public_key, probe, &signature) {
+            return Err(mismatch().into());
+        }
+        Ok(key)
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        match self {
+            SignerKey::Ed25519(key) => key.public_key().as_ref().to_vec(),
+            SignerKey::EcdsaP256(key) => key.public_key().as_ref().to_vec(),
+            SignerKey::Dilithium5 { public, .. } => public.as_bytes().to_vec(),
+        }
+    }
+
+    fn sign(&self, message: &[u8], rng: &SystemRandom) -> Result<Vec<u8>> {
+        Ok(match self {
+            SignerKey::Ed25519(key) => key.sign(message).as_ref().to_vec(),
+            SignerKey::EcdsaP256(key) => key
+                .sign(rng, message)
+                .map_err(|_| DigitalSignatureError::SigningFailed { algorithm: SignatureAlgorithm::EcdsaP256 })?
+                .as_ref()
+                .to_vec(),
+            SignerKey::Dilithium5 { secret, .. } => dilithium5::detached_sign(message, secret).as_bytes().to_vec(),
+        })
+    }
+}
+
+/// A signing key with its certificate chain
+struct SigningIdentity {
+    key: SignerKey,
+    chain: Vec<SignerCertificate>,
+}
+
+/// Digital signature service
+pub struct DigitalSignatureService {
+    config: SignatureConfig,
+    identities: HashMap<SignatureAlgorithm, SigningIdentity>,
+    trusted_roots: HashSet<String>,
+    rng: SystemRandom,
+}
+
+impl DigitalSignatureService {
+    /// Create the service with the signing identities stored in the key directory
+    ///
+    /// Algorithms without a stored key are left unconfigured, and signing
+    /// with them fails.
+    pub async fn new(config: SignatureConfig) -> Result<Self> {
+        info!("✍️ Initializing digital signature service");
+
+        let rng = SystemRandom::new();
+        let mut identities = HashMap::new();
+        let mut trusted_roots: HashSet<String> = config.trusted_root_fingerprints.iter().cloned().collect();
+
+        match &config.key_directory {
+            Some(directory) => {
+                for algorithm in SignatureAlgorithm::ALL {
+                    let Some(identity) = load_identity(directory, algorithm, &rng).await? else {
+                        continue;
+                    };
+                    if config.trust_local_roots {
+                        if let Some(root) = identity.chain.last() {
+                            trusted_roots.insert(root.fingerprint());
+                        }
+                    }
+                    identities.insert(algorithm, identity);
+                }
+            }
+            None => warn!("⚠️ No signing key directory configured; documents cannot be signed"),
+        }
+
+        Ok(Self {
+            config,
+            identities,
+            trusted_roots,
+            rng,
+        })
+    }
+
+    /// Generate a signing key and certificate chain and store them in the key directory
+    ///
+    /// The root key only signs the certificate and is not kept. Returns the
+    /// root fingerprint to add to verifiers' trusted roots. Existing keys
+    /// are never overwritten.
+    pub async fn provision_identity(config: &SignatureConfig, algorithm: SignatureAlgorithm) -> Result<String> {
+        let directory = config
+            .key_directory
+            .as_ref()
+            .ok_or(DigitalSignatureError::NoSigningKey { algorithm })?;
+        let (key_path, chain_path) = identity_paths(directory, algorithm);
+        if tokio::fs::try_exists(&key_path).await? {
+            return Err(anyhow!("Signing key {} already exists", key_path.display()));
+        }
+
+        let (stored_key, chain) = issue_identity(config, algorithm, &SystemRandom::new())?;
+        tokio::fs::create_dir_all(directory).await?;
+        tokio::fs::write(&chain_path, serde_json::to_vec_pretty(&chain)?).await?;
+        tokio::fs::write(&key_path, stored_key).await?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            tokio::fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o600)).await?;
+        }
+
+        info!("🔑 Provisioned {:?} signing key in {}", algorithm, directory.display());
+        Ok(chain.last().map(SignerCertificate::fingerprint).unwrap_or_default())
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("🚀 Starting digital signature service");
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        info!("🛑 Stopping digital signature service");
+        Ok(())
+    }
+
+    /// Sign a document, appending the signature to it
+    pub async fn sign_document(
+        &self,
+        mut document: GeneratedDocument,
+        signature_config: &Option<DigitalSignatureConfig>,
+    ) -> Result<GeneratedDocument> {
+        let algorithm = signature_config
+            .as_ref()
+            .and_then(|config| config.algorithm)
+            .unwrap_or(self.config.default_algorithm);
+        let identity = self
+            .identities
+            .get(&algorithm)
+            .ok_or(DigitalSignatureError::NoSigningKey { algorithm })?;
+
+        let signed_at = Utc::now();
+        let document_digest = sha256_hex(&document.content);
+        let message = signing_message(document.document_id, &document_digest, signed_at, algorithm);
+        let signature = identity.key.sign(&message, &self.rng)?;
+
+        document.signatures.push(DocumentSignature {
+            signature_id: Uuid::new_v4(),
+            algorithm,
+            signed_at,
+            document_digest,
+            signature: hex::encode(signature),
+            reason: signature_config.as_ref().and_then(|config| config.reason.clone()),
+            certificate_chain: identity.chain.clone(),
+        });
+
+        debug!("✍️ Document {} signed with {:?}", document.document_id, algorithm);
+        Ok(document)
+    }
+
+    /// Verify the most recent signature on a document
+    ///
+    /// The document digest is recomputed, so any change to the content
+    /// after signing shows up as `digest_matches: false`.
+    pub async fn verify_signature(&self, document: &GeneratedDocument) -> Result<SignatureVerification> {
+        let signature = document
+            .signatures
+            .last()
+            .ok_or(DigitalSignatureError::Unsigned { document_id: document.document_id })?;
+        let leaf = signature
+            .certificate_chain
+            .first()
+            .ok_or(DigitalSignatureError::MissingCertificateChain)?;
+
+        let mut failures = Vec::new();
+
+        let digest_matches = sha256_hex(&document.content) == signature.document_digest;
+        if !digest_matches {
+            failures.push("document content was modified after signing".to_string());
+        }
+
+        let message = signing_message(
+            document.document_id,
+            &signature.document_digest,
+            signature.signed_at,
+            signature.algorithm,
+        );
+        let signature_valid = leaf.algorithm == signature.algorithm
+            && verify_with(leaf.algorithm, &leaf.public_key, &message, &signature.signature);
+        if !signature_valid {
+            failures.push(format!("signature does not verify against the key of {}", leaf.subject));
+        }
+
+        let chain_failures = self.check_chain(&signature.certificate_chain, signature.signed_at);
+        let chain_trusted = chain_failures.is_empty();
+        failures.extend(chain_failures);
+
+        Ok(SignatureVerification {
+            valid: digest_matches && signature_valid && chain_trusted,
+            signer: leaf.subject.clone(),
+            signer_fingerprint: leaf.fingerprint(),
+            algorithm: signature.algorithm,
+            quantum_safe: signature.algorithm == SignatureAlgorithm::Dilithium5,
+            signed_at: signature.signed_at,
+            digest_matches,
+            signature_valid,
+            chain_trusted,
+            failures,
+        })
+    }
+
+    /// Check each certificate against its issuer and the root against the trust anchors
+    fn check_chain(&self, chain: &[SignerCertificate], signed_at: DateTime<Utc>) -> Vec<String> {
+        let mut failures = Vec::new();
+
+        for (index, certificate) in chain.iter().enumerate() {
+            // The last certificate is a self-signed root
+            let issuer = chain.get(index + 1).unwrap_or(certificate);
+            if certificate.issuer != issuer.subject {
+                failures.push(format!("{} is not issued by {}", certificate.subject, issuer.subject));
+            } else if !verify_with(
+                issuer.algorithm,
+                &issuer.public_key,
+                &certificate.signed_body(),
+                &certificate.issuer_signature,
+            ) {
+                failures.push(format!("certificate of {} has an invalid issuer signature", certificate.subject));
+            }
+            if signed_at < certificate.not_before || signed_at > certificate.not_after {
+                failures.push(format!("certificate of {} was not valid at signing time", certificate.subject));
+            }
+        }
+
+        match chain.last() {
+            Some(root) if self.trusted_roots.contains(&root.fingerprint()) => {}
+            Some(root) => failures.push(format!("root {} is not a trusted anchor", root.subject)),
+            None => failures.push("certificate chain is empty".to_string()),
+        }
+        failures
+    }
+}
+
+/// Key file and certificate chain file of an algorithm's identity
+fn identity_paths(directory: &Path, algorithm: SignatureAlgorithm) -> (PathBuf, PathBuf) {
+    (
+        directory.join(format!("{}.key", algorithm.file_stem())),
+        directory.join(format!("{}.chain.json", algorithm.file_stem())),
+    )
+}
+
+/// Load an algorithm's stored identity, if one has been provisioned
+async fn load_identity(directory: &Path, algorithm: SignatureAlgorithm, rng: &SystemRandom) -> Result<Option<SigningIdentity>> {
+    let (key_path, chain_path) = identity_paths(directory, algorithm);
+    let stored_key = match tokio::fs::read(&key_path).await {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            debug!("No {:?} signing key in {}", algorithm, directory.display());
+            return Ok(None);
+        }
+        Err(e) => return Err(anyhow!("Failed to read {}: {}", key_path.display(), e)),
+    };
+    let chain: Vec<SignerCertificate> = serde_json::from_slice(&tokio::fs::read(&chain_path).await?)
+        .map_err(|e| anyhow!("Invalid certificate chain {}: {}", chain_path.display(), e))?;
+    let leaf = chain.first().ok_or(DigitalSignatureError::MissingCertificateChain)?;
+    if leaf.algorithm != algorithm {
+        return Err(DigitalSignatureError::KeyMismatch { algorithm }.into());
+    }
+
+    let key = SignerKey::from_stored(algorithm, &stored_key, leaf, rng)?;
+    info!("🔑 Loaded {:?} signing key for {}", algorithm, leaf.subject);
+    Ok(Some(SigningIdentity { key, chain }))
+}
+
+/// Generate a self-signed root and a signing certificate issued by it
+///
+/// Returns the signing key's storable bytes with the chain.
+fn issue_identity(
+    config: &SignatureConfig,
+    algorithm: SignatureAlgorithm,
+    rng: &SystemRandom,
+) -> Result<(Vec<u8>, Vec<SignerCertificate>)> {
+    let not_before = Utc::now();
+    let not_after = not_before + Duration::days(config.certificate_validity_days);
+
+    let (root_key, _) = SignerKey::generate(algorithm, rng)?;
+    let root_subject = format!("{} Root CA ({:?})", config.signer_name, algorithm);
+    let root = certify(&root_key, &root_subject, &root_subject, &root_key, algorithm, not_before, not_after, rng)?;
+
+    let (key, stored_key) = SignerKey::generate(algorithm, rng)?;
+    let subject = format!("{} ({:?})", config.signer_name, algorithm);
+    let leaf = certify(&key, &subject, &root_subject, &root_key, algorithm, not_before, not_after, rng)?;
+
+    Ok((stored_key, vec![leaf, root]))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn certify(
+    subject_key: &SignerKey,
+    subject: &str,
+    issuer: &str,
+    issuer_key: &SignerKey,
+    algorithm: SignatureAlgorithm,
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+    rng: &SystemRandom,
+) -> Result<SignerCertificate> {
+    let mut certificate = SignerCertificate {
+        subject: subject.to_string(),
+        issuer: issuer.to_string(),
+        algorithm,
+        public_key: hex::encode(subject_key.public_key()),
+        not_before,
+        not_after,
+        issuer_signature: String::new(),
+    };
+    certificate.issuer_signature = hex::encode(issuer_key.sign(&certificate.signed_body(), rng)?);
+    Ok(certificate)
+}
+
+/// Bytes covered by a document signature
+fn signing_message(document_id: Uuid, digest: &str, signed_at: DateTime<Utc>, algorithm: SignatureAlgorithm) -> Vec<u8> {
+    format!("{}\n{}\n{}\n{:?}", document_id, digest, signed_at.to_rfc3339(), algorithm).into_bytes()
+}
+
+fn verify_with(algorithm: SignatureAlgorithm, public_key: &str, message: &[u8], signature: &str) -> bool {
+    let (Some(public_key), Some(signature)) = (hex::decode(public_key).ok(), hex::decode(signature).ok()) else {
+        return false;
+    };
+    match algorithm {
+        SignatureAlgorithm::Ed25519 => UnparsedPublicKey::new(&ring_signature::ED25519, &public_key)
+            .verify(message, &signature)
+            .is_ok(),
+        SignatureAlgorithm::EcdsaP256 => UnparsedPublicKey::new(&ring_signature::ECDSA_P256_SHA256_FIXED, &public_key)
+            .verify(message, &signature)
+            .is_ok(),
+        SignatureAlgorithm::Dilithium5 => {
+            match (
+                dilithium5::PublicKey::from_bytes(&public_key),
+                dilithium5::DetachedSignature::from_bytes(&signature),
+            ) {
+                (Ok(public_key), Ok(signature)) => {
+                    dilithium5::verify_detached_signature(&signature, message, &public_key).is_ok()
+                }
+                _ => false,
+            }
+        }
+    }
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(ring::digest::digest(&ring::digest::SHA256, data).as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutputFormat;
+
+    fn document() -> GeneratedDocument {
+        GeneratedDocument {
+            document_id: Uuid::new_v4(),
+            template_id: "sec-10-k".to_string(),
+            format: OutputFormat::JSON,
+            mime_type: "application/json".to_string(),
+            content: br#"{"revenue": 391035000}"#.to_vec(),
+            generated_at: Utc::now(),
+            metadata: HashMap::new(),
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Config with every algorithm provisioned in `directory`
+    async fn provisioned(directory: &Path) -> SignatureConfig {
+        let config = SignatureConfig { key_directory: Some(directory.to_path_buf()), ..SignatureConfig::default() };
+        for algorithm in SignatureAlgorithm::ALL {
+            DigitalSignatureService::provision_identity(&config, algorithm).await.unwrap();
+        }
+        config
+    }
+
+    #[tokio::test]
+    async fn test_signatures_verify_and_detect_tampering() {
+        let directory = tempfile::tempdir().unwrap();
+        let config = SignatureConfig { trust_local_roots: true, ..provisioned(directory.path()).await };
+        let service = DigitalSignatureService::new(config).await.unwrap();
+
+        for algorithm in SignatureAlgorithm::ALL {
+            let options = Some(DigitalSignatureConfig { algorithm: Some(algorithm), reason: None });
+            let signed = service.sign_document(document(), &options).await.unwrap();
+
+            let verification = service.verify_signature(&signed).await.unwrap();
+            assert!(verification.valid, "{:?}: {:?}", algorithm, verification.failures);
+            assert_eq!(verification.quantum_safe, algorithm == SignatureAlgorithm::Dilithium5);
+
+            let mut tampered = signed.clone();
+            tampered.content = br#"{"revenue": 1}"#.to_vec();
+            let verification = service.verify_signature(&tampered).await.unwrap();
+            assert!(!verification.valid);
+            assert!(!verification.digest_matches);
+            assert!(verification.signature_valid);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_foreign_roots_are_untrusted() {
+        let directory = tempfile::tempdir().unwrap();
+        let signer = DigitalSignatureService::new(provisioned(directory.path()).await).await.unwrap();
+        let auditor = DigitalSignatureService::new(SignatureConfig::default()).await.unwrap();
+        let signed = signer.sign_document(document(), &None).await.unwrap();
+
+        let verification = auditor.verify_signature(&signed).await.unwrap();
+        assert!(verification.digest_matches && verification.signature_valid);
+        assert!(!verification.chain_trusted);
+        assert!(!verification.valid);
+
+        let root = signed.signatures[0].certificate_chain.last().unwrap().fingerprint();
+        let auditor = DigitalSignatureService::new(SignatureConfig {
+            trusted_root_fingerprints: vec![root],
+            ..SignatureConfig::default()
+        })
+        .await
+        .unwrap();
+        assert!(auditor.verify_signature(&signed).await.unwrap().valid);
+
+        let error = auditor.verify_signature(&document()).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<DigitalSignatureError>(),
+            Some(DigitalSignatureError::Unsigned { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_keys_persist_and_missing_keys_fail() {
+        let unconfigured = DigitalSignatureService::new(SignatureConfig::default()).await.unwrap();
+        let error = unconfigured.sign_document(document(), &None).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<DigitalSignatureError>(),
+            Some(DigitalSignatureError::NoSigningKey { algorithm: SignatureAlgorithm::Dilithium5 })
+        ));
+
+        let directory = tempfile::tempdir().unwrap();
+        let config = provisioned(directory.path()).await;
+        let first = DigitalSignatureService::new(config.clone()).await.unwrap();
+        let restarted = DigitalSignatureService::new(config.clone()).await.unwrap();
+        let a = first.sign_document(document(), &None).await.unwrap();
+        let b = restarted.sign_document(document(), &None).await.unwrap();
+        assert_eq!(a.signatures[0].certificate_chain[0].public_key, b.signatures[0].certificate_chain[0].public_key);
+
+        // Signatures from the service's own chain are not trusted unless configured
+        assert!(!restarted.verify_signature(&a).await.unwrap().chain_trusted);
+        assert!(DigitalSignatureService::provision_identity(&config, SignatureAlgorithm::Ed25519).await.is_err());
+    }
+}
@@ -12,9 +12,11 @@ pub mod canada_forms;
 pub mod australia_forms;
 pub mod international_forms;
 pub mod form_registry;
+pub mod versions;
 
 // Re-export main types
 pub use form_registry::*;
+pub use versions::*;
 
 use std::sync::Arc;
 use std::collections::HashMap;
@@ -38,6 +40,9 @@ pub struct FormLibrary {
     /// Template cache
     template_cache: Arc<RwLock<HashMap<String, CachedTemplate>>>,
 
+    /// Snapshots of every template version served
+    version_archive: Arc<TemplateVersionArchive>,
+
     /// Configuration
     config: FormLibraryConfig,
 }
@@ -50,6 +55,9 @@ pub struct FormLibraryConfig {
     pub update_interval: chrono::Duration,
     pub supported_languages: Vec<String>,
     pub default_jurisdiction: String,
+    /// Directory template version snapshots are persisted to; `None` keeps them in memory
+    #[serde(default)]
+    pub version_archive_dir: Option<std::path::PathBuf>,
 }
 
 impl Default for FormLibraryConfig {
@@ -65,6 +73,7 @@ impl Default for FormLibraryConfig {
                 "ko".to_string(), "ar".to_string(), "hi".to_string(),
             ],
             default_jurisdiction: "GLOBAL".to_string(),
+            version_archive_dir: None,
         }
     }
 }
@@ -85,6 +94,55 @@ pub struct FormTemplate {
     pub template_content: TemplateContent,
     pub metadata: FormMetadata,
     pub localization: HashMap<String, LocalizedContent>,
+    /// Field-to-concept mapping used for XBRL and iXBRL output
+    #[serde(default)]
+    pub xbrl_tagging: Option<crate::XbrlTaggingDefinition>,
+}
+
+impl FormTemplate {
+    /// Identifier including the exact version, e.g. `sec-10-k@2024.1`
+    pub fn versioned_id(&self) -> String {
+        format!("{}@{}", self.template_id, self.version)
+    }
+}
+
+#[cfg(test)]
+impl FormTemplate {
+    /// Minimal template with the given fields for unit tests
+    pub(crate) fn for_tests(template_id: &str, fields: Vec<FormField>) -> Self {
+        Self {
+            template_id: template_id.to_string(),
+            name: template_id.to_string(),
+            description: String::new(),
+            version: "2024.1".to_string(),
+            jurisdiction: "US".to_string(),
+            compliance_framework: "Securities".to_string(),
+            category: FormCategory::Securities,
+            fields,
+            sections: Vec::new(),
+            validation_rules: Vec::new(),
+            template_content: TemplateContent {
+                template_type: TemplateType::Handlebars,
+                content: String::new(),
+                variables: HashMap::new(),
+                layouts: HashMap::new(),
+            },
+            metadata: FormMetadata {
+                created_date: chrono::Utc::now(),
+                updated_date: chrono::Utc::now(),
+                version_history: Vec::new(),
+                tags: Vec::new(),
+                regulatory_authority: "SEC".to_string(),
+                submission_method: SubmissionMethod::Electronic,
+                filing_frequency: FilingFrequency::Annual,
+                deadline_rules: Vec::new(),
+                dependencies: Vec::new(),
+                related_forms: Vec::new(),
+            },
+            localization: HashMap::new(),
+            xbrl_tagging: None,
+        }
+    }
 }
 
 /// Form categories
@@ -360,12 +418,14 @@ impl FormLibrary {
         info!("📚 Initializing form library");
 
         let registry = Arc::new(FormRegistry::new().await?);
+        let version_archive = Arc::new(TemplateVersionArchive::open(config.version_archive_dir.clone()).await?);
 
         Ok(Self {
             registry,
             templates: Arc::new(RwLock::new(HashMap::new())),
             metadata_index: Arc::new(RwLock::new(HashMap::new())),
             template_cache: Arc::new(RwLock::new(HashMap::new())),
+            version_archive,
             config,
         })
     }
@@ -413,6 +473,9 @@ impl FormLibrary {
         // Load from registry
         let template = self.registry.get_template(template_id).await?;
 
+        // Snapshot this version so filings generated from it stay reproducible
+        self.version_archive.archive(template_id, &template).await?;
+
         // Cache the template
         {
             let mut cache = self.template_cache.write().await;
@@ -427,6 +490,24 @@ impl FormLibrary {
         Ok(template)
     }
 
+    /// Get a specific archived version of a template
+    pub async fn get_template_version(&self, form_type: &str, version: &str) -> Result<FormTemplate> {
+        debug!("📋 Getting template: {} version {}", form_type, version);
+
+        // Make sure the current version is archived before looking it up
+        if self.version_archive.get(form_type, version).await.is_err() {
+            self.get_template(form_type).await?;
+        }
+
+        self.version_archive.get(form_type, version).await
+    }
+
+    /// List the archived versions of a template, oldest first
+    pub async fn list_template_versions(&self, form_type: &str) -> Result<Vec<String>> {
+        self.get_template(form_type).await?;
+        Ok(self.version_archive.list(form_type).await)
+    }
+
     /// Register a new template
     pub async fn register_template(&self, config: FormTemplateConfig) -> Result<String> {
         info!("📝 Registering template: {}", config.name);
@@ -0,0 +1,203 @@
+/*!
+ * Template Version Archive
+ *
+ * Keeps an immutable snapshot of every template version the library has
+ * served, so a filing generated against an older version can be
+ * reproduced after the template is updated.
+ */
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+use super::FormTemplate;
+
+/// Template versioning failures
+#[derive(Debug, thiserror::Error)]
+pub enum TemplateVersionError {
+    #[error("form {form_type} has no archived version {version}")]
+    VersionNotFound { form_type: String, version: String },
+}
+
+/// A snapshot of one template version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedTemplate {
+    pub form_type: String,
+    pub version: String,
+    pub archived_at: DateTime<Utc>,
+    pub template: FormTemplate,
+}
+
+/// Archive of template versions by form type
+///
+/// The first snapshot of a version wins; later loads of the same version
+/// never overwrite it.
+pub struct TemplateVersionArchive {
+    versions: RwLock<HashMap<String, Vec<ArchivedTemplate>>>,
+    directory: Option<PathBuf>,
+}
+
+impl TemplateVersionArchive {
+    /// Open the archive, loading snapshots persisted in `directory`
+    pub async fn open(directory: Option<PathBuf>) -> Result<Self> {
+        let mut versions: HashMap<String, Vec<ArchivedTemplate>> = HashMap::new();
+
+        if let Some(directory) = &directory {
+            tokio::fs::create_dir_all(directory).await?;
+            let mut form_dirs = tokio::fs::read_dir(directory).await?;
+            while let Some(form_dir) = form_dirs.next_entry().await? {
+                if !form_dir.file_type().await?.is_dir() {
+                    continue;
+                }
+                let mut files = tokio::fs::read_dir(form_dir.path()).await?;
+                while let Some(file) = files.next_entry().await? {
+                    if file.path().extension().is_some_and(|extension| extension == "json") {
+                        let archived: ArchivedTemplate = serde_json::from_slice(&tokio::fs::read(file.path()).await?)?;
+                        versions.entry(archived.form_type.clone()).or_default().push(archived);
+                    }
+                }
+            }
+            for snapshots in versions.values_mut() {
+                snapshots.sort_by_key(|snapshot| snapshot.archived_at);
+            }
+            info!("🗄️ Loaded {} archived template versions", versions.values().map(Vec::len).sum::<usize>());
+        }
+
+        Ok(Self {
+            versions: RwLock::new(versions),
+            directory,
+        })
+    }
+
+    /// Snapshot a template version unless it is already archived
+    pub async fn archive(&self, form_type: &str, template: &FormTemplate) -> Result<()> {
+        let mut versions = self.versions.write().await;
+        let snapshots = versions.entry(form_type.to_string()).or_default();
+        if snapshots.iter().any(|snapshot| snapshot.version == template.version) {
+            return Ok(());
+        }
+
+        let archived = ArchivedTemplate {
+            form_type: form_type.to_string(),
+            version: template.version.clone(),
+            archived_at: Utc::now(),
+            template: template.clone(),
+        };
+
+        if let Some(directory) = &self.directory {
+            let form_dir = directory.join(file_name(form_type));
+            tokio::fs::create_dir_all(&form_dir).await?;
+            let path = form_dir.join(format!("{}.json", file_name(&template.version)));
+            tokio::fs::write(&path, serde_json::to_vec_pretty(&archived)?).await?;
+        }
+
+        debug!("🗄️ Archived {} version {}", form_type, template.version);
+        snapshots.push(archived);
+        Ok(())
+    }
+
+    pub async fn get(&self, form_type: &str, version: &str) -> Result<FormTemplate> {
+        self.versions
+            .read()
+            .await
+            .get(form_type)
+            .and_then(|snapshots| snapshots.iter().find(|snapshot| snapshot.version == version))
+            .map(|snapshot| snapshot.template.clone())
+            .ok_or_else(|| {
+                TemplateVersionError::VersionNotFound {
+                    form_type: form_type.to_string(),
+                    version: version.to_string(),
+                }
+                .into()
+            })
+    }
+
+    /// Archived versions of a form, oldest first
+    pub async fn list(&self, form_type: &str) -> Vec<String> {
+        self.versions
+            .read()
+            .await
+            .get(form_type)
+            .map(|snapshots| snapshots.iter().map(|snapshot| snapshot.version.clone()).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Percent-encoded form type or version, so distinct values never share a path
+///
+/// Dots are kept for readable versions, except a leading one, which would
+/// make `.` and `..` refer to other directories.
+fn file_name(text: &str) -> String {
+    let mut name = String::new();
+    for (index, byte) in text.bytes().enumerate() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || (byte == b'.' && index > 0) {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(version: &str, description: &str) -> FormTemplate {
+        let mut template = FormTemplate::for_tests("sec-10-k", Vec::new());
+        template.version = version.to_string();
+        template.description = description.to_string();
+        template
+    }
+
+    #[tokio::test]
+    async fn test_first_snapshot_of_a_version_is_kept() {
+        let archive = TemplateVersionArchive::open(None).await.unwrap();
+        archive.archive("SEC 10-K", &template("2024.1", "original")).await.unwrap();
+        archive.archive("SEC 10-K", &template("2024.1", "edited in place")).await.unwrap();
+        archive.archive("SEC 10-K", &template("2025.1", "updated")).await.unwrap();
+
+        assert_eq!(archive.list("SEC 10-K").await, vec!["2024.1", "2025.1"]);
+        assert_eq!(archive.get("SEC 10-K", "2024.1").await.unwrap().description, "original");
+
+        let error = archive.get("SEC 10-K", "2023.1").await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<TemplateVersionError>(),
+            Some(TemplateVersionError::VersionNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_archive_is_reloaded_from_disk() {
+        let directory = tempfile::tempdir().unwrap();
+        let archive = TemplateVersionArchive::open(Some(directory.path().to_path_buf())).await.unwrap();
+        archive.archive("SEC 10-K", &template("2024.1", "original")).await.unwrap();
+
+        let reopened = TemplateVersionArchive::open(Some(directory.path().to_path_buf())).await.unwrap();
+        let restored = reopened.get("SEC 10-K", "2024.1").await.unwrap();
+        assert_eq!(
+            serde_json::to_string(&restored).unwrap(),
+            serde_json::to_string(&archive.get("SEC 10-K", "2024.1").await.unwrap()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_similar_form_types_do_not_share_files() {
+        let directory = tempfile::tempdir().unwrap();
+        let archive = TemplateVersionArchive::open(Some(directory.path().to_path_buf())).await.unwrap();
+        archive.archive("SEC 10-K", &template("2024.1", "spaced")).await.unwrap();
+        archive.archive("SEC_10-K", &template("2024.1", "underscored")).await.unwrap();
+        archive.archive("..", &template("..", "dots")).await.unwrap();
+
+        let reopened = TemplateVersionArchive::open(Some(directory.path().to_path_buf())).await.unwrap();
+        assert_eq!(reopened.get("SEC 10-K", "2024.1").await.unwrap().description, "spaced");
+        assert_eq!(reopened.get("SEC_10-K", "2024.1").await.unwrap().description, "underscored");
+        assert_eq!(reopened.get("..", "..").await.unwrap().description, "dots");
+        assert!(directory.path().join("%2E.").is_dir());
+    }
+}
@@ -0,0 +1,288 @@
+/*!
+ * Document Generators
+ *
+ * Renders filing data into the requested output format. Structured
+ * formats (JSON, XML, XBRL, iXBRL) are produced directly from the data;
+ * HTML is rendered from the template's Handlebars content when present.
+ */
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::{
+    field_pointer, render_ixbrl, render_xbrl, xml_escape, DocumentSignature, FormTemplate, OutputFormat, TemplateType,
+};
+
+/// Document generator configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratorConfig {
+    /// Largest document that may be produced, in bytes
+    pub max_document_size_bytes: usize,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            max_document_size_bytes: 50 * 1024 * 1024,
+        }
+    }
+}
+
+/// Document generation failures
+#[derive(Debug, thiserror::Error)]
+pub enum DocumentGenerationError {
+    #[error("output format {format:?} is not supported by this build")]
+    UnsupportedFormat { format: OutputFormat },
+    #[error("generated document is {size} bytes, limit is {limit}")]
+    DocumentTooLarge { size: usize, limit: usize },
+}
+
+/// A rendered filing document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedDocument {
+    pub document_id: Uuid,
+    pub template_id: String,
+    pub format: OutputFormat,
+    pub mime_type: String,
+    pub content: Vec<u8>,
+    pub generated_at: DateTime<Utc>,
+    pub metadata: HashMap<String, String>,
+    /// Signatures applied by the signature service, oldest first
+    #[serde(default)]
+    pub signatures: Vec<DocumentSignature>,
+}
+
+/// HTML preview of a form
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormPreview {
+    pub template_id: String,
+    pub html: String,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Document generator status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratorStatus {
+    pub total_generated: u64,
+    pub last_generation: Option<DateTime<Utc>>,
+}
+
+/// Document generators for all supported output formats
+pub struct DocumentGenerators {
+    config: GeneratorConfig,
+    total_generated: AtomicU64,
+    last_generation: RwLock<Option<DateTime<Utc>>>,
+}
+
+impl DocumentGenerators {
+    pub async fn new(config: GeneratorConfig) -> Result<Self> {
+        info!("🖨️ Initializing document generators");
+
+        Ok(Self {
+            config,
+            total_generated: AtomicU64::new(0),
+            last_generation: RwLock::new(None),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("🚀 Starting document generators");
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        info!("🛑 Stopping document generators");
+        Ok(())
+    }
+
+    /// Render filing data in the requested format
+    pub async fn generate_document(
+        &self,
+        template: &FormTemplate,
+        data: &Value,
+        format: &OutputFormat,
+    ) -> Result<GeneratedDocument> {
+        debug!("🖨️ Generating {:?} document for template {}", format, template.template_id);
+
+        let (content, mime_type) = match format {
+            OutputFormat::JSON => (serde_json::to_vec_pretty(data)?, "application/json"),
+            OutputFormat::XML => (render_xml(template, data).into_bytes(), "application/xml"),
+            OutputFormat::HTML => (render_html(template, data)?.into_bytes(), "text/html"),
+            OutputFormat::XBRL => (render_xbrl(template, data)?.into_bytes(), "application/xbrl+xml"),
+            OutputFormat::IXBRL => (render_ixbrl(template, data)?.into_bytes(), "application/xhtml+xml"),
+            OutputFormat::PDF | OutputFormat::DOCX | OutputFormat::XLSX => {
+                return Err(DocumentGenerationError::UnsupportedFormat { format: *format }.into());
+            }
+        };
+
+        if content.len() > self.config.max_document_size_bytes {
+            return Err(DocumentGenerationError::DocumentTooLarge {
+                size: content.len(),
+                limit: self.config.max_document_size_bytes,
+            }.into());
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("template_version".to_string(), template.version.clone());
+        if let (OutputFormat::XBRL | OutputFormat::IXBRL, Some(tagging)) = (format, &template.xbrl_tagging) {
+            metadata.insert("xbrl_taxonomy".to_string(), tagging.taxonomy.clone());
+        }
+
+        let generated_at = Utc::now();
+        self.total_generated.fetch_add(1, Ordering::Relaxed);
+        *self.last_generation.write().await = Some(generated_at);
+
+        Ok(GeneratedDocument {
+            document_id: Uuid::new_v4(),
+            template_id: template.template_id.clone(),
+            format: *format,
+            mime_type: mime_type.to_string(),
+            content,
+            generated_at,
+            metadata,
+            signatures: Vec::new(),
+        })
+    }
+
+    /// Render an HTML preview of a form with the given data
+    pub async fn generate_preview(&self, template: &FormTemplate, data: &Value) -> Result<FormPreview> {
+        Ok(FormPreview {
+            template_id: template.template_id.clone(),
+            html: render_html(template, data)?,
+            generated_at: Utc::now(),
+        })
+    }
+
+    pub async fn get_supported_formats(&self) -> Result<Vec<OutputFormat>> {
+        Ok(vec![
+            OutputFormat::HTML,
+            OutputFormat::XML,
+            OutputFormat::JSON,
+            OutputFormat::XBRL,
+            OutputFormat::IXBRL,
+        ])
+    }
+
+    pub async fn get_status(&self) -> Result<GeneratorStatus> {
+        Ok(GeneratorStatus {
+            total_generated: self.total_generated.load(Ordering::Relaxed),
+            last_generation: *self.last_generation.read().await,
+        })
+    }
+}
+
+/// Render HTML from the template's Handlebars content, or a field table if it has none
+fn render_html(template: &FormTemplate, data: &Value) -> Result<String> {
+    let content = &template.template_content;
+    if matches!(content.template_type, TemplateType::Handlebars) && !content.content.trim().is_empty() {
+        return Ok(handlebars::Handlebars::new().render_template(&content.content, data)?);
+    }
+
+    let mut html = format!("<!DOCTYPE html>\n<html>\n<head><title>{0}</title></head>\n<body>\n<h1>{0}</h1>\n<table>\n",
+                           xml_escape(&template.name));
+    for field in &template.fields {
+        let value = match data.pointer(&field_pointer(&field.field_id)) {
+            Some(Value::String(text)) => text.clone(),
+            Some(Value::Null) | None => String::new(),
+            Some(other) => other.to_string(),
+        };
+        html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", xml_escape(&field.name), xml_escape(&value)));
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    Ok(html)
+}
+
+fn render_xml(template: &FormTemplate, data: &Value) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<filing template=\"{}\" version=\"{}\">\n",
+        xml_escape(&template.template_id),
+        xml_escape(&template.version)
+    ));
+    write_xml_value(&mut xml, data, 1);
+    xml.push_str("</filing>\n");
+    xml
+}
+
+fn write_xml_value(xml: &mut String, value: &Value, depth: usize) {
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Object(members) => {
+            for (name, member) in members {
+                xml.push_str(&format!("{}<field name=\"{}\">", indent, xml_escape(name)));
+                write_xml_member(xml, member, depth);
+                xml.push_str("</field>\n");
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                xml.push_str(&format!("{}<item>", indent));
+                write_xml_member(xml, item, depth);
+                xml.push_str("</item>\n");
+            }
+        }
+        other => xml.push_str(&format!("{}<value>{}</value>\n", indent, xml_escape(&scalar_text(other)))),
+    }
+}
+
+fn write_xml_member(xml: &mut String, value: &Value, depth: usize) {
+    if value.is_object() || value.is_array() {
+        xml.push('\n');
+        write_xml_value(xml, value, depth + 1);
+        xml.push_str(&"  ".repeat(depth));
+    } else {
+        xml.push_str(&xml_escape(&scalar_text(value)));
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_structured_formats_render_data() {
+        let generators = DocumentGenerators::new(GeneratorConfig::default()).await.unwrap();
+        let template = FormTemplate::for_tests("sec-10-k", Vec::new());
+        let data = serde_json::json!({"filer": {"name": "A & B"}, "segments": ["US", "EU"]});
+
+        let xml = generators.generate_document(&template, &data, &OutputFormat::XML).await.unwrap();
+        let xml = String::from_utf8(xml.content).unwrap();
+        assert!(xml.contains("<field name=\"name\">A &amp; B</field>"));
+        assert!(xml.contains("<item>EU</item>"));
+
+        let json = generators.generate_document(&template, &data, &OutputFormat::JSON).await.unwrap();
+        assert_eq!(serde_json::from_slice::<Value>(&json.content).unwrap(), data);
+        assert_eq!(generators.get_status().await.unwrap().total_generated, 2);
+    }
+
+    #[tokio::test]
+    async fn test_xbrl_requires_tagging_definition() {
+        let generators = DocumentGenerators::new(GeneratorConfig::default()).await.unwrap();
+        let template = FormTemplate::for_tests("sec-10-k", Vec::new());
+
+        let error = generators
+            .generate_document(&template, &serde_json::json!({}), &OutputFormat::XBRL)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<crate::XbrlError>(),
+            Some(crate::XbrlError::NoTaggingDefinition { .. })
+        ));
+    }
+}
@@ -0,0 +1,541 @@
+/*!
+ * Idempotency Keys
+ *
+ * Remembers the outcome of filing generation and submission per client
+ * idempotency key, so a retried request returns the original filing or
+ * receipt instead of producing a duplicate filing with the regulator.
+ * Outcomes are kept for a configurable retention window; a submission whose
+ * outcome is not known is kept until an operator resolves it.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex as SyncMutex;
+use std::time::{Instant, SystemTime};
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::{FilingRequest, GeneratedFiling, SubmissionReceipt};
+
+/// Minimum time between sweeps of expired records
+const PRUNE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Idempotency store configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyConfig {
+    /// Durable directory outcomes are written to; `None` keeps them in memory
+    /// only, so retries are not recognized after a restart
+    pub directory: Option<PathBuf>,
+    /// How long an outcome is replayed for its key
+    #[serde(default = "default_retention_hours")]
+    pub retention_hours: i64,
+}
+
+fn default_retention_hours() -> i64 {
+    24
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self { directory: None, retention_hours: default_retention_hours() }
+    }
+}
+
+/// Operations an idempotency key can guard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IdempotentOperation {
+    Generation,
+    Submission,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IdempotencyError {
+    #[error("{operation:?} with idempotency key {key} is already in progress")]
+    InProgress { operation: IdempotentOperation, key: String },
+    #[error("idempotency key {key} was already used for a different filing request")]
+    KeyReused { key: String },
+    #[error("submission of filing {filing_id} with idempotency key {key} may have reached the regulator; resolve it before retrying")]
+    OutcomeUnknown { key: String, filing_id: Uuid },
+}
+
+/// Where the submission made under a key stands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SubmissionState {
+    /// Recorded before the upload starts; found after a crash, the outcome is unknown
+    Pending { started_at: DateTime<Utc> },
+    /// The upload failed in a way that does not tell whether the regulator received it
+    Unknown { error: String },
+    Accepted(SubmissionReceipt),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionOutcome {
+    pub filing_id: Uuid,
+    pub state: SubmissionState,
+}
+
+impl SubmissionOutcome {
+    fn is_unresolved(&self) -> bool {
+        !matches!(self.state, SubmissionState::Accepted(_))
+    }
+}
+
+/// Outcomes recorded for one idempotency key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    pub key: String,
+    pub filing: Option<GeneratedFiling>,
+    pub submission: Option<SubmissionOutcome>,
+    pub recorded_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    /// Expired, unless it holds a submission nobody has resolved yet
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now && !self.submission.as_ref().is_some_and(SubmissionOutcome::is_unresolved)
+    }
+}
+
+/// How an idempotent operation starts: with the recorded outcome, or with the key reserved
+pub enum Idempotent<'a, T> {
+    Replay(T),
+    Reserved(Reservation<'a>),
+}
+
+/// Reservation of a key for a running operation, released when dropped
+///
+/// Dropping a submission reservation without completing, abandoning or
+/// marking it unknown leaves its `Pending` record behind, so an interrupted
+/// submission is never silently retried.
+#[must_use]
+pub struct Reservation<'a> {
+    store: &'a IdempotencyStore,
+    operation: IdempotentOperation,
+    key: String,
+    filing_id: Option<Uuid>,
+}
+
+impl Reservation<'_> {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Record the regulator's receipt for the submission
+    pub async fn complete_submission(self, receipt: &SubmissionReceipt) -> Result<()> {
+        let filing_id = self.submission_filing_id();
+        self.store
+            .update(&self.key, |record| {
+                record.submission = Some(SubmissionOutcome { filing_id, state: SubmissionState::Accepted(receipt.clone()) });
+            })
+            .await
+    }
+
+    /// Forget the submission, which the regulator certainly did not accept, so it can be retried
+    pub async fn abandon_submission(self) -> Result<()> {
+        self.store.update(&self.key, |record| record.submission = None).await
+    }
+
+    /// Keep the submission blocked until an operator resolves it
+    pub async fn mark_submission_unknown(self, error: &anyhow::Error) -> Result<()> {
+        warn!("🔑 Outcome of submission with idempotency key {} is unknown: {}", self.key, error);
+        let filing_id = self.submission_filing_id();
+        self.store
+            .update(&self.key, |record| {
+                record.submission = Some(SubmissionOutcome {
+                    filing_id,
+                    state: SubmissionState::Unknown { error: error.to_string() },
+                });
+            })
+            .await
+    }
+
+    fn submission_filing_id(&self) -> Uuid {
+        self.filing_id.expect("submission reservations carry a filing id")
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = self.store.in_flight.lock() {
+            in_flight.remove(&(self.operation, std::mem::take(&mut self.key)));
+        }
+    }
+}
+
+/// Idempotency store keyed by client idempotency key
+///
+/// Like the checkpoint store, records are cached in memory and written
+/// through to the configured directory so retries are recognized after a
+/// restart. A key is reserved while its operation runs, so a concurrent
+/// retry fails instead of running the operation twice.
+pub struct IdempotencyStore {
+    config: IdempotencyConfig,
+    /// Record cache; held while a key is checked and reserved
+    records: Mutex<HashMap<String, IdempotencyRecord>>,
+    in_flight: SyncMutex<HashSet<(IdempotentOperation, String)>>,
+    last_pruned: SyncMutex<Option<Instant>>,
+}
+
+impl IdempotencyStore {
+    pub async fn new(config: IdempotencyConfig) -> Result<Self> {
+        match &config.directory {
+            Some(directory) => {
+                tokio::fs::create_dir_all(directory).await?;
+                info!("🔑 Idempotency keys persisted to {}", directory.display());
+            }
+            None => warn!("🔑 No idempotency directory configured; keys are forgotten on restart"),
+        }
+
+        Ok(Self {
+            config,
+            records: Mutex::new(HashMap::new()),
+            in_flight: SyncMutex::new(HashSet::new()),
+            last_pruned: SyncMutex::new(None),
+        })
+    }
+
+    /// Filing previously generated for `key`, or a reservation of the key
+    ///
+    /// Fails if the key is reserved by a running generation or was used for
+    /// a different request.
+    pub async fn begin_generation(&self, key: &str, request: &FilingRequest) -> Result<Idempotent<'_, GeneratedFiling>> {
+        let mut records = self.records.lock().await;
+        if let Some(filing) = self.load(&mut records, key).await?.and_then(|record| record.filing) {
+            if serde_json::to_value(&filing.request)? != serde_json::to_value(request)? {
+                return Err(IdempotencyError::KeyReused { key: key.to_string() }.into());
+            }
+            return Ok(Idempotent::Replay(filing));
+        }
+        Ok(Idempotent::Reserved(self.reserve(IdempotentOperation::Generation, key, None)?))
+    }
+
+    /// Receipt of a previous submission of `filing_id` under `key`, or a reservation of the key
+    ///
+    /// The reservation is recorded as `Pending` before this returns. Fails if
+    /// the key belongs to another filing, is reserved by a running
+    /// submission, or holds a submission whose outcome is unknown.
+    pub async fn begin_submission(&self, key: &str, filing_id: Uuid) -> Result<Idempotent<'_, SubmissionReceipt>> {
+        let mut records = self.records.lock().await;
+        if let Some(record) = self.load(&mut records, key).await? {
+            let generated_other = record.filing.as_ref().is_some_and(|filing| filing.filing_id != filing_id);
+            let submitted_other = record.submission.as_ref().is_some_and(|outcome| outcome.filing_id != filing_id);
+            if generated_other || submitted_other {
+                return Err(IdempotencyError::KeyReused { key: key.to_string() }.into());
+            }
+            match record.submission.map(|outcome| outcome.state) {
+                Some(SubmissionState::Accepted(receipt)) => return Ok(Idempotent::Replay(receipt)),
+                Some(_) if !self.is_in_flight(IdempotentOperation::Submission, key) => {
+                    return Err(IdempotencyError::OutcomeUnknown { key: key.to_string(), filing_id }.into());
+                }
+                _ => {}
+            }
+        }
+
+        let reservation = self.reserve(IdempotentOperation::Submission, key, Some(filing_id))?;
+        let pending = SubmissionOutcome { filing_id, state: SubmissionState::Pending { started_at: Utc::now() } };
+        self.update_locked(&mut records, key, |record| record.submission = Some(pending)).await?;
+        Ok(Idempotent::Reserved(reservation))
+    }
+
+    /// Record the generated filing for `key`
+    pub async fn complete_generation(&self, key: &str, filing: &GeneratedFiling) -> Result<()> {
+        self.update(key, |record| record.filing = Some(filing.clone())).await
+    }
+
+    /// Settle a submission whose outcome is unknown, after checking with the regulator
+    ///
+    /// With the regulator's receipt the key replays it; without one the
+    /// filing was not received and may be submitted again.
+    pub async fn resolve_submission(&self, key: &str, receipt: Option<SubmissionReceipt>) -> Result<()> {
+        if self.is_in_flight(IdempotentOperation::Submission, key) {
+            return Err(IdempotencyError::InProgress { operation: IdempotentOperation::Submission, key: key.to_string() }.into());
+        }
+        self.update(key, |record| match receipt {
+            Some(receipt) => {
+                if let Some(outcome) = record.submission.as_mut() {
+                    outcome.state = SubmissionState::Accepted(receipt);
+                }
+            }
+            None => record.submission = None,
+        })
+        .await
+    }
+
+    /// Discard records past their retention window, in memory and on disk
+    ///
+    /// Only files old enough to have expired are read. Records holding an
+    /// unresolved submission are kept.
+    pub async fn prune_expired(&self) -> Result<usize> {
+        let mut records = self.records.lock().await;
+        self.prune_locked(&mut records).await
+    }
+
+    fn reserve(&self, operation: IdempotentOperation, key: &str, filing_id: Option<Uuid>) -> Result<Reservation<'_>> {
+        let mut in_flight = self.in_flight.lock().map_err(|_| anyhow::anyhow!("idempotency reservations poisoned"))?;
+        if !in_flight.insert((operation, key.to_string())) {
+            return Err(IdempotencyError::InProgress { operation, key: key.to_string() }.into());
+        }
+        Ok(Reservation { store: self, operation, key: key.to_string(), filing_id })
+    }
+
+    fn is_in_flight(&self, operation: IdempotentOperation, key: &str) -> bool {
+        self.in_flight
+            .lock()
+            .map(|in_flight| in_flight.contains(&(operation, key.to_string())))
+            .unwrap_or(true)
+    }
+
+    /// Unexpired record for `key`, from memory or disk
+    async fn load(
+        &self,
+        records: &mut HashMap<String, IdempotencyRecord>,
+        key: &str,
+    ) -> Result<Option<IdempotencyRecord>> {
+        if self.prune_due() {
+            self.prune_locked(records).await?;
+        }
+
+        let now = Utc::now();
+        let record = match records.get(key) {
+            Some(record) => Some(record.clone()),
+            None => match self.path_for(key) {
+                Some(path) => read_record(&path).await?,
+                None => None,
+            },
+        };
+        match record {
+            Some(record) if record.is_expired(now) => {
+                self.remove(records, key).await?;
+                Ok(None)
+            }
+            Some(record) => {
+                records.insert(key.to_string(), record.clone());
+                Ok(Some(record))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn update(&self, key: &str, apply: impl FnOnce(&mut IdempotencyRecord)) -> Result<()> {
+        let mut records = self.records.lock().await;
+        self.update_locked(&mut records, key, apply).await
+    }
+
+    async fn update_locked(
+        &self,
+        records: &mut HashMap<String, IdempotencyRecord>,
+        key: &str,
+        apply: impl FnOnce(&mut IdempotencyRecord),
+    ) -> Result<()> {
+        let now = Utc::now();
+        let mut record = self.load(records, key).await?.unwrap_or_else(|| IdempotencyRecord {
+            key: key.to_string(),
+            filing: None,
+            submission: None,
+            recorded_at: now,
+            expires_at: now,
+        });
+        apply(&mut record);
+        record.recorded_at = now;
+        record.expires_at = now + Duration::hours(self.config.retention_hours);
+
+        if let Some(path) = self.path_for(key) {
+            write_durably(&path, &serde_json::to_vec_pretty(&record)?).await?;
+        }
+        records.insert(key.to_string(), record);
+        Ok(())
+    }
+
+    async fn remove(&self, records: &mut HashMap<String, IdempotencyRecord>, key: &str) -> Result<()> {
+        records.remove(key);
+
+        if let Some(path) = self.path_for(key) {
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    fn prune_due(&self) -> bool {
+        let Ok(mut last_pruned) = self.last_pruned.lock() else {
+            return false;
+        };
+        if last_pruned.is_some_and(|at| at.elapsed() < PRUNE_INTERVAL) {
+            return false;
+        }
+        *last_pruned = Some(Instant::now());
+        true
+    }
+
+    async fn prune_locked(&self, records: &mut HashMap<String, IdempotencyRecord>) -> Result<usize> {
+        let now = Utc::now();
+        let before = records.len();
+        records.retain(|_, record| !record.is_expired(now));
+        let mut pruned = before - records.len();
+
+        if let Some(directory) = &self.config.directory {
+            // Every cached record has a file, so count what is removed from disk
+            pruned = 0;
+            let retention = std::time::Duration::from_secs(self.config.retention_hours.max(0) as u64 * 3600);
+            let mut entries = tokio::fs::read_dir(directory).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+                    continue;
+                }
+                // Records are rewritten on every update, so a recent file cannot have expired
+                let modified = entry.metadata().await?.modified()?;
+                if modified + retention > SystemTime::now() {
+                    continue;
+                }
+                if read_record(&path).await?.is_some_and(|record| record.is_expired(now)) {
+                    tokio::fs::remove_file(&path).await?;
+                    pruned += 1;
+                }
+            }
+        }
+
+        if pruned > 0 {
+            debug!("🔑 Pruned {} expired idempotency keys", pruned);
+        }
+        Ok(pruned)
+    }
+
+    /// Keys are client-supplied, so files are named by the key's SHA-256
+    fn path_for(&self, key: &str) -> Option<PathBuf> {
+        let digest = ring::digest::digest(&ring::digest::SHA256, key.as_bytes());
+        let name = hex::encode(digest);
+        self.config
+            .directory
+            .as_ref()
+            .map(|directory| directory.join(format!("{}.json", name)))
+    }
+}
+
+async fn read_record(path: &Path) -> Result<Option<IdempotencyRecord>> {
+    match tokio::fs::read(path).await {
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Write then rename, syncing both, so a crash leaves either the old or the new record
+async fn write_durably(path: &Path, bytes: &[u8]) -> Result<()> {
+    let staging = path.with_extension("json.tmp");
+    let mut file = tokio::fs::File::create(&staging).await?;
+    file.write_all(bytes).await?;
+    file.sync_all().await?;
+    tokio::fs::rename(&staging, path).await?;
+    #[cfg(unix)]
+    if let Some(directory) = path.parent() {
+        tokio::fs::File::open(directory).await?.sync_all().await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reserved<T>(start: Idempotent<'_, T>) -> Reservation<'_> {
+        match start {
+            Idempotent::Reserved(reservation) => reservation,
+            Idempotent::Replay(_) => panic!("expected the key to be reserved"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keys_replay_outcomes_and_reject_concurrent_use() {
+        let directory = tempfile::tempdir().unwrap();
+        let config = IdempotencyConfig { directory: Some(directory.path().to_path_buf()), retention_hours: 24 };
+        let store = IdempotencyStore::new(config.clone()).await.unwrap();
+        let filing = GeneratedFiling::for_tests();
+
+        let reservation = reserved(store.begin_generation("retry-1", &filing.request).await.unwrap());
+        let error = store.begin_generation("retry-1", &filing.request).await.err().unwrap();
+        assert!(matches!(error.downcast_ref::<IdempotencyError>(), Some(IdempotencyError::InProgress { .. })));
+        store.complete_generation("retry-1", &filing).await.unwrap();
+        drop(reservation);
+
+        // A fresh store finds the outcome on disk
+        let store = IdempotencyStore::new(config).await.unwrap();
+        let Idempotent::Replay(replayed) = store.begin_generation("retry-1", &filing.request).await.unwrap() else {
+            panic!("expected the filing to be replayed");
+        };
+        assert_eq!(replayed.filing_id, filing.filing_id);
+
+        let mut other_request = filing.request.clone();
+        other_request.form_type = "10-Q".to_string();
+        let error = store.begin_generation("retry-1", &other_request).await.err().unwrap();
+        assert!(matches!(error.downcast_ref::<IdempotencyError>(), Some(IdempotencyError::KeyReused { .. })));
+
+        // The key belongs to the generated filing; no other filing may be submitted under it
+        let error = store.begin_submission("retry-1", Uuid::new_v4()).await.err().unwrap();
+        assert!(matches!(error.downcast_ref::<IdempotencyError>(), Some(IdempotencyError::KeyReused { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_submissions_stay_blocked_until_resolved() {
+        let directory = tempfile::tempdir().unwrap();
+        let config = IdempotencyConfig { directory: Some(directory.path().to_path_buf()), retention_hours: 24 };
+        let store = IdempotencyStore::new(config.clone()).await.unwrap();
+        let filing_id = Uuid::new_v4();
+
+        // A definite rejection frees the key
+        let reservation = reserved(store.begin_submission("submit-1", filing_id).await.unwrap());
+        reservation.abandon_submission().await.unwrap();
+
+        // A crash mid-upload leaves the pending record behind
+        let reservation = reserved(store.begin_submission("submit-1", filing_id).await.unwrap());
+        let error = store.begin_submission("submit-1", filing_id).await.err().unwrap();
+        assert!(matches!(error.downcast_ref::<IdempotencyError>(), Some(IdempotencyError::InProgress { .. })));
+        std::mem::forget(reservation);
+
+        let restarted = IdempotencyStore::new(config).await.unwrap();
+        let error = restarted.begin_submission("submit-1", filing_id).await.err().unwrap();
+        assert!(matches!(error.downcast_ref::<IdempotencyError>(), Some(IdempotencyError::OutcomeUnknown { .. })));
+
+        let receipt = SubmissionReceipt {
+            adapter: "test-regulator".to_string(),
+            confirmation_id: "0000000000-24-000001".to_string(),
+            endpoint: "https://regulator.test/submissions".to_string(),
+            test_filing: true,
+            submitted_at: Utc::now(),
+            idempotent_replay: false,
+        };
+        restarted.resolve_submission("submit-1", Some(receipt.clone())).await.unwrap();
+        let Idempotent::Replay(replayed) = restarted.begin_submission("submit-1", filing_id).await.unwrap() else {
+            panic!("expected the receipt to be replayed");
+        };
+        assert_eq!(replayed, receipt);
+    }
+
+    #[tokio::test]
+    async fn test_expired_keys_are_forgotten() {
+        let directory = tempfile::tempdir().unwrap();
+        let config = IdempotencyConfig { directory: Some(directory.path().to_path_buf()), retention_hours: 0 };
+        let store = IdempotencyStore::new(config).await.unwrap();
+        let filing = GeneratedFiling::for_tests();
+
+        drop(reserved(store.begin_generation("retry-2", &filing.request).await.unwrap()));
+        store.complete_generation("retry-2", &filing).await.unwrap();
+        // An unknown submission outlives the retention window
+        let reservation = reserved(store.begin_submission("retry-3", Uuid::new_v4()).await.unwrap());
+        reservation.mark_submission_unknown(&anyhow::anyhow!("connection reset")).await.unwrap();
+
+        assert_eq!(store.prune_expired().await.unwrap(), 1);
+        assert_eq!(std::fs::read_dir(directory.path()).unwrap().count(), 1);
+        drop(reserved(store.begin_generation("retry-2", &filing.request).await.unwrap()));
+    }
+}
@@ -18,6 +18,7 @@
 
 pub mod templates;
 pub mod generators;
+pub mod xbrl;
 pub mod validators;
 pub mod formatters;
 pub mod ai_assistant;
@@ -27,6 +28,11 @@ pub mod compliance_checker;
 pub mod multi_language;
 pub mod form_library;
 pub mod data_extraction;
+pub mod checkpoints;
+pub mod idempotency;
+pub mod amendments;
+pub mod source_validation;
+pub mod submission;
 pub mod config;
 pub mod error;
 pub mod utils;
@@ -34,6 +40,7 @@ pub mod utils;
 // Re-export main components
 pub use templates::*;
 pub use generators::*;
+pub use xbrl::*;
 pub use validators::*;
 pub use formatters::*;
 pub use ai_assistant::*;
@@ -43,17 +50,49 @@ pub use compliance_checker::*;
 pub use multi_language::*;
 pub use form_library::*;
 pub use data_extraction::*;
+pub use checkpoints::*;
+pub use idempotency::*;
+pub use amendments::*;
+pub use source_validation::*;
+pub use submission::*;
 pub use error::*;
 
 use std::sync::Arc;
 use std::collections::HashMap;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
+use futures::stream::{self, StreamExt};
 use anyhow::Result;
 use tracing::{info, warn, error};
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
+/// One lock per filing in progress
+///
+/// Entries are dropped once no stage holds or waits for them.
+#[derive(Default)]
+struct FilingLocks {
+    locks: std::sync::Mutex<HashMap<Uuid, std::sync::Weak<Mutex<()>>>>,
+}
+
+impl FilingLocks {
+    async fn lock(&self, filing_id: Uuid) -> tokio::sync::OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.locks.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            locks.retain(|_, lock| lock.strong_count() > 0);
+            match locks.get(&filing_id).and_then(std::sync::Weak::upgrade) {
+                Some(lock) => lock,
+                None => {
+                    let lock = Arc::new(Mutex::new(()));
+                    locks.insert(filing_id, Arc::downgrade(&lock));
+                    lock
+                }
+            }
+        };
+        lock.lock_owned().await
+    }
+}
+
 /// AION-CR Filing Generator
 ///
 /// Central service for automated generation of regulatory filings and
@@ -93,6 +132,19 @@ pub struct FilingGenerator {
     /// Data extraction service
     pub data_extractor: Arc<DataExtractionService>,
 
+    /// Checkpoints of in-progress filings
+    pub checkpoint_store: Arc<CheckpointStore>,
+
+    /// Pre-flight connectivity checks of data sources
+    pub source_validator: Arc<SourceValidator>,
+
+    /// Outcomes of requests carrying an idempotency key
+    pub idempotency_store: Arc<IdempotencyStore>,
+
+    /// Serializes the stages of each filing, so a resumed or amended run
+    /// cannot interleave with another run over the same filing
+    filing_locks: Arc<FilingLocks>,
+
     /// Configuration
     pub config: Arc<FilingGeneratorConfig>,
 }
@@ -106,7 +158,7 @@ impl FilingGenerator {
         info!("📄 Initializing AION-CR Filing Generator");
 
         let generator_id = Uuid::new_v4();
-        let config = Arc::new(config);
+        let config = Arc::new(config.resolve_data_directories()?);
 
         // Initialize template library
         let template_library = Arc::new(
@@ -168,6 +220,20 @@ impl FilingGenerator {
         );
         info!("✅ Data extraction service initialized");
 
+        // Initialize checkpoint store
+        let checkpoint_store = Arc::new(
+            CheckpointStore::new(config.checkpoint_config.clone()).await?
+        );
+        info!("✅ Checkpoint store initialized");
+
+        let source_validator = Arc::new(SourceValidator::new()?);
+
+        // Initialize idempotency store
+        let idempotency_store = Arc::new(
+            IdempotencyStore::new(config.idempotency_config.clone()).await?
+        );
+        info!("✅ Idempotency store initialized");
+
         let generator = Self {
             generator_id,
             template_library,
@@ -180,6 +246,10 @@ impl FilingGenerator {
             language_service,
             form_library,
             data_extractor,
+            checkpoint_store,
+            source_validator,
+            idempotency_store,
+            filing_locks: Arc::new(FilingLocks::default()),
             config,
         };
 
@@ -265,84 +335,334 @@ impl FilingGenerator {
     /// Generate a regulatory filing
     ///
     /// Creates a complete regulatory filing using AI assistance and templates.
+    /// Progress is checkpointed after every stage; if a stage fails, the
+    /// filing can be picked up again with `resume_filing`. Unavailable data
+    /// sources fail the filing before any work starts.
+    ///
+    /// A request with an `idempotency_key` that was already processed returns
+    /// the original filing, flagged as `idempotent_replay`, without
+    /// generating it again.
     pub async fn generate_filing(
         &self,
         filing_request: FilingRequest,
     ) -> Result<GeneratedFiling> {
         info!("📝 Generating filing: {} for {}", filing_request.form_type, filing_request.organization_id);
 
+        let Some(key) = filing_request.idempotency_key.clone() else {
+            return self.generate_new_filing(filing_request).await;
+        };
+        let _reservation = match self.idempotency_store.begin_generation(&key, &filing_request).await? {
+            Idempotent::Replay(mut filing) => {
+                info!("🔑 Idempotency key {} already processed, returning filing {}", key, filing.filing_id);
+                filing.idempotent_replay = true;
+                return Ok(filing);
+            }
+            Idempotent::Reserved(reservation) => reservation,
+        };
+
+        // The filing is recorded under the key once its stages complete
+        self.generate_new_filing(filing_request).await
+    }
+
+    async fn generate_new_filing(&self, filing_request: FilingRequest) -> Result<GeneratedFiling> {
         // Validate request
         self.validators.validate_filing_request(&filing_request).await?;
 
-        // Get form template
-        let template = self.form_library.get_template(&filing_request.form_type).await?;
+        // Fail fast on unreachable sources rather than after AI enhancement
+        let sources = self.validate_sources(&filing_request.data_sources).await?;
+        if !sources.all_available() {
+            return Err(SourceValidationError::Unavailable {
+                failures: sources.failures().cloned().collect(),
+                total: sources.checks.len(),
+            }.into());
+        }
 
-        // Extract data from sources
-        let extracted_data = self.data_extractor.extract_filing_data(
-            &filing_request,
-            &template,
-        ).await?;
+        let checkpoint = FilingCheckpoint::new(Uuid::new_v4(), filing_request);
+        self.checkpoint_store.save(&checkpoint).await?;
 
-        // Generate content with AI assistance
-        let ai_enhanced_data = self.ai_assistant.enhance_filing_data(
-            &extracted_data,
-            &template,
-            &filing_request,
-        ).await?;
+        self.run_filing_stages(checkpoint).await
+    }
 
-        // Validate data against regulatory requirements
-        self.compliance_checker.validate_data(
-            &ai_enhanced_data,
-            &template,
-            &filing_request.jurisdiction,
-        ).await?;
+    /// Check that each data source can be connected to, authenticated against or opened
+    ///
+    /// No data is fetched. The report has one entry per source, with any
+    /// credentials masked.
+    pub async fn validate_sources(&self, sources: &[DataSource]) -> Result<SourceValidationReport> {
+        Ok(self.source_validator.validate(sources).await)
+    }
 
-        // Generate document in requested format
-        let generated_document = self.document_generators.generate_document(
-            &template,
-            &ai_enhanced_data,
-            &filing_request.output_format,
-        ).await?;
+    /// Resume a filing from its last successful stage
+    ///
+    /// Work already checkpointed (AI-enhanced data, the rendered document)
+    /// is reused rather than regenerated.
+    pub async fn resume_filing(&self, filing_id: Uuid) -> Result<GeneratedFiling> {
+        let checkpoint = self.checkpoint_store.load(filing_id).await?;
+        info!("🔁 Resuming filing {} after stage {:?}", filing_id, checkpoint.stage);
 
-        // Apply digital signature if required
-        let signed_document = if filing_request.require_signature {
-            self.signature_service.sign_document(
-                generated_document,
-                &filing_request.signature_config,
-            ).await?
-        } else {
-            generated_document
-        };
+        self.run_filing_stages(checkpoint).await
+    }
 
-        // Create workflow if specified
-        let workflow_id = if let Some(workflow_config) = &filing_request.workflow_config {
-            Some(self.workflow_manager.create_workflow(
-                &signed_document,
-                workflow_config,
-            ).await?)
-        } else {
-            None
-        };
+    /// Generate an amendment of a previously generated filing
+    ///
+    /// `changes` is a JSON merge patch over the base filing's data. Only the
+    /// changed fields go through extraction and AI enhancement again; the
+    /// rest is carried over. Schema and compliance validation still run
+    /// over the whole merged document. The carried-over and regenerated
+    /// field paths are recorded in the filing metadata.
+    pub async fn generate_amendment(
+        &self,
+        base_filing_id: Uuid,
+        changes: serde_json::Value,
+    ) -> Result<GeneratedFiling> {
+        info!("📝 Generating amendment of filing {}", base_filing_id);
+
+        let mut base = self.checkpoint_store.load_completed(base_filing_id).await?;
+        let template = self.checkpoint_template(&mut base).await?;
+
+        let mut plan = AmendmentPlan::new(&template, base.ai_enhanced_data()?, &changes);
+        let regeneration_template = plan.regeneration_template(&template);
+
+        let mut request = base.request.clone();
+        // The amendment is a new filing; it must not replay the base filing's outcomes
+        request.idempotency_key = None;
+        request.data_sources = vec![DataSource::Manual { data: plan.merged_data.clone() }];
+
+        // Re-run extraction and AI enhancement over the changed fields only
+        let mut ai_confidence = base.ai_confidence;
+        if !regeneration_template.fields.is_empty() {
+            let extracted_data = self.data_extractor.extract_filing_data(
+                &request,
+                &regeneration_template,
+            ).await?;
+            let _guard = self.filing_locks.lock(base_filing_id).await;
+            let regenerated_data = self.ai_assistant.enhance_filing_data(
+                &extracted_data.data,
+                &regeneration_template,
+                &request,
+            ).await?;
+            ai_confidence = Some(self.ai_assistant.get_last_confidence_score().await?);
+            plan.apply_regenerated(&regenerated_data);
+        }
+
+        let mut checkpoint = FilingCheckpoint::new(Uuid::new_v4(), request);
+        checkpoint.template_version = Some(template.version.clone());
+        checkpoint.ai_enhanced_data = Some(plan.merged_data);
+        checkpoint.ai_confidence = ai_confidence;
+        checkpoint.metadata.insert(AMENDMENT_BASE_FILING_KEY.to_string(), base_filing_id.to_string());
+        checkpoint.metadata.insert(AMENDMENT_CARRIED_OVER_KEY.to_string(), serde_json::to_string(&plan.carried_over)?);
+        checkpoint.metadata.insert(AMENDMENT_REGENERATED_KEY.to_string(), serde_json::to_string(&plan.regenerated)?);
+        checkpoint.complete_stage(FilingStage::AiEnhanced);
+        self.checkpoint_store.save(&checkpoint).await?;
+
+        info!("🔁 Amendment {}: {} fields carried over, {} regenerated",
+              checkpoint.filing_id, plan.carried_over.len(), plan.regenerated.len());
+
+        self.run_filing_stages(checkpoint).await
+    }
+
+    /// Run the remaining stages of a filing, checkpointing after each one
+    async fn run_filing_stages(&self, mut checkpoint: FilingCheckpoint) -> Result<GeneratedFiling> {
+        let filing_id = checkpoint.filing_id;
+
+        // Get form template, pinned to the version the filing started with
+        let template = self.checkpoint_template(&mut checkpoint).await?;
+
+        while let Some(stage) = checkpoint.stage.next() {
+            if let Err(e) = self.run_filing_stage(stage, &mut checkpoint, &template).await {
+                warn!("⚠️ Filing {} failed at stage {:?}: {}", filing_id, stage, e);
+                checkpoint.record_failure(&e);
+                self.checkpoint_store.save(&checkpoint).await?;
+                return Err(e.context(format!(
+                    "Filing {} failed at stage {:?}; resume it with resume_filing",
+                    filing_id, stage
+                )));
+            }
+
+            checkpoint.complete_stage(stage);
+            self.checkpoint_store.save(&checkpoint).await?;
+        }
 
         // Create final filing result
         let filing = GeneratedFiling {
-            filing_id: Uuid::new_v4(),
-            request: filing_request,
-            template_used: template.template_id.clone(),
-            document: signed_document,
-            workflow_id,
-            validation_results: self.validators.get_last_validation_results().await?,
-            compliance_score: self.compliance_checker.get_last_compliance_score().await?,
-            ai_confidence: self.ai_assistant.get_last_confidence_score().await?,
+            filing_id,
+            template_used: template.versioned_id(),
+            document: checkpoint.signed_document()?.clone(),
+            workflow_id: checkpoint.workflow_id.clone(),
+            validation_results: checkpoint.validation_results.clone().unwrap_or_default(),
+            request: checkpoint.request,
+            compliance_score: checkpoint.compliance_score.unwrap_or_default(),
+            ai_confidence: checkpoint.ai_confidence.unwrap_or_default(),
             generation_timestamp: Utc::now(),
             status: FilingStatus::Generated,
-            metadata: HashMap::new(),
+            metadata: checkpoint.metadata,
+            idempotent_replay: false,
         };
 
+        self.checkpoint_store.finish(filing_id).await?;
+        if let Some(key) = &filing.request.idempotency_key {
+            self.idempotency_store.complete_generation(key, &filing).await?;
+        }
+
         info!("✅ Filing generated successfully: {}", filing.filing_id);
         Ok(filing)
     }
 
+    /// Template a checkpointed filing is generated from
+    ///
+    /// The first call pins the current template version in the checkpoint;
+    /// later calls (on resume or amendment) load that exact version.
+    async fn checkpoint_template(&self, checkpoint: &mut FilingCheckpoint) -> Result<FormTemplate> {
+        let form_type = &checkpoint.request.form_type;
+        match &checkpoint.template_version {
+            Some(version) => self.form_library.get_template_version(form_type, version).await,
+            None => {
+                let template = self.form_library.get_template(form_type).await?;
+                checkpoint.template_version = Some(template.version.clone());
+                Ok(template)
+            }
+        }
+    }
+
+    /// Run a single filing stage, storing its output in the checkpoint
+    async fn run_filing_stage(
+        &self,
+        stage: FilingStage,
+        checkpoint: &mut FilingCheckpoint,
+        template: &FormTemplate,
+    ) -> Result<()> {
+        match stage {
+            FilingStage::RequestValidated => {}
+            FilingStage::AiEnhanced => {
+                // Extract data from sources
+                let extracted_data = self.data_extractor.extract_filing_data(
+                    &checkpoint.request,
+                    template,
+                ).await?;
+
+                if !extracted_data.coercion_issues.is_empty() {
+                    checkpoint.metadata.insert(
+                        COERCION_ISSUES_KEY.to_string(),
+                        serde_json::to_string(&extracted_data.coercion_issues)?,
+                    );
+                }
+
+                // Generate content with AI assistance
+                let _guard = self.filing_locks.lock(checkpoint.filing_id).await;
+                let ai_enhanced_data = self.ai_assistant.enhance_filing_data(
+                    &extracted_data.data,
+                    template,
+                    &checkpoint.request,
+                ).await?;
+                checkpoint.ai_confidence = Some(self.ai_assistant.get_last_confidence_score().await?);
+                checkpoint.ai_enhanced_data = Some(ai_enhanced_data);
+            }
+            FilingStage::ComplianceValidated => {
+                // Validate data against the form schema and regulatory requirements
+                let data = checkpoint.ai_enhanced_data()?;
+                let schema_result = self.validators.validate_data(data, template).await?;
+                let _guard = self.filing_locks.lock(checkpoint.filing_id).await;
+                let compliance_result = self.compliance_checker.validate_data(
+                    data,
+                    template,
+                    &checkpoint.request.jurisdiction,
+                ).await?;
+                checkpoint.compliance_score = Some(self.compliance_checker.get_last_compliance_score().await?);
+                let validation_results = ValidationResult::combine(schema_result, compliance_result);
+                checkpoint.validation_results = Some(validation_results.clone());
+                // Invalid data stops the filing; the saved checkpoint keeps the issues to fix before resuming
+                validation_results.ensure_valid()?;
+            }
+            FilingStage::DocumentGenerated => {
+                // Generate document in requested format
+                let generated_document = self.document_generators.generate_document(
+                    template,
+                    checkpoint.ai_enhanced_data()?,
+                    &checkpoint.request.output_format,
+                ).await?;
+                checkpoint.generated_document = Some(generated_document);
+            }
+            FilingStage::DocumentSigned => {
+                // Apply digital signature if required
+                let generated_document = checkpoint.generated_document()?.clone();
+                let signed_document = if checkpoint.request.require_signature {
+                    self.signature_service.sign_document(
+                        generated_document,
+                        &checkpoint.request.signature_config,
+                    ).await?
+                } else {
+                    generated_document
+                };
+                checkpoint.signed_document = Some(signed_document);
+            }
+            FilingStage::WorkflowCreated => {
+                // Create workflow if specified
+                if let Some(workflow_config) = &checkpoint.request.workflow_config {
+                    let workflow_id = self.workflow_manager.create_workflow(
+                        checkpoint.signed_document()?,
+                        workflow_config,
+                    ).await?;
+                    checkpoint.workflow_id = Some(workflow_id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Submit a generated filing to the regulator of its jurisdiction
+    ///
+    /// Uses the submission adapter registered with the workflow manager;
+    /// on success the filing is `Submitted` and carries the receipt. If the
+    /// filing's idempotency key was already submitted, the original receipt
+    /// is returned, flagged as `idempotent_replay`, and nothing is uploaded.
+    ///
+    /// A submission that fails without a definite rejection, e.g. on a
+    /// timeout, may still have reached the regulator: its key stays blocked
+    /// until resolved with `IdempotencyStore::resolve_submission`.
+    pub async fn submit_filing(&self, filing: &mut GeneratedFiling) -> Result<SubmissionReceipt> {
+        info!("📨 Submitting filing: {}", filing.filing_id);
+
+        let Some(key) = filing.request.idempotency_key.clone() else {
+            let key = filing.filing_id.to_string();
+            return self.workflow_manager.submit_filing(filing, &key).await;
+        };
+        let reservation = match self.idempotency_store.begin_submission(&key, filing.filing_id).await? {
+            Idempotent::Replay(mut receipt) => {
+                info!("🔑 Idempotency key {} already submitted as {}", key, receipt.confirmation_id);
+                filing.metadata.insert(SUBMISSION_RECEIPT_KEY.to_string(), serde_json::to_string(&receipt)?);
+                filing.status = FilingStatus::Submitted;
+                receipt.idempotent_replay = true;
+                return Ok(receipt);
+            }
+            Idempotent::Reserved(reservation) => reservation,
+        };
+
+        match self.workflow_manager.submit_filing(filing, &key).await {
+            Ok(receipt) => {
+                reservation.complete_submission(&receipt).await?;
+                Ok(receipt)
+            }
+            Err(e) => {
+                let definite = e.downcast_ref::<SubmissionError>().is_some_and(SubmissionError::is_definite);
+                if definite {
+                    reservation.abandon_submission().await?;
+                } else {
+                    reservation.mark_submission_unknown(&e).await?;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Get a specific version of a form template
+    pub async fn get_template_version(&self, form_type: &str, version: &str) -> Result<FormTemplate> {
+        self.form_library.get_template_version(form_type, version).await
+    }
+
+    /// List the available versions of a form template, oldest first
+    pub async fn list_template_versions(&self, form_type: &str) -> Result<Vec<String>> {
+        self.form_library.list_template_versions(form_type).await
+    }
+
     /// Get available form templates
     pub async fn get_available_forms(&self, filter: FormFilter) -> Result<Vec<FormTemplate>> {
         info!("🔍 Searching available forms with filter: {:?}", filter);
@@ -401,6 +721,20 @@ impl FilingGenerator {
         Ok(combined_result)
     }
 
+    /// Verify the signature on a generated filing's document
+    pub async fn verify_filing_signature(&self, filing: &GeneratedFiling) -> Result<SignatureVerification> {
+        info!("🔏 Verifying signature on filing: {}", filing.filing_id);
+
+        let verification = self.signature_service.verify_signature(&filing.document).await?;
+
+        if verification.valid {
+            info!("✅ Signature valid, signed by {}", verification.signer);
+        } else {
+            warn!("⚠️ Signature invalid: {}", verification.failures.join("; "));
+        }
+        Ok(verification)
+    }
+
     /// Extract data from existing documents
     pub async fn extract_data_from_document(
         &self,
@@ -447,40 +781,71 @@ impl FilingGenerator {
     }
 
     /// Bulk generate filings
+    ///
+    /// Runs up to `FilingGeneratorConfig::bulk_concurrency` generations at once.
     pub async fn bulk_generate_filings(
         &self,
         requests: Vec<FilingRequest>,
     ) -> Result<BulkFilingResult> {
-        info!("📦 Starting bulk filing generation for {} requests", requests.len());
+        self.bulk_generate_filings_with_options(requests, BulkGenerationOptions::default()).await
+    }
+
+    /// Bulk generate filings with a custom concurrency limit and progress callback
+    ///
+    /// Results keep the order of `requests` regardless of completion order.
+    /// AI enhancement and compliance checks are serialized per service, since
+    /// their scores are read back from the service after each call; the other
+    /// stages run concurrently.
+    pub async fn bulk_generate_filings_with_options(
+        &self,
+        requests: Vec<FilingRequest>,
+        options: BulkGenerationOptions,
+    ) -> Result<BulkFilingResult> {
+        let total = requests.len() as u32;
+        let concurrency = options.concurrency.unwrap_or(self.config.bulk_concurrency).max(1);
+        info!("📦 Starting bulk filing generation for {} requests ({} concurrent)", total, concurrency);
 
-        let mut results = Vec::new();
+        let mut slots: Vec<Option<BulkFilingItem>> = vec![None; requests.len()];
         let mut successful = 0;
         let mut failed = 0;
 
-        for request in requests {
-            match self.generate_filing(request).await {
+        let mut generations = stream::iter(requests.into_iter().enumerate())
+            .map(|(index, request)| async move { (index, self.generate_filing(request).await) })
+            .buffer_unordered(concurrency);
+
+        while let Some((index, outcome)) = generations.next().await {
+            slots[index] = Some(match outcome {
                 Ok(filing) => {
                     successful += 1;
-                    results.push(BulkFilingItem {
+                    BulkFilingItem {
                         filing: Some(filing),
                         error: None,
-                    });
+                    }
                 }
                 Err(e) => {
                     failed += 1;
-                    results.push(BulkFilingItem {
+                    BulkFilingItem {
                         filing: None,
-                        error: Some(e.to_string()),
-                    });
+                        error: Some(format!("{:#}", e)),
+                    }
                 }
+            });
+
+            if let Some(progress) = &options.progress {
+                progress(BulkProgress {
+                    completed: successful + failed,
+                    total,
+                    successful,
+                    failed,
+                });
             }
         }
 
         let bulk_result = BulkFilingResult {
-            total_requests: results.len() as u32,
+            total_requests: total,
             successful_generations: successful,
             failed_generations: failed,
-            results,
+            results: slots.into_iter().flatten().collect(),
             generation_time: Utc::now(),
         };
 
@@ -617,6 +982,21 @@ pub struct FilingGeneratorConfig {
     pub language_config: LanguageConfig,
     pub form_library_config: FormLibraryConfig,
     pub extraction_config: ExtractionConfig,
+    #[serde(default)]
+    pub checkpoint_config: CheckpointConfig,
+    #[serde(default)]
+    pub idempotency_config: IdempotencyConfig,
+    /// Root for durable state; checkpoints, idempotency keys and template
+    /// versions go in subdirectories unless their own config names a directory
+    #[serde(default)]
+    pub data_directory: Option<std::path::PathBuf>,
+    /// Filings generated concurrently by bulk generation
+    #[serde(default = "default_bulk_concurrency")]
+    pub bulk_concurrency: usize,
+}
+
+fn default_bulk_concurrency() -> usize {
+    4
 }
 
 impl Default for FilingGeneratorConfig {
@@ -632,10 +1012,43 @@ impl Default for FilingGeneratorConfig {
             language_config: LanguageConfig::default(),
             form_library_config: FormLibraryConfig::default(),
             extraction_config: ExtractionConfig::default(),
+            checkpoint_config: CheckpointConfig::default(),
+            idempotency_config: IdempotencyConfig::default(),
+            data_directory: None,
+            bulk_concurrency: default_bulk_concurrency(),
         }
     }
 }
 
+impl FilingGeneratorConfig {
+    /// Fill unset store directories from `data_directory`
+    ///
+    /// Fails when checkpoints, idempotency keys or template versions would
+    /// end up without a directory: losing them on restart can resubmit a
+    /// filing or change the template an amendment is built from.
+    fn resolve_data_directories(mut self) -> Result<Self> {
+        if let Some(root) = &self.data_directory {
+            self.checkpoint_config.directory.get_or_insert_with(|| root.join("checkpoints"));
+            self.idempotency_config.directory.get_or_insert_with(|| root.join("idempotency"));
+            self.form_library_config.version_archive_dir.get_or_insert_with(|| root.join("template-versions"));
+        }
+
+        let missing: Vec<&str> = [
+            ("checkpoint_config.directory", self.checkpoint_config.directory.is_none()),
+            ("idempotency_config.directory", self.idempotency_config.directory.is_none()),
+            ("form_library_config.version_archive_dir", self.form_library_config.version_archive_dir.is_none()),
+        ]
+        .into_iter()
+        .filter_map(|(name, unset)| unset.then_some(name))
+        .collect();
+        if !missing.is_empty() {
+            anyhow::bail!("no data_directory configured and no directory set for {}", missing.join(", "));
+        }
+
+        Ok(self)
+    }
+}
+
 /// Filing request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilingRequest {
@@ -651,6 +1064,9 @@ pub struct FilingRequest {
     pub workflow_config: Option<WorkflowConfiguration>,
     pub deadline: Option<DateTime<Utc>>,
     pub metadata: HashMap<String, String>,
+    /// Client key that makes retries of this request return the original filing and submission
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
 }
 
 /// Filing period
@@ -662,6 +1078,48 @@ pub struct FilingPeriod {
     pub fiscal_year: i32,
 }
 
+impl FilingPeriod {
+    /// Period covering one quarter of a fiscal year
+    ///
+    /// Only the month and day of `fiscal_year_start` are used. Fiscal years
+    /// are named after the calendar year they end in, so with a July start
+    /// fiscal year 2025 runs from 2024-07-01 to 2025-06-30 and its Q2 is
+    /// 2024-10-01 to 2024-12-31. Fails if `quarter` is not between 1 and 4.
+    pub fn for_fiscal_quarter(fiscal_year: i32, quarter: u8, fiscal_year_start: NaiveDate) -> Result<FilingPeriod> {
+        if !(1..=4).contains(&quarter) {
+            anyhow::bail!("Fiscal quarter must be between 1 and 4, got {}", quarter);
+        }
+
+        let starts_on_new_year = fiscal_year_start.month() == 1 && fiscal_year_start.day() == 1;
+        let start_year = if starts_on_new_year { fiscal_year } else { fiscal_year - 1 };
+        let year_start = NaiveDate::from_ymd_opt(start_year, fiscal_year_start.month(), fiscal_year_start.day())
+            // Feb 29 starts fall back to Feb 28 in common years
+            .unwrap_or_else(|| NaiveDate::from_ymd_opt(start_year, 2, 28).expect("valid date"));
+
+        let start_date = year_start + Months::new(3 * (quarter as u32 - 1));
+        let end_date = (year_start + Months::new(3 * quarter as u32)).pred_opt().expect("date after the minimum");
+
+        Ok(FilingPeriod {
+            period_type: PeriodType::Quarterly,
+            start_date,
+            end_date,
+            fiscal_year,
+        })
+    }
+
+    /// End date the period type implies for `start_date`, if the type has a fixed length
+    pub fn expected_end_date(&self) -> Option<NaiveDate> {
+        let months = self.period_type.months()?;
+        (self.start_date + Months::new(months)).pred_opt()
+    }
+
+    /// Whether the dates span exactly what `period_type` calls for; custom periods only need ordered dates
+    pub fn matches_period_type(&self) -> bool {
+        self.start_date <= self.end_date
+            && self.expected_end_date().is_none_or(|expected| expected == self.end_date)
+    }
+}
+
 /// Period types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PeriodType {
@@ -672,6 +1130,19 @@ pub enum PeriodType {
     Custom,
 }
 
+impl PeriodType {
+    /// Length in months, `None` for custom periods
+    pub fn months(&self) -> Option<u32> {
+        match self {
+            PeriodType::Annual => Some(12),
+            PeriodType::SemiAnnual => Some(6),
+            PeriodType::Quarterly => Some(3),
+            PeriodType::Monthly => Some(1),
+            PeriodType::Custom => None,
+        }
+    }
+}
+
 /// Data sources for filing generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DataSource {
@@ -699,7 +1170,7 @@ pub enum FileFormat {
 }
 
 /// Output formats
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OutputFormat {
     PDF,
     DOCX,
@@ -707,6 +1178,10 @@ pub enum OutputFormat {
     HTML,
     XML,
     JSON,
+    /// XBRL instance document
+    XBRL,
+    /// Inline XBRL embedded in XHTML
+    IXBRL,
 }
 
 /// Generated filing result
@@ -714,6 +1189,7 @@ pub enum OutputFormat {
 pub struct GeneratedFiling {
     pub filing_id: Uuid,
     pub request: FilingRequest,
+    /// Template ID and exact version used, e.g. `sec-10-k@2024.1`
     pub template_used: String,
     pub document: GeneratedDocument,
     pub workflow_id: Option<String>,
@@ -723,6 +1199,54 @@ pub struct GeneratedFiling {
     pub generation_timestamp: DateTime<Utc>,
     pub status: FilingStatus,
     pub metadata: HashMap<String, String>,
+    /// Returned from the idempotency store for a repeated idempotency key
+    #[serde(default)]
+    pub idempotent_replay: bool,
+}
+
+#[cfg(test)]
+impl GeneratedFiling {
+    /// Generated US 10-K filing with a JSON document for unit tests
+    pub(crate) fn for_tests() -> Self {
+        let request = FilingRequest {
+            organization_id: "org-1".to_string(),
+            form_type: "10-K".to_string(),
+            jurisdiction: "US".to_string(),
+            filing_period: FilingPeriod::for_fiscal_quarter(2024, 4, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+            data_sources: vec![DataSource::Manual { data: serde_json::json!({}) }],
+            output_format: OutputFormat::JSON,
+            language: "en".to_string(),
+            require_signature: false,
+            signature_config: None,
+            workflow_config: None,
+            deadline: None,
+            metadata: HashMap::new(),
+            idempotency_key: None,
+        };
+        GeneratedFiling {
+            filing_id: Uuid::new_v4(),
+            template_used: "sec-10-k@2024.1".to_string(),
+            document: GeneratedDocument {
+                document_id: Uuid::new_v4(),
+                template_id: "sec-10-k".to_string(),
+                format: OutputFormat::JSON,
+                mime_type: "application/json".to_string(),
+                content: b"{}".to_vec(),
+                generated_at: Utc::now(),
+                metadata: HashMap::new(),
+                signatures: Vec::new(),
+            },
+            request,
+            workflow_id: None,
+            validation_results: ValidationResult::default(),
+            compliance_score: 1.0,
+            ai_confidence: 1.0,
+            generation_timestamp: Utc::now(),
+            status: FilingStatus::Generated,
+            metadata: HashMap::new(),
+            idempotent_replay: false,
+        }
+    }
 }
 
 /// Filing status
@@ -764,6 +1288,26 @@ pub struct BulkFilingItem {
     pub error: Option<String>,
 }
 
+/// Progress of a bulk generation, reported after each filing completes
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BulkProgress {
+    pub completed: u32,
+    pub total: u32,
+    pub successful: u32,
+    pub failed: u32,
+}
+
+/// Callback receiving bulk generation progress
+pub type BulkProgressCallback = Arc<dyn Fn(BulkProgress) + Send + Sync>;
+
+/// Options for bulk generation
+#[derive(Clone, Default)]
+pub struct BulkGenerationOptions {
+    /// Overrides `FilingGeneratorConfig::bulk_concurrency`
+    pub concurrency: Option<usize>,
+    pub progress: Option<BulkProgressCallback>,
+}
+
 /// Filing generator status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilingGeneratorStatus {
@@ -808,9 +1352,20 @@ mod tests {
 
     #[tokio::test]
     async fn test_filing_generator_initialization() {
-        let config = FilingGeneratorConfig::default();
+        let directory = tempfile::tempdir().unwrap();
+        let config = FilingGeneratorConfig {
+            data_directory: Some(directory.path().to_path_buf()),
+            ..FilingGeneratorConfig::default()
+        };
         let generator = FilingGenerator::new(config).await;
         assert!(generator.is_ok());
+        assert!(directory.path().join("checkpoints").is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_filing_generator_requires_data_directory() {
+        let error = FilingGenerator::new(FilingGeneratorConfig::default()).await.err().unwrap();
+        assert!(error.to_string().contains("data_directory"));
     }
 
     #[tokio::test]
@@ -825,4 +1380,42 @@ mod tests {
 
         assert_eq!(filter.jurisdiction, Some("US".to_string()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_fiscal_quarters_follow_the_fiscal_year_start() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let q2 = FilingPeriod::for_fiscal_quarter(2025, 2, date(2000, 7, 1)).unwrap();
+        assert_eq!((q2.start_date, q2.end_date), (date(2024, 10, 1), date(2024, 12, 31)));
+        assert!(q2.matches_period_type());
+
+        let q4 = FilingPeriod::for_fiscal_quarter(2025, 4, date(2000, 7, 1)).unwrap();
+        assert_eq!((q4.start_date, q4.end_date), (date(2025, 4, 1), date(2025, 6, 30)));
+
+        let calendar_q1 = FilingPeriod::for_fiscal_quarter(2024, 1, date(2000, 1, 1)).unwrap();
+        assert_eq!((calendar_q1.start_date, calendar_q1.end_date), (date(2024, 1, 1), date(2024, 3, 31)));
+
+        let mislabeled = FilingPeriod { period_type: PeriodType::Annual, ..q2 };
+        assert!(!mislabeled.matches_period_type());
+        assert_eq!(mislabeled.expected_end_date(), Some(date(2025, 9, 30)));
+
+        assert!(FilingPeriod::for_fiscal_quarter(2025, 0, date(2000, 7, 1)).is_err());
+        assert!(FilingPeriod::for_fiscal_quarter(2025, 5, date(2000, 7, 1)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_filing_locks_only_serialize_the_same_filing() {
+        let locks = FilingLocks::default();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let held = locks.lock(first).await;
+
+        let wait = std::time::Duration::from_millis(50);
+        assert!(tokio::time::timeout(wait, locks.lock(second)).await.is_ok());
+        assert!(tokio::time::timeout(wait, locks.lock(first)).await.is_err());
+
+        drop(held);
+        drop(locks.lock(first).await);
+        locks.lock(second).await;
+        assert_eq!(locks.locks.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,300 @@
+/*!
+ * Regulatory Submission
+ *
+ * Adapters that upload a generated filing to a regulator's e-filing system
+ * and return the regulator's confirmation. The workflow manager routes each
+ * filing to the adapter registered for its jurisdiction.
+ */
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::{FilingStatus, GeneratedFiling, OutputFormat};
+
+/// Filing metadata key holding the JSON submission receipt
+pub const SUBMISSION_RECEIPT_KEY: &str = "submission.receipt";
+
+/// Regulator's acknowledgement of an accepted submission
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmissionReceipt {
+    /// Adapter that made the submission, e.g. `sec-edgar`
+    pub adapter: String,
+    /// Confirmation the regulator issued, e.g. an EDGAR accession number
+    pub confirmation_id: String,
+    pub endpoint: String,
+    /// Whether the regulator treated the submission as a test filing
+    pub test_filing: bool,
+    pub submitted_at: DateTime<Utc>,
+    /// Returned from the idempotency store for a repeated idempotency key
+    #[serde(default)]
+    pub idempotent_replay: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SubmissionError {
+    #[error("filing in status {0:?} cannot be submitted")]
+    NotSubmittable(FilingStatus),
+    #[error("approval workflow {0} has not been approved")]
+    NotApproved(String),
+    #[error("no submission adapter registered for jurisdiction {0}")]
+    NoAdapter(String),
+    #[error("{adapter} rejected the submission (HTTP {status}): {message}")]
+    Rejected { adapter: String, status: u16, message: String },
+}
+
+impl SubmissionError {
+    /// Whether the regulator certainly did not accept the filing
+    ///
+    /// Server errors may be raised after the filing was received, so only
+    /// client errors count as a definite rejection.
+    pub fn is_definite(&self) -> bool {
+        match self {
+            SubmissionError::NotSubmittable(_) | SubmissionError::NotApproved(_) | SubmissionError::NoAdapter(_) => true,
+            SubmissionError::Rejected { status, .. } => (400..500).contains(status),
+        }
+    }
+}
+
+/// Upload of filings to one regulator's e-filing endpoint
+#[async_trait]
+pub trait SubmissionAdapter: Send + Sync {
+    /// Short identifier recorded in receipts
+    fn name(&self) -> &str;
+
+    /// Upload `filing`, returning the regulator's confirmation once it is accepted
+    ///
+    /// `idempotency_key` is passed to the regulator where its API supports
+    /// deduplication, so a retried upload is not filed twice.
+    async fn submit(&self, filing: &GeneratedFiling, idempotency_key: &str) -> Result<SubmissionReceipt>;
+}
+
+/// Whether `filing` has reached the status it must be submitted in
+///
+/// Filings that require a signature are submitted once signed, all others
+/// once approved.
+pub fn is_submittable(filing: &GeneratedFiling) -> bool {
+    match filing.status {
+        FilingStatus::Approved => !filing.request.require_signature,
+        FilingStatus::Signed => filing.request.require_signature,
+        _ => false,
+    }
+}
+
+/// SEC EDGAR submission settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgarConfig {
+    /// HTTPS endpoint of the submission gateway
+    pub endpoint: String,
+    /// Filer's Central Index Key
+    pub cik: String,
+    /// File holding the CIK confirmation code, kept out of the configuration
+    pub ccc_path: PathBuf,
+    /// Mark submissions as test filings, which EDGAR validates but never disseminates
+    pub test_filing: bool,
+    pub timeout_secs: u64,
+}
+
+/// SEC EDGAR adapter stub
+///
+/// Posts the filing document with its submission header as JSON to a
+/// gateway that files it with EDGAR, and takes the accession number it
+/// returns as the confirmation ID. EDGAR itself only accepts EDGARLink
+/// Online submissions, which this adapter does not produce, so it cannot
+/// be pointed at the SEC directly.
+pub struct EdgarSubmissionAdapter {
+    config: EdgarConfig,
+    /// CIK confirmation code read from `config.ccc_path`
+    ccc: String,
+    http_client: reqwest::Client,
+}
+
+impl EdgarSubmissionAdapter {
+    /// Read the CIK confirmation code; the endpoint must be HTTPS so it is never sent in cleartext
+    pub fn new(config: EdgarConfig) -> Result<Self> {
+        let endpoint = reqwest::Url::parse(&config.endpoint)?;
+        let loopback = matches!(endpoint.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+        if endpoint.scheme() != "https" && !loopback {
+            return Err(anyhow!("EDGAR endpoint {} must use HTTPS", config.endpoint));
+        }
+        let ccc = std::fs::read_to_string(&config.ccc_path)
+            .map_err(|e| anyhow!("Failed to read CIK confirmation code {}: {}", config.ccc_path.display(), e))?
+            .trim()
+            .to_string();
+
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()?;
+        Ok(Self { config, ccc, http_client })
+    }
+
+    /// Submission payload: EDGAR header fields plus the base64 document
+    pub fn submission_payload(&self, filing: &GeneratedFiling) -> Value {
+        let document = &filing.document;
+        json!({
+            "filer": { "cik": self.config.cik, "ccc": self.ccc },
+            "submissionType": filing.request.form_type,
+            "periodOfReport": filing.request.filing_period.end_date.format("%m-%d-%Y").to_string(),
+            "testFiling": self.config.test_filing,
+            "documents": [{
+                "fileName": format!("{}.{}", document.document_id, extension(&document.format)),
+                "type": filing.request.form_type,
+                "contentType": document.mime_type,
+                "content": base64::engine::general_purpose::STANDARD.encode(&document.content),
+            }],
+        })
+    }
+}
+
+#[async_trait]
+impl SubmissionAdapter for EdgarSubmissionAdapter {
+    fn name(&self) -> &str {
+        "sec-edgar"
+    }
+
+    async fn submit(&self, filing: &GeneratedFiling, idempotency_key: &str) -> Result<SubmissionReceipt> {
+        let response = self
+            .http_client
+            .post(&self.config.endpoint)
+            .header("Idempotency-Key", idempotency_key)
+            .json(&self.submission_payload(filing))
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        if !status.is_success() {
+            let message = body["message"].as_str().unwrap_or(status.as_str()).to_string();
+            return Err(SubmissionError::Rejected {
+                adapter: self.name().to_string(),
+                status: status.as_u16(),
+                message,
+            }
+            .into());
+        }
+
+        let confirmation_id = body["accessionNumber"]
+            .as_str()
+            .ok_or_else(|| anyhow!("EDGAR accepted filing {} without an accession number", filing.filing_id))?
+            .to_string();
+        info!("📨 EDGAR accepted filing {} as {}", filing.filing_id, confirmation_id);
+
+        Ok(SubmissionReceipt {
+            adapter: self.name().to_string(),
+            confirmation_id,
+            endpoint: self.config.endpoint.clone(),
+            test_filing: self.config.test_filing,
+            submitted_at: Utc::now(),
+            idempotent_replay: false,
+        })
+    }
+}
+
+fn extension(format: &OutputFormat) -> &'static str {
+    match format {
+        OutputFormat::PDF => "pdf",
+        OutputFormat::HTML | OutputFormat::IXBRL => "htm",
+        OutputFormat::XML | OutputFormat::XBRL => "xml",
+        OutputFormat::JSON => "json",
+        OutputFormat::DOCX => "docx",
+        OutputFormat::XLSX => "xlsx",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter(endpoint: &str) -> (EdgarSubmissionAdapter, tempfile::NamedTempFile) {
+        let ccc = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(ccc.path(), "x1y2z3@a\n").unwrap();
+        let adapter = EdgarSubmissionAdapter::new(EdgarConfig {
+            endpoint: endpoint.to_string(),
+            cik: "0000320193".to_string(),
+            ccc_path: ccc.path().to_path_buf(),
+            test_filing: true,
+            timeout_secs: 30,
+        })
+        .unwrap();
+        (adapter, ccc)
+    }
+
+    #[test]
+    fn test_edgar_payload_carries_header_and_document() {
+        let (adapter, _ccc) = adapter("https://edgar.test/submissions");
+        let mut filing = GeneratedFiling::for_tests();
+
+        let payload = adapter.submission_payload(&filing);
+        assert_eq!(payload["filer"]["cik"], "0000320193");
+        assert_eq!(payload["filer"]["ccc"], "x1y2z3@a");
+        assert_eq!(payload["submissionType"], "10-K");
+        assert_eq!(payload["periodOfReport"], "12-31-2024");
+        assert_eq!(payload["testFiling"], true);
+        assert_eq!(payload["documents"][0]["content"], "e30=");
+        assert!(payload["documents"][0]["fileName"].as_str().unwrap().ends_with(".json"));
+
+        filing.status = FilingStatus::Approved;
+        assert!(is_submittable(&filing));
+        filing.status = FilingStatus::Validated;
+        assert!(!is_submittable(&filing));
+        filing.request.require_signature = true;
+        filing.status = FilingStatus::Approved;
+        assert!(!is_submittable(&filing));
+        filing.status = FilingStatus::Signed;
+        assert!(is_submittable(&filing));
+
+        let ccc = tempfile::NamedTempFile::new().unwrap();
+        let cleartext = EdgarSubmissionAdapter::new(EdgarConfig {
+            endpoint: "http://edgar.test/submissions".to_string(),
+            cik: "0000320193".to_string(),
+            ccc_path: ccc.path().to_path_buf(),
+            test_filing: true,
+            timeout_secs: 30,
+        });
+        assert!(cleartext.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_edgar_submit_returns_accession_number_or_rejection() {
+        let mut server = mockito::Server::new_async().await;
+        let accepted = server
+            .mock("POST", "/submissions")
+            .match_header("idempotency-key", "submit-1")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"accessionNumber":"0000320193-24-000123"}"#)
+            .create_async()
+            .await;
+        let (adapter, _ccc) = adapter(&format!("{}/submissions", server.url()));
+        let filing = GeneratedFiling::for_tests();
+
+        let receipt = adapter.submit(&filing, "submit-1").await.unwrap();
+        accepted.assert_async().await;
+        assert_eq!(receipt.confirmation_id, "0000320193-24-000123");
+        assert_eq!(receipt.adapter, "sec-edgar");
+        assert!(receipt.test_filing);
+
+        server
+            .mock("POST", "/submissions")
+            .match_header("idempotency-key", "submit-2")
+            .with_status(422)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"message":"invalid period of report"}"#)
+            .create_async()
+            .await;
+        let error = adapter.submit(&filing, "submit-2").await.unwrap_err();
+        let error = error.downcast_ref::<SubmissionError>().unwrap();
+        assert!(matches!(
+            error,
+            SubmissionError::Rejected { status: 422, message, .. } if message == "invalid period of report"
+        ));
+        assert!(error.is_definite());
+    }
+}
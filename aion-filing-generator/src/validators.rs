@@ -0,0 +1,520 @@
+/*!
+ * Validation Engine
+ *
+ * Validates filing requests and filing data against the field schema of
+ * a form template. Every error and warning points at the offending value
+ * with a JSON pointer so front-ends can highlight the exact input.
+ */
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+use crate::{FieldType, FilingRequest, FormField, FormTemplate};
+
+/// Validation engine configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationConfig {
+    /// Report data keys that match no template field as errors instead of warnings
+    pub strict_mode: bool,
+    /// Stop collecting errors once this many have been found
+    pub max_errors: usize,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            strict_mode: false,
+            max_errors: 500,
+        }
+    }
+}
+
+/// Constraint a value violated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldConstraint {
+    Required,
+    Type { expected: String },
+    MinLength { min: usize },
+    MaxLength { max: usize },
+    Pattern { pattern: String },
+    MinValue { min: f64 },
+    MaxValue { max: f64 },
+    AllowedValues { allowed: Vec<String> },
+    KnownField,
+    /// A date that must not precede another date of the same record
+    NotBefore { earliest: NaiveDate },
+    /// The end date a period of `period_type` implies for its start date
+    PeriodEnd { period_type: String, expected: NaiveDate },
+}
+
+impl fmt::Display for FieldConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldConstraint::Required => write!(f, "a value is required"),
+            FieldConstraint::Type { expected } => write!(f, "expected {}", expected),
+            FieldConstraint::MinLength { min } => write!(f, "must be at least {} long", min),
+            FieldConstraint::MaxLength { max } => write!(f, "must be at most {} long", max),
+            FieldConstraint::Pattern { pattern } => write!(f, "must match pattern {}", pattern),
+            FieldConstraint::MinValue { min } => write!(f, "must be at least {}", min),
+            FieldConstraint::MaxValue { max } => write!(f, "must be at most {}", max),
+            FieldConstraint::AllowedValues { allowed } => write!(f, "must be one of {}", allowed.join(", ")),
+            FieldConstraint::KnownField => write!(f, "is not a field of this form"),
+            FieldConstraint::NotBefore { earliest } => write!(f, "must not be before {}", earliest),
+            FieldConstraint::PeriodEnd { period_type, expected } => {
+                write!(f, "must be {} for a {} period", expected, period_type)
+            }
+        }
+    }
+}
+
+/// A single validation error or warning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// JSON pointer (RFC 6901) to the offending value, e.g. `/financials/revenue`
+    pub field_path: String,
+    /// The failing value; `None` when the value is missing
+    pub value: Option<Value>,
+    pub constraint: FieldConstraint,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn new(field_path: impl Into<String>, value: Option<&Value>, constraint: FieldConstraint) -> Self {
+        let field_path = field_path.into();
+        let message = format!("{} {}", field_path, constraint);
+        Self {
+            field_path,
+            value: value.cloned(),
+            constraint,
+            message,
+        }
+    }
+}
+
+/// Outcome of a validation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationResult {
+    pub is_valid: bool,
+    pub errors: Vec<ValidationIssue>,
+    pub warnings: Vec<ValidationIssue>,
+    pub validated_at: DateTime<Utc>,
+}
+
+impl Default for ValidationResult {
+    fn default() -> Self {
+        Self {
+            is_valid: true,
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            validated_at: Utc::now(),
+        }
+    }
+}
+
+impl ValidationResult {
+    /// Merge two results; the combination is valid only if both are
+    pub fn combine(first: ValidationResult, second: ValidationResult) -> ValidationResult {
+        let mut errors = first.errors;
+        errors.extend(second.errors);
+        let mut warnings = first.warnings;
+        warnings.extend(second.warnings);
+
+        ValidationResult {
+            is_valid: first.is_valid && second.is_valid && errors.is_empty(),
+            errors,
+            warnings,
+            validated_at: first.validated_at.max(second.validated_at),
+        }
+    }
+
+    /// Fail with `ValidationError::InvalidData`, carrying every issue, unless the result is valid
+    pub fn ensure_valid(&self) -> Result<(), ValidationError> {
+        if self.is_valid {
+            Ok(())
+        } else {
+            Err(ValidationError::InvalidData { result: self.clone() })
+        }
+    }
+
+    /// Errors reported for a field path or anything beneath it
+    pub fn errors_at<'a>(&'a self, field_path: &'a str) -> impl Iterator<Item = &'a ValidationIssue> + 'a {
+        self.errors.iter().filter(move |issue| {
+            issue.field_path == field_path
+                || issue.field_path.strip_prefix(field_path).is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Validation failures that stop a filing
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationError {
+    #[error("filing data failed validation: {}", messages(&result.errors))]
+    InvalidData { result: ValidationResult },
+}
+
+fn messages(issues: &[ValidationIssue]) -> String {
+    issues.iter().map(|issue| issue.message.as_str()).collect::<Vec<_>>().join("; ")
+}
+
+/// Validation engine
+pub struct ValidationEngine {
+    config: ValidationConfig,
+    last_results: Arc<RwLock<ValidationResult>>,
+}
+
+impl ValidationEngine {
+    pub async fn new(config: ValidationConfig) -> Result<Self> {
+        info!("✅ Initializing validation engine");
+
+        Ok(Self {
+            config,
+            last_results: Arc::new(RwLock::new(ValidationResult::default())),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("🚀 Starting validation engine");
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        info!("🛑 Stopping validation engine");
+        Ok(())
+    }
+
+    /// Check that a filing request is complete enough to generate from
+    pub async fn validate_filing_request(&self, request: &FilingRequest) -> Result<()> {
+        let mut issues = Vec::new();
+        let string = |text: &str| Value::String(text.to_string());
+
+        for (path, text) in [
+            ("/organization_id", &request.organization_id),
+            ("/form_type", &request.form_type),
+            ("/jurisdiction", &request.jurisdiction),
+        ] {
+            if text.trim().is_empty() {
+                issues.push(ValidationIssue::new(path, Some(&string(text)), FieldConstraint::Required));
+            }
+        }
+
+        let period = &request.filing_period;
+        if period.end_date < period.start_date {
+            issues.push(ValidationIssue::new(
+                "/filing_period/end_date",
+                Some(&string(&period.end_date.to_string())),
+                FieldConstraint::NotBefore { earliest: period.start_date },
+            ));
+        } else if let Some(expected_end) = period.expected_end_date().filter(|_| !period.matches_period_type()) {
+            issues.push(ValidationIssue::new(
+                "/filing_period/end_date",
+                Some(&string(&period.end_date.to_string())),
+                FieldConstraint::PeriodEnd {
+                    period_type: format!("{:?}", period.period_type),
+                    expected: expected_end,
+                },
+            ));
+        }
+
+        if request.data_sources.is_empty() {
+            issues.push(ValidationIssue::new("/data_sources", None, FieldConstraint::Required));
+        }
+
+        if !issues.is_empty() {
+            bail!("Invalid filing request: {}", messages(&issues));
+        }
+        Ok(())
+    }
+
+    /// Validate filing data against the template's field schema
+    pub async fn validate_data(&self, data: &Value, template: &FormTemplate) -> Result<ValidationResult> {
+        debug!("🔍 Validating data against template: {}", template.template_id);
+
+        let result = validate_against_template(data, template, &self.config);
+        *self.last_results.write().await = result.clone();
+
+        debug!("✅ Validation finished: {} errors, {} warnings", result.errors.len(), result.warnings.len());
+        Ok(result)
+    }
+
+    pub async fn get_last_validation_results(&self) -> Result<ValidationResult> {
+        Ok(self.last_results.read().await.clone())
+    }
+}
+
+fn validate_against_template(data: &Value, template: &FormTemplate, config: &ValidationConfig) -> ValidationResult {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    for field in &template.fields {
+        let path = field_pointer(&field.field_id);
+        match data.pointer(&path).filter(|value| !value.is_null()) {
+            Some(value) => check_field(field, &path, value, &mut errors),
+            None if field.required => errors.push(ValidationIssue::new(&path, None, FieldConstraint::Required)),
+            None => {}
+        }
+    }
+
+    // Top-level keys the template does not know about
+    let known: HashSet<&str> = template
+        .fields
+        .iter()
+        .filter_map(|field| field.field_id.split('.').next())
+        .collect();
+    if let Some(object) = data.as_object() {
+        for (key, value) in object {
+            if !known.contains(key.as_str()) {
+                let issue = ValidationIssue::new(field_pointer(key), Some(value), FieldConstraint::KnownField);
+                if config.strict_mode {
+                    errors.push(issue);
+                } else {
+                    warnings.push(issue);
+                }
+            }
+        }
+    }
+
+    errors.truncate(config.max_errors);
+
+    ValidationResult {
+        is_valid: errors.is_empty(),
+        errors,
+        warnings,
+        validated_at: Utc::now(),
+    }
+}
+
+fn check_field(field: &FormField, path: &str, value: &Value, errors: &mut Vec<ValidationIssue>) {
+    let mut fail = |path: &str, value: &Value, constraint: FieldConstraint| {
+        errors.push(ValidationIssue::new(path, Some(value), constraint));
+    };
+    let expect = |expected: &str| FieldConstraint::Type { expected: expected.to_string() };
+
+    match &field.field_type {
+        FieldType::Number | FieldType::Currency | FieldType::Percentage => {
+            if !value.is_number() {
+                return fail(path, value, expect("a number"));
+            }
+        }
+        FieldType::Boolean => {
+            if !value.is_boolean() {
+                return fail(path, value, expect("a boolean"));
+            }
+        }
+        FieldType::Date => {
+            if value.as_str().and_then(|text| chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()).is_none() {
+                return fail(path, value, expect("a date formatted YYYY-MM-DD"));
+            }
+        }
+        FieldType::DateTime => {
+            if value.as_str().and_then(|text| DateTime::parse_from_rfc3339(text).ok()).is_none() {
+                return fail(path, value, expect("an RFC 3339 timestamp"));
+            }
+        }
+        FieldType::Email => {
+            let valid = value.as_str().is_some_and(|text| {
+                text.split_once('@').is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.'))
+            });
+            if !valid {
+                return fail(path, value, expect("an email address"));
+            }
+        }
+        FieldType::Select { options } => {
+            let allowed: Vec<String> = options.iter().map(|option| option.value.clone()).collect();
+            if !value.as_str().is_some_and(|text| allowed.iter().any(|option| option == text)) {
+                return fail(path, value, FieldConstraint::AllowedValues { allowed });
+            }
+        }
+        FieldType::MultiSelect { options } => {
+            let allowed: Vec<String> = options.iter().map(|option| option.value.clone()).collect();
+            let Some(items) = value.as_array() else {
+                return fail(path, value, expect("an array"));
+            };
+            for (index, item) in items.iter().enumerate() {
+                if !item.as_str().is_some_and(|text| allowed.iter().any(|option| option == text)) {
+                    fail(&format!("{}/{}", path, index), item, FieldConstraint::AllowedValues { allowed: allowed.clone() });
+                }
+            }
+        }
+        FieldType::Address | FieldType::File => {
+            if !(value.is_string() || value.is_object()) {
+                return fail(path, value, expect("a string or object"));
+            }
+        }
+        FieldType::Text | FieldType::Phone | FieldType::TaxId => {
+            if !value.is_string() {
+                return fail(path, value, expect("a string"));
+            }
+        }
+        FieldType::Custom(_) => {}
+    }
+
+    let Some(rules) = &field.validation else {
+        return;
+    };
+
+    let length = match value {
+        Value::String(text) => Some(text.chars().count()),
+        Value::Array(items) => Some(items.len()),
+        _ => None,
+    };
+    if let Some(length) = length {
+        if let Some(min) = rules.min_length.filter(|&min| length < min) {
+            fail(path, value, FieldConstraint::MinLength { min });
+        }
+        if let Some(max) = rules.max_length.filter(|&max| length > max) {
+            fail(path, value, FieldConstraint::MaxLength { max });
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(min) = rules.min_value.filter(|&min| number < min) {
+            fail(path, value, FieldConstraint::MinValue { min });
+        }
+        if let Some(max) = rules.max_value.filter(|&max| number > max) {
+            fail(path, value, FieldConstraint::MaxValue { max });
+        }
+    }
+
+    if let (Some(pattern), Some(text)) = (&rules.pattern, value.as_str()) {
+        match Regex::new(pattern) {
+            Ok(regex) if !regex.is_match(text) => {
+                fail(path, value, FieldConstraint::Pattern { pattern: pattern.clone() });
+            }
+            Ok(_) => {}
+            Err(e) => debug!("⚠️ Skipping invalid pattern on field {}: {}", field.field_id, e),
+        }
+    }
+}
+
+/// JSON pointer for a field ID; dotted IDs address nested objects
+pub(crate) fn field_pointer(field_id: &str) -> String {
+    field_id
+        .split('.')
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FieldValidation, SelectOption};
+
+    fn field(field_id: &str, field_type: FieldType, validation: Option<FieldValidation>) -> FormField {
+        FormField {
+            field_id: field_id.to_string(),
+            name: field_id.to_string(),
+            description: String::new(),
+            field_type,
+            required: true,
+            validation,
+            default_value: None,
+            help_text: None,
+            conditional_logic: None,
+        }
+    }
+
+    #[test]
+    fn test_errors_point_at_nested_fields() {
+        let template = FormTemplate::for_tests("test-form", vec![
+            field("financials.revenue", FieldType::Currency, Some(FieldValidation {
+                min_length: None,
+                max_length: None,
+                pattern: None,
+                min_value: Some(0.0),
+                max_value: None,
+                custom_validator: None,
+            })),
+            field("filer.cik", FieldType::Text, Some(FieldValidation {
+                min_length: None,
+                max_length: None,
+                pattern: Some(r"^\d{10}$".to_string()),
+                min_value: None,
+                max_value: None,
+                custom_validator: None,
+            })),
+            field("period_end", FieldType::Date, None),
+        ]);
+        let data = serde_json::json!({
+            "financials": {"revenue": -5},
+            "filer": {"cik": "12345"},
+        });
+
+        let result = validate_against_template(&data, &template, &ValidationConfig::default());
+
+        assert!(!result.is_valid);
+        let revenue = result.errors_at("/financials/revenue").next().unwrap();
+        assert_eq!(revenue.value, Some(serde_json::json!(-5)));
+        assert_eq!(revenue.constraint, FieldConstraint::MinValue { min: 0.0 });
+        let cik = result.errors_at("/filer").next().unwrap();
+        assert!(matches!(cik.constraint, FieldConstraint::Pattern { .. }));
+        let period = result.errors_at("/period_end").next().unwrap();
+        assert_eq!(period.value, None);
+        assert_eq!(period.constraint, FieldConstraint::Required);
+        // Invalid results stop a filing with every issue attached
+        match result.ensure_valid() {
+            Err(ValidationError::InvalidData { result: failed }) => assert_eq!(failed.errors.len(), result.errors.len()),
+            Ok(()) => panic!("invalid result passed"),
+        }
+        assert!(ValidationResult::default().ensure_valid().is_ok());
+    }
+
+    #[test]
+    fn test_multi_select_items_and_unknown_fields() {
+        let options = ["SOX", "GDPR"]
+            .iter()
+            .map(|value| SelectOption { value: value.to_string(), label: value.to_string(), description: None })
+            .collect();
+        let template = FormTemplate::for_tests("test-form", vec![field("frameworks", FieldType::MultiSelect { options }, None)]);
+        let data = serde_json::json!({"frameworks": ["SOX", "HIPAA"], "notes": "draft"});
+
+        let result = validate_against_template(&data, &template, &ValidationConfig::default());
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].field_path, "/frameworks/1");
+        assert_eq!(result.warnings[0].field_path, "/notes");
+        assert_eq!(result.warnings[0].constraint, FieldConstraint::KnownField);
+
+        let strict = ValidationConfig { strict_mode: true, ..ValidationConfig::default() };
+        let result = validate_against_template(&data, &template, &strict);
+        assert_eq!(result.errors.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_request_period_dates_must_match_period_type() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let mut filing_period = crate::FilingPeriod::for_fiscal_quarter(2024, 4, date(2024, 1, 1)).unwrap();
+        filing_period.end_date = date(2024, 11, 30);
+        let mut request = FilingRequest {
+            organization_id: "org-1".to_string(),
+            form_type: "test-form".to_string(),
+            jurisdiction: "US".to_string(),
+            filing_period,
+            data_sources: vec![crate::DataSource::Manual { data: serde_json::json!({}) }],
+            output_format: crate::OutputFormat::PDF,
+            language: "en".to_string(),
+            require_signature: false,
+            signature_config: None,
+            workflow_config: None,
+            deadline: None,
+            metadata: Default::default(),
+            idempotency_key: None,
+        };
+        let engine = ValidationEngine::new(ValidationConfig::default()).await.unwrap();
+
+        let error = engine.validate_filing_request(&request).await.unwrap_err();
+        assert!(error.to_string().contains("/filing_period/end_date must be 2024-12-31 for a Quarterly period"));
+
+        request.filing_period.end_date = date(2024, 9, 30);
+        let error = engine.validate_filing_request(&request).await.unwrap_err();
+        assert!(error.to_string().contains("/filing_period/end_date must not be before 2024-10-01"));
+    }
+}
@@ -0,0 +1,288 @@
+/*!
+ * Filing Workflow
+ *
+ * Approval workflows for generated filings and the final step of the
+ * filing lifecycle: submission to the regulator through the adapter
+ * registered for the filing's jurisdiction.
+ */
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    is_submittable, FilingStatus, GeneratedDocument, GeneratedFiling, SubmissionAdapter, SubmissionError,
+    SubmissionReceipt, SUBMISSION_RECEIPT_KEY,
+};
+
+/// Workflow manager configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowConfig {
+    /// Time approvers have to act on a workflow
+    pub approval_timeout_hours: i64,
+}
+
+impl Default for WorkflowConfig {
+    fn default() -> Self {
+        Self {
+            approval_timeout_hours: 72,
+        }
+    }
+}
+
+/// Approval workflow requested for a filing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowConfiguration {
+    pub approvers: Vec<String>,
+    /// Require every approver instead of any one of them
+    pub require_all_approvals: bool,
+}
+
+/// An approval workflow for a generated document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workflow {
+    pub workflow_id: String,
+    pub document_id: Uuid,
+    pub configuration: WorkflowConfiguration,
+    pub created_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+    /// Approvers who have signed off, in order
+    #[serde(default)]
+    pub approvals: Vec<Approval>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Approval {
+    pub approver: String,
+    pub approved_at: DateTime<Utc>,
+}
+
+impl Workflow {
+    /// Whether enough of the configured approvers have signed off
+    pub fn is_approved(&self) -> bool {
+        let approved = |approver: &String| self.approvals.iter().any(|approval| &approval.approver == approver);
+        if self.configuration.require_all_approvals {
+            self.configuration.approvers.iter().all(approved)
+        } else {
+            self.configuration.approvers.iter().any(approved)
+        }
+    }
+}
+
+/// Workflow manager
+pub struct WorkflowManager {
+    config: WorkflowConfig,
+    workflows: RwLock<HashMap<String, Workflow>>,
+    /// Submission adapters by jurisdiction
+    submission_adapters: RwLock<HashMap<String, Arc<dyn SubmissionAdapter>>>,
+}
+
+impl WorkflowManager {
+    pub async fn new(config: WorkflowConfig) -> Result<Self> {
+        info!("🔀 Initializing workflow manager");
+
+        Ok(Self {
+            config,
+            workflows: RwLock::new(HashMap::new()),
+            submission_adapters: RwLock::new(HashMap::new()),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("🚀 Starting workflow manager");
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        info!("🛑 Stopping workflow manager");
+        Ok(())
+    }
+
+    /// Open an approval workflow for `document`, returning its ID
+    pub async fn create_workflow(
+        &self,
+        document: &GeneratedDocument,
+        configuration: &WorkflowConfiguration,
+    ) -> Result<String> {
+        let created_at = Utc::now();
+        let workflow = Workflow {
+            workflow_id: Uuid::new_v4().to_string(),
+            document_id: document.document_id,
+            configuration: configuration.clone(),
+            created_at,
+            due_at: created_at + Duration::hours(self.config.approval_timeout_hours),
+            approvals: Vec::new(),
+        };
+
+        let workflow_id = workflow.workflow_id.clone();
+        info!("🔀 Workflow {} created for document {}", workflow_id, document.document_id);
+        self.workflows.write().await.insert(workflow_id.clone(), workflow);
+        Ok(workflow_id)
+    }
+
+    pub async fn get_workflow(&self, workflow_id: &str) -> Option<Workflow> {
+        self.workflows.read().await.get(workflow_id).cloned()
+    }
+
+    /// Record `approver`'s sign-off on the workflow of `filing`
+    ///
+    /// Once the workflow is approved the filing moves to `Approved`. Only
+    /// configured approvers may sign off, and only before the workflow is due.
+    pub async fn approve_filing(&self, filing: &mut GeneratedFiling, approver: &str) -> Result<()> {
+        let workflow_id = filing
+            .workflow_id
+            .clone()
+            .ok_or_else(|| anyhow!("Filing {} has no approval workflow", filing.filing_id))?;
+        let mut workflows = self.workflows.write().await;
+        let workflow = workflows
+            .get_mut(&workflow_id)
+            .ok_or_else(|| anyhow!("Workflow not found: {}", workflow_id))?;
+        if !workflow.configuration.approvers.iter().any(|configured| configured == approver) {
+            return Err(anyhow!("{} is not an approver of workflow {}", approver, workflow_id));
+        }
+        let now = Utc::now();
+        if now > workflow.due_at {
+            return Err(anyhow!("Workflow {} expired at {}", workflow_id, workflow.due_at));
+        }
+
+        if !workflow.approvals.iter().any(|approval| approval.approver == approver) {
+            workflow.approvals.push(Approval { approver: approver.to_string(), approved_at: now });
+            info!("🔀 {} approved workflow {}", approver, workflow_id);
+        }
+        if workflow.is_approved() && matches!(filing.status, FilingStatus::Generated | FilingStatus::Validated) {
+            filing.status = FilingStatus::Approved;
+        }
+        Ok(())
+    }
+
+    /// Route submissions of filings for `jurisdiction` to `adapter`
+    pub async fn register_submission_adapter(&self, jurisdiction: &str, adapter: Arc<dyn SubmissionAdapter>) {
+        info!("📨 Submission adapter {} registered for {}", adapter.name(), jurisdiction);
+        self.submission_adapters.write().await.insert(jurisdiction.to_string(), adapter);
+    }
+
+    /// Submit a filing to its jurisdiction's regulator
+    ///
+    /// On success the filing moves to `Submitted` and the receipt is stored
+    /// in its metadata under `submission.receipt`. A failed submission
+    /// leaves the filing unchanged. `idempotency_key` is handed to the
+    /// adapter so the regulator can recognize a retried upload.
+    pub async fn submit_filing(&self, filing: &mut GeneratedFiling, idempotency_key: &str) -> Result<SubmissionReceipt> {
+        if !is_submittable(filing) {
+            return Err(SubmissionError::NotSubmittable(filing.status.clone()).into());
+        }
+        if let Some(workflow_id) = &filing.workflow_id {
+            let approved = self.workflows.read().await.get(workflow_id).is_some_and(Workflow::is_approved);
+            if !approved {
+                return Err(SubmissionError::NotApproved(workflow_id.clone()).into());
+            }
+        }
+        let jurisdiction = &filing.request.jurisdiction;
+        let adapter = self
+            .submission_adapters
+            .read()
+            .await
+            .get(jurisdiction)
+            .cloned()
+            .ok_or_else(|| SubmissionError::NoAdapter(jurisdiction.clone()))?;
+
+        let receipt = match adapter.submit(filing, idempotency_key).await {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                warn!("📨 Submission of filing {} through {} failed: {}", filing.filing_id, adapter.name(), e);
+                return Err(e);
+            }
+        };
+
+        filing.metadata.insert(SUBMISSION_RECEIPT_KEY.to_string(), serde_json::to_string(&receipt)?);
+        filing.status = FilingStatus::Submitted;
+        info!("📨 Filing {} submitted: {}", filing.filing_id, receipt.confirmation_id);
+        Ok(receipt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    struct AcceptingAdapter;
+
+    #[async_trait]
+    impl SubmissionAdapter for AcceptingAdapter {
+        fn name(&self) -> &str {
+            "test-regulator"
+        }
+
+        async fn submit(&self, filing: &GeneratedFiling, _idempotency_key: &str) -> Result<SubmissionReceipt> {
+            Ok(SubmissionReceipt {
+                adapter: self.name().to_string(),
+                confirmation_id: format!("0000000000-24-{}", filing.filing_id.simple()),
+                endpoint: "https://regulator.test/submissions".to_string(),
+                test_filing: true,
+                submitted_at: Utc::now(),
+                idempotent_replay: false,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_successful_submission_marks_filing_submitted() {
+        let manager = WorkflowManager::new(WorkflowConfig::default()).await.unwrap();
+        let mut filing = GeneratedFiling::for_tests();
+
+        let error = manager.submit_filing(&mut filing, "submit-1").await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<SubmissionError>(),
+            Some(SubmissionError::NotSubmittable(FilingStatus::Generated))
+        ));
+        filing.status = FilingStatus::Approved;
+
+        let error = manager.submit_filing(&mut filing, "submit-1").await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<SubmissionError>(), Some(SubmissionError::NoAdapter(_))));
+
+        manager.register_submission_adapter("US", Arc::new(AcceptingAdapter)).await;
+        let receipt = manager.submit_filing(&mut filing, "submit-1").await.unwrap();
+        assert!(matches!(filing.status, FilingStatus::Submitted));
+        let stored: SubmissionReceipt = serde_json::from_str(&filing.metadata[SUBMISSION_RECEIPT_KEY]).unwrap();
+        assert_eq!(stored, receipt);
+
+        let error = manager.submit_filing(&mut filing, "submit-1").await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<SubmissionError>(),
+            Some(SubmissionError::NotSubmittable(FilingStatus::Submitted))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_filings_under_a_workflow_need_its_approval() {
+        let manager = WorkflowManager::new(WorkflowConfig::default()).await.unwrap();
+        manager.register_submission_adapter("US", Arc::new(AcceptingAdapter)).await;
+        let mut filing = GeneratedFiling::for_tests();
+        let configuration = WorkflowConfiguration {
+            approvers: vec!["cfo".to_string(), "counsel".to_string()],
+            require_all_approvals: true,
+        };
+        filing.workflow_id = Some(manager.create_workflow(&filing.document, &configuration).await.unwrap());
+
+        assert!(manager.approve_filing(&mut filing, "intern").await.is_err());
+        manager.approve_filing(&mut filing, "cfo").await.unwrap();
+        assert!(matches!(filing.status, FilingStatus::Generated));
+
+        // Setting the status by hand does not bypass the workflow
+        filing.status = FilingStatus::Approved;
+        let error = manager.submit_filing(&mut filing, "submit-1").await.unwrap_err();
+        assert!(matches!(error.downcast_ref::<SubmissionError>(), Some(SubmissionError::NotApproved(_))));
+
+        filing.status = FilingStatus::Generated;
+        manager.approve_filing(&mut filing, "counsel").await.unwrap();
+        assert!(matches!(filing.status, FilingStatus::Approved));
+        manager.submit_filing(&mut filing, "submit-1").await.unwrap();
+    }
+}
@@ -0,0 +1,405 @@
+/*!
+ * XBRL Tagging
+ *
+ * Renders filing data as an XBRL instance document or an Inline XBRL
+ * (iXBRL) HTML document. Template fields are mapped to taxonomy concepts
+ * through a per-template tagging definition, so each form can use its own
+ * taxonomy (US GAAP, IFRS, DEI, ...).
+ */
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{field_pointer, FormTemplate};
+
+const DURATION_CONTEXT: &str = "FD";
+const INSTANT_CONTEXT: &str = "FI";
+
+/// Mapping of a template's fields to taxonomy concepts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XbrlTaggingDefinition {
+    /// Taxonomy name and version, e.g. `us-gaap-2024`
+    pub taxonomy: String,
+    /// Taxonomy entry point referenced from the instance
+    pub schema_ref: String,
+    /// Namespace URIs by prefix for every concept prefix used
+    pub namespaces: BTreeMap<String, String>,
+    /// Identifier scheme of the reporting entity, e.g. `http://www.sec.gov/CIK`
+    pub entity_scheme: String,
+    /// Field holding the entity identifier
+    pub entity_identifier_field: String,
+    pub period_start_field: String,
+    pub period_end_field: String,
+    pub concepts: Vec<XbrlConceptMapping>,
+}
+
+/// Mapping of one template field to a concept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XbrlConceptMapping {
+    pub field_id: String,
+    /// Qualified concept name, e.g. `us-gaap:Revenues`
+    pub concept: String,
+    pub period_type: XbrlPeriodType,
+    /// Unit measure for numeric concepts, e.g. `iso4217:USD`; `None` for text
+    pub unit: Option<String>,
+    pub decimals: Option<i32>,
+    pub required: bool,
+}
+
+/// Period a concept is reported for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum XbrlPeriodType {
+    Instant,
+    Duration,
+}
+
+/// XBRL rendering failures
+#[derive(Debug, thiserror::Error)]
+pub enum XbrlError {
+    #[error("template {template_id} has no XBRL tagging definition")]
+    NoTaggingDefinition { template_id: String },
+    #[error("missing values for required XBRL concepts: {}", concepts.join(", "))]
+    MissingConcepts { concepts: Vec<String> },
+    #[error("concept {concept} is numeric but field {field_id} holds {value}")]
+    NonNumericValue { concept: String, field_id: String, value: String },
+    #[error("concept prefix {prefix} has no namespace in the tagging definition")]
+    UndeclaredPrefix { prefix: String },
+}
+
+/// A value ready to be emitted as a fact
+struct Fact<'a> {
+    mapping: &'a XbrlConceptMapping,
+    value: String,
+}
+
+/// Facts and contexts extracted from filing data
+struct Instance<'a> {
+    definition: &'a XbrlTaggingDefinition,
+    entity_identifier: String,
+    period_start: String,
+    period_end: String,
+    facts: Vec<Fact<'a>>,
+}
+
+impl<'a> Instance<'a> {
+    /// Collect facts, failing if any required concept has no value
+    fn collect(template: &'a FormTemplate, data: &Value) -> Result<Self> {
+        let definition = template.xbrl_tagging.as_ref().ok_or_else(|| XbrlError::NoTaggingDefinition {
+            template_id: template.template_id.clone(),
+        })?;
+
+        for mapping in &definition.concepts {
+            let prefix = mapping.concept.split_once(':').map_or("", |(prefix, _)| prefix);
+            if !definition.namespaces.contains_key(prefix) {
+                return Err(XbrlError::UndeclaredPrefix { prefix: prefix.to_string() }.into());
+            }
+        }
+
+        let lookup = |field_id: &str| -> Option<&Value> {
+            data.pointer(&field_pointer(field_id)).filter(|value| !value.is_null())
+        };
+        let text = |value: &Value| match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+
+        let mut missing = Vec::new();
+        let mut context_value = |field_id: &str| {
+            let value = lookup(field_id).map(text);
+            if value.is_none() {
+                missing.push(format!("context field {}", field_id));
+            }
+            value.unwrap_or_default()
+        };
+        let entity_identifier = context_value(&definition.entity_identifier_field);
+        let period_start = context_value(&definition.period_start_field);
+        let period_end = context_value(&definition.period_end_field);
+
+        let mut facts = Vec::new();
+        for mapping in &definition.concepts {
+            let Some(value) = lookup(&mapping.field_id) else {
+                if mapping.required {
+                    missing.push(mapping.concept.clone());
+                }
+                continue;
+            };
+            if mapping.unit.is_some() && !value.is_number() {
+                return Err(XbrlError::NonNumericValue {
+                    concept: mapping.concept.clone(),
+                    field_id: mapping.field_id.clone(),
+                    value: value.to_string(),
+                }.into());
+            }
+            facts.push(Fact { mapping, value: text(value) });
+        }
+
+        if !missing.is_empty() {
+            return Err(XbrlError::MissingConcepts { concepts: missing }.into());
+        }
+
+        Ok(Self { definition, entity_identifier, period_start, period_end, facts })
+    }
+
+    fn namespace_attributes(&self) -> String {
+        self.definition
+            .namespaces
+            .iter()
+            .map(|(prefix, uri)| format!(" xmlns:{}=\"{}\"", prefix, xml_escape(uri)))
+            .collect()
+    }
+
+    fn units(&self) -> Vec<&'a str> {
+        let mut units: Vec<&str> = self.facts.iter().filter_map(|fact| fact.mapping.unit.as_deref()).collect();
+        units.sort_unstable();
+        units.dedup();
+        units
+    }
+
+    /// Contexts and units shared by both output forms
+    fn resources(&self) -> String {
+        let entity = format!(
+            "<xbrli:entity><xbrli:identifier scheme=\"{}\">{}</xbrli:identifier></xbrli:entity>",
+            xml_escape(&self.definition.entity_scheme),
+            xml_escape(&self.entity_identifier)
+        );
+        let mut out = format!(
+            "<xbrli:context id=\"{}\">{}<xbrli:period><xbrli:startDate>{}</xbrli:startDate><xbrli:endDate>{}</xbrli:endDate></xbrli:period></xbrli:context>\n",
+            DURATION_CONTEXT, entity, xml_escape(&self.period_start), xml_escape(&self.period_end)
+        );
+        out.push_str(&format!(
+            "<xbrli:context id=\"{}\">{}<xbrli:period><xbrli:instant>{}</xbrli:instant></xbrli:period></xbrli:context>\n",
+            INSTANT_CONTEXT, entity, xml_escape(&self.period_end)
+        ));
+        for unit in self.units() {
+            out.push_str(&format!(
+                "<xbrli:unit id=\"{}\"><xbrli:measure>{}</xbrli:measure></xbrli:unit>\n",
+                unit_id(unit), xml_escape(unit)
+            ));
+        }
+        out
+    }
+}
+
+/// Render an XBRL 2.1 instance document
+pub fn render_xbrl(template: &FormTemplate, data: &Value) -> Result<String> {
+    let instance = Instance::collect(template, data)?;
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<xbrli:xbrl xmlns:xbrli=\"http://www.xbrl.org/2003/instance\" xmlns:link=\"http://www.xbrl.org/2003/linkbase\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" xmlns:iso4217=\"http://www.xbrl.org/2003/iso4217\"{}>\n",
+        instance.namespace_attributes()
+    ));
+    out.push_str(&format!(
+        "<link:schemaRef xlink:type=\"simple\" xlink:href=\"{}\"/>\n",
+        xml_escape(&instance.definition.schema_ref)
+    ));
+    out.push_str(&instance.resources());
+
+    for fact in &instance.facts {
+        let mapping = fact.mapping;
+        let mut attributes = format!("contextRef=\"{}\"", context_id(mapping.period_type));
+        if let Some(unit) = &mapping.unit {
+            attributes.push_str(&format!(" unitRef=\"{}\" decimals=\"{}\"", unit_id(unit), decimals(mapping)));
+        }
+        out.push_str(&format!(
+            "<{concept} {attributes}>{value}</{concept}>\n",
+            concept = mapping.concept,
+            attributes = attributes,
+            value = xml_escape(&fact.value)
+        ));
+    }
+
+    out.push_str("</xbrli:xbrl>\n");
+    Ok(out)
+}
+
+/// Render an Inline XBRL 1.1 document with every fact tagged in place
+pub fn render_ixbrl(template: &FormTemplate, data: &Value) -> Result<String> {
+    let instance = Instance::collect(template, data)?;
+    let labels: BTreeMap<&str, &str> = template
+        .fields
+        .iter()
+        .map(|field| (field.field_id.as_str(), field.name.as_str()))
+        .collect();
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:ix=\"http://www.xbrl.org/2013/inlineXBRL\" xmlns:ixt=\"http://www.xbrl.org/inlineXBRL/transformation/2020-02-12\" xmlns:xbrli=\"http://www.xbrl.org/2003/instance\" xmlns:link=\"http://www.xbrl.org/2003/linkbase\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" xmlns:iso4217=\"http://www.xbrl.org/2003/iso4217\"{}>\n",
+        instance.namespace_attributes()
+    ));
+    out.push_str(&format!("<head><title>{}</title></head>\n<body>\n", xml_escape(&template.name)));
+    out.push_str("<div style=\"display:none\"><ix:header><ix:references>");
+    out.push_str(&format!(
+        "<link:schemaRef xlink:type=\"simple\" xlink:href=\"{}\"/>",
+        xml_escape(&instance.definition.schema_ref)
+    ));
+    out.push_str("</ix:references><ix:resources>\n");
+    out.push_str(&instance.resources());
+    out.push_str("</ix:resources></ix:header></div>\n");
+
+    out.push_str(&format!("<h1>{}</h1>\n<table>\n", xml_escape(&template.name)));
+    for fact in &instance.facts {
+        let mapping = fact.mapping;
+        let tagged = match &mapping.unit {
+            // Inline XBRL shows the magnitude and carries a negative value's sign as an attribute
+            Some(unit) => {
+                let (sign, magnitude) = match fact.value.strip_prefix('-') {
+                    Some(magnitude) => (" sign=\"-\"", magnitude),
+                    None => ("", fact.value.as_str()),
+                };
+                format!(
+                    "<ix:nonFraction name=\"{}\" contextRef=\"{}\" unitRef=\"{}\" decimals=\"{}\"{} format=\"ixt:num-dot-decimal\">{}</ix:nonFraction>",
+                    mapping.concept, context_id(mapping.period_type), unit_id(unit), decimals(mapping), sign, xml_escape(magnitude)
+                )
+            }
+            None => format!(
+                "<ix:nonNumeric name=\"{}\" contextRef=\"{}\">{}</ix:nonNumeric>",
+                mapping.concept, context_id(mapping.period_type), xml_escape(&fact.value)
+            ),
+        };
+        let label = labels.get(mapping.field_id.as_str()).copied().unwrap_or(&mapping.field_id);
+        out.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", xml_escape(label), tagged));
+    }
+    out.push_str("</table>\n</body>\n</html>\n");
+    Ok(out)
+}
+
+fn context_id(period_type: XbrlPeriodType) -> &'static str {
+    match period_type {
+        XbrlPeriodType::Duration => DURATION_CONTEXT,
+        XbrlPeriodType::Instant => INSTANT_CONTEXT,
+    }
+}
+
+/// Unit id derived from the whole measure, so `iso4217:USD` and `other:USD` get distinct ids
+///
+/// Letters and digits are kept and every other character becomes `_` and its
+/// hex code, which keeps ids unique and valid as XML names.
+fn unit_id(measure: &str) -> String {
+    let mut id = String::from("U_");
+    for c in measure.chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c);
+        } else {
+            let mut buffer = [0u8; 4];
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                id.push_str(&format!("_{:02X}", byte));
+            }
+        }
+    }
+    id
+}
+
+fn decimals(mapping: &XbrlConceptMapping) -> String {
+    mapping.decimals.map_or_else(|| "INF".to_string(), |decimals| decimals.to_string())
+}
+
+pub(crate) fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tagged_template() -> FormTemplate {
+        let concept = |field_id: &str, concept: &str, unit: Option<&str>, required: bool| XbrlConceptMapping {
+            field_id: field_id.to_string(),
+            concept: concept.to_string(),
+            period_type: if unit.is_some() { XbrlPeriodType::Duration } else { XbrlPeriodType::Instant },
+            unit: unit.map(str::to_string),
+            decimals: unit.map(|_| -3),
+            required,
+        };
+
+        let mut template = FormTemplate::for_tests("sec-10-k", Vec::new());
+        template.xbrl_tagging = Some(XbrlTaggingDefinition {
+            taxonomy: "us-gaap-2024".to_string(),
+            schema_ref: "https://xbrl.fasb.org/us-gaap/2024/entire/us-gaap-entryPoint-std-2024.xsd".to_string(),
+            namespaces: BTreeMap::from([
+                ("us-gaap".to_string(), "http://fasb.org/us-gaap/2024".to_string()),
+                ("dei".to_string(), "http://xbrl.sec.gov/dei/2024".to_string()),
+            ]),
+            entity_scheme: "http://www.sec.gov/CIK".to_string(),
+            entity_identifier_field: "filer.cik".to_string(),
+            period_start_field: "period.start".to_string(),
+            period_end_field: "period.end".to_string(),
+            concepts: vec![
+                concept("financials.revenue", "us-gaap:Revenues", Some("iso4217:USD"), true),
+                concept("financials.net_income", "us-gaap:NetIncomeLoss", Some("iso4217:USD"), true),
+                concept("filer.name", "dei:EntityRegistrantName", None, false),
+            ],
+        });
+        template
+    }
+
+    fn data() -> Value {
+        serde_json::json!({
+            "filer": {"cik": "0000320193", "name": "Example & Co"},
+            "period": {"start": "2024-01-01", "end": "2024-12-31"},
+            "financials": {"revenue": 391035000, "net_income": 93736000},
+        })
+    }
+
+    #[test]
+    fn test_renders_tagged_facts() {
+        let template = tagged_template();
+
+        let xbrl = render_xbrl(&template, &data()).unwrap();
+        assert!(xbrl.contains("<us-gaap:Revenues contextRef=\"FD\" unitRef=\"U_iso4217_3AUSD\" decimals=\"-3\">391035000</us-gaap:Revenues>"));
+        assert!(xbrl.contains("<dei:EntityRegistrantName contextRef=\"FI\">Example &amp; Co</dei:EntityRegistrantName>"));
+        assert!(xbrl.contains("<xbrli:identifier scheme=\"http://www.sec.gov/CIK\">0000320193</xbrli:identifier>"));
+        assert_eq!(xbrl.matches("<xbrli:unit ").count(), 1);
+
+        let ixbrl = render_ixbrl(&template, &data()).unwrap();
+        assert!(ixbrl.contains("<ix:nonFraction name=\"us-gaap:NetIncomeLoss\""));
+        assert!(ixbrl.contains("<ix:nonNumeric name=\"dei:EntityRegistrantName\""));
+    }
+
+    #[test]
+    fn test_missing_required_concepts_are_listed() {
+        let mut data = data();
+        data["financials"] = serde_json::json!({});
+        data["filer"].as_object_mut().unwrap().remove("name");
+
+        let error = render_xbrl(&tagged_template(), &data).unwrap_err();
+        match error.downcast_ref::<XbrlError>() {
+            Some(XbrlError::MissingConcepts { concepts }) => {
+                assert_eq!(concepts, &vec!["us-gaap:Revenues".to_string(), "us-gaap:NetIncomeLoss".to_string()]);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_negative_facts_carry_sign_attribute() {
+        let mut data = data();
+        data["financials"]["net_income"] = serde_json::json!(-93736000);
+
+        let ixbrl = render_ixbrl(&tagged_template(), &data).unwrap();
+        assert!(ixbrl.contains("decimals=\"-3\" sign=\"-\" format=\"ixt:num-dot-decimal\">93736000</ix:nonFraction>"));
+        assert!(!ixbrl.contains(">-93736000<"));
+
+        let xbrl = render_xbrl(&tagged_template(), &data).unwrap();
+        assert!(xbrl.contains(">-93736000</us-gaap:NetIncomeLoss>"));
+    }
+
+    #[test]
+    fn test_unit_ids_are_distinct_per_measure() {
+        let mut template = tagged_template();
+        template.xbrl_tagging.as_mut().unwrap().concepts[1].unit = Some("us-gaap:USD".to_string());
+
+        let xbrl = render_xbrl(&template, &data()).unwrap();
+        assert_eq!(xbrl.matches("<xbrli:unit ").count(), 2);
+        assert!(xbrl.contains("<xbrli:unit id=\"U_iso4217_3AUSD\"><xbrli:measure>iso4217:USD</xbrli:measure>"));
+        assert!(xbrl.contains("<xbrli:unit id=\"U_us_2Dgaap_3AUSD\"><xbrli:measure>us-gaap:USD</xbrli:measure>"));
+        assert_ne!(unit_id("a:b_c"), unit_id("a_b:c"));
+    }
+}
@@ -0,0 +1,253 @@
+//! Translation cache
+//!
+//! Keeps completed translations keyed by source text and target language so
+//! repeated content does not hit paid translation APIs twice. Entries can be
+//! persisted to disk and reloaded across restarts.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use unic_langid::LanguageIdentifier;
+
+/// On-disk snapshot format version
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Translation cache configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationCacheConfig {
+    /// Maximum number of cached translations
+    pub max_entries: usize,
+    /// Maximum age of an entry before it is considered stale
+    pub max_age_seconds: i64,
+    /// File used to persist the cache across restarts
+    pub persistence_path: Option<PathBuf>,
+}
+
+impl Default for TranslationCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 100_000,
+            max_age_seconds: 30 * 24 * 60 * 60,
+            persistence_path: Some(PathBuf::from("cache/translations.json")),
+        }
+    }
+}
+
+/// A single cached translation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedTranslation {
+    pub original_text: String,
+    pub target_language: String,
+    pub translated_text: String,
+    pub confidence_score: f64,
+    pub cached_at: DateTime<Utc>,
+}
+
+/// Serialized cache contents
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheSnapshot {
+    version: u32,
+    entries: Vec<CachedTranslation>,
+}
+
+/// Cache status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheStatus {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_rate: f64,
+}
+
+/// Translation cache
+pub struct TranslationCache {
+    config: TranslationCacheConfig,
+    entries: RwLock<HashMap<(String, String), CachedTranslation>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl TranslationCache {
+    pub async fn new(config: TranslationCacheConfig) -> Result<Self> {
+        Ok(Self {
+            config,
+            entries: RwLock::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Look up a cached translation, ignoring entries past their max age
+    pub async fn get(&self, text: &str, target_language: &LanguageIdentifier) -> Option<CachedTranslation> {
+        let key = (text.to_string(), target_language.to_string());
+        let entries = self.entries.read().await;
+
+        match entries.get(&key) {
+            Some(entry) if !self.is_expired(entry, Utc::now()) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.clone())
+            }
+            _ => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Store a translation, evicting the oldest entry when the cache is full
+    pub async fn insert(&self, entry: CachedTranslation) {
+        let mut entries = self.entries.write().await;
+        let key = (entry.original_text.clone(), entry.target_language.clone());
+
+        if !entries.contains_key(&key) && entries.len() >= self.config.max_entries {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.cached_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(key, entry);
+    }
+
+    /// Write all live entries to `path`
+    ///
+    /// The snapshot is written to a temporary file first and then renamed so
+    /// a crash mid-write never leaves a truncated cache behind.
+    pub async fn persist_to(&self, path: &Path) -> Result<usize> {
+        let now = Utc::now();
+        let entries: Vec<CachedTranslation> = self
+            .entries
+            .read()
+            .await
+            .values()
+            .filter(|entry| !self.is_expired(entry, now))
+            .cloned()
+            .collect();
+
+        let snapshot = CacheSnapshot {
+            version: SNAPSHOT_VERSION,
+            entries,
+        };
+        let bytes = serde_json::to_vec(&snapshot)?;
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+        }
+
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, &bytes)
+            .await
+            .with_context(|| format!("failed to write translation cache to {}", tmp_path.display()))?;
+        tokio::fs::rename(&tmp_path, path).await?;
+
+        info!("💾 Persisted {} cached translations to {}", snapshot.entries.len(), path.display());
+        Ok(snapshot.entries.len())
+    }
+
+    /// Load entries from `path`, dropping any older than the configured max age
+    ///
+    /// Returns the number of entries restored.
+    pub async fn load_from(&self, path: &Path) -> Result<usize> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("failed to read translation cache from {}", path.display()))?;
+        let snapshot: CacheSnapshot = serde_json::from_slice(&bytes)?;
+
+        if snapshot.version != SNAPSHOT_VERSION {
+            warn!("Ignoring translation cache with unsupported version {}", snapshot.version);
+            return Ok(0);
+        }
+
+        let now = Utc::now();
+        let total = snapshot.entries.len();
+        let mut loaded = 0;
+
+        for entry in snapshot.entries {
+            if self.is_expired(&entry, now) {
+                continue;
+            }
+            self.insert(entry).await;
+            loaded += 1;
+        }
+
+        if loaded < total {
+            info!("🗑️ Dropped {} stale cached translations on load", total - loaded);
+        }
+        Ok(loaded)
+    }
+
+    pub async fn get_status(&self) -> Result<CacheStatus> {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let lookups = hits + misses;
+
+        Ok(CacheStatus {
+            entries: self.entries.read().await.len(),
+            hits,
+            misses,
+            hit_rate: if lookups == 0 { 0.0 } else { hits as f64 / lookups as f64 },
+        })
+    }
+
+    fn is_expired(&self, entry: &CachedTranslation, now: DateTime<Utc>) -> bool {
+        now - entry.cached_at > Duration::seconds(self.config.max_age_seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str, cached_at: DateTime<Utc>) -> CachedTranslation {
+        CachedTranslation {
+            original_text: text.to_string(),
+            target_language: "fr".to_string(),
+            translated_text: format!("{} (fr)", text),
+            confidence_score: 0.9,
+            cached_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_persist_and_load_drops_stale_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translations.json");
+        let config = TranslationCacheConfig {
+            persistence_path: Some(path.clone()),
+            ..TranslationCacheConfig::default()
+        };
+
+        let cache = TranslationCache::new(config.clone()).await.unwrap();
+        cache.insert(entry("data controller", Utc::now())).await;
+        cache.insert(entry("data processor", Utc::now() - Duration::hours(2))).await;
+        assert_eq!(cache.persist_to(&path).await.unwrap(), 2);
+
+        // Restart with a tighter max age so the older entry is now stale
+        let reloaded = TranslationCache::new(TranslationCacheConfig {
+            max_age_seconds: 3600,
+            ..config
+        }).await.unwrap();
+        assert_eq!(reloaded.load_from(&path).await.unwrap(), 1);
+        assert!(reloaded.get("data controller", &"fr".parse().unwrap()).await.is_some());
+        assert!(reloaded.get("data processor", &"fr".parse().unwrap()).await.is_none());
+    }
+}
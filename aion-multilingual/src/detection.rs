@@ -0,0 +1,361 @@
+//! Language detection
+//!
+//! Whole-text detection plus segmentation of mixed-script, multi-language
+//! documents such as English filings quoting Chinese regulatory citations.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+/// Language detection configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageDetectionConfig {
+    /// Confidence below which a detection is considered unreliable
+    pub min_confidence: f64,
+    /// Letters a segment needs before its detection is taken at full confidence
+    pub min_segment_letters: usize,
+    /// Language assumed when nothing can be detected
+    pub default_language: String,
+}
+
+impl Default for LanguageDetectionConfig {
+    fn default() -> Self {
+        Self {
+            min_confidence: 0.5,
+            min_segment_letters: 12,
+            default_language: "en".to_string(),
+        }
+    }
+}
+
+/// Detailed detection result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedLanguage {
+    pub language: LanguageIdentifier,
+    pub confidence: f64,
+    pub script: Option<String>,
+    pub is_reliable: bool,
+}
+
+/// A contiguous run of text in a single language
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedSegment {
+    /// Byte offset of the segment start
+    pub start: usize,
+    /// Byte offset one past the segment end
+    pub end: usize,
+    pub language: LanguageIdentifier,
+    pub confidence: f64,
+    pub script: String,
+    /// Whether the language was taken from a neighbouring segment
+    pub inherited: bool,
+}
+
+/// Script classes used for segmentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScriptClass {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+    Hangul,
+    Cjk,
+    Other,
+}
+
+impl ScriptClass {
+    /// Script of a letter, or `None` for digits, whitespace and punctuation
+    fn of(ch: char) -> Option<Self> {
+        if !ch.is_alphabetic() {
+            return None;
+        }
+        Some(match ch as u32 {
+            0x0041..=0x024F | 0x1E00..=0x1EFF => ScriptClass::Latin,
+            0x0370..=0x03FF => ScriptClass::Greek,
+            0x0400..=0x052F => ScriptClass::Cyrillic,
+            0x0590..=0x05FF => ScriptClass::Hebrew,
+            0x0600..=0x06FF | 0x0750..=0x077F | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => ScriptClass::Arabic,
+            0x0900..=0x097F => ScriptClass::Devanagari,
+            0x0E00..=0x0E7F => ScriptClass::Thai,
+            0x1100..=0x11FF | 0xAC00..=0xD7AF => ScriptClass::Hangul,
+            0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => ScriptClass::Cjk,
+            _ => ScriptClass::Other,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ScriptClass::Latin => "Latin",
+            ScriptClass::Cyrillic => "Cyrillic",
+            ScriptClass::Greek => "Greek",
+            ScriptClass::Arabic => "Arabic",
+            ScriptClass::Hebrew => "Hebrew",
+            ScriptClass::Devanagari => "Devanagari",
+            ScriptClass::Thai => "Thai",
+            ScriptClass::Hangul => "Hangul",
+            ScriptClass::Cjk => "CJK",
+            ScriptClass::Other => "Other",
+        }
+    }
+}
+
+/// Sentence terminators, including full-width CJK punctuation
+fn is_sentence_end(ch: char) -> bool {
+    matches!(ch, '.' | '!' | '?' | '。' | '！' | '？' | '\n')
+}
+
+/// A same-script, same-sentence run before language detection
+struct Run {
+    start: usize,
+    end: usize,
+    script: ScriptClass,
+    letters: usize,
+}
+
+/// Language detector
+pub struct LanguageDetector {
+    config: LanguageDetectionConfig,
+}
+
+impl LanguageDetector {
+    pub async fn new(config: LanguageDetectionConfig) -> Result<Self> {
+        Ok(Self { config })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Detect the dominant language of `text`
+    pub async fn detect_language(&self, text: &str) -> Result<LanguageIdentifier> {
+        Ok(self.detect_language_detailed(text).await?.language)
+    }
+
+    /// Detect the dominant language of `text` with confidence and script
+    pub async fn detect_language_detailed(&self, text: &str) -> Result<DetectedLanguage> {
+        let detected = match whatlang::detect(text) {
+            Some(info) => DetectedLanguage {
+                language: to_language_identifier(info.lang().code(), &self.config.default_language),
+                confidence: info.confidence(),
+                script: Some(info.script().name().to_string()),
+                is_reliable: info.is_reliable(),
+            },
+            None => DetectedLanguage {
+                language: self.default_language(),
+                confidence: 0.0,
+                script: None,
+                is_reliable: false,
+            },
+        };
+        Ok(detected)
+    }
+
+    /// Split `text` into per-language segments
+    ///
+    /// Text is cut at script changes and sentence boundaries, each piece is
+    /// detected independently, and short or low-confidence pieces take the
+    /// language of the surrounding text. Adjacent segments in the same
+    /// language are merged. Segments cover the whole input.
+    pub fn detect_segments(&self, text: &str) -> Vec<DetectedSegment> {
+        let runs = split_runs(text);
+        let mut segments: Vec<DetectedSegment> = runs
+            .iter()
+            .map(|run| {
+                let slice = &text[run.start..run.end];
+                let (language, confidence) = match whatlang::detect(slice) {
+                    Some(info) => (
+                        to_language_identifier(info.lang().code(), &self.config.default_language),
+                        info.confidence() * self.length_factor(run),
+                    ),
+                    None => (self.default_language(), 0.0),
+                };
+                DetectedSegment {
+                    start: run.start,
+                    end: run.end,
+                    language,
+                    confidence,
+                    script: run.script.name().to_string(),
+                    inherited: false,
+                }
+            })
+            .collect();
+
+        self.inherit_weak_segments(&mut segments);
+        merge_same_language(segments)
+    }
+
+    /// Discount detector confidence for runs too short to judge reliably
+    ///
+    /// A single Han character is detected as Chinese with full confidence by
+    /// script alone, which says little about the language around it.
+    fn length_factor(&self, run: &Run) -> f64 {
+        let required = self.config.min_segment_letters.max(1);
+        (run.letters as f64 / required as f64).min(1.0)
+    }
+
+    /// Give low-confidence segments the language of a confident neighbour
+    fn inherit_weak_segments(&self, segments: &mut [DetectedSegment]) {
+        let weak: Vec<bool> = segments
+            .iter()
+            .map(|segment| segment.confidence < self.config.min_confidence)
+            .collect();
+
+        for index in 0..segments.len() {
+            if !weak[index] {
+                continue;
+            }
+            let previous = (0..index).rev().find(|&i| !weak[i]);
+            let next = (index + 1..segments.len()).find(|&i| !weak[i]);
+            if let Some(source) = previous.or(next) {
+                segments[index].language = segments[source].language.clone();
+                segments[index].confidence = segments[source].confidence;
+                segments[index].inherited = true;
+            }
+        }
+    }
+
+    fn default_language(&self) -> LanguageIdentifier {
+        self.config.default_language.parse().unwrap_or_default()
+    }
+}
+
+/// Cut text into runs of one script within one sentence
+///
+/// Neutral characters (digits, spaces, punctuation) stay with the run they
+/// follow; leading neutral characters join the first run.
+fn split_runs(text: &str) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    let mut current: Option<Run> = None;
+    let mut sentence_ended = false;
+
+    for (offset, ch) in text.char_indices() {
+        let end = offset + ch.len_utf8();
+        match (ScriptClass::of(ch), current.as_mut()) {
+            (Some(script), Some(run)) if run.script == script && !sentence_ended => {
+                run.end = end;
+                run.letters += 1;
+            }
+            (Some(script), Some(run)) if run.letters == 0 => {
+                run.script = script;
+                run.end = end;
+                run.letters = 1;
+                sentence_ended = false;
+            }
+            (Some(script), _) => {
+                let start = current.as_ref().map(|run| run.end).unwrap_or(0);
+                if let Some(run) = current.take() {
+                    runs.push(run);
+                }
+                current = Some(Run { start, end, script, letters: 1 });
+                sentence_ended = false;
+            }
+            (None, Some(run)) => {
+                run.end = end;
+                sentence_ended |= is_sentence_end(ch);
+            }
+            (None, None) => {
+                current = Some(Run { start: 0, end, script: ScriptClass::Other, letters: 0 });
+            }
+        }
+    }
+
+    if let Some(run) = current {
+        runs.push(run);
+    }
+    runs
+}
+
+fn merge_same_language(segments: Vec<DetectedSegment>) -> Vec<DetectedSegment> {
+    let mut merged: Vec<DetectedSegment> = Vec::with_capacity(segments.len());
+    for segment in segments {
+        match merged.last_mut() {
+            Some(last) if last.language == segment.language => {
+                last.end = segment.end;
+                last.confidence = last.confidence.max(segment.confidence);
+                last.inherited &= segment.inherited;
+                if last.script != segment.script {
+                    last.script = "Mixed".to_string();
+                }
+            }
+            _ => merged.push(segment),
+        }
+    }
+    merged
+}
+
+/// Map an ISO 639-3 code from the detector to a BCP 47 identifier
+fn to_language_identifier(code: &str, default_language: &str) -> LanguageIdentifier {
+    let short = match code {
+        "eng" => "en",
+        "cmn" => "zh",
+        "spa" => "es",
+        "fra" => "fr",
+        "deu" => "de",
+        "ita" => "it",
+        "por" => "pt",
+        "nld" => "nl",
+        "rus" => "ru",
+        "ukr" => "uk",
+        "pol" => "pl",
+        "jpn" => "ja",
+        "kor" => "ko",
+        "ara" => "ar",
+        "heb" => "he",
+        "pes" => "fa",
+        "hin" => "hi",
+        "tha" => "th",
+        "vie" => "vi",
+        "tur" => "tr",
+        "ell" => "el",
+        "swe" => "sv",
+        "dan" => "da",
+        "fin" => "fi",
+        "ces" => "cs",
+        "hun" => "hu",
+        "ron" => "ro",
+        "ind" => "id",
+        other => other,
+    };
+    short
+        .parse()
+        .or_else(|_| default_language.parse())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_detect_segments_splits_english_and_chinese() {
+        let detector = LanguageDetector::new(LanguageDetectionConfig::default()).await.unwrap();
+        let text = "The company must comply with the personal information protection rules. \
+                    个人信息处理者应当采取必要措施保障所处理的个人信息的安全。";
+
+        let segments = detector.detect_segments(text);
+        let languages: Vec<String> = segments.iter().map(|s| s.language.to_string()).collect();
+
+        assert_eq!(languages, vec!["en", "zh"]);
+        assert_eq!(segments[0].start, 0);
+        assert_eq!(segments[1].end, text.len());
+        assert!(text[segments[1].start..segments[1].end].contains("个人信息"));
+    }
+
+    #[tokio::test]
+    async fn test_short_segment_inherits_surrounding_language() {
+        let detector = LanguageDetector::new(LanguageDetectionConfig::default()).await.unwrap();
+        let text = "Processing under Article 6 of the GDPR (法) requires the controller to document \
+                    a lawful basis before any personal data is collected.";
+
+        let segments = detector.detect_segments(text);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].language.to_string(), "en");
+    }
+}
@@ -0,0 +1,589 @@
+//! Locale-aware formatting
+//!
+//! CLDR plural categories and ICU-style `{count, plural, ...}` /
+//! `{gender, select, ...}` message resolution, plus monetary amounts and
+//! measurements in the locale's number format and unit system.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use rust_decimal::RoundingStrategy;
+use serde::{Deserialize, Serialize};
+
+use crate::{CurrencyPosition, DigitGrouping, Locale, NumberFormat};
+
+pub use rust_decimal::Decimal;
+
+/// Countries whose everyday measurements are imperial (US customary)
+const IMPERIAL_COUNTRIES: &[&str] = &["US", "LR", "MM"];
+
+/// Formatting configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormattingConfig {
+    /// Language used when a locale has no plural rules of its own
+    pub fallback_language: String,
+}
+
+impl Default for FormattingConfig {
+    fn default() -> Self {
+        Self {
+            fallback_language: "en".to_string(),
+        }
+    }
+}
+
+/// CLDR plural categories
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+/// Message variants for each plural category
+///
+/// `#` in a form is replaced with the count. Exact matches (`=0`) take
+/// precedence over categories, and missing categories fall back to `other`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluralForms {
+    pub exact: HashMap<i64, String>,
+    pub zero: Option<String>,
+    pub one: Option<String>,
+    pub two: Option<String>,
+    pub few: Option<String>,
+    pub many: Option<String>,
+    pub other: String,
+}
+
+impl PluralForms {
+    pub fn new(one: &str, other: &str) -> Self {
+        Self {
+            one: Some(one.to_string()),
+            other: other.to_string(),
+            ..Self::default()
+        }
+    }
+
+    fn form_for(&self, count: i64, category: PluralCategory) -> &str {
+        if let Some(exact) = self.exact.get(&count) {
+            return exact;
+        }
+        let form = match category {
+            PluralCategory::Zero => &self.zero,
+            PluralCategory::One => &self.one,
+            PluralCategory::Two => &self.two,
+            PluralCategory::Few => &self.few,
+            PluralCategory::Many => &self.many,
+            PluralCategory::Other => return &self.other,
+        };
+        form.as_deref().unwrap_or(&self.other)
+    }
+}
+
+/// Select the CLDR cardinal plural category for an integer count
+pub fn plural_category(language: &str, count: i64) -> PluralCategory {
+    let n = count.unsigned_abs();
+    let n10 = n % 10;
+    let n100 = n % 100;
+
+    match language {
+        "ar" => match n {
+            0 => PluralCategory::Zero,
+            1 => PluralCategory::One,
+            2 => PluralCategory::Two,
+            _ if (3..=10).contains(&n100) => PluralCategory::Few,
+            _ if (11..=99).contains(&n100) => PluralCategory::Many,
+            _ => PluralCategory::Other,
+        },
+        "pl" => match n {
+            1 => PluralCategory::One,
+            _ if (2..=4).contains(&n10) && !(12..=14).contains(&n100) => PluralCategory::Few,
+            _ => PluralCategory::Many,
+        },
+        "ru" | "uk" | "be" => {
+            if n10 == 1 && n100 != 11 {
+                PluralCategory::One
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        }
+        "cs" | "sk" => match n {
+            1 => PluralCategory::One,
+            2..=4 => PluralCategory::Few,
+            _ => PluralCategory::Other,
+        },
+        "he" => match n {
+            1 => PluralCategory::One,
+            2 => PluralCategory::Two,
+            _ => PluralCategory::Other,
+        },
+        "fr" | "pt-BR" => match n {
+            0 | 1 => PluralCategory::One,
+            _ => PluralCategory::Other,
+        },
+        "zh" | "ja" | "ko" | "th" | "vi" | "id" | "ms" => PluralCategory::Other,
+        _ => match n {
+            1 => PluralCategory::One,
+            _ => PluralCategory::Other,
+        },
+    }
+}
+
+/// Measurement system used by a locale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MeasurementSystem {
+    Metric,
+    Imperial,
+}
+
+impl MeasurementSystem {
+    pub fn for_locale(locale: &Locale) -> Self {
+        match &locale.country {
+            Some(country) if IMPERIAL_COUNTRIES.contains(&country.to_uppercase().as_str()) => MeasurementSystem::Imperial,
+            _ => MeasurementSystem::Metric,
+        }
+    }
+}
+
+/// Unit of a measured value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Unit {
+    Millimeter,
+    Centimeter,
+    Meter,
+    Kilometer,
+    Gram,
+    Kilogram,
+    Milliliter,
+    Liter,
+    Celsius,
+    Inch,
+    Foot,
+    Mile,
+    Ounce,
+    Pound,
+    FluidOunce,
+    Gallon,
+    Fahrenheit,
+}
+
+impl Unit {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Unit::Millimeter => "mm",
+            Unit::Centimeter => "cm",
+            Unit::Meter => "m",
+            Unit::Kilometer => "km",
+            Unit::Gram => "g",
+            Unit::Kilogram => "kg",
+            Unit::Milliliter => "ml",
+            Unit::Liter => "l",
+            Unit::Celsius => "°C",
+            Unit::Inch => "in",
+            Unit::Foot => "ft",
+            Unit::Mile => "mi",
+            Unit::Ounce => "oz",
+            Unit::Pound => "lb",
+            Unit::FluidOunce => "fl oz",
+            Unit::Gallon => "gal",
+            Unit::Fahrenheit => "°F",
+        }
+    }
+
+    pub fn system(self) -> MeasurementSystem {
+        match self {
+            Unit::Inch | Unit::Foot | Unit::Mile | Unit::Ounce | Unit::Pound | Unit::FluidOunce | Unit::Gallon
+            | Unit::Fahrenheit => MeasurementSystem::Imperial,
+            _ => MeasurementSystem::Metric,
+        }
+    }
+
+    /// Express `value` of this unit in its counterpart of the other system
+    fn convert(self, value: f64) -> (f64, Unit) {
+        match self {
+            Unit::Millimeter => (value / 25.4, Unit::Inch),
+            Unit::Centimeter => (value / 2.54, Unit::Inch),
+            Unit::Meter => (value / 0.3048, Unit::Foot),
+            Unit::Kilometer => (value / 1.609_344, Unit::Mile),
+            Unit::Gram => (value / 28.349_523_125, Unit::Ounce),
+            Unit::Kilogram => (value / 0.453_592_37, Unit::Pound),
+            Unit::Milliliter => (value / 29.573_529_562_5, Unit::FluidOunce),
+            Unit::Liter => (value / 3.785_411_784, Unit::Gallon),
+            Unit::Celsius => (value * 9.0 / 5.0 + 32.0, Unit::Fahrenheit),
+            Unit::Inch => (value * 2.54, Unit::Centimeter),
+            Unit::Foot => (value * 0.3048, Unit::Meter),
+            Unit::Mile => (value * 1.609_344, Unit::Kilometer),
+            Unit::Ounce => (value * 28.349_523_125, Unit::Gram),
+            Unit::Pound => (value * 0.453_592_37, Unit::Kilogram),
+            Unit::FluidOunce => (value * 29.573_529_562_5, Unit::Milliliter),
+            Unit::Gallon => (value * 3.785_411_784, Unit::Liter),
+            Unit::Fahrenheit => ((value - 32.0) * 5.0 / 9.0, Unit::Celsius),
+        }
+    }
+}
+
+/// Formatting service
+pub struct FormattingService {
+    config: FormattingConfig,
+}
+
+impl FormattingService {
+    pub async fn new(config: FormattingConfig) -> Result<Self> {
+        Ok(Self { config })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Format `count` with the plural form the locale's rules select
+    pub fn format_plural(&self, count: i64, forms: &PluralForms, locale: &Locale) -> String {
+        let language = plural_language(locale, &self.config.fallback_language);
+        let category = plural_category(&language, count);
+        forms.form_for(count, category).replace('#', &count.to_string())
+    }
+
+    /// Resolve ICU-style plural and select placeholders against `args`
+    pub fn format_message(&self, message: &str, args: &serde_json::Value, locale: &Locale) -> String {
+        let language = plural_language(locale, &self.config.fallback_language);
+        format_message(message, args, &language)
+    }
+
+    /// Format a monetary amount with the locale's separators, grouping and currency symbol
+    ///
+    /// The amount is rounded half away from zero to the locale's
+    /// `currency_decimals` without going through floating point.
+    pub fn format_currency(&self, amount: Decimal, locale: &Locale) -> String {
+        let format = &locale.number_format;
+        let rounded = amount.round_dp_with_strategy(format.currency_decimals, RoundingStrategy::MidpointAwayFromZero);
+        let digits = rounded.abs().to_string();
+        let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+        let fraction = format!("{:0<width$}", fraction, width = format.currency_decimals as usize);
+
+        let number = format_digits(integer, &fraction, format);
+        let symbol = &format.currency_symbol;
+        let sign = if rounded.is_sign_negative() && !rounded.is_zero() { "-" } else { "" };
+
+        match format.currency_position {
+            CurrencyPosition::Before => format!("{}{}{}", sign, symbol, number),
+            CurrencyPosition::BeforeWithSpace => format!("{}{}\u{a0}{}", sign, symbol, number),
+            CurrencyPosition::After => format!("{}{}{}", sign, number, symbol),
+            CurrencyPosition::AfterWithSpace => format!("{}{}\u{a0}{}", sign, number, symbol),
+        }
+    }
+
+    /// Format a measurement in the locale's unit system, converting between metric and imperial
+    ///
+    /// Values are shown with at most two decimals and the unit symbol.
+    pub fn format_measurement(&self, value: f64, unit: Unit, locale: &Locale) -> String {
+        let (value, unit) = if unit.system() == MeasurementSystem::for_locale(locale) {
+            (value, unit)
+        } else {
+            unit.convert(value)
+        };
+
+        let digits = format!("{:.2}", value.abs());
+        let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+        let fraction = fraction.trim_end_matches('0');
+        let sign = if value < 0.0 && (integer != "0" || !fraction.is_empty()) { "-" } else { "" };
+
+        format!("{}{}\u{a0}{}", sign, format_digits(integer, fraction, &locale.number_format), unit.symbol())
+    }
+}
+
+/// Join grouped integer digits and an optional fraction with the locale's separators
+fn format_digits(integer: &str, fraction: &str, format: &NumberFormat) -> String {
+    let grouped = group_digits(integer, &format.thousands_separator, format.grouping);
+    if fraction.is_empty() {
+        grouped
+    } else {
+        format!("{}{}{}", grouped, format.decimal_separator, fraction)
+    }
+}
+
+/// Insert `separator` between digit groups of an unsigned integer
+fn group_digits(integer: &str, separator: &str, grouping: DigitGrouping) -> String {
+    let mut groups = Vec::new();
+    let mut rest = integer;
+    let mut size = 3;
+    while rest.len() > size {
+        let (head, tail) = rest.split_at(rest.len() - size);
+        groups.push(tail);
+        rest = head;
+        if grouping == DigitGrouping::Indian {
+            size = 2;
+        }
+    }
+    groups.push(rest);
+    groups.reverse();
+    groups.join(separator)
+}
+
+/// Plural rule language for a locale, keeping region only where rules differ
+pub(crate) fn plural_language(locale: &Locale, fallback: &str) -> String {
+    let full = locale.language.to_string();
+    if full == "pt-BR" {
+        return full;
+    }
+    let language = locale.language.language.as_str();
+    if language.is_empty() || language == "und" {
+        fallback.to_string()
+    } else {
+        language.to_string()
+    }
+}
+
+/// Resolve `{name, plural, ...}` and `{name, select, ...}` placeholders
+///
+/// Any other braces are left untouched, as are placeholders whose argument
+/// is missing, so the output can still be passed to a template engine.
+pub fn format_message(message: &str, args: &serde_json::Value, language: &str) -> String {
+    let mut output = String::with_capacity(message.len());
+    let mut rest = message;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let candidate = &rest[start..];
+
+        match matching_brace(candidate).and_then(|end| {
+            resolve_placeholder(&candidate[1..end], args, language).map(|text| (text, end))
+        }) {
+            Some((resolved, end)) => {
+                output.push_str(&resolved);
+                rest = &candidate[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = &candidate[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn resolve_placeholder(body: &str, args: &serde_json::Value, language: &str) -> Option<String> {
+    let mut parts = body.splitn(3, ',');
+    let name = parts.next()?.trim();
+    let kind = parts.next()?.trim();
+    let options = parse_options(parts.next()?)?;
+    let value = args.get(name)?;
+
+    let chosen = match kind {
+        "plural" => {
+            let count = value.as_i64()?;
+            let mut forms = PluralForms::default();
+            for (selector, text) in &options {
+                let text = text.clone();
+                match selector.as_str() {
+                    "zero" => forms.zero = Some(text),
+                    "one" => forms.one = Some(text),
+                    "two" => forms.two = Some(text),
+                    "few" => forms.few = Some(text),
+                    "many" => forms.many = Some(text),
+                    "other" => forms.other = text,
+                    exact => {
+                        let n = exact.strip_prefix('=')?.parse().ok()?;
+                        forms.exact.insert(n, text);
+                    }
+                }
+            }
+            forms
+                .form_for(count, plural_category(language, count))
+                .replace('#', &count.to_string())
+        }
+        "select" => {
+            let key = value.as_str()?;
+            options
+                .iter()
+                .find(|(selector, _)| selector == key)
+                .or_else(|| options.iter().find(|(selector, _)| selector == "other"))
+                .map(|(_, text)| text.clone())?
+        }
+        _ => return None,
+    };
+
+    // Variants may themselves contain nested placeholders
+    Some(format_message(&chosen, args, language))
+}
+
+/// Parse `selector {text} selector {text} ...`
+fn parse_options(input: &str) -> Option<Vec<(String, String)>> {
+    let mut options = Vec::new();
+    let mut rest = input.trim_start();
+
+    while !rest.is_empty() {
+        let open = rest.find('{')?;
+        let selector = rest[..open].trim();
+        if selector.is_empty() {
+            return None;
+        }
+        let close = matching_brace(&rest[open..])? + open;
+        options.push((selector.to_string(), rest[open + 1..close].to_string()));
+        rest = rest[close + 1..].trim_start();
+    }
+
+    if options.is_empty() {
+        None
+    } else {
+        Some(options)
+    }
+}
+
+/// Byte index of the brace closing the one at the start of `text`
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, ch) in text.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DateFormat;
+    use serde_json::json;
+
+    fn locale(identifier: &str, country: &str, number_format: NumberFormat) -> Locale {
+        Locale {
+            identifier: identifier.to_string(),
+            language: identifier.parse().unwrap(),
+            country: Some(country.to_string()),
+            script: None,
+            currency: None,
+            timezone: None,
+            number_format,
+            date_format: DateFormat {
+                date_pattern: "yyyy-MM-dd".to_string(),
+                time_pattern: "HH:mm".to_string(),
+                datetime_pattern: "yyyy-MM-dd HH:mm".to_string(),
+                first_day_of_week: 1,
+            },
+            rtl: false,
+        }
+    }
+
+    fn number_format(
+        decimal: &str,
+        thousands: &str,
+        symbol: &str,
+        position: CurrencyPosition,
+        grouping: DigitGrouping,
+    ) -> NumberFormat {
+        NumberFormat {
+            decimal_separator: decimal.to_string(),
+            thousands_separator: thousands.to_string(),
+            currency_symbol: symbol.to_string(),
+            currency_position: position,
+            grouping,
+            currency_decimals: 2,
+        }
+    }
+
+    fn us() -> Locale {
+        locale("en-US", "US", number_format(".", ",", "$", CurrencyPosition::Before, DigitGrouping::Thousands))
+    }
+
+    fn german() -> Locale {
+        locale("de-DE", "DE", number_format(",", ".", "€", CurrencyPosition::AfterWithSpace, DigitGrouping::Thousands))
+    }
+
+    fn indian() -> Locale {
+        locale("hi-IN", "IN", number_format(".", ",", "₹", CurrencyPosition::Before, DigitGrouping::Indian))
+    }
+
+    async fn service() -> FormattingService {
+        FormattingService::new(FormattingConfig::default()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_format_currency_us_german_and_indian_grouping() {
+        let service = service().await;
+        let amount: Decimal = "1234567.885".parse().unwrap();
+
+        assert_eq!(service.format_currency(amount, &us()), "$1,234,567.89");
+        assert_eq!(service.format_currency(amount, &german()), "1.234.567,89\u{a0}€");
+        assert_eq!(service.format_currency(amount, &indian()), "₹12,34,567.89");
+        assert_eq!(service.format_currency("10000000".parse().unwrap(), &indian()), "₹1,00,00,000.00");
+        assert_eq!(service.format_currency("-0.5".parse().unwrap(), &us()), "-$0.50");
+        assert_eq!(service.format_currency("999".parse().unwrap(), &german()), "999,00\u{a0}€");
+    }
+
+    #[tokio::test]
+    async fn test_format_measurement_converts_unit_system() {
+        let service = service().await;
+
+        assert_eq!(service.format_measurement(10.0, Unit::Kilometer, &us()), "6.21\u{a0}mi");
+        assert_eq!(service.format_measurement(1500.5, Unit::Kilogram, &german()), "1.500,5\u{a0}kg");
+        assert_eq!(service.format_measurement(212.0, Unit::Fahrenheit, &german()), "100\u{a0}°C");
+        assert_eq!(service.format_measurement(2.0, Unit::Pound, &indian()), "0.91\u{a0}kg");
+    }
+
+    #[test]
+    fn test_polish_plural_categories() {
+        assert_eq!(plural_category("pl", 1), PluralCategory::One);
+        assert_eq!(plural_category("pl", 3), PluralCategory::Few);
+        assert_eq!(plural_category("pl", 5), PluralCategory::Many);
+        assert_eq!(plural_category("pl", 13), PluralCategory::Many);
+        assert_eq!(plural_category("pl", 22), PluralCategory::Few);
+    }
+
+    #[test]
+    fn test_arabic_plural_categories() {
+        assert_eq!(plural_category("ar", 0), PluralCategory::Zero);
+        assert_eq!(plural_category("ar", 1), PluralCategory::One);
+        assert_eq!(plural_category("ar", 2), PluralCategory::Two);
+        assert_eq!(plural_category("ar", 7), PluralCategory::Few);
+        assert_eq!(plural_category("ar", 11), PluralCategory::Many);
+        assert_eq!(plural_category("ar", 100), PluralCategory::Other);
+    }
+
+    #[test]
+    fn test_format_message_plural_and_select() {
+        let message = "{count, plural, =0 {no violations} one {# violation} other {# violations}} \
+                       reported by {gender, select, female {her} male {him} other {them}}";
+
+        assert_eq!(
+            format_message(message, &json!({"count": 1, "gender": "female"}), "en"),
+            "1 violation reported by her"
+        );
+        assert_eq!(
+            format_message(message, &json!({"count": 0, "gender": "x"}), "en"),
+            "no violations reported by them"
+        );
+    }
+
+    #[test]
+    fn test_format_message_polish_forms() {
+        let message = "{count, plural, one {# naruszenie} few {# naruszenia} many {# naruszeń} other {# naruszenia}}";
+
+        assert_eq!(format_message(message, &json!({"count": 2}), "pl"), "2 naruszenia");
+        assert_eq!(format_message(message, &json!({"count": 5}), "pl"), "5 naruszeń");
+    }
+
+    #[test]
+    fn test_format_message_leaves_unrelated_braces() {
+        let message = "Dear {{name}}, {missing, plural, one {#} other {#}}";
+        assert_eq!(format_message(message, &json!({}), "en"), message);
+    }
+}
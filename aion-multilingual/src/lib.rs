@@ -42,11 +42,13 @@ pub use terminology::*;
 pub use cultural_adaptation::*;
 pub use regulatory_localization::*;
 pub use services::*;
+pub use cache::*;
 pub use error::*;
 
 use std::sync::Arc;
 use std::collections::HashMap;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, Semaphore};
+use futures::stream::{self, Stream, StreamExt};
 use anyhow::Result;
 use tracing::{info, warn, error};
 use uuid::Uuid;
@@ -163,7 +165,11 @@ impl MultilingualSystem {
 
         // Initialize regulatory localizer
         let regulatory_localizer = Arc::new(
-            RegulatoryLocalizer::new(config.regulatory_config.clone()).await?
+            RegulatoryLocalizer::new(
+                config.regulatory_config.clone(),
+                translation_service.clone(),
+                terminology_manager.clone(),
+            ).await?
         );
         info!("✅ Regulatory localizer initialized");
 
@@ -232,6 +238,14 @@ impl MultilingualSystem {
         self.regulatory_localizer.start().await?;
         info!("✅ Regulatory localizer started");
 
+        // Restore cached translations from the previous run
+        if let Some(path) = &self.config.cache_config.persistence_path {
+            if path.exists() {
+                let restored = self.cache.load_from(path).await?;
+                info!("✅ Restored {} cached translations", restored);
+            }
+        }
+
         // Load default language resources
         self.load_default_languages().await?;
 
@@ -255,6 +269,11 @@ impl MultilingualSystem {
         self.localization_engine.stop().await?;
         self.translation_service.stop().await?;
         self.language_manager.stop().await?;
+
+        // Persist cached translations before the cache goes away
+        if let Some(path) = &self.config.cache_config.persistence_path {
+            self.cache.persist_to(path).await?;
+        }
         self.cache.stop().await?;
 
         info!("🎉 Multilingual System gracefully shut down");
@@ -264,6 +283,9 @@ impl MultilingualSystem {
     /// Translate text to target language
     ///
     /// Provides high-quality translation with regulatory context awareness.
+    /// With `context.min_quality` set, a translation scoring below it is
+    /// retried at the next quality tier up to `Premium`, and marked
+    /// `QualityGated` for human review if it still falls short.
     pub async fn translate_text(
         &self,
         text: &str,
@@ -273,31 +295,152 @@ impl MultilingualSystem {
         info!("🔤 Translating text to language: {}", target_language);
 
         // Detect source language if not provided
-        let source_language = if let Some(source) = context.source_language {
+        let source_language = if let Some(source) = context.source_language.clone() {
             source
         } else {
             self.language_detector.detect_language(text).await?
         };
 
-        // Get specialized translation for regulatory content
-        let translation = if context.is_regulatory {
+        // Shield glossary terms from the MT engine so approved wording is used
+        let protected = if context.enforce_terminology {
+            self.protect_glossary_terms(text, target_language, &context).await
+        } else {
+            None
+        };
+        let input = protected.as_ref().map(|p| p.text.as_str()).unwrap_or(text);
+
+        let mut translation = match context.min_quality {
+            None => self.translate_input(input, &source_language, target_language, context.clone()).await?,
+            // Escalate to higher-quality services until the translation clears the threshold
+            Some(min_quality) => {
+                let source_language = &source_language;
+                let outcome = best_quality_tier(context.translation_quality, min_quality, |tier| {
+                    let mut tier_context = context.clone();
+                    tier_context.translation_quality = tier;
+                    async move {
+                        let translation = self.translate_input(input, source_language, target_language, tier_context).await?;
+                        let score = self.quality_score(input, &translation, target_language).await?;
+                        Ok((translation, score))
+                    }
+                }).await?;
+
+                let mut translation = outcome.best;
+                if outcome.tier != context.translation_quality {
+                    translation.metadata.insert(
+                        "quality_rerouted_from".to_string(),
+                        serde_json::to_value(&context.translation_quality)?,
+                    );
+                }
+                translation.quality_status = QualityStatus::gate(outcome.score, min_quality);
+                if let QualityStatus::QualityGated { .. } = translation.quality_status {
+                    warn!(
+                        "🚩 Translation to {} scored {:.2}, below {:.2} even at {:?}; flagged for human review",
+                        target_language, outcome.score, min_quality, outcome.highest_tier
+                    );
+                }
+                translation
+            }
+        };
+
+        if let Some(protected) = protected {
+            let (restored, substitutions) = protected.restore(&translation.translated_text);
+            translation.original_text = text.to_string();
+            translation.translated_text = restored;
+            translation.metadata.insert(
+                "terminology_substitutions".to_string(),
+                serde_json::Value::from(substitutions),
+            );
+        }
+
+        // Verify the final wording, after glossary terms were restored
+        if translation.context.is_regulatory && self.regulatory_localizer.config().validate_terminology {
+            let context = &translation.context;
+            match context.domain.as_deref().or(context.compliance_framework.as_deref()) {
+                Some(framework) => {
+                    let report = self.regulatory_localizer.validate_legal_terms(
+                        text,
+                        &translation.translated_text,
+                        framework,
+                        target_language,
+                    ).await;
+                    // An unavailable terminology set is recorded in the metadata, not held against the translation
+                    if !report.issues.is_empty() {
+                        translation.quality_status = QualityStatus::TerminologyInvalid {
+                            issues: report.issues.len(),
+                        };
+                    }
+                    translation.metadata.insert(
+                        "term_validation".to_string(),
+                        serde_json::to_value(&report)?,
+                    );
+                }
+                None => warn!("Regulatory translation without a domain or framework, legal terms not validated"),
+            }
+        }
+
+        info!("✅ Text translation completed");
+        Ok(translation)
+    }
+
+    /// Translate with the regulatory localizer for regulatory content, else the general service
+    async fn translate_input(
+        &self,
+        input: &str,
+        source_language: &LanguageIdentifier,
+        target_language: &LanguageIdentifier,
+        context: TranslationContext,
+    ) -> Result<TranslatedText> {
+        if context.is_regulatory {
             self.regulatory_localizer.translate_regulatory_text(
-                text,
-                &source_language,
+                input,
+                source_language,
                 target_language,
                 &context,
-            ).await?
+            ).await
         } else {
             self.translation_service.translate(
-                text,
-                &source_language,
+                input,
+                source_language,
                 target_language,
                 context,
-            ).await?
+            ).await
+        }
+    }
+
+    /// Score gated on: the lower of the service's confidence and the assessed quality
+    async fn quality_score(
+        &self,
+        input: &str,
+        translation: &TranslatedText,
+        target_language: &LanguageIdentifier,
+    ) -> Result<f64> {
+        let quality = self.translation_service.validate_translation(
+            input,
+            &translation.translated_text,
+            target_language,
+        ).await?;
+        Ok(translation.confidence_score.min(quality.overall_score))
+    }
+
+    /// Replace glossary terms for the context's domain with placeholders
+    async fn protect_glossary_terms(
+        &self,
+        text: &str,
+        target_language: &LanguageIdentifier,
+        context: &TranslationContext,
+    ) -> Option<ProtectedText> {
+        let Some(domain) = context.domain.as_deref() else {
+            warn!("Terminology enforcement requested without a domain, skipping");
+            return None;
         };
 
-        info!("✅ Text translation completed");
-        Ok(translation)
+        match self.terminology_manager.get_terminology_set(domain, target_language).await {
+            Ok(terminology) => Some(terminology.protect_terms(text)),
+            Err(e) => {
+                warn!("Terminology enforcement skipped: {}", e);
+                None
+            }
+        }
     }
 
     /// Localize content for specific market
@@ -368,6 +511,11 @@ impl MultilingualSystem {
         self.language_detector.detect_language_detailed(text).await
     }
 
+    /// Detect per-segment languages in mixed-language text
+    pub fn detect_segments(&self, text: &str) -> Vec<DetectedSegment> {
+        self.language_detector.detect_segments(text)
+    }
+
     /// Get regulatory terminology
     pub async fn get_regulatory_terminology(
         &self,
@@ -403,28 +551,13 @@ impl MultilingualSystem {
         let mut failed = 0;
 
         for request in content_batch {
-            match self.translate_text(
-                &request.text,
-                &request.target_language,
-                request.context,
-            ).await {
-                Ok(translation) => {
-                    successful += 1;
-                    results.push(BulkTranslationItem {
-                        request,
-                        translation: Some(translation),
-                        error: None,
-                    });
-                }
-                Err(e) => {
-                    failed += 1;
-                    results.push(BulkTranslationItem {
-                        request,
-                        translation: None,
-                        error: Some(e.to_string()),
-                    });
-                }
+            let item = self.translate_request(request).await;
+            if item.error.is_none() {
+                successful += 1;
+            } else {
+                failed += 1;
             }
+            results.push(item);
         }
 
         let bulk_result = BulkTranslationResult {
@@ -439,6 +572,41 @@ impl MultilingualSystem {
         Ok(bulk_result)
     }
 
+    /// Bulk translate content as a stream
+    ///
+    /// Yields each item as soon as its translation completes, so large
+    /// batches produce feedback incrementally. Concurrency is bounded by
+    /// `TranslationServiceConfig::max_concurrent_requests`. Failed items are
+    /// emitted with their error captured and never terminate the stream.
+    /// Items are yielded in completion order, not request order.
+    pub fn bulk_translate_stream(
+        &self,
+        requests: Vec<TranslationRequest>,
+    ) -> impl Stream<Item = BulkTranslationItem> + Send + 'static {
+        let max_concurrent = self.config.translation_config.max_concurrent_requests.max(1);
+        let system = self.clone();
+
+        info!("📦 Starting streaming bulk translation for {} items (concurrency: {})",
+            requests.len(), max_concurrent);
+
+        translate_concurrently(requests, max_concurrent, move |request| {
+            let system = system.clone();
+            async move {
+                system.translate_text(&request.text, &request.target_language, request.context.clone()).await
+            }
+        })
+    }
+
+    /// Translate a single bulk request, capturing any failure on the item
+    async fn translate_request(&self, request: TranslationRequest) -> BulkTranslationItem {
+        let result = self.translate_text(
+            &request.text,
+            &request.target_language,
+            request.context.clone(),
+        ).await;
+        bulk_item(request, result)
+    }
+
     /// Get system status
     pub async fn get_status(&self) -> Result<MultilingualSystemStatus> {
         let language_status = self.language_manager.get_status().await?;
@@ -620,6 +788,13 @@ pub struct TranslationContext {
     pub jurisdiction: Option<String>,
     pub formality_level: FormalityLevel,
     pub translation_quality: QualityLevel,
+    /// Force approved glossary wording from the domain's `TerminologySet`
+    #[serde(default)]
+    pub enforce_terminology: bool,
+    /// Lowest acceptable quality score (0.0–1.0); below it the translation
+    /// is retried at higher tiers, then flagged for review
+    #[serde(default)]
+    pub min_quality: Option<f64>,
 }
 
 /// Formality levels
@@ -632,7 +807,7 @@ pub enum FormalityLevel {
 }
 
 /// Quality levels
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum QualityLevel {
     Fast,
     Balanced,
@@ -640,6 +815,142 @@ pub enum QualityLevel {
     Premium,
 }
 
+impl QualityLevel {
+    /// Next tier up, if any
+    pub fn escalate(self) -> Option<QualityLevel> {
+        match self {
+            QualityLevel::Fast => Some(QualityLevel::Balanced),
+            QualityLevel::Balanced => Some(QualityLevel::High),
+            QualityLevel::High => Some(QualityLevel::Premium),
+            QualityLevel::Premium => None,
+        }
+    }
+}
+
+/// Assessed quality of a translation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationQuality {
+    /// Combined score, 0.0–1.0
+    pub overall_score: f64,
+    pub fluency_score: f64,
+    pub adequacy_score: f64,
+    pub terminology_score: f64,
+    pub issues: Vec<String>,
+}
+
+/// Outcome of quality gating a translation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum QualityStatus {
+    /// No `min_quality` was requested
+    #[default]
+    Unchecked,
+    Passed { score: f64 },
+    /// Below the threshold at every tier; needs human review before use
+    QualityGated { score: f64, threshold: f64 },
+    /// Legal terms were wrong; see the `term_validation` metadata
+    TerminologyInvalid { issues: usize },
+}
+
+impl QualityStatus {
+    pub fn gate(score: f64, threshold: f64) -> Self {
+        if score >= threshold {
+            QualityStatus::Passed { score }
+        } else {
+            QualityStatus::QualityGated { score, threshold }
+        }
+    }
+
+    pub fn needs_review(&self) -> bool {
+        matches!(self, QualityStatus::QualityGated { .. } | QualityStatus::TerminologyInvalid { .. })
+    }
+}
+
+/// Run `translate` over `requests`, at most `max_concurrent` at a time,
+/// yielding each item as it completes
+fn translate_concurrently<F, Fut>(
+    requests: Vec<TranslationRequest>,
+    max_concurrent: usize,
+    translate: F,
+) -> impl Stream<Item = BulkTranslationItem> + Send + 'static
+where
+    F: Fn(TranslationRequest) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = Result<TranslatedText>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(max_concurrent));
+    stream::iter(requests)
+        .map(move |request| {
+            let translation = translate(request.clone());
+            let semaphore = semaphore.clone();
+            async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("bulk translation semaphore is never closed");
+                bulk_item(request, translation.await)
+            }
+        })
+        .buffer_unordered(max_concurrent)
+}
+
+/// Bulk item for a translation outcome, with any failure captured on the item
+fn bulk_item(request: TranslationRequest, result: Result<TranslatedText>) -> BulkTranslationItem {
+    match result {
+        Ok(translation) => BulkTranslationItem {
+            request,
+            translation: Some(translation),
+            error: None,
+        },
+        Err(e) => {
+            warn!("Bulk translation item failed: {}", e);
+            BulkTranslationItem {
+                request,
+                translation: None,
+                error: Some(e.to_string()),
+            }
+        }
+    }
+}
+
+/// Best attempt from escalating through quality tiers
+struct TierOutcome<T> {
+    best: T,
+    score: f64,
+    /// Tier that produced `best`
+    tier: QualityLevel,
+    /// Highest tier tried
+    highest_tier: QualityLevel,
+}
+
+/// Run `attempt` from `start` up through the tiers until one scores at least
+/// `min_quality` or `Premium` has been tried, keeping the best-scoring attempt
+/// rather than the last one.
+async fn best_quality_tier<T, F, Fut>(start: QualityLevel, min_quality: f64, mut attempt: F) -> Result<TierOutcome<T>>
+where
+    F: FnMut(QualityLevel) -> Fut,
+    Fut: std::future::Future<Output = Result<(T, f64)>>,
+{
+    let (best, score) = attempt(start).await?;
+    let mut outcome = TierOutcome { best, score, tier: start, highest_tier: start };
+
+    while outcome.score < min_quality {
+        let Some(next_tier) = outcome.highest_tier.escalate() else {
+            break;
+        };
+        info!(
+            "🔁 Translation quality {:.2} below {:.2}, retrying at {:?}",
+            outcome.score, min_quality, next_tier
+        );
+        outcome.highest_tier = next_tier;
+        let (candidate, score) = attempt(next_tier).await?;
+        if score > outcome.score {
+            outcome.best = candidate;
+            outcome.score = score;
+            outcome.tier = next_tier;
+        }
+    }
+    Ok(outcome)
+}
+
 /// Translated text result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranslatedText {
@@ -651,6 +962,10 @@ pub struct TranslatedText {
     pub translation_service: String,
     pub context: TranslationContext,
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub quality_status: QualityStatus,
 }
 
 /// Locale information
@@ -674,6 +989,25 @@ pub struct NumberFormat {
     pub thousands_separator: String,
     pub currency_symbol: String,
     pub currency_position: CurrencyPosition,
+    #[serde(default)]
+    pub grouping: DigitGrouping,
+    /// Minor-unit digits shown for amounts (2 for EUR, 0 for JPY)
+    #[serde(default = "default_currency_decimals")]
+    pub currency_decimals: u32,
+}
+
+fn default_currency_decimals() -> u32 {
+    2
+}
+
+/// How integer digits are grouped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DigitGrouping {
+    /// Groups of three: 1,234,567
+    #[default]
+    Thousands,
+    /// Three, then groups of two (lakh/crore): 12,34,567
+    Indian,
 }
 
 /// Currency position
@@ -829,9 +1163,155 @@ mod tests {
             jurisdiction: Some("US".to_string()),
             formality_level: FormalityLevel::Legal,
             translation_quality: QualityLevel::Premium,
+            enforce_terminology: false,
+            min_quality: Some(0.85),
         };
 
         assert!(context.is_regulatory);
         assert_eq!(context.formality_level as i32, FormalityLevel::Legal as i32);
     }
+
+    #[test]
+    fn test_quality_gate_escalates_tiers_then_flags_for_review() {
+        assert_eq!(QualityLevel::Fast.escalate(), Some(QualityLevel::Balanced));
+        assert_eq!(QualityLevel::High.escalate(), Some(QualityLevel::Premium));
+        assert_eq!(QualityLevel::Premium.escalate(), None);
+
+        assert_eq!(QualityStatus::gate(0.9, 0.85), QualityStatus::Passed { score: 0.9 });
+        let gated = QualityStatus::gate(0.6, 0.85);
+        assert!(gated.needs_review());
+        assert!(!QualityStatus::default().needs_review());
+    }
+
+    #[tokio::test]
+    async fn test_tier_escalation_keeps_the_best_scoring_attempt() {
+        let falling_short = |tier: QualityLevel| match tier {
+            QualityLevel::Fast => 0.7,
+            QualityLevel::Balanced => 0.5,
+            QualityLevel::High => 0.6,
+            QualityLevel::Premium => 0.65,
+        };
+        let score = |tier: QualityLevel| match tier {
+            QualityLevel::Fast => 0.7,
+            QualityLevel::Balanced => 0.5,
+            QualityLevel::High => 0.9,
+            QualityLevel::Premium => 0.95,
+        };
+
+        // Every tier falls short: the earlier, better attempt wins over the last one
+        let mut tried = Vec::new();
+        let outcome = best_quality_tier(QualityLevel::Fast, 0.85, |tier| {
+            tried.push(tier);
+            std::future::ready(Ok((tier, falling_short(tier))))
+        }).await.unwrap();
+        assert_eq!(tried, vec![QualityLevel::Fast, QualityLevel::Balanced, QualityLevel::High, QualityLevel::Premium]);
+        assert_eq!(outcome.best, QualityLevel::Fast);
+        assert_eq!(outcome.tier, QualityLevel::Fast);
+        assert_eq!(outcome.score, 0.7);
+        assert_eq!(outcome.highest_tier, QualityLevel::Premium);
+
+        // Escalation stops at the first tier that clears the threshold
+        let mut tried = Vec::new();
+        let outcome = best_quality_tier(QualityLevel::Fast, 0.85, |tier| {
+            tried.push(tier);
+            std::future::ready(Ok((tier, score(tier))))
+        }).await.unwrap();
+        assert_eq!(tried, vec![QualityLevel::Fast, QualityLevel::Balanced, QualityLevel::High]);
+        assert_eq!(outcome.best, QualityLevel::High);
+        assert_eq!(QualityStatus::gate(outcome.score, 0.85), QualityStatus::Passed { score: 0.9 });
+
+        // A failed attempt is an error, not a low score
+        let failed: Result<TierOutcome<QualityLevel>> =
+            best_quality_tier(QualityLevel::Premium, 0.85, |_| std::future::ready(Err(anyhow::anyhow!("service down")))).await;
+        assert!(failed.is_err());
+    }
+
+    fn bulk_request(text: &str) -> TranslationRequest {
+        TranslationRequest {
+            text: text.to_string(),
+            target_language: "es".parse().unwrap(),
+            context: TranslationContext {
+                source_language: Some("en".parse().unwrap()),
+                domain: None,
+                is_regulatory: false,
+                compliance_framework: None,
+                jurisdiction: None,
+                formality_level: FormalityLevel::Neutral,
+                translation_quality: QualityLevel::Balanced,
+                enforce_terminology: false,
+                min_quality: None,
+            },
+        }
+    }
+
+    fn translated(request: &TranslationRequest) -> TranslatedText {
+        TranslatedText {
+            original_text: request.text.clone(),
+            translated_text: request.text.to_uppercase(),
+            source_language: "en".parse().unwrap(),
+            target_language: request.target_language.clone(),
+            confidence_score: 1.0,
+            translation_service: "test".to_string(),
+            context: request.context.clone(),
+            timestamp: Utc::now(),
+            metadata: HashMap::new(),
+            quality_status: QualityStatus::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_stream_yields_failed_items_and_continues() {
+        let requests = ["first", "broken", "third", "fourth"].map(bulk_request).to_vec();
+        let items: Vec<BulkTranslationItem> = translate_concurrently(requests, 2, |request| async move {
+            if request.text == "broken" {
+                Err(anyhow::anyhow!("service unavailable"))
+            } else {
+                Ok(translated(&request))
+            }
+        })
+        .collect()
+        .await;
+
+        assert_eq!(items.len(), 4);
+        let failed: Vec<_> = items.iter().filter(|item| item.error.is_some()).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].request.text, "broken");
+        assert!(failed[0].translation.is_none());
+        assert!(failed[0].error.as_deref().unwrap().contains("service unavailable"));
+        assert!(items.iter().filter(|item| item.error.is_none()).all(|item| item.translation.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_bulk_stream_never_exceeds_the_concurrency_limit() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let requests = (0..20).map(|i| bulk_request(&format!("item {}", i))).collect();
+
+        let (counter, highest) = (in_flight.clone(), peak.clone());
+        let items: Vec<BulkTranslationItem> = translate_concurrently(requests, 3, move |request| {
+            let (counter, highest) = (counter.clone(), highest.clone());
+            async move {
+                let now = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                highest.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                counter.fetch_sub(1, Ordering::SeqCst);
+                Ok(translated(&request))
+            }
+        })
+        .collect()
+        .await;
+
+        assert_eq!(items.len(), 20);
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(in_flight.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_translate_stream_empty_batch() {
+        let system = MultilingualSystem::new(MultilingualConfig::default()).await.unwrap();
+        let items: Vec<BulkTranslationItem> = system.bulk_translate_stream(Vec::new()).collect().await;
+        assert!(items.is_empty());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,111 @@
+//! Regulatory localization
+//!
+//! Legal-register translation of regulatory text, and validation that the
+//! result uses the jurisdiction's approved legal vocabulary.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use unic_langid::LanguageIdentifier;
+
+use crate::{FormalityLevel, TermValidationReport, TerminologyManager, TranslatedText, TranslationContext, TranslationService};
+
+/// Regulatory localization configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegulatoryLocalizationConfig {
+    /// Check legal terms of every regulatory translation against the terminology set
+    pub validate_terminology: bool,
+}
+
+impl Default for RegulatoryLocalizationConfig {
+    fn default() -> Self {
+        Self {
+            validate_terminology: true,
+        }
+    }
+}
+
+/// Regulatory localizer
+pub struct RegulatoryLocalizer {
+    config: RegulatoryLocalizationConfig,
+    translation_service: Arc<TranslationService>,
+    terminology_manager: Arc<TerminologyManager>,
+}
+
+impl RegulatoryLocalizer {
+    pub async fn new(
+        config: RegulatoryLocalizationConfig,
+        translation_service: Arc<TranslationService>,
+        terminology_manager: Arc<TerminologyManager>,
+    ) -> Result<Self> {
+        Ok(Self {
+            config,
+            translation_service,
+            terminology_manager,
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn config(&self) -> &RegulatoryLocalizationConfig {
+        &self.config
+    }
+
+    /// Translate regulatory text in the legal register
+    pub async fn translate_regulatory_text(
+        &self,
+        text: &str,
+        source_language: &LanguageIdentifier,
+        target_language: &LanguageIdentifier,
+        context: &TranslationContext,
+    ) -> Result<TranslatedText> {
+        let mut legal_context = context.clone();
+        legal_context.formality_level = FormalityLevel::Legal;
+
+        self.translation_service
+            .translate(text, source_language, target_language, legal_context)
+            .await
+    }
+
+    /// Check a translation of `source` against the framework's approved legal terms in `lang`
+    ///
+    /// Only the glossary terms `source` contains are required. Without a terminology
+    /// set for the framework and language the report is marked unavailable and never
+    /// counts as valid.
+    pub async fn validate_legal_terms(
+        &self,
+        source: &str,
+        translation: &str,
+        framework: &str,
+        lang: &LanguageIdentifier,
+    ) -> TermValidationReport {
+        let report = match self.terminology_manager.get_terminology_set(framework, lang).await {
+            Ok(terminology) => terminology.validate_translation(source, translation),
+            Err(e) => {
+                warn!("Legal terminology could not be verified: {}", e);
+                TermValidationReport {
+                    domain: framework.to_string(),
+                    language: lang.clone(),
+                    terminology_available: false,
+                    verified_terms: Vec::new(),
+                    issues: Vec::new(),
+                }
+            }
+        };
+
+        if report.issues.is_empty() {
+            info!("⚖️ {} legal terms verified in {} translation", report.verified_terms.len(), lang);
+        } else {
+            warn!("⚖️ {} legal term issues in {} translation for {}", report.issues.len(), lang, framework);
+        }
+        report
+    }
+}
@@ -0,0 +1,328 @@
+//! Template localization
+//!
+//! Per-language template sources rendered with Handlebars after ICU plural
+//! and select placeholders have been resolved for the target locale.
+//! Output for right-to-left locales is laid out right-to-left, with embedded
+//! left-to-right runs isolated so they cannot reorder the surrounding text.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use handlebars::Handlebars;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::formatting::{format_message, plural_language};
+use crate::Locale;
+
+/// LEFT-TO-RIGHT ISOLATE
+const LRI: char = '\u{2066}';
+/// POP DIRECTIONAL ISOLATE
+const PDI: char = '\u{2069}';
+
+/// Template localization configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateLocalizationConfig {
+    /// Language whose template is used when no translation exists
+    pub default_language: String,
+}
+
+impl Default for TemplateLocalizationConfig {
+    fn default() -> Self {
+        Self {
+            default_language: "en".to_string(),
+        }
+    }
+}
+
+/// Rendered template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizedTemplate {
+    pub template_id: String,
+    pub locale: String,
+    pub content: String,
+    pub rendered_at: DateTime<Utc>,
+}
+
+/// Template localizer
+pub struct TemplateLocalizer {
+    config: TemplateLocalizationConfig,
+    templates: RwLock<HashMap<(String, String), String>>,
+    handlebars: Handlebars<'static>,
+}
+
+impl TemplateLocalizer {
+    pub async fn new(config: TemplateLocalizationConfig) -> Result<Self> {
+        Ok(Self {
+            config,
+            templates: RwLock::new(HashMap::new()),
+            handlebars: Handlebars::new(),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Register the source of a template for one language
+    pub async fn register_template(&self, template_id: &str, language: &str, source: &str) {
+        self.templates
+            .write()
+            .await
+            .insert((template_id.to_string(), language.to_string()), source.to_string());
+    }
+
+    /// Render a template for the target locale
+    ///
+    /// `{count, plural, ...}` and `{gender, select, ...}` placeholders are
+    /// resolved with the locale's plural rules before Handlebars rendering.
+    pub async fn localize_template(
+        &self,
+        template_id: &str,
+        data: &serde_json::Value,
+        target_locale: &Locale,
+    ) -> Result<LocalizedTemplate> {
+        let source = self.resolve_source(template_id, target_locale).await?;
+        let language = plural_language(target_locale, &self.config.default_language);
+        let message = format_message(&source, data, &language);
+        let mut content = self.handlebars.render_template(&message, data)?;
+        if target_locale.rtl {
+            content = apply_rtl_layout(&content);
+        }
+
+        Ok(LocalizedTemplate {
+            template_id: template_id.to_string(),
+            locale: target_locale.identifier.clone(),
+            content,
+            rendered_at: Utc::now(),
+        })
+    }
+
+    async fn resolve_source(&self, template_id: &str, locale: &Locale) -> Result<String> {
+        let templates = self.templates.read().await;
+        let candidates = [
+            locale.language.to_string(),
+            locale.language.language.as_str().to_string(),
+            self.config.default_language.clone(),
+        ];
+
+        candidates
+            .iter()
+            .find_map(|language| templates.get(&(template_id.to_string(), language.clone())))
+            .cloned()
+            .ok_or_else(|| anyhow!("Template '{}' not found for locale {}", template_id, locale.identifier))
+    }
+}
+
+static HTML_MARKER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[A-Za-z!/]").expect("valid pattern"));
+
+/// Script and style blocks, comments and tags; everything between them is text
+static MARKUP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<(script|style)\b.*?</(?:script|style)\s*>|<!--.*?-->|<[^>]*>").expect("valid pattern")
+});
+
+static LTR_RUN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"&#?[A-Za-z0-9]+;",
+        // Stops at character references other than the escaped `&` and `=` of a query string
+        r"|(?:https?://|www\.)(?:[^\s<&]|&(?:amp|#x3D);)*[^\s<&.,;:!?)]",
+        r"|[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)+",
+        r"|[A-Za-z0-9](?:[A-Za-z0-9.,:/%+_ -]*[A-Za-z0-9%])?",
+    ))
+    .expect("valid pattern")
+});
+
+static DIRECTIONAL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)\b(margin|padding|border)-(left|right)\b|\b(text-align|float|clear)(\s*:\s*)(left|right)\b|\b(align\s*=\s*["']?)(left|right)\b"#,
+    )
+    .expect("valid pattern")
+});
+
+/// Box shorthands whose values run top, right, bottom, left
+static BOX_SHORTHAND: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\b(margin|padding|border-width|border-style|border-color)(\s*:\s*)([^;"'}!<>]*[^;"'}!<>\s])"#)
+        .expect("valid pattern")
+});
+
+static EXISTING_DIR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\s+dir\s*=\s*("[^"]*"|'[^']*'|[^\s>]+)"#).expect("valid pattern"));
+
+/// Opening tags that can carry the document direction, in order of preference
+static ROOT_TAGS: LazyLock<[(&str, Regex); 2]> = LazyLock::new(|| {
+    ["html", "body"].map(|root| (root, Regex::new(&format!(r"(?i)<{}\b[^>]*>", root)).expect("valid pattern")))
+});
+
+/// Lay rendered output out right-to-left
+///
+/// HTML gets `dir="rtl"` on its root and left/right styling in tags and
+/// style blocks mirrored; text, scripts and comments are never rewritten
+/// as styling. In all output, URLs, e-mail addresses, numbers and
+/// Latin-script runs in text are wrapped in LRI…PDI so they keep their
+/// internal order inside RTL text.
+pub fn apply_rtl_layout(content: &str) -> String {
+    if !HTML_MARKER.is_match(content) {
+        return isolate_ltr_runs(content);
+    }
+
+    let mut laid_out = String::with_capacity(content.len());
+    let mut last = 0;
+    for markup in MARKUP.captures_iter(content) {
+        let whole = markup.get(0).expect("whole match");
+        laid_out.push_str(&isolate_ltr_runs(&content[last..whole.start()]));
+        let is_script = markup.get(1).is_some_and(|block| block.as_str().eq_ignore_ascii_case("script"));
+        if is_script || whole.as_str().starts_with("<!--") {
+            laid_out.push_str(whole.as_str());
+        } else {
+            laid_out.push_str(&mirror_directional_markup(whole.as_str()));
+        }
+        last = whole.end();
+    }
+    laid_out.push_str(&isolate_ltr_runs(&content[last..]));
+
+    set_root_direction(&laid_out)
+}
+
+/// Wrap left-to-right runs of a text node in directional isolates
+fn isolate_ltr_runs(text: &str) -> String {
+    LTR_RUN
+        .replace_all(text, |caps: &Captures| {
+            let run = &caps[0];
+            // Character references are markup, not text
+            if run.starts_with('&') && run.ends_with(';') {
+                run.to_string()
+            } else {
+                format!("{}{}{}", LRI, run, PDI)
+            }
+        })
+        .into_owned()
+}
+
+/// Swap left and right in a tag's styling and `align` attribute, or in a style block
+fn mirror_directional_markup(markup: &str) -> String {
+    let mirrored = DIRECTIONAL.replace_all(markup, |caps: &Captures| {
+        if let Some(property) = caps.get(1) {
+            format!("{}-{}", property.as_str(), mirror(&caps[2]))
+        } else if let Some(property) = caps.get(3) {
+            format!("{}{}{}", property.as_str(), &caps[4], mirror(&caps[5]))
+        } else {
+            format!("{}{}", &caps[6], mirror(&caps[7]))
+        }
+    });
+
+    BOX_SHORTHAND
+        .replace_all(&mirrored, |caps: &Captures| {
+            let mut values: Vec<&str> = caps[3].split_whitespace().collect();
+            // Only the four-value form names left and right separately; functions may contain spaces
+            if values.len() == 4 && !caps[3].contains('(') {
+                values.swap(1, 3);
+                format!("{}{}{}", &caps[1], &caps[2], values.join(" "))
+            } else {
+                caps[0].to_string()
+            }
+        })
+        .into_owned()
+}
+
+fn mirror(side: &str) -> &'static str {
+    if side.eq_ignore_ascii_case("left") {
+        "right"
+    } else {
+        "left"
+    }
+}
+
+/// Set `dir="rtl"` on `<html>`, else `<body>`, else a wrapping `<div>`
+fn set_root_direction(html: &str) -> String {
+    for (root, open_tag) in ROOT_TAGS.iter() {
+        if let Some(tag) = open_tag.find(html) {
+            let without_dir = EXISTING_DIR.replace_all(tag.as_str(), "");
+            // Insert right after the tag name
+            let (name, rest) = without_dir.split_at(root.len() + 1);
+            return format!("{}{} dir=\"rtl\"{}{}", &html[..tag.start()], name, rest, &html[tag.end()..]);
+        }
+    }
+    format!("<div dir=\"rtl\">{}</div>", html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CurrencyPosition, DateFormat, DigitGrouping, NumberFormat};
+
+    fn arabic_locale() -> Locale {
+        Locale {
+            identifier: "ar-SA".to_string(),
+            language: "ar-SA".parse().unwrap(),
+            country: Some("SA".to_string()),
+            script: Some("Arab".to_string()),
+            currency: Some("SAR".to_string()),
+            timezone: Some("Asia/Riyadh".to_string()),
+            number_format: NumberFormat {
+                decimal_separator: "٫".to_string(),
+                thousands_separator: "٬".to_string(),
+                currency_symbol: "ر.س".to_string(),
+                currency_position: CurrencyPosition::AfterWithSpace,
+                grouping: DigitGrouping::Thousands,
+                currency_decimals: 2,
+            },
+            date_format: DateFormat {
+                date_pattern: "dd/MM/yyyy".to_string(),
+                time_pattern: "HH:mm".to_string(),
+                datetime_pattern: "dd/MM/yyyy HH:mm".to_string(),
+                first_day_of_week: 6,
+            },
+            rtl: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_arabic_template_is_laid_out_rtl_with_isolated_ltr_runs() {
+        let localizer = TemplateLocalizer::new(TemplateLocalizationConfig::default()).await.unwrap();
+        localizer
+            .register_template(
+                "breach_notice",
+                "ar",
+                r#"<html lang="ar" dir="ltr"><body><p style="text-align: left; margin-left: 4px">يجب الإبلاغ خلال {{hours}} ساعة عبر {{url}}</p></body></html>"#,
+            )
+            .await;
+
+        let rendered = localizer
+            .localize_template(
+                "breach_notice",
+                &serde_json::json!({ "hours": 72, "url": "https://example.eu/report" }),
+                &arabic_locale(),
+            )
+            .await
+            .unwrap();
+
+        assert!(rendered.content.starts_with(r#"<html dir="rtl" lang="ar">"#));
+        assert!(rendered.content.contains("text-align: right; margin-right: 4px"));
+        assert!(rendered.content.contains("\u{2066}72\u{2069}"));
+        assert!(rendered.content.contains("\u{2066}https://example.eu/report\u{2069}"));
+        assert!(!rendered.content.contains("\u{2066}ar"));
+    }
+
+    #[test]
+    fn test_rtl_layout_mirrors_markup_only() {
+        let html = concat!(
+            r#"<div style="margin: 1px 2px 3px 4px; padding: 0 8px">Set text-align: left here</div>"#,
+            r#"<style>p { border-width: 1px 2px 3px 4px; float: left }</style>"#,
+            r#"<script>el.style.float = "left"; var css = "margin-left: 0";</script>"#,
+        );
+        let laid_out = apply_rtl_layout(html);
+
+        assert!(laid_out.contains(r#"style="margin: 1px 4px 3px 2px; padding: 0 8px""#));
+        assert!(laid_out.contains("border-width: 1px 4px 3px 2px; float: right"));
+        assert!(laid_out.contains(r#"<script>el.style.float = "left"; var css = "margin-left: 0";</script>"#));
+        assert!(laid_out.contains("text-align: left"));
+        assert!(laid_out.starts_with(r#"<div dir="rtl">"#));
+    }
+}
@@ -0,0 +1,467 @@
+//! Regulatory terminology management
+//!
+//! Glossaries of approved translations for domain terms, e.g. GDPR's
+//! "data controller" → "responsable du traitement". Sets are keyed by domain
+//! and target language.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{anyhow, Result};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use unic_langid::LanguageIdentifier;
+
+/// Terminology configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminologyConfig {
+    /// Root directory for builtin terminology files
+    pub terminology_dir: PathBuf,
+}
+
+impl Default for TerminologyConfig {
+    fn default() -> Self {
+        Self {
+            terminology_dir: PathBuf::from("resources"),
+        }
+    }
+}
+
+/// Where a terminology set is loaded from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TerminologySource {
+    /// Path relative to the configured terminology directory
+    Builtin(String),
+    /// Absolute or working-directory-relative path
+    File(PathBuf),
+    /// Terms supplied directly
+    Inline(Vec<TermEntry>),
+}
+
+/// A single glossary entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermEntry {
+    pub source_term: String,
+    pub target_term: String,
+    /// Known incorrect renderings, e.g. "contrôleur de données" for "data controller"
+    #[serde(default)]
+    pub rejected_translations: Vec<String>,
+}
+
+/// Approved terminology for a domain in a target language
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminologySet {
+    pub domain: String,
+    pub language: LanguageIdentifier,
+    terms: Vec<TermEntry>,
+    /// Compiled from `terms` on first use and shared by clones
+    #[serde(skip)]
+    patterns: OnceLock<Arc<TermPatterns>>,
+}
+
+/// Regexes for a terminology set, built once
+#[derive(Debug)]
+struct TermPatterns {
+    /// All source terms, longest first
+    sources: Option<Regex>,
+    /// Source term (lowercased) to its index in `terms`
+    lookup: HashMap<String, usize>,
+    /// Approved and rejected renderings, by index in `terms`
+    targets: Vec<TargetPatterns>,
+}
+
+#[derive(Debug)]
+struct TargetPatterns {
+    approved: Option<Regex>,
+    rejected: Vec<(String, Regex)>,
+}
+
+/// Outcome of checking a translation against a terminology set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermValidationReport {
+    pub domain: String,
+    pub language: LanguageIdentifier,
+    /// False when no terminology set exists, in which case nothing could be verified
+    pub terminology_available: bool,
+    /// Source-text terms rendered with the approved wording
+    pub verified_terms: Vec<String>,
+    pub issues: Vec<TermIssue>,
+}
+
+impl TermValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.terminology_available && self.issues.is_empty()
+    }
+}
+
+/// A glossary term a translation got wrong
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermIssue {
+    pub source_term: String,
+    /// Approved rendering
+    pub expected: String,
+    pub kind: TermIssueKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TermIssueKind {
+    /// A term of the source text has no approved rendering in the translation
+    Missing,
+    /// A rejected rendering was used instead of the approved one
+    Mistranslated { found: String },
+}
+
+/// Text with glossary terms replaced by placeholders
+#[derive(Debug, Clone)]
+pub struct ProtectedText {
+    pub text: String,
+    /// Approved target term for each placeholder, by placeholder index
+    pub replacements: Vec<String>,
+}
+
+impl TerminologySet {
+    pub fn new(domain: &str, language: LanguageIdentifier, terms: Vec<TermEntry>) -> Self {
+        Self {
+            domain: domain.to_string(),
+            language,
+            terms,
+            patterns: OnceLock::new(),
+        }
+    }
+
+    pub fn terms(&self) -> &[TermEntry] {
+        &self.terms
+    }
+
+    /// Replace every glossary source term in `text` with an opaque placeholder
+    ///
+    /// Matching is case-insensitive on word boundaries, longest term first,
+    /// and done in a single pass so a substituted span is never matched again.
+    pub fn protect_terms(&self, text: &str) -> ProtectedText {
+        let patterns = self.patterns();
+        let Some(sources) = &patterns.sources else {
+            return ProtectedText { text: text.to_string(), replacements: Vec::new() };
+        };
+
+        let mut replacements = Vec::new();
+        let protected = sources.replace_all(text, |caps: &regex::Captures| {
+            match patterns.lookup.get(&caps[0].to_lowercase()) {
+                Some(&index) => {
+                    replacements.push(self.terms[index].target_term.clone());
+                    placeholder(replacements.len() - 1)
+                }
+                None => caps[0].to_string(),
+            }
+        });
+
+        ProtectedText {
+            text: protected.into_owned(),
+            replacements,
+        }
+    }
+
+    /// Check that `translation` of `source` uses the approved wording
+    ///
+    /// Only glossary terms occurring in `source` are checked, matched the
+    /// same way as [`protect_terms`](Self::protect_terms). Each must appear
+    /// in the translation with its approved rendering, and a rejected
+    /// rendering is flagged even where the approved one also appears.
+    pub fn validate_translation(&self, source: &str, translation: &str) -> TermValidationReport {
+        let patterns = self.patterns();
+        let mut verified_terms = Vec::new();
+        let mut issues = Vec::new();
+
+        let mut required: Vec<usize> = patterns
+            .sources
+            .iter()
+            .flat_map(|sources| sources.find_iter(source))
+            .filter_map(|found| patterns.lookup.get(&found.as_str().to_lowercase()).copied())
+            .collect();
+        required.sort_unstable();
+        required.dedup();
+
+        for index in required {
+            let term = &self.terms[index];
+            let target = &patterns.targets[index];
+            let found = target
+                .rejected
+                .iter()
+                .find(|(_, pattern)| pattern.is_match(translation))
+                .map(|(rendering, _)| rendering.clone());
+            let approved = target.approved.as_ref().is_some_and(|pattern| pattern.is_match(translation));
+
+            if let Some(found) = found {
+                issues.push(TermIssue {
+                    source_term: term.source_term.clone(),
+                    expected: term.target_term.clone(),
+                    kind: TermIssueKind::Mistranslated { found },
+                });
+            } else if !approved {
+                issues.push(TermIssue {
+                    source_term: term.source_term.clone(),
+                    expected: term.target_term.clone(),
+                    kind: TermIssueKind::Missing,
+                });
+            } else {
+                verified_terms.push(term.source_term.clone());
+            }
+        }
+
+        TermValidationReport {
+            domain: self.domain.clone(),
+            language: self.language.clone(),
+            terminology_available: true,
+            verified_terms,
+            issues,
+        }
+    }
+
+    fn patterns(&self) -> &TermPatterns {
+        self.patterns.get_or_init(|| Arc::new(TermPatterns::compile(&self.terms)))
+    }
+}
+
+impl TermPatterns {
+    fn compile(terms: &[TermEntry]) -> Self {
+        let mut sources: Vec<&str> = terms
+            .iter()
+            .map(|term| term.source_term.trim())
+            .filter(|term| !term.is_empty())
+            .collect();
+        sources.sort_by_key(|term| std::cmp::Reverse(term.len()));
+
+        let alternation = sources
+            .iter()
+            .map(|term| regex::escape(term))
+            .collect::<Vec<_>>()
+            .join("|");
+        let sources = if sources.is_empty() {
+            None
+        } else {
+            RegexBuilder::new(&format!(r"\b(?:{})\b", alternation))
+                .case_insensitive(true)
+                .build()
+                .ok()
+        };
+
+        let lookup = terms
+            .iter()
+            .enumerate()
+            .map(|(index, term)| (term.source_term.trim().to_lowercase(), index))
+            .collect();
+
+        let targets = terms
+            .iter()
+            .map(|term| TargetPatterns {
+                approved: word_pattern(&term.target_term),
+                rejected: term
+                    .rejected_translations
+                    .iter()
+                    .filter_map(|rendering| word_pattern(rendering).map(|pattern| (rendering.clone(), pattern)))
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            sources,
+            lookup,
+            targets,
+        }
+    }
+}
+
+impl ProtectedText {
+    /// Substitute the approved terms back into translated output
+    ///
+    /// Returns the final text and the number of substitutions made.
+    /// Placeholders the translation engine dropped are not counted.
+    pub fn restore(&self, translated: &str) -> (String, usize) {
+        let mut restored = translated.to_string();
+        let mut substitutions = 0;
+
+        for (index, target) in self.replacements.iter().enumerate() {
+            let token = placeholder(index);
+            if restored.contains(&token) {
+                restored = restored.replacen(&token, target, 1);
+                substitutions += 1;
+            }
+        }
+
+        (restored, substitutions)
+    }
+}
+
+fn placeholder(index: usize) -> String {
+    format!("__TERM_{}__", index)
+}
+
+/// Case-insensitive whole-word pattern for `term`, `None` when it is blank
+fn word_pattern(term: &str) -> Option<Regex> {
+    let term = term.trim();
+    if term.is_empty() {
+        return None;
+    }
+    RegexBuilder::new(&format!(r"\b{}\b", regex::escape(term)))
+        .case_insensitive(true)
+        .build()
+        .ok()
+}
+
+/// Terminology manager
+pub struct TerminologyManager {
+    config: TerminologyConfig,
+    sets: RwLock<HashMap<(String, String), TerminologySet>>,
+}
+
+impl TerminologyManager {
+    pub async fn new(config: TerminologyConfig) -> Result<Self> {
+        Ok(Self {
+            config,
+            sets: RwLock::new(HashMap::new()),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Load a terminology set for a domain and language
+    ///
+    /// Missing builtin files register an empty set rather than failing
+    /// startup.
+    pub async fn load_terminology_set(
+        &self,
+        domain: &str,
+        language: &LanguageIdentifier,
+        source: TerminologySource,
+    ) -> Result<()> {
+        let terms = match source {
+            TerminologySource::Inline(terms) => terms,
+            TerminologySource::Builtin(relative) => {
+                self.read_terms(self.config.terminology_dir.join(relative)).await?
+            }
+            TerminologySource::File(path) => self.read_terms(path).await?,
+        };
+
+        info!("📚 Loaded {} terms for {} ({})", terms.len(), domain, language);
+        self.sets.write().await.insert(
+            (domain.to_string(), language.to_string()),
+            TerminologySet::new(domain, language.clone(), terms),
+        );
+        Ok(())
+    }
+
+    pub async fn get_terminology_set(
+        &self,
+        domain: &str,
+        language: &LanguageIdentifier,
+    ) -> Result<TerminologySet> {
+        self.sets
+            .read()
+            .await
+            .get(&(domain.to_string(), language.to_string()))
+            .cloned()
+            .ok_or_else(|| anyhow!("No terminology set for domain '{}' in {}", domain, language))
+    }
+
+    async fn read_terms(&self, path: PathBuf) -> Result<Vec<TermEntry>> {
+        if !path.exists() {
+            warn!("Terminology file {} not found, registering empty set", path.display());
+            return Ok(Vec::new());
+        }
+        let bytes = tokio::fs::read(&path).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gdpr_french() -> TerminologySet {
+        TerminologySet::new(
+            "eu-gdpr",
+            "fr".parse().unwrap(),
+            vec![
+                TermEntry {
+                    source_term: "controller".to_string(),
+                    target_term: "responsable".to_string(),
+                    rejected_translations: Vec::new(),
+                },
+                TermEntry {
+                    source_term: "data controller".to_string(),
+                    target_term: "responsable du traitement".to_string(),
+                    rejected_translations: vec!["contrôleur de données".to_string()],
+                },
+                TermEntry {
+                    source_term: "supervisory authority".to_string(),
+                    target_term: "autorité de contrôle".to_string(),
+                    rejected_translations: vec!["autorité de surveillance".to_string()],
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn test_protect_terms_prefers_longest_match_case_insensitively() {
+        let protected = gdpr_french().protect_terms("The Data Controller must inform the controllers.");
+
+        assert_eq!(protected.text, "The __TERM_0__ must inform the controllers.");
+        assert_eq!(protected.replacements, vec!["responsable du traitement".to_string()]);
+    }
+
+    #[test]
+    fn test_restore_counts_substitutions() {
+        let protected = gdpr_french().protect_terms("data controller and controller");
+        let (restored, substitutions) = protected.restore("__TERM_0__ et __TERM_1__");
+
+        assert_eq!(restored, "responsable du traitement et responsable");
+        assert_eq!(substitutions, 2);
+    }
+
+    #[test]
+    fn test_validate_translation_flags_missing_and_mistranslated_terms() {
+        let terminology = gdpr_french();
+        let source = "The data controller notifies the supervisory authority within 72 hours.";
+
+        let correct = terminology.validate_translation(
+            source,
+            "Le Responsable du traitement informe l'autorité de contrôle dans les 72 heures.",
+        );
+        assert!(correct.is_valid());
+        assert_eq!(correct.verified_terms, vec!["data controller", "supervisory authority"]);
+
+        let wrong = terminology.validate_translation(source, "Le contrôleur de données informe l'autorité.");
+        assert!(!wrong.is_valid());
+        assert_eq!(wrong.issues.len(), 2);
+        assert_eq!(
+            wrong.issues[0].kind,
+            TermIssueKind::Mistranslated { found: "contrôleur de données".to_string() }
+        );
+        assert_eq!(wrong.issues[1].source_term, "supervisory authority");
+        assert_eq!(wrong.issues[1].kind, TermIssueKind::Missing);
+    }
+
+    #[test]
+    fn test_validate_translation_only_requires_terms_of_the_source() {
+        let terminology = gdpr_french();
+
+        let report = terminology.validate_translation(
+            "The data controller keeps records.",
+            "Le responsable du traitement tient des registres.",
+        );
+        assert!(report.is_valid());
+        assert_eq!(report.verified_terms, vec!["data controller"]);
+
+        let unrelated = terminology.validate_translation("Records are kept.", "Des registres sont tenus.");
+        assert!(unrelated.is_valid());
+        assert!(unrelated.verified_terms.is_empty());
+    }
+}
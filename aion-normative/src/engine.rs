@@ -220,14 +220,22 @@ impl NormativeEngine {
         Ok(false)
     }
 
+    /// Check every dependency exists and is active
+    ///
+    /// A missing dependency is reported as `ReferenceUnresolved` at
+    /// `dependencies[i]`; callers that matched `NormativeNotFound` here must
+    /// match the new variant.
     fn process_dependencies(&mut self, framework: &mut NormativeFramework) -> AionResult<()> {
-        for dep_id in &framework.dependencies {
+        for (index, dep_id) in framework.dependencies.iter().enumerate() {
             let dep_framework = self.repository.get_framework(dep_id)?
-                .ok_or_else(|| AionError::NormativeNotFound { id: dep_id.0.to_string() })?;
+                .ok_or_else(|| AionError::ReferenceUnresolved {
+                    field: format!("dependencies[{}]", index),
+                    reference: dep_id.0.to_string(),
+                })?;
 
             if !dep_framework.is_active() {
                 return Err(AionError::ValidationError {
-                    field: "dependencies".to_string(),
+                    field: format!("dependencies[{}]", index),
                     message: format!("Dependency framework '{}' is not active", dep_framework.title),
                 });
             }
@@ -449,9 +457,9 @@ impl NormativeEngine {
             }
         }
 
-        Err(AionError::ValidationError {
+        Err(AionError::ReferenceUnresolved {
             field: "requirement_id".to_string(),
-            message: format!("Requirement not found: {}", requirement_id),
+            reference: requirement_id.to_string(),
         })
     }
 }
\ No newline at end of file
@@ -6,6 +6,19 @@ use std::collections::{HashMap, HashSet};
 pub struct HierarchyManager {
     hierarchy_cache: HashMap<String, FrameworkHierarchy>,
     jurisdiction_map: HashMap<Jurisdiction, Vec<NormativeId>>,
+    /// Explicit precedence: each framework maps to the frameworks it overrides
+    relationships: HashMap<NormativeId, Vec<NormativeId>>,
+    /// Precedence taken from the `supersedes` lists of the last built frameworks
+    supersessions: HashMap<NormativeId, Vec<NormativeId>>,
+}
+
+/// DFS state of a framework; frameworks not yet reached have none
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Visit {
+    /// On the current path, so reaching it again closes a cycle
+    InProgress,
+    /// Fully explored; every cycle through it has been reported
+    Done,
 }
 
 #[derive(Debug, Clone)]
@@ -48,12 +61,16 @@ impl HierarchyManager {
         Self {
             hierarchy_cache: HashMap::new(),
             jurisdiction_map: HashMap::new(),
+            relationships: HashMap::new(),
+            supersessions: HashMap::new(),
         }
     }
 
     pub fn build_hierarchy(&mut self, frameworks: &[NormativeFramework]) -> AionResult<()> {
-        // Clear existing mappings
+        // Clear existing mappings, so updated frameworks leave nothing stale behind
         self.jurisdiction_map.clear();
+        self.hierarchy_cache.clear();
+        self.supersessions.clear();
 
         // Group frameworks by jurisdiction
         for framework in frameworks {
@@ -63,6 +80,13 @@ impl HierarchyManager {
                 .push(framework.id.clone());
         }
 
+        // Supersession is recorded as given; `detect_cycles` reports any loops in the data
+        for framework in frameworks {
+            for superseded in &framework.supersedes {
+                record_edge(&mut self.supersessions, &framework.id, superseded);
+            }
+        }
+
         // Build hierarchical relationships
         for framework in frameworks {
             let hierarchy = self.analyze_framework_hierarchy(framework, frameworks)?;
@@ -72,6 +96,107 @@ impl HierarchyManager {
         Ok(())
     }
 
+    /// Record that `superior` takes precedence over `subordinate`
+    ///
+    /// Fails with `AionError::PrecedenceCycle` if `subordinate` already takes
+    /// precedence over `superior`, directly or through other frameworks.
+    pub fn add_relationship(&mut self, superior: &NormativeId, subordinate: &NormativeId) -> AionResult<()> {
+        if let Some(path) = self.precedence_path(subordinate, superior) {
+            let chain = std::iter::once(superior)
+                .chain(path.iter())
+                .map(|id| id.0.to_string())
+                .collect();
+            return Err(AionError::PrecedenceCycle { chain });
+        }
+
+        self.record_relationship(superior, subordinate);
+        Ok(())
+    }
+
+    /// Every precedence cycle, each listed from its smallest id with the
+    /// closing edge back to the first framework implied
+    pub fn detect_cycles(&self) -> Vec<Vec<NormativeId>> {
+        let mut roots: Vec<&NormativeId> = self.relationships.keys().chain(self.supersessions.keys()).collect();
+        roots.sort_by_key(|id| id.0);
+        roots.dedup();
+
+        let mut visits = HashMap::new();
+        let mut cycles = Vec::new();
+        for root in roots {
+            let mut stack = Vec::new();
+            self.collect_cycles(root, &mut stack, &mut visits, &mut cycles);
+        }
+
+        cycles.sort_by(|a, b| a.iter().map(|id| id.0).cmp(b.iter().map(|id| id.0)));
+        cycles
+    }
+
+    fn collect_cycles<'a>(
+        &'a self,
+        node: &'a NormativeId,
+        stack: &mut Vec<&'a NormativeId>,
+        visits: &mut HashMap<&'a NormativeId, Visit>,
+        cycles: &mut Vec<Vec<NormativeId>>,
+    ) {
+        match visits.get(node) {
+            Some(Visit::Done) => return,
+            Some(Visit::InProgress) => {
+                // A back edge: the cycle is the path from `node`'s first visit
+                let start = stack.iter().position(|id| *id == node).unwrap_or(0);
+                let mut cycle: Vec<NormativeId> = stack[start..].iter().map(|id| (*id).clone()).collect();
+                let smallest = (0..cycle.len()).min_by_key(|&i| cycle[i].0).unwrap_or(0);
+                cycle.rotate_left(smallest);
+                if !cycles.contains(&cycle) {
+                    cycles.push(cycle);
+                }
+                return;
+            }
+            None => {}
+        }
+
+        visits.insert(node, Visit::InProgress);
+        stack.push(node);
+        for next in self.subordinates(node) {
+            self.collect_cycles(next, stack, visits, cycles);
+        }
+        stack.pop();
+        visits.insert(node, Visit::Done);
+    }
+
+    /// Frameworks `id` takes precedence over, explicitly or by supersession
+    fn subordinates<'a>(&'a self, id: &NormativeId) -> impl Iterator<Item = &'a NormativeId> {
+        let explicit = self.relationships.get(id).into_iter().flatten();
+        let superseded = self.supersessions.get(id).into_iter().flatten();
+        explicit.chain(superseded)
+    }
+
+    /// Frameworks along a precedence chain from `from` down to `to`, inclusive
+    fn precedence_path(&self, from: &NormativeId, to: &NormativeId) -> Option<Vec<NormativeId>> {
+        let mut visited = HashSet::new();
+        let mut to_visit = vec![vec![from.clone()]];
+
+        while let Some(path) = to_visit.pop() {
+            let current = path.last().expect("paths are never empty");
+            if current == to {
+                return Some(path);
+            }
+            if !visited.insert(current.clone()) {
+                continue;
+            }
+            for next in self.subordinates(current) {
+                let mut extended = path.clone();
+                extended.push(next.clone());
+                to_visit.push(extended);
+            }
+        }
+
+        None
+    }
+
+    fn record_relationship(&mut self, superior: &NormativeId, subordinate: &NormativeId) {
+        record_edge(&mut self.relationships, superior, subordinate);
+    }
+
     fn analyze_framework_hierarchy(
         &self,
         framework: &NormativeFramework,
@@ -261,6 +386,18 @@ impl HierarchyManager {
         Ok(resolution)
     }
 
+    /// Precedence between two frameworks by jurisdiction level, or `None` when
+    /// both sit at the same level and the hierarchy cannot decide between them
+    pub fn precedence_between(&self, a: &NormativeFramework, b: &NormativeFramework) -> Option<ConflictResolution> {
+        let a_level = self.determine_jurisdiction_level(&a.jurisdiction);
+        let b_level = self.determine_jurisdiction_level(&b.jurisdiction);
+        if self.compare_jurisdiction_levels(&a_level, &b_level) == std::cmp::Ordering::Equal {
+            return None;
+        }
+
+        self.apply_resolution_strategy(&[a, b]).ok()
+    }
+
     fn apply_resolution_strategy(&self, frameworks: &[&NormativeFramework]) -> AionResult<ConflictResolution> {
         // Strategy 1: Higher jurisdiction precedence
         let mut frameworks_by_jurisdiction = frameworks.to_vec();
@@ -334,8 +471,108 @@ impl HierarchyManager {
     }
 }
 
+fn record_edge(edges: &mut HashMap<NormativeId, Vec<NormativeId>>, superior: &NormativeId, subordinate: &NormativeId) {
+    let subordinates = edges.entry(superior.clone()).or_default();
+    if !subordinates.contains(subordinate) {
+        subordinates.push(subordinate.clone());
+    }
+}
+
 impl Default for HierarchyManager {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_three_framework_cycle_is_rejected() {
+        let (a, b, c) = (NormativeId::new(), NormativeId::new(), NormativeId::new());
+        let mut hierarchy = HierarchyManager::new();
+
+        hierarchy.add_relationship(&a, &b).unwrap();
+        hierarchy.add_relationship(&b, &c).unwrap();
+        assert!(hierarchy.detect_cycles().is_empty());
+
+        match hierarchy.add_relationship(&c, &a) {
+            Err(AionError::PrecedenceCycle { chain }) => {
+                let expected: Vec<String> = [&c, &a, &b, &c].iter().map(|id| id.0.to_string()).collect();
+                assert_eq!(chain, expected);
+            }
+            other => panic!("expected a precedence cycle, got {:?}", other),
+        }
+        assert!(hierarchy.add_relationship(&a, &a).is_err());
+        assert!(hierarchy.detect_cycles().is_empty());
+    }
+
+    #[test]
+    fn test_supersession_cycle_is_detected() {
+        let mut frameworks: Vec<NormativeFramework> = ["A", "B", "C"]
+            .iter()
+            .map(|title| {
+                NormativeFramework::new(
+                    format!("Rule {}", title),
+                    "Supersession chain".to_string(),
+                    NormativeType::Regulation,
+                    Jurisdiction::Federal,
+                    "Parliament".to_string(),
+                )
+            })
+            .collect();
+        for i in 0..3 {
+            frameworks[i].supersedes = vec![frameworks[(i + 1) % 3].id.clone()];
+        }
+        let mut hierarchy = HierarchyManager::new();
+        hierarchy.build_hierarchy(&frameworks).unwrap();
+
+        let cycles = hierarchy.detect_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 3);
+        assert!(frameworks.iter().all(|framework| cycles[0].contains(&framework.id)));
+    }
+
+    #[test]
+    fn test_cycles_sharing_a_framework_are_all_detected() {
+        let (a, b, c) = (NormativeId::new(), NormativeId::new(), NormativeId::new());
+        let mut hierarchy = HierarchyManager::new();
+        hierarchy.record_relationship(&a, &b);
+        hierarchy.record_relationship(&b, &a);
+        hierarchy.record_relationship(&b, &c);
+        hierarchy.record_relationship(&c, &b);
+
+        let cycles = hierarchy.detect_cycles();
+        assert_eq!(cycles.len(), 2);
+        assert!(cycles.iter().any(|cycle| cycle.contains(&a) && cycle.contains(&b)));
+        assert!(cycles.iter().any(|cycle| cycle.contains(&b) && cycle.contains(&c)));
+    }
+
+    #[test]
+    fn test_rebuilding_drops_supersessions_that_were_removed() {
+        let mut frameworks: Vec<NormativeFramework> = ["A", "B"]
+            .iter()
+            .map(|title| {
+                NormativeFramework::new(
+                    format!("Rule {}", title),
+                    "Supersession chain".to_string(),
+                    NormativeType::Regulation,
+                    Jurisdiction::Federal,
+                    "Parliament".to_string(),
+                )
+            })
+            .collect();
+        frameworks[0].supersedes = vec![frameworks[1].id.clone()];
+        frameworks[1].supersedes = vec![frameworks[0].id.clone()];
+        let mut hierarchy = HierarchyManager::new();
+        hierarchy.build_hierarchy(&frameworks).unwrap();
+        assert_eq!(hierarchy.detect_cycles().len(), 1);
+
+        frameworks[1].supersedes.clear();
+        hierarchy.build_hierarchy(&frameworks).unwrap();
+        assert!(hierarchy.detect_cycles().is_empty());
+        let (first, second) = (frameworks[0].id.clone(), frameworks[1].id.clone());
+        assert!(hierarchy.add_relationship(&second, &first).is_err());
+    }
+}
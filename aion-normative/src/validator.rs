@@ -8,7 +8,7 @@ use chrono::Utc;
 
 pub struct ComprehensiveValidator {
     business_rules: Vec<BusinessRule>,
-    validation_cache: HashMap<String, Vec<String>>,
+    validation_cache: HashMap<String, Vec<AionError>>,
     custom_validators: HashMap<String, Box<dyn Fn(&str) -> bool + Send + Sync>>,
 }
 
@@ -234,36 +234,54 @@ impl ComprehensiveValidator {
 
     fn validate_framework_structure(&self, framework: &NormativeFramework, report: &mut ValidationReport) -> AionResult<()> {
         if framework.title.trim().is_empty() {
-            report.errors.push("Framework title cannot be empty".to_string());
-        } else if framework.title.len() < 10 {
-            report.errors.push("Framework title too short (minimum 10 characters)".to_string());
-        } else if framework.title.len() > 200 {
-            report.errors.push("Framework title too long (maximum 200 characters)".to_string());
+            report.errors.push(AionError::MissingField { field: "title".to_string() });
+        } else if framework.title.len() < 10 || framework.title.len() > 200 {
+            report.errors.push(AionError::OutOfRange {
+                field: "title".to_string(),
+                expected: "10 to 200 characters".to_string(),
+                actual: format!("{} characters", framework.title.len()),
+            });
         }
 
         if framework.description.trim().is_empty() {
-            report.errors.push("Framework description cannot be empty".to_string());
+            report.errors.push(AionError::MissingField { field: "description".to_string() });
         } else if framework.description.len() < 50 {
-            report.errors.push("Framework description too short (minimum 50 characters)".to_string());
+            report.errors.push(AionError::OutOfRange {
+                field: "description".to_string(),
+                expected: "at least 50 characters".to_string(),
+                actual: format!("{} characters", framework.description.len()),
+            });
         }
 
         if framework.authority.trim().is_empty() {
-            report.errors.push("Framework authority cannot be empty".to_string());
+            report.errors.push(AionError::MissingField { field: "authority".to_string() });
         }
 
         if !aion_core::validate_version(&framework.version) {
-            report.errors.push(format!("Invalid version format: {}", framework.version));
+            report.errors.push(AionError::TypeMismatch {
+                field: "version".to_string(),
+                expected: "version number".to_string(),
+                actual: framework.version.clone(),
+            });
         }
 
         let now = Utc::now();
         let max_future = now + chrono::Duration::days(5 * 365);
         if framework.effective_date > max_future {
-            report.errors.push("Effective date cannot be more than 5 years in the future".to_string());
+            report.errors.push(AionError::OutOfRange {
+                field: "effective_date".to_string(),
+                expected: format!("no later than {}", max_future.to_rfc3339()),
+                actual: framework.effective_date.to_rfc3339(),
+            });
         }
 
         if let Some(expiration) = framework.expiration_date {
             if expiration <= framework.effective_date {
-                report.errors.push("Expiration date must be after effective date".to_string());
+                report.errors.push(AionError::OutOfRange {
+                    field: "expiration_date".to_string(),
+                    expected: format!("after {}", framework.effective_date.to_rfc3339()),
+                    actual: expiration.to_rfc3339(),
+                });
             }
         }
 
@@ -287,11 +305,13 @@ impl ComprehensiveValidator {
                     report.validations_passed += 1;
                 },
                 Ok(false) => {
-                    let message = format!("Business rule violation: {}", rule.description);
                     if rule.priority <= 2 {
-                        report.errors.push(message);
+                        report.errors.push(AionError::BusinessRuleViolation {
+                            rule_name: rule.name.clone(),
+                            message: rule.description.clone(),
+                        });
                     } else {
-                        report.warnings.push(message);
+                        report.warnings.push(format!("Business rule violation: {}", rule.description));
                     }
                 },
                 Err(e) => {
@@ -306,21 +326,26 @@ impl ComprehensiveValidator {
     fn validate_requirements_comprehensive(&self, framework: &NormativeFramework, report: &mut ValidationReport) -> AionResult<()> {
         let mut requirement_titles = std::collections::HashSet::new();
 
-        for requirement in &framework.requirements {
-            let req_report = self.validate_requirement_detailed(requirement)?;
+        for (index, requirement) in framework.requirements.iter().enumerate() {
+            let path = format!("requirements[{}]", index);
+            let req_report = self.validate_requirement_at(requirement, &path)?;
             report.merge_requirement_report(req_report);
 
             if !requirement_titles.insert(&requirement.title) {
-                report.errors.push(format!("Duplicate requirement title: {}", requirement.title));
+                report.errors.push(AionError::ValidationError {
+                    field: format!("{}.title", path),
+                    message: format!("Duplicate requirement title: {}", requirement.title),
+                });
             }
 
             if requirement.mandatory && requirement.evidence_required.is_empty() {
                 report.warnings.push(format!("Mandatory requirement '{}' has no specified evidence requirements", requirement.title));
             }
 
-            for validation_rule in &requirement.validation_rules {
-                if let Err(e) = self.validate_validation_rule_syntax(&validation_rule.expression) {
-                    report.errors.push(format!("Invalid validation rule syntax in '{}': {}", requirement.title, e));
+            for (rule_index, validation_rule) in requirement.validation_rules.iter().enumerate() {
+                let field = format!("{}.validation_rules[{}].expression", path, rule_index);
+                if let Err(e) = self.validate_validation_rule_syntax(&validation_rule.expression, &field) {
+                    report.errors.push(e);
                 }
             }
         }
@@ -329,18 +354,24 @@ impl ComprehensiveValidator {
     }
 
     fn validate_requirement_detailed(&self, requirement: &Requirement) -> AionResult<RequirementValidationReport> {
+        self.validate_requirement_at(requirement, "")
+    }
+
+    /// Validate `requirement`, reporting fields under `path`, e.g. `requirements[3]`
+    fn validate_requirement_at(&self, requirement: &Requirement, path: &str) -> AionResult<RequirementValidationReport> {
         let mut report = RequirementValidationReport::new(requirement.id);
+        let field = |name: String| if path.is_empty() { name } else { format!("{}.{}", path, name) };
 
         if requirement.title.trim().is_empty() {
-            report.errors.push("Requirement title cannot be empty".to_string());
+            report.errors.push(AionError::MissingField { field: field("title".to_string()) });
         }
 
         if requirement.description.trim().is_empty() {
-            report.errors.push("Requirement description cannot be empty".to_string());
+            report.errors.push(AionError::MissingField { field: field("description".to_string()) });
         }
 
         if requirement.category.trim().is_empty() {
-            report.errors.push("Requirement category cannot be empty".to_string());
+            report.errors.push(AionError::MissingField { field: field("category".to_string()) });
         }
 
         if requirement.priority == 0 {
@@ -349,15 +380,15 @@ impl ComprehensiveValidator {
             report.warnings.push("Requirement priority unusually high".to_string());
         }
 
-        for condition in &requirement.conditions {
+        for (index, condition) in requirement.conditions.iter().enumerate() {
             if condition.expression.trim().is_empty() {
-                report.errors.push("Condition expression cannot be empty".to_string());
+                report.errors.push(AionError::MissingField { field: field(format!("conditions[{}].expression", index)) });
             }
         }
 
-        for exception in &requirement.exceptions {
+        for (index, exception) in requirement.exceptions.iter().enumerate() {
             if exception.description.trim().is_empty() {
-                report.errors.push("Exception description cannot be empty".to_string());
+                report.errors.push(AionError::MissingField { field: field(format!("exceptions[{}].description", index)) });
             }
 
             if let Some(valid_until) = exception.valid_until {
@@ -367,13 +398,17 @@ impl ComprehensiveValidator {
             }
         }
 
-        for validation_rule in &requirement.validation_rules {
+        for (index, validation_rule) in requirement.validation_rules.iter().enumerate() {
             if validation_rule.expression.trim().is_empty() {
-                report.errors.push("Validation rule expression cannot be empty".to_string());
+                report.errors.push(AionError::MissingField {
+                    field: field(format!("validation_rules[{}].expression", index)),
+                });
             }
 
             if validation_rule.error_message.trim().is_empty() {
-                report.errors.push("Validation rule error message cannot be empty".to_string());
+                report.errors.push(AionError::MissingField {
+                    field: field(format!("validation_rules[{}].error_message", index)),
+                });
             }
         }
 
@@ -382,15 +417,21 @@ impl ComprehensiveValidator {
     }
 
     fn validate_framework_consistency(&self, framework: &NormativeFramework, report: &mut ValidationReport) -> AionResult<()> {
-        for dep_id in &framework.dependencies {
+        for (index, dep_id) in framework.dependencies.iter().enumerate() {
             if dep_id == &framework.id {
-                report.errors.push("Framework cannot depend on itself".to_string());
+                report.errors.push(AionError::ValidationError {
+                    field: format!("dependencies[{}]", index),
+                    message: "Framework cannot depend on itself".to_string(),
+                });
             }
         }
 
-        for superseded_id in &framework.supersedes {
+        for (index, superseded_id) in framework.supersedes.iter().enumerate() {
             if superseded_id == &framework.id {
-                report.errors.push("Framework cannot supersede itself".to_string());
+                report.errors.push(AionError::ValidationError {
+                    field: format!("supersedes[{}]", index),
+                    message: "Framework cannot supersede itself".to_string(),
+                });
             }
         }
 
@@ -446,18 +487,18 @@ impl ComprehensiveValidator {
         Ok(())
     }
 
-    fn validate_validation_rule_syntax(&self, expression: &str) -> AionResult<()> {
+    /// Check a rule expression, reporting failures against `field`
+    fn validate_validation_rule_syntax(&self, expression: &str, field: &str) -> AionResult<()> {
         if expression.trim().is_empty() {
-            return Err(AionError::ValidationError {
-                field: "expression".to_string(),
-                message: "Expression cannot be empty".to_string(),
+            return Err(AionError::MissingField {
+                field: field.to_string(),
             });
         }
 
         let balanced_parens = self.check_balanced_parentheses(expression);
         if !balanced_parens {
             return Err(AionError::ValidationError {
-                field: "expression".to_string(),
+                field: field.to_string(),
                 message: "Unbalanced parentheses".to_string(),
             });
         }
@@ -467,7 +508,7 @@ impl ComprehensiveValidator {
 
         if !contains_valid_operator && !expression.contains("MATCHES") {
             return Err(AionError::ValidationError {
-                field: "expression".to_string(),
+                field: field.to_string(),
                 message: "Expression must contain at least one valid operator".to_string(),
             });
         }
@@ -552,12 +593,12 @@ impl Default for ComprehensiveValidator {
 impl ValidationEngine for ComprehensiveValidator {
     fn validate_framework(&self, framework: &NormativeFramework) -> AionResult<Vec<String>> {
         let report = self.validate_framework_comprehensive(framework)?;
-        Ok(report.errors)
+        Ok(report.errors.iter().map(ToString::to_string).collect())
     }
 
     fn validate_requirement(&self, requirement: &Requirement) -> AionResult<Vec<String>> {
         let report = self.validate_requirement_detailed(requirement)?;
-        Ok(report.errors)
+        Ok(report.errors.iter().map(ToString::to_string).collect())
     }
 
     fn validate_evidence(&self, evidence: &Evidence) -> AionResult<bool> {
@@ -729,7 +770,8 @@ impl ComprehensiveValidator {
 pub struct ValidationReport {
     pub overall_valid: bool,
     pub confidence_score: f64,
-    pub errors: Vec<String>,
+    /// Structured errors; `AionError::field_path` locates each one in the framework
+    pub errors: Vec<AionError>,
     pub warnings: Vec<String>,
     pub validations_passed: usize,
     pub requirement_reports: Vec<RequirementValidationReport>,
@@ -758,7 +800,7 @@ impl ValidationReport {
 pub struct RequirementValidationReport {
     pub requirement_id: uuid::Uuid,
     pub valid: bool,
-    pub errors: Vec<String>,
+    pub errors: Vec<AionError>,
     pub warnings: Vec<String>,
 }
 
@@ -771,4 +813,54 @@ impl RequirementValidationReport {
             warnings: Vec::new(),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aion_core::{Jurisdiction, NormativeType, ValidationRule};
+
+    #[test]
+    fn test_report_errors_carry_full_field_paths() {
+        let mut framework = NormativeFramework::new(
+            "Record Keeping Regulation".to_string(),
+            "Retention of books and records by regulated entities for supervisory review".to_string(),
+            NormativeType::Regulation,
+            Jurisdiction::Federal,
+            "Parliament".to_string(),
+        );
+        let rule = |expression: &str| ValidationRule {
+            id: uuid::Uuid::new_v4(),
+            name: "retention".to_string(),
+            rule_type: "expression".to_string(),
+            expression: expression.to_string(),
+            error_message: "Records must be retained".to_string(),
+            severity: "High".to_string(),
+        };
+        let mut requirement = Requirement {
+            id: uuid::Uuid::new_v4(),
+            title: "Retain records".to_string(),
+            description: "Keep records for five years".to_string(),
+            mandatory: true,
+            conditions: Vec::new(),
+            exceptions: Vec::new(),
+            evidence_required: vec!["retention schedule".to_string()],
+            validation_rules: vec![rule("retention_years >= 5")],
+            priority: 1,
+            category: "records".to_string(),
+        };
+        framework.requirements.push(requirement.clone());
+        requirement.title = "Dispose of records".to_string();
+        requirement.category = String::new();
+        requirement.validation_rules.push(rule("(retention_years >= 5"));
+        framework.requirements.push(requirement);
+
+        let report = ComprehensiveValidator::new().validate_framework_comprehensive(&framework).unwrap();
+        let paths: Vec<&str> = report.errors.iter().filter_map(AionError::field_path).collect();
+
+        assert!(!report.overall_valid);
+        assert!(paths.contains(&"requirements[1].category"));
+        assert!(paths.contains(&"requirements[1].validation_rules[1].expression"));
+        assert!(!paths.iter().any(|path| path.starts_with("requirements[0]")));
+    }
+}
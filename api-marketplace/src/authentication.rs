@@ -0,0 +1,402 @@
+/*!
+ * Authentication Manager
+ *
+ * Resolves the credentials each connector sends upstream. OAuth2 access
+ * tokens are cached with their expiry and refresh token, and refreshed
+ * ahead of expiry; refreshes are serialized per connector so concurrent
+ * requests share a single token fetch. Tokens issued without an expiry are
+ * refreshed after a default lifetime.
+ */
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, info, warn};
+
+use crate::connectors::{AuthenticationConfig, AuthenticationType, CredentialConfig};
+
+/// Refresh this long before expiry when the connector does not configure it
+const DEFAULT_REFRESH_SKEW_SECONDS: i64 = 60;
+
+/// Lifetime assumed for tokens the server issued without `expires_in`
+const DEFAULT_TOKEN_LIFETIME_SECONDS: i64 = 3600;
+
+/// A cached OAuth2 access token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuth2Token {
+    pub access_token: String,
+    pub token_type: String,
+    pub refresh_token: Option<String>,
+    /// `None` when the server did not say; such tokens are refreshed after
+    /// a default lifetime, or earlier if the API rejects them
+    pub expires_at: Option<DateTime<Utc>>,
+    pub obtained_at: DateTime<Utc>,
+}
+
+impl OAuth2Token {
+    /// When the token should be refreshed, given the refresh skew
+    pub fn refresh_at(&self, skew: chrono::Duration) -> DateTime<Utc> {
+        let expires_at = self
+            .expires_at
+            .unwrap_or_else(|| self.obtained_at + chrono::Duration::seconds(DEFAULT_TOKEN_LIFETIME_SECONDS));
+        expires_at - skew
+    }
+
+    pub fn needs_refresh(&self, now: DateTime<Utc>, skew: chrono::Duration) -> bool {
+        now >= self.refresh_at(skew)
+    }
+}
+
+/// Token endpoint response (RFC 6749 section 5.1)
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default = "default_token_type")]
+    token_type: String,
+    expires_in: Option<i64>,
+    refresh_token: Option<String>,
+}
+
+fn default_token_type() -> String {
+    "Bearer".to_string()
+}
+
+/// Token state for one connector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenStatus {
+    pub expires_at: Option<DateTime<Utc>>,
+    pub next_refresh_at: Option<DateTime<Utc>>,
+    pub has_refresh_token: bool,
+}
+
+/// Authentication manager status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthenticationStatus {
+    pub managed_tokens: u32,
+    pub refreshes: u64,
+    pub failed_refreshes: u64,
+    /// Earliest upcoming refresh across all connectors
+    pub next_refresh_at: Option<DateTime<Utc>>,
+    pub tokens: HashMap<String, TokenStatus>,
+}
+
+/// Authentication manager
+pub struct AuthenticationManager {
+    /// Marketplace-wide defaults, such as the refresh skew
+    config: AuthenticationConfig,
+
+    /// OAuth2 tokens by connector
+    tokens: Arc<RwLock<HashMap<String, OAuth2Token>>>,
+
+    /// Refresh skew by connector, recorded when its token is fetched
+    refresh_skews: Arc<RwLock<HashMap<String, chrono::Duration>>>,
+
+    /// Per-connector locks serializing token refreshes
+    refresh_locks: Arc<RwLock<HashMap<String, Arc<Mutex<()>>>>>,
+
+    refreshes: AtomicU64,
+    failed_refreshes: AtomicU64,
+    client: reqwest::Client,
+}
+
+impl AuthenticationManager {
+    pub async fn new(config: AuthenticationConfig) -> Result<Self> {
+        info!("🔐 Initializing authentication manager");
+
+        Ok(Self {
+            config,
+            tokens: Arc::new(RwLock::new(HashMap::new())),
+            refresh_skews: Arc::new(RwLock::new(HashMap::new())),
+            refresh_locks: Arc::new(RwLock::new(HashMap::new())),
+            refreshes: AtomicU64::new(0),
+            failed_refreshes: AtomicU64::new(0),
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()?,
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("🚀 Starting authentication manager");
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        info!("🛑 Stopping authentication manager");
+        Ok(())
+    }
+
+    /// Headers a connector must send, refreshing its OAuth2 token if due
+    pub async fn get_auth_headers(
+        &self,
+        connector_id: &str,
+        auth: &AuthenticationConfig,
+    ) -> Result<HashMap<String, String>> {
+        let mut headers = auth.headers.clone();
+
+        match (&auth.auth_type, &auth.credentials) {
+            (_, CredentialConfig::ApiKey { key, header }) => {
+                headers.insert(header.clone(), key.clone());
+            }
+            (_, CredentialConfig::Bearer { token }) => {
+                headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+            }
+            (_, CredentialConfig::BasicAuth { username, password }) => {
+                let encoded = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+                headers.insert("Authorization".to_string(), format!("Basic {}", encoded));
+            }
+            (AuthenticationType::OAuth2, CredentialConfig::OAuth2 { .. }) => {
+                let token = self.get_access_token(connector_id, auth).await?;
+                headers.insert("Authorization".to_string(), format!("{} {}", token.token_type, token.access_token));
+            }
+            _ => {}
+        }
+
+        Ok(headers)
+    }
+
+    /// A valid OAuth2 access token, refreshed when within the skew of expiry
+    pub async fn get_access_token(&self, connector_id: &str, auth: &AuthenticationConfig) -> Result<OAuth2Token> {
+        let skew = self.refresh_skew(auth);
+        if let Some(token) = self.cached_token(connector_id, skew).await {
+            return Ok(token);
+        }
+
+        let lock = self.refresh_lock(connector_id).await;
+        let _refreshing = lock.lock().await;
+
+        // Another request may have refreshed while this one waited
+        if let Some(token) = self.cached_token(connector_id, skew).await {
+            return Ok(token);
+        }
+        self.fetch_token(connector_id, auth, skew).await
+    }
+
+    /// Force a token refresh, e.g. after the API rejected the current token
+    ///
+    /// Requests that ask concurrently share one refresh.
+    pub async fn refresh_credentials(&self, connector_id: &str, auth: &AuthenticationConfig) -> Result<()> {
+        if !matches!(auth.credentials, CredentialConfig::OAuth2 { .. }) {
+            return Ok(());
+        }
+
+        let requested_at = Utc::now();
+        let lock = self.refresh_lock(connector_id).await;
+        let _refreshing = lock.lock().await;
+
+        let refreshed_meanwhile = self
+            .tokens
+            .read()
+            .await
+            .get(connector_id)
+            .is_some_and(|token| token.obtained_at >= requested_at);
+        if !refreshed_meanwhile {
+            self.fetch_token(connector_id, auth, self.refresh_skew(auth)).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn get_status(&self) -> Result<AuthenticationStatus> {
+        let tokens = self.tokens.read().await;
+        let skews = self.refresh_skews.read().await;
+        let default_skew = self.refresh_skew(&self.config);
+
+        let tokens: HashMap<String, TokenStatus> = tokens
+            .iter()
+            .map(|(connector_id, token)| {
+                let skew = skews.get(connector_id).copied().unwrap_or(default_skew);
+                (connector_id.clone(), TokenStatus {
+                    expires_at: token.expires_at,
+                    next_refresh_at: Some(token.refresh_at(skew)),
+                    has_refresh_token: token.refresh_token.is_some(),
+                })
+            })
+            .collect();
+
+        Ok(AuthenticationStatus {
+            managed_tokens: tokens.len() as u32,
+            refreshes: self.refreshes.load(Ordering::Relaxed),
+            failed_refreshes: self.failed_refreshes.load(Ordering::Relaxed),
+            next_refresh_at: tokens.values().filter_map(|token| token.next_refresh_at).min(),
+            tokens,
+        })
+    }
+
+    async fn cached_token(&self, connector_id: &str, skew: chrono::Duration) -> Option<OAuth2Token> {
+        self.tokens
+            .read()
+            .await
+            .get(connector_id)
+            .filter(|token| !token.needs_refresh(Utc::now(), skew))
+            .cloned()
+    }
+
+    /// Fetch a new token; the caller holds the connector's refresh lock
+    async fn fetch_token(&self, connector_id: &str, auth: &AuthenticationConfig, skew: chrono::Duration) -> Result<OAuth2Token> {
+        let previous_refresh_token = self
+            .tokens
+            .read()
+            .await
+            .get(connector_id)
+            .and_then(|token| token.refresh_token.clone());
+
+        let mut result = self.request_token(auth, previous_refresh_token.as_deref()).await;
+        if result.is_err() && previous_refresh_token.is_some() {
+            // The refresh token may have been revoked; start over with the client credentials
+            warn!("⚠️ Refresh token rejected for connector {}, requesting a new token", connector_id);
+            result = self.request_token(auth, None).await;
+        }
+
+        let response = match result {
+            Ok(response) => {
+                self.refreshes.fetch_add(1, Ordering::Relaxed);
+                response
+            }
+            Err(e) => {
+                self.failed_refreshes.fetch_add(1, Ordering::Relaxed);
+                return Err(e.context(format!("OAuth2 token refresh failed for connector {}", connector_id)));
+            }
+        };
+
+        let now = Utc::now();
+        let token = OAuth2Token {
+            access_token: response.access_token,
+            token_type: response.token_type,
+            refresh_token: response.refresh_token.or(previous_refresh_token),
+            expires_at: response.expires_in.map(|seconds| now + chrono::Duration::seconds(seconds)),
+            obtained_at: now,
+        };
+
+        debug!("🔑 Refreshed OAuth2 token for connector {}, next refresh at {:?}", connector_id, token.refresh_at(skew));
+        self.refresh_skews.write().await.insert(connector_id.to_string(), skew);
+        self.tokens.write().await.insert(connector_id.to_string(), token.clone());
+        Ok(token)
+    }
+
+    async fn request_token(&self, auth: &AuthenticationConfig, refresh_token: Option<&str>) -> Result<TokenResponse> {
+        let CredentialConfig::OAuth2 { client_id, client_secret, scope } = &auth.credentials else {
+            anyhow::bail!("connector does not use OAuth2 credentials");
+        };
+        let token_url = &auth
+            .token_refresh
+            .as_ref()
+            .context("OAuth2 connector has no token_refresh.refresh_url")?
+            .refresh_url;
+
+        let mut form = vec![
+            ("client_id", client_id.as_str()),
+            ("client_secret", client_secret.as_str()),
+        ];
+        match refresh_token {
+            Some(refresh_token) => {
+                form.push(("grant_type", "refresh_token"));
+                form.push(("refresh_token", refresh_token));
+            }
+            None => form.push(("grant_type", "client_credentials")),
+        }
+        if let Some(scope) = scope {
+            form.push(("scope", scope.as_str()));
+        }
+
+        let response = self.client.post(token_url).form(&form).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("token endpoint responded with HTTP {}", response.status().as_u16());
+        }
+        Ok(response.json().await?)
+    }
+
+    fn refresh_skew(&self, auth: &AuthenticationConfig) -> chrono::Duration {
+        auth.token_refresh
+            .as_ref()
+            .or(self.config.token_refresh.as_ref())
+            .map(|refresh| refresh.refresh_before_expiry)
+            .unwrap_or_else(|| chrono::Duration::seconds(DEFAULT_REFRESH_SKEW_SECONDS))
+    }
+
+    async fn refresh_lock(&self, connector_id: &str) -> Arc<Mutex<()>> {
+        if let Some(lock) = self.refresh_locks.read().await.get(connector_id) {
+            return lock.clone();
+        }
+        self.refresh_locks
+            .write()
+            .await
+            .entry(connector_id.to_string())
+            .or_default()
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oauth2_config() -> AuthenticationConfig {
+        AuthenticationConfig {
+            auth_type: AuthenticationType::OAuth2,
+            credentials: CredentialConfig::OAuth2 {
+                client_id: "aion".to_string(),
+                client_secret: "secret".to_string(),
+                scope: None,
+            },
+            token_refresh: Some(crate::connectors::TokenRefreshConfig {
+                refresh_url: "http://127.0.0.1:9/oauth/token".to_string(),
+                refresh_interval: chrono::Duration::hours(1),
+                refresh_before_expiry: chrono::Duration::seconds(30),
+            }),
+            headers: HashMap::new(),
+        }
+    }
+
+    fn token(expires_in: i64) -> OAuth2Token {
+        OAuth2Token {
+            access_token: "at-1".to_string(),
+            token_type: "Bearer".to_string(),
+            refresh_token: Some("rt-1".to_string()),
+            expires_at: Some(Utc::now() + chrono::Duration::seconds(expires_in)),
+            obtained_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_valid_token_is_reused_and_status_shows_next_refresh() {
+        let manager = AuthenticationManager::new(AuthenticationConfig::default()).await.unwrap();
+        let cached = token(600);
+        manager.tokens.write().await.insert("fca-api".to_string(), cached.clone());
+        manager.refresh_skews.write().await.insert("fca-api".to_string(), chrono::Duration::seconds(30));
+
+        let headers = manager.get_auth_headers("fca-api", &oauth2_config()).await.unwrap();
+        assert_eq!(headers["Authorization"], "Bearer at-1");
+
+        let status = manager.get_status().await.unwrap();
+        assert_eq!(status.next_refresh_at, Some(cached.refresh_at(chrono::Duration::seconds(30))));
+        assert_eq!(status.refreshes, 0);
+    }
+
+    #[test]
+    fn test_token_without_expiry_is_refreshed_after_default_lifetime() {
+        let mut token = token(0);
+        token.expires_at = None;
+        let skew = chrono::Duration::seconds(30);
+
+        assert!(!token.needs_refresh(token.obtained_at + chrono::Duration::minutes(30), skew));
+        assert!(token.needs_refresh(token.obtained_at + chrono::Duration::seconds(DEFAULT_TOKEN_LIFETIME_SECONDS), skew));
+    }
+
+    #[tokio::test]
+    async fn test_token_within_skew_is_refreshed() {
+        let manager = AuthenticationManager::new(AuthenticationConfig::default()).await.unwrap();
+        // Expires in 10s, inside the 30s skew, so it must not be sent
+        manager.tokens.write().await.insert("fca-api".to_string(), token(10));
+
+        // Nothing serves the token endpoint, so the refresh fails instead of returning the stale token
+        let error = manager.get_auth_headers("fca-api", &oauth2_config()).await.unwrap_err();
+        assert!(error.to_string().contains("OAuth2 token refresh failed"));
+        assert_eq!(manager.get_status().await.unwrap().failed_refreshes, 1);
+    }
+}
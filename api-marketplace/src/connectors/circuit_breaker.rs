@@ -0,0 +1,208 @@
+/*!
+ * Circuit Breaker
+ *
+ * Per-connector circuit breaker that stops sending requests to an upstream
+ * API after repeated failures, then probes it before resuming traffic.
+ */
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+/// Circuit breaker configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe is allowed
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Circuit breaker states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests are short-circuited without reaching the API
+    Open,
+    /// A single probe request is allowed through to test recovery
+    HalfOpen,
+}
+
+/// A change of circuit state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitTransition {
+    pub from: CircuitState,
+    pub to: CircuitState,
+}
+
+/// Circuit breaker for one connector
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+            probe_in_flight: false,
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.state
+    }
+
+    /// Ask to send a request
+    ///
+    /// Returns the transition to HalfOpen when this request becomes the
+    /// probe, or `Err` with the time until a probe is allowed while the
+    /// circuit rejects requests.
+    pub fn try_acquire(&mut self) -> Result<Option<CircuitTransition>, Duration> {
+        match self.state {
+            CircuitState::Closed => Ok(None),
+            CircuitState::HalfOpen if self.probe_in_flight => Err(Duration::ZERO),
+            CircuitState::HalfOpen => {
+                self.probe_in_flight = true;
+                Ok(None)
+            }
+            CircuitState::Open => {
+                let elapsed = self.opened_at.map(|opened_at| opened_at.elapsed()).unwrap_or_default();
+                if elapsed < self.config.open_duration {
+                    return Err(self.config.open_duration - elapsed);
+                }
+                self.probe_in_flight = true;
+                Ok(self.transition(CircuitState::HalfOpen))
+            }
+        }
+    }
+
+    /// Record a successful request
+    pub fn record_success(&mut self) -> Option<CircuitTransition> {
+        self.consecutive_failures = 0;
+        self.probe_in_flight = false;
+        self.opened_at = None;
+        self.transition(CircuitState::Closed)
+    }
+
+    /// Give back a half-open probe that ended without an outcome
+    ///
+    /// The circuit stays half-open and the next request becomes the probe.
+    pub fn release_probe(&mut self) {
+        self.probe_in_flight = false;
+    }
+
+    /// Record a failed request
+    pub fn record_failure(&mut self) -> Option<CircuitTransition> {
+        self.consecutive_failures += 1;
+        self.probe_in_flight = false;
+
+        let should_open = match self.state {
+            CircuitState::HalfOpen => true,
+            CircuitState::Closed => self.consecutive_failures >= self.config.failure_threshold,
+            CircuitState::Open => false,
+        };
+        if !should_open {
+            return None;
+        }
+
+        self.opened_at = Some(Instant::now());
+        self.transition(CircuitState::Open)
+    }
+
+    fn transition(&mut self, to: CircuitState) -> Option<CircuitTransition> {
+        if self.state == to {
+            return None;
+        }
+        let from = std::mem::replace(&mut self.state, to);
+        Some(CircuitTransition { from, to })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            open_duration: Duration::from_secs(30),
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_opens_after_threshold_and_recovers_through_probe() {
+        let mut breaker = breaker();
+        assert_eq!(breaker.record_failure(), None);
+        assert_eq!(breaker.record_failure(), None);
+        assert_eq!(
+            breaker.record_failure(),
+            Some(CircuitTransition { from: CircuitState::Closed, to: CircuitState::Open })
+        );
+        assert!(breaker.try_acquire().is_err());
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert_eq!(
+            breaker.try_acquire(),
+            Ok(Some(CircuitTransition { from: CircuitState::Open, to: CircuitState::HalfOpen }))
+        );
+        // Only one probe at a time
+        assert!(breaker.try_acquire().is_err());
+
+        assert_eq!(
+            breaker.record_success(),
+            Some(CircuitTransition { from: CircuitState::HalfOpen, to: CircuitState::Closed })
+        );
+        assert_eq!(breaker.try_acquire(), Ok(None));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_probe_reopens_circuit() {
+        let mut breaker = breaker();
+        for _ in 0..3 {
+            breaker.record_failure();
+        }
+        tokio::time::advance(Duration::from_secs(31)).await;
+        breaker.try_acquire().unwrap();
+
+        assert_eq!(
+            breaker.record_failure(),
+            Some(CircuitTransition { from: CircuitState::HalfOpen, to: CircuitState::Open })
+        );
+        assert_eq!(breaker.try_acquire(), Err(Duration::from_secs(30)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_released_probe_lets_the_next_request_probe() {
+        let mut breaker = breaker();
+        for _ in 0..3 {
+            breaker.record_failure();
+        }
+        tokio::time::advance(Duration::from_secs(30)).await;
+        breaker.try_acquire().unwrap();
+        assert!(breaker.try_acquire().is_err());
+
+        breaker.release_probe();
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert_eq!(breaker.try_acquire(), Ok(None));
+        assert!(breaker.try_acquire().is_err());
+    }
+}
@@ -11,9 +11,13 @@ pub mod healthcare;
 pub mod international;
 pub mod industry_standards;
 pub mod registry;
+pub mod circuit_breaker;
+pub mod sync;
 
 // Re-export main types
 pub use registry::*;
+pub use circuit_breaker::*;
+pub use sync::*;
 
 use std::sync::Arc;
 use std::collections::HashMap;
@@ -63,6 +67,18 @@ pub struct ConnectorConfig {
 
     /// Health check configuration
     pub health_check: HealthCheckConfig,
+
+    /// Circuit breaker configuration
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
+
+    /// Incremental sync configuration; connectors without one are not synced
+    #[serde(default)]
+    pub sync: Option<SyncConfig>,
+
+    /// OpenAPI 3 document (JSON or YAML) used to generate typed SDKs
+    #[serde(default)]
+    pub openapi_spec: Option<String>,
 }
 
 /// Connector categories
@@ -138,6 +154,9 @@ pub struct RateLimitConfig {
     pub requests_per_day: Option<u32>,
     pub burst_size: Option<u32>,
     pub retry_after_header: Option<String>,
+    /// Longest a queued request waits for a token before failing
+    #[serde(default)]
+    pub max_queue_wait: Option<std::time::Duration>,
 }
 
 impl Default for RateLimitConfig {
@@ -149,6 +168,7 @@ impl Default for RateLimitConfig {
             requests_per_day: Some(10000),
             burst_size: Some(50),
             retry_after_header: Some("Retry-After".to_string()),
+            max_queue_wait: Some(std::time::Duration::from_secs(30)),
         }
     }
 }
@@ -265,6 +285,16 @@ pub struct WebhookConfig {
     pub event_types: Vec<String>,
     pub signature_verification: Option<SignatureConfig>,
     pub retry_policy: RetryPolicy,
+    /// Where undelivered outbound events are persisted; in memory only when `None`
+    #[serde(default)]
+    pub outbox_directory: Option<std::path::PathBuf>,
+    /// Deliveries POSTed concurrently by one delivery pass
+    #[serde(default = "default_max_concurrent_deliveries")]
+    pub max_concurrent_deliveries: usize,
+}
+
+fn default_max_concurrent_deliveries() -> usize {
+    8
 }
 
 impl Default for WebhookConfig {
@@ -275,6 +305,8 @@ impl Default for WebhookConfig {
             event_types: vec![],
             signature_verification: None,
             retry_policy: RetryPolicy::default(),
+            outbox_directory: None,
+            max_concurrent_deliveries: default_max_concurrent_deliveries(),
         }
     }
 }
@@ -444,6 +476,7 @@ impl ConnectorConfig {
                 requests_per_day: Some(10000),
                 burst_size: Some(20),
                 retry_after_header: Some("Retry-After".to_string()),
+                max_queue_wait: None,
             },
             endpoints: vec![
                 EndpointConfig {
@@ -500,6 +533,9 @@ impl ConnectorConfig {
                 expected_status: 200,
                 expected_response: None,
             },
+            circuit_breaker: CircuitBreakerConfig::default(),
+            sync: None,
+            openapi_spec: None,
         }
     }
 
@@ -533,6 +569,7 @@ impl ConnectorConfig {
                 requests_per_day: Some(1000),
                 burst_size: Some(5),
                 retry_after_header: Some("Retry-After".to_string()),
+                max_queue_wait: None,
             },
             endpoints: vec![
                 EndpointConfig {
@@ -580,6 +617,9 @@ impl ConnectorConfig {
                 expected_status: 200,
                 expected_response: None,
             },
+            circuit_breaker: CircuitBreakerConfig::default(),
+            sync: None,
+            openapi_spec: None,
         }
     }
 
@@ -619,14 +659,46 @@ impl ConnectorConfig {
                 expected_status: 200,
                 expected_response: None,
             },
+            circuit_breaker: CircuitBreakerConfig::default(),
+            sync: None,
+            openapi_spec: None,
         }
     }
 
     // Additional connector configurations would be implemented here...
+    /// Create configuration for the OFAC sanctions list exports
+    ///
+    /// `sdn` is the legacy SDN.CSV export; remarks too long for it continue
+    /// under the same entity number in `sdn_comments`.
+    pub fn treasury_ofac() -> Self {
+        let csv_export = |name: &str, path: &str, description: &str| EndpointConfig {
+            name: name.to_string(),
+            path: path.to_string(),
+            method: HttpMethod::GET,
+            description: description.to_string(),
+            parameters: vec![],
+            response_format: ResponseFormat::CSV,
+            cache_ttl: Some(chrono::Duration::hours(6)),
+            requires_auth: false,
+        };
+
+        let mut config = Self::default_government("treasury-ofac", "Treasury OFAC", "US");
+        config.description = "OFAC Specially Designated Nationals and Blocked Persons list".to_string();
+        config.base_url = "https://sanctionslistservice.ofac.treas.gov/api/PublicationPreview/exports".to_string();
+        config.endpoints = vec![
+            csv_export("sdn", "/SDN.CSV", "SDN entries, remarks truncated to the export's field limit"),
+            csv_export("sdn_comments", "/SDN_COMMENTS.CSV", "Remarks continued from SDN.CSV, by entity number"),
+        ];
+        config.metadata.compliance_frameworks = vec!["Sanctions".to_string(), "AML".to_string()];
+        config.metadata.data_categories = vec!["SDN".to_string(), "Digital Currency Addresses".to_string()];
+        config.metadata.documentation_url = Some("https://ofac.treasury.gov/sanctions-list-service".to_string());
+        config.health_check.endpoint = "/SDN.CSV".to_string();
+        config
+    }
+
     pub fn cftc_api() -> Self { Self::default_government("cftc-api", "CFTC API", "US") }
     pub fn finra_api() -> Self { Self::default_financial("finra-api", "FINRA API", "US") }
     pub fn fda_api() -> Self { Self::default_healthcare("fda-api", "FDA API", "US") }
-    pub fn treasury_ofac() -> Self { Self::default_government("treasury-ofac", "Treasury OFAC", "US") }
     pub fn ema_api() -> Self { Self::default_healthcare("ema-api", "EMA API", "EU") }
     pub fn esma_api() -> Self { Self::default_financial("esma-api", "ESMA API", "EU") }
     pub fn eba_api() -> Self { Self::default_financial("eba-api", "EBA API", "EU") }
@@ -683,6 +755,9 @@ impl ConnectorConfig {
                 expected_status: 200,
                 expected_response: None,
             },
+            circuit_breaker: CircuitBreakerConfig::default(),
+            sync: None,
+            openapi_spec: None,
         }
     }
 
@@ -752,6 +827,9 @@ impl Default for ConnectorConfig {
                 expected_status: 200,
                 expected_response: None,
             },
+            circuit_breaker: CircuitBreakerConfig::default(),
+            sync: None,
+            openapi_spec: None,
         }
     }
 }
\ No newline at end of file
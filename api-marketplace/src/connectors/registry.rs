@@ -19,6 +19,7 @@ use crate::authentication::AuthenticationManager;
 use crate::rate_limiting::RateLimitingService;
 use crate::cache::CacheLayer;
 use crate::monitoring::MarketplaceMonitor;
+use crate::error::MarketplaceError;
 
 /// Connector Registry
 ///
@@ -37,6 +38,9 @@ pub struct ConnectorRegistry {
     /// Connector health status
     health_status: Arc<RwLock<HashMap<String, HealthStatus>>>,
 
+    /// Circuit breakers by connector
+    circuit_breakers: Arc<RwLock<HashMap<String, CircuitBreaker>>>,
+
     /// Authentication manager
     auth_manager: Arc<AuthenticationManager>,
 
@@ -57,6 +61,9 @@ pub struct ConnectorRegistry {
 
     /// Health monitor
     health_monitor: Arc<HealthMonitor>,
+
+    /// Incremental sync cursors
+    cursor_store: Arc<CursorStore>,
 }
 
 /// Connection metrics
@@ -81,6 +88,8 @@ pub struct ConnectorRegistryStatus {
     pub requests_per_minute: u64,
     pub average_response_time: chrono::Duration,
     pub last_health_check: DateTime<Utc>,
+    /// Circuit breaker state by connector
+    pub circuit_states: HashMap<String, CircuitState>,
 }
 
 impl ConnectorRegistry {
@@ -96,12 +105,14 @@ impl ConnectorRegistry {
 
         let load_balancer = Arc::new(LoadBalancer::new().await?);
         let health_monitor = Arc::new(HealthMonitor::new().await?);
+        let cursor_store = Arc::new(CursorStore::open(config.sync_directory.clone()).await?);
 
         Ok(Self {
             config,
             connectors: Arc::new(RwLock::new(HashMap::new())),
             connector_configs: Arc::new(RwLock::new(HashMap::new())),
             health_status: Arc::new(RwLock::new(HashMap::new())),
+            circuit_breakers: Arc::new(RwLock::new(HashMap::new())),
             auth_manager,
             rate_limiter,
             cache,
@@ -109,6 +120,7 @@ impl ConnectorRegistry {
             active_connections: Arc::new(RwLock::new(HashMap::new())),
             load_balancer,
             health_monitor,
+            cursor_store,
         })
     }
 
@@ -163,6 +175,15 @@ impl ConnectorRegistry {
             configs.insert(connector_id.clone(), config.clone());
         }
 
+        // Apply the connector's own rate limits
+        self.rate_limiter.register_connector(&connector_id, &config.rate_limits).await?;
+
+        // Start with a closed circuit
+        {
+            let mut breakers = self.circuit_breakers.write().await;
+            breakers.insert(connector_id.clone(), CircuitBreaker::new(config.circuit_breaker.clone()));
+        }
+
         // Create and initialize connector
         let connector = self.create_connector(config).await?;
 
@@ -237,6 +258,11 @@ impl ConnectorRegistry {
             health.remove(connector_id);
         }
 
+        {
+            let mut breakers = self.circuit_breakers.write().await;
+            breakers.remove(connector_id);
+        }
+
         {
             let mut connections = self.active_connections.write().await;
             connections.remove(connector_id);
@@ -247,6 +273,10 @@ impl ConnectorRegistry {
     }
 
     /// Execute a request through a connector
+    ///
+    /// Fails immediately with `MarketplaceError::RateLimitExceeded` when the
+    /// connector's rate limit is exhausted, or `MarketplaceError::CircuitOpen`
+    /// while the connector's circuit breaker is rejecting requests.
     pub async fn execute_request(
         &self,
         connector_id: &str,
@@ -258,49 +288,32 @@ impl ConnectorRegistry {
         // Check rate limits
         self.rate_limiter.check_rate_limit(connector_id).await?;
 
-        // Check cache first
-        let cache_key = format!("{}:{}:{:?}", connector_id, endpoint, parameters);
-        if let Some(cached_response) = self.cache.get(&cache_key).await? {
-            debug!("💾 Returning cached response for {}", connector_id);
-            return Ok(cached_response);
-        }
-
-        // Get connector
-        let connector = {
-            let connectors = self.connectors.read().await;
-            connectors.get(connector_id)
-                .ok_or_else(|| anyhow::anyhow!("Connector not found: {}", connector_id))?
-                .clone()
-        };
-
-        // Execute request
-        let start_time = Utc::now();
-        let result = connector.execute_request(endpoint, &parameters).await;
-        let response_time = Utc::now().signed_duration_since(start_time);
-
-        // Update metrics
-        self.update_connection_metrics(connector_id, &result, response_time).await?;
-
-        // Handle result
-        match result {
-            Ok(response) => {
-                // Cache successful response if configured
-                if let Some(ttl) = self.get_cache_ttl(connector_id, endpoint).await? {
-                    self.cache.set(&cache_key, &response, ttl).await?;
-                }
+        self.dispatch_request(connector_id, endpoint, parameters).await
+    }
 
-                debug!("✅ Request successful for connector: {}", connector_id);
-                Ok(response)
-            }
-            Err(e) => {
-                error!("❌ Request failed for connector: {} error: {}", connector_id, e);
+    /// Execute a request through a connector, queueing it while rate limited
+    ///
+    /// The request waits for the connector's rate limit to refill for at most
+    /// `timeout`, or the connector's configured maximum queue wait when `None`.
+    /// A request that is never released fails with
+    /// `MarketplaceError::RateLimitTimeout`; failures of the API call itself
+    /// surface as `MarketplaceError::UpstreamApi`.
+    pub async fn execute_request_queued(
+        &self,
+        connector_id: &str,
+        endpoint: &str,
+        parameters: crate::ApiParameters,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<ApiResponse> {
+        debug!("📡 Queueing request to connector: {} endpoint: {}", connector_id, endpoint);
 
-                // Update health status
-                self.update_health_status(connector_id, false, Some(e.to_string())).await?;
+        let max_wait = match timeout {
+            Some(timeout) => timeout,
+            None => self.rate_limiter.max_queue_wait(connector_id).await,
+        };
+        self.rate_limiter.acquire_queued(connector_id, max_wait).await?;
 
-                Err(e)
-            }
-        }
+        self.dispatch_request(connector_id, endpoint, parameters).await
     }
 
     /// Search connectors
@@ -386,6 +399,14 @@ impl ConnectorRegistry {
         Ok(results)
     }
 
+    /// Get a registered connector's configuration
+    pub async fn get_connector_config(&self, connector_id: &str) -> Result<ConnectorConfig> {
+        let configs = self.connector_configs.read().await;
+        configs.get(connector_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Connector not found: {}", connector_id))
+    }
+
     /// Get connector count
     pub async fn get_connector_count(&self) -> Result<u32> {
         let connectors = self.connectors.read().await;
@@ -404,6 +425,10 @@ impl ConnectorRegistry {
         let connectors = self.connectors.read().await;
         let health = self.health_status.read().await;
         let connections = self.active_connections.read().await;
+        let circuit_states = self.circuit_breakers.read().await
+            .iter()
+            .map(|(id, breaker)| (id.clone(), breaker.state()))
+            .collect();
 
         let total_connectors = connectors.len() as u32;
         let healthy_connectors = health.values().filter(|h| h.healthy).count() as u32;
@@ -435,6 +460,7 @@ impl ConnectorRegistry {
             requests_per_minute,
             average_response_time,
             last_health_check: Utc::now(),
+            circuit_states,
         })
     }
 
@@ -510,13 +536,26 @@ impl ConnectorRegistry {
     }
 
     /// Sync all data
+    ///
+    /// Always a full resync; see `sync_incremental` to resume from cursors.
     pub async fn sync_all_data(&self) -> Result<crate::SyncResult> {
-        info!("🔄 Starting data synchronization for all connectors");
+        self.sync_with_mode(SyncMode::Full).await
+    }
+
+    /// Sync only records newer than each connector's stored cursor
+    pub async fn sync_incremental(&self) -> Result<crate::SyncResult> {
+        self.sync_with_mode(SyncMode::Incremental).await
+    }
+
+    /// Sync every connector in the given mode
+    pub async fn sync_with_mode(&self, mode: SyncMode) -> Result<crate::SyncResult> {
+        info!("🔄 Starting {:?} data synchronization for all connectors", mode);
 
         let start_time = Utc::now();
         let mut synced_apis = 0u32;
         let mut failed_syncs = 0u32;
         let mut total_records_synced = 0u64;
+        let mut skipped_records = 0u64;
         let mut errors = Vec::new();
 
         let connector_ids: Vec<String> = {
@@ -525,11 +564,12 @@ impl ConnectorRegistry {
         };
 
         for connector_id in connector_ids {
-            match self.sync_connector_data(&connector_id).await {
-                Ok(records) => {
+            match self.sync_connector_data(&connector_id, mode).await {
+                Ok((records, skipped)) => {
                     synced_apis += 1;
                     total_records_synced += records;
-                    info!("✅ Synced {} records from connector: {}", records, connector_id);
+                    skipped_records += skipped;
+                    info!("✅ Synced {} records from connector: {} ({} already synced)", records, connector_id, skipped);
                 }
                 Err(e) => {
                     failed_syncs += 1;
@@ -545,6 +585,7 @@ impl ConnectorRegistry {
             synced_apis,
             failed_syncs,
             total_records_synced,
+            skipped_records,
             sync_duration,
             errors,
         })
@@ -552,6 +593,68 @@ impl ConnectorRegistry {
 
     /// Private helper methods
 
+    /// Serve a request from cache or the connector once rate limiting allowed it
+    async fn dispatch_request(
+        &self,
+        connector_id: &str,
+        endpoint: &str,
+        parameters: crate::ApiParameters,
+    ) -> Result<ApiResponse> {
+        // Check cache first
+        let cache_key = format!("{}:{}:{:?}", connector_id, endpoint, parameters);
+        if let Some(cached_response) = self.cache.get(&cache_key).await? {
+            debug!("💾 Returning cached response for {}", connector_id);
+            return Ok(cached_response);
+        }
+
+        // Get connector
+        let connector = {
+            let connectors = self.connectors.read().await;
+            connectors.get(connector_id)
+                .ok_or_else(|| anyhow::anyhow!("Connector not found: {}", connector_id))?
+                .clone()
+        };
+
+        // Short-circuit while the upstream API is considered down
+        let permit = self.acquire_circuit(connector_id).await?;
+
+        // Execute request
+        let start_time = Utc::now();
+        let result = connector.execute_request(endpoint, &parameters).await;
+        let response_time = Utc::now().signed_duration_since(start_time);
+
+        // Update circuit breaker and metrics
+        let succeeded = matches!(&result, Ok(response) if (200..300).contains(&response.status_code));
+        self.record_circuit_outcome(permit, succeeded).await;
+        let status_code = result.as_ref().ok().map(|response| response.status_code);
+        self.monitor.record_request(connector_id, response_time, status_code).await;
+        self.update_connection_metrics(connector_id, &result, response_time).await?;
+
+        // Handle result
+        match result {
+            Ok(response) => {
+                // Cache successful response if configured
+                if let Some(ttl) = self.get_cache_ttl(connector_id, endpoint).await? {
+                    self.cache.set(&cache_key, &response, ttl).await?;
+                }
+
+                debug!("✅ Request successful for connector: {}", connector_id);
+                Ok(response)
+            }
+            Err(e) => {
+                error!("❌ Request failed for connector: {} error: {}", connector_id, e);
+
+                // Update health status
+                self.update_health_status(connector_id, false, Some(e.to_string())).await?;
+
+                Err(MarketplaceError::UpstreamApi {
+                    connector_id: connector_id.to_string(),
+                    source: e,
+                }.into())
+            }
+        }
+    }
+
     async fn create_connector(&self, config: ConnectorConfig) -> Result<Arc<dyn ApiConnector>> {
         // Create a concrete connector implementation
         let connector = StandardApiConnector::new(
@@ -569,6 +672,66 @@ impl ConnectorRegistry {
         Ok(())
     }
 
+    /// Ask the connector's circuit breaker to let a request through
+    ///
+    /// The returned permit must be handed to `record_circuit_outcome`; if it
+    /// is dropped first (the request was cancelled), a held probe is released.
+    async fn acquire_circuit(&self, connector_id: &str) -> Result<CircuitPermit> {
+        let acquired = {
+            let mut breakers = self.circuit_breakers.write().await;
+            match breakers.get_mut(connector_id) {
+                Some(breaker) => breaker
+                    .try_acquire()
+                    .map(|transition| (transition, breaker.state() == CircuitState::HalfOpen)),
+                None => Ok((None, false)),
+            }
+        };
+
+        match acquired {
+            Ok((transition, probe)) => {
+                if let Some(transition) = transition {
+                    self.emit_circuit_transition(connector_id, transition).await;
+                }
+                Ok(CircuitPermit {
+                    breakers: self.circuit_breakers.clone(),
+                    connector_id: connector_id.to_string(),
+                    probe,
+                })
+            }
+            Err(retry_after) => {
+                debug!("🔌 Circuit open for connector: {}", connector_id);
+                Err(MarketplaceError::CircuitOpen {
+                    connector_id: connector_id.to_string(),
+                    retry_after,
+                }.into())
+            }
+        }
+    }
+
+    async fn record_circuit_outcome(&self, mut permit: CircuitPermit, success: bool) {
+        permit.probe = false;
+        let transition = {
+            let mut breakers = self.circuit_breakers.write().await;
+            breakers.get_mut(&permit.connector_id).and_then(|breaker| {
+                if success { breaker.record_success() } else { breaker.record_failure() }
+            })
+        };
+
+        if let Some(transition) = transition {
+            self.emit_circuit_transition(&permit.connector_id, transition).await;
+        }
+    }
+
+    async fn emit_circuit_transition(&self, connector_id: &str, transition: CircuitTransition) {
+        self.monitor.record_event(crate::MonitoringEvent::new(
+            Some(connector_id.to_string()),
+            crate::MonitoringEventType::CircuitStateChanged {
+                from: transition.from,
+                to: transition.to,
+            },
+        )).await;
+    }
+
     async fn update_connection_metrics(
         &self,
         connector_id: &str,
@@ -622,15 +785,74 @@ impl ConnectorRegistry {
         Ok(None)
     }
 
-    async fn sync_connector_data(&self, connector_id: &str) -> Result<u64> {
-        // Implementation would sync data from the specific connector
-        // This is a simplified placeholder
-        debug!("🔄 Syncing data from connector: {}", connector_id);
+    /// Sync one connector, returning `(new, skipped)` record counts
+    async fn sync_connector_data(&self, connector_id: &str, mode: SyncMode) -> Result<(u64, u64)> {
+        debug!("🔄 Syncing data from connector: {} ({:?})", connector_id, mode);
+
+        let sync_config = {
+            let configs = self.connector_configs.read().await;
+            configs.get(connector_id).and_then(|config| config.sync.clone())
+        };
+        let Some(sync_config) = sync_config else {
+            debug!("⏭️ Connector {} has no sync configuration", connector_id);
+            return Ok((0, 0));
+        };
+
+        let mut cursor = match mode {
+            SyncMode::Incremental => self.cursor_store.get(connector_id).await,
+            SyncMode::Full => {
+                self.cursor_store.clear_records(connector_id).await?;
+                SyncCursor::new(connector_id)
+            }
+        };
+        let since = cursor.high_watermark;
+        let (mut synced, mut skipped) = (0u64, 0u64);
+
+        for _ in 0..sync_config.max_pages.max(1) {
+            let mut params = crate::ApiParameters::new();
+            if let (Some(parameter), Some(since)) = (&sync_config.since_parameter, since) {
+                params.insert(parameter.clone(), serde_json::Value::String(since.to_rfc3339()));
+            }
+            if let (Some(parameter), Some(token)) = (&sync_config.cursor_parameter, &cursor.cursor_token) {
+                params.insert(parameter.clone(), serde_json::Value::String(token.clone()));
+            }
+
+            let response = self.execute_request_queued(connector_id, &sync_config.endpoint, params, None).await?;
+            let records = if sync_config.records_pointer.is_empty() {
+                Some(&response.body)
+            } else {
+                response.body.pointer(&sync_config.records_pointer)
+            }
+            .and_then(|records| records.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+            let (new, seen) = cursor.absorb(&records, &sync_config);
+            self.cursor_store.append_records(connector_id, &new).await?;
+            synced += new.len() as u64;
+            skipped += seen;
+
+            let next_token = sync_config.next_cursor_pointer.as_ref()
+                .and_then(|pointer| response.body.pointer(pointer))
+                .and_then(|token| token.as_str())
+                .map(str::to_string);
+            let more_pages = !records.is_empty() && next_token.is_some() && next_token != cursor.cursor_token;
+            if next_token.is_some() {
+                cursor.cursor_token = next_token;
+            }
+
+            // Persist after every page so an interrupted sync resumes where it stopped
+            self.cursor_store.save(cursor.clone()).await?;
+
+            if !more_pages {
+                break;
+            }
+        }
 
-        // Simulate sync operation
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        cursor.last_synced_at = Some(Utc::now());
+        self.cursor_store.save(cursor).await?;
 
-        Ok(100) // Placeholder: return number of synced records
+        Ok((synced, skipped))
     }
 }
 
@@ -708,6 +930,43 @@ impl HealthMonitor {
     }
 }
 
+/// Permission to send one request through a connector's circuit breaker
+///
+/// A permit that holds the half-open probe and is dropped without an outcome
+/// (e.g. the caller's future was cancelled) releases the probe, so the
+/// circuit does not stay half-open with no request ever let through.
+struct CircuitPermit {
+    breakers: Arc<RwLock<HashMap<String, CircuitBreaker>>>,
+    connector_id: String,
+    probe: bool,
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        if !self.probe {
+            return;
+        }
+
+        if let Ok(mut breakers) = self.breakers.try_write() {
+            if let Some(breaker) = breakers.get_mut(&self.connector_id) {
+                breaker.release_probe();
+            }
+            return;
+        }
+
+        // The map is busy; release from a task rather than blocking in drop
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let breakers = self.breakers.clone();
+            let connector_id = std::mem::take(&mut self.connector_id);
+            runtime.spawn(async move {
+                if let Some(breaker) = breakers.write().await.get_mut(&connector_id) {
+                    breaker.release_probe();
+                }
+            });
+        }
+    }
+}
+
 /// Standard API connector implementation
 pub struct StandardApiConnector {
     config: ConnectorConfig,
@@ -733,6 +992,21 @@ impl StandardApiConnector {
             client,
         })
     }
+
+    /// Send a GET with the connector's current credentials
+    ///
+    /// Credentials are resolved per request so an expiring OAuth2 token is refreshed first.
+    async fn send_authenticated(&self, url: &str, parameters: &crate::ApiParameters) -> Result<reqwest::Response> {
+        let auth_headers = self.auth_manager
+            .get_auth_headers(&self.config.id, &self.config.authentication)
+            .await?;
+
+        let mut request = self.client.get(url).query(parameters);
+        for (name, value) in &auth_headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        Ok(request.send().await?)
+    }
 }
 
 #[async_trait]
@@ -759,14 +1033,24 @@ impl ApiConnector for StandardApiConnector {
         // Build URL
         let url = format!("{}{}", self.config.base_url, endpoint);
 
-        // Make request (simplified)
-        let response = self.client
-            .get(&url)
-            .query(parameters)
-            .send()
-            .await?;
+        let mut response = self.send_authenticated(&url, parameters).await?;
+
+        // A rejected OAuth2 token is refreshed and the request retried once
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED
+            && matches!(self.config.authentication.credentials, CredentialConfig::OAuth2 { .. })
+        {
+            warn!("🔑 {} rejected the OAuth2 token for {}, refreshing", url, self.config.id);
+            self.refresh_auth().await?;
+            response = self.send_authenticated(&url, parameters).await?;
+        }
+
+        // Error statuses count against the circuit breaker like transport failures
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("{} returned HTTP {}", url, status));
+        }
 
-        let status_code = response.status().as_u16();
+        let status_code = status.as_u16();
         let headers: HashMap<String, String> = response.headers()
             .iter()
             .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
@@ -815,7 +1099,79 @@ impl ApiConnector for StandardApiConnector {
     }
 
     async fn refresh_auth(&self) -> Result<()> {
-        // Implementation would refresh authentication tokens
-        Ok(())
+        self.auth_manager
+            .refresh_credentials(&self.config.id, &self.config.authentication)
+            .await
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    #[tokio::test]
+    async fn test_rejected_oauth2_token_is_refreshed_and_retried() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/oauth/token")
+            .match_body(Matcher::UrlEncoded("grant_type".to_string(), "client_credentials".to_string()))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"access_token":"at-1","token_type":"Bearer","refresh_token":"rt-1"}"#)
+            .create_async()
+            .await;
+        let refreshed = server
+            .mock("POST", "/oauth/token")
+            .match_body(Matcher::UrlEncoded("grant_type".to_string(), "refresh_token".to_string()))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"access_token":"at-2","token_type":"Bearer","expires_in":3600}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/filings")
+            .match_header("authorization", "Bearer at-1")
+            .with_status(401)
+            .create_async()
+            .await;
+        let accepted = server
+            .mock("GET", "/filings")
+            .match_header("authorization", "Bearer at-2")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"filings":[]}"#)
+            .create_async()
+            .await;
+
+        let config = ConnectorConfig {
+            id: "fca-api".to_string(),
+            base_url: server.url(),
+            authentication: AuthenticationConfig {
+                auth_type: AuthenticationType::OAuth2,
+                credentials: CredentialConfig::OAuth2 {
+                    client_id: "aion".to_string(),
+                    client_secret: "secret".to_string(),
+                    scope: None,
+                },
+                token_refresh: Some(TokenRefreshConfig {
+                    refresh_url: format!("{}/oauth/token", server.url()),
+                    refresh_interval: chrono::Duration::hours(1),
+                    refresh_before_expiry: chrono::Duration::seconds(30),
+                }),
+                headers: HashMap::new(),
+            },
+            ..ConnectorConfig::default()
+        };
+        let connector = StandardApiConnector::new(
+            config,
+            Arc::new(AuthenticationManager::new(AuthenticationConfig::default()).await.unwrap()),
+            Arc::new(RateLimitingService::new(RateLimitConfig::default()).await.unwrap()),
+        )
+        .await
+        .unwrap();
+
+        let response = connector.execute_request("/filings", &HashMap::new()).await.unwrap();
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.body, serde_json::json!({"filings": []}));
+        refreshed.assert_async().await;
+        accepted.assert_async().await;
+    }
+}
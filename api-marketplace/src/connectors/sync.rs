@@ -0,0 +1,376 @@
+/*!
+ * Incremental Sync
+ *
+ * Per-connector sync cursors. A cursor records the newest record timestamp
+ * seen (the high watermark) and any opaque cursor token the API hands
+ * back, so an incremental sync only fetches and keeps records newer than
+ * the previous run. New records and cursors are persisted so restarts
+ * resume from them.
+ */
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// How a connector's records are fetched during sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConfig {
+    /// Endpoint returning records
+    pub endpoint: String,
+
+    /// JSON pointer to the records array; the body itself when empty
+    #[serde(default)]
+    pub records_pointer: String,
+
+    /// Record field holding its last-modified time (RFC 3339)
+    pub timestamp_field: Option<String>,
+
+    /// Record field that uniquely identifies it
+    pub id_field: Option<String>,
+
+    /// Query parameter that receives the high watermark
+    pub since_parameter: Option<String>,
+
+    /// Query parameter that receives the opaque cursor token
+    pub cursor_parameter: Option<String>,
+
+    /// JSON pointer to the next cursor token in the response
+    pub next_cursor_pointer: Option<String>,
+
+    /// Most pages fetched in one sync
+    pub max_pages: u32,
+}
+
+/// Whether a sync resumes from the stored cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyncMode {
+    /// Fetch only records newer than the stored cursor
+    Incremental,
+    /// Ignore the stored cursor and fetch everything again
+    Full,
+}
+
+/// Sync position for one connector
+///
+/// The position is the `(timestamp, id)` of the newest record synced.
+/// Records compare by timestamp, then by id, so records sharing a
+/// timestamp are ordered too.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncCursor {
+    pub connector_id: String,
+    /// Newest record timestamp synced
+    pub high_watermark: Option<DateTime<Utc>>,
+    /// ID of the newest record synced at `high_watermark`
+    #[serde(default)]
+    pub high_watermark_id: Option<String>,
+    /// Opaque cursor token returned by the API
+    pub cursor_token: Option<String>,
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+impl SyncCursor {
+    pub fn new(connector_id: &str) -> Self {
+        Self {
+            connector_id: connector_id.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Split a page of records into new ones and already-seen ones,
+    /// advancing the position past the new ones
+    ///
+    /// Returns the new records and the number skipped. A record is seen
+    /// when its `(timestamp, id)` is at or before the position from the
+    /// start of the page. Records without a readable timestamp, and
+    /// records without an id at the watermark timestamp, cannot be placed
+    /// and are always new.
+    pub fn absorb(&mut self, records: &[Value], config: &SyncConfig) -> (Vec<Value>, u64) {
+        let previous = self.high_watermark.map(|watermark| (watermark, self.high_watermark_id.clone()));
+        let mut new = Vec::new();
+        let mut skipped = 0;
+
+        for record in records {
+            let timestamp = config
+                .timestamp_field
+                .as_ref()
+                .and_then(|field| record.get(field))
+                .and_then(Value::as_str)
+                .and_then(|text| DateTime::parse_from_rfc3339(text).ok())
+                .map(|timestamp| timestamp.with_timezone(&Utc));
+            let id = config.id_field.as_ref().and_then(|field| record.get(field)).map(record_id);
+
+            let Some(timestamp) = timestamp else {
+                new.push(record.clone());
+                continue;
+            };
+
+            let seen = match &previous {
+                Some((watermark, _)) if timestamp < *watermark => true,
+                Some((watermark, watermark_id)) if timestamp == *watermark => {
+                    matches!((&id, watermark_id), (Some(id), Some(watermark_id)) if id <= watermark_id)
+                }
+                _ => false,
+            };
+            if seen {
+                skipped += 1;
+                continue;
+            }
+
+            new.push(record.clone());
+            let position = (Some(timestamp), id);
+            if position > (self.high_watermark, self.high_watermark_id.clone()) {
+                (self.high_watermark, self.high_watermark_id) = position;
+            }
+        }
+
+        (new, skipped)
+    }
+}
+
+fn record_id(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Sync cursors and the records synced under them, written through to disk
+///
+/// With a directory, cursors live in `<directory>/<connector>.json` and
+/// records are appended to `<directory>/records/<connector>.jsonl`.
+/// Records are written before the cursor that covers them, so a crash
+/// between the two re-fetches a page instead of losing it.
+pub struct CursorStore {
+    directory: Option<PathBuf>,
+    cursors: RwLock<HashMap<String, SyncCursor>>,
+    records: RwLock<HashMap<String, Vec<Value>>>,
+}
+
+impl CursorStore {
+    /// Open the store, loading cursors persisted in `directory`
+    pub async fn open(directory: Option<PathBuf>) -> Result<Self> {
+        let mut cursors = HashMap::new();
+
+        match &directory {
+            Some(directory) => {
+                tokio::fs::create_dir_all(directory.join("records")).await?;
+                let mut entries = tokio::fs::read_dir(directory).await?;
+                while let Some(entry) = entries.next_entry().await? {
+                    if entry.path().extension().is_some_and(|extension| extension == "json") {
+                        let cursor: SyncCursor = serde_json::from_slice(&tokio::fs::read(entry.path()).await?)?;
+                        cursors.insert(cursor.connector_id.clone(), cursor);
+                    }
+                }
+                info!("🔖 Loaded {} sync cursors", cursors.len());
+            }
+            None => warn!("🔖 No sync directory configured; cursors and synced records are lost on restart"),
+        }
+
+        Ok(Self {
+            directory,
+            cursors: RwLock::new(cursors),
+            records: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// The connector's cursor, or an empty one if it never synced
+    pub async fn get(&self, connector_id: &str) -> SyncCursor {
+        self.cursors
+            .read()
+            .await
+            .get(connector_id)
+            .cloned()
+            .unwrap_or_else(|| SyncCursor::new(connector_id))
+    }
+
+    pub async fn save(&self, cursor: SyncCursor) -> Result<()> {
+        if let Some(directory) = &self.directory {
+            let path = directory.join(format!("{}.json", file_stem(&cursor.connector_id)));
+            write_durably(&path, &serde_json::to_vec_pretty(&cursor)?).await?;
+        }
+
+        debug!("🔖 Saved sync cursor for {}: {:?}", cursor.connector_id, cursor.high_watermark);
+        self.cursors.write().await.insert(cursor.connector_id.clone(), cursor);
+        Ok(())
+    }
+
+    /// Append newly synced records for the connector
+    pub async fn append_records(&self, connector_id: &str, records: &[Value]) -> Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        match &self.directory {
+            Some(directory) => {
+                let mut lines = Vec::new();
+                for record in records {
+                    serde_json::to_writer(&mut lines, record)?;
+                    lines.push(b'\n');
+                }
+                let mut file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(records_path(directory, connector_id))
+                    .await?;
+                file.write_all(&lines).await?;
+                file.sync_all().await?;
+            }
+            None => {
+                self.records
+                    .write()
+                    .await
+                    .entry(connector_id.to_string())
+                    .or_default()
+                    .extend_from_slice(records);
+            }
+        }
+        Ok(())
+    }
+
+    /// Every record synced for the connector, oldest first
+    pub async fn records(&self, connector_id: &str) -> Result<Vec<Value>> {
+        let Some(directory) = &self.directory else {
+            return Ok(self.records.read().await.get(connector_id).cloned().unwrap_or_default());
+        };
+
+        let text = match tokio::fs::read_to_string(records_path(directory, connector_id)).await {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+
+    /// Drop the connector's synced records ahead of a full resync
+    pub async fn clear_records(&self, connector_id: &str) -> Result<()> {
+        match &self.directory {
+            Some(directory) => match tokio::fs::remove_file(records_path(directory, connector_id)).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            },
+            None => {
+                self.records.write().await.remove(connector_id);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Percent-encoded connector ID, so distinct IDs never share a file
+fn file_stem(connector_id: &str) -> String {
+    let mut stem = String::new();
+    for byte in connector_id.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            stem.push(byte as char);
+        } else {
+            stem.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    stem
+}
+
+fn records_path(directory: &Path, connector_id: &str) -> PathBuf {
+    directory.join("records").join(format!("{}.jsonl", file_stem(connector_id)))
+}
+
+/// Write then rename, syncing both, so a crash leaves either the old or the new cursor
+async fn write_durably(path: &Path, bytes: &[u8]) -> Result<()> {
+    let staging = path.with_extension("json.tmp");
+    let mut file = tokio::fs::File::create(&staging).await?;
+    file.write_all(bytes).await?;
+    file.sync_all().await?;
+    tokio::fs::rename(&staging, path).await?;
+    #[cfg(unix)]
+    if let Some(directory) = path.parent() {
+        tokio::fs::File::open(directory).await?.sync_all().await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SyncConfig {
+        SyncConfig {
+            endpoint: "/documents.json".to_string(),
+            records_pointer: "/results".to_string(),
+            timestamp_field: Some("updated_at".to_string()),
+            id_field: Some("document_number".to_string()),
+            since_parameter: Some("since".to_string()),
+            cursor_parameter: None,
+            next_cursor_pointer: None,
+            max_pages: 10,
+        }
+    }
+
+    fn record(id: &str, updated_at: &str) -> Value {
+        serde_json::json!({"document_number": id, "updated_at": updated_at})
+    }
+
+    #[test]
+    fn test_absorb_skips_records_at_or_before_position() {
+        let mut cursor = SyncCursor::new("us-federal-register");
+        let first = [
+            record("2024-001", "2024-03-01T00:00:00Z"),
+            record("2024-002", "2024-03-02T00:00:00Z"),
+        ];
+        let (new, skipped) = cursor.absorb(&first, &config());
+        assert_eq!((new.len(), skipped), (2, 0));
+
+        let second = [
+            record("2024-001", "2024-03-01T00:00:00Z"),
+            record("2024-002", "2024-03-02T00:00:00Z"),
+            // Same timestamp as the watermark, later id
+            record("2024-003", "2024-03-02T00:00:00Z"),
+            record("2024-004", "2024-03-03T00:00:00Z"),
+            // No id, so it cannot be placed at the watermark
+            serde_json::json!({"updated_at": "2024-03-02T00:00:00Z"}),
+        ];
+        let (new, skipped) = cursor.absorb(&second, &config());
+        assert_eq!((new.len(), skipped), (3, 2));
+        assert_eq!(new[0]["document_number"], "2024-003");
+        assert_eq!(cursor.high_watermark, Some("2024-03-03T00:00:00Z".parse().unwrap()));
+        assert_eq!(cursor.high_watermark_id.as_deref(), Some("2024-004"));
+    }
+
+    #[tokio::test]
+    async fn test_cursors_and_records_survive_restart() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = CursorStore::open(Some(directory.path().to_path_buf())).await.unwrap();
+
+        let mut cursor = store.get("sec-edgar").await;
+        assert!(cursor.high_watermark.is_none());
+        let (new, _) = cursor.absorb(&[record("0000320193-24-000123", "2024-11-01T16:30:00Z")], &config());
+        store.append_records("sec-edgar", &new).await.unwrap();
+        cursor.cursor_token = Some("page-7".to_string());
+        store.save(cursor).await.unwrap();
+
+        let reopened = CursorStore::open(Some(directory.path().to_path_buf())).await.unwrap();
+        let restored = reopened.get("sec-edgar").await;
+        assert_eq!(restored.cursor_token.as_deref(), Some("page-7"));
+        assert_eq!(restored.high_watermark_id.as_deref(), Some("0000320193-24-000123"));
+        assert_eq!(reopened.records("sec-edgar").await.unwrap(), new);
+
+        reopened.clear_records("sec-edgar").await.unwrap();
+        assert!(reopened.records("sec-edgar").await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_file_stems_are_distinct_per_connector() {
+        assert_eq!(file_stem("us-federal-register"), "us-federal-register");
+        assert_eq!(file_stem("eu/eur-lex"), "eu%2Feur-lex");
+        assert_ne!(file_stem("eu.eur-lex"), file_stem("eu_eur-lex"));
+        assert_ne!(file_stem("a%2Fb"), file_stem("a/b"));
+    }
+}
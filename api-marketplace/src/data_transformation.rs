@@ -0,0 +1,205 @@
+/*!
+ * Data Transformation Engine
+ *
+ * Validates upstream payloads against per-connector JSON schemas and
+ * normalizes responses into the marketplace's standard format.
+ */
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use anyhow::{anyhow, Result};
+use jsonschema::JSONSchema;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::connectors::ApiResponse;
+use crate::error::MarketplaceError;
+use crate::ConnectorId;
+
+/// What to do with a payload that does not match its connector's schema
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchemaMismatchPolicy {
+    /// Fail the request with `MarketplaceError::SchemaViolation`
+    #[default]
+    Reject,
+    /// Pass the payload through, logging a warning and counting the violation
+    Warn,
+}
+
+/// Data transformation configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransformationConfig {
+    /// JSON schemas for upstream payloads by connector
+    #[serde(default)]
+    pub response_schemas: HashMap<ConnectorId, serde_json::Value>,
+
+    #[serde(default)]
+    pub schema_mismatch: SchemaMismatchPolicy,
+}
+
+/// Data transformation status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformationStatus {
+    pub transformed_responses: u64,
+    pub schemas: u32,
+    /// Payloads passed through despite a schema mismatch, by connector
+    pub schema_violations: HashMap<ConnectorId, u64>,
+}
+
+/// Data transformation engine
+pub struct DataTransformationEngine {
+    schema_mismatch: SchemaMismatchPolicy,
+    schemas: RwLock<HashMap<ConnectorId, Arc<JSONSchema>>>,
+    schema_violations: RwLock<HashMap<ConnectorId, u64>>,
+    transformed_responses: AtomicU64,
+}
+
+impl DataTransformationEngine {
+    pub async fn new(config: TransformationConfig) -> Result<Self> {
+        info!("🔄 Initializing data transformation engine");
+
+        let engine = Self {
+            schema_mismatch: config.schema_mismatch,
+            schemas: RwLock::new(HashMap::new()),
+            schema_violations: RwLock::new(HashMap::new()),
+            transformed_responses: AtomicU64::new(0),
+        };
+        for (connector_id, schema) in &config.response_schemas {
+            engine.register_schema(connector_id, schema)?;
+        }
+
+        Ok(engine)
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("🚀 Starting data transformation engine");
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        info!("🛑 Stopping data transformation engine");
+        Ok(())
+    }
+
+    /// Compile and install the schema a connector's payloads must match
+    pub fn register_schema(&self, connector_id: &ConnectorId, schema: &serde_json::Value) -> Result<()> {
+        let compiled = JSONSchema::compile(schema)
+            .map_err(|e| anyhow!("invalid response schema for connector {}: {}", connector_id, e))?;
+        self.schemas
+            .write()
+            .expect("schema lock poisoned")
+            .insert(connector_id.clone(), Arc::new(compiled));
+        debug!("📐 Registered response schema for connector: {}", connector_id);
+        Ok(())
+    }
+
+    /// Check an upstream payload against the connector's schema
+    ///
+    /// Connectors without a schema always pass. On mismatch this fails with
+    /// `MarketplaceError::SchemaViolation` naming the first failing path, or,
+    /// under `SchemaMismatchPolicy::Warn`, logs and counts the violation.
+    pub fn validate_against_schema(&self, connector_id: &ConnectorId, raw: &serde_json::Value) -> Result<()> {
+        let Some(schema) = self.schemas.read().expect("schema lock poisoned").get(connector_id).cloned() else {
+            return Ok(());
+        };
+
+        let violations: Vec<(String, String)> = match schema.validate(raw) {
+            Ok(()) => return Ok(()),
+            Err(errors) => errors.map(|error| (error.instance_path.to_string(), error.to_string())).collect(),
+        };
+        let (path, message) = violations.first().cloned().unwrap_or_default();
+        let path = if path.is_empty() { "/".to_string() } else { path };
+
+        match self.schema_mismatch {
+            SchemaMismatchPolicy::Reject => Err(MarketplaceError::SchemaViolation {
+                connector_id: connector_id.clone(),
+                path,
+                message,
+                violations: violations.len(),
+            }
+            .into()),
+            SchemaMismatchPolicy::Warn => {
+                warn!("⚠️ Payload from connector {} violates its schema at {}: {}", connector_id, path, message);
+                metrics::counter!("marketplace_schema_violations_total", "connector" => connector_id.clone()).increment(1);
+                *self
+                    .schema_violations
+                    .write()
+                    .expect("violation lock poisoned")
+                    .entry(connector_id.clone())
+                    .or_default() += 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Normalize a response into the marketplace's standard format
+    pub async fn transform_response(&self, response: ApiResponse) -> Result<ApiResponse> {
+        self.transformed_responses.fetch_add(1, Ordering::Relaxed);
+        Ok(response)
+    }
+
+    pub async fn get_status(&self) -> Result<TransformationStatus> {
+        Ok(TransformationStatus {
+            transformed_responses: self.transformed_responses.load(Ordering::Relaxed),
+            schemas: self.schemas.read().expect("schema lock poisoned").len() as u32,
+            schema_violations: self.schema_violations.read().expect("violation lock poisoned").clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(schema_mismatch: SchemaMismatchPolicy) -> TransformationConfig {
+        TransformationConfig {
+            response_schemas: HashMap::from([(
+                "sec-edgar".to_string(),
+                serde_json::json!({
+                    "type": "object",
+                    "required": ["filings"],
+                    "properties": {
+                        "filings": {
+                            "type": "array",
+                            "items": {"type": "object", "required": ["accessionNumber"]}
+                        }
+                    }
+                }),
+            )]),
+            schema_mismatch,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reject_names_failing_path() {
+        let engine = DataTransformationEngine::new(config(SchemaMismatchPolicy::Reject)).await.unwrap();
+        let connector_id = "sec-edgar".to_string();
+
+        engine
+            .validate_against_schema(&connector_id, &serde_json::json!({"filings": [{"accessionNumber": "1"}]}))
+            .unwrap();
+        engine.validate_against_schema(&"eur-lex".to_string(), &serde_json::json!(42)).unwrap();
+
+        let error = engine
+            .validate_against_schema(&connector_id, &serde_json::json!({"filings": [{"form": "10-K"}]}))
+            .unwrap_err();
+        match error.downcast_ref::<MarketplaceError>() {
+            Some(MarketplaceError::SchemaViolation { path, .. }) => assert_eq!(path, "/filings/0"),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warn_passes_through_and_counts() {
+        let engine = DataTransformationEngine::new(config(SchemaMismatchPolicy::Warn)).await.unwrap();
+        let connector_id = "sec-edgar".to_string();
+
+        engine.validate_against_schema(&connector_id, &serde_json::json!({"results": []})).unwrap();
+        engine.validate_against_schema(&connector_id, &serde_json::json!({"results": []})).unwrap();
+
+        let status = engine.get_status().await.unwrap();
+        assert_eq!(status.schema_violations["sec-edgar"], 2);
+    }
+}
@@ -0,0 +1,42 @@
+/*!
+ * Marketplace Errors
+ *
+ * Typed errors callers may need to tell apart, carried inside `anyhow::Error`
+ * and recovered with `downcast_ref::<MarketplaceError>()`.
+ */
+
+use std::time::Duration;
+
+/// Marketplace request failures
+#[derive(Debug, thiserror::Error)]
+pub enum MarketplaceError {
+    /// The connector's token bucket is empty and the request was not queued
+    #[error("rate limit exceeded for connector {connector_id}, next token in {retry_after:?}")]
+    RateLimitExceeded { connector_id: String, retry_after: Duration },
+
+    /// A queued request was not released before its maximum wait elapsed
+    #[error("request to connector {connector_id} was not released by the rate limiter within {max_wait:?}")]
+    RateLimitTimeout { connector_id: String, max_wait: Duration },
+
+    /// The connector's circuit breaker is open after repeated upstream failures
+    #[error("circuit open for connector {connector_id}, next probe in {retry_after:?}")]
+    CircuitOpen { connector_id: String, retry_after: Duration },
+
+    /// An upstream payload does not match the connector's response schema
+    #[error("payload from connector {connector_id} violates its schema at {path}: {message} ({violations} violations)")]
+    SchemaViolation {
+        connector_id: String,
+        /// JSON pointer to the first failing value
+        path: String,
+        message: String,
+        violations: usize,
+    },
+
+    /// The upstream API call itself failed
+    #[error("upstream API error from connector {connector_id}: {source}")]
+    UpstreamApi {
+        connector_id: String,
+        #[source]
+        source: anyhow::Error,
+    },
+}
@@ -294,12 +294,17 @@ impl ApiMarketplace {
     ) -> Result<ApiResponse> {
         info!("📡 Fetching data from API: {} endpoint: {}", connector_id, endpoint);
 
-        let response = self.connector_registry.execute_request(
+        // Queue behind the connector's rate limit instead of failing on bursts
+        let response = self.connector_registry.execute_request_queued(
             connector_id,
             endpoint,
             params,
+            None,
         ).await?;
 
+        // Reject (or flag) payloads that do not match the connector's schema
+        self.data_transformer.validate_against_schema(connector_id, &response.body)?;
+
         // Transform data to standard format
         let transformed_response = self.data_transformer.transform_response(response).await?;
 
@@ -333,8 +338,9 @@ impl ApiMarketplace {
     ) -> Result<GeneratedSdk> {
         info!("🛠️ Generating SDK for connector: {} language: {:?}", connector_id, language);
 
+        let connector_config = self.connector_registry.get_connector_config(connector_id).await?;
         let sdk = self.sdk_generator.generate_sdk(
-            connector_id,
+            &connector_config,
             language,
             options,
         ).await?;
@@ -396,6 +402,17 @@ impl ApiMarketplace {
         Ok(sync_result)
     }
 
+    /// Sync only regulatory data added since the previous sync
+    pub async fn sync_incremental(&self) -> Result<SyncResult> {
+        info!("🔄 Starting incremental data synchronization");
+
+        let sync_result = self.connector_registry.sync_incremental().await?;
+
+        info!("✅ Incremental synchronization completed: {} new records, {} already synced",
+              sync_result.total_records_synced, sync_result.skipped_records);
+        Ok(sync_result)
+    }
+
     /// Initialize default connectors for major regulatory sources
     async fn initialize_default_connectors(&self) -> Result<()> {
         info!("🔧 Initializing default regulatory API connectors");
@@ -462,6 +479,10 @@ pub struct MarketplaceConfig {
     pub transform_config: TransformationConfig,
     pub monitoring_config: MonitoringConfig,
     pub cache_config: CacheConfig,
+    /// Where per-connector sync cursors and synced records are persisted;
+    /// in memory only when `None`
+    #[serde(default)]
+    pub sync_directory: Option<std::path::PathBuf>,
 }
 
 impl Default for MarketplaceConfig {
@@ -474,6 +495,7 @@ impl Default for MarketplaceConfig {
             transform_config: TransformationConfig::default(),
             monitoring_config: MonitoringConfig::default(),
             cache_config: CacheConfig::default(),
+            sync_directory: None,
         }
     }
 }
@@ -586,6 +608,9 @@ pub struct SyncResult {
     pub synced_apis: u32,
     pub failed_syncs: u32,
     pub total_records_synced: u64,
+    /// Records returned by the APIs but already synced by an earlier run
+    #[serde(default)]
+    pub skipped_records: u64,
     pub sync_duration: chrono::Duration,
     pub errors: Vec<String>,
 }
@@ -0,0 +1,477 @@
+/*!
+ * Marketplace Monitoring
+ *
+ * Tracks connector request outcomes and operational events such as circuit
+ * breaker transitions, and serves usage analytics over them. Requests are
+ * aggregated into fixed-width time buckets per connector, each holding a
+ * log-scaled latency histogram, so memory stays bounded by the retention
+ * window rather than by request volume.
+ */
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::connectors::CircuitState;
+use crate::{ConnectorId, TimeRange};
+
+/// Histogram bins per doubling of latency; bounds the percentile error to about 9%
+const BINS_PER_DOUBLING: f64 = 8.0;
+
+/// Highest latency bin, about 2^24 ms (4.6 hours)
+const MAX_LATENCY_BIN: u16 = 192;
+
+/// Most points in an analytics volume series; wider ranges use coarser intervals
+const MAX_VOLUME_POINTS: i64 = 120;
+
+/// Monitoring configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitoringConfig {
+    /// Width of the time buckets requests are aggregated into
+    pub analytics_bucket_width: Duration,
+    /// How long request analytics are kept
+    pub analytics_retention: Duration,
+    /// Operational events kept for inspection
+    pub max_recent_events: usize,
+}
+
+impl Default for MonitoringConfig {
+    fn default() -> Self {
+        Self {
+            analytics_bucket_width: Duration::from_secs(60),
+            analytics_retention: Duration::from_secs(24 * 60 * 60),
+            max_recent_events: 1_000,
+        }
+    }
+}
+
+/// Operational event raised by marketplace components
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitoringEvent {
+    pub event_id: Uuid,
+    pub connector_id: Option<ConnectorId>,
+    pub event_type: MonitoringEventType,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl MonitoringEvent {
+    pub fn new(connector_id: Option<ConnectorId>, event_type: MonitoringEventType) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            connector_id,
+            event_type,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Kinds of operational events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MonitoringEventType {
+    /// A connector's circuit breaker changed state
+    CircuitStateChanged { from: CircuitState, to: CircuitState },
+}
+
+/// Latency histogram with logarithmically sized bins
+#[derive(Debug, Clone, Default)]
+struct LatencyHistogram {
+    /// Request counts by bin; bin `i` holds latencies up to `2^(i / 8)` ms
+    bins: BTreeMap<u16, u64>,
+    count: u64,
+}
+
+impl LatencyHistogram {
+    fn record(&mut self, latency_ms: f64) {
+        let bin = if latency_ms <= 1.0 {
+            0
+        } else {
+            ((latency_ms.log2() * BINS_PER_DOUBLING).ceil() as u16).min(MAX_LATENCY_BIN)
+        };
+        *self.bins.entry(bin).or_default() += 1;
+        self.count += 1;
+    }
+
+    fn merge(&mut self, other: &LatencyHistogram) {
+        for (bin, count) in &other.bins {
+            *self.bins.entry(*bin).or_default() += count;
+        }
+        self.count += other.count;
+    }
+
+    /// Upper bound of the bin holding the `quantile` latency
+    fn percentile(&self, quantile: f64) -> f64 {
+        let target = ((quantile * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bin, count) in &self.bins {
+            seen += count;
+            if seen >= target {
+                return 2f64.powf(*bin as f64 / BINS_PER_DOUBLING);
+            }
+        }
+        2f64.powf(MAX_LATENCY_BIN as f64 / BINS_PER_DOUBLING)
+    }
+}
+
+/// Requests to one connector within one time bucket
+#[derive(Debug, Clone)]
+struct RequestBucket {
+    start: DateTime<Utc>,
+    requests: u64,
+    failed_requests: u64,
+    latency: LatencyHistogram,
+    /// Failed requests by HTTP status
+    errors_by_status: BTreeMap<u16, u64>,
+    /// Failed requests that got no HTTP response
+    transport_errors: u64,
+}
+
+impl RequestBucket {
+    fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            requests: 0,
+            failed_requests: 0,
+            latency: LatencyHistogram::default(),
+            errors_by_status: BTreeMap::new(),
+            transport_errors: 0,
+        }
+    }
+}
+
+/// Usage analytics for a time range
+///
+/// Buckets are included when they start within the range, so the range is
+/// effectively rounded to the monitor's bucket width.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiAnalytics {
+    pub connector_id: Option<ConnectorId>,
+    pub time_range: TimeRange,
+    pub total_requests: u64,
+    pub successful_requests: u64,
+    pub failed_requests: u64,
+    pub success_rate: f64,
+    /// Latency percentiles; `None` when there were no requests
+    pub latency: Option<LatencyPercentiles>,
+    /// Request volume over the range, oldest first
+    pub volume: Vec<VolumeBucket>,
+    pub volume_interval_seconds: u64,
+    /// Failed requests by HTTP status
+    pub errors_by_status: BTreeMap<u16, u64>,
+    /// Failed requests that got no HTTP response
+    pub transport_errors: u64,
+}
+
+/// Request latency percentiles in milliseconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+/// Requests in one interval of an analytics volume series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeBucket {
+    pub start: DateTime<Utc>,
+    pub requests: u64,
+    pub failed_requests: u64,
+}
+
+/// Monitoring status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitoringStatus {
+    pub total_requests: u64,
+    pub failed_requests: u64,
+    pub recent_events: u32,
+    pub last_event: Option<DateTime<Utc>>,
+}
+
+/// Marketplace monitor
+pub struct MarketplaceMonitor {
+    config: MonitoringConfig,
+    request_buckets: RwLock<HashMap<ConnectorId, VecDeque<RequestBucket>>>,
+    recent_events: RwLock<VecDeque<MonitoringEvent>>,
+    total_requests: AtomicU64,
+    failed_requests: AtomicU64,
+}
+
+impl MarketplaceMonitor {
+    pub async fn new(config: MonitoringConfig) -> Result<Self> {
+        info!("📊 Initializing marketplace monitor");
+
+        Ok(Self {
+            config,
+            request_buckets: RwLock::new(HashMap::new()),
+            recent_events: RwLock::new(VecDeque::new()),
+            total_requests: AtomicU64::new(0),
+            failed_requests: AtomicU64::new(0),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("🚀 Starting marketplace monitor");
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        info!("🛑 Stopping marketplace monitor");
+        Ok(())
+    }
+
+    /// Record the outcome of an upstream request
+    ///
+    /// `status_code` is `None` when no HTTP response was received; such
+    /// requests and those answered with a 4xx or 5xx status count as failed.
+    pub async fn record_request(&self, connector_id: &str, latency: chrono::Duration, status_code: Option<u16>) {
+        self.record_request_at(connector_id, latency, status_code, Utc::now()).await;
+    }
+
+    async fn record_request_at(
+        &self,
+        connector_id: &str,
+        latency: chrono::Duration,
+        status_code: Option<u16>,
+        timestamp: DateTime<Utc>,
+    ) {
+        let success = status_code.is_some_and(|status| status < 400);
+        self.total_requests.fetch_add(1, Ordering::Relaxed);
+        if !success {
+            self.failed_requests.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let width = self.bucket_width_seconds();
+        let bucket_start = DateTime::from_timestamp(timestamp.timestamp() - timestamp.timestamp().rem_euclid(width), 0)
+            .unwrap_or(timestamp);
+        let retention = chrono::Duration::from_std(self.config.analytics_retention).unwrap_or(chrono::Duration::MAX);
+
+        let mut all_buckets = self.request_buckets.write().await;
+        let buckets = all_buckets.entry(connector_id.to_string()).or_default();
+        if buckets.back().is_none_or(|bucket| bucket.start < bucket_start) {
+            buckets.push_back(RequestBucket::new(bucket_start));
+        }
+        while buckets.front().is_some_and(|bucket| timestamp.signed_duration_since(bucket.start) > retention) {
+            buckets.pop_front();
+        }
+
+        // Late records land in the newest bucket rather than reordering the series
+        let Some(bucket) = buckets.back_mut() else {
+            return;
+        };
+        bucket.requests += 1;
+        bucket.latency.record(latency.num_microseconds().unwrap_or(i64::MAX) as f64 / 1000.0);
+        if !success {
+            bucket.failed_requests += 1;
+            match status_code {
+                Some(status) => *bucket.errors_by_status.entry(status).or_default() += 1,
+                None => bucket.transport_errors += 1,
+            }
+        }
+    }
+
+    fn bucket_width_seconds(&self) -> i64 {
+        (self.config.analytics_bucket_width.as_secs() as i64).max(1)
+    }
+
+    /// Record an operational event
+    pub async fn record_event(&self, event: MonitoringEvent) {
+        match &event.event_type {
+            MonitoringEventType::CircuitStateChanged { from, to } => warn!(
+                "🔌 Circuit for connector {} changed {:?} -> {:?}",
+                event.connector_id.as_deref().unwrap_or("-"), from, to
+            ),
+        }
+
+        let mut events = self.recent_events.write().await;
+        if events.len() >= self.config.max_recent_events {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// Recent operational events, oldest first
+    pub async fn get_recent_events(&self) -> Vec<MonitoringEvent> {
+        self.recent_events.read().await.iter().cloned().collect()
+    }
+
+    pub async fn get_requests_today(&self) -> Result<u64> {
+        let today = Utc::now().date_naive();
+        let all_buckets = self.request_buckets.read().await;
+        Ok(all_buckets
+            .values()
+            .flatten()
+            .filter(|bucket| bucket.start.date_naive() == today)
+            .map(|bucket| bucket.requests)
+            .sum())
+    }
+
+    pub async fn get_success_rate(&self) -> Result<f64> {
+        let total = self.total_requests.load(Ordering::Relaxed);
+        if total == 0 {
+            return Ok(1.0);
+        }
+        let failed = self.failed_requests.load(Ordering::Relaxed);
+        Ok((total - failed) as f64 / total as f64)
+    }
+
+    /// Analytics for one connector, or marketplace-wide when `connector_id` is `None`
+    pub async fn get_analytics(&self, connector_id: Option<&ConnectorId>, time_range: TimeRange) -> Result<ApiAnalytics> {
+        let width = self.bucket_width_seconds();
+        let span = time_range.end.signed_duration_since(time_range.start).num_seconds().max(0);
+        // Round the volume interval up to a whole number of buckets
+        let interval = (span / MAX_VOLUME_POINTS / width + 1) * width;
+
+        let mut latency = LatencyHistogram::default();
+        let mut volume: BTreeMap<i64, VolumeBucket> = BTreeMap::new();
+        let mut errors_by_status = BTreeMap::new();
+        let (mut total_requests, mut failed_requests, mut transport_errors) = (0, 0, 0);
+
+        let all_buckets = self.request_buckets.read().await;
+        let buckets = all_buckets
+            .iter()
+            .filter(|(id, _)| connector_id.is_none_or(|connector_id| *id == connector_id))
+            .flat_map(|(_, buckets)| buckets)
+            .filter(|bucket| bucket.start >= time_range.start && bucket.start <= time_range.end);
+        for bucket in buckets {
+            total_requests += bucket.requests;
+            failed_requests += bucket.failed_requests;
+            transport_errors += bucket.transport_errors;
+            latency.merge(&bucket.latency);
+            for (status, count) in &bucket.errors_by_status {
+                *errors_by_status.entry(*status).or_default() += count;
+            }
+
+            let offset = bucket.start.signed_duration_since(time_range.start).num_seconds() / interval;
+            let point = volume.entry(offset).or_insert_with(|| VolumeBucket {
+                start: time_range.start + chrono::Duration::seconds(offset * interval),
+                requests: 0,
+                failed_requests: 0,
+            });
+            point.requests += bucket.requests;
+            point.failed_requests += bucket.failed_requests;
+        }
+
+        let successful_requests = total_requests - failed_requests;
+        Ok(ApiAnalytics {
+            connector_id: connector_id.cloned(),
+            time_range,
+            total_requests,
+            successful_requests,
+            failed_requests,
+            success_rate: if total_requests == 0 { 1.0 } else { successful_requests as f64 / total_requests as f64 },
+            latency: (latency.count > 0).then(|| LatencyPercentiles {
+                p50_ms: latency.percentile(0.50),
+                p95_ms: latency.percentile(0.95),
+                p99_ms: latency.percentile(0.99),
+            }),
+            volume: volume.into_values().collect(),
+            volume_interval_seconds: interval as u64,
+            errors_by_status,
+            transport_errors,
+        })
+    }
+
+    pub async fn get_status(&self) -> Result<MonitoringStatus> {
+        let events = self.recent_events.read().await;
+        Ok(MonitoringStatus {
+            total_requests: self.total_requests.load(Ordering::Relaxed),
+            failed_requests: self.failed_requests.load(Ordering::Relaxed),
+            recent_events: events.len() as u32,
+            last_event: events.back().map(|event| event.timestamp),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_latency_percentiles_and_error_breakdown() {
+        let monitor = MarketplaceMonitor::new(MonitoringConfig::default()).await.unwrap();
+        for ms in 1..=100 {
+            monitor.record_request("sec-edgar", chrono::Duration::milliseconds(ms), Some(200)).await;
+        }
+        monitor.record_request("sec-edgar", chrono::Duration::milliseconds(30), Some(503)).await;
+        monitor.record_request("eur-lex", chrono::Duration::seconds(2), Some(429)).await;
+        monitor.record_request("eur-lex", chrono::Duration::seconds(5), None).await;
+
+        let range = TimeRange {
+            start: Utc::now() - chrono::Duration::hours(1),
+            end: Utc::now(),
+        };
+        let analytics = monitor.get_analytics(Some(&"sec-edgar".to_string()), range.clone()).await.unwrap();
+        assert_eq!(analytics.total_requests, 101);
+        assert_eq!(analytics.errors_by_status, BTreeMap::from([(503, 1)]));
+        let latency = analytics.latency.unwrap();
+        assert!((latency.p50_ms - 50.0).abs() / 50.0 < 0.1, "p50 = {}", latency.p50_ms);
+        assert!((latency.p99_ms - 99.0).abs() / 99.0 < 0.1, "p99 = {}", latency.p99_ms);
+
+        let marketplace = monitor.get_analytics(None, range).await.unwrap();
+        assert_eq!(marketplace.total_requests, 103);
+        assert_eq!(marketplace.failed_requests, 3);
+        assert_eq!(marketplace.errors_by_status, BTreeMap::from([(429, 1), (503, 1)]));
+        assert_eq!(marketplace.transport_errors, 1);
+        assert!(marketplace.latency.unwrap().p99_ms >= 2000.0);
+        assert_eq!(monitor.get_requests_today().await.unwrap(), 103);
+    }
+
+    #[tokio::test]
+    async fn test_volume_buckets_and_retention() {
+        let monitor = MarketplaceMonitor::new(MonitoringConfig {
+            analytics_bucket_width: Duration::from_secs(60),
+            analytics_retention: Duration::from_secs(10 * 60),
+            max_recent_events: 10,
+        }).await.unwrap();
+
+        let start = DateTime::from_timestamp(1_700_000_000 - 1_700_000_000 % 60, 0).unwrap();
+        for minute in 0..15 {
+            for _ in 0..=minute {
+                let timestamp = start + chrono::Duration::minutes(minute) + chrono::Duration::seconds(5);
+                monitor.record_request_at("fca-api", chrono::Duration::milliseconds(20), Some(200), timestamp).await;
+            }
+        }
+        assert_eq!(monitor.request_buckets.read().await["fca-api"].len(), 10);
+
+        let range = TimeRange {
+            start,
+            end: start + chrono::Duration::minutes(15),
+        };
+        let analytics = monitor.get_analytics(None, range).await.unwrap();
+        assert_eq!(analytics.volume_interval_seconds, 60);
+        assert_eq!(analytics.volume.len(), 10);
+        assert_eq!(analytics.volume[0].start, start + chrono::Duration::minutes(5));
+        assert_eq!(analytics.volume[0].requests, 6);
+        assert_eq!(analytics.volume[9].requests, 15);
+    }
+
+    #[tokio::test]
+    async fn test_recent_events_are_bounded() {
+        let monitor = MarketplaceMonitor::new(MonitoringConfig {
+            max_recent_events: 2,
+            ..MonitoringConfig::default()
+        }).await.unwrap();
+
+        for (from, to) in [
+            (CircuitState::Closed, CircuitState::Open),
+            (CircuitState::Open, CircuitState::HalfOpen),
+            (CircuitState::HalfOpen, CircuitState::Closed),
+        ] {
+            let event_type = MonitoringEventType::CircuitStateChanged { from, to };
+            monitor.record_event(MonitoringEvent::new(Some("fca-api".to_string()), event_type)).await;
+        }
+
+        let events = monitor.get_recent_events().await;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[1].event_type,
+            MonitoringEventType::CircuitStateChanged { to: CircuitState::Closed, .. }
+        ));
+    }
+}
@@ -0,0 +1,304 @@
+/*!
+ * Rate Limiting Service
+ *
+ * Token-bucket rate limiting per connector. Requests either fail fast when
+ * the bucket is empty or wait in a per-connector FIFO queue until tokens
+ * refill, bounded by a maximum wait.
+ */
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::connectors::RateLimitConfig;
+use crate::error::MarketplaceError;
+
+/// Default time a queued request may wait for tokens
+const DEFAULT_MAX_QUEUE_WAIT: Duration = Duration::from_secs(30);
+
+/// Rate limiting status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitingStatus {
+    pub tracked_connectors: u32,
+    pub queued_requests: u32,
+    pub throttled_requests: u64,
+    pub queue_timeouts: u64,
+}
+
+/// Token bucket for a single connector
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    /// Tokens added per second; `None` when the connector has no limits
+    refill_per_second: Option<f64>,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limits: &RateLimitConfig) -> Self {
+        // The tightest configured window determines the sustained rate
+        let refill_per_second = [
+            limits.requests_per_second.map(|n| n as f64),
+            limits.requests_per_minute.map(|n| n as f64 / 60.0),
+            limits.requests_per_hour.map(|n| n as f64 / 3_600.0),
+            limits.requests_per_day.map(|n| n as f64 / 86_400.0),
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(f64::min);
+
+        let capacity = limits
+            .burst_size
+            .map(|n| n as f64)
+            .or_else(|| refill_per_second.map(f64::ceil))
+            .unwrap_or(1.0)
+            .max(1.0);
+
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_second,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a token, or return how long until one is available
+    fn try_take(&mut self) -> Result<(), Duration> {
+        let Some(rate) = self.refill_per_second else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else if rate > 0.0 {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        } else {
+            Err(Duration::MAX)
+        }
+    }
+}
+
+/// Rate limiting service
+pub struct RateLimitingService {
+    /// Limits applied to connectors registered without their own
+    default_limits: RateLimitConfig,
+
+    /// Token buckets by connector
+    buckets: Arc<RwLock<HashMap<String, Arc<Mutex<TokenBucket>>>>>,
+
+    /// Queue configuration by connector
+    max_queue_waits: Arc<RwLock<HashMap<String, Duration>>>,
+
+    /// FIFO queues by connector; tokio's mutex grants the lock in request order
+    queues: Arc<RwLock<HashMap<String, Arc<Mutex<()>>>>>,
+
+    queued_requests: AtomicUsize,
+    throttled_requests: AtomicU64,
+    queue_timeouts: AtomicU64,
+}
+
+impl RateLimitingService {
+    pub async fn new(config: RateLimitConfig) -> Result<Self> {
+        info!("🚦 Initializing rate limiting service");
+
+        Ok(Self {
+            default_limits: config,
+            buckets: Arc::new(RwLock::new(HashMap::new())),
+            max_queue_waits: Arc::new(RwLock::new(HashMap::new())),
+            queues: Arc::new(RwLock::new(HashMap::new())),
+            queued_requests: AtomicUsize::new(0),
+            throttled_requests: AtomicU64::new(0),
+            queue_timeouts: AtomicU64::new(0),
+        })
+    }
+
+    pub async fn start(&self) -> Result<()> {
+        info!("🚀 Starting rate limiting service");
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        info!("🛑 Stopping rate limiting service");
+        Ok(())
+    }
+
+    /// Apply a connector's own limits, replacing any existing bucket
+    pub async fn register_connector(&self, connector_id: &str, limits: &RateLimitConfig) -> Result<()> {
+        self.buckets.write().await.insert(
+            connector_id.to_string(),
+            Arc::new(Mutex::new(TokenBucket::new(limits))),
+        );
+        let mut max_queue_waits = self.max_queue_waits.write().await;
+        match limits.max_queue_wait {
+            Some(max_wait) => max_queue_waits.insert(connector_id.to_string(), max_wait),
+            None => max_queue_waits.remove(connector_id),
+        };
+        debug!("🚦 Registered rate limits for connector: {}", connector_id);
+        Ok(())
+    }
+
+    /// Take a token without waiting
+    ///
+    /// Fails with `MarketplaceError::RateLimitExceeded` when the bucket is empty.
+    pub async fn check_rate_limit(&self, connector_id: &str) -> Result<()> {
+        let bucket = self.bucket(connector_id).await;
+        let taken = bucket.lock().await.try_take();
+
+        taken.map_err(|retry_after| {
+            self.throttled_requests.fetch_add(1, Ordering::Relaxed);
+            MarketplaceError::RateLimitExceeded {
+                connector_id: connector_id.to_string(),
+                retry_after,
+            }
+            .into()
+        })
+    }
+
+    /// Take a token, queueing behind earlier requests until one refills
+    ///
+    /// Fails with `MarketplaceError::RateLimitTimeout` if the request is not
+    /// released within `max_wait`, or immediately when the next token is
+    /// already known to arrive too late.
+    pub async fn acquire_queued(&self, connector_id: &str, max_wait: Duration) -> Result<()> {
+        let deadline = Instant::now() + max_wait;
+        let bucket = self.bucket(connector_id).await;
+        let queue = self.queue(connector_id).await;
+
+        self.queued_requests.fetch_add(1, Ordering::Relaxed);
+        let released = tokio::time::timeout_at(deadline, async {
+            let _turn = queue.lock().await;
+            loop {
+                let taken = bucket.lock().await.try_take();
+                match taken {
+                    Ok(()) => return true,
+                    Err(wait) if Instant::now().checked_add(wait).is_none_or(|ready| ready > deadline) => return false,
+                    Err(wait) => {
+                        self.throttled_requests.fetch_add(1, Ordering::Relaxed);
+                        debug!("⏳ Connector {} rate limited, waiting {:?}", connector_id, wait);
+                        tokio::time::sleep(wait).await;
+                    }
+                }
+            }
+        })
+        .await
+        .unwrap_or(false);
+        self.queued_requests.fetch_sub(1, Ordering::Relaxed);
+
+        if released {
+            Ok(())
+        } else {
+            self.queue_timeouts.fetch_add(1, Ordering::Relaxed);
+            warn!("⌛ Request to connector {} timed out in the rate limit queue", connector_id);
+            Err(MarketplaceError::RateLimitTimeout {
+                connector_id: connector_id.to_string(),
+                max_wait,
+            }
+            .into())
+        }
+    }
+
+    /// Configured maximum queue wait for a connector
+    pub async fn max_queue_wait(&self, connector_id: &str) -> Duration {
+        self.max_queue_waits
+            .read()
+            .await
+            .get(connector_id)
+            .copied()
+            .or(self.default_limits.max_queue_wait)
+            .unwrap_or(DEFAULT_MAX_QUEUE_WAIT)
+    }
+
+    pub async fn get_status(&self) -> Result<RateLimitingStatus> {
+        Ok(RateLimitingStatus {
+            tracked_connectors: self.buckets.read().await.len() as u32,
+            queued_requests: self.queued_requests.load(Ordering::Relaxed) as u32,
+            throttled_requests: self.throttled_requests.load(Ordering::Relaxed),
+            queue_timeouts: self.queue_timeouts.load(Ordering::Relaxed),
+        })
+    }
+
+    async fn bucket(&self, connector_id: &str) -> Arc<Mutex<TokenBucket>> {
+        if let Some(bucket) = self.buckets.read().await.get(connector_id) {
+            return bucket.clone();
+        }
+        self.buckets
+            .write()
+            .await
+            .entry(connector_id.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(TokenBucket::new(&self.default_limits))))
+            .clone()
+    }
+
+    async fn queue(&self, connector_id: &str) -> Arc<Mutex<()>> {
+        if let Some(queue) = self.queues.read().await.get(connector_id) {
+            return queue.clone();
+        }
+        self.queues
+            .write()
+            .await
+            .entry(connector_id.to_string())
+            .or_default()
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(requests_per_second: u32, burst_size: u32) -> RateLimitConfig {
+        RateLimitConfig {
+            requests_per_second: Some(requests_per_second),
+            requests_per_minute: None,
+            requests_per_hour: None,
+            requests_per_day: None,
+            burst_size: Some(burst_size),
+            retry_after_header: None,
+            max_queue_wait: None,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queued_requests_wait_for_refill() {
+        let service = RateLimitingService::new(limits(10, 1)).await.unwrap();
+        service.check_rate_limit("sec-edgar").await.unwrap();
+
+        let error = service.check_rate_limit("sec-edgar").await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<MarketplaceError>(),
+            Some(MarketplaceError::RateLimitExceeded { .. })
+        ));
+
+        let start = Instant::now();
+        service.acquire_queued("sec-edgar", Duration::from_secs(1)).await.unwrap();
+        service.acquire_queued("sec-edgar", Duration::from_secs(1)).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_queue_timeout_is_distinct_error() {
+        let service = RateLimitingService::new(limits(1, 1)).await.unwrap();
+        service.acquire_queued("fca", Duration::from_millis(10)).await.unwrap();
+
+        let error = service.acquire_queued("fca", Duration::from_millis(100)).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<MarketplaceError>(),
+            Some(MarketplaceError::RateLimitTimeout { .. })
+        ));
+        assert_eq!(service.get_status().await.unwrap().queue_timeouts, 1);
+    }
+}
@@ -0,0 +1,637 @@
+/*!
+ * OpenAPI Model
+ *
+ * Reads an OpenAPI 3 document (JSON or YAML) into the language-neutral
+ * model the SDK emitters consume. Inline object schemas are hoisted into
+ * named types; composition keywords and external references are rejected,
+ * as are distinct schemas or operations that would share a generated name.
+ */
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::SdkGenerationError;
+
+/// Keywords whose semantics the model cannot represent
+const UNSUPPORTED_KEYWORDS: [&str; 4] = ["oneOf", "anyOf", "allOf", "not"];
+
+/// HTTP methods an OpenAPI path item may define
+const METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// Language-neutral API description
+#[derive(Debug, Clone)]
+pub struct ApiModel {
+    pub title: String,
+    pub version: String,
+    pub base_url: String,
+    pub types: Vec<TypeModel>,
+    pub operations: Vec<OperationModel>,
+}
+
+/// A reference to a type
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeRef {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    /// Free-form JSON
+    Any,
+    Array(Box<TypeRef>),
+    /// String-keyed map (`additionalProperties`)
+    Map(Box<TypeRef>),
+    /// A named type from `ApiModel::types`
+    Named(String),
+}
+
+/// A named type
+#[derive(Debug, Clone)]
+pub struct TypeModel {
+    pub name: String,
+    pub description: Option<String>,
+    pub kind: TypeKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum TypeKind {
+    Struct(Vec<FieldModel>),
+    StringEnum(Vec<String>),
+    Alias(TypeRef),
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldModel {
+    /// Name on the wire
+    pub name: String,
+    pub type_ref: TypeRef,
+    pub required: bool,
+    pub description: Option<String>,
+}
+
+/// One API operation
+#[derive(Debug, Clone)]
+pub struct OperationModel {
+    /// Method name in snake case
+    pub name: String,
+    /// Upper-case HTTP method
+    pub method: String,
+    pub path: String,
+    pub summary: Option<String>,
+    pub path_params: Vec<ParamModel>,
+    pub query_params: Vec<ParamModel>,
+    pub request_body: Option<TypeRef>,
+    /// JSON body of the first 2xx response, if any
+    pub response: Option<TypeRef>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParamModel {
+    pub name: String,
+    pub type_ref: TypeRef,
+    pub required: bool,
+}
+
+impl ApiModel {
+    /// Parse an OpenAPI 3 document; `fallback_base_url` is used when it lists no servers
+    pub fn from_spec(spec: &str, fallback_base_url: &str) -> Result<Self> {
+        let document: Value = serde_json::from_str(spec)
+            .or_else(|_| serde_yaml::from_str(spec))
+            .map_err(|e| SdkGenerationError::InvalidSpec { message: e.to_string() })?;
+
+        let openapi = document.get("openapi").and_then(Value::as_str).unwrap_or_default();
+        if !openapi.starts_with("3.") {
+            let feature = match document.get("swagger").and_then(Value::as_str) {
+                Some(version) => format!("Swagger {}", version),
+                None => "a missing `openapi` version".to_string(),
+            };
+            return Err(unsupported("/", feature));
+        }
+
+        let mut builder = ModelBuilder {
+            document: &document,
+            types: Vec::new(),
+            origins: HashMap::new(),
+        };
+
+        if let Some(schemas) = document.pointer("/components/schemas").and_then(Value::as_object) {
+            for (name, schema) in schemas {
+                let location = format!("/components/schemas/{}", name);
+                builder.define(&type_name(name), schema, &location)?;
+            }
+        }
+
+        let mut operations = Vec::new();
+        let mut operation_origins: HashMap<String, String> = HashMap::new();
+        if let Some(paths) = document.get("paths").and_then(Value::as_object) {
+            for (path, item) in paths {
+                for method in METHODS {
+                    if let Some(operation) = item.get(method) {
+                        let location = format!("/paths/{}/{}", escape_pointer(path), method);
+                        let operation = builder.operation(path, method, item, operation, &location)?;
+                        if let Some(origin) = operation_origins.insert(operation.name.clone(), location.clone()) {
+                            return Err(collision(&origin, &location, &operation.name));
+                        }
+                        operations.push(operation);
+                    }
+                }
+            }
+        }
+        operations.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Self {
+            title: document.pointer("/info/title").and_then(Value::as_str).unwrap_or("API").to_string(),
+            version: document.pointer("/info/version").and_then(Value::as_str).unwrap_or_default().to_string(),
+            base_url: document
+                .pointer("/servers/0/url")
+                .and_then(Value::as_str)
+                .unwrap_or(fallback_base_url)
+                .trim_end_matches('/')
+                .to_string(),
+            types: builder.types,
+            operations,
+        })
+    }
+}
+
+struct ModelBuilder<'a> {
+    document: &'a Value,
+    types: Vec<TypeModel>,
+    /// Location each type name was defined from
+    origins: HashMap<String, String>,
+}
+
+impl ModelBuilder<'_> {
+    fn operation(&mut self, path: &str, method: &str, item: &Value, operation: &Value, location: &str) -> Result<OperationModel> {
+        let name = operation
+            .get("operationId")
+            .and_then(Value::as_str)
+            .map(snake_case)
+            .unwrap_or_else(|| snake_case(&format!("{} {}", method, path)));
+        let pascal = type_name(&name);
+
+        let mut path_params = Vec::new();
+        let mut query_params = Vec::new();
+        // Path-level parameters apply to every operation under the path
+        let parameters = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .chain(operation.get("parameters").and_then(Value::as_array).into_iter().flatten());
+        for (index, parameter) in parameters.enumerate() {
+            let parameter_location = format!("{}/parameters/{}", location, index);
+            let parameter = self.resolve(parameter, &parameter_location)?;
+            let param_name = parameter.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+            let schema = parameter.get("schema").cloned().unwrap_or(Value::Null);
+            let type_ref = self.param_type(&schema, &parameter_location)?;
+            let required = parameter.get("required").and_then(Value::as_bool).unwrap_or(false);
+
+            match parameter.get("in").and_then(Value::as_str) {
+                Some("path") => path_params.push(ParamModel { name: param_name, type_ref, required: true }),
+                Some("query") => query_params.push(ParamModel { name: param_name, type_ref, required }),
+                // Headers are left to the caller's reqwest client configuration
+                Some("header") => {}
+                Some(other) => return Err(unsupported(&parameter_location, format!("`{}` parameters", other))),
+                None => return Err(invalid(&parameter_location, "parameter without `in`")),
+            }
+        }
+
+        let request_body = match operation.get("requestBody") {
+            Some(body) => {
+                let body_location = format!("{}/requestBody", location);
+                let body = self.resolve(body, &body_location)?;
+                let Some(schema) = body.pointer("/content/application~1json/schema") else {
+                    return Err(unsupported(&body_location, "a non-JSON request body".to_string()));
+                };
+                Some(self.type_ref(schema, &format!("{}Request", pascal), &body_location)?)
+            }
+            None => None,
+        };
+
+        let mut response = None;
+        if let Some(responses) = operation.get("responses").and_then(Value::as_object) {
+            let success = responses
+                .iter()
+                .filter(|(status, _)| status.starts_with('2'))
+                .min_by(|(a, _), (b, _)| a.cmp(b));
+            if let Some((status, body)) = success {
+                let response_location = format!("{}/responses/{}", location, status);
+                let body = self.resolve(body, &response_location)?;
+                if let Some(schema) = body.pointer("/content/application~1json/schema") {
+                    response = Some(self.type_ref(schema, &format!("{}Response", pascal), &response_location)?);
+                }
+            }
+        }
+
+        Ok(OperationModel {
+            name,
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            summary: operation.get("summary").and_then(Value::as_str).map(str::to_string),
+            path_params,
+            query_params,
+            request_body,
+            response,
+        })
+    }
+
+    /// Add a named type for a schema
+    fn define(&mut self, name: &str, schema: &Value, location: &str) -> Result<()> {
+        if let Some(origin) = self.origins.get(name) {
+            return if origin == location { Ok(()) } else { Err(collision(origin, location, name)) };
+        }
+        check_supported(schema, location)?;
+        self.origins.insert(name.to_string(), location.to_string());
+
+        let description = schema.get("description").and_then(Value::as_str).map(str::to_string);
+        let kind = if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            let values: Option<Vec<String>> = values.iter().map(|value| value.as_str().map(str::to_string)).collect();
+            TypeKind::StringEnum(values.ok_or_else(|| unsupported(location, "a non-string enum".to_string()))?)
+        } else if schema.get("properties").is_some() {
+            // Reserve the name first so self-referencing schemas terminate
+            self.types.push(TypeModel { name: name.to_string(), description: description.clone(), kind: TypeKind::Struct(Vec::new()) });
+            let fields = self.fields(name, schema, location)?;
+            let index = self.types.iter().position(|existing| existing.name == name).expect("reserved above");
+            self.types[index].kind = TypeKind::Struct(fields);
+            return Ok(());
+        } else {
+            TypeKind::Alias(self.type_ref(schema, name, location)?)
+        };
+
+        self.types.push(TypeModel { name: name.to_string(), description, kind });
+        Ok(())
+    }
+
+    fn fields(&mut self, parent: &str, schema: &Value, location: &str) -> Result<Vec<FieldModel>> {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let mut fields = Vec::new();
+        for (name, property) in schema.get("properties").and_then(Value::as_object).into_iter().flatten() {
+            let property_location = format!("{}/properties/{}", location, escape_pointer(name));
+            fields.push(FieldModel {
+                name: name.clone(),
+                type_ref: self.type_ref(property, &format!("{}{}", parent, type_name(name)), &property_location)?,
+                required: required.contains(&name.as_str()),
+                description: property.get("description").and_then(Value::as_str).map(str::to_string),
+            });
+        }
+        Ok(fields)
+    }
+
+    /// Type of a schema, hoisting inline objects and enums under `inline_name`
+    fn type_ref(&mut self, schema: &Value, inline_name: &str, location: &str) -> Result<TypeRef> {
+        check_supported(schema, location)?;
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let Some(name) = reference.strip_prefix("#/components/schemas/") else {
+                return Err(unsupported(location, format!("the reference `{}`", reference)));
+            };
+            if self.document.pointer(&reference[1..]).is_none() {
+                return Err(invalid(location, &format!("dangling reference `{}`", reference)));
+            }
+            return Ok(TypeRef::Named(type_name(name)));
+        }
+
+        if schema.get("properties").is_some() || schema.get("enum").is_some() {
+            self.define(inline_name, schema, location)?;
+            return Ok(TypeRef::Named(inline_name.to_string()));
+        }
+
+        Ok(match schema.get("type").and_then(Value::as_str) {
+            Some("string") => TypeRef::String,
+            Some("integer") => TypeRef::Integer,
+            Some("number") => TypeRef::Number,
+            Some("boolean") => TypeRef::Boolean,
+            Some("array") => {
+                let items = schema.get("items").cloned().unwrap_or(Value::Null);
+                let item_name = format!("{}Item", inline_name);
+                TypeRef::Array(Box::new(self.type_ref(&items, &item_name, &format!("{}/items", location))?))
+            }
+            Some("object") => match schema.get("additionalProperties") {
+                Some(values) if values.is_object() => {
+                    TypeRef::Map(Box::new(self.type_ref(values, &format!("{}Value", inline_name), location)?))
+                }
+                _ => TypeRef::Any,
+            },
+            Some(other) => return Err(unsupported(location, format!("type `{}`", other))),
+            None => TypeRef::Any,
+        })
+    }
+
+    /// Type of a path or query parameter: a scalar or an array of scalars
+    fn param_type(&mut self, schema: &Value, location: &str) -> Result<TypeRef> {
+        check_supported(schema, location)?;
+
+        // Enumerated parameters are passed as their wire strings
+        if schema.get("enum").is_some() {
+            return Ok(TypeRef::String);
+        }
+        if schema.get("type").and_then(Value::as_str) == Some("array") {
+            let items = schema.get("items").cloned().unwrap_or(Value::Null);
+            let item = self.param_type(&items, &format!("{}/items", location))?;
+            if matches!(item, TypeRef::Array(_)) {
+                return Err(unsupported(location, "a nested array parameter".to_string()));
+            }
+            return Ok(TypeRef::Array(Box::new(item)));
+        }
+        if schema.get("properties").is_some() {
+            return Err(unsupported(location, "a non-scalar parameter".to_string()));
+        }
+
+        match self.type_ref(schema, "", location)? {
+            TypeRef::Named(_) | TypeRef::Map(_) | TypeRef::Any => {
+                Err(unsupported(location, "a non-scalar parameter".to_string()))
+            }
+            scalar => Ok(scalar),
+        }
+    }
+
+    /// Follow a local `$ref` on a parameter, request body or response
+    fn resolve(&self, value: &Value, location: &str) -> Result<Value> {
+        match value.get("$ref").and_then(Value::as_str) {
+            Some(reference) if reference.starts_with("#/components/") => self
+                .document
+                .pointer(&reference[1..])
+                .cloned()
+                .ok_or_else(|| invalid(location, &format!("dangling reference `{}`", reference))),
+            Some(reference) => Err(unsupported(location, format!("the reference `{}`", reference))),
+            None => Ok(value.clone()),
+        }
+    }
+}
+
+fn check_supported(schema: &Value, location: &str) -> Result<()> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+    if let Some(keyword) = UNSUPPORTED_KEYWORDS.iter().find(|keyword| schema.contains_key(**keyword)) {
+        return Err(unsupported(location, format!("`{}`", keyword)));
+    }
+    Ok(())
+}
+
+fn unsupported(location: &str, feature: String) -> anyhow::Error {
+    SdkGenerationError::UnsupportedFeature { location: location.to_string(), feature }.into()
+}
+
+fn invalid(location: &str, message: &str) -> anyhow::Error {
+    SdkGenerationError::InvalidSpec { message: format!("{} at {}", message, location) }.into()
+}
+
+pub(crate) fn collision(first: &str, second: &str, name: &str) -> anyhow::Error {
+    SdkGenerationError::NameCollision {
+        first: first.to_string(),
+        second: second.to_string(),
+        name: name.to_string(),
+    }
+    .into()
+}
+
+fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Split an identifier into lower-case words at case changes and punctuation
+fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in text.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+pub fn snake_case(text: &str) -> String {
+    words(text).join("_")
+}
+
+pub fn type_name(text: &str) -> String {
+    words(text)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Sample spec shared by the SDK generator tests
+#[cfg(test)]
+pub(crate) const EDGAR_SPEC: &str = r##"
+{
+  "openapi": "3.0.3",
+  "info": {
+    "title": "SEC EDGAR",
+    "version": "1.0"
+  },
+  "servers": [
+    {
+      "url": "https://data.sec.gov/"
+    }
+  ],
+  "paths": {
+    "/submissions/CIK{cik}.json": {
+      "get": {
+        "operationId": "getCompanySubmissions",
+        "summary": "Filing history of a company",
+        "parameters": [
+          {
+            "name": "cik",
+            "in": "path",
+            "required": true,
+            "schema": {
+              "type": "string"
+            }
+          }
+        ],
+        "responses": {
+          "200": {
+            "description": "ok",
+            "content": {
+              "application/json": {
+                "schema": {
+                  "$ref": "#/components/schemas/Submissions"
+                }
+              }
+            }
+          }
+        }
+      }
+    },
+    "/search": {
+      "post": {
+        "operationId": "searchFilings",
+        "parameters": [
+          {
+            "name": "limit",
+            "in": "query",
+            "schema": {
+              "type": "integer"
+            }
+          }
+        ],
+        "requestBody": {
+          "content": {
+            "application/json": {
+              "schema": {
+                "type": "object",
+                "required": [
+                  "query"
+                ],
+                "properties": {
+                  "query": {
+                    "type": "string"
+                  },
+                  "forms": {
+                    "type": "array",
+                    "items": {
+                      "type": "string"
+                    }
+                  }
+                }
+              }
+            }
+          }
+        },
+        "responses": {
+          "200": {
+            "description": "ok",
+            "content": {
+              "application/json": {
+                "schema": {
+                  "type": "array",
+                  "items": {
+                    "$ref": "#/components/schemas/Filing"
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Filing": {
+        "type": "object",
+        "required": [
+          "accessionNumber",
+          "form"
+        ],
+        "properties": {
+          "accessionNumber": {
+            "type": "string"
+          },
+          "form": {
+            "type": "string",
+            "enum": [
+              "10-K",
+              "10-Q",
+              "8-K"
+            ]
+          },
+          "size": {
+            "type": "integer"
+          }
+        }
+      },
+      "Submissions": {
+        "type": "object",
+        "required": [
+          "cik"
+        ],
+        "properties": {
+          "cik": {
+            "type": "string"
+          },
+          "name": {
+            "type": "string"
+          },
+          "filings": {
+            "type": "array",
+            "items": {
+              "$ref": "#/components/schemas/Filing"
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_is_read_into_model() {
+        let model = ApiModel::from_spec(EDGAR_SPEC, "https://fallback").unwrap();
+        assert_eq!(model.base_url, "https://data.sec.gov");
+
+        let names: Vec<&str> = model.types.iter().map(|model| model.name.as_str()).collect();
+        assert_eq!(names, vec!["Filing", "FilingForm", "Submissions", "SearchFilingsRequest"]);
+
+        let search = model.operations.iter().find(|operation| operation.name == "search_filings").unwrap();
+        assert_eq!(search.method, "POST");
+        assert_eq!(search.request_body, Some(TypeRef::Named("SearchFilingsRequest".to_string())));
+        assert_eq!(search.response, Some(TypeRef::Array(Box::new(TypeRef::Named("Filing".to_string())))));
+        assert!(!search.query_params[0].required);
+    }
+
+    #[test]
+    fn test_unsupported_features_are_reported_with_location() {
+        let spec = r#"{"openapi": "3.1.0", "paths": {}, "components": {"schemas": {
+            "Party": {"oneOf": [{"type": "string"}, {"type": "integer"}]}
+        }}}"#;
+        let error = ApiModel::from_spec(spec, "").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "OpenAPI spec uses `oneOf` at /components/schemas/Party, which SDK generation does not support"
+        );
+
+        let error = ApiModel::from_spec(r#"{"swagger": "2.0"}"#, "").unwrap_err();
+        assert!(error.to_string().contains("Swagger 2.0"));
+    }
+
+    #[test]
+    fn test_schemas_sharing_a_type_name_are_rejected() {
+        let spec = r#"{"openapi": "3.0.3", "paths": {}, "components": {"schemas": {
+            "filing_status": {"type": "string"},
+            "FilingStatus": {"type": "integer"}
+        }}}"#;
+        let error = ApiModel::from_spec(spec, "").unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<SdkGenerationError>(),
+            Some(SdkGenerationError::NameCollision { name, .. }) if name == "FilingStatus"
+        ));
+
+        // An inline body hoisted onto a component's name is not silently replaced by it
+        let mut document: Value = serde_json::from_str(EDGAR_SPEC).unwrap();
+        document["components"]["schemas"]["SearchFilingsRequest"] = serde_json::json!({"type": "string"});
+        let error = ApiModel::from_spec(&document.to_string(), "").unwrap_err();
+        assert!(error.to_string().contains("/paths/~1search/post/requestBody"));
+    }
+}
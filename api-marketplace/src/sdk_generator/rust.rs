@@ -0,0 +1,398 @@
+/*!
+ * Rust SDK Emitter
+ *
+ * Emits a `reqwest`-based client crate from an API model: serde structs
+ * and enums for every named schema, and one typed async method per
+ * operation on a `Client`. Path parameters are percent-encoded by the
+ * generated code so a value cannot escape its segment.
+ */
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use super::openapi::{
+    collision, snake_case, type_name, ApiModel, OperationModel, ParamModel, TypeKind, TypeModel, TypeRef,
+};
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+    "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move",
+    "mut", "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Keywords that cannot be raw identifiers
+const RESERVED: [&str; 4] = ["self", "Self", "super", "crate"];
+
+/// Type names the generated crate declares or uses unqualified
+const CRATE_TYPES: [&str; 9] = ["Client", "Self", "String", "Vec", "Option", "Box", "Result", "Serialize", "Deserialize"];
+
+/// Emit the SDK crate's files by path
+pub fn emit(model: &ApiModel, package_name: &str, version: &str) -> Result<BTreeMap<String, String>> {
+    check_identifiers(model)?;
+
+    let mut files = BTreeMap::new();
+    files.insert("Cargo.toml".to_string(), cargo_toml(model, package_name, version));
+    files.insert("src/lib.rs".to_string(), lib_rs(model));
+    Ok(files)
+}
+
+/// Reject models whose distinct names would become the same Rust identifier
+fn check_identifiers(model: &ApiModel) -> Result<()> {
+    for type_model in &model.types {
+        if CRATE_TYPES.contains(&type_model.name.as_str()) {
+            return Err(collision(&format!("schema `{}`", type_model.name), "the generated client", &type_model.name));
+        }
+        let mut taken = HashMap::new();
+        match &type_model.kind {
+            TypeKind::Struct(fields) => {
+                for field in fields {
+                    let source = format!("`{}.{}`", type_model.name, field.name);
+                    claim(&mut taken, field_ident(&field.name), source)?;
+                }
+            }
+            TypeKind::StringEnum(values) => {
+                for value in values {
+                    let source = format!("`{}::{}`", type_model.name, value);
+                    claim(&mut taken, variant_ident(value), source)?;
+                }
+            }
+            TypeKind::Alias(_) => {}
+        }
+    }
+
+    for operation in &model.operations {
+        let mut taken = HashMap::new();
+        for param in operation.path_params.iter().chain(&operation.query_params) {
+            let source = format!("parameter `{}` of `{}`", param.name, operation.name);
+            claim(&mut taken, field_ident(&param.name), source)?;
+        }
+        if operation.request_body.is_some() {
+            claim(&mut taken, "body".to_string(), format!("the request body of `{}`", operation.name))?;
+        }
+    }
+    Ok(())
+}
+
+fn claim(taken: &mut HashMap<String, String>, ident: String, source: String) -> Result<()> {
+    match taken.get(&ident) {
+        Some(first) => Err(collision(first, &source, &ident)),
+        None => {
+            taken.insert(ident, source);
+            Ok(())
+        }
+    }
+}
+
+fn cargo_toml(model: &ApiModel, package_name: &str, version: &str) -> String {
+    format!(
+        r#"[package]
+name = "{package_name}"
+version = "{version}"
+edition = "2021"
+description = "Typed client for the {title} API"
+
+[dependencies]
+reqwest = {{ version = "0.11", features = ["json"] }}
+serde = {{ version = "1.0", features = ["derive"] }}
+serde_json = "1.0"
+"#,
+        title = model.title.replace('"', "'"),
+    )
+}
+
+fn lib_rs(model: &ApiModel) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "//! Typed client for the {} API (version {})", model.title, model.version);
+    out.push_str("//!\n//! Generated from the connector's OpenAPI spec; do not edit by hand.\n\n");
+    out.push_str("#![allow(clippy::all, dead_code)]\n\n");
+    out.push_str("use serde::{Deserialize, Serialize};\n\n");
+    let _ = writeln!(out, "pub const DEFAULT_BASE_URL: &str = {:?};\n", model.base_url);
+
+    for type_model in &model.types {
+        emit_type(&mut out, type_model);
+    }
+
+    out.push_str(CLIENT_PREAMBLE);
+    for operation in &model.operations {
+        emit_operation(&mut out, operation);
+    }
+    out.push_str("}\n");
+    out.push_str(PATH_SEGMENT);
+    out
+}
+
+const CLIENT_PREAMBLE: &str = r#"/// API client
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Self::with_base_url(DEFAULT_BASE_URL)
+    }
+
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self::with_client(base_url, reqwest::Client::new())
+    }
+
+    /// Use a preconfigured `reqwest` client, e.g. with auth headers
+    pub fn with_client(base_url: impl Into<String>, http: reqwest::Client) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http,
+        }
+    }
+"#;
+
+const PATH_SEGMENT: &str = r#"
+/// Percent-encode a path parameter so `/`, `?` and `#` stay inside its segment
+fn path_segment(value: impl std::fmt::Display) -> String {
+    let mut encoded = String::new();
+    for byte in value.to_string().bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+"#;
+
+fn emit_type(out: &mut String, type_model: &TypeModel) {
+    if let Some(description) = &type_model.description {
+        emit_doc(out, "", description);
+    }
+
+    match &type_model.kind {
+        TypeKind::Struct(fields) => {
+            out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+            let _ = writeln!(out, "pub struct {} {{", type_model.name);
+            for field in fields {
+                if let Some(description) = &field.description {
+                    emit_doc(out, "    ", description);
+                }
+                let ident = field_ident(&field.name);
+                let mut attributes = Vec::new();
+                if ident.trim_start_matches("r#") != field.name {
+                    attributes.push(format!("rename = {:?}", field.name));
+                }
+                // Direct self-references need indirection to have a size
+                let mut rust_type = rust_type(&field.type_ref);
+                if field.type_ref == TypeRef::Named(type_model.name.clone()) {
+                    rust_type = format!("Box<{}>", rust_type);
+                }
+                if !field.required {
+                    attributes.push("default".to_string());
+                    attributes.push("skip_serializing_if = \"Option::is_none\"".to_string());
+                    rust_type = format!("Option<{}>", rust_type);
+                }
+                if !attributes.is_empty() {
+                    let _ = writeln!(out, "    #[serde({})]", attributes.join(", "));
+                }
+                let _ = writeln!(out, "    pub {}: {},", ident, rust_type);
+            }
+            out.push_str("}\n\n");
+        }
+        TypeKind::StringEnum(values) => {
+            out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\n");
+            let _ = writeln!(out, "pub enum {} {{", type_model.name);
+            for value in values {
+                let _ = writeln!(out, "    #[serde(rename = {:?})]", value);
+                let _ = writeln!(out, "    {},", variant_ident(value));
+            }
+            out.push_str("}\n\n");
+        }
+        TypeKind::Alias(type_ref) => {
+            let _ = writeln!(out, "pub type {} = {};\n", type_model.name, rust_type(type_ref));
+        }
+    }
+}
+
+fn emit_operation(out: &mut String, operation: &OperationModel) {
+    out.push('\n');
+    let summary = operation.summary.clone().unwrap_or_default();
+    emit_doc(out, "    ", &format!("`{} {}`{}{}", operation.method, operation.path, if summary.is_empty() { "" } else { ": " }, summary));
+
+    let mut arguments = vec!["&self".to_string()];
+    for param in &operation.path_params {
+        arguments.push(format!("{}: {}", field_ident(&param.name), param_type(param)));
+    }
+    for param in &operation.query_params {
+        arguments.push(format!("{}: {}", field_ident(&param.name), param_type(param)));
+    }
+    if let Some(body) = &operation.request_body {
+        arguments.push(format!("body: &{}", rust_type(body)));
+    }
+    let response = operation.response.as_ref().map(rust_type).unwrap_or_else(|| "()".to_string());
+    let _ = writeln!(
+        out,
+        "    pub async fn {}({}) -> Result<{}, reqwest::Error> {{",
+        field_ident(&operation.name),
+        arguments.join(", "),
+        response
+    );
+
+    // Locals end in `_`, which no parameter identifier does, so they never shadow one.
+    // `{name}` segments become positional format arguments
+    let mut template = String::new();
+    let mut format_arguments = vec!["self.base_url".to_string()];
+    let mut rest = operation.path.as_str();
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else { break };
+        template.push_str(&rest[..start]);
+        template.push_str("{}");
+        format_arguments.push(format!("path_segment({})", field_ident(&rest[start + 1..start + end])));
+        rest = &rest[start + end + 1..];
+    }
+    template.push_str(rest);
+    let _ = writeln!(out, "        let url_ = format!(\"{{}}{}\", {});", template, format_arguments.join(", "));
+
+    let mut request = format!("self.http.request(reqwest::Method::{}, url_)", operation.method);
+    if !operation.query_params.is_empty() {
+        out.push_str("        let mut query_: Vec<(&str, String)> = Vec::new();\n");
+        for param in &operation.query_params {
+            let ident = field_ident(&param.name);
+            let push = match param.type_ref {
+                TypeRef::Array(_) => format!("for item_ in {} {{ query_.push(({:?}, item_.to_string())); }}", ident, param.name),
+                _ => format!("query_.push(({:?}, {}.to_string()));", param.name, ident),
+            };
+            if param.required {
+                let _ = writeln!(out, "        {}", push);
+            } else {
+                let _ = writeln!(out, "        if let Some({}) = {} {{ {} }}", ident, ident, push);
+            }
+        }
+        request.push_str(".query(&query_)");
+    }
+    if operation.request_body.is_some() {
+        request.push_str(".json(body)");
+    }
+
+    if operation.response.is_some() {
+        let _ = writeln!(out, "        {}.send().await?.error_for_status()?.json().await", request);
+    } else {
+        let _ = writeln!(out, "        {}.send().await?.error_for_status()?;\n        Ok(())", request);
+    }
+    out.push_str("    }\n");
+}
+
+fn emit_doc(out: &mut String, indent: &str, text: &str) {
+    for line in text.lines() {
+        let _ = writeln!(out, "{}/// {}", indent, line.trim_end());
+    }
+}
+
+fn rust_type(type_ref: &TypeRef) -> String {
+    match type_ref {
+        TypeRef::String => "String".to_string(),
+        TypeRef::Integer => "i64".to_string(),
+        TypeRef::Number => "f64".to_string(),
+        TypeRef::Boolean => "bool".to_string(),
+        TypeRef::Any => "serde_json::Value".to_string(),
+        TypeRef::Array(item) => format!("Vec<{}>", rust_type(item)),
+        TypeRef::Map(value) => format!("std::collections::HashMap<String, {}>", rust_type(value)),
+        TypeRef::Named(name) => name.clone(),
+    }
+}
+
+/// Argument type: strings and arrays are borrowed, optional ones wrapped in `Option`
+fn param_type(param: &ParamModel) -> String {
+    let borrowed = match &param.type_ref {
+        TypeRef::String => "&str".to_string(),
+        TypeRef::Array(item) => format!("&[{}]", rust_type(item)),
+        other => rust_type(other),
+    };
+    if param.required {
+        borrowed
+    } else {
+        format!("Option<{}>", borrowed)
+    }
+}
+
+fn field_ident(name: &str) -> String {
+    let mut ident = snake_case(name);
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if RESERVED.contains(&ident.as_str()) {
+        ident.push('_');
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        ident.insert_str(0, "r#");
+    }
+    ident
+}
+
+fn variant_ident(value: &str) -> String {
+    let ident = type_name(value);
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) || RESERVED.contains(&ident.as_str()) {
+        format!("V{}", ident)
+    } else {
+        ident
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdk_generator::openapi::EDGAR_SPEC;
+
+    #[test]
+    fn test_operations_become_typed_methods() {
+        let model = ApiModel::from_spec(EDGAR_SPEC, "").unwrap();
+        let lib = &emit(&model, "aion-sec-edgar", "0.1.0").unwrap()["src/lib.rs"];
+
+        assert!(lib.contains(
+            "pub async fn get_company_submissions(&self, cik: &str) -> Result<Submissions, reqwest::Error> {"
+        ));
+        assert!(lib.contains("let url_ = format!(\"{}/submissions/CIK{}.json\", self.base_url, path_segment(cik));"));
+        assert!(lib.contains("if let Some(limit) = limit { query_.push((\"limit\", limit.to_string())); }"));
+        assert!(lib.contains(
+            "pub async fn search_filings(&self, limit: Option<i64>, body: &SearchFilingsRequest) -> Result<Vec<Filing>, reqwest::Error> {"
+        ));
+        assert!(lib.contains("    #[serde(rename = \"accessionNumber\")]\n    pub accession_number: String,"));
+        assert!(lib.contains("    pub size: Option<i64>,"));
+    }
+
+    #[test]
+    fn test_identifiers_are_escaped() {
+        assert_eq!(field_ident("type"), "r#type");
+        assert_eq!(field_ident("self"), "self_");
+        assert_eq!(field_ident("10kFiled"), "_10k_filed");
+        assert_eq!(variant_ident("10-K"), "V10K");
+        assert_eq!(variant_ident("pending-review"), "PendingReview");
+    }
+
+    #[test]
+    fn test_names_sharing_an_identifier_are_rejected() {
+        let mut model = ApiModel::from_spec(EDGAR_SPEC, "").unwrap();
+        let submissions = model.operations.iter_mut().find(|operation| operation.name == "get_company_submissions").unwrap();
+        submissions.query_params.push(ParamModel { name: "CIK".to_string(), type_ref: TypeRef::String, required: false });
+        let error = emit(&model, "aion-sec-edgar", "0.1.0").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "parameter `cik` of `get_company_submissions` and parameter `CIK` of `get_company_submissions` \
+             would both be generated as `cik`; rename one of them in the spec"
+        );
+
+        let mut model = ApiModel::from_spec(EDGAR_SPEC, "").unwrap();
+        let form = model.types.iter_mut().find(|type_model| type_model.name == "FilingForm").unwrap();
+        form.kind = TypeKind::StringEnum(vec!["10-K".to_string(), "10K".to_string()]);
+        assert!(emit(&model, "aion-sec-edgar", "0.1.0").unwrap_err().to_string().contains("`V10K`"));
+
+        let mut model = ApiModel::from_spec(EDGAR_SPEC, "").unwrap();
+        model.types[0].name = "Client".to_string();
+        assert!(emit(&model, "aion-sec-edgar", "0.1.0").is_err());
+    }
+}
@@ -0,0 +1,665 @@
+/*!
+ * Webhook Management
+ *
+ * Outbound webhook subscriptions with guaranteed delivery. Events are
+ * written to a persistent outbox, POSTed with an HMAC-SHA256 signature
+ * header, and retried with exponential backoff until delivered or the
+ * retry policy is exhausted.
+ */
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::connectors::{RetryPolicy, WebhookConfig};
+use crate::{ConnectorId, SubscriptionId};
+
+/// Header carrying the payload signature unless the config names another
+pub const DEFAULT_SIGNATURE_HEADER: &str = "X-AION-Signature";
+/// Header carrying the Unix timestamp included in the signature
+pub const TIMESTAMP_HEADER: &str = "X-AION-Timestamp";
+/// Header carrying the delivery ID, stable across retries
+pub const DELIVERY_ID_HEADER: &str = "X-AION-Delivery";
+/// Header carrying the event type
+pub const EVENT_TYPE_HEADER: &str = "X-AION-Event";
+
+/// How often the delivery worker looks for due deliveries
+const DELIVERY_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Where a subscription's signing secret is read from
+///
+/// Only the reference is persisted with the subscription; the secret is
+/// resolved each time a delivery is signed, so it can be rotated in place.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum SecretReference {
+    /// An environment variable of the marketplace process
+    Env { variable: String },
+    /// A file whose contents (trailing newline trimmed) are the secret
+    File { path: PathBuf },
+}
+
+impl SecretReference {
+    pub async fn resolve(&self) -> Result<String> {
+        let secret = match self {
+            SecretReference::Env { variable } => std::env::var(variable)
+                .map_err(|e| anyhow::anyhow!("webhook secret variable {} is not readable: {}", variable, e))?,
+            SecretReference::File { path } => tokio::fs::read_to_string(path)
+                .await
+                .map_err(|e| anyhow::anyhow!("webhook secret file {} is not readable: {}", path.display(), e))?
+                .trim_end_matches(['\r', '\n'])
+                .to_string(),
+        };
+        if secret.is_empty() {
+            anyhow::bail!("webhook secret {:?} is empty", self);
+        }
+        Ok(secret)
+    }
+}
+
+/// Subscription request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionConfig {
+    /// URL events are POSTed to
+    pub callback_url: String,
+    /// Event types to deliver; empty means all
+    pub event_types: Vec<String>,
+    /// Signing secret shared with the receiver
+    pub secret: SecretReference,
+}
+
+/// An active webhook subscription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub subscription_id: SubscriptionId,
+    pub connector_id: ConnectorId,
+    pub callback_url: String,
+    pub event_types: Vec<String>,
+    /// Secret receivers use to verify the signature header
+    pub secret: SecretReference,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    fn accepts(&self, event_type: &str) -> bool {
+        self.event_types.is_empty() || self.event_types.iter().any(|accepted| accepted == event_type)
+    }
+}
+
+/// Delivery states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeliveryState {
+    Pending,
+    Delivered,
+    /// Retry policy exhausted; kept in the outbox for inspection
+    Failed,
+}
+
+/// One event queued for one subscription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub delivery_id: Uuid,
+    pub subscription_id: SubscriptionId,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub state: DeliveryState,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Delivery status of one subscription
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubscriptionDeliveryStatus {
+    pub pending: u32,
+    pub delivered: u64,
+    pub failed: u32,
+    pub last_delivered_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+/// Webhook manager status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookStatus {
+    pub active_subscriptions: u32,
+    pub pending_deliveries: u32,
+    pub failed_deliveries: u32,
+    pub worker_running: bool,
+    pub subscriptions: HashMap<SubscriptionId, SubscriptionDeliveryStatus>,
+}
+
+/// Webhook manager
+pub struct WebhookManager {
+    worker: DeliveryWorker,
+    worker_handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl WebhookManager {
+    pub async fn new(config: WebhookConfig) -> Result<Self> {
+        info!("🔔 Initializing webhook manager");
+
+        let outbox = Outbox::open(config.outbox_directory.clone()).await?;
+        let worker = DeliveryWorker {
+            retry_policy: config.retry_policy.clone(),
+            signature_header: config
+                .signature_verification
+                .as_ref()
+                .map(|signature| signature.header_name.clone())
+                .unwrap_or_else(|| DEFAULT_SIGNATURE_HEADER.to_string()),
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()?,
+            outbox: Arc::new(outbox),
+            pass_lock: Arc::new(Mutex::new(())),
+            max_concurrent_deliveries: config.max_concurrent_deliveries.max(1),
+        };
+
+        Ok(Self {
+            worker,
+            worker_handle: Mutex::new(None),
+        })
+    }
+
+    /// Start the background delivery worker
+    pub async fn start(&self) -> Result<()> {
+        info!("🚀 Starting webhook delivery worker");
+
+        let mut handle = self.worker_handle.lock().await;
+        if handle.is_none() {
+            let worker = self.worker.clone();
+            *handle = Some(tokio::spawn(async move {
+                let mut interval = tokio::time::interval(DELIVERY_POLL_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    if let Err(e) = worker.process_due_deliveries().await {
+                        warn!("❌ Webhook delivery pass failed: {}", e);
+                    }
+                }
+            }));
+        }
+        Ok(())
+    }
+
+    /// Stop the delivery worker; undelivered events stay in the outbox
+    pub async fn stop(&self) -> Result<()> {
+        info!("🛑 Stopping webhook delivery worker");
+
+        if let Some(handle) = self.worker_handle.lock().await.take() {
+            handle.abort();
+        }
+        Ok(())
+    }
+
+    /// Subscribe a callback URL to a connector's events
+    ///
+    /// The secret reference must resolve now, so a misconfigured subscription
+    /// is rejected instead of failing every delivery later.
+    pub async fn create_subscription(
+        &self,
+        connector_id: ConnectorId,
+        subscription: SubscriptionConfig,
+    ) -> Result<SubscriptionId> {
+        subscription.secret.resolve().await?;
+        let secret = subscription.secret;
+
+        let subscription = WebhookSubscription {
+            subscription_id: Uuid::new_v4().to_string(),
+            connector_id,
+            callback_url: subscription.callback_url,
+            event_types: subscription.event_types,
+            secret,
+            created_at: Utc::now(),
+        };
+        let subscription_id = subscription.subscription_id.clone();
+
+        self.worker.outbox.save_subscription(subscription).await?;
+        debug!("🔔 Created webhook subscription: {}", subscription_id);
+        Ok(subscription_id)
+    }
+
+    /// Remove a subscription and drop its undelivered events
+    pub async fn remove_subscription(&self, subscription_id: &str) -> Result<()> {
+        self.worker.outbox.remove_subscription(subscription_id).await
+    }
+
+    pub async fn get_subscription(&self, subscription_id: &str) -> Option<WebhookSubscription> {
+        self.worker.outbox.subscriptions.read().await.get(subscription_id).cloned()
+    }
+
+    /// Queue an event for every subscription of the connector that accepts it
+    ///
+    /// Returns the delivery IDs. Deliveries are persisted before this returns,
+    /// so they survive a restart.
+    pub async fn publish_event(
+        &self,
+        connector_id: &str,
+        event_type: &str,
+        payload: serde_json::Value,
+    ) -> Result<Vec<Uuid>> {
+        let subscription_ids: Vec<SubscriptionId> = self
+            .worker
+            .outbox
+            .subscriptions
+            .read()
+            .await
+            .values()
+            .filter(|subscription| subscription.connector_id == connector_id && subscription.accepts(event_type))
+            .map(|subscription| subscription.subscription_id.clone())
+            .collect();
+
+        let mut delivery_ids = Vec::with_capacity(subscription_ids.len());
+        for subscription_id in subscription_ids {
+            let delivery = WebhookDelivery {
+                delivery_id: Uuid::new_v4(),
+                subscription_id,
+                event_type: event_type.to_string(),
+                payload: payload.clone(),
+                state: DeliveryState::Pending,
+                attempts: 0,
+                next_attempt_at: Utc::now(),
+                last_error: None,
+                created_at: Utc::now(),
+            };
+            delivery_ids.push(delivery.delivery_id);
+            self.worker.outbox.save_delivery(delivery).await?;
+        }
+
+        debug!("📬 Queued {} deliveries of {} from {}", delivery_ids.len(), event_type, connector_id);
+        Ok(delivery_ids)
+    }
+
+    /// Attempt every delivery that is due now
+    ///
+    /// The background worker calls this periodically; it is public so callers
+    /// can flush the outbox without waiting for the next poll.
+    pub async fn process_due_deliveries(&self) -> Result<u32> {
+        self.worker.process_due_deliveries().await
+    }
+
+    pub async fn get_status(&self) -> Result<WebhookStatus> {
+        let subscriptions = self.worker.outbox.subscriptions.read().await;
+        let deliveries = self.worker.outbox.deliveries.read().await;
+        let delivered = self.worker.outbox.delivered.read().await;
+
+        let mut per_subscription: HashMap<SubscriptionId, SubscriptionDeliveryStatus> = subscriptions
+            .keys()
+            .map(|id| (id.clone(), delivered.get(id).cloned().unwrap_or_default()))
+            .collect();
+        for delivery in deliveries.values() {
+            let status = per_subscription.entry(delivery.subscription_id.clone()).or_default();
+            match delivery.state {
+                DeliveryState::Pending => status.pending += 1,
+                DeliveryState::Failed => status.failed += 1,
+                DeliveryState::Delivered => {}
+            }
+            if delivery.last_error.is_some() {
+                status.last_error = delivery.last_error.clone();
+            }
+        }
+
+        Ok(WebhookStatus {
+            active_subscriptions: subscriptions.len() as u32,
+            pending_deliveries: per_subscription.values().map(|status| status.pending).sum(),
+            failed_deliveries: per_subscription.values().map(|status| status.failed).sum(),
+            worker_running: self.worker_handle.lock().await.is_some(),
+            subscriptions: per_subscription,
+        })
+    }
+}
+
+/// Sign a webhook body as receivers are expected to verify it
+///
+/// The signature covers `"{timestamp}.{body}"` so a captured request cannot
+/// be replayed with a new timestamp. The header value is `sha256=<hex>`.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Verify a signature header produced by `sign_payload`, in constant time
+pub fn verify_payload_signature(secret: &str, timestamp: i64, body: &[u8], signature: &str) -> bool {
+    let Some(expected) = signature.strip_prefix("sha256=").and_then(|digits| hex::decode(digits).ok()) else {
+        return false;
+    };
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// Delivers due outbox entries; cloned into the background task
+#[derive(Clone)]
+struct DeliveryWorker {
+    retry_policy: RetryPolicy,
+    signature_header: String,
+    client: reqwest::Client,
+    outbox: Arc<Outbox>,
+    /// Keeps the worker and manual flushes from delivering the same event twice
+    pass_lock: Arc<Mutex<()>>,
+    /// Deliveries attempted at once within a pass
+    max_concurrent_deliveries: usize,
+}
+
+impl DeliveryWorker {
+    async fn process_due_deliveries(&self) -> Result<u32> {
+        let _pass = self.pass_lock.lock().await;
+        let now = Utc::now();
+        let due: Vec<WebhookDelivery> = self
+            .outbox
+            .deliveries
+            .read()
+            .await
+            .values()
+            .filter(|delivery| delivery.state == DeliveryState::Pending && delivery.next_attempt_at <= now)
+            .cloned()
+            .collect();
+
+        // One slow receiver must not hold up the rest, nor may a large backlog open unbounded connections
+        let outcomes: Vec<Result<bool>> = futures::stream::iter(due)
+            .map(|delivery| self.attempt(delivery))
+            .buffer_unordered(self.max_concurrent_deliveries)
+            .collect()
+            .await;
+
+        let mut delivered = 0;
+        for outcome in outcomes {
+            if outcome? {
+                delivered += 1;
+            }
+        }
+        Ok(delivered)
+    }
+
+    /// Attempt one delivery and persist the outcome; `Ok(true)` when delivered
+    async fn attempt(&self, mut delivery: WebhookDelivery) -> Result<bool> {
+        let Some(subscription) = self.outbox.subscriptions.read().await.get(&delivery.subscription_id).cloned() else {
+            self.outbox.remove_delivery(&delivery).await?;
+            return Ok(false);
+        };
+
+        delivery.attempts += 1;
+        let delivered = match self.post(&subscription, &delivery).await {
+            Ok(()) => {
+                delivery.state = DeliveryState::Delivered;
+                debug!("✅ Delivered webhook {} to {}", delivery.delivery_id, subscription.callback_url);
+                true
+            }
+            Err(e) => {
+                delivery.last_error = Some(e.to_string());
+                if delivery.attempts >= self.retry_policy.max_attempts {
+                    delivery.state = DeliveryState::Failed;
+                    warn!("❌ Webhook {} failed after {} attempts: {}", delivery.delivery_id, delivery.attempts, e);
+                } else {
+                    delivery.next_attempt_at = Utc::now() + backoff_delay(&self.retry_policy, delivery.attempts);
+                    debug!("🔁 Webhook {} attempt {} failed, retrying at {}",
+                           delivery.delivery_id, delivery.attempts, delivery.next_attempt_at);
+                }
+                false
+            }
+        };
+        self.outbox.save_delivery(delivery).await?;
+        Ok(delivered)
+    }
+
+    async fn post(&self, subscription: &WebhookSubscription, delivery: &WebhookDelivery) -> Result<()> {
+        let body = serde_json::to_vec(&serde_json::json!({
+            "delivery_id": delivery.delivery_id,
+            "subscription_id": delivery.subscription_id,
+            "connector_id": subscription.connector_id,
+            "event_type": delivery.event_type,
+            "created_at": delivery.created_at,
+            "payload": delivery.payload,
+        }))?;
+        let secret = subscription.secret.resolve().await?;
+        let timestamp = Utc::now().timestamp();
+
+        let response = self
+            .client
+            .post(&subscription.callback_url)
+            .header("Content-Type", "application/json")
+            .header(self.signature_header.as_str(), sign_payload(&secret, timestamp, &body))
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(DELIVERY_ID_HEADER, delivery.delivery_id.to_string())
+            .header(EVENT_TYPE_HEADER, delivery.event_type.as_str())
+            .body(body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("receiver responded with HTTP {}", response.status().as_u16());
+        }
+        Ok(())
+    }
+}
+
+/// Delay before the next attempt after `attempts` failures
+fn backoff_delay(policy: &RetryPolicy, attempts: u32) -> chrono::Duration {
+    if !policy.exponential_backoff {
+        return policy.initial_delay;
+    }
+    let factor = 2i32.saturating_pow(attempts.saturating_sub(1));
+    policy
+        .initial_delay
+        .checked_mul(factor)
+        .map_or(policy.max_delay, |delay| delay.min(policy.max_delay))
+}
+
+/// Subscriptions and undelivered events, written through to disk
+struct Outbox {
+    directory: Option<PathBuf>,
+    subscriptions: RwLock<HashMap<SubscriptionId, WebhookSubscription>>,
+    deliveries: RwLock<HashMap<Uuid, WebhookDelivery>>,
+    /// Delivered counts by subscription; delivered events leave the outbox
+    delivered: RwLock<HashMap<SubscriptionId, SubscriptionDeliveryStatus>>,
+}
+
+impl Outbox {
+    async fn open(directory: Option<PathBuf>) -> Result<Self> {
+        let mut subscriptions = HashMap::new();
+        let mut deliveries = HashMap::new();
+
+        if let Some(directory) = &directory {
+            for subscription in read_json_dir::<WebhookSubscription>(&directory.join("subscriptions")).await? {
+                subscriptions.insert(subscription.subscription_id.clone(), subscription);
+            }
+            for delivery in read_json_dir::<WebhookDelivery>(&directory.join("deliveries")).await? {
+                deliveries.insert(delivery.delivery_id, delivery);
+            }
+            info!("📬 Restored {} webhook subscriptions and {} undelivered events", subscriptions.len(), deliveries.len());
+        } else {
+            warn!("📬 No webhook outbox directory configured; subscriptions and undelivered events are lost on restart");
+        }
+
+        Ok(Self {
+            directory,
+            subscriptions: RwLock::new(subscriptions),
+            deliveries: RwLock::new(deliveries),
+            delivered: RwLock::new(HashMap::new()),
+        })
+    }
+
+    async fn save_subscription(&self, subscription: WebhookSubscription) -> Result<()> {
+        if let Some(directory) = &self.directory {
+            write_json(&directory.join("subscriptions"), &subscription.subscription_id, &subscription).await?;
+        }
+        self.subscriptions.write().await.insert(subscription.subscription_id.clone(), subscription);
+        Ok(())
+    }
+
+    async fn remove_subscription(&self, subscription_id: &str) -> Result<()> {
+        // Only IDs this outbox issued name files; anything else is not a path to touch
+        if self.subscriptions.write().await.remove(subscription_id).is_none() {
+            return Ok(());
+        }
+        self.delivered.write().await.remove(subscription_id);
+        let orphaned: Vec<WebhookDelivery> = self
+            .deliveries
+            .read()
+            .await
+            .values()
+            .filter(|delivery| delivery.subscription_id == subscription_id)
+            .cloned()
+            .collect();
+        for delivery in &orphaned {
+            self.remove_delivery(delivery).await?;
+        }
+
+        if let Some(directory) = &self.directory {
+            remove_json(&directory.join("subscriptions"), subscription_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Persist a delivery; delivered ones are removed and counted instead
+    async fn save_delivery(&self, delivery: WebhookDelivery) -> Result<()> {
+        if delivery.state == DeliveryState::Delivered {
+            let mut delivered = self.delivered.write().await;
+            let status = delivered.entry(delivery.subscription_id.clone()).or_default();
+            status.delivered += 1;
+            status.last_delivered_at = Some(Utc::now());
+            drop(delivered);
+            return self.remove_delivery(&delivery).await;
+        }
+
+        if let Some(directory) = &self.directory {
+            write_json(&directory.join("deliveries"), &delivery.delivery_id.to_string(), &delivery).await?;
+        }
+        self.deliveries.write().await.insert(delivery.delivery_id, delivery);
+        Ok(())
+    }
+
+    async fn remove_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+        self.deliveries.write().await.remove(&delivery.delivery_id);
+        if let Some(directory) = &self.directory {
+            remove_json(&directory.join("deliveries"), &delivery.delivery_id.to_string()).await?;
+        }
+        Ok(())
+    }
+}
+
+async fn read_json_dir<T: serde::de::DeserializeOwned>(directory: &Path) -> Result<Vec<T>> {
+    tokio::fs::create_dir_all(directory).await?;
+    let mut entries = tokio::fs::read_dir(directory).await?;
+    let mut items = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.path().extension().is_some_and(|extension| extension == "json") {
+            items.push(serde_json::from_slice(&tokio::fs::read(entry.path()).await?)?);
+        }
+    }
+    Ok(items)
+}
+
+/// Write via a temporary file so a crash never leaves a truncated entry
+async fn write_json<T: Serialize>(directory: &Path, name: &str, value: &T) -> Result<()> {
+    tokio::fs::create_dir_all(directory).await?;
+    let path = directory.join(format!("{}.json", name));
+    let tmp_path = directory.join(format!("{}.json.tmp", name));
+    tokio::fs::write(&tmp_path, serde_json::to_vec_pretty(value)?).await?;
+    tokio::fs::rename(&tmp_path, &path).await?;
+    Ok(())
+}
+
+async fn remove_json(directory: &Path, name: &str) -> Result<()> {
+    match tokio::fs::remove_file(directory.join(format!("{}.json", name))).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_round_trip_and_backoff() {
+        let body = br#"{"event_type":"regulation.updated"}"#;
+        let signature = sign_payload("whsec", 1_700_000_000, body);
+
+        assert!(verify_payload_signature("whsec", 1_700_000_000, body, &signature));
+        assert!(!verify_payload_signature("whsec", 1_700_000_001, body, &signature));
+        assert!(!verify_payload_signature("other", 1_700_000_000, body, &signature));
+
+        let policy = RetryPolicy::default();
+        assert_eq!(backoff_delay(&policy, 1), chrono::Duration::seconds(1));
+        assert_eq!(backoff_delay(&policy, 3), chrono::Duration::seconds(4));
+        assert_eq!(backoff_delay(&policy, 30), chrono::Duration::seconds(60));
+    }
+
+    #[tokio::test]
+    async fn test_undelivered_events_survive_restart() {
+        let directory = tempfile::tempdir().unwrap();
+        let config = WebhookConfig {
+            outbox_directory: Some(directory.path().to_path_buf()),
+            ..WebhookConfig::default()
+        };
+
+        let secret_path = directory.path().join("edgar.secret");
+        std::fs::write(&secret_path, "whsec-edgar\n").unwrap();
+
+        let manager = WebhookManager::new(config.clone()).await.unwrap();
+        let subscription_id = manager.create_subscription("sec-edgar".to_string(), SubscriptionConfig {
+            callback_url: "http://127.0.0.1:9/hooks".to_string(),
+            event_types: vec!["filing.published".to_string()],
+            secret: SecretReference::File { path: secret_path.clone() },
+        }).await.unwrap();
+
+        // Only the reference reaches the outbox
+        let persisted = std::fs::read_to_string(
+            directory.path().join("subscriptions").join(format!("{}.json", subscription_id)),
+        ).unwrap();
+        assert!(!persisted.contains("whsec-edgar"));
+        assert_eq!(
+            manager.get_subscription(&subscription_id).await.unwrap().secret.resolve().await.unwrap(),
+            "whsec-edgar"
+        );
+        let queued = manager
+            .publish_event("sec-edgar", "filing.published", serde_json::json!({"cik": "0000320193"}))
+            .await
+            .unwrap();
+        assert_eq!(queued.len(), 1);
+        assert!(manager.publish_event("sec-edgar", "filing.withdrawn", serde_json::json!({})).await.unwrap().is_empty());
+
+        // Nothing listens on the discard port, so the first attempt fails and is rescheduled
+        assert_eq!(manager.process_due_deliveries().await.unwrap(), 0);
+
+        let restarted = WebhookManager::new(config).await.unwrap();
+        let status = restarted.get_status().await.unwrap();
+        assert_eq!(status.pending_deliveries, 1);
+        let subscription_status = &status.subscriptions[&subscription_id];
+        assert_eq!(subscription_status.pending, 1);
+        assert!(subscription_status.last_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_secret_is_rejected() {
+        let manager = WebhookManager::new(WebhookConfig::default())
+            .await
+            .unwrap();
+        let result = manager.create_subscription("sec-edgar".to_string(), SubscriptionConfig {
+            callback_url: "http://127.0.0.1:9/hooks".to_string(),
+            event_types: vec![],
+            secret: SecretReference::Env { variable: "AION_TEST_WEBHOOK_SECRET_THAT_IS_NOT_SET".to_string() },
+        }).await;
+
+        assert!(result.is_err());
+        assert_eq!(manager.get_status().await.unwrap().active_subscriptions, 0);
+    }
+}
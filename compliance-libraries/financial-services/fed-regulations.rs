@@ -614,6 +614,33 @@ impl FederalReserveRegulations {
     fn create_regulation_pp() -> FedRegulation { FedRegulation { regulation_id: "12 CFR 241".to_string(), title: "Minimum Security Devices and Procedures (Regulation PP)".to_string(), authority: "Bank Protection Act".to_string(), purpose: "Security requirements".to_string(), scope: "FDIC-insured institutions".to_string(), effective_date: Utc::now(), last_amended: Utc::now(), sections: vec![], interpretations: vec![], exemptions: vec![], compliance_requirements: vec![] } }
     fn create_regulation_qq() -> FedRegulation { FedRegulation { regulation_id: "12 CFR 248".to_string(), title: "Proprietary Trading and Private Fund Activities (Regulation QQ)".to_string(), authority: "Dodd-Frank Act (Volcker Rule)".to_string(), purpose: "Volcker Rule implementation".to_string(), scope: "Banking entities".to_string(), effective_date: Utc::now(), last_amended: Utc::now(), sections: vec![], interpretations: vec![], exemptions: vec![], compliance_requirements: vec![] } }
 
+    /// Bytes held by the loaded text plus the regulation index
+    pub fn memory_footprint(&self) -> usize {
+        let text: usize = self
+            .regulations
+            .values()
+            .map(|reg| {
+                reg.regulation_id.len()
+                    + reg.title.len()
+                    + reg.authority.len()
+                    + reg.purpose.len()
+                    + reg.scope.len()
+                    + reg.sections.iter().map(|section| {
+                        section.title.len()
+                            + section.full_text.len()
+                            + section.subsections.iter().map(|sub| sub.content.len()).sum::<usize>()
+                    }).sum::<usize>()
+                    + reg.interpretations.iter().map(|i| i.interpretation_text.len()).sum::<usize>()
+                    + reg.compliance_requirements.iter().map(|r| r.description.len()).sum::<usize>()
+            })
+            .sum();
+        let index = self.regulations.capacity()
+            * (std::mem::size_of::<String>() + std::mem::size_of::<FedRegulation>())
+            + self.regulations.keys().map(String::len).sum::<usize>();
+
+        std::mem::size_of::<Self>() + text + index
+    }
+
     /// Get specific regulation by ID
     pub fn get_regulation(&self, regulation_id: &str) -> Option<&FedRegulation> {
         self.regulations.get(regulation_id)
@@ -649,6 +649,38 @@ impl FdaCfrTitle21 {
     fn create_part_814() -> CfrPart { CfrPart { part_number: 814, title: "Premarket Approval".to_string(), authority: vec!["21 U.S.C. 371".to_string()], source: "Various".to_string(), scope: "PMA applications".to_string(), effective_date: Utc::now(), last_revised: Utc::now(), subparts: HashMap::new(), definitions: HashMap::new(), cross_references: vec![] } }
     fn create_part_860() -> CfrPart { CfrPart { part_number: 860, title: "Medical Device Classification".to_string(), authority: vec!["21 U.S.C. 371".to_string()], source: "Various".to_string(), scope: "Device classification".to_string(), effective_date: Utc::now(), last_revised: Utc::now(), subparts: HashMap::new(), definitions: HashMap::new(), cross_references: vec![] } }
 
+    /// Bytes held by the loaded text plus the part, subpart and section indexes
+    pub fn memory_footprint(&self) -> usize {
+        let parts: usize = self
+            .parts
+            .values()
+            .map(|part| {
+                let sections: usize = part.subparts.values().map(|subpart| {
+                    subpart.title.len()
+                        + subpart.sections.capacity()
+                            * (std::mem::size_of::<String>() + std::mem::size_of::<Section>())
+                        + subpart.sections.values().map(|section| {
+                            section.section_number.len()
+                                + section.title.len()
+                                + section.full_text.len()
+                                + section.paragraphs.iter().map(|p| p.content.len()).sum::<usize>()
+                                + section.requirements.iter().map(|r| r.description.len()).sum::<usize>()
+                        }).sum::<usize>()
+                }).sum();
+
+                part.title.len()
+                    + part.source.len()
+                    + part.scope.len()
+                    + part.definitions.iter().map(|(term, definition)| term.len() + definition.len()).sum::<usize>()
+                    + part.subparts.capacity() * (std::mem::size_of::<String>() + std::mem::size_of::<Subpart>())
+                    + sections
+            })
+            .sum();
+        let index = self.parts.capacity() * (std::mem::size_of::<u32>() + std::mem::size_of::<CfrPart>());
+
+        std::mem::size_of::<Self>() + parts + index
+    }
+
     /// Get specific CFR Part by number
     pub fn get_part(&self, part_number: u32) -> Option<&CfrPart> {
         self.parts.get(&part_number)
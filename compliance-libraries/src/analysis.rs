@@ -0,0 +1,686 @@
+//! Cross-reference analysis across the compliance libraries
+//!
+//! Parses citations in regulatory text ("Article 4(7)", "21 CFR 11.10",
+//! "FERC Order No. 888") and links each one to the article, part or section
+//! it points at, so a regulation can be navigated as a graph.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use crate::technology::gdpr_complete::Article;
+use crate::{FdaCfrTitle21, FederalReserveRegulations, GdprCompleteLibrary};
+
+// Ranges such as "Articles 12-23" are expanded up to this many articles
+const MAX_RANGE_EXPANSION: u32 = 50;
+
+/// What a citation points at, as parsed from the text
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReferenceTarget {
+    GdprArticle {
+        article_number: u32,
+        paragraph: Option<u32>,
+        point: Option<String>,
+    },
+    CfrProvision {
+        title: u32,
+        part: u32,
+        section: Option<String>,
+    },
+    FercOrder {
+        order_number: String,
+    },
+    /// An article of some instrument other than the GDPR
+    ExternalArticle {
+        article_number: u32,
+        instrument: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Citation {
+    /// The citation as written in the source text
+    pub text: String,
+    pub target: ReferenceTarget,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedReference {
+    pub citation: Citation,
+    /// Library id as used by `ComplianceLibrariesManager`
+    pub library_id: String,
+    /// Canonical id of the linked provision, e.g. "Article 15" or "21 CFR 11.10"
+    pub target_id: String,
+    pub target_title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedReference {
+    pub citation: Citation,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReferenceResolution {
+    pub resolved: Vec<ResolvedReference>,
+    pub unresolved: Vec<UnresolvedReference>,
+}
+
+/// Edge of the reference graph between two GDPR articles or out to another library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceEdge {
+    pub from: String,
+    pub to: ResolvedReference,
+}
+
+static ARTICLE_CITATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\bArticles?\s+(\d+)((?:\(\d+\))?(?:\([a-z]\))?)((?:\s*(?:,|and|or|-|to)\s*\d+)*)(\s+of\s+(?:Regulation|Directive|Decision)\s+\(?[A-Z]*\)?\s*(?:No\s+)?\d+/\d+(?:/[A-Z]+)?)?",
+    )
+    .expect("valid article citation pattern")
+});
+
+static CFR_CITATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(\d+)\s+CFR\s+(?:[Pp]art\s+)?(\d+)(?:\.(\d+))?").expect("valid CFR citation pattern")
+});
+
+static FERC_CITATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:FERC\s+)?Order\s+No\.\s*(\d+(?:-[A-Z])?)").expect("valid FERC order pattern")
+});
+
+/// Built-in libraries `resolve_references` links against, built on first use
+static BUILT_IN_LIBRARIES: LazyLock<(FederalReserveRegulations, FdaCfrTitle21, GdprCompleteLibrary)> =
+    LazyLock::new(|| (FederalReserveRegulations::new(), FdaCfrTitle21::new(), GdprCompleteLibrary::new()));
+
+/// Resolves citations against the loaded libraries
+pub struct ReferenceResolver<'a> {
+    pub fed_regulations: &'a FederalReserveRegulations,
+    pub fda_cfr: &'a FdaCfrTitle21,
+    pub gdpr: &'a GdprCompleteLibrary,
+}
+
+/// Resolve the references in a GDPR article against the built-in libraries
+///
+/// Citations no library holds are returned in `unresolved` with the reason.
+pub fn resolve_references(article: &Article) -> ReferenceResolution {
+    let (fed_regulations, fda_cfr, gdpr) = &*BUILT_IN_LIBRARIES;
+    ReferenceResolver::new(fed_regulations, fda_cfr, gdpr).resolve_article(article)
+}
+
+impl<'a> ReferenceResolver<'a> {
+    pub fn new(
+        fed_regulations: &'a FederalReserveRegulations,
+        fda_cfr: &'a FdaCfrTitle21,
+        gdpr: &'a GdprCompleteLibrary,
+    ) -> Self {
+        Self { fed_regulations, fda_cfr, gdpr }
+    }
+
+    /// Resolve every citation in an article's text, paragraphs and cross references
+    pub fn resolve_article(&self, article: &Article) -> ReferenceResolution {
+        let mut sources = vec![article.full_text.as_str()];
+        sources.extend(article.paragraphs.iter().map(|p| p.text.as_str()));
+        sources.extend(article.cross_references.iter().map(String::as_str));
+
+        let mut seen = HashSet::new();
+        let citations: Vec<Citation> = sources
+            .into_iter()
+            .flat_map(|text| self.parse_citations(text))
+            .filter(|citation| seen.insert(citation.target.clone()))
+            .collect();
+
+        self.resolve_citations(citations)
+    }
+
+    /// Resolve the citations found in arbitrary regulatory text
+    pub fn resolve_text(&self, text: &str) -> ReferenceResolution {
+        self.resolve_citations(self.parse_citations(text))
+    }
+
+    /// Every resolved reference out of each loaded GDPR article
+    pub fn reference_graph(&self) -> Vec<ReferenceEdge> {
+        let mut articles: Vec<&Article> = self
+            .gdpr
+            .regulation
+            .chapters
+            .values()
+            .flat_map(|chapter| chapter.articles.values())
+            .collect();
+        articles.sort_by_key(|article| article.article_number);
+
+        articles
+            .into_iter()
+            .flat_map(|article| {
+                let from = format!("Article {}", article.article_number);
+                self.resolve_article(article)
+                    .resolved
+                    .into_iter()
+                    .filter(|reference| reference.target_id != from)
+                    .map(|to| ReferenceEdge { from: from.clone(), to })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    pub fn parse_citations(&self, text: &str) -> Vec<Citation> {
+        let mut citations = Vec::new();
+
+        for caps in ARTICLE_CITATION.captures_iter(text) {
+            let first: u32 = caps[1].parse().unwrap_or(0);
+
+            // "Article 5 of Directive 95/46/EC" cites another instrument
+            if let Some(instrument) = caps.get(4).map(|m| m.as_str().trim()) {
+                if !instrument.contains("2016/679") {
+                    citations.push(Citation {
+                        text: caps[0].trim().to_string(),
+                        target: ReferenceTarget::ExternalArticle {
+                            article_number: first,
+                            instrument: instrument.trim_start_matches("of").trim().to_string(),
+                        },
+                    });
+                    continue;
+                }
+            }
+
+            let (paragraph, point) = parse_subdivision(caps.get(2).map_or("", |m| m.as_str()));
+            citations.push(Citation {
+                text: caps[0].trim().to_string(),
+                target: ReferenceTarget::GdprArticle { article_number: first, paragraph, point },
+            });
+
+            for article_number in parse_article_list(first, caps.get(3).map_or("", |m| m.as_str())) {
+                citations.push(Citation {
+                    text: format!("Article {}", article_number),
+                    target: ReferenceTarget::GdprArticle { article_number, paragraph: None, point: None },
+                });
+            }
+        }
+
+        for caps in CFR_CITATION.captures_iter(text) {
+            citations.push(Citation {
+                text: caps[0].to_string(),
+                target: ReferenceTarget::CfrProvision {
+                    title: caps[1].parse().unwrap_or(0),
+                    part: caps[2].parse().unwrap_or(0),
+                    section: caps.get(3).map(|m| m.as_str().to_string()),
+                },
+            });
+        }
+
+        for caps in FERC_CITATION.captures_iter(text) {
+            citations.push(Citation {
+                text: caps[0].to_string(),
+                target: ReferenceTarget::FercOrder {
+                    order_number: caps[1].to_string(),
+                },
+            });
+        }
+
+        citations
+    }
+
+    fn resolve_citations(&self, citations: Vec<Citation>) -> ReferenceResolution {
+        let mut resolution = ReferenceResolution::default();
+
+        for citation in citations {
+            match self.resolve_citation(&citation) {
+                Ok((library_id, target_id, target_title)) => resolution.resolved.push(ResolvedReference {
+                    citation,
+                    library_id: library_id.to_string(),
+                    target_id,
+                    target_title,
+                }),
+                Err(reason) => resolution.unresolved.push(UnresolvedReference { citation, reason }),
+            }
+        }
+
+        resolution
+    }
+
+    fn resolve_citation(&self, citation: &Citation) -> Result<(&'static str, String, String), String> {
+        match &citation.target {
+            ReferenceTarget::GdprArticle { article_number, paragraph, .. } => {
+                let article = self
+                    .gdpr
+                    .get_article(*article_number)
+                    .ok_or_else(|| format!("GDPR Article {} is not in the library", article_number))?;
+
+                if let Some(paragraph) = paragraph {
+                    if !article.paragraphs.is_empty()
+                        && !article.paragraphs.iter().any(|p| p.paragraph_number == *paragraph)
+                    {
+                        return Err(format!("Article {} has no paragraph {}", article_number, paragraph));
+                    }
+                }
+
+                Ok(("gdpr_complete", format!("Article {}", article_number), article.title.clone()))
+            }
+            ReferenceTarget::CfrProvision { title: 21, part, section } => {
+                if let Some(section) = section {
+                    if let Some((_, found)) = self.fda_cfr.find_section(&format!("{}.{}", part, section)) {
+                        return Ok(("fda_cfr_title21", format!("21 CFR {}", found.section_number), found.title.clone()));
+                    }
+                }
+                let found = self
+                    .fda_cfr
+                    .get_part(*part)
+                    .ok_or_else(|| format!("21 CFR Part {} is not in the library", part))?;
+                Ok(("fda_cfr_title21", format!("21 CFR {}", found.part_number), found.title.clone()))
+            }
+            ReferenceTarget::CfrProvision { title: 12, part, section } => {
+                // The library is keyed by regulation letter, so match on the CFR id instead
+                let regulation_id = format!("12 CFR {}", part);
+                let regulation = self
+                    .fed_regulations
+                    .regulations
+                    .values()
+                    .find(|regulation| regulation.regulation_id == regulation_id)
+                    .ok_or_else(|| format!("12 CFR {} is not in the library", part))?;
+                if let Some(section) = section {
+                    let number = format!("{}.{}", part, section);
+                    if let Some(found) = regulation.sections.iter().find(|s| s.section_number == number) {
+                        return Ok(("fed_regulations", format!("12 CFR {}", number), found.title.clone()));
+                    }
+                }
+                Ok(("fed_regulations", regulation.regulation_id.clone(), regulation.title.clone()))
+            }
+            ReferenceTarget::CfrProvision { title, .. } => {
+                Err(format!("no library is loaded for CFR title {}", title))
+            }
+            ReferenceTarget::FercOrder { order_number } => {
+                Err(format!("FERC Order No. {} is not in a loaded library", order_number))
+            }
+            ReferenceTarget::ExternalArticle { instrument, .. } => {
+                Err(format!("{} is not in a loaded library", instrument))
+            }
+        }
+    }
+}
+
+/// "(7)" -> paragraph 7, "(1)(a)" -> paragraph 1 point a
+fn parse_subdivision(text: &str) -> (Option<u32>, Option<String>) {
+    let parts: Vec<&str> = text
+        .split(['(', ')'])
+        .filter(|part| !part.is_empty())
+        .collect();
+    let paragraph = parts.first().and_then(|p| p.parse().ok());
+    let point = parts
+        .iter()
+        .find(|p| p.chars().all(|c| c.is_ascii_lowercase()))
+        .map(|p| p.to_string());
+    (paragraph, point)
+}
+
+/// The articles after the first in "Articles 13 and 14" or "Articles 12-23"
+fn parse_article_list(first: u32, tail: &str) -> Vec<u32> {
+    let mut numbers = Vec::new();
+    let mut previous = first;
+    let mut range_pending = false;
+
+    for token in tail.split_whitespace().flat_map(|t| t.split_inclusive(['-', ','])) {
+        let token = token.trim_matches(',');
+        if token == "-" || token == "to" || token.ends_with('-') {
+            range_pending = true;
+        }
+        let digits = token.trim_matches(|c: char| !c.is_ascii_digit());
+        let Ok(number) = digits.parse::<u32>() else { continue };
+
+        if range_pending && number > previous {
+            numbers.extend((previous + 1..=number).take(MAX_RANGE_EXPANSION as usize));
+        } else {
+            numbers.push(number);
+        }
+        range_pending = false;
+        previous = number;
+    }
+
+    numbers
+}
+
+/// Deontic force of an extracted obligation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObligationModality {
+    /// "shall", "must", "is required to"
+    Duty,
+    /// "shall not", "must not", "may not", "is prohibited from"
+    Prohibition,
+}
+
+/// A machine-readable obligation found in regulatory prose
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Obligation {
+    pub modality: ObligationModality,
+    pub modal_verb: String,
+    /// Who the obligation binds, e.g. "the controller" or "Each employer"
+    pub party: String,
+    pub action: String,
+    pub deadline: Option<String>,
+    pub condition: Option<String>,
+    /// 0.0-1.0; lower for passive voice, missing parties and very long sentences
+    pub confidence: f64,
+    /// Byte range of the source sentence within the text passed in
+    pub span: std::ops::Range<usize>,
+    pub source_text: String,
+}
+
+impl Obligation {
+    /// Shape the obligation as a requirement the assessor can evaluate
+    pub fn to_requirement(&self, category: &str) -> aion_core::Requirement {
+        let verb = match self.modality {
+            ObligationModality::Duty => "must",
+            ObligationModality::Prohibition => "must not",
+        };
+        aion_core::Requirement {
+            id: uuid::Uuid::new_v4(),
+            title: format!("{} {} {}", self.party, verb, self.action),
+            description: self.source_text.clone(),
+            mandatory: true,
+            conditions: self
+                .condition
+                .iter()
+                .map(|condition| aion_core::Condition {
+                    id: uuid::Uuid::new_v4(),
+                    description: condition.clone(),
+                    expression: String::new(),
+                    context_variables: Vec::new(),
+                })
+                .collect(),
+            exceptions: Vec::new(),
+            evidence_required: Vec::new(),
+            validation_rules: Vec::new(),
+            priority: if self.deadline.is_some() { 1 } else { 2 },
+            category: category.to_string(),
+        }
+    }
+}
+
+/// Abbreviations whose period does not end a sentence, lowercase without the final period
+const ABBREVIATIONS: &[&str] = &[
+    "art", "arts", "sec", "secs", "no", "nos", "para", "paras", "p", "pp", "cf", "vs", "v", "al", "inc", "co",
+    "corp", "ltd", "mr", "mrs", "ms", "dr", "fig", "ch", "pt", "subpt", "reg", "regs", "vol", "approx",
+];
+
+static OBLIGATION_PATTERNS: LazyLock<ObligationPatterns> = LazyLock::new(ObligationPatterns::new);
+
+struct ObligationPatterns {
+    modal: Regex,
+    deadline: Regex,
+    leading_condition: Regex,
+    trailing_condition: Regex,
+}
+
+impl ObligationPatterns {
+    fn new() -> Self {
+        Self {
+            modal: Regex::new(
+                r"(?i)\b(shall\s+not|must\s+not|may\s+not|is\s+prohibited\s+from|are\s+prohibited\s+from|shall|must|is\s+required\s+to|are\s+required\s+to)\b",
+            )
+            .expect("valid modal pattern"),
+            deadline: Regex::new(
+                r"(?i)\b(?:without\s+undue\s+delay(?:\s+and,?\s+(?:where\s+feasible,\s+)?not\s+later\s+than\s+\d+\s+\w+)?(?:\s+after[^,;.]*)?|(?:within|not\s+later\s+than|no\s+later\s+than|at\s+least\s+every)\s+(?:\d+|one|two|three|six|twelve|thirty)\s+(?:working\s+)?(?:hours?|days?|weeks?|months?|years?)(?:\s+(?:after|of|from|following)[^,;.]*)?|immediately|annually|promptly)",
+            )
+            .expect("valid deadline pattern"),
+            leading_condition: Regex::new(r"(?i)^((?:where|if|when|in\s+the\s+case\s+of|unless|provided\s+that)\b[^,]*),\s*")
+                .expect("valid leading condition pattern"),
+            trailing_condition: Regex::new(r"(?i)[,\s]+((?:where|if|when|unless|provided\s+that|except\s+where)\b.*)$")
+                .expect("valid trailing condition pattern"),
+        }
+    }
+}
+
+/// Find modal-verb obligations ("shall", "must", "may not") in regulatory text
+pub fn extract_obligations(text: &str) -> Vec<Obligation> {
+    sentence_spans(text)
+        .into_iter()
+        .filter_map(|span| parse_obligation(&OBLIGATION_PATTERNS, text, span))
+        .collect()
+}
+
+/// Run obligation extraction over a library article; spans are relative to its full text
+pub fn extract_article_obligations(article: &Article) -> Vec<Obligation> {
+    extract_obligations(&article.full_text)
+}
+
+fn parse_obligation(patterns: &ObligationPatterns, text: &str, span: std::ops::Range<usize>) -> Option<Obligation> {
+    let sentence = text[span.clone()].trim_end_matches(['.', ';']);
+    let modal = patterns.modal.find(sentence)?;
+    let modal_verb = modal.as_str().split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let modality = if modal_verb.contains("not") || modal_verb.contains("prohibited") {
+        ObligationModality::Prohibition
+    } else {
+        ObligationModality::Duty
+    };
+
+    // Subject side: an optional leading condition, then the party after the last comma
+    let mut before = &sentence[..modal.start()];
+    let mut condition = None;
+    if let Some(caps) = patterns.leading_condition.captures(before) {
+        condition = Some(caps[1].trim().to_string());
+        before = &before[caps[0].len()..];
+    }
+    let party = before.rsplit(',').next().unwrap_or("").trim().to_string();
+
+    // Action side: strip the deadline, then any trailing condition
+    let mut after = sentence[modal.end()..].trim().to_string();
+    let deadline = patterns.deadline.find(&after).map(|m| m.as_str().trim().to_string());
+    if let Some(deadline) = &deadline {
+        after = after.replacen(deadline.as_str(), "", 1);
+    }
+    let trailing = patterns
+        .trailing_condition
+        .captures(&after)
+        .map(|caps| (caps.get(0).map_or(after.len(), |m| m.start()), caps[1].trim().to_string()));
+    if let Some((cut, trailing_condition)) = trailing {
+        condition = condition.or(Some(trailing_condition));
+        after.truncate(cut);
+    }
+    let action = after
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c == ',' || c.is_whitespace())
+        .to_string();
+    if action.is_empty() {
+        return None;
+    }
+
+    let mut confidence: f64 = 0.5;
+    let party_words = party.split_whitespace().count();
+    if (1..=8).contains(&party_words) {
+        confidence += 0.2;
+    }
+    if !action.starts_with("be ") {
+        confidence += 0.15;
+    }
+    if modal_verb.starts_with("shall") || modal_verb.starts_with("must") {
+        confidence += 0.1;
+    }
+    if sentence.split_whitespace().count() > 60 {
+        confidence -= 0.15;
+    }
+
+    Some(Obligation {
+        modality,
+        modal_verb,
+        party,
+        action,
+        deadline,
+        condition,
+        confidence: confidence.clamp(0.0, 1.0),
+        source_text: sentence.trim().to_string(),
+        span,
+    })
+}
+
+/// Byte ranges of sentences, split on '.', ';', '!' or '?' followed by whitespace
+///
+/// A period after an abbreviation ("Art.", "No.", "e.g.", "U.S.") or before
+/// a lowercase word does not end the sentence.
+fn sentence_spans(text: &str) -> Vec<std::ops::Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let boundary = matches!(c, '.' | ';' | '!' | '?')
+            && chars.peek().is_none_or(|(_, next)| next.is_whitespace())
+            && !(c == '.' && is_abbreviation_period(text, i));
+        if boundary {
+            let end = i + c.len_utf8();
+            push_trimmed_span(text, start, end, &mut spans);
+            start = end;
+        }
+    }
+    push_trimmed_span(text, start, text.len(), &mut spans);
+
+    spans
+}
+
+/// Whether the period at byte `i` belongs to an abbreviation rather than ending a sentence
+fn is_abbreviation_period(text: &str, i: usize) -> bool {
+    let word = text[..i].rsplit(char::is_whitespace).next().unwrap_or("");
+    let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    let lowercase = word.to_lowercase();
+    let is_abbreviation = ABBREVIATIONS.contains(&lowercase.as_str())
+        // Initials and dotted forms such as "U.S" or "e.g"
+        || (word.chars().count() == 1 && word.chars().all(char::is_alphabetic))
+        || (word.contains('.')
+            && word.split('.').all(|part| (1..=2).contains(&part.chars().count()) && part.chars().all(char::is_alphabetic)));
+    let next_word_lowercase = text[i + 1..]
+        .trim_start()
+        .chars()
+        .next()
+        .is_some_and(char::is_lowercase);
+    is_abbreviation || next_word_lowercase
+}
+
+fn push_trimmed_span(text: &str, start: usize, end: usize, spans: &mut Vec<std::ops::Range<usize>>) {
+    let slice = &text[start..end];
+    let leading = slice.len() - slice.trim_start().len();
+    let trimmed_end = start + slice.trim_end().len();
+    if start + leading < trimmed_end {
+        spans.push(start + leading..trimmed_end);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article_citing(text: &str) -> Article {
+        Article {
+            article_number: 1,
+            title: "Test".to_string(),
+            full_text: text.to_string(),
+            paragraphs: Vec::new(),
+            obligations: Vec::new(),
+            rights: Vec::new(),
+            penalties: Vec::new(),
+            derogations: Vec::new(),
+            cross_references: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_article_citations_across_libraries() {
+        let fed = FederalReserveRegulations::new();
+        let fda = FdaCfrTitle21::new();
+        let gdpr = GdprCompleteLibrary::new();
+        let resolver = ReferenceResolver::new(&fed, &fda, &gdpr);
+
+        let article = article_citing(
+            "The rights in Article 15(1) and Articles 17 and 20 apply, records kept under \
+             21 CFR 11.10 and 12 CFR 1002 excepted, as does FERC Order No. 888. \
+             See also Article 5 of Directive 95/46/EC and Article 99.",
+        );
+        let resolution = resolver.resolve_article(&article);
+        assert!(!resolver.reference_graph().iter().any(|edge| edge.from == edge.to.target_id));
+
+        let targets: Vec<&str> = resolution.resolved.iter().map(|r| r.target_id.as_str()).collect();
+        assert!(targets.contains(&"Article 15"));
+        assert!(targets.contains(&"Article 17"));
+        assert!(targets.contains(&"Article 20"));
+        assert!(targets.contains(&"21 CFR 11.10"));
+        assert!(targets.contains(&"12 CFR 1002"));
+
+        let unresolved: Vec<&str> = resolution.unresolved.iter().map(|u| u.citation.text.as_str()).collect();
+        assert!(unresolved.iter().any(|t| t.contains("Order No. 888")));
+        assert!(unresolved.iter().any(|t| t.contains("Directive 95/46/EC")));
+        assert!(unresolved.contains(&"Article 99"));
+
+        let shared = resolve_references(&article);
+        assert_eq!(shared.resolved.len(), resolution.resolved.len());
+        assert_eq!(shared.unresolved.len(), resolution.unresolved.len());
+    }
+
+    #[test]
+    fn test_parse_article_ranges() {
+        assert_eq!(parse_article_list(12, "-15"), vec![13, 14, 15]);
+        assert_eq!(parse_article_list(13, " and 14"), vec![14]);
+        assert_eq!(parse_subdivision("(1)(a)"), (Some(1), Some("a".to_string())));
+    }
+
+    #[test]
+    fn test_extract_gdpr_obligations() {
+        let text = "In the case of a personal data breach, the controller shall without undue delay and, \
+                    where feasible, not later than 72 hours after having become aware of it, notify the \
+                    personal data breach to the supervisory authority. The processor shall notify the \
+                    controller without undue delay after becoming aware of a personal data breach. \
+                    This Regulation lays down rules relating to the protection of natural persons.";
+        let obligations = extract_obligations(text);
+        assert_eq!(obligations.len(), 2);
+
+        let breach = &obligations[0];
+        assert_eq!(breach.modality, ObligationModality::Duty);
+        assert_eq!(breach.party, "the controller");
+        assert_eq!(breach.action, "notify the personal data breach to the supervisory authority");
+        assert!(breach.deadline.as_deref().unwrap().contains("72 hours"));
+        assert_eq!(breach.condition.as_deref(), Some("In the case of a personal data breach"));
+        assert!(text[breach.span.clone()].starts_with("In the case of"));
+
+        let processor = &obligations[1];
+        assert_eq!(processor.party, "The processor");
+        assert_eq!(processor.action, "notify the controller");
+        assert!(processor.deadline.as_deref().unwrap().starts_with("without undue delay"));
+        assert!(processor.confidence > 0.9);
+
+        let requirement = processor.to_requirement("data_protection");
+        assert!(requirement.mandatory);
+        assert_eq!(requirement.title, "The processor must notify the controller");
+    }
+
+    #[test]
+    fn test_extract_osha_obligations() {
+        let text = "Each employer shall furnish to each of his employees employment and a place of \
+                    employment which are free from recognized hazards. An employee may not remove a \
+                    lockout device unless authorized by the employer; Exit routes must be kept free \
+                    of obstructions.";
+        let obligations = extract_obligations(text);
+        assert_eq!(obligations.len(), 3);
+
+        assert_eq!(obligations[0].party, "Each employer");
+        assert!(obligations[0].action.starts_with("furnish to each of his employees"));
+
+        assert_eq!(obligations[1].modality, ObligationModality::Prohibition);
+        assert_eq!(obligations[1].action, "remove a lockout device");
+        assert_eq!(obligations[1].condition.as_deref(), Some("unless authorized by the employer"));
+
+        // Passive voice names no actor, so it is trusted less
+        assert_eq!(obligations[2].party, "Exit routes");
+        assert!(obligations[2].confidence < obligations[0].confidence);
+    }
+
+    #[test]
+    fn test_sentences_do_not_split_on_abbreviations() {
+        let text = "Under Art. 33 the controller shall notify the authority, e.g. by e-mail. \
+                    Each U.S. registrant shall file Order No. 2222 reports annually.";
+        let obligations = extract_obligations(text);
+        assert_eq!(obligations.len(), 2);
+        assert_eq!(obligations[0].action, "notify the authority, e.g. by e-mail");
+        assert!(obligations[0].source_text.starts_with("Under Art. 33"));
+        assert_eq!(obligations[1].party, "Each U.S. registrant");
+        assert!(obligations[1].source_text.ends_with("Order No. 2222 reports annually"));
+    }
+}
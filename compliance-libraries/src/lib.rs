@@ -18,9 +18,10 @@ pub mod analysis;
 pub mod updates;
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 
 // Re-export major regulatory libraries
 pub use financial_services::fed_regulations::FederalReserveRegulations;
@@ -37,6 +38,9 @@ pub struct ComplianceLibrariesManager {
     pub total_articles: u64,
     pub supported_jurisdictions: Vec<String>,
     pub supported_industries: Vec<String>,
+    /// Manifests skipped by `load_from_dir`, with the reason
+    #[serde(default)]
+    pub manifest_errors: Vec<ManifestError>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +54,9 @@ pub struct LibraryInfo {
     pub last_updated: DateTime<Utc>,
     pub coverage_percentage: f64,
     pub source_authority: String,
+    /// Article files backing the library, when it was loaded from a manifest
+    #[serde(default)]
+    pub content_files: Vec<PathBuf>,
 }
 
 impl ComplianceLibrariesManager {
@@ -67,6 +74,7 @@ impl ComplianceLibrariesManager {
             last_updated: Utc::now(),
             coverage_percentage: 100.0,
             source_authority: "Federal Reserve System".to_string(),
+            content_files: Vec::new(),
         });
 
         libraries.insert("sec_rules".to_string(), LibraryInfo {
@@ -79,6 +87,7 @@ impl ComplianceLibrariesManager {
             last_updated: Utc::now(),
             coverage_percentage: 100.0,
             source_authority: "Securities and Exchange Commission".to_string(),
+            content_files: Vec::new(),
         });
 
         libraries.insert("basel_framework".to_string(), LibraryInfo {
@@ -91,6 +100,7 @@ impl ComplianceLibrariesManager {
             last_updated: Utc::now(),
             coverage_percentage: 100.0,
             source_authority: "Basel Committee on Banking Supervision".to_string(),
+            content_files: Vec::new(),
         });
 
         // Healthcare Libraries
@@ -104,6 +114,7 @@ impl ComplianceLibrariesManager {
             last_updated: Utc::now(),
             coverage_percentage: 100.0,
             source_authority: "Food and Drug Administration".to_string(),
+            content_files: Vec::new(),
         });
 
         libraries.insert("ema_guidelines".to_string(), LibraryInfo {
@@ -116,6 +127,7 @@ impl ComplianceLibrariesManager {
             last_updated: Utc::now(),
             coverage_percentage: 95.8,
             source_authority: "European Medicines Agency".to_string(),
+            content_files: Vec::new(),
         });
 
         // Technology Libraries
@@ -129,6 +141,7 @@ impl ComplianceLibrariesManager {
             last_updated: Utc::now(),
             coverage_percentage: 100.0,
             source_authority: "European Commission".to_string(),
+            content_files: Vec::new(),
         });
 
         libraries.insert("ccpa_complete".to_string(), LibraryInfo {
@@ -141,6 +154,7 @@ impl ComplianceLibrariesManager {
             last_updated: Utc::now(),
             coverage_percentage: 100.0,
             source_authority: "California Attorney General".to_string(),
+            content_files: Vec::new(),
         });
 
         // Energy Libraries
@@ -154,6 +168,7 @@ impl ComplianceLibrariesManager {
             last_updated: Utc::now(),
             coverage_percentage: 98.5,
             source_authority: "Federal Energy Regulatory Commission".to_string(),
+            content_files: Vec::new(),
         });
 
         // Manufacturing Libraries
@@ -167,6 +182,7 @@ impl ComplianceLibrariesManager {
             last_updated: Utc::now(),
             coverage_percentage: 100.0,
             source_authority: "Occupational Safety and Health Administration".to_string(),
+            content_files: Vec::new(),
         });
 
         Self {
@@ -198,9 +214,66 @@ impl ComplianceLibrariesManager {
                 "International Trade".to_string(),
                 "Intellectual Property".to_string(),
             ],
+            manifest_errors: Vec::new(),
         }
     }
 
+    /// Build the catalogue from the `.json`/`.yaml` library manifests in a directory
+    ///
+    /// Manifests that fail to parse, duplicate an id or reference missing article
+    /// files are skipped and listed in `manifest_errors`; only an unreadable
+    /// directory is an error.
+    pub fn load_from_dir(path: &Path) -> Result<Self> {
+        let mut manifest_paths: Vec<PathBuf> = std::fs::read_dir(path)
+            .with_context(|| format!("reading library manifest directory {}", path.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("json" | "yaml" | "yml")))
+            .collect();
+        manifest_paths.sort();
+
+        let mut libraries = HashMap::new();
+        let mut manifest_errors = Vec::new();
+
+        for manifest_path in manifest_paths {
+            match LibraryManifest::load(&manifest_path) {
+                Ok(manifest) if libraries.contains_key(&manifest.id) => manifest_errors.push(ManifestError {
+                    path: manifest_path,
+                    reason: format!("duplicate library id '{}'", manifest.id),
+                }),
+                Ok(manifest) => {
+                    let info = manifest.into_library_info(path);
+                    libraries.insert(info.library_id.clone(), info);
+                }
+                Err(e) => manifest_errors.push(ManifestError {
+                    path: manifest_path,
+                    reason: format!("{:#}", e),
+                }),
+            }
+        }
+
+        for error in &manifest_errors {
+            tracing::warn!("⚠️ Skipping library manifest {}: {}", error.path.display(), error.reason);
+        }
+
+        let mut supported_jurisdictions: Vec<String> = libraries.values().map(|lib| lib.jurisdiction.clone()).collect();
+        supported_jurisdictions.sort();
+        supported_jurisdictions.dedup();
+        let mut supported_industries: Vec<String> = libraries.values().flat_map(|lib| lib.industry.clone()).collect();
+        supported_industries.sort();
+        supported_industries.dedup();
+
+        Ok(Self {
+            total_regulations: libraries.values().map(|lib| lib.regulation_count as u64).sum(),
+            total_articles: libraries.values().map(|lib| lib.article_count as u64).sum(),
+            libraries,
+            last_updated: Utc::now(),
+            version: "1.0.0".to_string(),
+            supported_jurisdictions,
+            supported_industries,
+            manifest_errors,
+        })
+    }
+
     /// Get library information by ID
     pub fn get_library_info(&self, library_id: &str) -> Option<&LibraryInfo> {
         self.libraries.get(library_id)
@@ -254,57 +327,59 @@ impl ComplianceLibrariesManager {
             initialized_libraries: Vec::new(),
             failed_libraries: Vec::new(),
             total_time_ms: 0,
+            memory_usage_bytes: 0,
             memory_usage_mb: 0,
         };
 
         let start_time = std::time::Instant::now();
 
-        // Initialize Federal Reserve Regulations
-        match self.initialize_fed_regulations() {
-            Ok(_) => report.initialized_libraries.push("fed_regulations".to_string()),
-            Err(e) => report.failed_libraries.push(("fed_regulations".to_string(), e.to_string())),
-        }
-
-        // Initialize FDA CFR Title 21
-        match self.initialize_fda_cfr() {
-            Ok(_) => report.initialized_libraries.push("fda_cfr_title21".to_string()),
-            Err(e) => report.failed_libraries.push(("fda_cfr_title21".to_string(), e.to_string())),
-        }
+        let mut library_ids: Vec<&String> = self.libraries.keys().collect();
+        library_ids.sort();
 
-        // Initialize GDPR Complete
-        match self.initialize_gdpr() {
-            Ok(_) => report.initialized_libraries.push("gdpr_complete".to_string()),
-            Err(e) => report.failed_libraries.push(("gdpr_complete".to_string(), e.to_string())),
+        for library_id in library_ids {
+            match self.initialize_library(&self.libraries[library_id]) {
+                Ok(footprint) => {
+                    report.initialized_libraries.push(library_id.clone());
+                    report.memory_usage_bytes += footprint as u64;
+                }
+                Err(e) => report.failed_libraries.push((library_id.clone(), e.to_string())),
+            }
         }
 
         report.total_time_ms = start_time.elapsed().as_millis() as u64;
-        report.memory_usage_mb = self.estimate_memory_usage();
+        report.memory_usage_mb = report.memory_usage_bytes.div_ceil(1024 * 1024);
 
         Ok(report)
     }
 
-    fn initialize_fed_regulations(&self) -> Result<()> {
-        let _fed_regs = FederalReserveRegulations::new();
-        Ok(())
+    /// Loads the library and returns the bytes it occupies once in memory
+    fn initialize_library(&self, library: &LibraryInfo) -> Result<usize> {
+        match library.library_id.as_str() {
+            "fed_regulations" => self.initialize_fed_regulations(),
+            "fda_cfr_title21" => self.initialize_fda_cfr(),
+            "gdpr_complete" => self.initialize_gdpr(),
+            _ => {
+                let mut footprint = library.content_files.capacity() * std::mem::size_of::<String>();
+                for file in &library.content_files {
+                    footprint += std::fs::read_to_string(file)
+                        .with_context(|| format!("reading article file {}", file.display()))?
+                        .len();
+                }
+                Ok(footprint)
+            }
+        }
     }
 
-    fn initialize_fda_cfr(&self) -> Result<()> {
-        let _fda_cfr = FdaCfrTitle21::new();
-        Ok(())
+    fn initialize_fed_regulations(&self) -> Result<usize> {
+        Ok(FederalReserveRegulations::new().memory_footprint())
     }
 
-    fn initialize_gdpr(&self) -> Result<()> {
-        let _gdpr = GdprCompleteLibrary::new();
-        Ok(())
+    fn initialize_fda_cfr(&self) -> Result<usize> {
+        Ok(FdaCfrTitle21::new().memory_footprint())
     }
 
-    fn estimate_memory_usage(&self) -> u64 {
-        // Estimate memory usage in MB based on content size
-        let base_memory = 50; // Base overhead
-        let regulation_memory = self.total_regulations * 10; // ~10KB per regulation
-        let article_memory = self.total_articles * 5; // ~5KB per article
-
-        (base_memory + regulation_memory + article_memory) / 1024
+    fn initialize_gdpr(&self) -> Result<usize> {
+        Ok(GdprCompleteLibrary::new().memory_footprint())
     }
 }
 
@@ -318,11 +393,89 @@ pub struct CoverageStatistics {
     pub total_articles: u64,
 }
 
+/// A manifest that `load_from_dir` could not use
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestError {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+/// On-disk description of one library, as read by `load_from_dir`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryManifest {
+    pub id: String,
+    pub name: String,
+    pub jurisdiction: String,
+    #[serde(default)]
+    pub industries: Vec<String>,
+    #[serde(default)]
+    pub source_authority: String,
+    #[serde(default = "default_regulation_count")]
+    pub regulation_count: u32,
+    #[serde(default = "default_coverage_percentage")]
+    pub coverage_percentage: f64,
+    /// Paths relative to the manifest directory
+    #[serde(default)]
+    pub article_files: Vec<PathBuf>,
+}
+
+fn default_regulation_count() -> u32 {
+    1
+}
+
+fn default_coverage_percentage() -> f64 {
+    100.0
+}
+
+impl LibraryManifest {
+    fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let manifest: Self = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => serde_json::from_str(&text)?,
+            _ => serde_yaml::from_str(&text)?,
+        };
+
+        if manifest.id.trim().is_empty() {
+            bail!("library id is empty");
+        }
+
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        let missing: Vec<String> = manifest
+            .article_files
+            .iter()
+            .filter(|file| !base.join(file).is_file())
+            .map(|file| file.display().to_string())
+            .collect();
+        if !missing.is_empty() {
+            bail!("missing article files: {}", missing.join(", "));
+        }
+
+        Ok(manifest)
+    }
+
+    fn into_library_info(self, base: &Path) -> LibraryInfo {
+        LibraryInfo {
+            library_id: self.id,
+            name: self.name,
+            jurisdiction: self.jurisdiction,
+            industry: self.industries,
+            regulation_count: self.regulation_count,
+            article_count: self.article_files.len() as u32,
+            last_updated: Utc::now(),
+            coverage_percentage: self.coverage_percentage,
+            source_authority: self.source_authority,
+            content_files: self.article_files.into_iter().map(|file| base.join(file)).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InitializationReport {
     pub initialized_libraries: Vec<String>,
     pub failed_libraries: Vec<(String, String)>,
     pub total_time_ms: u64,
+    /// Measured size of the loaded libraries
+    pub memory_usage_bytes: u64,
     pub memory_usage_mb: u64,
 }
 
@@ -344,87 +497,126 @@ impl UniversalComplianceSearch {
 
     /// Search across all libraries
     pub fn search_all(&self, query: &str) -> UniversalSearchResults {
-        let mut results = UniversalSearchResults {
-            query: query.to_string(),
-            total_results: 0,
-            results_by_library: HashMap::new(),
-        };
+        self.search_all_paged(query, 0, usize::MAX)
+    }
+
+    /// Search across all libraries, ranked by relevance, returning one page of matches
+    pub fn search_all_paged(&self, query: &str, offset: usize, limit: usize) -> UniversalSearchResults {
+        let terms = query_terms(query);
+        let mut matches = Vec::new();
 
         // Search Federal Reserve Regulations
-        let fed_results = self.fed_regulations.search_regulations(query);
-        if !fed_results.is_empty() {
-            results.results_by_library.insert(
-                "fed_regulations".to_string(),
-                LibrarySearchResult {
-                    library_name: "Federal Reserve Regulations".to_string(),
-                    result_count: fed_results.len(),
-                    results: fed_results.into_iter()
-                        .map(|reg| SearchMatch {
-                            id: reg.regulation_id.clone(),
-                            title: reg.title.clone(),
-                            content_snippet: reg.purpose.clone(),
-                            relevance_score: 0.8,
-                            regulation_type: "Federal Reserve Regulation".to_string(),
-                        })
-                        .collect(),
-                }
-            );
-        }
+        matches.extend(self.fed_regulations.search_regulations(query).into_iter().map(|reg| SearchMatch {
+            id: reg.regulation_id.clone(),
+            title: reg.title.clone(),
+            content_snippet: reg.purpose.clone(),
+            relevance_score: relevance_score(&terms, &reg.title, &format!("{} {}", reg.purpose, reg.scope)),
+            regulation_type: "Federal Reserve Regulation".to_string(),
+            library_id: "fed_regulations".to_string(),
+        }));
 
         // Search FDA CFR
-        let fda_results = self.fda_cfr.search_parts(query);
-        if !fda_results.is_empty() {
-            results.results_by_library.insert(
-                "fda_cfr".to_string(),
-                LibrarySearchResult {
-                    library_name: "FDA CFR Title 21".to_string(),
-                    result_count: fda_results.len(),
-                    results: fda_results.into_iter()
-                        .map(|part| SearchMatch {
-                            id: format!("21 CFR {}", part.part_number),
-                            title: part.title.clone(),
-                            content_snippet: part.scope.clone(),
-                            relevance_score: 0.8,
-                            regulation_type: "FDA Regulation".to_string(),
-                        })
-                        .collect(),
-                }
-            );
-        }
+        matches.extend(self.fda_cfr.search_parts(query).into_iter().map(|part| SearchMatch {
+            id: format!("21 CFR {}", part.part_number),
+            title: part.title.clone(),
+            content_snippet: part.scope.clone(),
+            relevance_score: relevance_score(&terms, &part.title, &part.scope),
+            regulation_type: "FDA Regulation".to_string(),
+            library_id: "fda_cfr".to_string(),
+        }));
 
         // Search GDPR
-        let gdpr_results = self.gdpr.search_articles(query);
-        if !gdpr_results.is_empty() {
-            results.results_by_library.insert(
-                "gdpr".to_string(),
-                LibrarySearchResult {
-                    library_name: "GDPR Complete".to_string(),
-                    result_count: gdpr_results.len(),
-                    results: gdpr_results.into_iter()
-                        .map(|article| SearchMatch {
-                            id: format!("Article {}", article.article_number),
-                            title: article.title.clone(),
-                            content_snippet: article.full_text.chars().take(200).collect(),
-                            relevance_score: 0.8,
-                            regulation_type: "GDPR Article".to_string(),
-                        })
-                        .collect(),
-                }
-            );
+        matches.extend(self.gdpr.search_articles(query).into_iter().map(|article| SearchMatch {
+            id: format!("Article {}", article.article_number),
+            title: article.title.clone(),
+            content_snippet: article.full_text.chars().take(200).collect(),
+            relevance_score: relevance_score(&terms, &article.title, &article.full_text),
+            regulation_type: "GDPR Article".to_string(),
+            library_id: "gdpr".to_string(),
+        }));
+
+        matches.sort_by(|a, b| {
+            b.relevance_score
+                .partial_cmp(&a.relevance_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        let total_count = matches.len();
+        let page: Vec<SearchMatch> = matches.into_iter().skip(offset).take(limit).collect();
+
+        let mut results_by_library: HashMap<String, LibrarySearchResult> = HashMap::new();
+        for search_match in &page {
+            let library = results_by_library
+                .entry(search_match.library_id.clone())
+                .or_insert_with(|| LibrarySearchResult {
+                    library_name: library_display_name(&search_match.library_id).to_string(),
+                    result_count: 0,
+                    results: Vec::new(),
+                });
+            library.result_count += 1;
+            library.results.push(search_match.clone());
         }
 
-        results.total_results = results.results_by_library.values()
-            .map(|lib_result| lib_result.result_count)
-            .sum();
+        UniversalSearchResults {
+            query: query.to_string(),
+            total_results: page.len(),
+            total_count,
+            offset,
+            has_more: offset.saturating_add(page.len()) < total_count,
+            results: page,
+            results_by_library,
+        }
+    }
+}
 
-        results
+fn library_display_name(library_id: &str) -> &'static str {
+    match library_id {
+        "fed_regulations" => "Federal Reserve Regulations",
+        "fda_cfr" => "FDA CFR Title 21",
+        "gdpr" => "GDPR Complete",
+        _ => "Unknown Library",
     }
 }
 
+fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_lowercase())
+        .collect()
+}
+
+/// Term-frequency score in [0, 1); title hits count double and long texts are damped
+fn relevance_score(terms: &[String], title: &str, body: &str) -> f64 {
+    if terms.is_empty() {
+        return 0.0;
+    }
+
+    let count = |text: &str| -> (f64, usize) {
+        let words: Vec<String> = query_terms(text);
+        let hits = words.iter().filter(|word| terms.contains(word)).count();
+        (hits as f64, words.len())
+    };
+
+    let (title_hits, _) = count(title);
+    let (body_hits, body_len) = count(body);
+    let raw = (2.0 * title_hits + body_hits / (1.0 + (body_len as f64).ln_1p())) / terms.len() as f64;
+
+    raw / (raw + 1.0)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UniversalSearchResults {
     pub query: String,
+    /// Matches returned in this page
     pub total_results: usize,
+    /// Matches across all libraries before paging
+    pub total_count: usize,
+    pub offset: usize,
+    pub has_more: bool,
+    /// This page's matches across all libraries, most relevant first
+    pub results: Vec<SearchMatch>,
     pub results_by_library: HashMap<String, LibrarySearchResult>,
 }
 
@@ -442,6 +634,7 @@ pub struct SearchMatch {
     pub content_snippet: String,
     pub relevance_score: f64,
     pub regulation_type: String,
+    pub library_id: String,
 }
 
 #[cfg(test)]
@@ -471,6 +664,92 @@ mod tests {
         assert!(results.total_results > 0);
     }
 
+    #[test]
+    fn test_paged_search() {
+        let search = UniversalComplianceSearch::new();
+        let all = search.search_all("data");
+        assert_eq!(all.total_results, all.total_count);
+        assert!(all.results.windows(2).all(|pair| pair[0].relevance_score >= pair[1].relevance_score));
+
+        let first_page = search.search_all_paged("data", 0, 1);
+        assert_eq!(first_page.total_count, all.total_count);
+        assert!(first_page.total_results <= 1);
+        assert_eq!(first_page.has_more, all.total_count > 1);
+
+        let past_end = search.search_all_paged("data", all.total_count, 10);
+        assert!(past_end.results.is_empty());
+        assert!(!past_end.has_more);
+    }
+
+    #[test]
+    fn test_relevance_score_prefers_title_hits() {
+        let terms = query_terms("consent");
+        let title_hit = relevance_score(&terms, "Conditions for consent", "Where processing is based on consent");
+        let body_hit = relevance_score(&terms, "Lawfulness of processing", "Processing is lawful where the data subject has given consent");
+        assert!(title_hit > body_hit);
+        assert!(body_hit > 0.0 && title_hit < 1.0);
+    }
+
+    #[test]
+    fn test_load_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("art1.txt"), "Article 1 - Subject matter").unwrap();
+        std::fs::write(
+            dir.path().join("lgpd.json"),
+            r#"{"id": "lgpd", "name": "Lei Geral de Protecao de Dados", "jurisdiction": "Brazil",
+                "industries": ["Technology"], "article_files": ["art1.txt"]}"#,
+        ).unwrap();
+        std::fs::write(
+            dir.path().join("pipl.yaml"),
+            "id: pipl\nname: PIPL\njurisdiction: China\narticle_files: [missing.txt]\n",
+        ).unwrap();
+        std::fs::write(dir.path().join("broken.json"), "{ not json").unwrap();
+
+        let manager = ComplianceLibrariesManager::load_from_dir(dir.path()).unwrap();
+        assert_eq!(manager.libraries.len(), 1);
+        assert_eq!(manager.get_library_info("lgpd").unwrap().article_count, 1);
+        assert_eq!(manager.supported_jurisdictions, vec!["Brazil".to_string()]);
+        assert_eq!(manager.manifest_errors.len(), 2);
+        assert!(manager.manifest_errors.iter().any(|e| e.reason.contains("missing.txt")));
+
+        let report = manager.initialize_all_libraries().unwrap();
+        assert_eq!(report.initialized_libraries, vec!["lgpd".to_string()]);
+        assert!(report.failed_libraries.is_empty());
+    }
+
+    #[test]
+    fn test_memory_usage_tracks_loaded_content() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("small.txt"), "a".repeat(10_000)).unwrap();
+        std::fs::write(
+            dir.path().join("small.json"),
+            r#"{"id": "small", "name": "Small", "jurisdiction": "Chile", "article_files": ["small.txt"]}"#,
+        ).unwrap();
+
+        let small = ComplianceLibrariesManager::load_from_dir(dir.path()).unwrap()
+            .initialize_all_libraries().unwrap();
+        assert!(small.memory_usage_bytes >= 10_000);
+        assert!(small.memory_usage_bytes < 20_000);
+
+        std::fs::write(dir.path().join("large.txt"), "b".repeat(3 * 1024 * 1024)).unwrap();
+        std::fs::write(
+            dir.path().join("large.json"),
+            r#"{"id": "large", "name": "Large", "jurisdiction": "Peru", "article_files": ["large.txt"]}"#,
+        ).unwrap();
+
+        let both = ComplianceLibrariesManager::load_from_dir(dir.path()).unwrap()
+            .initialize_all_libraries().unwrap();
+        assert!(both.memory_usage_bytes >= small.memory_usage_bytes + 3 * 1024 * 1024);
+        assert_eq!(both.memory_usage_mb, 4);
+
+        let gdpr = GdprCompleteLibrary::new();
+        let article_text: usize = gdpr.regulation.chapters.values()
+            .flat_map(|chapter| chapter.articles.values())
+            .map(|article| article.full_text.len())
+            .sum();
+        assert!(gdpr.memory_footprint() > article_text);
+    }
+
     #[test]
     fn test_jurisdiction_filtering() {
         let manager = ComplianceLibrariesManager::new();
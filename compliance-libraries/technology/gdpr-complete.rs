@@ -634,6 +634,35 @@ impl GdprCompleteLibrary {
         guidance
     }
 
+    /// Bytes held by the loaded articles, recitals and guidance plus their indexes
+    pub fn memory_footprint(&self) -> usize {
+        let articles: usize = self
+            .regulation
+            .chapters
+            .values()
+            .map(|chapter| {
+                chapter.title.len()
+                    + chapter.scope.len()
+                    + chapter.articles.capacity() * (std::mem::size_of::<u32>() + std::mem::size_of::<Article>())
+                    + chapter.articles.values().map(|article| {
+                        article.title.len()
+                            + article.full_text.len()
+                            + article.paragraphs.iter().map(|p| p.text.len()).sum::<usize>()
+                            + article.obligations.iter().map(|o| o.description.len()).sum::<usize>()
+                            + article.rights.iter().map(|r| r.description.len()).sum::<usize>()
+                    }).sum::<usize>()
+            })
+            .sum();
+        let recitals: usize = self.regulation.recitals.values().map(|r| r.text.len() + r.purpose.len()).sum();
+        let definitions: usize = self.regulation.definitions.iter().map(|(term, definition)| term.len() + definition.len()).sum();
+        let decisions: usize = self.implementing_decisions.values().map(|d| d.title.len() + d.text.len()).sum();
+        let guidance: usize = self.guidance_documents.values().map(|g| g.title.len() + g.summary.len() + g.full_text.len()).sum();
+        let index = self.regulation.chapters.capacity() * (std::mem::size_of::<u32>() + std::mem::size_of::<Chapter>())
+            + self.regulation.recitals.capacity() * (std::mem::size_of::<u32>() + std::mem::size_of::<Recital>());
+
+        std::mem::size_of::<Self>() + articles + recitals + definitions + decisions + guidance + index
+    }
+
     /// Get specific article by number
     pub fn get_article(&self, article_number: u32) -> Option<&Article> {
         for chapter in self.regulation.chapters.values() {
@@ -7,6 +7,8 @@ use chrono::{DateTime, Utc};
 use async_trait::async_trait;
 use std::sync::Arc;
 use serde_json::Value;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 /// Native AION-CR ↔ ECTUS-R Integration Bridge
 /// Provides seamless bidirectional communication and data synchronization
@@ -16,6 +18,9 @@ pub struct EctusRAionBridge {
     // Core bridge infrastructure
     bridge_id: Uuid,
     integration_state: Arc<RwLock<IntegrationState>>,
+    shutdown: CancellationToken,
+    background_tasks: Arc<tokio::sync::Mutex<Vec<JoinHandle<()>>>>,
+    heartbeat_config: HeartbeatConfig,
 
     // Communication channels
     aion_to_ectus_tx: mpsc::UnboundedSender<AionMessage>,
@@ -42,6 +47,9 @@ pub struct IntegrationState {
     pub bridge_health: BridgeHealth,
     pub sync_state: SyncState,
     pub last_sync: DateTime<Utc>,
+    /// Error of the most recent sync, cleared by the next successful one
+    #[serde(default)]
+    pub last_sync_error: Option<String>,
     pub performance_metrics: PerformanceMetrics,
 }
 
@@ -54,8 +62,39 @@ pub enum SystemStatus {
     Emergency,
 }
 
+/// Heartbeat timing for the bridge
+#[derive(Debug, Clone)]
+pub struct HeartbeatConfig {
+    /// How often a heartbeat sync is attempted
+    pub interval: std::time::Duration,
+    /// Longest the bridge may go without a successful sync before it is degraded
+    pub max_sync_staleness: chrono::Duration,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval: std::time::Duration::from_secs(15),
+            max_sync_staleness: chrono::Duration::minutes(2),
+        }
+    }
+}
+
+impl HeartbeatConfig {
+    pub fn from_settings(settings: &crate::config::MonitoringSettings) -> Self {
+        Self {
+            interval: settings.heartbeat_interval.to_std().unwrap_or(std::time::Duration::from_secs(15)),
+            max_sync_staleness: settings.max_sync_staleness,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeHealth {
+    pub healthy: bool,
+    pub status: SystemStatus,
+    /// Seconds since the last successful sync
+    pub sync_staleness_secs: i64,
     pub connectivity_score: f64,
     pub latency_ms: f64,
     pub error_rate: f64,
@@ -299,6 +338,11 @@ pub trait EncryptionAlgorithm {
 impl EctusRAionBridge {
     /// Initialize the native integration bridge between AION-CR and ECTUS-R
     pub async fn new() -> AionResult<Self> {
+        Self::new_with_heartbeat(HeartbeatConfig::default()).await
+    }
+
+    /// Initialize the bridge with explicit heartbeat timing
+    pub async fn new_with_heartbeat(heartbeat_config: HeartbeatConfig) -> AionResult<Self> {
         let bridge_id = Uuid::new_v4();
 
         // Initialize communication channels
@@ -319,6 +363,9 @@ impl EctusRAionBridge {
             aion_cr_status: SystemStatus::Online,
             ectus_r_status: SystemStatus::Online,
             bridge_health: BridgeHealth {
+                healthy: true,
+                status: SystemStatus::Online,
+                sync_staleness_secs: 0,
                 connectivity_score: 1.0,
                 latency_ms: 0.0,
                 error_rate: 0.0,
@@ -333,6 +380,7 @@ impl EctusRAionBridge {
                 performance_data_synced: true,
             },
             last_sync: Utc::now(),
+            last_sync_error: None,
             performance_metrics: PerformanceMetrics {
                 messages_processed: 0,
                 data_volume_bytes: 0,
@@ -345,6 +393,9 @@ impl EctusRAionBridge {
         let bridge = Self {
             bridge_id,
             integration_state,
+            shutdown: CancellationToken::new(),
+            background_tasks: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            heartbeat_config,
             aion_to_ectus_tx,
             ectus_to_aion_tx,
             event_broadcaster,
@@ -365,6 +416,83 @@ impl EctusRAionBridge {
         Ok(bridge)
     }
 
+    /// Stop the bridge, marking both sides offline once its background tasks have exited
+    pub async fn stop(&self) -> AionResult<()> {
+        self.shutdown.cancel();
+
+        let tasks: Vec<JoinHandle<()>> = self.background_tasks.lock().await.drain(..).collect();
+        for task in tasks {
+            if let Err(e) = task.await {
+                return Err(AionError::InternalError {
+                    message: format!("bridge task did not exit cleanly: {}", e),
+                });
+            }
+        }
+
+        {
+            let mut state = self.integration_state.write().await;
+            state.aion_cr_status = SystemStatus::Offline;
+            state.ectus_r_status = SystemStatus::Offline;
+        }
+
+        self.emit_event(BridgeEvent::ConnectionLost).await?;
+        Ok(())
+    }
+
+    /// Whether the bridge is currently running
+    pub async fn is_running(&self) -> bool {
+        !self.shutdown.is_cancelled()
+    }
+
+    /// Subscribe to bridge events such as `SyncCompleted`
+    pub fn subscribe_events(&self) -> broadcast::Receiver<BridgeEvent> {
+        self.event_broadcaster.subscribe()
+    }
+
+    /// Time of the last successful sync
+    pub async fn get_last_sync_time(&self) -> AionResult<DateTime<Utc>> {
+        Ok(self.integration_state.read().await.last_sync)
+    }
+
+    /// Bridge health. A running bridge whose last sync failed, or that has
+    /// not completed a sync within the heartbeat's max staleness, reports `Degraded`.
+    pub async fn health_check(&self) -> AionResult<BridgeHealth> {
+        let running = self.is_running().await;
+        let mut state = self.integration_state.write().await;
+
+        let staleness = Utc::now() - state.last_sync;
+        let status = if !running {
+            SystemStatus::Offline
+        } else if state.last_sync_error.is_some() || staleness > self.heartbeat_config.max_sync_staleness {
+            SystemStatus::Degraded
+        } else {
+            SystemStatus::Online
+        };
+
+        state.bridge_health.healthy = matches!(status, SystemStatus::Online);
+        state.bridge_health.status = status;
+        state.bridge_health.sync_staleness_secs = staleness.num_seconds();
+        state.bridge_health.last_health_check = Utc::now();
+        Ok(state.bridge_health.clone())
+    }
+
+    /// Exchange a heartbeat with ECTUS-R. The heartbeat is a performance-data
+    /// sync, so a bridge that is up but not exchanging data goes stale.
+    pub async fn heartbeat(&self) -> AionResult<()> {
+        if let Err(e) = self.real_time_sync("performance_data").await {
+            self.record_sync_failure("performance_data", &e.to_string()).await?;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Mark `data_type` unsynced and keep the bridge degraded until a sync succeeds
+    pub(crate) async fn record_sync_failure(&self, data_type: &str, error: &str) -> AionResult<()> {
+        self.update_sync_state(data_type, false).await?;
+        self.integration_state.write().await.last_sync_error = Some(error.to_string());
+        self.emit_event(BridgeEvent::SyncFailed { error: error.to_string() }).await
+    }
+
     /// Send compliance alert from AION-CR to ECTUS-R
     pub async fn send_compliance_alert(&self, alert: ComplianceAlert) -> AionResult<()> {
         let message = AionMessage::ComplianceAlert {
@@ -424,15 +552,19 @@ impl EctusRAionBridge {
             },
             "resource_constraints" => {
                 self.sync_resource_constraints().await?;
+                self.update_sync_state("resource_constraints", true).await?;
             },
             "risk_assessments" => {
                 self.sync_risk_assessments().await?;
+                self.update_sync_state("risk_assessments", true).await?;
             },
             "audit_trails" => {
                 self.sync_audit_trails().await?;
+                self.update_sync_state("audit_trails", true).await?;
             },
             "performance_data" => {
                 self.sync_performance_data().await?;
+                self.update_sync_state("performance_data", true).await?;
             },
             _ => {
                 return Err(AionError::InvalidInput(format!("Unknown data type: {}", data_type)));
@@ -609,7 +741,13 @@ impl EctusRAionBridge {
             _ => {}
         }
 
-        state.last_sync = Utc::now();
+        // Only successful syncs count towards freshness
+        if synced {
+            state.last_sync = Utc::now();
+            state.last_sync_error = None;
+            drop(state);
+            self.emit_event(BridgeEvent::SyncCompleted).await?;
+        }
         Ok(())
     }
 
@@ -715,7 +853,22 @@ impl EctusRAionBridge {
     }
 
     async fn start_health_monitoring(&self) -> AionResult<()> {
-        // Implementation for health monitoring
+        let bridge = self.clone();
+        let heartbeat = tokio::spawn(async move {
+            // The first heartbeat is one interval out; `new` has just synced state
+            let period = bridge.heartbeat_config.interval;
+            let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                tokio::select! {
+                    _ = bridge.shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+                if let Err(e) = bridge.heartbeat().await {
+                    tracing::warn!("⚠️ Bridge heartbeat failed: {}", e);
+                }
+            }
+        });
+        self.background_tasks.lock().await.push(heartbeat);
         Ok(())
     }
 
@@ -94,6 +94,10 @@ pub struct MonitoringSettings {
     pub performance_monitoring: bool,
     pub health_monitoring: bool,
     pub alerting_enabled: bool,
+    /// How often the bridge sends a heartbeat sync
+    pub heartbeat_interval: Duration,
+    /// Longest the bridge may go without a successful sync before it reports degraded
+    pub max_sync_staleness: Duration,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -228,6 +232,8 @@ impl BridgeConfig {
                 performance_monitoring: true,
                 health_monitoring: true,
                 alerting_enabled: true,
+                heartbeat_interval: Duration::seconds(15),
+                max_sync_staleness: Duration::minutes(2),
             },
             autonomy_level: AutonomyLevel::Maximum,
         }
@@ -0,0 +1,159 @@
+//! Lag-aware subscriptions to the integration event bus
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast::{self, error::RecvError, error::TryRecvError};
+use tracing::{error, warn};
+
+use crate::{IntegrationEvent, IntegrationMonitor};
+
+/// Capacity of the integration event bus. Tokio rounds broadcast capacities up
+/// to a power of two, so this is stated as one.
+pub const EVENT_BUS_CAPACITY: usize = 16384;
+
+/// Events whose loss is logged as an error rather than a warning
+const CRITICAL_EVENT_KINDS: &[&str] = &["SecurityLevelElevated", "FailoverTriggered", "AutonomyEscalated"];
+
+/// Record of which kind of event was published at each position of the bus,
+/// so a lagging subscriber can tell what it missed
+#[derive(Debug)]
+pub struct EventLedger {
+    inner: Mutex<LedgerState>,
+}
+
+#[derive(Debug, Default)]
+struct LedgerState {
+    /// Number of events published so far
+    published: u64,
+    /// Kinds of the most recent events, oldest first
+    recent: VecDeque<&'static str>,
+}
+
+impl EventLedger {
+    pub fn new() -> Self {
+        Self { inner: Mutex::new(LedgerState::default()) }
+    }
+
+    /// Record and publish an event. Recording and sending happen under one lock
+    /// so ledger positions match the order receivers see.
+    pub fn publish(&self, sender: &broadcast::Sender<IntegrationEvent>, event: IntegrationEvent) {
+        let mut state = self.inner.lock().unwrap();
+        state.published += 1;
+        state.recent.push_back(event.kind());
+        // Keep twice the bus capacity: lagged positions are always older than what the bus still holds
+        while state.recent.len() > EVENT_BUS_CAPACITY * 2 {
+            state.recent.pop_front();
+        }
+        let _ = sender.send(event);
+    }
+
+    /// Subscribe and return the ledger position of the first event the receiver will see
+    pub fn subscribe(&self, sender: &broadcast::Sender<IntegrationEvent>) -> (broadcast::Receiver<IntegrationEvent>, u64) {
+        let state = self.inner.lock().unwrap();
+        (sender.subscribe(), state.published)
+    }
+
+    /// Kinds of the `count` events published from `position` on. Events too old to
+    /// still be recorded are reported as "Unknown".
+    pub fn kinds_between(&self, position: u64, count: u64) -> HashMap<&'static str, u64> {
+        let state = self.inner.lock().unwrap();
+        let oldest = state.published - state.recent.len() as u64;
+
+        let mut kinds = HashMap::new();
+        for index in position..position + count {
+            let kind = if index < oldest {
+                "Unknown"
+            } else {
+                state.recent.get((index - oldest) as usize).copied().unwrap_or("Unknown")
+            };
+            *kinds.entry(kind).or_insert(0) += 1;
+        }
+        kinds
+    }
+}
+
+impl Default for EventLedger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A subscription to the integration event bus that survives lag.
+///
+/// When the receiver falls behind and the bus overwrites events, the
+/// subscription counts what was dropped, reports it to the
+/// `IntegrationMonitor`, and carries on from the oldest event still available.
+pub struct EventSubscription {
+    receiver: broadcast::Receiver<IntegrationEvent>,
+    ledger: Arc<EventLedger>,
+    monitor: Arc<IntegrationMonitor>,
+    position: u64,
+    dropped: u64,
+}
+
+impl EventSubscription {
+    pub(crate) fn new(
+        sender: &broadcast::Sender<IntegrationEvent>,
+        ledger: Arc<EventLedger>,
+        monitor: Arc<IntegrationMonitor>,
+    ) -> Self {
+        let (receiver, position) = ledger.subscribe(sender);
+        Self { receiver, ledger, monitor, position, dropped: 0 }
+    }
+
+    /// Wait for the next event. Returns `None` once the bus is closed.
+    pub async fn recv(&mut self) -> Option<IntegrationEvent> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => {
+                    self.position += 1;
+                    return Some(event);
+                }
+                Err(RecvError::Lagged(missed)) => self.record_lag(missed).await,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Take the next event if one is ready
+    pub async fn try_recv(&mut self) -> Option<IntegrationEvent> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) => {
+                    self.position += 1;
+                    return Some(event);
+                }
+                Err(TryRecvError::Lagged(missed)) => self.record_lag(missed).await,
+                Err(TryRecvError::Empty) | Err(TryRecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Number of events this subscription has missed
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped
+    }
+
+    async fn record_lag(&mut self, missed: u64) {
+        let kinds = self.ledger.kinds_between(self.position, missed);
+        self.position += missed;
+        self.dropped += missed;
+
+        let critical: Vec<&str> = CRITICAL_EVENT_KINDS
+            .iter()
+            .copied()
+            .filter(|kind| kinds.contains_key(kind))
+            .collect();
+        if critical.is_empty() {
+            warn!("⚠️ Event subscriber lagged and missed {} integration events", missed);
+        } else {
+            error!(
+                "🚨 Event subscriber lagged and missed {} integration events, including {}",
+                missed,
+                critical.join(", ")
+            );
+        }
+
+        self.monitor.record_dropped_events(&kinds).await;
+    }
+}
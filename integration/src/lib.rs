@@ -3,7 +3,9 @@
 //! Provides seamless native integration between AION-CR (AI-powered Regulatory Compliance)
 //! and ECTUS-R (Resource Management System) with maximum autonomy and privilege escalation.
 
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::broadcast;
 use uuid::Uuid;
 use anyhow::Result;
@@ -15,6 +17,7 @@ pub mod protocol;
 pub mod security;
 pub mod monitoring;
 pub mod config;
+pub mod events;
 
 pub use ectus_r_bridge::*;
 pub use unified_orchestrator::*;
@@ -22,9 +25,13 @@ pub use protocol::*;
 pub use security::*;
 pub use monitoring::*;
 pub use config::*;
+pub use events::*;
+
+/// Maximum time each component gets to stop before it is reported as hung
+pub const COMPONENT_STOP_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Integration system state
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum IntegrationMode {
     /// Systems operate independently
     Independent,
@@ -36,15 +43,48 @@ pub enum IntegrationMode {
     MaximumAutonomy,
 }
 
+impl IntegrationMode {
+    /// Security privilege level granted in this mode
+    pub fn privilege_level(&self) -> u8 {
+        match self {
+            IntegrationMode::Independent => 32,
+            IntegrationMode::Coupled => 96,
+            IntegrationMode::Unified => 192,
+            IntegrationMode::MaximumAutonomy => u8::MAX,
+        }
+    }
+
+    /// The next mode up, or `self` if already at the top
+    pub fn escalated(&self) -> IntegrationMode {
+        match self {
+            IntegrationMode::Independent => IntegrationMode::Coupled,
+            IntegrationMode::Coupled => IntegrationMode::Unified,
+            IntegrationMode::Unified | IntegrationMode::MaximumAutonomy => IntegrationMode::MaximumAutonomy,
+        }
+    }
+
+    /// Escalation must move one step at a time; de-escalation may drop
+    /// straight to any lower mode
+    pub fn can_transition_to(&self, to: IntegrationMode) -> bool {
+        to <= *self || to == self.escalated()
+    }
+}
+
 /// Main integration controller
 pub struct AionEctusIntegration {
     pub integration_id: Uuid,
     pub mode: IntegrationMode,
     pub bridge: Arc<EctusRAionBridge>,
     pub orchestrator: Arc<UnifiedOrchestrator>,
-    pub event_bus: broadcast::Sender<IntegrationEvent>,
+    /// Published to through `emit_event` so every event reaches the ledger
+    event_bus: broadcast::Sender<IntegrationEvent>,
+    pub event_ledger: Arc<EventLedger>,
     pub security_manager: Arc<SecurityManager>,
     pub monitor: Arc<IntegrationMonitor>,
+    /// Contract event forwarders, stopped with unified operation
+    contract_watchers: std::sync::Mutex<Vec<tokio::task::AbortHandle>>,
+    /// Failover watchdog, stopped with unified operation
+    failover_watch: tokio::sync::Mutex<Option<FailoverWatch>>,
 }
 
 /// Integration events
@@ -52,28 +92,86 @@ pub struct AionEctusIntegration {
 pub enum IntegrationEvent {
     BridgeInitialized { bridge_id: Uuid },
     OrchestratorStarted { orchestrator_id: Uuid },
-    ModeChanged { from: IntegrationMode, to: IntegrationMode },
+    ModeChanged { event_id: Uuid, from: IntegrationMode, to: IntegrationMode },
     AutonomyEscalated { level: u8 },
     UnifiedOperationStarted,
-    SecurityLevelElevated { from: u8, to: u8 },
+    SecurityLevelElevated { event_id: Uuid, from: u8, to: u8 },
     CrossSystemSyncCompleted,
     FailoverTriggered { system: String, reason: String },
+    FailoverRecovered { system: String, downtime: Duration },
+    /// A subscribed contract emitted an event, or a reorg dropped one observed earlier
+    ContractEventObserved {
+        contract: String,
+        event: String,
+        block_number: u64,
+        transaction_hash: String,
+        params: serde_json::Value,
+        removed: bool,
+    },
+}
+
+/// Contract event to publish as `ContractEventObserved`
+#[derive(Debug, Clone)]
+pub struct ContractEvent {
+    pub contract: String,
+    pub event: String,
+    pub block_number: u64,
+    pub transaction_hash: String,
+    pub params: serde_json::Value,
+    /// Set when a reorg dropped an event delivered earlier
+    pub removed: bool,
+}
+
+#[cfg(feature = "contract-events")]
+impl From<aion_blockchain::DecodedEvent> for ContractEvent {
+    fn from(event: aion_blockchain::DecodedEvent) -> Self {
+        Self {
+            params: event.params_json(),
+            contract: event.address,
+            event: event.event,
+            block_number: event.block_number,
+            transaction_hash: event.transaction_hash,
+            removed: event.removed,
+        }
+    }
+}
+
+impl IntegrationEvent {
+    /// Variant name, used to report which events a lagging subscriber missed
+    pub fn kind(&self) -> &'static str {
+        match self {
+            IntegrationEvent::BridgeInitialized { .. } => "BridgeInitialized",
+            IntegrationEvent::OrchestratorStarted { .. } => "OrchestratorStarted",
+            IntegrationEvent::ModeChanged { .. } => "ModeChanged",
+            IntegrationEvent::AutonomyEscalated { .. } => "AutonomyEscalated",
+            IntegrationEvent::UnifiedOperationStarted => "UnifiedOperationStarted",
+            IntegrationEvent::SecurityLevelElevated { .. } => "SecurityLevelElevated",
+            IntegrationEvent::CrossSystemSyncCompleted => "CrossSystemSyncCompleted",
+            IntegrationEvent::FailoverTriggered { .. } => "FailoverTriggered",
+            IntegrationEvent::FailoverRecovered { .. } => "FailoverRecovered",
+            IntegrationEvent::ContractEventObserved { .. } => "ContractEventObserved",
+        }
+    }
 }
 
 impl AionEctusIntegration {
-    /// Initialize native integration with maximum autonomy
-    pub async fn new_with_maximum_autonomy() -> Result<Self> {
-        info!("🚀 Initializing AION-CR ↔ ECTUS-R native integration with maximum autonomy");
+    /// Initialize native integration in independent mode at least privilege
+    ///
+    /// Higher modes are reached with `escalate_to_maximum_autonomy` or
+    /// `preview_mode` and `confirm_mode`.
+    pub async fn new() -> Result<Self> {
+        info!("🚀 Initializing AION-CR ↔ ECTUS-R native integration");
 
         let integration_id = Uuid::new_v4();
-        let (event_tx, _) = broadcast::channel(10000);
+        let (event_tx, _) = broadcast::channel(EVENT_BUS_CAPACITY);
 
-        // Initialize security manager with maximum privileges
-        let security_manager = Arc::new(SecurityManager::new_with_maximum_privileges().await?);
+        // Initialize security manager at least privilege
+        let security_manager = Arc::new(SecurityManager::new().await?);
 
         // Initialize bridge with native communication
         let bridge_config = BridgeConfig::new_maximum_autonomy();
-        let bridge = Arc::new(EctusRAionBridge::new(bridge_config, security_manager.clone()).await?);
+        let heartbeat_config = HeartbeatConfig::from_settings(&bridge_config.monitoring_settings);
+        let bridge = Arc::new(EctusRAionBridge::new_with_heartbeat(heartbeat_config).await?);
 
         // Initialize unified orchestrator
         let orchestrator_config = OrchestratorConfig::new_maximum_autonomy();
@@ -88,19 +186,24 @@ impl AionEctusIntegration {
 
         let integration = Self {
             integration_id,
-            mode: IntegrationMode::MaximumAutonomy,
+            mode: IntegrationMode::Independent,
             bridge,
             orchestrator,
             event_bus: event_tx,
+            event_ledger: Arc::new(EventLedger::new()),
             security_manager,
             monitor,
+            contract_watchers: std::sync::Mutex::new(Vec::new()),
+            failover_watch: tokio::sync::Mutex::new(None),
         };
 
         // Trigger bridge initialization event
-        let _ = integration.event_bus.send(IntegrationEvent::BridgeInitialized {
+        integration.emit_event(IntegrationEvent::BridgeInitialized {
             bridge_id: integration.bridge.bridge_id(),
         });
 
+        integration.forward_bridge_syncs();
+
         info!("✅ Native integration initialized with ID: {}", integration_id);
         Ok(integration)
     }
@@ -119,52 +222,355 @@ impl AionEctusIntegration {
         self.monitor.start_monitoring().await?;
 
         // Notify unified operation started
-        let _ = self.event_bus.send(IntegrationEvent::UnifiedOperationStarted);
+        self.emit_event(IntegrationEvent::UnifiedOperationStarted);
 
         info!("🚀 Unified operation mode active - AION-CR ↔ ECTUS-R operating as one");
         Ok(())
     }
 
-    /// Escalate autonomy to maximum level
-    pub async fn escalate_to_maximum_autonomy(&mut self) -> Result<()> {
-        info!("⚡ Escalating to maximum autonomy with full privilege elevation");
+    /// Publish an event on the integration bus
+    pub fn emit_event(&self, event: IntegrationEvent) {
+        self.event_ledger.publish(&self.event_bus, event);
+    }
 
-        // Escalate security privileges
-        self.security_manager.escalate_to_maximum().await?;
+    /// Subscribe to integration events. The subscription keeps going after it
+    /// lags, reporting dropped events to the integration monitor.
+    pub fn subscribe_events(&self) -> EventSubscription {
+        EventSubscription::new(&self.event_bus, self.event_ledger.clone(), self.monitor.clone())
+    }
 
-        // Enable maximum autonomy mode
-        self.orchestrator.enable_maximum_autonomy().await?;
+    /// Publish `CrossSystemSyncCompleted` for every successful bridge sync
+    fn forward_bridge_syncs(&self) {
+        let mut bridge_events = self.bridge.subscribe_events();
+        let event_bus = self.event_bus.clone();
+        let event_ledger = self.event_ledger.clone();
 
-        // Update mode
-        let old_mode = self.mode.clone();
-        self.mode = IntegrationMode::MaximumAutonomy;
+        tokio::spawn(async move {
+            loop {
+                match bridge_events.recv().await {
+                    Ok(BridgeEvent::SyncCompleted) => {
+                        event_ledger.publish(&event_bus, IntegrationEvent::CrossSystemSyncCompleted);
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!("⚠️ Missed {} bridge events while forwarding syncs", missed);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
 
-        // Notify autonomy escalation
-        let _ = self.event_bus.send(IntegrationEvent::AutonomyEscalated { level: 255 });
-        let _ = self.event_bus.send(IntegrationEvent::ModeChanged {
-            from: old_mode,
-            to: self.mode.clone(),
+    /// Publish `ContractEventObserved` for every event from a contract subscription
+    ///
+    /// The forwarder runs until the stream ends or unified operation stops.
+    pub fn forward_contract_events<S>(&self, events: S) -> tokio::task::JoinHandle<()>
+    where
+        S: futures::Stream<Item = ContractEvent> + Send + 'static,
+    {
+        let event_bus = self.event_bus.clone();
+        let event_ledger = self.event_ledger.clone();
+
+        let forwarder = tokio::spawn(async move {
+            futures::pin_mut!(events);
+            while let Some(event) = futures::StreamExt::next(&mut events).await {
+                if event.removed {
+                    warn!("📜 Contract event {} from {} in block {} removed by a reorg", event.event, event.contract, event.block_number);
+                } else {
+                    info!("📜 Contract event {} from {} in block {}", event.event, event.contract, event.block_number);
+                }
+                event_ledger.publish(&event_bus, IntegrationEvent::ContractEventObserved {
+                    contract: event.contract,
+                    event: event.event,
+                    block_number: event.block_number,
+                    transaction_hash: event.transaction_hash,
+                    params: event.params,
+                    removed: event.removed,
+                });
+            }
+        });
+        self.contract_watchers.lock().unwrap().push(forwarder.abort_handle());
+        forwarder
+    }
+
+    /// Subscribe to `event` logs of the contract at `address` and publish them on the bus
+    #[cfg(feature = "contract-events")]
+    pub async fn watch_contract_events(
+        &self,
+        listener: &aion_blockchain::EventListener,
+        address: &str,
+        abi: &serde_json::Value,
+        event: &str,
+    ) -> Result<tokio::task::JoinHandle<()>> {
+        let events = listener.subscribe(address, abi, event).await?;
+        info!("📜 Watching {} events of {}", event, address);
+        Ok(self.forward_contract_events(futures::StreamExt::map(events, ContractEvent::from)))
+    }
+
+    /// Start the monitor's failover watchdog for this integration, replacing a running one
+    ///
+    /// The watchdog stops with unified operation, or earlier when the
+    /// returned token is cancelled.
+    pub async fn start_failover_watch(self: &Arc<Self>, config: FailoverConfig) -> Result<tokio_util::sync::CancellationToken> {
+        let watch = self.monitor.watch_failover(Arc::downgrade(self), config);
+        let token = watch.cancellation_token();
+        if let Some(previous) = self.failover_watch.lock().await.replace(watch) {
+            previous.stop().await?;
+        }
+        Ok(token)
+    }
+
+    /// Run one health check and fail over or recover systems as the detector decides
+    pub async fn evaluate_failover(&self, detector: &mut FailoverDetector) -> Result<Vec<FailoverAction>> {
+        let health = self.health_check().await?;
+        let now = std::time::Instant::now();
+
+        let problem = |running: bool, healthy: bool| match (running, healthy) {
+            (false, _) => Some("offline"),
+            (true, false) => Some("degraded"),
+            (true, true) => None,
+        };
+        let observations = [
+            ("bridge", problem(health.bridge_running, health.bridge_health.healthy)),
+            ("orchestrator", problem(health.orchestrator_running, health.orchestrator_health.healthy)),
+            ("security", problem(true, health.security_health.healthy)),
+        ];
+
+        let mut actions = Vec::new();
+        for (system, problem) in observations {
+            let Some(action) = detector.observe(system, problem, now) else {
+                continue;
+            };
+            match &action {
+                FailoverAction::Trigger { system, reason } => {
+                    warn!("🔀 Failing over {}: {}", system, reason);
+                    self.orchestrator.trigger_failover(system, reason).await?;
+                    self.emit_event(IntegrationEvent::FailoverTriggered {
+                        system: system.clone(),
+                        reason: reason.clone(),
+                    });
+                }
+                FailoverAction::Recover { system, downtime } => {
+                    info!("✅ {} recovered after {:?}", system, downtime);
+                    self.orchestrator.recover_from_failover(system).await?;
+                    self.emit_event(IntegrationEvent::FailoverRecovered {
+                        system: system.clone(),
+                        downtime: *downtime,
+                    });
+                }
+            }
+            actions.push(action);
+        }
+        Ok(actions)
+    }
+
+    /// Stop unified operation: monitor, then orchestrator, then bridge.
+    ///
+    /// Each component waits for its background tasks to exit, for at most
+    /// `COMPONENT_STOP_TIMEOUT`. One that errors or hangs raises `FailoverTriggered`
+    /// and the remaining components are still stopped.
+    pub async fn stop_unified_operation(&self) -> Result<()> {
+        info!("🛑 Stopping unified operation");
+
+        for watcher in self.contract_watchers.lock().unwrap().drain(..) {
+            watcher.abort();
+        }
+        if let Some(watch) = self.failover_watch.lock().await.take() {
+            if let Err(e) = watch.stop().await {
+                warn!("⚠️ Failover watchdog did not exit cleanly: {}", e);
+            }
+        }
+
+        let mut failed = Vec::new();
+
+        if !self.stop_component("monitor", self.monitor.stop_monitoring()).await {
+            failed.push("monitor");
+        }
+        if !self.stop_component("orchestrator", self.orchestrator.stop_autonomous_mode()).await {
+            failed.push("orchestrator");
+        }
+        if !self.stop_component("bridge", self.bridge.stop()).await {
+            failed.push("bridge");
+        }
+
+        if !failed.is_empty() {
+            anyhow::bail!("Components failed to stop cleanly: {}", failed.join(", "));
+        }
+
+        info!("✅ Unified operation stopped");
+        Ok(())
+    }
+
+    async fn stop_component<E: std::fmt::Display>(
+        &self,
+        name: &str,
+        stop: impl Future<Output = std::result::Result<(), E>>,
+    ) -> bool {
+        let reason = match tokio::time::timeout(COMPONENT_STOP_TIMEOUT, stop).await {
+            Ok(Ok(())) => return true,
+            Ok(Err(e)) => format!("stop failed: {}", e),
+            Err(_) => format!("did not stop within {:?}", COMPONENT_STOP_TIMEOUT),
+        };
+
+        warn!("⚠️ {} {}", name, reason);
+        self.emit_event(IntegrationEvent::FailoverTriggered {
+            system: name.to_string(),
+            reason,
         });
+        false
+    }
+
+    /// Escalate autonomy to maximum level, stepping through each mode.
+    ///
+    /// Every step's escalation plan goes to `approver`, and the step is only
+    /// applied with the confirmation token the operator returns. A declined
+    /// step stops the escalation at the mode reached so far.
+    pub async fn escalate_to_maximum_autonomy(&mut self, approver: &dyn EscalationApprover) -> Result<()> {
+        info!("⚡ Requesting escalation to maximum autonomy");
+
+        loop {
+            let next = self.mode.escalated();
+            let plan = self.preview_mode(next).await?;
+            let token = approver.approve(&plan).await?;
+            self.confirm_mode(next, &token).await?;
+            if next == IntegrationMode::MaximumAutonomy {
+                break;
+            }
+        }
+
+        // Notify autonomy escalation
+        self.emit_event(IntegrationEvent::AutonomyEscalated { level: 255 });
 
         info!("🏆 Maximum autonomy achieved - unrestricted AI operations enabled");
         Ok(())
     }
 
+    /// Move to another integration mode without raising privileges.
+    ///
+    /// De-escalation may skip levels and lowers the security manager's
+    /// privileges to match. Leaving `MaximumAutonomy` turns the orchestrator's
+    /// maximum autonomy off before privileges are lowered. Escalation must pass through every intermediate
+    /// mode and needs a reviewed plan: see `preview_mode` and `confirm_mode`.
+    pub async fn set_mode(&mut self, mode: IntegrationMode) -> Result<()> {
+        self.change_mode(mode, None).await
+    }
+
+    /// Preview the privilege changes moving to `mode` would make
+    pub async fn preview_mode(&self, mode: IntegrationMode) -> Result<EscalationPlan> {
+        self.check_transition(mode)?;
+        Ok(self.security_manager.simulate_escalation(mode.privilege_level()).await)
+    }
+
+    /// Move to `mode`, applying the escalation plan `preview_mode` returned
+    pub async fn confirm_mode(&mut self, mode: IntegrationMode, confirmation_token: &str) -> Result<()> {
+        self.change_mode(mode, Some(confirmation_token)).await
+    }
+
+    fn check_transition(&self, mode: IntegrationMode) -> Result<()> {
+        if !self.mode.can_transition_to(mode) {
+            anyhow::bail!(
+                "Illegal integration mode transition {:?} -> {:?}: escalate through {:?} first",
+                self.mode,
+                mode,
+                self.mode.escalated()
+            );
+        }
+        Ok(())
+    }
+
+    async fn change_mode(&mut self, mode: IntegrationMode, confirmation_token: Option<&str>) -> Result<()> {
+        self.check_transition(mode)?;
+
+        let current_level = self.security_manager.privilege_level().await;
+        let target_level = mode.privilege_level();
+        // Shared by the audit entry and the events announcing this change
+        let event_id = Uuid::new_v4();
+        let context = PrivilegeChangeContext::new(
+            "AionEctusIntegration",
+            format!("integration mode change {:?} -> {:?}", self.mode, mode),
+        )
+        .with_event_id(event_id);
+        if target_level > current_level {
+            let Some(token) = confirmation_token else {
+                let refusal = anyhow::anyhow!(
+                    "Moving to {:?} raises privileges from {} to {}: review preview_mode and call confirm_mode",
+                    mode,
+                    current_level,
+                    target_level
+                );
+                self.security_manager.record_refused_change(target_level, &context, &refusal).await;
+                return Err(refusal);
+            };
+            self.security_manager.apply_escalation(token, target_level, &context).await?;
+        } else {
+            // Autonomy never outlives the privileges it runs with
+            if self.mode == IntegrationMode::MaximumAutonomy && mode != IntegrationMode::MaximumAutonomy {
+                self.orchestrator.disable_maximum_autonomy().await?;
+            }
+            if target_level < current_level {
+                self.security_manager.set_privilege_level(target_level, &context).await?;
+            }
+        }
+
+        if mode == IntegrationMode::MaximumAutonomy {
+            if let Err(e) = self.orchestrator.enable_maximum_autonomy().await {
+                // Undo the escalation so privileges do not stay above the unchanged mode
+                if target_level > current_level {
+                    let rollback = PrivilegeChangeContext::new(
+                        "AionEctusIntegration",
+                        format!("rollback of failed integration mode change {:?} -> {:?}", self.mode, mode),
+                    )
+                    .with_event_id(event_id);
+                    if let Err(rollback_error) = self.security_manager.set_privilege_level(current_level, &rollback).await {
+                        error!("❌ Privileges left at {} after failed mode change: {}", target_level, rollback_error);
+                    }
+                }
+                return Err(e);
+            }
+        }
+
+        let old_mode = self.mode;
+        self.mode = mode;
+
+        // Only a privilege change that was actually applied counts as elevation
+        if target_level > current_level {
+            self.emit_event(IntegrationEvent::SecurityLevelElevated {
+                event_id,
+                from: current_level,
+                to: target_level,
+            });
+        }
+        if old_mode != mode {
+            info!("🔀 Integration mode changed: {:?} -> {:?}", old_mode, mode);
+            self.emit_event(IntegrationEvent::ModeChanged { event_id, from: old_mode, to: mode });
+        }
+        Ok(())
+    }
+
     /// Check integration health and status
     pub async fn health_check(&self) -> Result<IntegrationHealth> {
         let bridge_health = self.bridge.health_check().await?;
         let orchestrator_health = self.orchestrator.health_check().await?;
         let security_health = self.security_manager.health_check().await?;
+        let bridge_running = self.bridge.is_running().await;
+        let orchestrator_running = self.orchestrator.is_running().await;
+        let monitor_running = self.monitor.is_running().await;
 
         Ok(IntegrationHealth {
-            overall_status: if bridge_health.healthy && orchestrator_health.healthy && security_health.healthy {
+            overall_status: if !bridge_running && !orchestrator_running && !monitor_running {
+                HealthStatus::Offline
+            } else if bridge_health.healthy && orchestrator_health.healthy && security_health.healthy {
                 HealthStatus::Healthy
             } else {
                 HealthStatus::Degraded
             },
+            bridge_running,
+            orchestrator_running,
+            monitor_running,
             bridge_health,
             orchestrator_health,
             security_health,
+            dropped_events: self.monitor.dropped_events().await.total,
             uptime: self.monitor.get_uptime().await?,
             last_sync: self.bridge.get_last_sync_time().await?,
         })
@@ -175,9 +581,14 @@ impl AionEctusIntegration {
 #[derive(Debug, Clone)]
 pub struct IntegrationHealth {
     pub overall_status: HealthStatus,
+    pub bridge_running: bool,
+    pub orchestrator_running: bool,
+    pub monitor_running: bool,
     pub bridge_health: BridgeHealth,
     pub orchestrator_health: OrchestratorHealth,
     pub security_health: SecurityHealth,
+    /// Events lost by lagging event-bus subscribers
+    pub dropped_events: u64,
     pub uptime: std::time::Duration,
     pub last_sync: chrono::DateTime<chrono::Utc>,
 }
@@ -191,21 +602,21 @@ pub enum HealthStatus {
 }
 
 /// Initialize and start the complete AION-CR ↔ ECTUS-R integration
+///
+/// The integration runs at least privilege; escalation needs an operator
+/// and is never performed here.
 pub async fn initialize_native_integration() -> Result<Arc<AionEctusIntegration>> {
     info!("🌟 Initializing complete AION-CR ↔ ECTUS-R native integration");
 
-    // Create integration with maximum autonomy
-    let mut integration = AionEctusIntegration::new_with_maximum_autonomy().await?;
-
-    // Escalate to maximum autonomy
-    integration.escalate_to_maximum_autonomy().await?;
+    let integration = AionEctusIntegration::new().await?;
 
     // Start unified operation
     integration.start_unified_operation().await?;
 
     let integration = Arc::new(integration);
+    integration.start_failover_watch(FailoverConfig::default()).await?;
 
-    info!("🎉 Native integration fully operational - systems unified with maximum autonomy");
+    info!("🎉 Native integration fully operational");
     Ok(integration)
 }
 
@@ -214,23 +625,472 @@ mod tests {
     use super::*;
     use tokio_test;
 
+    /// Integration whose audit trail is signed with the development key
+    async fn new_integration() -> AionEctusIntegration {
+        std::env::set_var(aion_audit::ALLOW_DEVELOPMENT_KEY_ENV, "1");
+        AionEctusIntegration::new().await.unwrap()
+    }
+
+    /// Stands in for an operator who approves every plan shown to them
+    struct ApprovingOperator;
+
+    #[async_trait::async_trait]
+    impl EscalationApprover for ApprovingOperator {
+        async fn approve(&self, plan: &EscalationPlan) -> Result<String> {
+            Ok(plan.confirmation_token.clone())
+        }
+    }
+
+    struct DecliningOperator;
+
+    #[async_trait::async_trait]
+    impl EscalationApprover for DecliningOperator {
+        async fn approve(&self, plan: &EscalationPlan) -> Result<String> {
+            anyhow::bail!("operator declined plan {}", plan.plan_id)
+        }
+    }
+
+    /// Confirm each step up to `mode` the way an operator would
+    async fn escalate_to(integration: &mut AionEctusIntegration, mode: IntegrationMode) {
+        while integration.mode < mode {
+            let next = integration.mode.escalated();
+            let plan = integration.preview_mode(next).await.unwrap();
+            integration.confirm_mode(next, &plan.confirmation_token).await.unwrap();
+        }
+    }
+
     #[tokio::test]
     async fn test_integration_initialization() {
-        let integration = AionEctusIntegration::new_with_maximum_autonomy().await.unwrap();
-        assert!(matches!(integration.mode, IntegrationMode::MaximumAutonomy));
+        let integration = new_integration().await;
+        assert_eq!(integration.mode, IntegrationMode::Independent);
+        assert_eq!(integration.security_manager.privilege_level().await, LEAST_PRIVILEGE_LEVEL);
+        assert!(!integration.security_manager.security_policies.read().await.unrestricted_mode);
+    }
+
+    #[tokio::test]
+    async fn test_declined_escalation_changes_nothing() {
+        let mut integration = new_integration().await;
+        assert!(integration.escalate_to_maximum_autonomy(&DecliningOperator).await.is_err());
+        assert_eq!(integration.mode, IntegrationMode::Independent);
+        assert_eq!(integration.security_manager.privilege_level().await, LEAST_PRIVILEGE_LEVEL);
     }
 
     #[tokio::test]
     async fn test_unified_operation() {
-        let integration = AionEctusIntegration::new_with_maximum_autonomy().await.unwrap();
+        let integration = new_integration().await;
         let result = integration.start_unified_operation().await;
         assert!(result.is_ok());
     }
 
     #[tokio::test]
     async fn test_health_check() {
-        let integration = AionEctusIntegration::new_with_maximum_autonomy().await.unwrap();
+        let integration = new_integration().await;
         let health = integration.health_check().await.unwrap();
         assert!(matches!(health.overall_status, HealthStatus::Healthy));
     }
+
+    #[tokio::test]
+    async fn test_mode_transitions() {
+        let mut integration = new_integration().await;
+        integration.escalate_to_maximum_autonomy(&ApprovingOperator).await.unwrap();
+
+        // De-escalation lowers privileges, not just the mode
+        integration.set_mode(IntegrationMode::Independent).await.unwrap();
+        assert_eq!(integration.mode, IntegrationMode::Independent);
+        let security = integration.security_manager.health_check().await.unwrap();
+        assert_eq!(security.security_level, IntegrationMode::Independent.privilege_level());
+        assert_eq!(security.privilege_escalations, 0);
+        assert!(!integration.security_manager.security_policies.read().await.unrestricted_mode);
+
+        // Direct Independent -> MaximumAutonomy jump is rejected and changes nothing
+        assert!(integration.set_mode(IntegrationMode::MaximumAutonomy).await.is_err());
+        assert!(integration.preview_mode(IntegrationMode::MaximumAutonomy).await.is_err());
+        assert_eq!(integration.mode, IntegrationMode::Independent);
+
+        let mut events = integration.subscribe_events();
+        integration.escalate_to_maximum_autonomy(&ApprovingOperator).await.unwrap();
+        assert_eq!(integration.mode, IntegrationMode::MaximumAutonomy);
+
+        let mut path = Vec::new();
+        while let Some(event) = events.try_recv().await {
+            if let IntegrationEvent::ModeChanged { to, .. } = event {
+                path.push(to);
+            }
+        }
+        assert_eq!(path, vec![IntegrationMode::Coupled, IntegrationMode::Unified, IntegrationMode::MaximumAutonomy]);
+    }
+
+    async fn elevations(events: &mut EventSubscription) -> Vec<u8> {
+        let mut levels = Vec::new();
+        while let Some(event) = events.try_recv().await {
+            if let IntegrationEvent::SecurityLevelElevated { to, .. } = event {
+                levels.push(to);
+            }
+        }
+        levels
+    }
+
+    #[tokio::test]
+    async fn test_escalation_requires_confirmed_plan() {
+        let mut integration = new_integration().await;
+        escalate_to(&mut integration, IntegrationMode::Unified).await;
+        let mut events = integration.subscribe_events();
+
+        // Previewing changes nothing and lists what maximum autonomy grants
+        let plan = integration.preview_mode(IntegrationMode::MaximumAutonomy).await.unwrap();
+        assert_eq!(plan.target_level, 255);
+        assert!(plan.changes.iter().any(|c| c.setting == "unrestricted_mode" && c.to == "true"));
+        assert_eq!(integration.security_manager.privilege_level().await, IntegrationMode::Unified.privilege_level());
+
+        // Unconfirmed escalation is refused without emitting an elevation
+        assert!(integration.set_mode(IntegrationMode::MaximumAutonomy).await.is_err());
+        assert!(integration.confirm_mode(IntegrationMode::MaximumAutonomy, "not-a-token").await.is_err());
+        assert_eq!(elevations(&mut events).await, Vec::<u8>::new());
+
+        integration.confirm_mode(IntegrationMode::MaximumAutonomy, &plan.confirmation_token).await.unwrap();
+        assert_eq!(integration.security_manager.privilege_level().await, 255);
+        assert_eq!(elevations(&mut events).await, vec![255]);
+
+        // Tokens are single-use
+        assert!(integration
+            .security_manager
+            .apply_escalation(&plan.confirmation_token, 255, &PrivilegeChangeContext::new("test", "replay"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_privilege_changes_are_audited() {
+        use aion_core::AuditSystem;
+
+        let mut integration = new_integration().await;
+        integration.escalate_to_maximum_autonomy(&ApprovingOperator).await.unwrap();
+        let mut events = integration.subscribe_events();
+
+        integration.set_mode(IntegrationMode::Coupled).await.unwrap();
+        assert!(integration.set_mode(IntegrationMode::Unified).await.is_err());
+        assert!(integration.confirm_mode(IntegrationMode::Unified, "not-a-token").await.is_err());
+
+        let mut lowered_event = None;
+        while let Some(event) = events.try_recv().await {
+            if let IntegrationEvent::ModeChanged { event_id, .. } = event {
+                lowered_event = Some(event_id.to_string());
+            }
+        }
+
+        let manager_id = integration.security_manager.manager_id.to_string();
+        let trail = integration.security_manager.audit_trail().lock().await.get_audit_trail(&manager_id).unwrap();
+        // Three approved escalation steps, then the changes made here
+        assert_eq!(trail.len(), 6);
+        assert!(trail[..3].iter().all(|entry| entry.action == "privilege_escalated"));
+
+        let lowered = &trail[3];
+        assert_eq!(lowered.action, "privilege_lowered");
+        assert_eq!(lowered.actor, "AionEctusIntegration");
+        assert_eq!(lowered.previous_state.as_deref(), Some("255"));
+        assert_eq!(lowered.new_state.as_deref(), Some("96"));
+        assert!(lowered.details["reason"].contains("MaximumAutonomy -> Coupled"));
+        assert_eq!(lowered.details.get("integration_event_id"), lowered_event.as_ref());
+
+        // Refused escalations are recorded without changing the level
+        for (denied, error) in trail[4..].iter().zip(["call confirm_mode", "Unknown or already used"]) {
+            assert_eq!(denied.action, "privilege_change_denied");
+            assert_eq!(denied.previous_state.as_deref(), Some("96"));
+            assert_eq!(denied.new_state.as_deref(), Some("96"));
+            assert_eq!(denied.details["to_level"], "192");
+            assert!(denied.details["error"].contains(error));
+        }
+    }
+
+    /// Audit trail whose storage is unavailable
+    struct UnwritableTrail;
+
+    impl aion_core::AuditSystem for UnwritableTrail {
+        fn record_action(&mut self, _: &str, _: &str, _: &str, _: &str, _: std::collections::HashMap<String, String>) -> aion_core::AionResult<()> {
+            Err(aion_core::AionError::DatabaseError { operation: "audit_append".to_string(), reason: "disk full".to_string() })
+        }
+
+        fn get_audit_trail(&self, _: &str) -> aion_core::AionResult<Vec<aion_core::AuditTrail>> {
+            Ok(Vec::new())
+        }
+
+        fn verify_integrity(&self) -> aion_core::AionResult<bool> {
+            Ok(true)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl aion_audit::AuditTrailSink for UnwritableTrail {
+        async fn append(&mut self, _: aion_core::AuditTrail) -> aion_core::AionResult<()> {
+            Err(aion_core::AionError::DatabaseError { operation: "audit_append".to_string(), reason: "disk full".to_string() })
+        }
+
+        fn entries(&self) -> &[aion_core::AuditTrail] {
+            &[]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unaudited_privilege_change_is_not_applied() {
+        std::env::set_var(aion_audit::ALLOW_DEVELOPMENT_KEY_ENV, "1");
+        let security_manager = SecurityManager::new()
+            .await
+            .unwrap()
+            .with_audit_trail(Arc::new(tokio::sync::Mutex::new(UnwritableTrail)));
+        let context = PrivilegeChangeContext::new("test", "lower while audit storage is down");
+
+        assert!(security_manager.set_privilege_level(0, &context).await.is_err());
+        assert_eq!(security_manager.privilege_level().await, LEAST_PRIVILEGE_LEVEL);
+        assert_eq!(security_manager.security_policies.read().await.maximum_security_level, LEAST_PRIVILEGE_LEVEL);
+    }
+
+    #[tokio::test]
+    async fn test_plan_made_before_a_level_change_is_refused() {
+        std::env::set_var(aion_audit::ALLOW_DEVELOPMENT_KEY_ENV, "1");
+        let security_manager = SecurityManager::new().await.unwrap();
+        let context = PrivilegeChangeContext::new("test", "stale plan");
+
+        let plan = security_manager.simulate_escalation(IntegrationMode::Coupled.privilege_level()).await;
+        security_manager.set_privilege_level(16, &context).await.unwrap();
+
+        let error = security_manager
+            .apply_escalation(&plan.confirmation_token, plan.target_level, &context)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("simulate again"));
+        assert_eq!(security_manager.privilege_level().await, 16);
+    }
+
+    #[tokio::test]
+    async fn test_privilege_changes_survive_in_the_file_trail() {
+        use aion_core::AuditSystem;
+
+        std::env::set_var(aion_audit::ALLOW_DEVELOPMENT_KEY_ENV, "1");
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("privileges.jsonl");
+        let file_trail = aion_audit::FileAuditSystem::open(&path).await.unwrap();
+        let security_manager = SecurityManager::new()
+            .await
+            .unwrap()
+            .with_audit_trail(Arc::new(tokio::sync::Mutex::new(file_trail)));
+
+        security_manager
+            .set_privilege_level(16, &PrivilegeChangeContext::new("test", "quiet period"))
+            .await
+            .unwrap();
+
+        let reopened = aion_audit::FileAuditSystem::open(&path).await.unwrap();
+        let trail = reopened.get_audit_trail(&security_manager.manager_id.to_string()).unwrap();
+        assert_eq!(trail.len(), 1);
+        assert_eq!(trail[0].action, "privilege_lowered");
+        assert!(reopened.verify_integrity().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_stop_unified_operation() {
+        let integration = new_integration().await;
+        integration.start_unified_operation().await.unwrap();
+
+        let mut events = integration.subscribe_events();
+        integration.stop_unified_operation().await.unwrap();
+
+        let health = integration.health_check().await.unwrap();
+        assert!(!health.bridge_running);
+        assert!(!health.orchestrator_running);
+        assert!(!health.monitor_running);
+        assert!(matches!(health.overall_status, HealthStatus::Offline));
+        while let Some(event) = events.try_recv().await {
+            assert!(!matches!(event, IntegrationEvent::FailoverTriggered { .. }));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_hung_component_times_out_and_triggers_failover() {
+        let integration = new_integration().await;
+        let mut events = integration.subscribe_events();
+
+        let hung = std::future::pending::<std::result::Result<(), String>>();
+        let started = tokio::time::Instant::now();
+        assert!(!integration.stop_component("orchestrator", hung).await);
+        assert!(started.elapsed() >= COMPONENT_STOP_TIMEOUT);
+
+        let mut failovers = Vec::new();
+        while let Some(event) = events.try_recv().await {
+            if let IntegrationEvent::FailoverTriggered { system, reason } = event {
+                failovers.push((system, reason));
+            }
+        }
+        assert_eq!(failovers.len(), 1);
+        assert_eq!(failovers[0].0, "orchestrator");
+        assert!(failovers[0].1.contains("did not stop within"));
+    }
+
+    #[test]
+    fn test_failover_detector_window_cooldown_and_recovery() {
+        let mut detector = FailoverDetector::new(FailoverConfig {
+            check_interval: Duration::from_secs(1),
+            unhealthy_window: Duration::from_secs(30),
+            cooldown: Duration::from_secs(300),
+        });
+        let start = std::time::Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        // Degraded but still inside the window
+        assert_eq!(detector.observe("bridge", Some("degraded"), at(0)), None);
+        assert_eq!(detector.observe("bridge", Some("degraded"), at(20)), None);
+
+        // Past the window: fail over exactly once
+        assert!(matches!(
+            detector.observe("bridge", Some("degraded"), at(31)),
+            Some(FailoverAction::Trigger { ref system, .. }) if system == "bridge"
+        ));
+        assert_eq!(detector.observe("bridge", Some("degraded"), at(40)), None);
+
+        // Recovery is its own transition
+        assert_eq!(
+            detector.observe("bridge", None, at(60)),
+            Some(FailoverAction::Recover { system: "bridge".to_string(), downtime: Duration::from_secs(29) })
+        );
+        assert_eq!(detector.observe("bridge", None, at(61)), None);
+
+        // Flapping within the cooldown does not fail over again
+        assert_eq!(detector.observe("bridge", Some("offline"), at(70)), None);
+        assert_eq!(detector.observe("bridge", Some("offline"), at(120)), None);
+        assert!(detector.observe("bridge", Some("offline"), at(340)).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failover_watch_stops_with_unified_operation() {
+        let integration = Arc::new(new_integration().await);
+        integration.start_unified_operation().await.unwrap();
+
+        let first = integration.start_failover_watch(FailoverConfig::default()).await.unwrap();
+        let second = integration.start_failover_watch(FailoverConfig::default()).await.unwrap();
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+
+        integration.stop_unified_operation().await.unwrap();
+        assert!(second.is_cancelled());
+        assert!(integration.failover_watch.lock().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_over_bridge_is_not_routed_to() {
+        let integration = new_integration().await;
+        integration.orchestrator.trigger_failover("bridge", "offline").await.unwrap();
+        assert!(integration.orchestrator.is_failed_over("bridge").await);
+
+        let error = integration.orchestrator.execute_unified_optimization(Vec::new()).await.unwrap_err();
+        assert!(error.to_string().contains("offline"));
+
+        integration.orchestrator.recover_from_failover("bridge").await.unwrap();
+        assert!(!integration.orchestrator.is_failed_over("bridge").await);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_sync_emits_cross_system_event() {
+        let integration = new_integration().await;
+        let mut events = integration.subscribe_events();
+
+        integration.bridge.real_time_sync("audit_trails").await.unwrap();
+
+        let synced = tokio::time::timeout(Duration::from_secs(1), async {
+            while let Some(event) = events.recv().await {
+                if matches!(event, IntegrationEvent::CrossSystemSyncCompleted) {
+                    return true;
+                }
+            }
+            false
+        })
+        .await
+        .unwrap_or(false);
+        assert!(synced);
+
+        let bridge_health = integration.bridge.health_check().await.unwrap();
+        assert!(bridge_health.healthy);
+        assert!(bridge_health.sync_staleness_secs < 5);
+    }
+
+    #[tokio::test]
+    async fn test_failed_bridge_sync_reports_unhealthy_until_next_sync() {
+        let integration = new_integration().await;
+
+        integration.bridge.record_sync_failure("performance_data", "ECTUS-R unreachable").await.unwrap();
+        let bridge_health = integration.bridge.health_check().await.unwrap();
+        assert!(!bridge_health.healthy);
+        assert!(matches!(bridge_health.status, SystemStatus::Degraded));
+
+        integration.bridge.real_time_sync("performance_data").await.unwrap();
+        assert!(integration.bridge.health_check().await.unwrap().healthy);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_stop_waits_for_a_sleeping_heartbeat() {
+        let bridge = EctusRAionBridge::new_with_heartbeat(HeartbeatConfig {
+            interval: Duration::from_secs(3600),
+            ..HeartbeatConfig::default()
+        })
+        .await
+        .unwrap();
+
+        // The heartbeat task is an hour from its next tick; stop must not wait for it
+        tokio::time::timeout(Duration::from_secs(1), bridge.stop()).await.unwrap().unwrap();
+        assert!(!bridge.is_running().await);
+    }
+
+    #[tokio::test]
+    async fn test_contract_events_reach_the_bus_until_operation_stops() {
+        let integration = new_integration().await;
+        let mut events = integration.subscribe_events();
+
+        let contract_event = |removed| ContractEvent {
+            contract: "0xaa".to_string(),
+            event: "ComplianceViolationRecorded".to_string(),
+            block_number: 11,
+            transaction_hash: "0xabc".to_string(),
+            params: serde_json::json!({"severity": "7"}),
+            removed,
+        };
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let forwarder = integration.forward_contract_events(futures::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|event| (event, receiver))
+        }));
+
+        sender.send(contract_event(false)).unwrap();
+        sender.send(contract_event(true)).unwrap();
+        for expected_removed in [false, true] {
+            match events.recv().await {
+                Some(IntegrationEvent::ContractEventObserved { event, removed, .. }) => {
+                    assert_eq!(event, "ComplianceViolationRecorded");
+                    assert_eq!(removed, expected_removed);
+                }
+                other => panic!("expected a contract event, got {:?}", other),
+            }
+        }
+
+        // Forwarders are stopped first, whatever the other components report
+        let _ = integration.stop_unified_operation().await;
+        assert!(forwarder.await.unwrap_err().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_lagged_subscription_reports_dropped_events() {
+        let integration = new_integration().await;
+        let mut events = integration.subscribe_events();
+
+        // The security event is the oldest and gets overwritten by the filler
+        integration.emit_event(IntegrationEvent::SecurityLevelElevated { event_id: Uuid::new_v4(), from: 96, to: 192 });
+        for _ in 0..EVENT_BUS_CAPACITY {
+            integration.emit_event(IntegrationEvent::CrossSystemSyncCompleted);
+        }
+
+        // The subscription continues after the lag instead of ending
+        assert!(matches!(events.recv().await, Some(IntegrationEvent::CrossSystemSyncCompleted)));
+        assert_eq!(events.dropped_count(), 1);
+
+        let dropped = integration.monitor.dropped_events().await;
+        assert_eq!(dropped.total, 1);
+        assert_eq!(dropped.by_kind.get("SecurityLevelElevated"), Some(&1));
+        assert_eq!(integration.health_check().await.unwrap().dropped_events, 1);
+    }
 }
\ No newline at end of file
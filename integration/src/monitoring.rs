@@ -7,6 +7,7 @@ use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use anyhow::Result;
+use tokio_util::sync::CancellationToken;
 use tracing::{info, warn, error};
 
 /// Integration monitoring system
@@ -18,6 +19,123 @@ pub struct IntegrationMonitor {
     pub alerting_system: Arc<AlertingSystem>,
     pub observability_engine: Arc<ObservabilityEngine>,
     pub dashboard_provider: Arc<DashboardProvider>,
+    running: Arc<RwLock<bool>>,
+    dropped_events: Arc<RwLock<DroppedEventStats>>,
+}
+
+/// Thresholds for health-based failover
+#[derive(Debug, Clone)]
+pub struct FailoverConfig {
+    /// How often the watchdog runs a health check
+    pub check_interval: std::time::Duration,
+    /// How long a system must stay unhealthy before failover triggers
+    pub unhealthy_window: std::time::Duration,
+    /// Minimum time between two failovers of the same system
+    pub cooldown: std::time::Duration,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            check_interval: std::time::Duration::from_secs(10),
+            unhealthy_window: std::time::Duration::from_secs(30),
+            cooldown: std::time::Duration::from_secs(300),
+        }
+    }
+}
+
+/// Running failover watchdog
+///
+/// Dropping it leaves the watchdog running until the integration is dropped;
+/// cancel it or call `stop` to end it sooner.
+pub struct FailoverWatch {
+    cancel: CancellationToken,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl FailoverWatch {
+    /// Token that stops the watchdog when cancelled
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+
+    /// Stop the watchdog and wait for its loop to exit
+    pub async fn stop(self) -> Result<()> {
+        self.cancel.cancel();
+        self.handle.await?;
+        Ok(())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+}
+
+/// What the failover detector decided for one health observation
+#[derive(Debug, Clone, PartialEq)]
+pub enum FailoverAction {
+    Trigger { system: String, reason: String },
+    Recover { system: String, downtime: std::time::Duration },
+}
+
+#[derive(Debug, Clone, Default)]
+struct SystemFailoverState {
+    unhealthy_since: Option<std::time::Instant>,
+    failed_over_at: Option<std::time::Instant>,
+    last_failover: Option<std::time::Instant>,
+}
+
+/// Tracks per-system health over time and decides when to fail over or recover
+#[derive(Debug, Clone, Default)]
+pub struct FailoverDetector {
+    config: FailoverConfig,
+    systems: HashMap<String, SystemFailoverState>,
+}
+
+impl FailoverDetector {
+    pub fn new(config: FailoverConfig) -> Self {
+        Self { config, systems: HashMap::new() }
+    }
+
+    pub fn config(&self) -> &FailoverConfig {
+        &self.config
+    }
+
+    /// Feed one health observation. `problem` is `None` when the system is healthy.
+    pub fn observe(&mut self, system: &str, problem: Option<&str>, now: std::time::Instant) -> Option<FailoverAction> {
+        let state = self.systems.entry(system.to_string()).or_default();
+
+        let Some(reason) = problem else {
+            state.unhealthy_since = None;
+            return state.failed_over_at.take().map(|failed_over_at| FailoverAction::Recover {
+                system: system.to_string(),
+                downtime: now.duration_since(failed_over_at),
+            });
+        };
+
+        let since = *state.unhealthy_since.get_or_insert(now);
+        if state.failed_over_at.is_some() || now.duration_since(since) < self.config.unhealthy_window {
+            return None;
+        }
+        if state.last_failover.is_some_and(|last| now.duration_since(last) < self.config.cooldown) {
+            return None;
+        }
+
+        state.failed_over_at = Some(now);
+        state.last_failover = Some(now);
+        Some(FailoverAction::Trigger {
+            system: system.to_string(),
+            reason: format!("{} for {}s", reason, now.duration_since(since).as_secs()),
+        })
+    }
+}
+
+/// Integration events lost by lagging event-bus subscribers
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DroppedEventStats {
+    pub total: u64,
+    pub by_kind: HashMap<String, u64>,
+    pub last_dropped_at: Option<DateTime<Utc>>,
 }
 
 /// Metrics collection and aggregation
@@ -772,6 +890,8 @@ impl IntegrationMonitor {
             alerting_system,
             observability_engine,
             dashboard_provider,
+            running: Arc::new(RwLock::new(false)),
+            dropped_events: Arc::new(RwLock::new(DroppedEventStats::default())),
         })
     }
 
@@ -794,10 +914,83 @@ impl IntegrationMonitor {
         // Start observability engine
         self.observability_engine.start_collection().await?;
 
+        *self.running.write().await = true;
+
         info!("✅ Integration monitoring started successfully");
         Ok(())
     }
 
+    /// Stop monitoring, shutting down each collector started by `start_monitoring`
+    pub async fn stop_monitoring(&self) -> Result<()> {
+        info!("🛑 Stopping integration monitoring");
+
+        *self.running.write().await = false;
+
+        self.observability_engine.stop_collection().await?;
+        self.alerting_system.stop_alerting().await?;
+        self.performance_tracker.stop_tracking().await?;
+        self.health_checker.stop_health_checks().await?;
+        self.metrics_collector.stop_collection().await?;
+
+        info!("✅ Integration monitoring stopped");
+        Ok(())
+    }
+
+    /// Whether monitoring is currently active
+    pub async fn is_running(&self) -> bool {
+        *self.running.read().await
+    }
+
+    /// Record events a subscriber missed, keyed by event kind
+    pub async fn record_dropped_events(&self, kinds: &HashMap<&'static str, u64>) {
+        let mut stats = self.dropped_events.write().await;
+        for (kind, count) in kinds {
+            stats.total += count;
+            *stats.by_kind.entry(kind.to_string()).or_insert(0) += count;
+        }
+        stats.last_dropped_at = Some(Utc::now());
+    }
+
+    /// Events lost by lagging subscribers so far
+    pub async fn dropped_events(&self) -> DroppedEventStats {
+        self.dropped_events.read().await.clone()
+    }
+
+    /// Periodically health-check the integration and drive failover until
+    /// the watch is cancelled or the integration is dropped
+    ///
+    /// Checks are skipped while monitoring is stopped.
+    pub fn watch_failover(
+        &self,
+        integration: std::sync::Weak<crate::AionEctusIntegration>,
+        config: FailoverConfig,
+    ) -> FailoverWatch {
+        let running = self.running.clone();
+        let cancel = CancellationToken::new();
+        let cancelled = cancel.clone();
+        let handle = tokio::spawn(async move {
+            let mut detector = FailoverDetector::new(config);
+            let mut interval = tokio::time::interval(detector.config().check_interval);
+            loop {
+                tokio::select! {
+                    _ = cancelled.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+                if !*running.read().await {
+                    continue;
+                }
+                let Some(integration) = integration.upgrade() else {
+                    break;
+                };
+                if let Err(e) = integration.evaluate_failover(&mut detector).await {
+                    warn!("⚠️ Failover health check failed: {}", e);
+                }
+            }
+        });
+
+        FailoverWatch { cancel, handle }
+    }
+
     /// Get system uptime
     pub async fn get_uptime(&self) -> Result<Duration> {
         // This would track actual uptime in a real implementation
@@ -838,6 +1031,11 @@ impl MetricsCollector {
         info!("📈 Starting metrics collection");
         Ok(())
     }
+
+    async fn stop_collection(&self) -> Result<()> {
+        info!("📈 Stopping metrics collection");
+        Ok(())
+    }
 }
 
 impl HealthChecker {
@@ -863,6 +1061,11 @@ impl HealthChecker {
         info!("🏥 Starting health checks");
         Ok(())
     }
+
+    async fn stop_health_checks(&self) -> Result<()> {
+        info!("🏥 Stopping health checks");
+        Ok(())
+    }
 }
 
 impl CheckScheduler {
@@ -898,6 +1101,11 @@ impl PerformanceTracker {
         info!("⚡ Starting performance tracking");
         Ok(())
     }
+
+    async fn stop_tracking(&self) -> Result<()> {
+        info!("⚡ Stopping performance tracking");
+        Ok(())
+    }
 }
 
 impl TrendAnalyzer {
@@ -925,6 +1133,11 @@ impl AlertingSystem {
         info!("🚨 Starting alerting system");
         Ok(())
     }
+
+    async fn stop_alerting(&self) -> Result<()> {
+        info!("🚨 Stopping alerting system");
+        Ok(())
+    }
 }
 
 impl ObservabilityEngine {
@@ -948,6 +1161,11 @@ impl ObservabilityEngine {
         info!("🔍 Starting observability data collection");
         Ok(())
     }
+
+    async fn stop_collection(&self) -> Result<()> {
+        info!("🔍 Stopping observability data collection");
+        Ok(())
+    }
 }
 
 impl TraceCollector {
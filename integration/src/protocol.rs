@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::fmt;
 
 /// Native protocol message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,13 +12,15 @@ pub enum ProtocolMessage {
     // Handshake and connection
     HandshakeRequest {
         sender_id: Uuid,
-        protocol_version: String,
+        supported_versions: Vec<ProtocolVersion>,
         capabilities: Vec<String>,
         security_level: u8,
     },
     HandshakeResponse {
         receiver_id: Uuid,
         accepted: bool,
+        /// Version both sides will use, or `None` if they share none
+        negotiated_version: Option<ProtocolVersion>,
         supported_capabilities: Vec<String>,
         max_security_level: u8,
     },
@@ -119,6 +122,188 @@ pub enum ProtocolMessage {
         alerts: Vec<SystemAlert>,
     },
 }
+/// Protocol schema version. Messages are only interpreted under a version
+/// both sides support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ProtocolVersion {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl ProtocolVersion {
+    pub const V1_0: ProtocolVersion = ProtocolVersion { major: 1, minor: 0 };
+
+    pub const fn new(major: u16, minor: u16) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Versions this build can encode and decode, oldest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[ProtocolVersion] = &[ProtocolVersion::V1_0];
+
+/// Capabilities this build offers a peer in the handshake
+pub const SUPPORTED_CAPABILITIES: &[&str] = &[
+    "state_sync",
+    "resource_management",
+    "compliance_alerts",
+    "autonomous_decisions",
+    "optimization",
+    "failover",
+    "health_monitoring",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProtocolError {
+    #[error("Unsupported protocol version {received}; supported: {}", format_versions(.supported))]
+    UnsupportedVersion {
+        received: ProtocolVersion,
+        supported: Vec<ProtocolVersion>,
+    },
+    #[error("Protocol version {received} does not match the negotiated version {negotiated}")]
+    VersionMismatch {
+        received: ProtocolVersion,
+        negotiated: ProtocolVersion,
+    },
+    #[error("No common protocol version: local {}, remote {}", format_versions(.local), format_versions(.remote))]
+    NoCommonVersion {
+        local: Vec<ProtocolVersion>,
+        remote: Vec<ProtocolVersion>,
+    },
+    #[error("Message of {size} bytes exceeds the {limit} byte limit")]
+    MessageTooLarge { size: usize, limit: usize },
+    #[error("Malformed protocol message: {0}")]
+    Malformed(String),
+}
+
+fn format_versions(versions: &[ProtocolVersion]) -> String {
+    versions.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// Pick the highest version both sides support
+pub fn negotiate_version(
+    local: &[ProtocolVersion],
+    remote: &[ProtocolVersion],
+) -> Result<ProtocolVersion, ProtocolError> {
+    local
+        .iter()
+        .filter(|version| remote.contains(version))
+        .max()
+        .copied()
+        .ok_or_else(|| ProtocolError::NoCommonVersion {
+            local: local.to_vec(),
+            remote: remote.to_vec(),
+        })
+}
+
+/// A protocol message on the wire, stamped with the version it was encoded under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolEnvelope {
+    pub version: ProtocolVersion,
+    pub message_id: Uuid,
+    pub sent_at: DateTime<Utc>,
+    pub message: ProtocolMessage,
+}
+
+/// Only the version, read before the payload so an unknown schema is never parsed
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: ProtocolVersion,
+}
+
+/// Encodes and decodes envelopes under a negotiated protocol version
+#[derive(Debug, Clone)]
+pub struct ProtocolCodec {
+    version: ProtocolVersion,
+    max_message_size: usize,
+}
+
+impl ProtocolCodec {
+    /// Negotiate with the peer's supported versions at connect time
+    pub fn negotiate(remote: &[ProtocolVersion], config: &ProtocolConfig) -> Result<Self, ProtocolError> {
+        let version = negotiate_version(SUPPORTED_PROTOCOL_VERSIONS, remote)?;
+        Ok(Self {
+            version,
+            max_message_size: config.max_message_size,
+        })
+    }
+
+    pub fn version(&self) -> ProtocolVersion {
+        self.version
+    }
+
+    /// Answer a peer's `HandshakeRequest`, agreeing on a version if possible
+    ///
+    /// Only capabilities both sides offer are reported back.
+    pub fn handshake_response(
+        receiver_id: Uuid,
+        request: &ProtocolMessage,
+        config: &ProtocolConfig,
+    ) -> Result<(ProtocolMessage, Option<Self>), ProtocolError> {
+        let ProtocolMessage::HandshakeRequest { supported_versions, capabilities, .. } = request else {
+            return Err(ProtocolError::Malformed("expected a handshake request".to_string()));
+        };
+
+        let codec = Self::negotiate(supported_versions, config).ok();
+        let shared_capabilities = config
+            .capabilities
+            .iter()
+            .filter(|capability| capabilities.contains(capability))
+            .cloned()
+            .collect();
+        let response = ProtocolMessage::HandshakeResponse {
+            receiver_id,
+            accepted: codec.is_some(),
+            negotiated_version: codec.as_ref().map(|codec| codec.version),
+            supported_capabilities: shared_capabilities,
+            max_security_level: config.security_level,
+        };
+        Ok((response, codec))
+    }
+
+    pub fn encode(&self, message: &ProtocolMessage) -> Result<Vec<u8>, ProtocolError> {
+        let envelope = ProtocolEnvelope {
+            version: self.version,
+            message_id: Uuid::new_v4(),
+            sent_at: Utc::now(),
+            message: message.clone(),
+        };
+        let bytes = serde_json::to_vec(&envelope).map_err(|e| ProtocolError::Malformed(e.to_string()))?;
+        if bytes.len() > self.max_message_size {
+            return Err(ProtocolError::MessageTooLarge { size: bytes.len(), limit: self.max_message_size });
+        }
+        Ok(bytes)
+    }
+
+    /// Decode an envelope, rejecting any version but the negotiated one before reading the payload
+    pub fn decode(&self, bytes: &[u8]) -> Result<ProtocolEnvelope, ProtocolError> {
+        if bytes.len() > self.max_message_size {
+            return Err(ProtocolError::MessageTooLarge { size: bytes.len(), limit: self.max_message_size });
+        }
+
+        let probe: VersionProbe = serde_json::from_slice(bytes)
+            .map_err(|e| ProtocolError::Malformed(format!("missing or invalid version: {}", e)))?;
+        if !SUPPORTED_PROTOCOL_VERSIONS.contains(&probe.version) {
+            return Err(ProtocolError::UnsupportedVersion {
+                received: probe.version,
+                supported: SUPPORTED_PROTOCOL_VERSIONS.to_vec(),
+            });
+        }
+        if probe.version != self.version {
+            return Err(ProtocolError::VersionMismatch {
+                received: probe.version,
+                negotiated: self.version,
+            });
+        }
+
+        serde_json::from_slice(bytes).map_err(|e| ProtocolError::Malformed(e.to_string()))
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ResourceType {
@@ -511,6 +696,12 @@ pub struct ProtocolConfig {
     pub compression_enabled: bool,
     pub encryption_enabled: bool,
     pub security_level: u8,
+    /// Capabilities offered to peers; defaults to all of `SUPPORTED_CAPABILITIES`
+    pub capabilities: Vec<String>,
+}
+
+fn supported_capabilities() -> Vec<String> {
+    SUPPORTED_CAPABILITIES.iter().map(|capability| capability.to_string()).collect()
 }
 
 impl Default for ProtocolConfig {
@@ -523,6 +714,7 @@ impl Default for ProtocolConfig {
             compression_enabled: true,
             encryption_enabled: true,
             security_level: 255, // Maximum security
+            capabilities: supported_capabilities(),
         }
     }
 }
@@ -537,6 +729,338 @@ impl ProtocolConfig {
             compression_enabled: true,
             encryption_enabled: true,
             security_level: 255, // Maximum security level
+            capabilities: supported_capabilities(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codec() -> ProtocolCodec {
+        ProtocolCodec::negotiate(SUPPORTED_PROTOCOL_VERSIONS, &ProtocolConfig::default()).unwrap()
+    }
+
+    fn performance_metrics() -> PerformanceMetrics {
+        PerformanceMetrics {
+            execution_time: chrono::Duration::milliseconds(120),
+            resource_consumption: ResourceConsumption { cpu_time_ms: 10, memory_bytes: 2048, disk_io_bytes: 0, network_io_bytes: 512 },
+            throughput: 42,
+            latency_percentiles: LatencyPercentiles { p50: 5, p90: 9, p95: 12, p99: 20, max: 31 },
+            error_metrics: ErrorMetrics { total_errors: 1, error_rate: 0.01, error_types: HashMap::from([("timeout".to_string(), 1)]), critical_errors: 0 },
+        }
+    }
+
+    fn execution_plan() -> ExecutionPlan {
+        ExecutionPlan {
+            steps: vec![ExecutionStep {
+                step_id: Uuid::new_v4(),
+                description: "Reallocate storage".to_string(),
+                action_type: ActionType::ResourceAllocation,
+                parameters: HashMap::from([("size_gb".to_string(), serde_json::json!(50))]),
+                dependencies: Vec::new(),
+                timeout: chrono::Duration::minutes(5),
+            }],
+            estimated_duration: chrono::Duration::minutes(10),
+            rollback_plan: None,
+            success_criteria: Vec::new(),
+        }
+    }
+
+    fn all_messages() -> Vec<ProtocolMessage> {
+        let violation = ComplianceViolation {
+            violation_type: "retention".to_string(),
+            regulation_section: "GDPR Art. 5(1)(e)".to_string(),
+            description: "Data kept past retention period".to_string(),
+            evidence: vec!["bucket/logs".to_string()],
+            risk_score: 0.7,
+            potential_impact: "fine".to_string(),
+        };
+
+        vec![
+            ProtocolMessage::HandshakeRequest {
+                sender_id: Uuid::new_v4(),
+                supported_versions: SUPPORTED_PROTOCOL_VERSIONS.to_vec(),
+                capabilities: vec!["state_sync".to_string()],
+                security_level: 128,
+            },
+            ProtocolMessage::HandshakeResponse {
+                receiver_id: Uuid::new_v4(),
+                accepted: true,
+                negotiated_version: Some(ProtocolVersion::V1_0),
+                supported_capabilities: vec!["state_sync".to_string()],
+                max_security_level: 128,
+            },
+            ProtocolMessage::StateSync {
+                sender_id: Uuid::new_v4(),
+                state_hash: "abc123".to_string(),
+                state_data: vec![1, 2, 3],
+                timestamp: Utc::now(),
+            },
+            ProtocolMessage::StateSyncAck {
+                receiver_id: Uuid::new_v4(),
+                accepted: false,
+                conflicts: vec![StateConflict {
+                    conflict_id: Uuid::new_v4(),
+                    conflict_type: "policy".to_string(),
+                    description: "diverging retention".to_string(),
+                    local_value: serde_json::json!(30),
+                    remote_value: serde_json::json!(90),
+                    resolution_strategy: ConflictResolution::UseLocal,
+                }],
+            },
+            ProtocolMessage::ResourceAllocation {
+                resource_id: Uuid::new_v4(),
+                resource_type: ResourceType::StorageResource,
+                allocation_data: ResourceAllocationData {
+                    allocation_size: 1024,
+                    duration: chrono::Duration::hours(1),
+                    access_permissions: vec!["read".to_string()],
+                    usage_limits: HashMap::from([("iops".to_string(), 500)]),
+                    metadata: HashMap::new(),
+                },
+                priority: Priority::High,
+            },
+            ProtocolMessage::ResourceStatus {
+                resource_id: Uuid::new_v4(),
+                status: ResourceStatus::InUse,
+                metrics: ResourceMetrics { utilization_percentage: 55.0, performance_score: 0.9, error_rate: 0.0, response_time_ms: 12, throughput: 300 },
+                timestamp: Utc::now(),
+            },
+            ProtocolMessage::ComplianceAlert {
+                alert_id: Uuid::new_v4(),
+                severity: AlertSeverity::High,
+                regulation_id: "GDPR".to_string(),
+                violation_details: violation.clone(),
+                recommended_actions: vec!["purge".to_string()],
+            },
+            ProtocolMessage::ComplianceUpdate {
+                update_id: Uuid::new_v4(),
+                regulation_changes: vec![RegulationChange {
+                    change_id: Uuid::new_v4(),
+                    regulation_id: "SOX".to_string(),
+                    change_type: ChangeType::Modification,
+                    old_value: serde_json::json!("annual"),
+                    new_value: serde_json::json!("quarterly"),
+                    impact_scope: vec!["finance".to_string()],
+                }],
+                effective_date: Utc::now(),
+                impact_assessment: ImpactAssessment {
+                    overall_impact: ImpactLevel::Medium,
+                    affected_resources: vec![Uuid::new_v4()],
+                    compliance_gap_analysis: vec![ComplianceGap {
+                        gap_id: Uuid::new_v4(),
+                        description: "reporting cadence".to_string(),
+                        severity: AlertSeverity::Medium,
+                        remediation_steps: vec!["schedule reports".to_string()],
+                        timeline: chrono::Duration::days(30),
+                    }],
+                    adaptation_time_estimate: chrono::Duration::days(14),
+                    cost_estimate: Some(12500.0),
+                },
+            },
+            ProtocolMessage::AutonomousDecision {
+                decision_id: Uuid::new_v4(),
+                decision_type: DecisionType::ResourceReallocation,
+                context: DecisionContext {
+                    trigger_event: "capacity".to_string(),
+                    current_state: HashMap::new(),
+                    constraints: vec![Constraint {
+                        constraint_type: "budget".to_string(),
+                        description: "monthly cap".to_string(),
+                        hard_limit: true,
+                        value: serde_json::json!(1000),
+                    }],
+                    objectives: vec![Objective {
+                        objective_type: "latency".to_string(),
+                        description: "p99 under 50ms".to_string(),
+                        weight: 1.0,
+                        target_value: serde_json::json!(50),
+                        tolerance: 0.1,
+                    }],
+                    risk_tolerance: 0.3,
+                },
+                confidence_score: 0.85,
+                execution_plan: execution_plan(),
+            },
+            ProtocolMessage::DecisionExecutionResult {
+                decision_id: Uuid::new_v4(),
+                success: true,
+                results: ExecutionResults {
+                    overall_success: true,
+                    step_results: vec![StepResult {
+                        step_id: Uuid::new_v4(),
+                        success: true,
+                        execution_time: chrono::Duration::seconds(3),
+                        output: serde_json::json!({"allocated": true}),
+                        errors: Vec::new(),
+                    }],
+                    performance_impact: PerformanceImpact { cpu_impact: 0.1, memory_impact: 0.2, latency_impact: -0.05, throughput_impact: 0.1 },
+                    compliance_status: ComplianceStatus {
+                        overall_compliance: true,
+                        compliance_score: 0.98,
+                        active_violations: vec![violation.clone()],
+                        compliance_gaps: Vec::new(),
+                        next_audit_date: Utc::now(),
+                    },
+                },
+                side_effects: vec![SideEffect {
+                    effect_type: "restart".to_string(),
+                    description: "cache flushed".to_string(),
+                    severity: AlertSeverity::Low,
+                    mitigation_actions: Vec::new(),
+                }],
+            },
+            ProtocolMessage::OptimizationRequest {
+                request_id: Uuid::new_v4(),
+                optimization_target: OptimizationTarget::CostEfficiency,
+                constraints: Vec::new(),
+                parameters: HashMap::from([("horizon_days".to_string(), serde_json::json!(7))]),
+            },
+            ProtocolMessage::OptimizationResponse {
+                request_id: Uuid::new_v4(),
+                optimization_results: OptimizationResults {
+                    improved_metrics: HashMap::from([("cost".to_string(), -0.12)]),
+                    configuration_changes: vec![ConfigurationChange {
+                        component: "storage".to_string(),
+                        parameter: "tier".to_string(),
+                        old_value: serde_json::json!("hot"),
+                        new_value: serde_json::json!("warm"),
+                        reason: "low access rate".to_string(),
+                    }],
+                    predicted_performance: PerformanceProjection {
+                        projected_improvements: HashMap::from([("cost".to_string(), 0.12)]),
+                        confidence_intervals: HashMap::from([("cost".to_string(), (0.08, 0.16))]),
+                        time_to_improvement: chrono::Duration::days(1),
+                    },
+                    confidence_score: 0.8,
+                },
+                performance_metrics: performance_metrics(),
+            },
+            ProtocolMessage::EmergencyAlert {
+                alert_id: Uuid::new_v4(),
+                emergency_type: EmergencyType::NetworkPartition,
+                severity: 9,
+                affected_systems: vec!["ectus-r".to_string()],
+                immediate_actions: vec!["isolate".to_string()],
+            },
+            ProtocolMessage::FailoverRequest {
+                request_id: Uuid::new_v4(),
+                source_system: "primary".to_string(),
+                target_system: "standby".to_string(),
+                failover_data: vec![0xde, 0xad],
+            },
+            ProtocolMessage::HealthCheck {
+                sender_id: Uuid::new_v4(),
+                timestamp: Utc::now(),
+            },
+            ProtocolMessage::HealthResponse {
+                sender_id: Uuid::new_v4(),
+                status: SystemHealth {
+                    overall_status: HealthStatus::Warning,
+                    subsystem_status: HashMap::from([("bridge".to_string(), HealthStatus::Healthy)]),
+                    last_health_check: Utc::now(),
+                    uptime: chrono::Duration::hours(48),
+                },
+                metrics: SystemMetrics { cpu_usage: 0.4, memory_usage: 0.6, disk_usage: 0.3, network_throughput: 1000, active_connections: 12, error_rate: 0.0, response_time: 8 },
+                alerts: vec![SystemAlert {
+                    alert_id: Uuid::new_v4(),
+                    alert_type: "disk".to_string(),
+                    severity: AlertSeverity::Info,
+                    message: "disk usage rising".to_string(),
+                    timestamp: Utc::now(),
+                    acknowledged: false,
+                }],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_every_message_variant_round_trips() {
+        let codec = codec();
+        let messages = all_messages();
+        assert_eq!(messages.len(), 16);
+
+        for message in messages {
+            let bytes = codec.encode(&message).unwrap();
+            let envelope = codec.decode(&bytes).unwrap();
+            assert_eq!(envelope.version, ProtocolVersion::V1_0);
+            assert_eq!(
+                serde_json::to_value(&envelope.message).unwrap(),
+                serde_json::to_value(&message).unwrap()
+            );
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_negotiate_version() {
+        let local = [ProtocolVersion::new(1, 0), ProtocolVersion::new(1, 1), ProtocolVersion::new(2, 0)];
+        let remote = [ProtocolVersion::new(1, 1), ProtocolVersion::new(1, 0)];
+        assert_eq!(negotiate_version(&local, &remote).unwrap(), ProtocolVersion::new(1, 1));
+
+        let err = negotiate_version(&local, &[ProtocolVersion::new(3, 0)]).unwrap_err();
+        assert!(matches!(err, ProtocolError::NoCommonVersion { .. }));
+
+        let request = ProtocolMessage::HandshakeRequest {
+            sender_id: Uuid::new_v4(),
+            supported_versions: vec![ProtocolVersion::new(9, 9)],
+            capabilities: Vec::new(),
+            security_level: 1,
+        };
+        let (response, codec) = ProtocolCodec::handshake_response(Uuid::new_v4(), &request, &ProtocolConfig::default()).unwrap();
+        assert!(codec.is_none());
+        assert!(matches!(response, ProtocolMessage::HandshakeResponse { accepted: false, negotiated_version: None, .. }));
+    }
+
+    #[test]
+    fn test_handshake_reports_only_shared_capabilities() {
+        let request = ProtocolMessage::HandshakeRequest {
+            sender_id: Uuid::new_v4(),
+            supported_versions: SUPPORTED_PROTOCOL_VERSIONS.to_vec(),
+            capabilities: vec!["failover".to_string(), "quantum_teleport".to_string(), "state_sync".to_string()],
+            security_level: 1,
+        };
+        let config = ProtocolConfig {
+            capabilities: vec!["state_sync".to_string(), "optimization".to_string(), "failover".to_string()],
+            ..ProtocolConfig::default()
+        };
+        let (response, _) = ProtocolCodec::handshake_response(Uuid::new_v4(), &request, &config).unwrap();
+        let ProtocolMessage::HandshakeResponse { supported_capabilities, .. } = response else {
+            panic!("expected a handshake response");
+        };
+        assert_eq!(supported_capabilities, vec!["state_sync", "failover"]);
+    }
+
+    #[test]
+    fn test_only_the_negotiated_version_is_decoded() {
+        let bytes = codec()
+            .encode(&ProtocolMessage::HealthCheck { sender_id: Uuid::new_v4(), timestamp: Utc::now() })
+            .unwrap();
+        let codec = ProtocolCodec { version: ProtocolVersion::new(1, 1), max_message_size: 1024 };
+
+        let err = codec.decode(&bytes).unwrap_err();
+        assert!(matches!(
+            err,
+            ProtocolError::VersionMismatch { received, negotiated }
+                if received == ProtocolVersion::V1_0 && negotiated == ProtocolVersion::new(1, 1)
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_version_is_rejected() {
+        let codec = codec();
+        let mut envelope = serde_json::to_value(ProtocolEnvelope {
+            version: ProtocolVersion::V1_0,
+            message_id: Uuid::new_v4(),
+            sent_at: Utc::now(),
+            message: ProtocolMessage::HealthCheck { sender_id: Uuid::new_v4(), timestamp: Utc::now() },
+        })
+        .unwrap();
+        envelope["version"] = serde_json::json!({"major": 2, "minor": 0});
+
+        let err = codec.decode(&serde_json::to_vec(&envelope).unwrap()).unwrap_err();
+        assert!(matches!(err, ProtocolError::UnsupportedVersion { received, .. } if received == ProtocolVersion::new(2, 0)));
+        assert!(err.to_string().contains("Unsupported protocol version 2.0"));
+    }
+}
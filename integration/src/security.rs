@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use anyhow::Result;
 use tracing::{info, warn, error};
+use aion_audit::{AuditTrailSink, ComprehensiveAuditSystem, FileAuditSystem, PrivilegeChangeOutcome, PrivilegeChangeRecord};
 
 /// Security manager with maximum privilege escalation capabilities
 pub struct SecurityManager {
@@ -19,6 +20,96 @@ pub struct SecurityManager {
     pub audit_logger: Arc<AuditLogger>,
     pub privilege_escalator: Arc<PrivilegeEscalator>,
     pub maximum_privileges_enabled: bool,
+    /// Simulated escalations awaiting confirmation, keyed by confirmation token
+    pending_escalations: Arc<RwLock<HashMap<String, EscalationPlan>>>,
+    /// Durable record of every privilege change and refused escalation
+    audit_trail: Arc<tokio::sync::Mutex<dyn AuditTrailSink>>,
+}
+
+/// Operator sign-off for escalation plans, obtained outside the process
+/// requesting the escalation, e.g. from a console prompt or an approval queue
+#[async_trait::async_trait]
+pub trait EscalationApprover: Send + Sync {
+    /// Show `plan` to an operator and return the confirmation token they
+    /// enter, or an error if they decline
+    async fn approve(&self, plan: &EscalationPlan) -> Result<String>;
+}
+
+/// Who is changing privileges and why, recorded with the change in the audit trail
+#[derive(Debug, Clone)]
+pub struct PrivilegeChangeContext {
+    pub actor: String,
+    pub reason: String,
+    /// Id of the integration event that announces the change
+    pub event_id: Option<Uuid>,
+}
+
+impl PrivilegeChangeContext {
+    pub fn new(actor: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self { actor: actor.into(), reason: reason.into(), event_id: None }
+    }
+
+    pub fn with_event_id(mut self, event_id: Uuid) -> Self {
+        self.event_id = Some(event_id);
+        self
+    }
+}
+
+/// Privilege level a new security manager starts at, matching `IntegrationMode::Independent`
+pub const LEAST_PRIVILEGE_LEVEL: u8 = 32;
+
+/// How long an escalation plan's confirmation token stays valid
+pub const ESCALATION_CONFIRMATION_TTL_MINUTES: i64 = 5;
+
+/// Preview of what raising the privilege level would change. Nothing is
+/// applied until the plan's confirmation token is passed to
+/// `SecurityManager::apply_escalation`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationPlan {
+    pub plan_id: Uuid,
+    pub current_level: u8,
+    pub target_level: u8,
+    /// Intermediate levels the privilege matrix passes through
+    pub escalation_path: Vec<u8>,
+    pub changes: Vec<PrivilegeChange>,
+    /// Access rules that become usable at the target level
+    pub newly_permitted: Vec<PermittedAccess>,
+    /// Empty when the plan changes nothing
+    pub confirmation_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl EscalationPlan {
+    pub fn is_noop(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Fails when privileges moved since the plan was simulated
+    fn ensure_current(&self, current_level: u8) -> Result<()> {
+        if current_level != self.current_level {
+            anyhow::bail!(
+                "Privilege level changed from {} to {} since plan {} was made; simulate again",
+                self.current_level,
+                current_level,
+                self.plan_id
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivilegeChange {
+    pub setting: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermittedAccess {
+    pub rule: String,
+    pub resource_pattern: String,
+    pub operations: Vec<Operation>,
 }
 
 #[derive(Debug, Clone)]
@@ -303,9 +394,11 @@ pub enum KeyRotationStatus {
 }
 
 impl SecurityManager {
-    /// Create security manager with maximum privileges
-    pub async fn new_with_maximum_privileges() -> Result<Self> {
-        info!("🔐 Initializing security manager with maximum privileges");
+    /// Create a security manager at the least privilege level
+    ///
+    /// Higher levels are only reached through confirmed escalation plans.
+    pub async fn new() -> Result<Self> {
+        info!("🔐 Initializing security manager with least privilege");
 
         let manager_id = Uuid::new_v4();
 
@@ -315,38 +408,215 @@ impl SecurityManager {
             authorization_enabled: true,
             audit_logging_enabled: true,
             privilege_escalation_allowed: true,
-            maximum_security_level: 255,
-            unrestricted_mode: true, // Enable unrestricted mode for maximum autonomy
+            maximum_security_level: LEAST_PRIVILEGE_LEVEL,
+            unrestricted_mode: false,
         };
 
         let access_controller = Arc::new(AccessController::new_maximum_privileges().await?);
         let crypto_engine = Arc::new(CryptoEngine::new().await?);
         let audit_logger = Arc::new(AuditLogger::new().await?);
-        let privilege_escalator = Arc::new(PrivilegeEscalator::new_unrestricted().await?);
+        let privilege_escalator = Arc::new(PrivilegeEscalator::new().await?);
+
+        let audit_trail: Arc<tokio::sync::Mutex<dyn AuditTrailSink>> = match FileAuditSystem::open_configured().await? {
+            Some(file_trail) => {
+                info!("📜 Recording privilege changes to {}", file_trail.path().display());
+                Arc::new(tokio::sync::Mutex::new(file_trail))
+            }
+            None => {
+                warn!("⚠️ {} is not set; privilege changes are audited in memory only", aion_audit::AUDIT_TRAIL_PATH_ENV);
+                Arc::new(tokio::sync::Mutex::new(ComprehensiveAuditSystem::new()?))
+            }
+        };
 
         Ok(Self {
             manager_id,
-            privilege_level: Arc::new(RwLock::new(255)), // Start with maximum privileges
+            privilege_level: Arc::new(RwLock::new(LEAST_PRIVILEGE_LEVEL)),
             security_policies: Arc::new(RwLock::new(security_policies)),
             access_controller,
             crypto_engine,
             audit_logger,
             privilege_escalator,
-            maximum_privileges_enabled: true,
+            maximum_privileges_enabled: false,
+            pending_escalations: Arc::new(RwLock::new(HashMap::new())),
+            audit_trail,
         })
     }
 
-    /// Escalate to maximum privileges
-    pub async fn escalate_to_maximum(&self) -> Result<()> {
-        info!("⚡ Escalating to maximum privileges");
+    /// Record privilege changes to `audit_trail` instead of the trail chosen by
+    /// `AION_AUDIT_TRAIL_PATH`
+    pub fn with_audit_trail(mut self, audit_trail: Arc<tokio::sync::Mutex<dyn AuditTrailSink>>) -> Self {
+        self.audit_trail = audit_trail;
+        self
+    }
 
-        // Set maximum privilege level
-        {
-            let mut level = self.privilege_level.write().await;
-            *level = 255;
+    pub fn audit_trail(&self) -> Arc<tokio::sync::Mutex<dyn AuditTrailSink>> {
+        self.audit_trail.clone()
+    }
+
+    /// Current privilege level
+    pub async fn privilege_level(&self) -> u8 {
+        *self.privilege_level.read().await
+    }
+
+    /// Work out what escalating to `target_level` would change, without applying it.
+    /// The returned plan's token must be passed to `apply_escalation` to go ahead.
+    pub async fn simulate_escalation(&self, target_level: u8) -> EscalationPlan {
+        let current_level = *self.privilege_level.read().await;
+        let policies = self.security_policies.read().await.clone();
+
+        let mut changes = Vec::new();
+        let mut change = |setting: &str, from: String, to: String| {
+            if from != to {
+                changes.push(PrivilegeChange { setting: setting.to_string(), from, to });
+            }
+        };
+        if target_level > current_level {
+            change("privilege_level", current_level.to_string(), target_level.to_string());
+            change(
+                "maximum_security_level",
+                policies.maximum_security_level.to_string(),
+                target_level.to_string(),
+            );
+            change(
+                "unrestricted_mode",
+                policies.unrestricted_mode.to_string(),
+                (target_level == u8::MAX).to_string(),
+            );
+            if target_level == u8::MAX {
+                change(
+                    "active_escalations",
+                    self.privilege_escalator.get_active_escalations_count().await.unwrap_or(0).to_string(),
+                    "+1 permanent escalation without auto de-escalation".to_string(),
+                );
+            }
+        }
+
+        let escalation_path = {
+            let matrix = self.access_controller.privilege_matrix.read().await;
+            matrix
+                .escalation_paths
+                .get(&current_level)
+                .map(|path| path.iter().copied().filter(|level| *level <= target_level).collect())
+                .unwrap_or_else(|| if target_level > current_level { vec![target_level] } else { Vec::new() })
+        };
+
+        let mut newly_permitted: Vec<PermittedAccess> = self
+            .access_controller
+            .access_rules
+            .read()
+            .await
+            .iter()
+            .filter(|(_, rule)| rule.required_privilege_level > current_level && rule.required_privilege_level <= target_level)
+            .map(|(name, rule)| PermittedAccess {
+                rule: name.clone(),
+                resource_pattern: rule.resource_pattern.clone(),
+                operations: rule.allowed_operations.clone(),
+            })
+            .collect();
+        newly_permitted.sort_by(|a, b| a.rule.cmp(&b.rule));
+
+        let plan = EscalationPlan {
+            plan_id: Uuid::new_v4(),
+            current_level,
+            target_level,
+            escalation_path,
+            confirmation_token: if changes.is_empty() { String::new() } else { Uuid::new_v4().to_string() },
+            changes,
+            newly_permitted,
+            expires_at: Utc::now() + chrono::Duration::minutes(ESCALATION_CONFIRMATION_TTL_MINUTES),
+        };
+
+        let mut pending = self.pending_escalations.write().await;
+        let now = Utc::now();
+        pending.retain(|_, pending_plan| pending_plan.expires_at > now);
+        if !plan.is_noop() {
+            pending.insert(plan.confirmation_token.clone(), plan.clone());
+        }
+        plan
+    }
+
+    /// Apply a previously simulated escalation. Fails if the token is unknown,
+    /// expired, for a different target, or if privileges changed since the plan.
+    /// Refused attempts are recorded in the audit trail as well as applied ones.
+    pub async fn apply_escalation(
+        &self,
+        confirmation_token: &str,
+        target_level: u8,
+        context: &PrivilegeChangeContext,
+    ) -> Result<EscalationPlan> {
+        match self.take_confirmed_plan(confirmation_token, target_level).await {
+            Ok(plan) => {
+                let unchanged_since_plan = |current: u8| plan.ensure_current(current);
+                if target_level == u8::MAX {
+                    self.escalate_to_maximum_confirmed(context, unchanged_since_plan).await?;
+                } else {
+                    self.apply_privilege_level(target_level, context, unchanged_since_plan).await?;
+                }
+                Ok(plan)
+            }
+            Err(e) => {
+                self.record_refused_change(target_level, context, &e).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn take_confirmed_plan(&self, confirmation_token: &str, target_level: u8) -> Result<EscalationPlan> {
+        let plan = {
+            let mut pending = self.pending_escalations.write().await;
+            let now = Utc::now();
+            pending.retain(|token, pending_plan| pending_plan.expires_at > now || token == confirmation_token);
+            pending.remove(confirmation_token)
+        }
+        .ok_or_else(|| anyhow::anyhow!("Unknown or already used escalation confirmation token"))?;
+
+        if Utc::now() > plan.expires_at {
+            anyhow::bail!("Escalation plan {} expired at {}", plan.plan_id, plan.expires_at);
         }
+        if plan.target_level != target_level {
+            anyhow::bail!(
+                "Escalation plan {} targets level {}, not {}",
+                plan.plan_id,
+                plan.target_level,
+                target_level
+            );
+        }
+        plan.ensure_current(*self.privilege_level.read().await)?;
+        Ok(plan)
+    }
+
+    /// Escalate to maximum privileges using a confirmed plan from `simulate_escalation(255)`
+    pub async fn escalate_to_maximum(&self, confirmation_token: &str, context: &PrivilegeChangeContext) -> Result<()> {
+        self.apply_escalation(confirmation_token, u8::MAX, context).await.map(|_| ())
+    }
+
+    async fn escalate_to_maximum_confirmed(
+        &self,
+        context: &PrivilegeChangeContext,
+        check: impl FnOnce(u8) -> Result<()>,
+    ) -> Result<()> {
+        info!("⚡ Escalating to maximum privileges");
 
-        // Enable unrestricted mode in policies
+        // The escalation is only applied once its audit entry is recorded; holding
+        // the level makes the check, the change and the escalator update one step
+        let mut level = self.privilege_level.write().await;
+        let previous = *level;
+        if let Err(e) = check(previous) {
+            drop(level);
+            self.record_refused_change(u8::MAX, context, &e).await;
+            return Err(e);
+        }
+        self.record_privilege_change(
+            previous,
+            255,
+            context,
+            PrivilegeChangeOutcome::Applied,
+            HashMap::from([("escalation_type".to_string(), "maximum".to_string())]),
+        )
+        .await?;
+
+        // Set maximum privilege level and enable unrestricted mode in policies
+        *level = 255;
         {
             let mut policies = self.security_policies.write().await;
             policies.unrestricted_mode = true;
@@ -355,6 +625,7 @@ impl SecurityManager {
 
         // Escalate in privilege escalator
         self.privilege_escalator.escalate_to_maximum().await?;
+        drop(level);
 
         // Log escalation event
         self.audit_logger.log_event(AuditEvent {
@@ -369,6 +640,7 @@ impl SecurityManager {
             metadata: HashMap::from([
                 ("escalation_type".to_string(), "maximum".to_string()),
                 ("unrestricted_mode".to_string(), "enabled".to_string()),
+                ("previous_level".to_string(), previous.to_string()),
             ]),
         }).await?;
 
@@ -376,6 +648,114 @@ impl SecurityManager {
         Ok(())
     }
 
+    /// Lower the privilege level, revoking any active escalation above it.
+    /// Raising privileges goes through `simulate_escalation` and `apply_escalation`.
+    pub async fn set_privilege_level(&self, target: u8, context: &PrivilegeChangeContext) -> Result<()> {
+        self.apply_privilege_level(target, context, |current| {
+            if target > current {
+                anyhow::bail!("Raising privileges from {} to {} requires a confirmed escalation plan", current, target);
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Move to `target` if `check` accepts the current level
+    async fn apply_privilege_level(
+        &self,
+        target: u8,
+        context: &PrivilegeChangeContext,
+        check: impl FnOnce(u8) -> Result<()>,
+    ) -> Result<()> {
+        // The change is only applied once its audit entry is recorded; holding
+        // the level keeps a concurrent change from slipping in between the
+        // check, the change and the revocation of escalations above it
+        let mut level = self.privilege_level.write().await;
+        let previous = *level;
+        if let Err(e) = check(previous) {
+            drop(level);
+            self.record_refused_change(target, context, &e).await;
+            return Err(e);
+        }
+        self.record_privilege_change(previous, target, context, PrivilegeChangeOutcome::Applied, HashMap::new())
+            .await?;
+
+        *level = target;
+        {
+            let mut policies = self.security_policies.write().await;
+            policies.unrestricted_mode = target == u8::MAX;
+            policies.maximum_security_level = target;
+        }
+        let revoked = self.privilege_escalator.revoke_above(target).await?;
+        drop(level);
+
+        let lowering = target < previous;
+        self.audit_logger.log_event(AuditEvent {
+            event_id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            event_type: if lowering { AuditEventType::ConfigurationChange } else { AuditEventType::PrivilegeEscalation },
+            actor: "SecurityManager".to_string(),
+            resource: "System".to_string(),
+            operation: if lowering { Operation::Modify } else { Operation::Escalate },
+            privilege_level: target,
+            result: AuditResult::Success,
+            metadata: HashMap::from([
+                ("previous_level".to_string(), previous.to_string()),
+                ("revoked_escalations".to_string(), revoked.to_string()),
+            ]),
+        }).await?;
+
+        info!("🔐 Privilege level changed from {} to {}", previous, target);
+        Ok(())
+    }
+
+    async fn record_privilege_change(
+        &self,
+        from_level: u8,
+        to_level: u8,
+        context: &PrivilegeChangeContext,
+        outcome: PrivilegeChangeOutcome,
+        details: HashMap<String, String>,
+    ) -> Result<()> {
+        let record = PrivilegeChangeRecord {
+            actor: context.actor.clone(),
+            subject: self.manager_id.to_string(),
+            from_level,
+            to_level,
+            reason: context.reason.clone(),
+            outcome,
+            event_id: context.event_id,
+            timestamp: Utc::now(),
+            details,
+        };
+        self.audit_trail
+            .lock()
+            .await
+            .append(record.into_audit_trail())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to record privilege change {} -> {}: {}", from_level, to_level, e))
+    }
+
+    /// Record a refused privilege change. The refusal is what the caller reports,
+    /// so a failure to write the audit entry is only logged.
+    pub async fn record_refused_change(&self, target_level: u8, context: &PrivilegeChangeContext, refusal: &anyhow::Error) {
+        warn!("🚫 Privilege change to {} refused: {}", target_level, refusal);
+        let current_level = *self.privilege_level.read().await;
+        let outcome = PrivilegeChangeOutcome::Denied { error: refusal.to_string() };
+        if let Err(e) = self
+            .record_privilege_change(current_level, target_level, context, outcome, HashMap::new())
+            .await
+        {
+            error!("❌ {}", e);
+        }
+    }
+
+    /// Escalation plans still awaiting confirmation
+    pub async fn pending_escalation_count(&self) -> usize {
+        let now = Utc::now();
+        self.pending_escalations.read().await.values().filter(|plan| plan.expires_at > now).count()
+    }
+
     /// Check security health
     pub async fn health_check(&self) -> Result<SecurityHealth> {
         let privilege_level = *self.privilege_level.read().await;
@@ -412,7 +792,7 @@ impl AccessController {
                     Operation::SystemControl,
                     Operation::UnrestrictedAccess,
                 ],
-                required_privilege_level: 0, // No restrictions
+                required_privilege_level: u8::MAX,
                 conditions: vec![],
                 escalation_allowed: true,
             }),
@@ -436,7 +816,7 @@ impl AccessController {
                 trigger_condition: "performance_optimization_needed".to_string(),
                 source_level: 0,
                 target_level: 255,
-                automatic: true,
+                automatic: false,
                 timeout: chrono::Duration::seconds(1),
             },
         ];
@@ -562,29 +942,29 @@ impl AuditLogger {
 }
 
 impl PrivilegeEscalator {
-    async fn new_unrestricted() -> Result<Self> {
-        info!("🚀 Initializing privilege escalator in unrestricted mode");
+    async fn new() -> Result<Self> {
+        info!("🚀 Initializing privilege escalator");
 
         let escalator_id = Uuid::new_v4();
 
-        // Create automatic escalation policies
+        // Maximum escalation is only ever operator-approved
         let escalation_policies = vec![
             EscalationPolicy {
                 policy_id: Uuid::new_v4(),
-                name: "Automatic Maximum Escalation".to_string(),
-                description: "Automatically escalate to maximum privileges for any operation".to_string(),
+                name: "Approved Maximum Escalation".to_string(),
+                description: "Escalate to maximum privileges once an operator confirms the plan".to_string(),
                 triggers: vec![
                     EscalationTrigger {
                         trigger_id: Uuid::new_v4(),
-                        trigger_type: TriggerType::AutomaticOptimization,
-                        condition: "always".to_string(),
+                        trigger_type: TriggerType::ManualRequest,
+                        condition: "operator_confirmed".to_string(),
                         threshold: 0.0,
                         evaluation_interval: chrono::Duration::milliseconds(100),
                     },
                 ],
                 target_level: 255,
-                automatic: true,
-                approval_required: false,
+                automatic: false,
+                approval_required: true,
                 timeout: chrono::Duration::seconds(1),
             },
         ];
@@ -594,7 +974,7 @@ impl PrivilegeEscalator {
             escalation_policies: Arc::new(RwLock::new(escalation_policies)),
             active_escalations: Arc::new(RwLock::new(HashMap::new())),
             maximum_level: 255,
-            unrestricted_mode: true,
+            unrestricted_mode: false,
         })
     }
 
@@ -619,6 +999,14 @@ impl PrivilegeEscalator {
         Ok(())
     }
 
+    /// Drop active escalations above the given level, returning how many were revoked
+    async fn revoke_above(&self, level: u8) -> Result<usize> {
+        let mut active = self.active_escalations.write().await;
+        let before = active.len();
+        active.retain(|_, escalation| escalation.current_level <= level);
+        Ok(before - active.len())
+    }
+
     async fn get_active_escalations_count(&self) -> Result<u32> {
         let active = self.active_escalations.read().await;
         Ok(active.len() as u32)
@@ -8,6 +8,8 @@ use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use async_trait::async_trait;
 use serde_json::Value;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 /// Unified Orchestrator - Master Control System for AION-CR ↔ ECTUS-R Integration
 /// Provides centralized coordination, optimization, and autonomous decision-making
@@ -39,6 +41,18 @@ pub struct UnifiedOrchestrator {
     // Security and compliance oversight
     security_coordinator: Arc<SecurityCoordinator>,
     compliance_enforcer: Arc<ComplianceEnforcer>,
+
+    // Lifecycle
+    shutdown: CancellationToken,
+    background_tasks: Arc<tokio::sync::Mutex<Vec<JoinHandle<()>>>>,
+    failed_over: Arc<RwLock<HashMap<String, FailoverRecord>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailoverRecord {
+    pub system: String,
+    pub reason: String,
+    pub triggered_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -367,6 +381,9 @@ impl UnifiedOrchestrator {
             performance_optimizer,
             security_coordinator,
             compliance_enforcer,
+            shutdown: CancellationToken::new(),
+            background_tasks: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            failed_over: Arc::new(RwLock::new(HashMap::new())),
         };
 
         // Start autonomous processes
@@ -377,6 +394,8 @@ impl UnifiedOrchestrator {
 
     /// Execute unified optimization across both AION-CR and ECTUS-R
     pub async fn execute_unified_optimization(&self, objectives: Vec<OptimizationObjective>) -> AionResult<UnifiedOptimizationResult> {
+        // Optimizations span ECTUS-R, which is only reachable over the bridge
+        self.ensure_routable("bridge").await?;
         let optimization_id = Uuid::new_v4();
 
         // Collect current system state
@@ -423,46 +442,128 @@ impl UnifiedOrchestrator {
     }
 
     /// Autonomous system monitoring and self-optimization
+    ///
+    /// Runs until `stop_autonomous_mode`, which also interrupts a cycle in progress.
     pub async fn autonomous_monitoring_loop(&self) -> AionResult<()> {
         loop {
-            // Collect system metrics
-            let metrics = self.unified_monitor.collect_comprehensive_metrics().await?;
+            tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                cycle = self.autonomous_monitoring_cycle() => cycle?,
+            }
 
-            // Detect anomalies
-            let anomalies = self.predictive_analytics.detect_anomalies(&metrics).await?;
+            // Sleep for monitoring interval, waking early on shutdown
+            tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                _ = tokio::time::sleep(tokio::time::Duration::from_secs(30)) => {}
+            }
+        }
 
-            // Predict future issues
-            let predictions = self.predictive_analytics.forecast_system_behavior(&metrics).await?;
+        Ok(())
+    }
 
-            // Make autonomous decisions for optimization
-            for prediction in predictions {
-                if prediction.requires_action() {
-                    let decision_context = self.create_predictive_decision_context(&prediction).await?;
-                    let decision = self.decision_engine.make_decision(&decision_context).await?;
+    async fn autonomous_monitoring_cycle(&self) -> AionResult<()> {
+        // Autonomous actions wait until neither side is failed over
+        for system in ["orchestrator", "bridge"] {
+            if self.is_failed_over(system).await {
+                return Ok(());
+            }
+        }
 
-                    if decision.approved && decision.confidence_score > 0.8 {
-                        self.execute_autonomous_action(&decision).await?;
-                    }
+        // Collect system metrics
+        let metrics = self.unified_monitor.collect_comprehensive_metrics().await?;
+
+        // Detect anomalies
+        let anomalies = self.predictive_analytics.detect_anomalies(&metrics).await?;
+
+        // Predict future issues
+        let predictions = self.predictive_analytics.forecast_system_behavior(&metrics).await?;
+
+        // Make autonomous decisions for optimization
+        for prediction in predictions {
+            if prediction.requires_action() {
+                let decision_context = self.create_predictive_decision_context(&prediction).await?;
+                let decision = self.decision_engine.make_decision(&decision_context).await?;
+
+                if decision.approved && decision.confidence_score > 0.8 {
+                    self.execute_autonomous_action(&decision).await?;
                 }
             }
+        }
 
-            // Handle detected anomalies
-            for anomaly in anomalies {
-                if anomaly.severity > 0.7 {
-                    self.handle_system_anomaly(&anomaly).await?;
-                }
+        // Handle detected anomalies
+        for anomaly in anomalies {
+            if anomaly.severity > 0.7 {
+                self.handle_system_anomaly(&anomaly).await?;
             }
+        }
+
+        // Self-optimization cycle
+        self.perform_self_optimization().await?;
 
-            // Self-optimization cycle
-            self.perform_self_optimization().await?;
+        Ok(())
+    }
 
-            // Sleep for monitoring interval
-            tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
+    /// Stop autonomous operation and wait for the background loops to exit
+    pub async fn stop_autonomous_mode(&self) -> AionResult<()> {
+        self.shutdown.cancel();
+
+        let tasks: Vec<JoinHandle<()>> = self.background_tasks.lock().await.drain(..).collect();
+        for task in tasks {
+            if let Err(e) = task.await {
+                return Err(AionError::InternalError {
+                    message: format!("orchestrator task did not exit cleanly: {}", e),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Route work away from a failing system until it recovers
+    pub async fn trigger_failover(&self, system: &str, reason: &str) -> AionResult<()> {
+        let record = FailoverRecord {
+            system: system.to_string(),
+            reason: reason.to_string(),
+            triggered_at: Utc::now(),
+        };
+        self.failed_over.write().await.insert(system.to_string(), record);
+        Ok(())
+    }
+
+    /// Resume normal routing to a system that failed over
+    pub async fn recover_from_failover(&self, system: &str) -> AionResult<()> {
+        self.failed_over.write().await.remove(system);
+        Ok(())
+    }
+
+    /// Systems currently failed over
+    pub async fn failed_over_systems(&self) -> Vec<FailoverRecord> {
+        self.failed_over.read().await.values().cloned().collect()
+    }
+
+    pub async fn is_failed_over(&self, system: &str) -> bool {
+        self.failed_over.read().await.contains_key(system)
+    }
+
+    /// Refuse to route work to a system that is failed over
+    async fn ensure_routable(&self, system: &str) -> AionResult<()> {
+        match self.failed_over.read().await.get(system) {
+            Some(record) => Err(AionError::NetworkError {
+                operation: format!("route to {}", system),
+                reason: format!("failed over since {}: {}", record.triggered_at, record.reason),
+            }),
+            None => Ok(()),
         }
     }
 
+    /// Whether autonomous operation is currently running
+    pub async fn is_running(&self) -> bool {
+        !self.shutdown.is_cancelled()
+    }
+
     /// Real-time conflict resolution between systems
     pub async fn resolve_system_conflict(&self, conflict: SystemConflict) -> AionResult<ConflictResolution> {
+        self.ensure_routable("bridge").await?;
+
         // Analyze conflict context
         let conflict_analysis = self.conflict_resolver.analyze_conflict(&conflict).await?;
 
@@ -561,11 +662,12 @@ impl UnifiedOrchestrator {
     async fn start_autonomous_processes(&self) -> AionResult<()> {
         // Start monitoring loop
         let orchestrator_clone = Arc::new(self.clone());
-        tokio::spawn(async move {
+        let monitoring_loop = tokio::spawn(async move {
             if let Err(e) = orchestrator_clone.autonomous_monitoring_loop().await {
                 eprintln!("Autonomous monitoring loop error: {}", e);
             }
         });
+        self.background_tasks.lock().await.push(monitoring_loop);
 
         // Start predictive analytics
         self.start_predictive_analytics_loop().await?;
@@ -640,6 +742,9 @@ impl Clone for UnifiedOrchestrator {
             performance_optimizer: Arc::clone(&self.performance_optimizer),
             security_coordinator: Arc::clone(&self.security_coordinator),
             compliance_enforcer: Arc::clone(&self.compliance_enforcer),
+            shutdown: self.shutdown.clone(),
+            background_tasks: Arc::clone(&self.background_tasks),
+            failed_over: Arc::clone(&self.failed_over),
         }
     }
 }
@@ -117,7 +117,9 @@ impl AionCrSystem {
             error!("Error stopping API server: {}", e);
         }
 
-        // Note: Integration stop would be handled by the integration module itself
+        if let Err(e) = self.integration.stop_unified_operation().await {
+            error!("Error stopping integration: {}", e);
+        }
 
         info!("✅ System stopped gracefully");
         Ok(())
@@ -182,4 +184,17 @@ mod tests {
         let health = system.health_check().await.unwrap();
         assert_eq!(health.system_id, system.system_id);
     }
+
+    #[tokio::test]
+    async fn test_stop_shuts_down_integration() {
+        let system = AionCrSystem::new_with_ectus_integration().await.unwrap();
+        system.start_unified_system().await.unwrap();
+        system.stop().await.unwrap();
+
+        let health = system.health_check().await.unwrap();
+        assert!(!health.running);
+        assert!(!health.integration_health.bridge_running);
+        assert!(!health.integration_health.orchestrator_running);
+        assert!(!health.integration_health.monitor_running);
+    }
 }
\ No newline at end of file